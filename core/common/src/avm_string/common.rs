@@ -70,6 +70,9 @@ ruffle_macros::define_common_strings! {
     "advanced",
     "album",
     "all",
+    "allButLast",
+    "allButMandatoryBreak",
+    "allIncludingLast",
     "alphaMultiplier",
     "alphaOffset",
     "always",
@@ -197,7 +200,9 @@ ruffle_macros::define_common_strings! {
     "keyUp",
     "left",
     "length",
+    "letterSpacing",
     "level",
+    "lineJustification",
     "lining",
     "littleEndian",
     "ll",
@@ -328,6 +333,7 @@ ruffle_macros::define_common_strings! {
     "rotate90",
     "rr",
     "save",
+    "securityError",
     "Selection",
     "separatorBefore",
     "smallCaps",
@@ -367,6 +373,7 @@ ruffle_macros::define_common_strings! {
     "type",
     "uncommon",
     "undefined",
+    "unjustified",
     "uppercase",
     "uri",
     "useDominantBaseline",
@@ -37,6 +37,7 @@ pub use globals::array::ArrayBuilder;
 pub use globals::context_menu::make_context_menu_state;
 pub use globals::sound::start as start_sound;
 pub use object::{NativeObject, Object, ObjectHandle, ObjectPtr};
+pub use object_reference::MovieClipReference;
 pub use property::Attribute;
 pub use property_map::PropertyMap;
 use ruffle_common::avm_string::AvmString;
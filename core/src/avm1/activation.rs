@@ -25,7 +25,6 @@ use std::fmt;
 use swf::avm1::read::Reader;
 use swf::avm1::types::*;
 use url::form_urlencoded;
-use web_time::Instant;
 
 use super::object_reference::MovieClipReference;
 
@@ -1197,7 +1196,10 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             *self.context.time_offset += 1;
         }
 
-        let time = Instant::now()
+        let time = self
+            .context
+            .time
+            .instant()
             .duration_since(self.context.start_time)
             .as_millis() as u32;
         let result = time.wrapping_add(*self.context.time_offset);
@@ -1867,7 +1869,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         // The max value is clamped to the range [0, 2^31 - 1).
         let max = self.context.avm1.pop().coerce_to_f64(self)? as i32;
         let result = if max > 0 {
-            self.context.rng.generate_random_number() % max
+            self.context.rng.generate_random_number(self.context.time) % max
         } else {
             0
         };
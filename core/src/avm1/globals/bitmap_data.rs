@@ -568,6 +568,7 @@ fn draw<'gc>(
         source,
         Transform {
             matrix,
+            matrix3d: None,
             color_transform,
             perspective_projection: None,
         },
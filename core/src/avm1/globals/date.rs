@@ -1,8 +1,9 @@
 use crate::avm1::clamp::Clamp;
 use crate::avm1::property_decl::{DeclContext, PropertyOrder, StaticDeclarations, SystemClass};
 use crate::avm1::{Activation, Error, NativeObject, Object, Value};
-use crate::locale::{get_current_date_time, get_timezone};
+use crate::backend::time::TimeBackend;
 use crate::string::AvmString;
+use chrono::FixedOffset;
 use gc_arena::Gc;
 use std::cell::Cell;
 use std::fmt;
@@ -55,8 +56,8 @@ impl Date {
     }
 
     /// Create from current date and time.
-    fn now() -> Self {
-        Self(get_current_date_time().timestamp_millis() as f64)
+    fn now(time: &dyn TimeBackend) -> Self {
+        Self(time.now().timestamp_millis() as f64)
     }
 
     /// Get milliseconds since epoch.
@@ -157,24 +158,24 @@ impl Date {
     }
 
     /// ECMA-262 LocalTZA - Get local timezone adjustment in milliseconds.
-    fn local_tza(self, _is_utc: bool) -> i32 {
+    fn local_tza(self, timezone: FixedOffset, _is_utc: bool) -> i32 {
         // TODO: Honor `is_utc` flag.
-        get_timezone().local_minus_utc() * Self::MS_PER_SECOND
+        timezone.local_minus_utc() * Self::MS_PER_SECOND
     }
 
     /// ECMA-262 LocalTime - Convert from UTC to local timezone.
-    fn local(self) -> Self {
-        Self(self.0 + f64::from(self.local_tza(true)))
+    fn local(self, timezone: FixedOffset) -> Self {
+        Self(self.0 + f64::from(self.local_tza(timezone, true)))
     }
 
     /// ECMA-262 UTC - Convert from local timezone to UTC.
-    fn utc(self) -> Self {
-        Self(self.0 - f64::from(self.local_tza(false)))
+    fn utc(self, timezone: FixedOffset) -> Self {
+        Self(self.0 - f64::from(self.local_tza(timezone, false)))
     }
 
     /// Get timezone offset in minutes.
-    fn timezone_offset(self) -> f64 {
-        (self.0 - self.local().0) / f64::from(Self::MS_PER_MINUTE)
+    fn timezone_offset(self, timezone: FixedOffset) -> f64 {
+        (self.0 - self.local(timezone).0) / f64::from(Self::MS_PER_MINUTE)
     }
 
     /// ECMA-262 HourFromTime - Get hours (0-23).
@@ -258,9 +259,13 @@ impl Date {
     }
 }
 
-impl fmt::Display for Date {
+/// A date in local time, along with its timezone, displayed as by `Date.toString()`.
+struct LocalDate(Date, FixedOffset);
+
+impl fmt::Display for LocalDate {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if !self.is_valid() {
+        let LocalDate(date, timezone) = *self;
+        if !date.is_valid() {
             return write!(f, "Invalid Date");
         }
 
@@ -269,20 +274,20 @@ impl fmt::Display for Date {
             "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
         ];
 
-        let timezone_offset = (-self.timezone_offset()).clamp_to_i32();
+        let timezone_offset = (-date.timezone_offset(timezone)).clamp_to_i32();
         write!(
             f,
             "{} {} {} {:02}:{:02}:{:02} GMT{}{:02}{:02} {}",
-            DAYS_OF_WEEK[self.week_day() as usize],
-            MONTHS[self.month() as usize],
-            self.date(),
-            self.hours(),
-            self.minutes(),
-            self.seconds(),
+            DAYS_OF_WEEK[date.week_day() as usize],
+            MONTHS[date.month() as usize],
+            date.date(),
+            date.hours(),
+            date.minutes(),
+            date.seconds(),
             if timezone_offset < 0 { '-' } else { '+' },
-            timezone_offset.abs() / Self::MINUTES_PER_HOUR,
-            timezone_offset.abs() % Self::MINUTES_PER_HOUR,
-            self.year(),
+            timezone_offset.abs() / Date::MINUTES_PER_HOUR,
+            timezone_offset.abs() % Date::MINUTES_PER_HOUR,
+            date.year(),
         )
     }
 }
@@ -358,7 +363,7 @@ fn constructor<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let date = match args[..] {
         [] => {
-            let date = Date::now();
+            let date = Date::now(activation.context.time);
             if activation.swf_version() > 7 {
                 Date(date.time().round())
             } else {
@@ -372,7 +377,8 @@ fn constructor<'gc>(
             let minute = args.get(4).copied().unwrap_or(0.0);
             let second = args.get(5).copied().unwrap_or(0.0);
             let millisecond = args.get(6).copied().unwrap_or(0.0);
-            Date::new(year, month, date, hour, minute, second, millisecond).utc()
+            let timezone = activation.context.time.timezone();
+            Date::new(year, month, date, hour, minute, second, millisecond).utc(timezone)
         }
     };
     this.set_native(
@@ -388,7 +394,9 @@ fn function<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok(AvmString::new_utf8(activation.gc(), Date::now().local().to_string()).into())
+    let timezone = activation.context.time.timezone();
+    let date = Date::now(activation.context.time).local(timezone);
+    Ok(AvmString::new_utf8(activation.gc(), LocalDate(date, timezone).to_string()).into())
 }
 
 /// ECMA-262 Date.UTC
@@ -477,6 +485,7 @@ pub fn method<'gc>(
         return Ok(Value::Undefined);
     };
     let date = date_ref.get();
+    let timezone = activation.context.time.timezone();
 
     match index {
         GET_TIME => return Ok(date.time().into()),
@@ -486,7 +495,7 @@ pub fn method<'gc>(
             date_ref.set(new_date);
             return Ok(new_date.time().into());
         }
-        GET_TIMEZONE_OFFSET => return Ok(date.timezone_offset().into()),
+        GET_TIMEZONE_OFFSET => return Ok(date.timezone_offset(timezone).into()),
         _ => {}
     }
 
@@ -518,12 +527,12 @@ pub fn method<'gc>(
             .or_else(|| (i == index).then_some(f64::NAN))
     };
 
-    let date = if is_utc { date } else { date.local() };
+    let date = if is_utc { date } else { date.local(timezone) };
 
     let set_date = |day: f64, time: f64| {
         let mut date = Date::make_date(day, time);
         if !is_utc {
-            date = date.utc();
+            date = date.utc(timezone);
         }
         date = date.clip();
         date_ref.set(date);
@@ -586,7 +595,9 @@ pub fn method<'gc>(
             )
             .into()
         }
-        TO_STRING => AvmString::new_utf8(activation.gc(), date.to_string()).into(),
+        TO_STRING => {
+            AvmString::new_utf8(activation.gc(), LocalDate(date, timezone).to_string()).into()
+        }
         GET_TIME..=GET_TIMEZONE_OFFSET | SET_YEAR.. => unreachable!(), // Handled above.
     })
 }
@@ -80,7 +80,7 @@ fn get_disk_usage<'gc>(
 }
 
 /// Deserializes a Lso into an object containing the properties stored
-fn deserialize_lso<'gc>(
+pub(crate) fn deserialize_lso<'gc>(
     activation: &mut Activation<'_, 'gc>,
     lso: &Lso,
     decoder: &AMF0Decoder,
@@ -104,7 +104,11 @@ fn deserialize_lso<'gc>(
     Ok(obj)
 }
 
-fn new_lso<'gc>(activation: &mut Activation<'_, 'gc>, name: &str, data: Object<'gc>) -> Lso {
+pub(crate) fn new_lso<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    name: &str,
+    data: Object<'gc>,
+) -> Lso {
     let mut w = Amf0Writer::default();
     recursive_serialize(activation, data, &mut w);
     w.commit_lso(
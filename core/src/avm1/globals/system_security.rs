@@ -68,7 +68,7 @@ fn get_sandbox_type<'gc>(
     let movie = activation.base_clip().movie();
     Ok(AvmString::new_utf8(
         activation.gc(),
-        match movie.sandbox_type() {
+        match activation.context.security.sandbox_type(&movie) {
             SandboxType::Remote => "remote",
             SandboxType::LocalWithFile => "localWithFile",
             SandboxType::LocalWithNetwork => "localWithNetwork",
@@ -49,6 +49,7 @@ mod array;
 pub mod bytearray;
 mod call_stack;
 mod class;
+pub mod debugger;
 mod domain;
 mod dynamic_map;
 mod e4x;
@@ -71,6 +72,7 @@ pub mod property;
 mod property_map;
 mod qname;
 mod regexp;
+pub mod save_state;
 mod scope;
 pub mod script;
 #[cfg(feature = "known_stubs")]
@@ -87,6 +89,7 @@ pub use crate::avm2::activation::Activation;
 pub use crate::avm2::array::ArrayStorage;
 pub use crate::avm2::call_stack::CallStack;
 pub use crate::avm2::class::Class;
+pub use crate::avm2::debugger::{Avm2Debugger, PauseReason, StepKind};
 #[allow(unused)] // For debug_ui
 pub use crate::avm2::domain::{Domain, DomainPtr};
 pub use crate::avm2::error::Error;
@@ -4,6 +4,7 @@ use crate::avm2::Multiname;
 use crate::avm2::Namespace;
 use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
+use crate::avm2::debugger;
 use crate::avm2::domain::Domain;
 use crate::avm2::e4x::{escape_attribute_value, escape_element_value};
 use crate::avm2::error::{
@@ -145,6 +146,14 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         }
     }
 
+    /// Like `from_nothing`, but with `movie` as the calling movie, so that
+    /// display objects constructed with it belong to `movie`.
+    pub fn from_movie(context: &'a mut UpdateContext<'gc>, movie: Arc<SwfMovie>) -> Self {
+        let mut activation = Self::from_nothing(context);
+        activation.caller_movie = Some(movie);
+        activation
+    }
+
     /// Like `from_nothing`, but with a specified domain.
     ///
     /// This should be used when you actually need to run AVM2 code, but
@@ -482,6 +491,11 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         self.stack.set_value_at(id as usize, value.into());
     }
 
+    /// The number of local registers of this activation.
+    pub fn num_locals(&self) -> usize {
+        self.num_locals
+    }
+
     /// Returns whether this Activation is running in "interpreter mode" as
     /// opposed to "JIT mode". Note that these modes do not actually correspond
     /// to whether the method is being interpreted or JITted.
@@ -2973,7 +2987,6 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(())
     }
 
-    #[cfg(feature = "avm_debug")]
     fn op_debug(
         &mut self,
         is_local_register: bool,
@@ -2991,6 +3004,12 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                     "Debug: {register_name} = <out-of-bounds register #{register}>",
                 );
             }
+
+            if self.context.avm2_debugger.is_attached() {
+                self.context
+                    .avm2_debugger
+                    .set_register_name(register as u32, register_name.to_string());
+            }
         } else {
             avm_debug!(self.avm2(), "Unknown debugging mode!");
         }
@@ -2998,41 +3017,37 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(())
     }
 
-    #[cfg(not(feature = "avm_debug"))]
-    fn op_debug(
-        &mut self,
-        _is_local_register: bool,
-        _register_name: AvmAtom<'gc>,
-        _register: u8,
-    ) -> Result<(), Error<'gc>> {
-        Ok(())
-    }
-
-    #[cfg(feature = "avm_debug")]
     fn op_debug_file(&mut self, file_name: AvmAtom<'gc>) -> Result<(), Error<'gc>> {
         avm_debug!(self.avm2(), "File: {file_name}");
 
-        Ok(())
-    }
+        if self.context.avm2_debugger.is_attached() {
+            self.context.avm2_debugger.set_file(file_name.to_string());
+        }
 
-    #[cfg(not(feature = "avm_debug"))]
-    fn op_debug_file(&mut self, _file_name: AvmAtom<'gc>) -> Result<(), Error<'gc>> {
         Ok(())
     }
 
     fn op_debug_line(&mut self, line_num: u32) -> Result<(), Error<'gc>> {
         avm_debug!(self.avm2(), "Line: {line_num}");
 
+        if self.context.avm2_debugger.is_attached() {
+            debugger::debug_line(self, line_num);
+        }
+
         Ok(())
     }
 
     fn op_bkpt(&mut self) -> Result<(), Error<'gc>> {
         // while a debugger is not attached, this is a no-op
+        self.context.avm2_debugger.pause();
+
         Ok(())
     }
 
     fn op_bkpt_line(&mut self, _line_num: u32) -> Result<(), Error<'gc>> {
         // while a debugger is not attached, this is a no-op
+        self.context.avm2_debugger.pause();
+
         Ok(())
     }
 
@@ -25,27 +25,40 @@ impl<'gc> CallStack<'gc> {
     pub fn display(&self, output: &mut WString) {
         for method in self.stack.iter().rev() {
             output.push_utf8("\n\tat ");
-
-            let bound_class = method.bound_class();
-
-            let is_global_init = bound_class.is_some_and(|c| {
-                // If the class is a script `global` class and its instance
-                // initializer is this method, then this is a script initializer
-                c.is_script_traits() && c.instance_init() == Some(*method)
-            });
-
-            // Special-case the printed message for script initializers
-            if is_global_init {
-                output.push_utf8("global$init()");
-            } else {
-                display_function(output, *method);
-            }
+            display_call(output, *method);
         }
     }
 
     pub fn is_empty(&self) -> bool {
         self.stack.is_empty()
     }
+
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Iterates over the methods on the stack, outermost call first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = Method<'gc>> + '_ {
+        self.stack.iter().copied()
+    }
+}
+
+/// Displays a call to a method as it appears in stack traces.
+pub fn display_call(output: &mut WString, method: Method<'_>) {
+    let bound_class = method.bound_class();
+
+    let is_global_init = bound_class.is_some_and(|c| {
+        // If the class is a script `global` class and its instance
+        // initializer is this method, then this is a script initializer
+        c.is_script_traits() && c.instance_init() == Some(method)
+    });
+
+    // Special-case the printed message for script initializers
+    if is_global_init {
+        output.push_utf8("global$init()");
+    } else {
+        display_function(output, method);
+    }
 }
 
 impl Default for CallStack<'_> {
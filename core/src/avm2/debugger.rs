@@ -0,0 +1,880 @@
+//! Source-level debugger for AVM2 code.
+//!
+//! Code compiled with debugging information contains `debugfile` and
+//! `debugline` ops recording the source position of the code that follows,
+//! as well as `debug` ops naming local registers. The verifier keeps these
+//! ops while the debugger is attached, and for movies with an
+//! `EnableDebugger` tag. The interpreter reports them here while the
+//! debugger is attached.
+//!
+//! Note that this debugger does *not* suspend execution. The interpreter
+//! can't be suspended in the middle of a script, so breaking only starts a
+//! trace: the state of the stack is recorded at every line that runs until
+//! control returns to the player, which then stays paused. Stepping and
+//! continuing replay that trace after the fact, so the code being inspected
+//! has already run to completion, along with all of its side effects. Once
+//! a step or continue runs past the end of the trace, the player resumes.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::call_stack::display_call;
+use crate::avm2::multiname::Multiname;
+use crate::avm2::object::TObject as _;
+use crate::avm2::property::Property;
+use crate::avm2::scope::Scope;
+use crate::avm2::value::Value;
+use crate::string::{AvmString, WString};
+
+/// The maximum number of lines recorded after breaking, so that code running
+/// in a loop can't exhaust memory.
+const MAX_RECORDED_LINES: usize = 10000;
+
+/// A location in the source code at which execution should break.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Breakpoint {
+    /// The source file, matched against the end of the file names recorded
+    /// in the debugging information (e.g. `Main.as` or `com/example/Main.as`).
+    pub file: String,
+
+    /// The line number, starting at 1.
+    pub line: u32,
+}
+
+impl Breakpoint {
+    /// Whether this breakpoint applies to the given source location.
+    pub fn matches(&self, file: &str, line: u32) -> bool {
+        if self.line != line {
+            return false;
+        }
+
+        // Flex records file names as `<source root>;<package path>;<file>`.
+        let file = normalize_path(file);
+        let breakpoint_file = normalize_path(&self.file);
+        let breakpoint_file = breakpoint_file.trim_start_matches('/');
+
+        file == breakpoint_file
+            || file
+                .strip_suffix(breakpoint_file)
+                .is_some_and(|prefix| prefix.ends_with('/'))
+    }
+}
+
+fn normalize_path(path: &str) -> String {
+    path.replace([';', '\\'], "/")
+}
+
+/// How far execution should run before breaking again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepKind {
+    /// Break at the next line, entering function calls.
+    Into,
+
+    /// Break at the next line of the current function, or of its caller if it
+    /// returns.
+    Over,
+
+    /// Break at the next line of the calling function.
+    Out,
+}
+
+/// Why execution broke.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PauseReason {
+    /// A breakpoint was hit.
+    Breakpoint,
+
+    /// A pause was requested, either by the user or by a `bkpt` op.
+    Pause,
+
+    /// A step was completed.
+    Step,
+}
+
+/// A named value, stringified at the time it was recorded.
+#[derive(Clone, Debug)]
+pub struct DebugVariable {
+    pub name: String,
+    pub value: String,
+}
+
+/// An entry of the scope chain of a stack frame.
+#[derive(Clone, Debug)]
+pub struct DebugScope {
+    /// A description of the object on the scope chain.
+    pub object: String,
+
+    /// Whether this is a `with` scope.
+    pub with: bool,
+
+    /// The slots of the object. These are only recorded for the innermost
+    /// stack frame.
+    pub properties: Vec<DebugVariable>,
+}
+
+/// A frame of the call stack.
+#[derive(Clone, Debug, Default)]
+pub struct DebugFrame {
+    /// The name of the function running in this frame.
+    pub function: String,
+
+    /// The source file being run, if the function has debugging information.
+    pub file: Option<String>,
+
+    /// The source line being run, if the function has debugging information.
+    pub line: Option<u32>,
+
+    /// The local registers of this frame.
+    pub locals: Vec<DebugVariable>,
+
+    /// The scope chain of this frame, innermost scope first.
+    pub scopes: Vec<DebugScope>,
+}
+
+/// A watch expression, evaluated in the innermost stack frame.
+#[derive(Clone, Debug)]
+pub struct DebugWatch {
+    pub expression: String,
+
+    /// The value of the expression, or the error evaluating it raised.
+    pub value: Result<String, String>,
+}
+
+/// The state of execution at a recorded line.
+#[derive(Clone, Debug)]
+pub struct PausedState {
+    pub reason: PauseReason,
+
+    /// The call stack, innermost frame first.
+    pub frames: Vec<DebugFrame>,
+
+    pub watches: Vec<DebugWatch>,
+}
+
+impl PausedState {
+    /// The frame execution is paused in.
+    pub fn current_frame(&self) -> Option<&DebugFrame> {
+        self.frames.first()
+    }
+}
+
+/// Debugging information about a frame of the AVM2 call stack.
+#[derive(Default)]
+struct FrameInfo {
+    file: Option<String>,
+    line: Option<u32>,
+    register_names: Vec<(u32, String)>,
+
+    /// The locals and scopes of this frame, recorded when it last called
+    /// into another function. These can't change until the call returns.
+    locals: Vec<DebugVariable>,
+    scopes: Vec<DebugScope>,
+}
+
+/// What should make execution break at the next line it runs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum BreakCondition {
+    /// Only breakpoints.
+    #[default]
+    Breakpoints,
+
+    /// Any line.
+    NextLine(PauseReason),
+}
+
+struct RecordedLine {
+    depth: usize,
+    state: PausedState,
+}
+
+/// The AVM2 debugger.
+///
+/// This is owned by the player and exposed through `Player::avm2_debugger`.
+#[derive(Default)]
+pub struct Avm2Debugger {
+    attached: bool,
+    breakpoints: Vec<Breakpoint>,
+    watches: Vec<String>,
+
+    /// Debugging information about each frame of the call stack.
+    frames: Vec<FrameInfo>,
+
+    break_condition: BreakCondition,
+
+    /// The lines recorded since execution broke.
+    recorded: Vec<RecordedLine>,
+
+    /// Whether lines are still being recorded.
+    recording: bool,
+
+    /// The index of the recorded line execution is paused at.
+    position: Option<usize>,
+}
+
+impl Avm2Debugger {
+    /// Attach the debugger.
+    ///
+    /// Unless the movie has an `EnableDebugger` tag, debugging information
+    /// is only kept for functions that first run after the debugger was
+    /// attached, so this should be done before loading the movie to debug.
+    pub fn attach(&mut self) {
+        self.attached = true;
+    }
+
+    /// Detach the debugger, resuming execution if it was paused.
+    pub fn detach(&mut self) {
+        self.attached = false;
+        self.frames.clear();
+        self.break_condition = BreakCondition::Breakpoints;
+        self.clear_recording();
+    }
+
+    pub fn is_attached(&self) -> bool {
+        self.attached
+    }
+
+    pub fn breakpoints(&self) -> &[Breakpoint] {
+        &self.breakpoints
+    }
+
+    pub fn add_breakpoint(&mut self, file: impl Into<String>, line: u32) {
+        let breakpoint = Breakpoint {
+            file: file.into(),
+            line,
+        };
+        if !self.breakpoints.contains(&breakpoint) {
+            self.breakpoints.push(breakpoint);
+        }
+    }
+
+    pub fn remove_breakpoint(&mut self, index: usize) {
+        if index < self.breakpoints.len() {
+            self.breakpoints.remove(index);
+        }
+    }
+
+    pub fn watches(&self) -> &[String] {
+        &self.watches
+    }
+
+    /// Add an expression to evaluate whenever execution breaks.
+    ///
+    /// Expressions are paths of properties starting at a local or a name on
+    /// the scope chain, such as `this.items[0].name` or `config["key"]`.
+    /// Only stored values are read: getters and methods are never called.
+    pub fn add_watch(&mut self, expression: impl Into<String>) {
+        self.watches.push(expression.into());
+    }
+
+    pub fn remove_watch(&mut self, index: usize) {
+        if index < self.watches.len() {
+            self.watches.remove(index);
+        }
+    }
+
+    /// Whether execution is paused. The player doesn't run while paused.
+    pub fn is_paused(&self) -> bool {
+        self.position.is_some()
+    }
+
+    /// The index of the recorded line being inspected, and the number of
+    /// recorded lines.
+    pub fn trace_position(&self) -> Option<(usize, usize)> {
+        self.position
+            .map(|position| (position, self.recorded.len()))
+    }
+
+    /// The state of execution at the line it is paused at.
+    pub fn paused_state(&self) -> Option<&PausedState> {
+        self.position.map(|position| &self.recorded[position].state)
+    }
+
+    /// Break at the next line that runs.
+    pub fn pause(&mut self) {
+        if self.attached && !self.is_paused() {
+            self.break_condition = BreakCondition::NextLine(PauseReason::Pause);
+        }
+    }
+
+    /// Move to the next recorded breakpoint, or resume the player if the
+    /// trace has none left.
+    pub fn resume(&mut self) {
+        let Some(position) = self.position else {
+            return;
+        };
+
+        let next_breakpoint = self.recorded[position + 1..]
+            .iter()
+            .position(|line| line.state.reason == PauseReason::Breakpoint);
+
+        match next_breakpoint {
+            Some(offset) => self.position = Some(position + 1 + offset),
+            None => self.clear_recording(),
+        }
+    }
+
+    /// Move to the recorded line where the given kind of step completes.
+    ///
+    /// Steps that run past the recorded lines resume the player, and break
+    /// at the next line it runs.
+    pub fn step(&mut self, kind: StepKind) {
+        let Some(position) = self.position else {
+            return;
+        };
+
+        let depth = self.recorded[position].depth;
+        let next = self.recorded[position + 1..]
+            .iter()
+            .position(|line| match kind {
+                StepKind::Into => true,
+                StepKind::Over => line.depth <= depth,
+                StepKind::Out => line.depth < depth,
+            });
+
+        match next {
+            Some(offset) => {
+                let position = position + 1 + offset;
+                let state = &mut self.recorded[position].state;
+                if state.reason != PauseReason::Breakpoint {
+                    state.reason = PauseReason::Step;
+                }
+                self.position = Some(position);
+            }
+            None => {
+                self.clear_recording();
+                self.break_condition = BreakCondition::NextLine(PauseReason::Step);
+            }
+        }
+    }
+
+    /// Stop recording lines, as control returned to the player.
+    pub(crate) fn finish_recording(&mut self) {
+        self.recording = false;
+    }
+
+    pub(crate) fn enter_frame(&mut self) {
+        self.frames.push(FrameInfo::default());
+    }
+
+    pub(crate) fn exit_frame(&mut self) {
+        self.frames.pop();
+    }
+
+    pub(crate) fn set_file(&mut self, file: String) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.file = Some(file);
+        }
+    }
+
+    pub(crate) fn set_register_name(&mut self, register: u32, name: String) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.register_names.retain(|(r, _)| *r != register);
+            frame.register_names.push((register, name));
+        }
+    }
+
+    fn clear_recording(&mut self) {
+        self.recorded.clear();
+        self.recording = false;
+        self.position = None;
+    }
+
+    /// Updates the line of the current frame, and returns why execution
+    /// should break there, if it should.
+    fn enter_line(&mut self, line: u32) -> Option<PauseReason> {
+        let frame = self.frames.last_mut()?;
+        frame.line = Some(line);
+
+        let at_breakpoint = frame.file.as_deref().is_some_and(|file| {
+            self.breakpoints
+                .iter()
+                .any(|breakpoint| breakpoint.matches(file, line))
+        });
+
+        if self.is_paused() {
+            // Only the code that runs until control returns to the player is
+            // recorded.
+            return self.recording.then_some(if at_breakpoint {
+                PauseReason::Breakpoint
+            } else {
+                PauseReason::Step
+            });
+        }
+
+        match self.break_condition {
+            _ if at_breakpoint => Some(PauseReason::Breakpoint),
+            BreakCondition::NextLine(reason) => Some(reason),
+            BreakCondition::Breakpoints => None,
+        }
+    }
+
+    fn record(&mut self, depth: usize, state: PausedState) {
+        if self.position.is_none() {
+            self.position = Some(self.recorded.len());
+            self.recording = true;
+            self.break_condition = BreakCondition::Breakpoints;
+        }
+
+        self.recorded.push(RecordedLine { depth, state });
+        if self.recorded.len() >= MAX_RECORDED_LINES {
+            self.recording = false;
+        }
+    }
+}
+
+/// Called when a `debugline` op runs.
+pub(crate) fn debug_line(activation: &mut Activation<'_, '_>, line: u32) {
+    let Some(reason) = activation.context.avm2_debugger.enter_line(line) else {
+        return;
+    };
+
+    let depth = activation.avm2().call_stack().borrow().len();
+    let state = capture_state(activation, reason);
+    activation.context.avm2_debugger.record(depth, state);
+}
+
+/// Called when a function is about to call another one, so that the locals
+/// and scopes of the caller can be shown while the callee runs.
+pub(crate) fn record_caller(activation: &mut Activation<'_, '_>) {
+    let has_debug_info = activation
+        .context
+        .avm2_debugger
+        .frames
+        .last()
+        .is_some_and(|frame| frame.file.is_some());
+    if !has_debug_info {
+        return;
+    }
+
+    let locals = capture_locals(activation);
+    let scopes = capture_scopes(activation, false);
+    if let Some(frame) = activation.context.avm2_debugger.frames.last_mut() {
+        frame.locals = locals;
+        frame.scopes = scopes;
+    }
+}
+
+fn capture_state(activation: &mut Activation<'_, '_>, reason: PauseReason) -> PausedState {
+    let locals = capture_locals(activation);
+    let scopes = capture_scopes(activation, true);
+
+    let watches = activation
+        .context
+        .avm2_debugger
+        .watches
+        .clone()
+        .into_iter()
+        .map(|expression| {
+            let value = evaluate_watch(activation, &expression);
+            DebugWatch { expression, value }
+        })
+        .collect();
+
+    let call_stack = activation.avm2().call_stack();
+    let call_stack = call_stack.borrow();
+    let debugger = &activation.context.avm2_debugger;
+
+    // The call stack and the frame information are pushed and popped
+    // together, so they line up from the innermost frame.
+    let mut frames: Vec<DebugFrame> = call_stack
+        .iter()
+        .rev()
+        .zip(
+            debugger
+                .frames
+                .iter()
+                .rev()
+                .map(Some)
+                .chain(std::iter::repeat(None)),
+        )
+        .map(|(method, info)| {
+            let mut function = WString::new();
+            display_call(&mut function, method);
+
+            DebugFrame {
+                function: function.to_string(),
+                file: info.and_then(|info| info.file.clone()),
+                line: info.and_then(|info| info.line),
+                locals: info.map(|info| info.locals.clone()).unwrap_or_default(),
+                scopes: info.map(|info| info.scopes.clone()).unwrap_or_default(),
+            }
+        })
+        .collect();
+
+    if let Some(frame) = frames.first_mut() {
+        frame.locals = locals;
+        frame.scopes = scopes;
+    }
+
+    PausedState {
+        reason,
+        frames,
+        watches,
+    }
+}
+
+fn capture_locals(activation: &mut Activation<'_, '_>) -> Vec<DebugVariable> {
+    let Some(frame) = activation.context.avm2_debugger.frames.last() else {
+        return Vec::new();
+    };
+    let mut registers = frame.register_names.clone();
+    registers.sort_by_key(|(register, _)| *register);
+
+    let num_locals = activation.num_locals();
+    if num_locals > 0 && !registers.iter().any(|(register, _)| *register == 0) {
+        registers.insert(0, (0, "this".to_string()));
+    }
+
+    registers
+        .into_iter()
+        .filter(|(register, _)| (*register as usize) < num_locals)
+        .map(|(register, name)| {
+            let value = activation.local_register(register);
+            DebugVariable {
+                name,
+                value: describe_value(activation, value),
+            }
+        })
+        .collect()
+}
+
+fn capture_scopes(activation: &mut Activation<'_, '_>, with_properties: bool) -> Vec<DebugScope> {
+    scope_chain(activation)
+        .into_iter()
+        .map(|scope| {
+            let values = scope.values();
+            let properties = if with_properties {
+                slots_of(activation, values)
+            } else {
+                Vec::new()
+            };
+
+            DebugScope {
+                object: describe_value(activation, values),
+                with: scope.with(),
+                properties,
+            }
+        })
+        .collect()
+}
+
+/// The scope chain of the activation, innermost scope first.
+fn scope_chain<'gc>(activation: &Activation<'_, 'gc>) -> Vec<Scope<'gc>> {
+    let mut scopes: Vec<Scope<'gc>> = activation.scope_frame().iter().rev().copied().collect();
+
+    let outer = activation.outer();
+    let outer_scopes = (0..)
+        .map_while(|index| outer.get(index))
+        .collect::<Vec<_>>();
+    scopes.extend(outer_scopes.into_iter().rev());
+
+    scopes
+}
+
+fn slots_of<'gc>(activation: &mut Activation<'_, 'gc>, value: Value<'gc>) -> Vec<DebugVariable> {
+    let Some(object) = value.as_object() else {
+        return Vec::new();
+    };
+
+    let mut slots: Vec<(String, usize)> = object
+        .vtable()
+        .resolved_traits()
+        .iter()
+        .filter_map(|(name, _ns, prop)| match prop {
+            Property::Slot { slot_id } | Property::ConstSlot { slot_id } => {
+                Some((name.to_utf8_lossy().into_owned(), *slot_id))
+            }
+            _ => None,
+        })
+        .collect();
+    slots.sort();
+
+    slots
+        .into_iter()
+        .map(|(name, slot_id)| DebugVariable {
+            name,
+            value: describe_value(activation, object.get_slot(slot_id)),
+        })
+        .collect()
+}
+
+/// Describes a value without running any ActionScript code.
+fn describe_value<'gc>(activation: &mut Activation<'_, 'gc>, value: Value<'gc>) -> String {
+    match value {
+        Value::String(string) => format!("{:?}", string.to_string()),
+        Value::Object(object) => {
+            if let Some(class) = object.as_class_object() {
+                let name = class.inner_class_definition().name();
+                format!("[class {}]", name.local_name())
+            } else if object.as_function_object().is_some() {
+                "[function]".to_string()
+            } else {
+                let name = object.instance_class().name();
+                format!("[object {}]", name.local_name())
+            }
+        }
+        value => match value.coerce_to_string(activation) {
+            Ok(string) => string.to_string(),
+            Err(_) => "?".to_string(),
+        },
+    }
+}
+
+/// A part of a watch expression.
+enum PathSegment {
+    Name(String),
+    Index(String),
+}
+
+fn parse_watch(expression: &str) -> Option<Vec<PathSegment>> {
+    let mut segments = Vec::new();
+    let mut rest = expression.trim();
+
+    while !rest.is_empty() {
+        if let Some(index) = rest.strip_prefix('[') {
+            let end = index.find(']')?;
+            let key = index[..end].trim();
+            let key = key
+                .strip_prefix('"')
+                .and_then(|key| key.strip_suffix('"'))
+                .or_else(|| {
+                    key.strip_prefix('\'')
+                        .and_then(|key| key.strip_suffix('\''))
+                })
+                .unwrap_or(key);
+            segments.push(PathSegment::Index(key.to_string()));
+            rest = &index[end + 1..];
+        } else {
+            if !segments.is_empty() {
+                rest = rest.strip_prefix('.')?;
+            }
+            let end = rest.find(['.', '[']).unwrap_or(rest.len());
+            let name = rest[..end].trim();
+            if name.is_empty() {
+                return None;
+            }
+            segments.push(PathSegment::Name(name.to_string()));
+            rest = &rest[end..];
+        }
+    }
+
+    if segments.is_empty() {
+        None
+    } else {
+        Some(segments)
+    }
+}
+
+fn evaluate_watch(activation: &mut Activation<'_, '_>, expression: &str) -> Result<String, String> {
+    let Some(segments) = parse_watch(expression) else {
+        return Err("Invalid expression".to_string());
+    };
+
+    let mut segments = segments.into_iter();
+    let Some(PathSegment::Name(first)) = segments.next() else {
+        return Err("Invalid expression".to_string());
+    };
+
+    let mut value = resolve_name(activation, &first)?;
+    for segment in segments {
+        if matches!(value, Value::Null | Value::Undefined) {
+            return Err(format!(
+                "Cannot access a property of {}",
+                describe_value(activation, value)
+            ));
+        }
+
+        let (PathSegment::Name(name) | PathSegment::Index(name)) = segment;
+        value = match read_member(activation, value, &name) {
+            Some(result) => result?,
+            None => {
+                return Err(format!(
+                    "{name} is not defined on {}",
+                    describe_value(activation, value)
+                ));
+            }
+        };
+    }
+
+    Ok(describe_value(activation, value))
+}
+
+/// Looks up a name as the interpreter would: in the locals, then on the
+/// scope chain, then in the domain.
+///
+/// Like the rest of watch evaluation, this never runs ActionScript code, so
+/// it doesn't run script initializers either: names defined by scripts that
+/// haven't run yet can't be evaluated.
+fn resolve_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    name: &str,
+) -> Result<Value<'gc>, String> {
+    if name == "this" && activation.num_locals() > 0 {
+        return Ok(activation.local_register(0));
+    }
+
+    let register = activation
+        .context
+        .avm2_debugger
+        .frames
+        .last()
+        .and_then(|frame| frame.register_names.iter().find(|(_, n)| n == name))
+        .map(|(register, _)| *register);
+    if let Some(register) = register
+        && (register as usize) < activation.num_locals()
+    {
+        return Ok(activation.local_register(register));
+    }
+
+    for scope in scope_chain(activation) {
+        if let Some(result) = read_member(activation, scope.values(), name) {
+            return result;
+        }
+    }
+
+    let avm_name = AvmString::new_utf8(activation.gc(), name);
+    let multiname = Multiname::new(activation.avm2().find_public_namespace(), avm_name);
+    if let Some((_, script)) = activation.domain().get_defining_script(&multiname) {
+        if !script.is_initialized() {
+            return Err(format!("{name} is not initialized yet"));
+        }
+
+        let (_, globals, _) = script.init();
+        if let Some(result) = read_member(activation, globals.into(), name) {
+            return result;
+        }
+    }
+
+    Err(format!("{name} is not defined"))
+}
+
+/// Reads a property of a value without running any ActionScript code.
+///
+/// Only slots, array and vector elements, and dynamic properties are read.
+/// Getters and methods are reported as errors rather than being called, and
+/// the prototype chain isn't searched. Returns `None` if the value has no
+/// such property.
+fn read_member<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+    name: &str,
+) -> Option<Result<Value<'gc>, String>> {
+    let object = value.as_object()?;
+
+    let property = object
+        .vtable()
+        .resolved_traits()
+        .iter()
+        .find(|(trait_name, _, _)| trait_name.to_utf8_lossy() == name)
+        .map(|(_, _, property)| *property);
+    match property {
+        Some(Property::Slot { slot_id } | Property::ConstSlot { slot_id }) => {
+            return Some(Ok(object.get_slot(slot_id)));
+        }
+        Some(Property::Virtual { .. }) => {
+            return Some(Err(format!("{name} is a getter, which isn't evaluated")));
+        }
+        Some(Property::Method { .. }) => {
+            return Some(Err(format!("{name} is a method, which isn't evaluated")));
+        }
+        None => {}
+    }
+
+    if let Ok(index) = name.parse::<usize>()
+        && let Some(value) = object.get_index_property(index)
+    {
+        return Some(Ok(value));
+    }
+
+    let name = AvmString::new_utf8(activation.gc(), name);
+    object.get_dynamic_property(name).map(Ok)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breakpoint_matches_flex_file_names() {
+        let breakpoint = Breakpoint {
+            file: "com/example/Main.as".to_string(),
+            line: 12,
+        };
+        assert!(breakpoint.matches("C:\\project\\src;com\\example;Main.as", 12));
+        assert!(breakpoint.matches("com/example/Main.as", 12));
+        assert!(!breakpoint.matches("C:\\project\\src;com\\example;Main.as", 13));
+        assert!(!breakpoint.matches("C:\\project\\src;com\\other;Main.as", 12));
+
+        let breakpoint = Breakpoint {
+            file: "Main.as".to_string(),
+            line: 3,
+        };
+        assert!(breakpoint.matches("/home/user/src;;Main.as", 3));
+        assert!(!breakpoint.matches("/home/user/src;;NotMain.as", 3));
+    }
+
+    #[test]
+    fn parse_watch_paths() {
+        let segments = parse_watch("this.items[0][\"name\"].length").unwrap();
+        let segments: Vec<_> = segments
+            .into_iter()
+            .map(|segment| match segment {
+                PathSegment::Name(name) => format!("name:{name}"),
+                PathSegment::Index(key) => format!("index:{key}"),
+            })
+            .collect();
+        assert_eq!(
+            segments,
+            [
+                "name:this",
+                "name:items",
+                "index:0",
+                "index:name",
+                "name:length"
+            ]
+        );
+
+        assert!(parse_watch("").is_none());
+        assert!(parse_watch("a..b").is_none());
+        assert!(parse_watch("a[0").is_none());
+    }
+
+    #[test]
+    fn stepping_walks_recorded_lines() {
+        let mut debugger = Avm2Debugger::default();
+        debugger.attach();
+
+        let state = |line| PausedState {
+            reason: PauseReason::Step,
+            frames: vec![DebugFrame {
+                line: Some(line),
+                ..Default::default()
+            }],
+            watches: vec![],
+        };
+        let line = |debugger: &Avm2Debugger| {
+            debugger
+                .paused_state()
+                .and_then(|state| state.current_frame())
+                .and_then(|frame| frame.line)
+        };
+
+        debugger.record(1, state(1));
+        debugger.record(2, state(10));
+        debugger.record(1, state(2));
+        debugger.record(0, state(20));
+        debugger.finish_recording();
+
+        assert_eq!(line(&debugger), Some(1));
+        debugger.step(StepKind::Into);
+        assert_eq!(line(&debugger), Some(10));
+        debugger.step(StepKind::Out);
+        assert_eq!(line(&debugger), Some(2));
+        debugger.step(StepKind::Over);
+        assert_eq!(line(&debugger), Some(20));
+        debugger.step(StepKind::Over);
+        assert!(!debugger.is_paused());
+        assert_eq!(
+            debugger.break_condition,
+            BreakCondition::NextLine(PauseReason::Step)
+        );
+    }
+}
@@ -7,6 +7,7 @@ use crate::avm2::method::Method;
 use crate::avm2::multiname::Multiname;
 use crate::avm2::object::{ClassObject, ErrorObject};
 use crate::avm2::value::Value;
+use crate::backend::filesystem::FileSystemError;
 use crate::error_message;
 use crate::string::{AvmString, WString};
 
@@ -1074,8 +1075,10 @@ pub fn make_error_2027<'gc>(
     ))
 }
 
+make_error_fn!(make_error_2029, 2029, io_error);
 make_error_fn!(make_error_2030, 2030, eof_error);
 make_error_fn!(make_error_2037, 2037, error);
+make_error_fn!(make_error_2052, 2052, argument_error);
 make_error_fn!(make_error_2058, 2058, io_error);
 make_error_fn!(make_error_2067, 2067, error);
 make_error_fn!(make_error_2078, 2078, illegal_operation_error);
@@ -1110,6 +1113,51 @@ pub fn make_error_2109<'gc>(
     ))
 }
 
+#[inline(never)]
+#[cold]
+pub fn make_error_2121<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    operation: &str,
+    caller_url: &str,
+    target_url: &str,
+) -> Error<'gc> {
+    make_error!(security_error(
+        activation,
+        error_message!(2121, operation, caller_url, target_url),
+        2121,
+    ))
+}
+
+#[inline(never)]
+#[cold]
+pub fn make_error_2122<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    operation: &str,
+    caller_url: &str,
+    target_url: &str,
+) -> Error<'gc> {
+    make_error!(security_error(
+        activation,
+        error_message!(2122, operation, caller_url, target_url),
+        2122,
+    ))
+}
+
+#[inline(never)]
+#[cold]
+pub fn make_error_2123<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    operation: &str,
+    caller_url: &str,
+    target_url: &str,
+) -> Error<'gc> {
+    make_error!(security_error(
+        activation,
+        error_message!(2123, operation, caller_url, target_url),
+        2123,
+    ))
+}
+
 make_error_fn!(make_error_2126, 2126, argument_error);
 make_error_fn!(make_error_2130, 2130, error);
 
@@ -1193,6 +1241,7 @@ make_error_fn!(
     argument_error
 );
 
+make_error_fn!(make_error_3767, 3767, argument_error);
 make_error_fn!(make_error_3771, 3771, argument_error);
 make_error_fn!(make_error_3772, 3772, argument_error);
 make_error_fn!(make_error_3773, 3773, argument_error);
@@ -1211,6 +1260,31 @@ pub fn make_error_3781<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc>
 
 make_error_fn!(make_error_3783, 3783, argument_error);
 
+/// Returns the code and the message of the error that AIR reports when a
+/// filesystem operation fails.
+pub fn filesystem_error_details(e: &FileSystemError) -> (u32, String) {
+    match e {
+        FileSystemError::NotFound => (3003, error_message!(3003)),
+        FileSystemError::AlreadyExists => (3002, error_message!(3002)),
+        FileSystemError::NotADirectory => (3007, error_message!(3007)),
+        FileSystemError::IsADirectory => (3006, error_message!(3006)),
+        FileSystemError::DirectoryNotEmpty => (3010, error_message!(3010)),
+        FileSystemError::ReadOnly => (3001, error_message!(3001)),
+        FileSystemError::InvalidDestination => (3014, error_message!(3014)),
+        FileSystemError::Io(_) => (2038, error_message!(2038)),
+    }
+}
+
+#[inline(never)]
+#[cold]
+pub fn make_filesystem_error<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    e: &FileSystemError,
+) -> Error<'gc> {
+    let (code, message) = filesystem_error_details(e);
+    make_error!(io_error(activation, message, code))
+}
+
 pub fn make_agal_upload_error<'gc>(
     activation: &mut Activation<'_, 'gc>,
     e: AgalError,
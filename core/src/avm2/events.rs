@@ -7,10 +7,12 @@ use crate::avm2::globals::slots::flash_events_event_dispatcher as slots;
 use crate::avm2::object::{EventObject, FunctionObject, Object, TObject as _};
 use crate::display_object::TDisplayObject;
 use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
 use fnv::FnvHashMap;
 use gc_arena::Collect;
 use std::collections::BTreeMap;
 use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 
 /// Which phase of event dispatch is currently occurring.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -254,6 +256,20 @@ impl<'gc> DispatchList<'gc> {
         }
     }
 
+    /// Remove every event handler whose code belongs to the given movie.
+    ///
+    /// This is used to detach the listeners an unloaded movie left behind
+    /// on objects that outlive it, such as the stage.
+    pub fn remove_listeners_for_movie(&mut self, movie: &Arc<SwfMovie>) {
+        for event_sheaf in self.0.values_mut() {
+            for set in event_sheaf.values_mut() {
+                set.retain(|h| {
+                    !Arc::ptr_eq(&h.handler.executable().as_method().owner_movie(), movie)
+                });
+            }
+        }
+    }
+
     /// Determine if there are any event listeners in this dispatch list.
     pub fn has_event_listener(&self, event: AvmString<'gc>) -> bool {
         if let Some(event_sheaf) = self.get_event(event) {
@@ -267,6 +283,22 @@ impl<'gc> DispatchList<'gc> {
         false
     }
 
+    /// Yield every event handler on this dispatch list, along with the event
+    /// type, priority and capture-phase flag it was registered with.
+    ///
+    /// Handlers of the same event and priority are yielded in the order they
+    /// were added.
+    pub fn listeners(
+        &self,
+    ) -> impl '_ + Iterator<Item = (AvmString<'gc>, i32, FunctionObject<'gc>, bool)> {
+        self.0.iter().flat_map(|(event, sheaf)| {
+            sheaf.iter().flat_map(move |(priority, set)| {
+                set.iter()
+                    .map(move |eh| (*event, *priority, eh.handler, eh.use_capture))
+            })
+        })
+    }
+
     /// Yield the event handlers on this dispatch list for a given event.
     ///
     /// Event handlers will be yielded in the order they are intended to be
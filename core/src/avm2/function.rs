@@ -1,5 +1,6 @@
 use crate::avm2::Multiname;
 use crate::avm2::activation::Activation;
+use crate::avm2::debugger;
 use crate::avm2::error::{Error, make_error_1001, make_error_1063};
 use crate::avm2::method::{Method, MethodKind, ParamConfig};
 use crate::avm2::object::{ClassObject, FunctionObject};
@@ -86,6 +87,21 @@ impl<'gc> BoundMethod<'gc> {
         self.method
     }
 
+    /// The scope this method was defined in.
+    pub fn scope(&self) -> ScopeChain<'gc> {
+        self.scope
+    }
+
+    /// The receiver this method is always called with, if it is bound.
+    pub fn bound_receiver(&self) -> Option<Value<'gc>> {
+        self.bound_receiver
+    }
+
+    /// The superclass of the class that defined this method, if any.
+    pub fn bound_superclass(&self) -> Option<ClassObject<'gc>> {
+        self.bound_superclass
+    }
+
     pub fn debug_full_name(&self) -> WString {
         let mut output = WString::new();
         display_function(&mut output, self.as_method());
@@ -200,6 +216,11 @@ pub fn exec<'gc>(
 
     let caller_dxns = activation.default_xml_namespace();
 
+    let debugger_attached = activation.context.avm2_debugger.is_attached();
+    if debugger_attached {
+        debugger::record_caller(activation);
+    }
+
     let ret = match method.method_kind() {
         MethodKind::Native { native_method, .. } => {
             let caller_domain = activation.caller_domain();
@@ -237,6 +258,9 @@ pub fn exec<'gc>(
             };
 
             activation.context.avm2.push_call(mc, method);
+            if debugger_attached {
+                activation.context.avm2_debugger.enter_frame();
+            }
 
             native_method(&mut activation, receiver, &arguments)
         }
@@ -285,6 +309,9 @@ pub fn exec<'gc>(
             };
 
             activation.context.avm2.push_call(mc, method);
+            if debugger_attached {
+                activation.context.avm2_debugger.enter_frame();
+            }
 
             let result = activation.run_actions(method);
 
@@ -294,6 +321,9 @@ pub fn exec<'gc>(
         }
     };
     activation.context.avm2.pop_call(mc);
+    if debugger_attached {
+        activation.context.avm2_debugger.exit_frame();
+    }
     ret
 }
 
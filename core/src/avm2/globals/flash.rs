@@ -6,7 +6,9 @@ pub mod display;
 pub mod display3D;
 pub mod events;
 pub mod external;
+pub mod filesystem;
 pub mod geom;
+pub mod globalization;
 pub mod media;
 pub mod net;
 pub mod system;
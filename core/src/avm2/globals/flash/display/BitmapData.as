@@ -209,8 +209,32 @@ package flash.display {
 
         [API("680")]
         public function encode(rect:Rectangle, compressor:Object, byteArray:ByteArray = null):ByteArray {
-            stub_method("flash.display.BitmapData", "encode");
-            return null;
+            if (byteArray == null) {
+                byteArray = new ByteArray();
+            }
+
+            if (compressor is PNGEncoderOptions) {
+                this.encodePNG(rect, PNGEncoderOptions(compressor).fastCompression, byteArray);
+            } else if (compressor is JPEGEncoderOptions) {
+                this.encodeJPEG(rect, JPEGEncoderOptions(compressor).quality, byteArray);
+            } else if (compressor is JPEGXREncoderOptions) {
+                var options:JPEGXREncoderOptions = JPEGXREncoderOptions(compressor);
+                this.encodeJPEGXR(rect, options.quantization, options.colorSpace, options.trimFlexBits, byteArray);
+            } else {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+
+            return byteArray;
         }
+
+        private native function encodePNG(rect:Rectangle, fastCompression:Boolean, byteArray:ByteArray):void;
+        private native function encodeJPEG(rect:Rectangle, quality:uint, byteArray:ByteArray):void;
+        private native function encodeJPEGXR(
+            rect:Rectangle,
+            quantization:uint,
+            colorSpace:String,
+            trimFlexBits:uint,
+            byteArray:ByteArray
+        ):void;
     }
 }
@@ -5,6 +5,11 @@ package flash.display {
 
     [Ruffle(Abstract)]
     public final class Graphics {
+        // The BitmapData objects used by bitmap fills, indexed by their
+        // bitmap ID in the drawing, so readGraphicsData can return them.
+        [Ruffle(NativeAccessible)]
+        private var _bitmaps:Array;
+
         public native function beginBitmapFill(
             bitmap:BitmapData,
             matrix:Matrix = null,
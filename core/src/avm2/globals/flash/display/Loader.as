@@ -1,6 +1,4 @@
 package flash.display {
-    import __ruffle__.stub_method;
-
     import flash.display.LoaderInfo;
     import flash.display.DisplayObject;
     import flash.errors.IllegalOperationError;
@@ -19,9 +17,7 @@ package flash.display {
             return this._contentLoaderInfo;
         }
 
-        public function get content():DisplayObject {
-            return this._contentLoaderInfo.content;
-        }
+        public native function get content():DisplayObject;
 
         public native function load(request:URLRequest, context:LoaderContext = null):void;
 
@@ -30,14 +26,9 @@ package flash.display {
         public native function unload():void;
 
         [API("662")]
-        public function unloadAndStop(gc:Boolean = true):void {
-            stub_method("flash.display.Loader", "unloadAndStop");
-            this.unload();
-        }
+        public native function unloadAndStop(gc:Boolean = true):void;
 
-        public function close():void {
-            stub_method("flash.display.Loader", "close");
-        }
+        public native function close():void;
 
         override public function addChild(child:DisplayObject):DisplayObject {
             throw new IllegalOperationError("Error #2069: The Loader class does not implement this method.", 2069);
@@ -1,7 +1,5 @@
 package flash.display {
-    import __ruffle__.stub_getter;
     import __ruffle__.stub_method;
-    import __ruffle__.stub_setter;
 
     import flash.errors.IllegalOperationError;
     import flash.events.Event;
@@ -52,20 +50,23 @@ package flash.display {
         // Playerglobals says all the *Bridge functions are AIR-only, but that
         // doesn't seem to be true
 
+        // The objects that the loading and the loaded movie expose to each
+        // other, regardless of their security sandboxes.
+        private var _parentSandboxBridge:Object = null;
+        private var _childSandboxBridge:Object = null;
+
         public function get parentSandboxBridge():Object {
-            stub_getter("flash.display.LoaderInfo", "parentSandboxBridge");
-            return null;
+            return this._parentSandboxBridge;
         }
         public function set parentSandboxBridge(obj:Object):void {
-            stub_setter("flash.display.LoaderInfo", "parentSandboxBridge");
+            this._parentSandboxBridge = obj;
         }
 
         public function get childSandboxBridge():Object {
-            stub_getter("flash.display.LoaderInfo", "childSandboxBridge");
-            return null;
+            return this._childSandboxBridge;
         }
         public function set childSandboxBridge(obj:Object):void {
-            stub_setter("flash.display.LoaderInfo", "childSandboxBridge");
+            this._childSandboxBridge = obj;
         }
     }
 }
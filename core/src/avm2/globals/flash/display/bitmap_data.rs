@@ -11,6 +11,7 @@ use crate::avm2::filters::FilterAvm2Ext;
 use crate::avm2::globals::flash::display::display_object::object_to_rectangle;
 use crate::avm2::globals::flash::geom::transform::object_to_color_transform;
 use crate::avm2::globals::flash::geom::transform::object_to_matrix;
+use crate::avm2::globals::flash::system::security::check_content_access;
 use crate::avm2::globals::slots::{
     flash_geom_point as point_slots, flash_geom_rectangle as rectangle_slots,
 };
@@ -22,13 +23,17 @@ use crate::avm2::vector::VectorStorage;
 use crate::avm2_stub_method;
 use crate::bitmap::bitmap_data::{BitmapData, ChannelOptions, ThresholdOperation};
 use crate::bitmap::bitmap_data::{BitmapDataDrawError, IBitmapDrawable};
+use crate::bitmap::jpegxr::{JpegXrColorSpace, JpegXrOptions};
+use crate::bitmap::operations::BitmapEncoding;
 use crate::bitmap::{is_size_valid, operations};
 use crate::character::{Character, CompressedBitmap};
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
 use crate::ecma_conversions::round_to_even;
 use crate::swf::BlendMode;
 use ruffle_render::filters::Filter;
 use ruffle_render::transform::Transform;
 use std::str::FromStr;
+use std::sync::Arc;
 use swf::{Rectangle, Twips};
 
 // Computes the integer x,y,width,height values from
@@ -358,6 +363,82 @@ pub fn copy_pixels_to_byte_array<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements the PNG path of `BitmapData.encode`.
+pub fn encode_png<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let fast_compression = args.get_bool(1);
+    encode(
+        activation,
+        this,
+        args,
+        BitmapEncoding::Png { fast_compression },
+    )
+}
+
+/// Implements the JPEG path of `BitmapData.encode`.
+pub fn encode_jpeg<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let quality = args.get_u32(1).clamp(1, 100) as u8;
+    encode(activation, this, args, BitmapEncoding::Jpeg { quality })
+}
+
+/// Implements the JPEG XR path of `BitmapData.encode`.
+pub fn encode_jpegxr<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let quantization = args.get_u32(1).min(100) as u8;
+    let color_space = JpegXrColorSpace::from_name(&args.get_string(activation, 2).to_string());
+    let trim_flex_bits = args.get_u32(3).min(15) as u8;
+    let options = JpegXrOptions {
+        quantization,
+        color_space,
+        trim_flex_bits,
+    };
+    encode(activation, this, args, BitmapEncoding::JpegXr(options))
+}
+
+fn encode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+    encoding: BitmapEncoding,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(bitmap_data) = this.as_bitmap_data() {
+        bitmap_data.check_valid(activation)?;
+        let rectangle = args.get_object(activation, 0, "rect")?;
+        let (x, y, width, height) = get_rectangle_x_y_width_height(activation, rectangle)?;
+        let bytes = operations::encode(
+            bitmap_data,
+            activation.context.renderer,
+            x,
+            y,
+            width,
+            height,
+            encoding,
+        )
+        .map_err(|e| Error::rust_error(format!("Failed to encode BitmapData: {e}").into()))?;
+
+        // The encoder options sit between the rectangle and the byte array.
+        let storage = args.get_object(activation, args.len() - 1, "byteArray")?;
+        let mut storage = storage.as_bytearray_mut().unwrap();
+        storage
+            .write_bytes(&bytes)
+            .map_err(|e| e.to_avm(activation))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_vector<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
@@ -947,6 +1028,7 @@ pub fn draw<'gc>(
         let source = args.get_object(activation, 0, "source")?;
 
         let source = if let Some(source_object) = source.as_display_object() {
+            check_draw_access(activation, "BitmapData.draw", source_object)?;
             IBitmapDrawable::DisplayObject(source_object)
         } else if let Some(source_bitmap) = source.as_bitmap_data() {
             IBitmapDrawable::BitmapData(source_bitmap)
@@ -1020,6 +1102,7 @@ pub fn draw_with_quality<'gc>(
         let source = args.get_object(activation, 0, "source")?;
 
         let source = if let Some(source_object) = source.as_display_object() {
+            check_draw_access(activation, "BitmapData.drawWithQuality", source_object)?;
             IBitmapDrawable::DisplayObject(source_object)
         } else if let Some(source_bitmap) = source.as_bitmap_data() {
             IBitmapDrawable::BitmapData(source_bitmap)
@@ -1056,6 +1139,32 @@ pub fn draw_with_quality<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Checks that the caller may read the pixels of every display object that
+/// `source` would draw, as content from other domains has to grant access.
+fn check_draw_access<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    operation: &str,
+    source: DisplayObject<'gc>,
+) -> Result<(), Error<'gc>> {
+    let caller = activation.caller_movie_or_root();
+    let mut pending = vec![source];
+
+    while let Some(object) = pending.pop() {
+        let movie = object.movie();
+        // Placeholder movies of `Loader`s that haven't loaded anything have no content.
+        let is_placeholder = !movie.is_movie() && movie.compressed_len() == 0;
+        if !Arc::ptr_eq(&movie, &caller) && !is_placeholder {
+            check_content_access(activation, operation, &movie)?;
+        }
+
+        if let Some(container) = object.as_container() {
+            pending.extend(container.iter_render_list());
+        }
+    }
+
+    Ok(())
+}
+
 /// Implement `BitmapData.fillRect`
 pub fn fill_rect<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -5,16 +5,19 @@ use crate::avm2::activation::Activation;
 use crate::avm2::error::{make_error_2005, make_error_2007, make_error_2008, make_error_2078};
 use crate::avm2::filters::FilterAvm2Ext;
 use crate::avm2::globals::flash::geom::transform::color_transform_from_transform_object;
-use crate::avm2::globals::flash::geom::transform::has_matrix3d_from_transform_object;
+use crate::avm2::globals::flash::geom::transform::matrix_3d_from_transform_object;
 use crate::avm2::globals::flash::geom::transform::matrix_from_transform_object;
+use crate::avm2::globals::flash::system::security::scriptable_display_object;
 use crate::avm2::globals::slots::flash_display_shader as shader_slots;
 use crate::avm2::globals::slots::flash_geom_point as point_slots;
 use crate::avm2::globals::slots::flash_geom_rectangle as rectangle_slots;
+use crate::avm2::globals::slots::flash_geom_vector_3d as vector3d_slots;
 use crate::avm2::object::{Object, TObject as _};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::{ArrayObject, ArrayStorage};
 use crate::avm2::{ClassObject, Error};
+use crate::avm2_stub_getter;
 use crate::context::UpdateContext;
 use crate::display_object::BoundsMode;
 use crate::ecma_conversions::round_to_even;
@@ -22,7 +25,6 @@ use crate::prelude::*;
 use crate::string::AvmString;
 use crate::types::{Degrees, Percent};
 use crate::vminterface::Instantiator;
-use crate::{avm2_stub_getter, avm2_stub_setter};
 use ruffle_render::blend::ExtendedBlendMode;
 use ruffle_render::filters::Filter;
 use std::str::FromStr;
@@ -126,7 +128,6 @@ pub fn get_scale9grid<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.as_object().unwrap();
 
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "scale9Grid");
     if let Some(dobj) = this.as_display_object() {
         let rect = dobj.scaling_grid();
         return if rect.is_valid() {
@@ -147,7 +148,6 @@ pub fn set_scale9grid<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.as_object().unwrap();
 
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "scale9Grid");
     if let Some(dobj) = this.as_display_object() {
         let rect = match args.try_get_object(0) {
             None => Rectangle::default(),
@@ -371,95 +371,195 @@ pub fn set_y<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Informs the ancestors of `dobj` that its 3D transformation changed.
+fn matrix3d_changed(dobj: DisplayObject<'_>) {
+    dobj.set_transformed_by_script(true);
+    if let Some(parent) = dobj.parent() {
+        // Self-transform changes are automatically handled,
+        // we only want to inform ancestors to avoid unnecessary invalidations for tx/ty
+        parent.invalidate_cached_bitmap();
+    }
+}
+
+/// Returns the rotation (in degrees) and scale of `dobj` along each axis.
+fn rotation_scale_3d(dobj: DisplayObject<'_>) -> ([f64; 3], [f64; 3]) {
+    match dobj.base().matrix3d() {
+        Some(matrix3d) => {
+            let (_, rotation, scale) = matrix3d.to_components();
+            (rotation.map(f64::to_degrees), scale)
+        }
+        None => ([0.0; 3], [1.0; 3]),
+    }
+}
+
+/// Normalizes a rotation into the range of [-180, 180].
+fn normalize_rotation(mut rotation: f64) -> f64 {
+    rotation %= 360.0;
+    if rotation < -180.0 {
+        rotation += 360.0
+    } else if rotation > 180.0 {
+        rotation -= 360.0
+    }
+    rotation
+}
+
+/// Implements `z`'s getter.
 pub fn get_z<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Value<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "z");
-    Ok(0.into())
+    let this = this.as_object().unwrap();
+
+    if let Some(dobj) = this.as_display_object() {
+        let z = dobj.base().matrix3d().map_or(0.0, |m| m.raw_data[14]);
+        return Ok(z.into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `z`'s setter.
 pub fn set_z<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Value<'gc>,
-    _args: &[Value<'gc>],
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "z");
+    let this = this.as_object().unwrap();
+
+    if let Some(dobj) = this.as_display_object() {
+        let mut matrix3d = dobj.base().ensure_matrix3d();
+        matrix3d.raw_data[14] = args.get_f64(0) as f32;
+        dobj.base().set_matrix3d(Some(matrix3d));
+        matrix3d_changed(dobj);
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `rotationX`'s getter.
 pub fn get_rotation_x<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Value<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "rotationX");
-    Ok(0.into())
+    let this = this.as_object().unwrap();
+
+    if let Some(dobj) = this.as_display_object() {
+        let ([rotation_x, _, _], _) = rotation_scale_3d(dobj);
+        return Ok(rotation_x.into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `rotationX`'s setter.
 pub fn set_rotation_x<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Value<'gc>,
-    _args: &[Value<'gc>],
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "rotationX");
+    let this = this.as_object().unwrap();
+
+    if let Some(dobj) = this.as_display_object() {
+        let radians = normalize_rotation(args.get_f64(0)).to_radians();
+        dobj.base()
+            .update_matrix3d_components(|_, rotation, _| rotation[0] = radians);
+        matrix3d_changed(dobj);
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `rotationY`'s getter.
 pub fn get_rotation_y<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Value<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "rotationY");
-    Ok(0.into())
+    let this = this.as_object().unwrap();
+
+    if let Some(dobj) = this.as_display_object() {
+        let ([_, rotation_y, _], _) = rotation_scale_3d(dobj);
+        return Ok(rotation_y.into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `rotationY`'s setter.
 pub fn set_rotation_y<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Value<'gc>,
-    _args: &[Value<'gc>],
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "rotationY");
+    let this = this.as_object().unwrap();
+
+    if let Some(dobj) = this.as_display_object() {
+        let radians = normalize_rotation(args.get_f64(0)).to_radians();
+        dobj.base()
+            .update_matrix3d_components(|_, rotation, _| rotation[1] = radians);
+        matrix3d_changed(dobj);
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `rotationZ`'s getter.
 pub fn get_rotation_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    // TODO This probably interacts with Matrix3D.
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "rotationZ");
+    // The rotation around the z axis is the 2D rotation.
     get_rotation(activation, this, args)
 }
 
+/// Implements `rotationZ`'s setter.
 pub fn set_rotation_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    // TODO This probably interacts with Matrix3D.
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "rotationZ");
+    if let Some(dobj) = this.as_object().and_then(|this| this.as_display_object()) {
+        dobj.base().ensure_matrix3d();
+        matrix3d_changed(dobj);
+    }
+
     set_rotation(activation, this, args)
 }
 
+/// Implements `scaleZ`'s getter.
 pub fn get_scale_z<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Value<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "scaleZ");
-    Ok(1.into())
+    let this = this.as_object().unwrap();
+
+    if let Some(dobj) = this.as_display_object() {
+        let (_, [_, _, scale_z]) = rotation_scale_3d(dobj);
+        return Ok(scale_z.into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `scaleZ`'s setter.
 pub fn set_scale_z<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Value<'gc>,
-    _args: &[Value<'gc>],
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "scaleZ");
+    let this = this.as_object().unwrap();
+
+    if let Some(dobj) = this.as_display_object() {
+        let value = args.get_f64(0);
+        dobj.base()
+            .update_matrix3d_components(|_, _, scale| scale[2] = value);
+        matrix3d_changed(dobj);
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -494,16 +594,7 @@ pub fn set_rotation<'gc>(
     let this = this.as_object().unwrap();
 
     if let Some(dobj) = this.as_display_object() {
-        let mut new_rotation = args.get_f64(0);
-
-        // Normalize into the range of [-180, 180].
-        new_rotation %= 360.0;
-        if new_rotation < -180.0 {
-            new_rotation += 360.0
-        } else if new_rotation > 180.0 {
-            new_rotation -= 360.0
-        }
-
+        let new_rotation = normalize_rotation(args.get_f64(0));
         dobj.set_rotation(Degrees::from(new_rotation));
     }
 
@@ -555,17 +646,17 @@ pub fn set_name<'gc>(
 
 /// Implements `parent`.
 pub fn get_parent<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.as_object().unwrap();
 
     if let Some(dobj) = this.as_display_object() {
-        return Ok(dobj
-            .avm2_parent()
-            .map(|parent| parent.object2_or_null())
-            .unwrap_or(Value::Null));
+        return match dobj.avm2_parent() {
+            Some(parent) => scriptable_display_object(activation, "DisplayObject.parent", parent),
+            None => Ok(Value::Null),
+        };
     }
 
     Ok(Value::Undefined)
@@ -573,17 +664,17 @@ pub fn get_parent<'gc>(
 
 /// Implements `root`.
 pub fn get_root<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.as_object().unwrap();
 
     if let Some(dobj) = this.as_display_object() {
-        return Ok(dobj
-            .avm2_root()
-            .map(|root| root.object2_or_null())
-            .unwrap_or(Value::Null));
+        return match dobj.avm2_root() {
+            Some(root) => scriptable_display_object(activation, "DisplayObject.root", root),
+            None => Ok(Value::Null),
+        };
     }
 
     Ok(Value::Undefined)
@@ -818,13 +909,13 @@ pub fn set_transform<'gc>(
 
     // FIXME - consider pixel bounds
     let matrix = matrix_from_transform_object(transform);
-    let has_matrix3d = has_matrix3d_from_transform_object(transform);
+    let matrix3d = matrix_3d_from_transform_object(transform);
     let color_transform = color_transform_from_transform_object(transform);
 
     let dobj = this.as_display_object().unwrap();
     let base = dobj.base();
+    base.set_matrix3d(matrix3d);
     base.set_matrix(matrix);
-    base.set_has_matrix3d_stub(has_matrix3d);
     base.set_color_transform(color_transform);
     if let Some(parent) = dobj.parent() {
         // Self-transform changes are automatically handled,
@@ -871,7 +962,7 @@ pub fn set_blend_mode<'gc>(
     Ok(Value::Undefined)
 }
 
-fn new_rectangle<'gc>(
+pub fn new_rectangle<'gc>(
     activation: &mut Activation<'_, 'gc>,
     rectangle: Rectangle<Twips>,
 ) -> Result<Value<'gc>, Error<'gc>> {
@@ -999,6 +1090,73 @@ pub fn global_to_local<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `DisplayObject.local3DToGlobal`.
+pub fn local_3d_to_global<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(dobj) = this.as_display_object() {
+        let point = args.get_object(activation, 0, "point3d")?;
+        let local = [
+            point.get_slot(vector3d_slots::X).as_f64() as f32,
+            point.get_slot(vector3d_slots::Y).as_f64() as f32,
+            point.get_slot(vector3d_slots::Z).as_f64() as f32,
+        ];
+
+        let matrix = dobj.local_to_global_matrix3d(activation.context.stage);
+        let [x, y, _, w] = matrix.transform_point(local).map(f64::from);
+        return activation
+            .avm2()
+            .classes()
+            .point
+            .construct(activation, &[(x / w).into(), (y / w).into()]);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.globalToLocal3D`.
+pub fn global_to_local_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(dobj) = this.as_display_object() {
+        let point = args.get_object(activation, 0, "point")?;
+        let x = point.get_slot(point_slots::X).as_f64();
+        let y = point.get_slot(point_slots::Y).as_f64();
+
+        // Find the point of the local `z = 0` plane which ends up at the given
+        // global position once projected, by solving
+        // `(m * p).x = x * (m * p).w` and `(m * p).y = y * (m * p).w`.
+        let m = dobj
+            .local_to_global_matrix3d(activation.context.stage)
+            .raw_data
+            .map(f64::from);
+        let (a, b, c) = (m[0] - x * m[3], m[4] - x * m[7], x * m[15] - m[12]);
+        let (d, e, f) = (m[1] - y * m[3], m[5] - y * m[7], y * m[15] - m[13]);
+        let determinant = a * e - b * d;
+        let (local_x, local_y) = if determinant != 0.0 {
+            ((c * e - b * f) / determinant, (a * f - c * d) / determinant)
+        } else {
+            (0.0, 0.0)
+        };
+
+        return activation
+            .avm2()
+            .classes()
+            .vector3d
+            .construct(activation, &[local_x.into(), local_y.into(), 0.0.into()]);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Helper method for getting the bounds of a `DisplayObject` in the target
 /// space of another `DisplayObject`, using a specific bounds mode. This method
 /// is used to implement `DisplayObject.getRect` and `DisplayObject.getBounds`.
@@ -8,6 +8,7 @@ use crate::avm2::error::{
     Error2006Type, make_error_2006, make_error_2024, make_error_2025, make_error_2150,
     make_error_2180, make_error_3783,
 };
+use crate::avm2::globals::flash::system::security::scriptable_display_object;
 use crate::avm2::globals::slots::flash_geom_point as point_slots;
 use crate::avm2::object::{Object, TObject as _};
 use crate::avm2::parameters::ParametersExt;
@@ -126,7 +127,7 @@ pub fn get_child_at<'gc>(
     {
         let index = args.get_i32(0);
         return if let Some(child) = dobj.child_by_index(index as usize) {
-            Ok(child.object2_or_null())
+            scriptable_display_object(activation, "DisplayObjectContainer.getChildAt", child)
         } else {
             Err(make_error_2006(activation, Error2006Type::RangeError))
         };
@@ -149,7 +150,11 @@ pub fn get_child_by_name<'gc>(
     {
         let name = args.get_string(activation, 0);
         if let Some(child) = dobj.child_by_name(&name, true) {
-            return Ok(child.object2_or_null());
+            return scriptable_display_object(
+                activation,
+                "DisplayObjectContainer.getChildByName",
+                child,
+            );
         } else {
             return Ok(Value::Null);
         }
@@ -2,28 +2,39 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::error::{Error2004Type, make_error_2004, make_error_2007, make_error_2008};
-use crate::avm2::globals::flash::geom::transform::object_to_matrix;
+use crate::avm2::globals::flash::display::bitmap_data::fill_bitmap_data_from_symbol;
+use crate::avm2::globals::flash::geom::transform::{matrix_to_object, object_to_matrix};
+use crate::avm2::globals::slots::flash_display_graphics as graphics_slots;
 use crate::avm2::globals::slots::flash_display_graphics_bitmap_fill as graphics_bitmap_fill_slots;
 use crate::avm2::globals::slots::flash_display_graphics_gradient_fill as graphics_gradient_fill_slots;
 use crate::avm2::globals::slots::flash_display_graphics_path as graphics_path_slots;
 use crate::avm2::globals::slots::flash_display_graphics_solid_fill as graphics_solid_fill_slots;
 use crate::avm2::globals::slots::flash_display_graphics_stroke as graphics_stroke_slots;
 use crate::avm2::globals::slots::flash_display_graphics_triangle_path as graphics_triangle_path_slots;
-use crate::avm2::object::{Object, TObject as _, VectorObject};
+use crate::avm2::globals::slots::flash_display_shape as shape_slots;
+use crate::avm2::globals::slots::flash_display_sprite as sprite_slots;
+use crate::avm2::object::{ArrayObject, BitmapDataObject, Object, TObject as _, VectorObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::vector::VectorStorage;
 use crate::avm2::{ArrayStorage, Error};
 use crate::avm2_stub_method;
-use crate::display_object::TDisplayObject;
+use crate::character::Character;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
 use crate::drawing::Drawing;
 use crate::string::{AvmString, WStr};
+use crate::tag_utils::SwfMovie;
 use either::Either;
-use ruffle_render::shape_utils::{DrawCommand, FillRule, GradientType};
+use ruffle_render::bitmap::BitmapInfo;
+use ruffle_render::matrix::Matrix as RenderMatrix;
+use ruffle_render::shape_utils::{
+    DistilledShape, DrawCommand, DrawPath, FillRule, GradientType, TexturedTriangle,
+};
 use std::f64::consts::FRAC_1_SQRT_2;
+use std::sync::Arc;
 use swf::{
-    Color, FillStyle, Fixed8, Gradient, GradientInterpolation, GradientRecord, GradientSpread,
-    LineCapStyle, LineJoinStyle, LineStyle, Matrix, Point, Twips,
+    CharacterId, Color, FillStyle, Fixed8, Gradient, GradientInterpolation, GradientRecord,
+    GradientSpread, LineCapStyle, LineJoinStyle, LineStyle, Matrix, Point, Twips,
 };
 
 /// Convert an RGB `color` and `alpha` argument pair into a `swf::Color`.
@@ -58,13 +69,10 @@ pub fn begin_bitmap_fill<'gc>(
     this: Value<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let this = this.as_object().unwrap();
+    let graphics = this.as_object().unwrap();
 
-    if let Some(this) = this.as_display_object() {
-        let bitmap = args
-            .get_object(activation, 0, "bitmap")?
-            .as_bitmap_data()
-            .expect("Bitmap argument is ensured to be a BitmapData from actionscript");
+    if let Some(this) = graphics.as_display_object() {
+        let bitmap = args.get_object(activation, 0, "bitmap")?;
         let matrix = if let Some(matrix) = args.try_get_object(1) {
             Matrix::from(object_to_matrix(matrix))
         } else {
@@ -74,20 +82,13 @@ pub fn begin_bitmap_fill<'gc>(
         let is_repeating = args.get_bool(2);
         let is_smoothed = args.get_bool(3);
 
-        let handle = bitmap.bitmap_handle(activation.gc(), activation.context.renderer);
-
-        let bitmap = ruffle_render::bitmap::BitmapInfo {
-            handle,
-            width: bitmap.width(),
-            height: bitmap.height(),
-        };
         let scale_matrix = Matrix::scale(
             (Twips::TWIPS_PER_PIXEL as i16).into(),
             (Twips::TWIPS_PER_PIXEL as i16).into(),
         );
 
         if let Some(mut draw) = this.as_drawing() {
-            let id = draw.add_bitmap(bitmap);
+            let id = add_bitmap(activation, graphics, &mut draw, bitmap);
             draw.set_fill_style(Some(FillStyle::Bitmap {
                 id,
                 matrix: matrix * scale_matrix,
@@ -252,16 +253,17 @@ fn parse_spread_method(spread_method: AvmString) -> GradientSpread {
 
 /// Implements `Graphics.clear`
 pub fn clear<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let this = this.as_object().unwrap();
+    let graphics = this.as_object().unwrap();
 
-    if let Some(this) = this.as_display_object()
+    if let Some(this) = graphics.as_display_object()
         && let Some(mut draw) = this.as_drawing()
     {
-        draw.clear()
+        draw.clear();
+        graphics.set_slot_no_coerce(graphics_slots::_BITMAPS, Value::Null, activation.gc());
     }
 
     Ok(Value::Undefined)
@@ -979,11 +981,11 @@ pub fn copy_from<'gc>(
     this: Value<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let this = this.as_object().unwrap();
+    let graphics = this.as_object().unwrap();
 
-    if let Some(this) = this.as_display_object() {
-        let source = args
-            .get_object(activation, 0, "sourceGraphics")?
+    if let Some(this) = graphics.as_display_object() {
+        let source_graphics = args.get_object(activation, 0, "sourceGraphics")?;
+        let source = source_graphics
             .as_display_object()
             .expect("Bad sourceGraphics");
 
@@ -994,6 +996,15 @@ pub fn copy_from<'gc>(
         let mut target_drawing = this.as_drawing().expect("Missing drawing for target");
 
         target_drawing.clone_from(&source);
+
+        // Both drawings may add more bitmaps from now on, so the array is copied.
+        let bitmaps = source_graphics
+            .get_slot(graphics_slots::_BITMAPS)
+            .as_object()
+            .and_then(|bitmaps| bitmaps.as_array_storage().map(|storage| storage.clone()))
+            .map(|storage| ArrayObject::from_storage(activation.context, storage).into())
+            .unwrap_or(Value::Null);
+        graphics.set_slot_no_coerce(graphics_slots::_BITMAPS, bitmaps, activation.gc());
     }
     Ok(Value::Undefined)
 }
@@ -1057,24 +1068,6 @@ pub fn draw_triangles<'gc>(
                 .ok_or_else(|| make_error_2004(activation, Error2004Type::ArgumentError))?
         };
 
-        // FIXME Triangles should be drawn using non-zero winding rule.
-        //   When fixed, update output.expected.png of avm2/graphics_draw_triangles.
-        avm2_stub_method!(
-            activation,
-            "flash.display.Graphics",
-            "drawTriangles",
-            "winding behavior"
-        );
-
-        if uvt_data.is_some() {
-            avm2_stub_method!(
-                activation,
-                "flash.display.Graphics",
-                "drawTriangles",
-                "with uvt data"
-            );
-        }
-
         draw_triangles_internal(
             activation,
             &mut drawing,
@@ -1193,22 +1186,65 @@ impl TriangleData {
         }
     }
 
-    fn iter_triangles(&self) -> impl Iterator<Item = [Point<Twips>; 3]> + '_ {
+    fn num_vertices(&self) -> usize {
+        match self {
+            Self::Indexed { vertices, .. } => vertices.len(),
+            Self::Sequential { triangles } => triangles.len() * 3,
+        }
+    }
+
+    /// Iterates over the triangles, along with the indices of their vertices.
+    fn iter_triangles(&self) -> impl Iterator<Item = ([usize; 3], [Point<Twips>; 3])> + '_ {
         match self {
             Self::Indexed { vertices, indices } => {
                 Either::Left(indices.iter().map(|&[i0, i1, i2]| {
-                    [
-                        vertices[i0 as usize],
-                        vertices[i1 as usize],
-                        vertices[i2 as usize],
-                    ]
+                    let indices = [i0 as usize, i1 as usize, i2 as usize];
+                    (indices, indices.map(|i| vertices[i]))
                 }))
             }
-            Self::Sequential { triangles } => Either::Right(triangles.iter().copied()),
+            Self::Sequential { triangles } => Either::Right(
+                triangles
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &triangle)| ([i * 3, i * 3 + 1, i * 3 + 2], triangle)),
+            ),
         }
     }
 }
 
+/// Parses the `uvtData` of `Graphics.drawTriangles` into the bitmap coordinates
+/// of each vertex.
+///
+/// The data contains either `u` and `v`, or `u`, `v` and `t` for each vertex.
+/// Without `t`, the bitmap is mapped without perspective.
+fn parse_uvt_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    uvt_data: &Object<'gc>,
+    num_vertices: usize,
+) -> Result<Box<[[f32; 3]]>, Error<'gc>> {
+    let uvt_storage = uvt_data
+        .as_vector_storage()
+        .expect("uvtData is not a Vector");
+    let uvt_storage = uvt_storage.storage();
+    let to_f32 = |value: &Value<'gc>| value.as_f64() as f32;
+
+    if uvt_storage.len() == num_vertices * 2 {
+        let uv_pairs = uvt_storage.as_chunks::<2>().0;
+        Ok(uv_pairs
+            .iter()
+            .map(|[u, v]| [to_f32(u), to_f32(v), 1.0])
+            .collect())
+    } else if uvt_storage.len() == num_vertices * 3 {
+        let uvt_triples = uvt_storage.as_chunks::<3>().0;
+        Ok(uvt_triples
+            .iter()
+            .map(|[u, v, t]| [to_f32(u), to_f32(v), to_f32(t)])
+            .collect())
+    } else {
+        Err(make_error_2004(activation, Error2004Type::ArgumentError))
+    }
+}
+
 fn make_point<'gc>([x, y]: &[Value<'gc>; 2]) -> Point<Twips> {
     let x = Twips::from_pixels(x.as_f64());
     let y = Twips::from_pixels(y.as_f64());
@@ -1261,18 +1297,28 @@ fn draw_triangles_internal<'gc>(
     drawing: &mut Drawing,
     vertices: &Object<'gc>,
     indices: Option<&Object<'gc>>,
-    _uvt_data: Option<&Object<'gc>>,
+    uvt_data: Option<&Object<'gc>>,
     culling: TriangleCulling,
 ) -> Result<(), Error<'gc>> {
     let Some(data) = TriangleData::new(activation, vertices, indices)? else {
         return Ok(());
     };
 
-    for [a, b, c] in data.iter_triangles().filter(|&tri| !culling.cull(tri)) {
-        drawing.draw_command(DrawCommand::MoveTo(a));
-        drawing.draw_command(DrawCommand::LineTo(b));
-        drawing.draw_command(DrawCommand::LineTo(c));
-        drawing.draw_command(DrawCommand::LineTo(a));
+    let triangles = data
+        .iter_triangles()
+        .filter(|&(_, points)| !culling.cull(points));
+
+    if let Some(uvt_data) = uvt_data {
+        let uvt = parse_uvt_data(activation, uvt_data, data.num_vertices())?;
+        let triangles = triangles
+            .map(|(indices, points)| TexturedTriangle {
+                points,
+                uvt: indices.map(|i| uvt[i]),
+            })
+            .collect();
+        drawing.draw_textured_triangles(triangles);
+    } else {
+        drawing.draw_triangles(triangles.map(|(_, points)| points));
     }
 
     Ok(())
@@ -1284,18 +1330,18 @@ pub fn draw_graphics_data<'gc>(
     this: Value<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let this = this.as_object().unwrap();
+    let graphics = this.as_object().unwrap();
 
     if let Some(vector) = args
         .get_object(activation, 0, "graphicsData")?
         .as_vector_storage()
     {
-        let this = this.as_display_object().expect("Bad this");
+        let this = graphics.as_display_object().expect("Bad this");
 
         if let Some(mut drawing) = this.as_drawing() {
             for elem in vector.iter() {
                 if let Some(obj) = elem.as_object() {
-                    handle_igraphics_data(activation, &mut drawing, &obj)?;
+                    handle_igraphics_data(activation, graphics, &mut drawing, &obj)?;
                 }
             }
         };
@@ -1310,13 +1356,10 @@ pub fn line_bitmap_style<'gc>(
     this: Value<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let this = this.as_object().unwrap();
+    let graphics = this.as_object().unwrap();
 
-    if let Some(this) = this.as_display_object() {
-        let bitmap = args
-            .get_object(activation, 0, "bitmap")?
-            .as_bitmap_data()
-            .expect("Bitmap argument is ensured to be a BitmapData from actionscript");
+    if let Some(this) = graphics.as_display_object() {
+        let bitmap = args.get_object(activation, 0, "bitmap")?;
         let matrix = if let Some(matrix) = args.try_get_object(1) {
             Matrix::from(object_to_matrix(matrix))
         } else {
@@ -1326,20 +1369,13 @@ pub fn line_bitmap_style<'gc>(
         let is_repeating = args.get_bool(2);
         let is_smoothed = args.get_bool(3);
 
-        let handle = bitmap.bitmap_handle(activation.gc(), activation.context.renderer);
-
-        let bitmap = ruffle_render::bitmap::BitmapInfo {
-            handle,
-            width: bitmap.width(),
-            height: bitmap.height(),
-        };
         let scale_matrix = Matrix::scale(
             (Twips::TWIPS_PER_PIXEL as i16).into(),
             (Twips::TWIPS_PER_PIXEL as i16).into(),
         );
 
         if let Some(mut draw) = this.as_drawing() {
-            let id = draw.add_bitmap(bitmap);
+            let id = add_bitmap(activation, graphics, &mut draw, bitmap);
             draw.set_line_fill_style(FillStyle::Bitmap {
                 id,
                 matrix: matrix * scale_matrix,
@@ -1355,15 +1391,470 @@ pub fn line_bitmap_style<'gc>(
 /// Implements `Graphics.readGraphicsData`
 pub fn read_graphics_data<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Value<'gc>,
-    _args: &[Value<'gc>],
+    this: Value<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display.Graphics", "readGraphicsData");
+    let this = this.as_object().unwrap();
+    let recurse = args.get_bool(0);
+
+    let mut reader = GraphicsDataReader::default();
+    if let Some(this) = this.as_display_object() {
+        reader.read_display_object(activation, this, RenderMatrix::IDENTITY, recurse)?;
+    }
+    reader.end_stroke(activation)?;
+
     let value_type = activation.avm2().class_defs().igraphicsdata;
-    let new_storage = VectorStorage::new(0, false, Some(value_type));
+    let new_storage = VectorStorage::from_values(reader.data, false, Some(value_type));
     Ok(VectorObject::from_vector(new_storage, activation).into())
 }
 
+/// Where the bitmaps used by the bitmap fills of a shape come from.
+enum FillBitmaps<'gc> {
+    /// The bitmaps added with the `Graphics` API, kept in the `_bitmaps`
+    /// array of the `Graphics` object.
+    Graphics(Option<Object<'gc>>),
+
+    /// The bitmap characters of the movie which defined the shape.
+    Library(Arc<SwfMovie>),
+}
+
+impl<'gc> FillBitmaps<'gc> {
+    fn for_drawing(dobj: DisplayObject<'gc>) -> Self {
+        let graphics = dobj.object2().map(|object| match dobj {
+            DisplayObject::Graphic(_) => object.get_slot(shape_slots::_GRAPHICS),
+            _ => object.get_slot(sprite_slots::_GRAPHICS),
+        });
+        let bitmaps = graphics
+            .and_then(|graphics| graphics.as_object())
+            .and_then(|graphics| graphics.get_slot(graphics_slots::_BITMAPS).as_object());
+        Self::Graphics(bitmaps)
+    }
+
+    fn bitmap_data(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        id: CharacterId,
+    ) -> Option<Object<'gc>> {
+        match self {
+            Self::Graphics(bitmaps) => bitmaps
+                .as_ref()?
+                .as_array_storage()?
+                .get(id.into())?
+                .as_object(),
+            Self::Library(movie) => {
+                let Some(Character::Bitmap(bitmap)) = activation
+                    .context
+                    .library
+                    .library_for_movie_mut(movie.clone())
+                    .character_by_id(id)
+                else {
+                    return None;
+                };
+
+                let bitmap_data = fill_bitmap_data_from_symbol(activation, bitmap.compressed());
+                let bitmap_data_obj =
+                    BitmapDataObject::from_bitmap_data(activation.context, bitmap_data);
+                bitmap_data.init_object2(activation.gc(), bitmap_data_obj);
+                Some(bitmap_data_obj.into())
+            }
+        }
+    }
+}
+
+/// Builds the `IGraphicsData` objects describing the drawings of display
+/// objects, for `Graphics.readGraphicsData`.
+#[derive(Default)]
+struct GraphicsDataReader<'gc> {
+    data: Vec<Value<'gc>>,
+
+    /// Whether the last `GraphicsStroke` is still in effect.
+    is_stroking: bool,
+}
+
+impl<'gc> GraphicsDataReader<'gc> {
+    fn read_display_object(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        dobj: DisplayObject<'gc>,
+        matrix: RenderMatrix,
+        recurse: bool,
+    ) -> Result<(), Error<'gc>> {
+        // The drawing is cloned, as reading it runs the constructors of the
+        // `IGraphicsData` classes.
+        match dobj {
+            DisplayObject::Graphic(graphic) => {
+                let drawing = graphic.drawing().map(|drawing| drawing.clone());
+                if let Some(drawing) = drawing {
+                    let bitmaps = FillBitmaps::for_drawing(dobj);
+                    self.read_shape(activation, &drawing.distilled_shape(), &bitmaps, matrix)?;
+                } else {
+                    let bitmaps = FillBitmaps::Library(graphic.movie());
+                    let shape = DistilledShape::from(graphic.swf_shape());
+                    self.read_shape(activation, &shape, &bitmaps, matrix)?;
+                }
+            }
+            DisplayObject::MovieClip(clip) => {
+                let drawing = clip.drawing().map(|drawing| drawing.clone());
+                if let Some(drawing) = drawing {
+                    let bitmaps = FillBitmaps::for_drawing(dobj);
+                    self.read_shape(activation, &drawing.distilled_shape(), &bitmaps, matrix)?;
+                }
+            }
+            _ => {}
+        }
+
+        if recurse && let Some(container) = dobj.as_container() {
+            for child in container.iter_render_list() {
+                let matrix = matrix * child.base().matrix();
+                self.read_display_object(activation, child, matrix, true)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read_shape(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        shape: &DistilledShape<'_>,
+        bitmaps: &FillBitmaps<'gc>,
+        matrix: RenderMatrix,
+    ) -> Result<(), Error<'gc>> {
+        for path in &shape.paths {
+            match path {
+                DrawPath::Fill {
+                    style,
+                    commands,
+                    winding_rule,
+                } => {
+                    if !has_segments(commands) {
+                        continue;
+                    }
+                    let Some(fill) = read_fill(activation, style, bitmaps, matrix)? else {
+                        continue;
+                    };
+                    self.end_stroke(activation)?;
+                    self.data.push(fill);
+                    let path = read_path(activation, commands, *winding_rule, matrix)?;
+                    self.data.push(path);
+                    self.end_fill(activation)?;
+                }
+                DrawPath::Stroke {
+                    style, commands, ..
+                } => {
+                    if !has_segments(commands) {
+                        continue;
+                    }
+                    let Some(fill) = read_fill(activation, style.fill_style(), bitmaps, matrix)?
+                    else {
+                        continue;
+                    };
+                    let stroke = read_stroke(activation, style, fill)?;
+                    self.data.push(stroke);
+                    self.is_stroking = true;
+                    let path = read_path(activation, commands, FillRule::EvenOdd, matrix)?;
+                    self.data.push(path);
+                }
+                DrawPath::TexturedTriangles {
+                    bitmap_id,
+                    is_smoothed,
+                    is_repeating,
+                    triangles,
+                } => {
+                    let Some(bitmap_data) = bitmaps.bitmap_data(activation, *bitmap_id) else {
+                        continue;
+                    };
+                    self.end_stroke(activation)?;
+                    let fill = activation.avm2().classes().graphicsbitmapfill.construct(
+                        activation,
+                        &[
+                            bitmap_data.into(),
+                            Value::Null,
+                            (*is_repeating).into(),
+                            (*is_smoothed).into(),
+                        ],
+                    )?;
+                    self.data.push(fill);
+                    let path = read_textured_triangles(activation, triangles, matrix)?;
+                    self.data.push(path);
+                    self.end_fill(activation)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Ends the current stroke, so that it isn't applied to the following fills.
+    fn end_stroke(&mut self, activation: &mut Activation<'_, 'gc>) -> Result<(), Error<'gc>> {
+        if std::mem::take(&mut self.is_stroking) {
+            let stroke = activation
+                .avm2()
+                .classes()
+                .graphicsstroke
+                .construct(activation, &[f64::NAN.into()])?;
+            self.data.push(stroke);
+        }
+        Ok(())
+    }
+
+    fn end_fill(&mut self, activation: &mut Activation<'_, 'gc>) -> Result<(), Error<'gc>> {
+        let end_fill = activation
+            .avm2()
+            .classes()
+            .graphicsendfill
+            .construct(activation, &[])?;
+        self.data.push(end_fill);
+        Ok(())
+    }
+}
+
+/// Converts a fill style into an `IGraphicsFill`, or `None` if its bitmap
+/// can't be found.
+fn read_fill<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style: &FillStyle,
+    bitmaps: &FillBitmaps<'gc>,
+    matrix: RenderMatrix,
+) -> Result<Option<Value<'gc>>, Error<'gc>> {
+    let (gradient, gradient_type, focal_point) = match style {
+        FillStyle::Color(color) => {
+            let fill = activation.avm2().classes().graphicssolidfill.construct(
+                activation,
+                &[color.to_rgb().into(), (f64::from(color.a) / 255.0).into()],
+            )?;
+            return Ok(Some(fill));
+        }
+        FillStyle::Bitmap {
+            id,
+            matrix: bitmap_matrix,
+            is_smoothed,
+            is_repeating,
+        } => {
+            let Some(bitmap_data) = bitmaps.bitmap_data(activation, *id) else {
+                return Ok(None);
+            };
+            let bitmap_matrix =
+                matrix * RenderMatrix::from(*bitmap_matrix) * RenderMatrix::TWIPS_TO_PIXELS;
+            let bitmap_matrix = matrix_to_object(bitmap_matrix, activation)?;
+            let fill = activation.avm2().classes().graphicsbitmapfill.construct(
+                activation,
+                &[
+                    bitmap_data.into(),
+                    bitmap_matrix,
+                    (*is_repeating).into(),
+                    (*is_smoothed).into(),
+                ],
+            )?;
+            return Ok(Some(fill));
+        }
+        FillStyle::LinearGradient(gradient) => (gradient, "linear", 0.0),
+        FillStyle::RadialGradient(gradient) => (gradient, "radial", 0.0),
+        FillStyle::FocalGradient {
+            gradient,
+            focal_point,
+        } => (gradient, "radial", focal_point.to_f64()),
+    };
+
+    let colors: Vec<Value<'gc>> = gradient
+        .records
+        .iter()
+        .map(|record| record.color.to_rgb().into())
+        .collect();
+    let alphas: Vec<Value<'gc>> = gradient
+        .records
+        .iter()
+        .map(|record| (f64::from(record.color.a) / 255.0).into())
+        .collect();
+    let ratios: Vec<Value<'gc>> = gradient
+        .records
+        .iter()
+        .map(|record| record.ratio.into())
+        .collect();
+    let spread = match gradient.spread {
+        GradientSpread::Pad => "pad",
+        GradientSpread::Reflect => "reflect",
+        GradientSpread::Repeat => "repeat",
+    };
+    let interpolation = match gradient.interpolation {
+        GradientInterpolation::Rgb => "rgb",
+        GradientInterpolation::LinearRgb => "linearRGB",
+    };
+
+    let args = [
+        AvmString::new_utf8(activation.gc(), gradient_type).into(),
+        ArrayObject::from_storage(activation.context, ArrayStorage::from_args(&colors)).into(),
+        ArrayObject::from_storage(activation.context, ArrayStorage::from_args(&alphas)).into(),
+        ArrayObject::from_storage(activation.context, ArrayStorage::from_args(&ratios)).into(),
+        matrix_to_object(matrix * RenderMatrix::from(gradient.matrix), activation)?,
+        AvmString::new_utf8(activation.gc(), spread).into(),
+        AvmString::new_utf8(activation.gc(), interpolation).into(),
+        focal_point.into(),
+    ];
+    let fill = activation
+        .avm2()
+        .classes()
+        .graphicsgradientfill
+        .construct(activation, &args)?;
+    Ok(Some(fill))
+}
+
+fn read_stroke<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style: &LineStyle,
+    fill: Value<'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let scale_mode = match (style.allow_scale_x(), style.allow_scale_y()) {
+        (true, true) => "normal",
+        (true, false) => "horizontal",
+        (false, true) => "vertical",
+        (false, false) => "none",
+    };
+    let caps = match style.start_cap() {
+        LineCapStyle::None => "none",
+        LineCapStyle::Round => "round",
+        LineCapStyle::Square => "square",
+    };
+    let (joints, miter_limit) = match style.join_style() {
+        LineJoinStyle::Round => ("round", 3.0),
+        LineJoinStyle::Bevel => ("bevel", 3.0),
+        LineJoinStyle::Miter(miter_limit) => ("miter", miter_limit.to_f64()),
+    };
+
+    let args = [
+        style.width().to_pixels().into(),
+        style.is_pixel_hinted().into(),
+        AvmString::new_utf8(activation.gc(), scale_mode).into(),
+        AvmString::new_utf8(activation.gc(), caps).into(),
+        AvmString::new_utf8(activation.gc(), joints).into(),
+        miter_limit.into(),
+        fill,
+    ];
+    activation
+        .avm2()
+        .classes()
+        .graphicsstroke
+        .construct(activation, &args)
+}
+
+/// Whether the commands draw anything, as paths which only move are omitted.
+fn has_segments(commands: &[DrawCommand]) -> bool {
+    commands
+        .iter()
+        .any(|command| !matches!(command, DrawCommand::MoveTo(_)))
+}
+
+/// Converts draw commands into a `GraphicsPath`.
+fn read_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    commands: &[DrawCommand],
+    winding_rule: FillRule,
+    matrix: RenderMatrix,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut command_values: Vec<Value<'gc>> = Vec::with_capacity(commands.len());
+    let mut data: Vec<Value<'gc>> = Vec::new();
+    let push_point = |data: &mut Vec<Value<'gc>>, point: Point<Twips>| {
+        let point = matrix * point;
+        data.push(point.x.to_pixels().into());
+        data.push(point.y.to_pixels().into());
+    };
+
+    let mut is_moving = false;
+    for command in commands {
+        match command {
+            DrawCommand::MoveTo(point) => {
+                // Only the last of consecutive moves has an effect.
+                if is_moving {
+                    data.truncate(data.len() - 2);
+                } else {
+                    command_values.push(1.into());
+                }
+                push_point(&mut data, *point);
+            }
+            DrawCommand::LineTo(point) => {
+                command_values.push(2.into());
+                push_point(&mut data, *point);
+            }
+            DrawCommand::QuadraticCurveTo { control, anchor } => {
+                command_values.push(3.into());
+                push_point(&mut data, *control);
+                push_point(&mut data, *anchor);
+            }
+            DrawCommand::CubicCurveTo {
+                control_a,
+                control_b,
+                anchor,
+            } => {
+                command_values.push(6.into());
+                push_point(&mut data, *control_a);
+                push_point(&mut data, *control_b);
+                push_point(&mut data, *anchor);
+            }
+        }
+        is_moving = matches!(command, DrawCommand::MoveTo(_));
+    }
+
+    let commands = VectorStorage::from_values(
+        command_values,
+        false,
+        Some(activation.avm2().class_defs().int),
+    );
+    let commands = VectorObject::from_vector(commands, activation);
+    let data = VectorStorage::from_values(data, false, Some(activation.avm2().class_defs().number));
+    let data = VectorObject::from_vector(data, activation);
+    let winding = match winding_rule {
+        FillRule::EvenOdd => "evenOdd",
+        FillRule::NonZero => "nonZero",
+    };
+
+    let args = [
+        commands.into(),
+        data.into(),
+        AvmString::new_utf8(activation.gc(), winding).into(),
+    ];
+    activation
+        .avm2()
+        .classes()
+        .graphicspath
+        .construct(activation, &args)
+}
+
+/// Converts textured triangles into a `GraphicsTrianglePath` with UVT data.
+fn read_textured_triangles<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    triangles: &[TexturedTriangle],
+    matrix: RenderMatrix,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut vertices: Vec<Value<'gc>> = Vec::with_capacity(triangles.len() * 6);
+    let mut uvt_data: Vec<Value<'gc>> = Vec::with_capacity(triangles.len() * 9);
+    for triangle in triangles {
+        for (point, uvt) in triangle.points.iter().zip(&triangle.uvt) {
+            let point = matrix * *point;
+            vertices.push(point.x.to_pixels().into());
+            vertices.push(point.y.to_pixels().into());
+            uvt_data.extend(uvt.iter().map(|&value| f64::from(value).into()));
+        }
+    }
+
+    let number = activation.avm2().class_defs().number;
+    let vertices = VectorStorage::from_values(vertices, false, Some(number));
+    let vertices = VectorObject::from_vector(vertices, activation);
+    let uvt_data = VectorStorage::from_values(uvt_data, false, Some(number));
+    let uvt_data = VectorObject::from_vector(uvt_data, activation);
+
+    let args = [
+        vertices.into(),
+        Value::Null,
+        uvt_data.into(),
+        AvmString::new_utf8(activation.gc(), "none").into(),
+    ];
+    activation
+        .avm2()
+        .classes()
+        .graphicstrianglepath
+        .construct(activation, &args)
+}
+
 fn read_point<'gc>(
     activation: &mut Activation<'_, 'gc>,
     data: &VectorStorage<'gc>,
@@ -1478,15 +1969,52 @@ fn process_commands<'gc>(
     Ok(())
 }
 
+/// Adds the bitmap of a bitmap fill to a drawing, returning its id.
+///
+/// The `BitmapData` object is also kept by the `Graphics` object under the
+/// same id, so that `readGraphicsData` can return it.
+fn add_bitmap<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    graphics: Object<'gc>,
+    drawing: &mut Drawing,
+    bitmap_data: Object<'gc>,
+) -> u16 {
+    let bitmap = bitmap_data
+        .as_bitmap_data()
+        .expect("Bitmap argument is ensured to be a BitmapData from actionscript");
+    let handle = bitmap.bitmap_handle(activation.gc(), activation.context.renderer);
+    let id = drawing.add_bitmap(BitmapInfo {
+        handle,
+        width: bitmap.width(),
+        height: bitmap.height(),
+    });
+
+    let bitmaps = match graphics.get_slot(graphics_slots::_BITMAPS).as_object() {
+        Some(bitmaps) => bitmaps,
+        None => {
+            let bitmaps = ArrayObject::empty(activation.context).into();
+            graphics.set_slot_no_coerce(graphics_slots::_BITMAPS, bitmaps, activation.gc());
+            bitmaps
+        }
+    };
+    bitmaps
+        .as_array_storage_mut(activation.gc())
+        .expect("Graphics bitmaps are stored in an Array")
+        .set(id.into(), bitmap_data.into());
+
+    id
+}
+
 fn handle_igraphics_data<'gc>(
     activation: &mut Activation<'_, 'gc>,
+    graphics: Object<'gc>,
     drawing: &mut Drawing,
     obj: &Object<'gc>,
 ) -> Result<(), Error<'gc>> {
     let class = obj.instance_class();
 
     if class == activation.avm2().class_defs().graphicsbitmapfill {
-        let style = handle_bitmap_fill(activation, drawing, obj)?;
+        let style = handle_bitmap_fill(activation, graphics, drawing, obj)?;
         drawing.set_fill_style(Some(style));
     } else if class == activation.avm2().class_defs().graphicsendfill {
         drawing.set_fill_style(None);
@@ -1537,7 +2065,7 @@ fn handle_igraphics_data<'gc>(
                 let fill = obj.get_slot(graphics_stroke_slots::FILL).as_object();
 
                 if let Some(fill) = fill {
-                    handle_igraphics_fill(activation, drawing, &fill)?
+                    handle_igraphics_fill(activation, graphics, drawing, &fill)?
                 } else {
                     None
                 }
@@ -1611,23 +2139,6 @@ fn handle_graphics_triangle_path<'gc>(
         .as_object();
 
     if let Some(vertices) = vertices {
-        // FIXME Triangles should be drawn using non-zero winding rule.
-        avm2_stub_method!(
-            activation,
-            "flash.display.Graphics",
-            "drawGraphicsData",
-            "GraphicsTrianglePath winding behavior"
-        );
-
-        if uvt_data.is_some() {
-            avm2_stub_method!(
-                activation,
-                "flash.display.Graphics",
-                "drawGraphicsData",
-                "GraphicsTrianglePath with uvt data"
-            );
-        }
-
         draw_triangles_internal(
             activation,
             drawing,
@@ -1643,13 +2154,14 @@ fn handle_graphics_triangle_path<'gc>(
 
 fn handle_igraphics_fill<'gc>(
     activation: &mut Activation<'_, 'gc>,
+    graphics: Object<'gc>,
     drawing: &mut Drawing,
     obj: &Object<'gc>,
 ) -> Result<Option<FillStyle>, Error<'gc>> {
     let class = obj.instance_class();
 
     if class == activation.avm2().class_defs().graphicsbitmapfill {
-        let style = handle_bitmap_fill(activation, drawing, obj)?;
+        let style = handle_bitmap_fill(activation, graphics, drawing, obj)?;
         Ok(Some(style))
     } else if class == activation.avm2().class_defs().graphicsendfill {
         Ok(None)
@@ -1776,15 +2288,14 @@ fn handle_gradient_fill<'gc>(
 
 fn handle_bitmap_fill<'gc>(
     activation: &mut Activation<'_, 'gc>,
+    graphics: Object<'gc>,
     drawing: &mut Drawing,
     obj: &Object<'gc>,
 ) -> Result<FillStyle, Error<'gc>> {
     let bitmap_data = obj
         .get_slot(graphics_bitmap_fill_slots::BITMAP_DATA)
         .as_object()
-        .ok_or_else(|| make_error_2007(activation, "bitmap"))?
-        .as_bitmap_data()
-        .expect("Bitmap argument is ensured to be a BitmapData from actionscript");
+        .ok_or_else(|| make_error_2007(activation, "bitmap"))?;
 
     let matrix = obj
         .get_slot(graphics_bitmap_fill_slots::MATRIX)
@@ -1800,20 +2311,12 @@ fn handle_bitmap_fill<'gc>(
         .get_slot(graphics_bitmap_fill_slots::SMOOTH)
         .coerce_to_boolean();
 
-    let handle = bitmap_data.bitmap_handle(activation.gc(), activation.context.renderer);
-
-    let bitmap = ruffle_render::bitmap::BitmapInfo {
-        handle,
-        width: bitmap_data.width(),
-        height: bitmap_data.height(),
-    };
-
     let scale_matrix = Matrix::scale(
         (Twips::TWIPS_PER_PIXEL as i16).into(),
         (Twips::TWIPS_PER_PIXEL as i16).into(),
     );
 
-    let id = drawing.add_bitmap(bitmap);
+    let id = add_bitmap(activation, graphics, drawing, bitmap_data);
 
     let style = FillStyle::Bitmap {
         id,
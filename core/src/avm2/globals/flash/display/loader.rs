@@ -6,6 +6,7 @@ use crate::avm2::ClassObject;
 use crate::avm2::activation::Activation;
 use crate::avm2::error::make_error_2007;
 use crate::avm2::globals::flash::display::display_object::initialize_for_allocator;
+use crate::avm2::globals::flash::display::loader_info::loader_content;
 use crate::avm2::globals::slots::flash_display_loader as loader_slots;
 use crate::avm2::globals::slots::flash_net_url_request as url_request_slots;
 use crate::avm2::globals::slots::flash_net_url_request_header as url_request_header_slots;
@@ -15,10 +16,10 @@ use crate::avm2::object::TObject as _;
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::{Error, Object};
-use crate::avm2_stub_method;
 use crate::backend::navigator::{NavigationMethod, Request};
 use crate::display_object::LoaderDisplay;
 use crate::display_object::MovieClip;
+use crate::loader;
 use crate::loader::LoadManager;
 use crate::loader::MovieLoaderVMData;
 use crate::tag_utils::SwfMovie;
@@ -77,20 +78,69 @@ pub fn load<'gc>(
 
     let loader_info = loader_info.as_loader_info_object().unwrap();
 
-    if loader_info.init_event_fired() {
-        // FIXME: When calling load/loadBytes, then calling load/loadBytes again
-        // before the `init` event is fired, the first load is cancelled.
-        avm2_stub_method!(
-            activation,
-            "flash.display.Loader",
-            "load",
-            "reusing a Loader"
-        );
-    }
+    // Reusing a Loader cancels the load in progress, if any, and unloads
+    // whatever was already loaded.
+    activation
+        .context
+        .load_manager
+        .cancel_avm2_loads(loader_info);
+    loader::unload_avm2_loader(activation.context, loader_info, false);
+
+    let request = request_from_url_request(activation, url_request)?;
+
+    start_loading(activation, loader_info, request, context);
+
+    Ok(Value::Undefined)
+}
+
+/// Creates a new `Loader` loading the given request.
+///
+/// This is used for content loaded by the player itself, such as images
+/// embedded in text fields. If `check_policy_file` is set, the content is
+/// loaded as if with a `LoaderContext` that has `checkPolicyFile` set.
+pub fn create_loader<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    request: Request,
+    check_policy_file: bool,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let context = if check_policy_file {
+        let context = activation
+            .avm2()
+            .classes()
+            .loadercontext
+            .construct(activation, &[true.into()])?;
+        context.as_object()
+    } else {
+        None
+    };
+
+    let loader = activation
+        .avm2()
+        .classes()
+        .loader
+        .construct(activation, &[])?
+        .as_object()
+        .unwrap();
+
+    let loader_info = loader
+        .get_slot(loader_slots::_CONTENT_LOADER_INFO)
+        .as_object()
+        .unwrap()
+        .as_loader_info_object()
+        .unwrap();
+
+    start_loading(activation, loader_info, request, context);
 
-    // Unload the loader, in case something was already loaded.
-    loader_info.unload(activation.context);
+    Ok(loader)
+}
 
+/// Starts loading the given request into the `Loader` owning `loader_info`.
+fn start_loading<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    loader_info: LoaderInfoObject<'gc>,
+    request: Request,
+    context: Option<Object<'gc>>,
+) {
     // This is a dummy MovieClip, which will get overwritten in `Loader`
     let movie = &activation.context.root_swf;
     let content = MovieClip::new(
@@ -108,8 +158,6 @@ pub fn load<'gc>(
         activation.gc(),
     );
 
-    let request = request_from_url_request(activation, url_request)?;
-
     let loader_url = activation.caller_movie_or_root().url().to_string();
 
     let future = activation.context.load_manager.load_movie_into_clip(
@@ -127,8 +175,6 @@ pub fn load<'gc>(
         },
     );
     activation.context.navigator.spawn_future(future);
-
-    Ok(Value::Undefined)
 }
 
 pub fn request_from_url_request<'gc>(
@@ -250,19 +296,13 @@ pub fn load_bytes<'gc>(
 
     let loader_info = loader_info.as_loader_info_object().unwrap();
 
-    if loader_info.init_event_fired() {
-        // FIXME: When calling load/loadBytes, then calling load/loadBytes again
-        // before the `init` event is fired, the first load is cancelled.
-        avm2_stub_method!(
-            activation,
-            "flash.display.Loader",
-            "loadBytes",
-            "reusing a Loader"
-        );
-    }
-
-    // Unload the loader, in case something was already loaded.
-    loader_info.unload(activation.context);
+    // Reusing a Loader cancels the load in progress, if any, and unloads
+    // whatever was already loaded.
+    activation
+        .context
+        .load_manager
+        .cancel_avm2_loads(loader_info);
+    loader::unload_avm2_loader(activation.context, loader_info, false);
 
     // This is a dummy MovieClip, which will get overwritten in `Loader`
     let movie = &activation.context.root_swf;
@@ -301,6 +341,24 @@ pub fn load_bytes<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `Loader.content`.
+pub fn get_content<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let loader_info = this
+        .get_slot(loader_slots::_CONTENT_LOADER_INFO)
+        .as_object()
+        .unwrap();
+
+    let loader_info = loader_info.as_loader_info_object().unwrap();
+
+    loader_content(activation, "Loader.content", loader_info)
+}
+
 pub fn unload<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
@@ -308,8 +366,47 @@ pub fn unload<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.as_object().unwrap();
 
-    // TODO: Broadcast an "unload" event on the LoaderInfo
-    avm2_stub_method!(activation, "flash.display.Loader", "unload");
+    let loader_info = this
+        .get_slot(loader_slots::_CONTENT_LOADER_INFO)
+        .as_object()
+        .unwrap();
+
+    let loader_info = loader_info.as_loader_info_object().unwrap();
+
+    loader::unload_avm2_loader(activation.context, loader_info, false);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Loader.unloadAndStop`.
+///
+/// The `gc` parameter is ignored, as we can't force a garbage collection.
+pub fn unload_and_stop<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let loader_info = this
+        .get_slot(loader_slots::_CONTENT_LOADER_INFO)
+        .as_object()
+        .unwrap();
+
+    let loader_info = loader_info.as_loader_info_object().unwrap();
+
+    loader::unload_avm2_loader(activation.context, loader_info, true);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Loader.close`.
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
 
     let loader_info = this
         .get_slot(loader_slots::_CONTENT_LOADER_INFO)
@@ -318,7 +415,10 @@ pub fn unload<'gc>(
 
     let loader_info = loader_info.as_loader_info_object().unwrap();
 
-    loader_info.unload(activation.context);
+    activation
+        .context
+        .load_manager
+        .cancel_avm2_loads(loader_info);
 
     Ok(Value::Undefined)
 }
@@ -4,12 +4,18 @@ use crate::avm2::Error;
 use crate::avm2::activation::Activation;
 use crate::avm2::bytearray::Endian;
 use crate::avm2::error::make_error_2099;
-use crate::avm2::object::{DomainObject, LoaderStream, ScriptObject, TObject as _};
+use crate::avm2::globals::flash::system::security::{
+    check_content_access, scriptable_display_object,
+};
+use crate::avm2::object::{
+    DomainObject, LoaderInfoObject, LoaderStream, ScriptObject, TObject as _,
+};
 use crate::avm2::value::Value;
+use crate::avm2_stub_method;
 use crate::display_object::TDisplayObject;
 use crate::loader::ContentType;
+use crate::security::SecurityManager;
 use crate::string::AvmString;
-use crate::{avm2_stub_getter, avm2_stub_method};
 use std::sync::Arc;
 use swf::{Compression, write_swf};
 use url::Url;
@@ -48,6 +54,7 @@ pub fn get_application_domain<'gc>(
     if let Some(loader_stream) = this.as_loader_info_object().map(|o| o.loader_stream()) {
         match &*loader_stream {
             LoaderStream::NotYetLoaded(movie, _, _) => {
+                check_content_access(activation, "LoaderInfo.applicationDomain", movie)?;
                 let domain = activation
                     .context
                     .library
@@ -61,14 +68,21 @@ pub fn get_application_domain<'gc>(
                 }
             }
 
-            // A loaded SWF will always have an AVM2 domain present.
+            // A loaded SWF will always have an AVM2 domain present, unless its
+            // library was freed by unloading it.
             LoaderStream::Swf(movie, _) => {
+                check_content_access(activation, "LoaderInfo.applicationDomain", movie)?;
                 let domain = activation
                     .context
                     .library
-                    .library_for_movie_mut(movie.clone())
-                    .avm2_domain();
-                return Ok(DomainObject::from_domain(activation, domain).into());
+                    .library_for_movie(movie.clone())
+                    .and_then(|library| library.try_avm2_domain());
+
+                if let Some(domain) = domain {
+                    return Ok(DomainObject::from_domain(activation, domain).into());
+                } else {
+                    return Ok(Value::Null);
+                }
             }
         }
     }
@@ -130,24 +144,33 @@ pub fn get_bytes_loaded<'gc>(
 
 /// `content` getter
 pub fn get_content<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.as_object().unwrap();
 
     let loader_info = this.as_loader_info_object().unwrap();
+    loader_content(activation, "LoaderInfo.content", loader_info)
+}
+
+/// The content of `loader_info`, shared by `LoaderInfo.content` and
+/// `Loader.content`.
+pub fn loader_content<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    operation: &str,
+    loader_info: LoaderInfoObject<'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
     if !loader_info.expose_content() {
         return Ok(Value::Null);
     }
 
-    let loader_stream = loader_info.loader_stream();
-    match &*loader_stream {
-        LoaderStream::Swf(_, root) | LoaderStream::NotYetLoaded(_, Some(root), _) => {
-            Ok(root.object2_or_null())
-        }
-        _ => Ok(Value::Null),
-    }
+    let root = match &*loader_info.loader_stream() {
+        LoaderStream::Swf(_, root) | LoaderStream::NotYetLoaded(_, Some(root), _) => *root,
+        _ => return Ok(Value::Null),
+    };
+
+    scriptable_display_object(activation, operation, root)
 }
 
 /// `contentType` getter
@@ -221,11 +244,33 @@ pub fn get_height<'gc>(
 /// `isURLInaccessible` getter
 pub fn get_is_url_inaccessible<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Value<'gc>,
+    this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.LoaderInfo", "isURLInaccessible");
-    Ok(false.into())
+    let this = this.as_object().unwrap();
+
+    let loader_info = this.as_loader_info_object().unwrap();
+    Ok(is_url_inaccessible(activation, loader_info).into())
+}
+
+/// Whether the content was redirected to a URL that the caller may not see.
+///
+/// `LoaderInfo.url` then only exposes the domain of the final URL.
+fn is_url_inaccessible(activation: &Activation<'_, '_>, loader_info: LoaderInfoObject<'_>) -> bool {
+    if !loader_info.redirected() {
+        return false;
+    }
+
+    let loader_stream = loader_info.loader_stream();
+    let LoaderStream::Swf(movie, _) = &*loader_stream else {
+        return false;
+    };
+    let caller = activation.caller_movie_or_root();
+    activation
+        .context
+        .security
+        .check_content_access(&caller, movie)
+        .is_err()
 }
 
 /// `sameDomain` getter
@@ -236,19 +281,19 @@ pub fn get_same_domain<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.as_object().unwrap();
 
-    if let Some(loader_stream) = this.as_loader_info_object().map(|o| o.loader_stream()) {
-        match &*loader_stream {
-            LoaderStream::NotYetLoaded(_, _, _) => {
-                return Err(make_error_2099(activation));
-            }
-            LoaderStream::Swf(_root, _) => {
-                avm2_stub_getter!(activation, "flash.display.LoaderInfo", "sameDomain");
-                return Ok(false.into());
+    let loader_info = this.as_loader_info_object().unwrap();
+    let loader_stream = loader_info.loader_stream();
+    match &*loader_stream {
+        LoaderStream::NotYetLoaded(_, _, _) => Err(make_error_2099(activation)),
+        LoaderStream::Swf(root, _) => match loader_info.loader() {
+            Some(loader) => {
+                let parent_movie = loader.display_object().movie();
+                Ok(SecurityManager::same_domain(&parent_movie, root).into())
             }
-        }
+            // The root movie wasn't loaded by anyone else.
+            None => Ok(true.into()),
+        },
     }
-
-    Ok(Value::Undefined)
 }
 
 /// `childAllowsParent` getter
@@ -264,20 +309,10 @@ pub fn get_child_allows_parent<'gc>(
     match &*loader_stream {
         LoaderStream::NotYetLoaded(_, _, _) => Err(make_error_2099(activation)),
         LoaderStream::Swf(root, dobj) => {
-            // TODO: respect allowDomain() and polices.
-            avm2_stub_getter!(activation, "flash.display.LoaderInfo", "childAllowsParent");
-
             if let Some(loader) = loader_info.loader() {
-                let loader = loader.display_object();
-                let parent_movie = loader.movie();
-
-                if let Ok(child_url) = Url::parse(root.url())
-                    && let Ok(parent_url) = Url::parse(parent_movie.url())
-                    && child_url.host() == parent_url.host()
-                {
-                    return Ok(true.into());
-                }
-                Ok(false.into())
+                let parent_movie = loader.display_object().movie();
+                let security = &activation.context.security;
+                Ok(security.can_script(&parent_movie, root).into())
             } else {
                 // Only the root movie is LoaderStream::Swf but missing a loader.
                 // In that case, return true.
@@ -304,20 +339,10 @@ pub fn get_parent_allows_child<'gc>(
     match &*loader_stream {
         LoaderStream::NotYetLoaded(_, _, _) => Err(make_error_2099(activation)),
         LoaderStream::Swf(root, dobj) => {
-            // TODO: respect allowDomain() and polices.
-            avm2_stub_getter!(activation, "flash.display.LoaderInfo", "parentAllowsChild");
-
             if let Some(loader) = loader_info.loader() {
-                let loader = loader.display_object();
-                let parent_movie = loader.movie();
-
-                if let Ok(child_url) = Url::parse(root.url())
-                    && let Ok(parent_url) = Url::parse(parent_movie.url())
-                    && child_url.host() == parent_url.host()
-                {
-                    return Ok(true.into());
-                }
-                Ok(false.into())
+                let parent_movie = loader.display_object().movie();
+                let security = &activation.context.security;
+                Ok(security.can_script(root, &parent_movie).into())
             } else {
                 // See comment on childAllowsParent
                 assert!(
@@ -369,6 +394,14 @@ pub fn get_url<'gc>(
         let root = match &*loader_stream {
             LoaderStream::NotYetLoaded(root, _, _) | LoaderStream::Swf(root, _) => root,
         };
+
+        if is_url_inaccessible(activation, loader_info)
+            && let Ok(url) = Url::parse(root.url())
+        {
+            let domain = format!("{}/", url.origin().ascii_serialization());
+            return Ok(AvmString::new_utf8(activation.gc(), domain).into());
+        }
+
         return Ok(AvmString::new_utf8(activation.gc(), root.url()).into());
     }
 
@@ -0,0 +1,88 @@
+//! `flash.filesystem` namespace
+
+use crate::avm2::error::{make_error_2037, make_filesystem_error};
+use crate::avm2::object::{EventObject, Object};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Avm2, Error, Value};
+use crate::backend::filesystem::{FileSystemError, VirtualPath};
+use crate::backend::navigator::OwnedFuture;
+use crate::loader::Error as LoaderError;
+use gc_arena::{Collect, DynamicRoot, Gc, Rootable};
+
+pub mod file;
+pub mod file_stream;
+
+/// Returns the path of a `File`, or throws if it doesn't point anywhere.
+fn air_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    file: Object<'gc>,
+) -> Result<VirtualPath, Error<'gc>> {
+    file.as_file_reference()
+        .and_then(|file| file.air_path())
+        .ok_or_else(|| make_error_2037(activation))
+}
+
+/// Throws if content isn't allowed to modify `path`.
+fn check_writable<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    path: &VirtualPath,
+) -> Result<(), Error<'gc>> {
+    if path.directory().is_read_only() {
+        return Err(make_filesystem_error(
+            activation,
+            &FileSystemError::ReadOnly,
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct PendingEvents<'gc> {
+    target: Object<'gc>,
+    events: Vec<EventObject<'gc>>,
+}
+
+/// Implements `dispatchAsync` for all the classes of the package.
+///
+/// The events are dispatched once the current script is done running, so
+/// that content can add its listeners after starting an asynchronous operation.
+pub fn dispatch_async<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+    let events = args.get_object(activation, 0, "events")?;
+
+    let events = events
+        .as_array_storage()
+        .expect("AS code passes an Array")
+        .iter()
+        .filter_map(|event| event.and_then(|event| event.as_object()?.as_event_object()))
+        .collect();
+    let pending: DynamicRoot<Rootable![PendingEvents<'_>]> = activation.context.dynamic_root.stash(
+        activation.gc(),
+        Gc::new(
+            activation.gc(),
+            PendingEvents {
+                target: this,
+                events,
+            },
+        ),
+    );
+
+    let player = activation.context.player_handle();
+    let future: OwnedFuture<(), LoaderError> = Box::pin(async move {
+        player.lock().unwrap().update(|uc| {
+            let pending = uc.dynamic_root.fetch(&pending);
+            for &event in &pending.events {
+                Avm2::dispatch_event(uc, event, pending.target);
+            }
+        });
+        Ok(())
+    });
+    activation.context.navigator.spawn_future(future);
+
+    Ok(Value::Undefined)
+}
@@ -1,111 +1,170 @@
 package flash.filesystem {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
-
+    import flash.errors.IOError;
+    import flash.events.Event;
+    import flash.events.FileListEvent;
+    import flash.events.IOErrorEvent;
     import flash.net.FileReference;
 
     [API("661")]
     public class File extends FileReference {
-        private static var _applicationDirectory:File = null;
-        private static var _applicationStorageDirectory:File = null;
-        private static var _documentsDirectory:File = null;
-        private static var _userDirectory:File = null;
-
         public function File(path:String = null) {
-            stub_constructor("flash.filesystem.File");
+            if (path != null) {
+                this.init(path);
+            }
         }
 
+        // Points this File to `path`, which is either a URL or a native path.
+        private native function init(path:String):void;
+
         public static function get applicationDirectory():File {
-            stub_getter("flash.filesystem.File", "applicationDirectory");
+            return new File("app:/");
+        }
 
-            if (_applicationDirectory === null) {
-                _applicationDirectory = new File();
-            }
+        public static function get applicationStorageDirectory():File {
+            return new File("app-storage:/");
+        }
 
-            return _applicationDirectory;
+        public static function get desktopDirectory():File {
+            return new File("file:///desktop");
         }
 
-        public static function get applicationStorageDirectory():File {
-            stub_getter("flash.filesystem.File", "applicationStorageDirectory");
+        public static function get documentsDirectory():File {
+            return new File("file:///documents");
+        }
 
-            if (_applicationStorageDirectory === null) {
-                _applicationStorageDirectory = new File();
-            }
+        public static function get userDirectory():File {
+            return new File("file:///user");
+        }
 
-            return _applicationStorageDirectory;
+        public static function get lineEnding():String {
+            return "\n";
         }
 
-        public static function get documentsDirectory():File {
-            stub_getter("flash.filesystem.File", "documentsDirectory");
+        public static function get separator():String {
+            return "/";
+        }
 
-            if (_documentsDirectory === null) {
-                _documentsDirectory = new File();
-            }
+        public native function get exists():Boolean;
+
+        public native function get isDirectory():Boolean;
 
-            return _documentsDirectory;
+        public function get isHidden():Boolean {
+            return this.name.charAt(0) == ".";
         }
 
-        public static function get userDirectory():File {
-            stub_getter("flash.filesystem.File", "userDirectory");
+        public native function get nativePath():String;
 
-            if (_userDirectory === null) {
-                _userDirectory = new File();
-            }
+        public function set nativePath(value:String):void {
+            this.init(value);
+        }
 
-            return _userDirectory;
+        public function get parent():File {
+            var parentUrl:String = this.getParentUrl();
+            return parentUrl == null ? null : new File(parentUrl);
         }
 
-        public static function get separator():String {
-            stub_getter("flash.filesystem.File", "separator");
+        private native function getParentUrl():String;
 
-            return "/";
+        public native function get url():String;
+
+        public function set url(value:String):void {
+            this.init(value);
         }
 
         public function resolvePath(path:String):File {
-            stub_method("flash.filesystem.File", "resolvePath");
+            return new File(this.resolveUrl(path));
+        }
+
+        private native function resolveUrl(path:String):String;
+
+        public function canonicalize():void {
+            // Paths are always kept in their canonical form.
+        }
 
-            return new File();
+        public function clone():File {
+            return new File(this.url);
         }
 
-        public function get exists():Boolean {
-            stub_getter("flash.filesystem.File", "exists");
+        public native function createDirectory():void;
+
+        public function copyTo(newLocation:FileReference, overwrite:Boolean = false):void {
+            this.copyInternal(File(newLocation), overwrite, false);
+        }
 
-            return false;
+        public function copyToAsync(newLocation:FileReference, overwrite:Boolean = false):void {
+            this.runAsync(function():void {
+                copyInternal(File(newLocation), overwrite, false);
+            });
         }
 
-        public function get nativePath():String {
-            stub_getter("flash.filesystem.File", "nativePath");
+        public function moveTo(newLocation:FileReference, overwrite:Boolean = false):void {
+            this.copyInternal(File(newLocation), overwrite, true);
+        }
 
-            return "";
+        public function moveToAsync(newLocation:FileReference, overwrite:Boolean = false):void {
+            this.runAsync(function():void {
+                copyInternal(File(newLocation), overwrite, true);
+            });
         }
 
-        public function get url():String {
-            stub_getter("flash.filesystem.File", "url");
+        private native function copyInternal(newLocation:File, overwrite:Boolean, move:Boolean):void;
 
-            return "";
+        public function deleteDirectory(deleteDirectoryContents:Boolean = false):void {
+            this.deleteInternal(true, deleteDirectoryContents);
         }
 
-        public function canonicalize():void {
-            stub_method("flash.filesystem.File", "canonicalize");
+        public function deleteDirectoryAsync(deleteDirectoryContents:Boolean = false):void {
+            this.runAsync(function():void {
+                deleteInternal(true, deleteDirectoryContents);
+            });
         }
 
-        public function createDirectory():void {
-            stub_method("flash.filesystem.File", "createDirectory");
+        public function deleteFile():void {
+            this.deleteInternal(false, false);
         }
 
-        public function deleteDirectory(deleteDirectoryContents:Boolean = false):void {
-            stub_method("flash.filesystem.File", "deleteDirectory");
+        public function deleteFileAsync():void {
+            this.runAsync(function():void {
+                deleteInternal(false, false);
+            });
         }
 
+        private native function deleteInternal(directory:Boolean, recursive:Boolean):void;
+
         public function getDirectoryListing():Array {
-            stub_method("flash.filesystem.File", "getDirectoryListing");
+            var names:Array = this.listDirectory();
+            var files:Array = [];
+            for each (var name:String in names) {
+                files.push(this.resolvePath(name));
+            }
+            return files;
+        }
 
-            return [];
+        public function getDirectoryListingAsync():void {
+            var files:Array;
+            try {
+                files = this.getDirectoryListing();
+            } catch (e:IOError) {
+                this.dispatchAsync([new IOErrorEvent(IOErrorEvent.IO_ERROR, false, false, e.message, e.errorID)]);
+                return;
+            }
+            this.dispatchAsync([new FileListEvent(FileListEvent.DIRECTORY_LISTING, false, false, files)]);
         }
 
-        public function moveTo(newLocation:FileReference, overwrite:Boolean = false):void {
-            stub_method("flash.filesystem.File", "moveTo");
+        private native function listDirectory():Array;
+
+        // Runs `operation`, then reports its result with an event once the
+        // current script is done, like AIR's asynchronous operations do.
+        private function runAsync(operation:Function):void {
+            try {
+                operation();
+            } catch (e:IOError) {
+                this.dispatchAsync([new IOErrorEvent(IOErrorEvent.IO_ERROR, false, false, e.message, e.errorID)]);
+                return;
+            }
+            this.dispatchAsync([new Event(Event.COMPLETE)]);
         }
+
+        private native function dispatchAsync(events:Array):void;
     }
 }
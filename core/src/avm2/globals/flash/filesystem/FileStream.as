@@ -1,18 +1,315 @@
 package flash.filesystem {
-    import __ruffle__.stub_method;
-
+    import flash.errors.IOError;
+    import flash.events.Event;
     import flash.events.EventDispatcher;
+    import flash.events.IOErrorEvent;
+    import flash.events.OutputProgressEvent;
+    import flash.events.ProgressEvent;
+    import flash.net.ObjectEncoding;
+    import flash.utils.ByteArray;
+    import flash.utils.Endian;
+    import flash.utils.IDataInput;
+    import flash.utils.IDataOutput;
 
-    // TODO this class should implement IDataInput and IDataOutput
-
+    // The contents of the file are kept in `_buffer` while the stream is open.
+    // Every write is passed through to the file, so that other streams and
+    // `File` objects see it before this stream is closed.
     [API("661")]
-    public class FileStream extends EventDispatcher {
+    public class FileStream extends EventDispatcher implements IDataInput, IDataOutput {
+        private var _file:File = null;
+        private var _mode:String = null;
+        private var _async:Boolean = false;
+        private var _buffer:ByteArray = new ByteArray();
+
+        private var _endian:String = Endian.BIG_ENDIAN;
+        private var _objectEncoding:uint = ObjectEncoding.AMF3;
+        private var _readAhead:Number = Number.POSITIVE_INFINITY;
+
         public function FileStream() {
             super();
         }
 
+        public function open(file:File, fileMode:String):void {
+            this.openInternal(file, fileMode, false);
+        }
+
+        public function openAsync(file:File, fileMode:String):void {
+            try {
+                this.openInternal(file, fileMode, true);
+            } catch (e:IOError) {
+                this.dispatchAsync([new IOErrorEvent(IOErrorEvent.IO_ERROR, false, false, e.message, e.errorID)]);
+                return;
+            }
+
+            if (fileMode != FileMode.WRITE) {
+                var length:Number = this._buffer.length;
+                this.dispatchAsync([
+                    new ProgressEvent(ProgressEvent.PROGRESS, false, false, length, length),
+                    new Event(Event.COMPLETE)
+                ]);
+            }
+        }
+
+        private function openInternal(file:File, fileMode:String, async:Boolean):void {
+            if (this._file != null) {
+                this.close();
+            }
+
+            if (fileMode != FileMode.READ && fileMode != FileMode.WRITE &&
+                fileMode != FileMode.APPEND && fileMode != FileMode.UPDATE) {
+                throw new ArgumentError("Error #2008: Parameter fileMode must be one of the accepted values.", 2008);
+            }
+
+            this._buffer = this.openFile(file, fileMode);
+            this._buffer.endian = this._endian;
+            this._buffer.objectEncoding = this._objectEncoding;
+            if (fileMode == FileMode.APPEND) {
+                this._buffer.position = this._buffer.length;
+            }
+
+            this._file = file;
+            this._mode = fileMode;
+            this._async = async;
+        }
+
+        // Reads the contents of `file` if `fileMode` needs them, creating the
+        // file if it doesn't exist and `fileMode` allows writing.
+        private native function openFile(file:File, fileMode:String):ByteArray;
+
+        private native function writeFile(file:File, data:ByteArray):void;
+
+        private native function dispatchAsync(events:Array):void;
+
         public function close():void {
-            stub_method("flash.filesystem.FileStream", "close");
+            if (this._file == null) {
+                return;
+            }
+
+            this._file = null;
+            if (this._async) {
+                this.dispatchAsync([new Event(Event.CLOSE)]);
+            }
+        }
+
+        // Writes the buffer back to the file after it was changed.
+        private function flush():void {
+            try {
+                this.writeFile(this._file, this._buffer);
+            } catch (e:IOError) {
+                if (!this._async) {
+                    throw e;
+                }
+                this.dispatchAsync([new IOErrorEvent(IOErrorEvent.IO_ERROR, false, false, e.message, e.errorID)]);
+                return;
+            }
+            if (this._async) {
+                var length:Number = this._buffer.length;
+                this.dispatchAsync([new OutputProgressEvent(OutputProgressEvent.OUTPUT_PROGRESS, false, false, 0, length)]);
+            }
+        }
+
+        private function checkReadable():void {
+            if (this._file == null || this._mode == FileMode.WRITE || this._mode == FileMode.APPEND) {
+                throw new IOError("Error #2029: This URLStream object does not have a stream opened.", 2029);
+            }
+        }
+
+        private function checkWritable():void {
+            if (this._file == null || this._mode == FileMode.READ) {
+                throw new IOError("Error #2029: This URLStream object does not have a stream opened.", 2029);
+            }
+            if (this._mode == FileMode.APPEND) {
+                this._buffer.position = this._buffer.length;
+            }
+        }
+
+        public function get bytesAvailable():uint {
+            if (this._file == null || this._mode == FileMode.WRITE || this._mode == FileMode.APPEND) {
+                return 0;
+            }
+            return this._buffer.bytesAvailable;
+        }
+
+        public function get endian():String {
+            return this._endian;
+        }
+
+        public function set endian(value:String):void {
+            this._buffer.endian = value;
+            this._endian = value;
+        }
+
+        public function get objectEncoding():uint {
+            return this._objectEncoding;
+        }
+
+        public function set objectEncoding(value:uint):void {
+            this._buffer.objectEncoding = value;
+            this._objectEncoding = value;
+        }
+
+        public function get position():Number {
+            return this._buffer.position;
+        }
+
+        public function set position(value:Number):void {
+            this._buffer.position = value;
+        }
+
+        public function get readAhead():Number {
+            return this._readAhead;
+        }
+
+        public function set readAhead(value:Number):void {
+            this._readAhead = value;
+        }
+
+        public function truncate():void {
+            this.checkWritable();
+            this._buffer.length = this._buffer.position;
+            this.flush();
+        }
+
+        public function readBoolean():Boolean {
+            this.checkReadable();
+            return this._buffer.readBoolean();
+        }
+
+        public function readByte():int {
+            this.checkReadable();
+            return this._buffer.readByte();
+        }
+
+        public function readBytes(bytes:ByteArray, offset:uint = 0, length:uint = 0):void {
+            this.checkReadable();
+            this._buffer.readBytes(bytes, offset, length);
+        }
+
+        public function readDouble():Number {
+            this.checkReadable();
+            return this._buffer.readDouble();
+        }
+
+        public function readFloat():Number {
+            this.checkReadable();
+            return this._buffer.readFloat();
+        }
+
+        public function readInt():int {
+            this.checkReadable();
+            return this._buffer.readInt();
+        }
+
+        public function readMultiByte(length:uint, charSet:String):String {
+            this.checkReadable();
+            return this._buffer.readMultiByte(length, charSet);
+        }
+
+        public function readObject():* {
+            this.checkReadable();
+            return this._buffer.readObject();
+        }
+
+        public function readShort():int {
+            this.checkReadable();
+            return this._buffer.readShort();
+        }
+
+        public function readUnsignedByte():uint {
+            this.checkReadable();
+            return this._buffer.readUnsignedByte();
+        }
+
+        public function readUnsignedInt():uint {
+            this.checkReadable();
+            return this._buffer.readUnsignedInt();
+        }
+
+        public function readUnsignedShort():uint {
+            this.checkReadable();
+            return this._buffer.readUnsignedShort();
+        }
+
+        public function readUTF():String {
+            this.checkReadable();
+            return this._buffer.readUTF();
+        }
+
+        public function readUTFBytes(length:uint):String {
+            this.checkReadable();
+            return this._buffer.readUTFBytes(length);
+        }
+
+        public function writeBoolean(value:Boolean):void {
+            this.checkWritable();
+            this._buffer.writeBoolean(value);
+            this.flush();
+        }
+
+        public function writeByte(value:int):void {
+            this.checkWritable();
+            this._buffer.writeByte(value);
+            this.flush();
+        }
+
+        public function writeBytes(bytes:ByteArray, offset:uint = 0, length:uint = 0):void {
+            this.checkWritable();
+            this._buffer.writeBytes(bytes, offset, length);
+            this.flush();
+        }
+
+        public function writeDouble(value:Number):void {
+            this.checkWritable();
+            this._buffer.writeDouble(value);
+            this.flush();
+        }
+
+        public function writeFloat(value:Number):void {
+            this.checkWritable();
+            this._buffer.writeFloat(value);
+            this.flush();
+        }
+
+        public function writeInt(value:int):void {
+            this.checkWritable();
+            this._buffer.writeInt(value);
+            this.flush();
+        }
+
+        public function writeMultiByte(value:String, charSet:String):void {
+            this.checkWritable();
+            this._buffer.writeMultiByte(value, charSet);
+            this.flush();
+        }
+
+        public function writeObject(object:*):void {
+            this.checkWritable();
+            this._buffer.writeObject(object);
+            this.flush();
+        }
+
+        public function writeShort(value:int):void {
+            this.checkWritable();
+            this._buffer.writeShort(value);
+            this.flush();
+        }
+
+        public function writeUnsignedInt(value:uint):void {
+            this.checkWritable();
+            this._buffer.writeUnsignedInt(value);
+            this.flush();
+        }
+
+        public function writeUTF(value:String):void {
+            this.checkWritable();
+            this._buffer.writeUTF(value);
+            this.flush();
+        }
+
+        public function writeUTFBytes(value:String):void {
+            this.checkWritable();
+            this._buffer.writeUTFBytes(value);
+            this.flush();
         }
     }
 }
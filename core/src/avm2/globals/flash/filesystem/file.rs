@@ -0,0 +1,216 @@
+//! `flash.filesystem.File` native methods
+
+use crate::avm2::error::{Error2004Type, make_error_2004, make_filesystem_error};
+use crate::avm2::globals::flash::filesystem::{air_path, check_writable};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, ArrayObject, ArrayStorage, Error, Value};
+use crate::backend::filesystem::{FileSystemError, VirtualPath};
+use crate::string::AvmString;
+
+pub use crate::avm2::globals::flash::filesystem::dispatch_async;
+
+/// Implements the private `File.init`
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+    let path = args.get_string_non_null(activation, 0, "path")?;
+
+    let Some(path) = VirtualPath::parse(&path.to_utf8_lossy()) else {
+        return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+    };
+    this.as_file_reference().unwrap().init_from_air_path(path);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `File.exists`
+pub fn get_exists<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = air_path(activation, this.as_object().unwrap())?;
+
+    Ok(activation.context.filesystem.metadata(&path).is_ok().into())
+}
+
+/// Implements `File.isDirectory`
+pub fn get_is_directory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = air_path(activation, this.as_object().unwrap())?;
+
+    let is_directory = activation
+        .context
+        .filesystem
+        .metadata(&path)
+        .is_ok_and(|metadata| metadata.is_directory);
+    Ok(is_directory.into())
+}
+
+/// Implements `File.nativePath`
+pub fn get_native_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = air_path(activation, this.as_object().unwrap())?;
+
+    Ok(AvmString::new_utf8(activation.gc(), path.native_path()).into())
+}
+
+/// Implements `File.url`
+pub fn get_url<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = air_path(activation, this.as_object().unwrap())?;
+
+    Ok(AvmString::new_utf8(activation.gc(), path.url()).into())
+}
+
+/// Implements the private `File.getParentUrl`
+pub fn get_parent_url<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = air_path(activation, this.as_object().unwrap())?;
+
+    Ok(match path.parent() {
+        Some(parent) => AvmString::new_utf8(activation.gc(), parent.url()).into(),
+        None => Value::Null,
+    })
+}
+
+/// Implements the private `File.resolveUrl`
+pub fn resolve_url<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = air_path(activation, this.as_object().unwrap())?;
+    let relative = args
+        .get_string_non_null(activation, 0, "path")?
+        .to_utf8_lossy()
+        .into_owned();
+
+    let is_absolute = relative.starts_with(['/', '\\']) || relative.contains(':');
+    let resolved = if is_absolute {
+        VirtualPath::parse(&relative)
+            .ok_or_else(|| make_error_2004(activation, Error2004Type::ArgumentError))?
+    } else {
+        path.resolve(&relative)
+    };
+
+    Ok(AvmString::new_utf8(activation.gc(), resolved.url()).into())
+}
+
+/// Implements `File.createDirectory`
+pub fn create_directory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = air_path(activation, this.as_object().unwrap())?;
+    check_writable(activation, &path)?;
+
+    activation
+        .context
+        .filesystem
+        .create_directory(&path)
+        .map_err(|e| make_filesystem_error(activation, &e))?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements the private `File.copyInternal`, used by `copyTo` and `moveTo`
+pub fn copy_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let from = air_path(activation, this.as_object().unwrap())?;
+    let new_location = args.get_object(activation, 0, "newLocation")?;
+    let to = air_path(activation, new_location)?;
+    let overwrite = args.get_bool(1);
+    let is_move = args.get_bool(2);
+
+    check_writable(activation, &to)?;
+    if is_move {
+        check_writable(activation, &from)?;
+    }
+
+    let filesystem = &mut *activation.context.filesystem;
+    let result = filesystem.metadata(&from).and_then(|_| {
+        if to.starts_with(&from) || from.starts_with(&to) {
+            return Err(FileSystemError::InvalidDestination);
+        }
+        match filesystem.metadata(&to) {
+            Ok(_) if overwrite => filesystem.delete(&to, true)?,
+            Ok(_) => return Err(FileSystemError::AlreadyExists),
+            Err(_) => {}
+        }
+        if is_move {
+            filesystem.rename(&from, &to)
+        } else {
+            filesystem.copy(&from, &to)
+        }
+    });
+    result.map_err(|e| make_filesystem_error(activation, &e))?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements the private `File.deleteInternal`, used by `deleteFile` and `deleteDirectory`
+pub fn delete_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = air_path(activation, this.as_object().unwrap())?;
+    let directory = args.get_bool(0);
+    let recursive = args.get_bool(1);
+    check_writable(activation, &path)?;
+
+    let filesystem = &mut *activation.context.filesystem;
+    let result =
+        filesystem
+            .metadata(&path)
+            .and_then(|metadata| match (directory, metadata.is_directory) {
+                (true, false) => Err(FileSystemError::NotADirectory),
+                (false, true) => Err(FileSystemError::IsADirectory),
+                _ => filesystem.delete(&path, recursive),
+            });
+    result.map_err(|e| make_filesystem_error(activation, &e))?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements the private `File.listDirectory`
+pub fn list_directory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = air_path(activation, this.as_object().unwrap())?;
+
+    let names = activation
+        .context
+        .filesystem
+        .list_directory(&path)
+        .map_err(|e| make_filesystem_error(activation, &e))?;
+    let storage = ArrayStorage::from_iter(
+        names
+            .into_iter()
+            .map(|name| AvmString::new_utf8(activation.gc(), name)),
+    );
+
+    Ok(ArrayObject::from_storage(activation.context, storage).into())
+}
@@ -0,0 +1,62 @@
+//! `flash.filesystem.FileStream` native methods
+
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::error::make_filesystem_error;
+use crate::avm2::globals::flash::filesystem::{air_path, check_writable};
+use crate::avm2::object::ByteArrayObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Value};
+use crate::backend::filesystem::FileSystemError;
+
+pub use crate::avm2::globals::flash::filesystem::dispatch_async;
+
+/// Implements the private `FileStream.openFile`
+pub fn open_file<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let file = args.get_object(activation, 0, "file")?;
+    let path = air_path(activation, file)?;
+    let mode = args.get_string(activation, 1);
+
+    let is_read = &mode == b"read";
+    if !is_read {
+        check_writable(activation, &path)?;
+    }
+
+    let filesystem = &mut *activation.context.filesystem;
+    let result = if is_read {
+        filesystem.read(&path)
+    } else if &mode == b"write" {
+        // Opening a file for writing truncates it.
+        filesystem.write(&path, &[]).map(|_| Vec::new())
+    } else {
+        match filesystem.read(&path) {
+            Err(FileSystemError::NotFound) => filesystem.write(&path, &[]).map(|_| Vec::new()),
+            result => result,
+        }
+    };
+    let data = result.map_err(|e| make_filesystem_error(activation, &e))?;
+
+    let storage = ByteArrayStorage::from_vec(activation.context, data);
+    Ok(ByteArrayObject::from_storage(activation.context, storage).into())
+}
+
+/// Implements the private `FileStream.writeFile`
+pub fn write_file<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let file = args.get_object(activation, 0, "file")?;
+    let path = air_path(activation, file)?;
+    let data = args.get_object(activation, 1, "data")?;
+    check_writable(activation, &path)?;
+
+    let data = data.as_bytearray().expect("AS code passes a ByteArray");
+    let result = activation.context.filesystem.write(&path, data.bytes());
+    result.map_err(|e| make_filesystem_error(activation, &e))?;
+
+    Ok(Value::Undefined)
+}
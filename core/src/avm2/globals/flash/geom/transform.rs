@@ -8,7 +8,6 @@ use crate::avm2::parameters::ParametersExt;
 use crate::avm2::{Activation, Error, Object, TObject as _, Value};
 use crate::display_object::{BoundsMode, TDisplayObject};
 use crate::prelude::{DisplayObject, Matrix, Twips};
-use crate::{avm2_stub_getter, avm2_stub_method, avm2_stub_setter};
 use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::perspective_projection::PerspectiveProjection;
 use ruffle_render::quality::StageQuality;
@@ -59,7 +58,7 @@ pub fn get_matrix<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.as_object().unwrap();
 
-    if get_display_object(this).base().has_matrix3d_stub() {
+    if get_display_object(this).base().matrix3d().is_some() {
         Ok(Value::Null)
     } else {
         let matrix = matrix_from_transform_object(this);
@@ -76,11 +75,13 @@ pub fn set_matrix<'gc>(
 
     let dobj = get_display_object(this);
     let Some(obj) = args.try_get_object(0) else {
-        dobj.base().set_has_matrix3d_stub(true);
+        // Setting a null matrix turns the object into a 3D one.
+        dobj.base().ensure_matrix3d();
         return Ok(Value::Undefined);
     };
 
     let matrix = object_to_matrix(obj);
+    dobj.base().set_matrix3d(None);
     dobj.set_matrix(matrix);
     dobj.set_transformed_by_script(true);
     if let Some(parent) = dobj.parent() {
@@ -88,7 +89,6 @@ pub fn set_matrix<'gc>(
         // we only want to inform ancestors to avoid unnecessary invalidations for tx/ty
         parent.invalidate_cached_bitmap();
     }
-    dobj.base().set_has_matrix3d_stub(false);
     Ok(Value::Undefined)
 }
 
@@ -132,10 +132,8 @@ pub fn get_concatenated_matrix<'gc>(
     }
 }
 
-pub fn has_matrix3d_from_transform_object(transform_object: Object<'_>) -> bool {
-    get_display_object(transform_object)
-        .base()
-        .has_matrix3d_stub()
+pub fn matrix_3d_from_transform_object(transform_object: Object<'_>) -> Option<Matrix3D> {
+    get_display_object(transform_object).base().matrix3d()
 }
 
 pub fn matrix_from_transform_object(transform_object: Object<'_>) -> Matrix {
@@ -281,18 +279,9 @@ pub fn get_matrix_3d<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.as_object().unwrap();
 
-    // FIXME: This Matrix3D is generated from the 2D Matrix.
-    // It does not work when the matrix contains any transformation in 3D.
-    // Support native Matrix3D.
-    avm2_stub_getter!(activation, "flash.geom.Transform", "matrix3D");
-
-    let display_object = get_display_object(this);
-    if display_object.base().has_matrix3d_stub() {
-        let matrix = get_display_object(this).base().matrix();
-        let matrix3d = Matrix3D::from_matrix(matrix);
-        Ok(Matrix3DObject::new(activation.context, matrix3d).into())
-    } else {
-        Ok(Value::Null)
+    match get_display_object(this).base().matrix3d() {
+        Some(matrix3d) => Ok(Matrix3DObject::new(activation.context, matrix3d).into()),
+        None => Ok(Value::Null),
     }
 }
 
@@ -303,30 +292,18 @@ pub fn set_matrix_3d<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.as_object().unwrap();
 
-    // FIXME: This sets 2D Matrix generated from the given Matrix3D, ignoring 3D parameters.
-    // Support native Matrix3D.
-    avm2_stub_setter!(activation, "flash.geom.Transform", "matrix3D");
-
     let display_object = get_display_object(this);
+    let matrix3d = args
+        .try_get_object(0)
+        .map(|obj| obj.as_matrix3d_object().unwrap().matrix());
 
-    let (matrix, has_matrix3d) = {
-        match args.try_get_object(0) {
-            Some(obj) => {
-                let matrix3d = obj.as_matrix3d_object().unwrap().matrix();
-                let matrix = matrix3d.to_matrix();
-                (matrix, true)
-            }
-            None => (Matrix::IDENTITY, false),
-        }
-    };
-
-    display_object.set_matrix(matrix);
+    display_object.base().set_matrix3d(matrix3d);
+    display_object.set_transformed_by_script(true);
     if let Some(parent) = display_object.parent() {
         // Self-transform changes are automatically handled,
         // we only want to inform ancestors to avoid unnecessary invalidations for tx/ty
         parent.invalidate_cached_bitmap();
     }
-    display_object.base().set_has_matrix3d_stub(has_matrix3d);
 
     Ok(Value::Undefined)
 }
@@ -369,9 +346,6 @@ pub fn set_perspective_projection<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.as_object().unwrap();
 
-    // FIXME: Render with the given PerspectiveProjection.
-    avm2_stub_setter!(activation, "flash.geom.Transform", "perspectiveProjection");
-
     let perspective_projection = args
         .try_get_object(0)
         .map(|object| object_to_perspective_projection(object, activation))
@@ -382,6 +356,25 @@ pub fn set_perspective_projection<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Concatenates the 3D matrices of `display_object` and its ancestors, up to
+/// the Stage, without any perspective projection.
+fn concatenated_matrix_3d(display_object: DisplayObject<'_>) -> Matrix3D {
+    let mut node = Some(display_object);
+    let mut matrix = Matrix3D::IDENTITY;
+    while let Some(display_object) = node {
+        if display_object.as_stage().is_some() {
+            break;
+        }
+        let base = display_object.base();
+        let local = base
+            .matrix3d()
+            .unwrap_or_else(|| Matrix3D::from_matrix(base.matrix()));
+        matrix = local.multiply(&matrix);
+        node = display_object.parent();
+    }
+    matrix
+}
+
 pub fn get_relative_matrix_3d<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
@@ -389,14 +382,20 @@ pub fn get_relative_matrix_3d<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.as_object().unwrap();
 
-    let _relative_to = args.get_object(activation, 0, "relativeTo")?;
-
-    avm2_stub_method!(activation, "flash.geom.Transform", "getRelativeMatrix3D");
+    let relative_to = args.get_object(activation, 0, "relativeTo")?;
 
     let display_object = get_display_object(this);
-    if !display_object.base().has_matrix3d_stub() {
+    if display_object.base().matrix3d().is_none() {
         return Ok(Value::Null);
     }
 
-    Ok(Matrix3DObject::new(activation.context, Matrix3D::IDENTITY).into())
+    let Some(relative_to) = relative_to
+        .as_display_object()
+        .and_then(|relative_to| concatenated_matrix_3d(relative_to).invert())
+    else {
+        return Ok(Value::Null);
+    };
+    let matrix3d = relative_to.multiply(&concatenated_matrix_3d(display_object));
+
+    Ok(Matrix3DObject::new(activation.context, matrix3d).into())
 }
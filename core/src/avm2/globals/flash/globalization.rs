@@ -0,0 +1,90 @@
+//! `flash.globalization` namespace
+
+use crate::avm2::object::{Object, TObject as _, VectorObject};
+use crate::avm2::vector::VectorStorage;
+use crate::avm2::{Activation, Error, Value};
+use crate::globalization::{self, LocaleData, OperationStatus};
+use crate::string::AvmString;
+
+pub mod collator;
+pub mod currency_formatter;
+pub mod date_time_formatter;
+pub mod locale_id;
+pub mod number_formatter;
+pub mod string_tools;
+
+/// Slots that every locale-dependent class of `flash.globalization` has.
+#[derive(Clone, Copy)]
+struct LocaleSlots {
+    requested_locale_id_name: usize,
+    actual_locale_id_name: usize,
+    last_operation_status: usize,
+}
+
+/// Resolves the locale requested by a newly constructed object, and stores
+/// the name of the locale actually used.
+fn init_locale<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    slots: LocaleSlots,
+) -> Result<&'static LocaleData, Error<'gc>> {
+    let requested = get_string_slot(this, slots.requested_locale_id_name);
+    let default_name = activation.context.ui.language().to_string();
+    let (locale, status) = globalization::resolve_locale(&requested, &default_name);
+
+    let actual_name = AvmString::new_utf8(activation.gc(), locale.name);
+    this.set_slot(slots.actual_locale_id_name, actual_name.into(), activation)?;
+    set_status(activation, this, slots.last_operation_status, status)?;
+    Ok(locale)
+}
+
+/// Finds the locale data of an object, from the name of the locale it resolved to.
+fn locale_of(this: Object<'_>, slots: LocaleSlots) -> &'static LocaleData {
+    let name = get_string_slot(this, slots.actual_locale_id_name);
+    globalization::resolve_locale(&name, &name).0
+}
+
+fn set_status<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    slot: usize,
+    status: OperationStatus,
+) -> Result<(), Error<'gc>> {
+    let status = AvmString::new_utf8(activation.gc(), status.as_str());
+    this.set_slot(slot, status.into(), activation)
+}
+
+/// Reads a `String` slot, with `null` read as the empty string.
+fn get_string_slot(this: Object<'_>, slot: usize) -> String {
+    match this.get_slot(slot) {
+        Value::String(string) => string.to_utf8_lossy().into_owned(),
+        _ => String::new(),
+    }
+}
+
+fn string_vector<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    strings: impl IntoIterator<Item = impl AsRef<str>>,
+) -> Value<'gc> {
+    let storage = VectorStorage::from_values(
+        strings
+            .into_iter()
+            .map(|string| AvmString::new_utf8(activation.gc(), string.as_ref()).into())
+            .collect(),
+        false,
+        Some(activation.avm2().class_defs().string),
+    );
+    VectorObject::from_vector(storage, activation).into()
+}
+
+/// Implements `getAvailableLocaleIDNames` for all the classes of the package.
+pub fn get_available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(string_vector(
+        activation,
+        globalization::available_locale_names(),
+    ))
+}
@@ -1,99 +1,127 @@
 package flash.globalization {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
-    import __ruffle__.stub_setter;
-
     [API("667")]
     public final class Collator {
+        [Ruffle(NativeAccessible)]
         private var _requestedLocaleIDName:String;
 
+        [Ruffle(NativeAccessible)]
+        private var _actualLocaleIDName:String;
+
+        [Ruffle(NativeAccessible)]
+        private var _lastOperationStatus:String;
+
+        [Ruffle(NativeAccessible)]
+        private var _ignoreCase:Boolean;
+
+        [Ruffle(NativeAccessible)]
+        private var _ignoreCharacterWidth:Boolean;
+
+        [Ruffle(NativeAccessible)]
+        private var _ignoreDiacritics:Boolean;
+
+        [Ruffle(NativeAccessible)]
+        private var _ignoreKanaType:Boolean;
+
+        [Ruffle(NativeAccessible)]
+        private var _ignoreSymbols:Boolean;
+
+        [Ruffle(NativeAccessible)]
+        private var _numericComparison:Boolean;
+
+        private static function throwNonNull(name:String) {
+            throw new TypeError("Error #2007: Parameter " + name + " must be non-null.", 2007);
+        }
+
         public function Collator(requestedLocaleIDName:String, initialMode:String = "sorting") {
-            stub_constructor("flash.globalization.Collator");
+            if (requestedLocaleIDName == null) {
+                throwNonNull("requestedLocaleIDName");
+            }
+            if (initialMode == null) {
+                throwNonNull("initialMode");
+            }
+
             this._requestedLocaleIDName = requestedLocaleIDName;
+            this.init();
+
+            if (initialMode == CollatorMode.MATCHING) {
+                this._ignoreCase = true;
+                this._ignoreCharacterWidth = true;
+                this._ignoreDiacritics = true;
+                this._ignoreKanaType = true;
+            } else if (initialMode != CollatorMode.SORTING) {
+                throw new ArgumentError("Error #2008: Parameter initialMode must be one of the accepted values.", 2008);
+            }
         }
 
+        // Resolves the requested locale.
+        private native function init():void;
+
         public function get actualLocaleIDName():String {
-            stub_getter("flash.globalization.Collator", "actualLocaleIDName");
-            return "en-US";
+            return this._actualLocaleIDName;
         }
 
-        public function get ignoreCase():Boolean {
-            stub_getter("flash.globalization.Collator", "ignoreCase");
-            return false;
+        public function get lastOperationStatus():String {
+            return this._lastOperationStatus;
+        }
+
+        public function get requestedLocaleIDName():String {
+            return this._requestedLocaleIDName;
         }
 
+        public function get ignoreCase():Boolean {
+            return this._ignoreCase;
+        }
         public function set ignoreCase(value:Boolean):void {
-            stub_setter("flash.globalization.Collator", "ignoreCase");
+            this._ignoreCase = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get ignoreCharacterWidth():Boolean {
-            stub_getter("flash.globalization.Collator", "ignoreCharacterWidth");
-            return false;
+            return this._ignoreCharacterWidth;
         }
-
         public function set ignoreCharacterWidth(value:Boolean):void {
-            stub_setter("flash.globalization.Collator", "ignoreCharacterWidth");
+            this._ignoreCharacterWidth = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get ignoreDiacritics():Boolean {
-            stub_getter("flash.globalization.Collator", "ignoreDiacritics");
-            return false;
+            return this._ignoreDiacritics;
         }
-
         public function set ignoreDiacritics(value:Boolean):void {
-            stub_setter("flash.globalization.Collator", "ignoreDiacritics");
+            this._ignoreDiacritics = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get ignoreKanaType():Boolean {
-            stub_getter("flash.globalization.Collator", "ignoreKanaType");
-            return false;
+            return this._ignoreKanaType;
         }
-
         public function set ignoreKanaType(value:Boolean):void {
-            stub_setter("flash.globalization.Collator", "ignoreKanaType");
+            this._ignoreKanaType = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get ignoreSymbols():Boolean {
-            stub_getter("flash.globalization.Collator", "ignoreSymbols");
-            return false;
+            return this._ignoreSymbols;
         }
-
         public function set ignoreSymbols(value:Boolean):void {
-            stub_setter("flash.globalization.Collator", "ignoreSymbols");
-        }
-
-        public function get lastOperationStatus():String {
-            stub_getter("flash.globalization.Collator", "lastOperationStatus");
-            return "noError";
+            this._ignoreSymbols = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get numericComparison():Boolean {
-            stub_getter("flash.globalization.Collator", "numericComparison");
-            return false;
+            return this._numericComparison;
         }
-
         public function set numericComparison(value:Boolean):void {
-            stub_setter("flash.globalization.Collator", "numericComparison");
+            this._numericComparison = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
-        public function get requestedLocaleIDName():String {
-            return this._requestedLocaleIDName;
-        }
-
-        public function compare(string1:String, string2:String):int {
-            stub_method("flash.globalization.Collator", "compare");
-            return string1.localeCompare(string2);
-        }
+        public native function compare(string1:String, string2:String):int;
 
         public function equals(string1:String, string2:String):Boolean {
-            stub_method("flash.globalization.Collator", "equals");
             return this.compare(string1, string2) == 0;
         }
 
-        public static function getAvailableLocaleIDNames():Vector.<String> {
-            stub_method("flash.globalization.Collator", "getAvailableLocaleIDNames");
-            return new <String>["en-US"];
-        }
+        public static native function getAvailableLocaleIDNames():Vector.<String>;
     }
 }
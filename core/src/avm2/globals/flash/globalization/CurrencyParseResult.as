@@ -1,7 +1,10 @@
 package flash.globalization {
     [API("667")]
     public final class CurrencyParseResult {
+        [Ruffle(NativeAccessible)]
         private var _value:Number;
+
+        [Ruffle(NativeAccessible)]
         private var _currencyString:String;
 
         public function CurrencyParseResult(value:Number = NaN, symbol:String = "") {
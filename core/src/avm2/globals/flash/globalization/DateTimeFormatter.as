@@ -1,86 +1,102 @@
 package flash.globalization {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
-
     [API("667")]
     public final class DateTimeFormatter {
+        [Ruffle(NativeAccessible)]
+        private var _requestedLocaleIDName:String;
+
+        [Ruffle(NativeAccessible)]
+        private var _actualLocaleIDName:String;
+
+        [Ruffle(NativeAccessible)]
+        private var _lastOperationStatus:String;
+
+        [Ruffle(NativeAccessible)]
         private var _dateStyle:String;
-        private var _dateTimePattern:String;
-        private var _localeIDName:String;
+
+        [Ruffle(NativeAccessible)]
         private var _timeStyle:String;
 
+        [Ruffle(NativeAccessible)]
+        private var _dateTimePattern:String;
+
         private static function throwNonNull(name:String) {
             throw new TypeError("Error #2007: Parameter " + name + " must be non-null.", 2007);
         }
 
-        public function DateTimeFormatter(requestedLocaleIDName:String, dateStyle:String = "long", timeStyle:String = "long") {
-            stub_constructor("flash.globalization.DateTimeFormatter");
+        private static function checkStyle(name:String, style:String) {
+            if (style == null) {
+                throwNonNull(name);
+            }
 
+            if (style != DateTimeStyle.LONG && style != DateTimeStyle.MEDIUM && style != DateTimeStyle.SHORT && style != DateTimeStyle.NONE) {
+                throw new ArgumentError("Error #2008: Parameter " + name + " must be one of the accepted values.", 2008);
+            }
+        }
+
+        public function DateTimeFormatter(requestedLocaleIDName:String, dateStyle:String = "long", timeStyle:String = "long") {
             if (requestedLocaleIDName == null) {
                 throwNonNull("requestedLocaleIDName");
             }
 
-            this._localeIDName = requestedLocaleIDName;
-            this._dateTimePattern = "EEEE, MMMM d, yyyy h:mm:ss a";
+            this._requestedLocaleIDName = requestedLocaleIDName;
+            this.init();
+
+            var status:String = this._lastOperationStatus;
             this.setDateTimeStyles(dateStyle, timeStyle);
+            this._lastOperationStatus = status;
         }
 
+        // Resolves the requested locale.
+        private native function init():void;
+
+        // Sets the date-time pattern to the one of the current styles.
+        private native function updatePattern():void;
+
         public function get actualLocaleIDName():String {
-            stub_getter("flash.globalization.DateTimeFormatter", "actualLocaleIDName");
-            return this._localeIDName;
+            return this._actualLocaleIDName;
         }
 
         public function get lastOperationStatus():String {
-            stub_getter("flash.globalization.DateTimeFormatter", "lastOperationStatus");
-            return LastOperationStatus.NO_ERROR;
+            return this._lastOperationStatus;
         }
 
         public function get requestedLocaleIDName():String {
-            return this._localeIDName;
+            return this._requestedLocaleIDName;
         }
 
         public function format(dateTime:Date):String {
-            stub_method("flash.globalization.DateTimeFormatter", "format");
-
             if (dateTime == null) {
                 throwNonNull("dateTime");
             }
 
-            return dateTime.toString();
+            return this.formatInternal(dateTime, false);
         }
 
         public function formatUTC(dateTime:Date):String {
-            stub_method("flash.globalization.DateTimeFormatter", "formatUTC");
-
             if (dateTime == null) {
                 throwNonNull("dateTime");
             }
 
-            return dateTime.toUTCString();
+            return this.formatInternal(dateTime, true);
         }
 
-        public static function getAvailableLocaleIDNames():Vector.<String> {
-            stub_method("flash.globalization.DateTimeFormatter", "getAvailableLocaleIDNames");
-            return new <String>["en-US"];
-        }
+        private native function formatInternal(dateTime:Date, utc:Boolean):String;
+
+        public static native function getAvailableLocaleIDNames():Vector.<String>;
 
         public function getDateStyle():String {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             return this._dateStyle;
         }
 
         public function getDateTimePattern():String {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             return this._dateTimePattern;
         }
 
-        public function getFirstWeekday():int {
-            stub_method("flash.globalization.DateTimeFormatter", "getFirstWeekday");
-            return 0;
-        }
+        public native function getFirstWeekday():int;
 
         public function getMonthNames(nameStyle:String = "full", context:String = "standalone"):Vector.<String> {
-            stub_method("flash.globalization.DateTimeFormatter", "getMonthNames");
-
             if (nameStyle == null) {
                 throwNonNull("nameStyle");
             }
@@ -89,29 +105,15 @@ package flash.globalization {
                 throwNonNull("context");
             }
 
-            return new <String>[
-                "January",
-                "February",
-                "March",
-                "April",
-                "May",
-                "June",
-                "July",
-                "August",
-                "September",
-                "October",
-                "November",
-                "December"
-            ];
+            return this.getNames(true, nameStyle);
         }
 
         public function getTimeStyle():String {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             return this._timeStyle;
         }
 
         public function getWeekdayNames(nameStyle:String = "full", context:String = "standalone"):Vector.<String> {
-            stub_method("flash.globalization.DateTimeFormatter", "getWeekdayNames");
-
             if (nameStyle == null) {
                 throwNonNull("nameStyle");
             }
@@ -120,32 +122,30 @@ package flash.globalization {
                 throwNonNull("context");
             }
 
-            return new <String>["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"];
+            return this.getNames(false, nameStyle);
         }
 
-        public function setDateTimePattern(pattern:String):void {
-            stub_method("flash.globalization.DateTimeFormatter", "setDateTimePattern");
+        private native function getNames(months:Boolean, nameStyle:String):Vector.<String>;
 
+        public function setDateTimePattern(pattern:String):void {
             if (pattern == null) {
                 throwNonNull("pattern");
             }
 
             this._dateTimePattern = pattern;
+            this._dateStyle = DateTimeStyle.CUSTOM;
+            this._timeStyle = DateTimeStyle.CUSTOM;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function setDateTimeStyles(dateStyle:String, timeStyle:String):void {
-            stub_method("flash.globalization.DateTimeFormatter", "setDateTimeStyles");
-
-            if (dateStyle == null) {
-                throwNonNull("dateStyle");
-            }
-
-            if (timeStyle == null) {
-                throwNonNull("timeStyle");
-            }
+            checkStyle("dateStyle", dateStyle);
+            checkStyle("timeStyle", timeStyle);
 
             this._dateStyle = dateStyle;
             this._timeStyle = timeStyle;
+            this.updatePattern();
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
     }
 }
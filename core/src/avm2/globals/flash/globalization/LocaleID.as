@@ -1,24 +1,25 @@
 package flash.globalization {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
-
     import flash.globalization.LastOperationStatus;
 
     [API("667")]
     public final class LocaleID {
         public static const DEFAULT:String = "i-default";
 
+        [Ruffle(NativeAccessible)]
         private var _name:String;
 
+        [Ruffle(NativeAccessible)]
+        private var _lastOperationStatus:String = LastOperationStatus.NO_ERROR;
+
         public function LocaleID(name:String) {
-            stub_constructor("flash.globalization.LocaleID");
+            if (name == null) {
+                throw new TypeError("Error #2007: Parameter name must be non-null.", 2007);
+            }
             this._name = name;
         }
 
         public function get lastOperationStatus():String {
-            stub_getter("flash.globalization.LocaleID", "lastOperationStatus");
-            return LastOperationStatus.NO_ERROR;
+            return this._lastOperationStatus;
         }
 
         public function get name():String {
@@ -30,38 +31,55 @@ package flash.globalization {
             have:Vector.<String>,
             keyword:String = "userinterface"
         ):Vector.<String> {
-            stub_method("flash.globalization.LocaleID", "determinePreferredLocales");
-            return null;
-        }
+            if (want == null) {
+                throw new TypeError("Error #2007: Parameter want must be non-null.", 2007);
+            }
+            if (have == null) {
+                throw new TypeError("Error #2007: Parameter have must be non-null.", 2007);
+            }
 
-        public function getKeysAndValues():Object {
-            stub_method("flash.globalization.LocaleID", "getKeysAndValues");
-            return new Object();
-        }
+            var result:Vector.<String> = new Vector.<String>();
+            for each (var wanted:String in want) {
+                var wantedId:LocaleID = new LocaleID(wanted);
+                var language:String = wantedId.getLanguage().toLowerCase();
+                var region:String = wantedId.getRegion().toLowerCase();
 
-        public function getLanguage():String {
-            stub_method("flash.globalization.LocaleID", "getLanguage");
-            return this._name;
+                // Locales that match exactly come first, followed by those that
+                // only share the language.
+                var sameLanguage:Vector.<String> = new Vector.<String>();
+                for each (var available:String in have) {
+                    if (result.indexOf(available) != -1) {
+                        continue;
+                    }
+                    var availableId:LocaleID = new LocaleID(available);
+                    if (availableId.getLanguage().toLowerCase() != language) {
+                        continue;
+                    }
+                    if (availableId.getRegion().toLowerCase() == region) {
+                        result.push(available);
+                    } else if (sameLanguage.indexOf(available) == -1) {
+                        sameLanguage.push(available);
+                    }
+                }
+                for each (available in sameLanguage) {
+                    if (result.indexOf(available) == -1) {
+                        result.push(available);
+                    }
+                }
+            }
+            return result;
         }
 
-        public function getRegion():String {
-            stub_method("flash.globalization.LocaleID", "getRegion");
-            return "";
-        }
+        public native function getKeysAndValues():Object;
 
-        public function getScript():String {
-            stub_method("flash.globalization.LocaleID", "getScript");
-            return "";
-        }
+        public native function getLanguage():String;
 
-        public function getVariant():String {
-            stub_method("flash.globalization.LocaleID", "getVariant");
-            return "";
-        }
+        public native function getRegion():String;
 
-        public function isRightToLeft():Boolean {
-            stub_method("flash.globalization.LocaleID", "isRightToLeft");
-            return false;
-        }
+        public native function getScript():String;
+
+        public native function getVariant():String;
+
+        public native function isRightToLeft():Boolean;
     }
 }
@@ -1,8 +1,13 @@
 package flash.globalization {
     [API("667")]
     public final class NumberParseResult {
+        [Ruffle(NativeAccessible)]
         private var _endIndex:int;
+
+        [Ruffle(NativeAccessible)]
         private var _startIndex:int;
+
+        [Ruffle(NativeAccessible)]
         private var _value:Number;
 
         public function NumberParseResult(value:Number = NaN, startIndex:int = 0x7fffffff, endIndex:int = 0x7fffffff) {
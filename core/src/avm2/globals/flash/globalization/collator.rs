@@ -0,0 +1,57 @@
+//! `flash.globalization.Collator` native methods
+
+use crate::avm2::globals::flash::globalization::{LocaleSlots, init_locale, set_status};
+use crate::avm2::globals::slots::flash_globalization_collator as slots;
+use crate::avm2::object::TObject as _;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Value};
+use crate::globalization::{CollatorOptions, OperationStatus};
+
+const LOCALE_SLOTS: LocaleSlots = LocaleSlots {
+    requested_locale_id_name: slots::_REQUESTED_LOCALE_ID_NAME,
+    actual_locale_id_name: slots::_ACTUAL_LOCALE_ID_NAME,
+    last_operation_status: slots::_LAST_OPERATION_STATUS,
+};
+
+pub use crate::avm2::globals::flash::globalization::get_available_locale_id_names;
+
+/// Implements the private `Collator.init`
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+    init_locale(activation, this, LOCALE_SLOTS)?;
+    Ok(Value::Undefined)
+}
+
+/// Implements `Collator.compare`
+pub fn compare<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+    let string1 = args.get_string_non_null(activation, 0, "string1")?;
+    let string2 = args.get_string_non_null(activation, 1, "string2")?;
+
+    let flag = |slot| this.get_slot(slot).coerce_to_boolean();
+    let options = CollatorOptions {
+        ignore_case: flag(slots::_IGNORE_CASE),
+        ignore_character_width: flag(slots::_IGNORE_CHARACTER_WIDTH),
+        ignore_diacritics: flag(slots::_IGNORE_DIACRITICS),
+        ignore_kana_type: flag(slots::_IGNORE_KANA_TYPE),
+        ignore_symbols: flag(slots::_IGNORE_SYMBOLS),
+        numeric_comparison: flag(slots::_NUMERIC_COMPARISON),
+    };
+    let ordering = options.compare(&string1.to_utf8_lossy(), &string2.to_utf8_lossy());
+    set_status(
+        activation,
+        this,
+        slots::_LAST_OPERATION_STATUS,
+        OperationStatus::NoError,
+    )?;
+
+    Ok((ordering as i32).into())
+}
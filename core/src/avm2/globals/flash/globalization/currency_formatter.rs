@@ -0,0 +1,143 @@
+//! `flash.globalization.CurrencyFormatter` native methods
+
+use crate::avm2::globals::flash::globalization::{
+    LocaleSlots, get_string_slot, init_locale, set_status,
+};
+use crate::avm2::globals::slots::flash_globalization_currency_formatter as slots;
+use crate::avm2::globals::slots::flash_globalization_currency_parse_result as result_slots;
+use crate::avm2::object::{Object, TObject as _};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Value};
+use crate::globalization::{NumberFormat, OperationStatus};
+use crate::string::AvmString;
+
+const LOCALE_SLOTS: LocaleSlots = LocaleSlots {
+    requested_locale_id_name: slots::_REQUESTED_LOCALE_ID_NAME,
+    actual_locale_id_name: slots::_ACTUAL_LOCALE_ID_NAME,
+    last_operation_status: slots::_LAST_OPERATION_STATUS,
+};
+
+pub use crate::avm2::globals::flash::globalization::get_available_locale_id_names;
+
+/// Implements the private `CurrencyFormatter.init`
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+    let locale = init_locale(activation, this, LOCALE_SLOTS)?;
+
+    let strings = [
+        (slots::_DECIMAL_SEPARATOR, locale.decimal_separator),
+        (slots::_GROUPING_SEPARATOR, locale.grouping_separator),
+        (slots::_CURRENCY_ISO_CODE, locale.currency_iso_code),
+        (slots::_CURRENCY_SYMBOL, locale.currency_symbol),
+    ];
+    for (slot, string) in strings {
+        let string = AvmString::new_utf8(activation.gc(), string);
+        this.set_slot(slot, string.into(), activation)?;
+    }
+    this.set_slot(
+        slots::_FRACTIONAL_DIGITS,
+        locale.currency_fractional_digits.into(),
+        activation,
+    )?;
+    this.set_slot(
+        slots::_POSITIVE_CURRENCY_FORMAT,
+        locale.positive_currency_format.into(),
+        activation,
+    )?;
+    this.set_slot(
+        slots::_NEGATIVE_CURRENCY_FORMAT,
+        locale.negative_currency_format.into(),
+        activation,
+    )?;
+
+    Ok(Value::Undefined)
+}
+
+/// Calls `f` with the formatting settings of a `CurrencyFormatter`.
+fn with_number_format<R>(this: Object<'_>, f: impl FnOnce(&NumberFormat<'_>) -> R) -> R {
+    let decimal_separator = get_string_slot(this, slots::_DECIMAL_SEPARATOR);
+    let grouping_separator = get_string_slot(this, slots::_GROUPING_SEPARATOR);
+    let grouping_pattern = get_string_slot(this, slots::_GROUPING_PATTERN);
+    let negative_symbol = get_string_slot(this, slots::_NEGATIVE_SYMBOL);
+    f(&NumberFormat {
+        decimal_separator: &decimal_separator,
+        grouping_separator: &grouping_separator,
+        grouping_pattern: &grouping_pattern,
+        digits_type: this.get_slot(slots::_DIGITS_TYPE).as_u32(),
+        fractional_digits: this.get_slot(slots::_FRACTIONAL_DIGITS).as_i32(),
+        leading_zero: this.get_slot(slots::_LEADING_ZERO).coerce_to_boolean(),
+        trailing_zeros: this.get_slot(slots::_TRAILING_ZEROS).coerce_to_boolean(),
+        use_grouping: this.get_slot(slots::_USE_GROUPING).coerce_to_boolean(),
+        negative_symbol: &negative_symbol,
+    })
+}
+
+/// Implements `CurrencyFormatter.format`
+pub fn format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+    let value = args.get_f64(0);
+    let with_currency_symbol = args.get_bool(1);
+
+    // Without the currency symbol, the ISO code is used instead.
+    let symbol = if with_currency_symbol {
+        get_string_slot(this, slots::_CURRENCY_SYMBOL)
+    } else {
+        get_string_slot(this, slots::_CURRENCY_ISO_CODE)
+    };
+    let positive_format = this.get_slot(slots::_POSITIVE_CURRENCY_FORMAT).as_u32();
+    let negative_format = this.get_slot(slots::_NEGATIVE_CURRENCY_FORMAT).as_u32();
+
+    let formatted = with_number_format(this, |format| {
+        format.format_currency(value, &symbol, positive_format, negative_format)
+    });
+    set_status(
+        activation,
+        this,
+        slots::_LAST_OPERATION_STATUS,
+        OperationStatus::NoError,
+    )?;
+
+    Ok(AvmString::new_utf8(activation.gc(), formatted).into())
+}
+
+/// Implements the private `CurrencyFormatter.parseInternal`
+pub fn parse_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+    let input = args.get_string(activation, 0).to_utf8_lossy().into_owned();
+    let result = args.get_object(activation, 1, "result")?;
+
+    let symbol = get_string_slot(this, slots::_CURRENCY_SYMBOL);
+    let iso_code = get_string_slot(this, slots::_CURRENCY_ISO_CODE);
+    let negative_format = this.get_slot(slots::_NEGATIVE_CURRENCY_FORMAT).as_u32();
+
+    let parsed = with_number_format(this, |format| {
+        format.parse_currency(&input, &symbol, &iso_code, negative_format)
+    });
+    let status = if let Some((value, currency_string)) = parsed {
+        let currency_string = AvmString::new_utf8(activation.gc(), currency_string);
+        result.set_slot(result_slots::_VALUE, value.into(), activation)?;
+        result.set_slot(
+            result_slots::_CURRENCY_STRING,
+            currency_string.into(),
+            activation,
+        )?;
+        OperationStatus::NoError
+    } else {
+        OperationStatus::ParseError
+    };
+    set_status(activation, this, slots::_LAST_OPERATION_STATUS, status)?;
+
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,131 @@
+//! `flash.globalization.DateTimeFormatter` native methods
+
+use crate::avm2::error::make_error_2008;
+use crate::avm2::globals::flash::globalization::{
+    LocaleSlots, get_string_slot, init_locale, locale_of, set_status, string_vector,
+};
+use crate::avm2::globals::slots::flash_globalization_date_time_formatter as slots;
+use crate::avm2::object::TObject as _;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Value};
+use crate::globalization::{DateTimeStyle, NameStyle, OperationStatus};
+use crate::string::AvmString;
+
+const LOCALE_SLOTS: LocaleSlots = LocaleSlots {
+    requested_locale_id_name: slots::_REQUESTED_LOCALE_ID_NAME,
+    actual_locale_id_name: slots::_ACTUAL_LOCALE_ID_NAME,
+    last_operation_status: slots::_LAST_OPERATION_STATUS,
+};
+
+pub use crate::avm2::globals::flash::globalization::get_available_locale_id_names;
+
+/// Implements the private `DateTimeFormatter.init`
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+    init_locale(activation, this, LOCALE_SLOTS)?;
+    Ok(Value::Undefined)
+}
+
+/// Implements the private `DateTimeFormatter.updatePattern`
+pub fn update_pattern<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+    let locale = locale_of(this, LOCALE_SLOTS);
+
+    // The styles have already been validated.
+    let style = |slot| {
+        DateTimeStyle::from_name(&get_string_slot(this, slot)).unwrap_or(DateTimeStyle::Long)
+    };
+    let pattern = locale.date_time_pattern(style(slots::_DATE_STYLE), style(slots::_TIME_STYLE));
+    let pattern = AvmString::new_utf8(activation.gc(), pattern);
+    this.set_slot(slots::_DATE_TIME_PATTERN, pattern.into(), activation)?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements the private `DateTimeFormatter.formatInternal`
+pub fn format_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+    let date = args.get_object(activation, 0, "dateTime")?;
+    let utc = args.get_bool(1);
+
+    let locale = locale_of(this, LOCALE_SLOTS);
+    let pattern = get_string_slot(this, slots::_DATE_TIME_PATTERN);
+    let formatted = match date.as_date_object().and_then(|date| date.date_time()) {
+        Some(date) if utc => locale.format_date_time(&date, &pattern),
+        Some(date) => {
+            let timezone = activation.context.time.timezone();
+            locale.format_date_time(&date.with_timezone(&timezone), &pattern)
+        }
+        // Invalid dates can't be formatted.
+        None => String::new(),
+    };
+    set_status(
+        activation,
+        this,
+        slots::_LAST_OPERATION_STATUS,
+        OperationStatus::NoError,
+    )?;
+
+    Ok(AvmString::new_utf8(activation.gc(), formatted).into())
+}
+
+/// Implements `DateTimeFormatter.getFirstWeekday`
+pub fn get_first_weekday<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+    let locale = locale_of(this, LOCALE_SLOTS);
+    set_status(
+        activation,
+        this,
+        slots::_LAST_OPERATION_STATUS,
+        OperationStatus::NoError,
+    )?;
+
+    Ok(locale.first_weekday.into())
+}
+
+/// Implements the private `DateTimeFormatter.getNames`, used for both
+/// `getMonthNames` and `getWeekdayNames`
+pub fn get_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+    let months = args.get_bool(0);
+    let name_style = args.get_string(activation, 1);
+
+    let Some(name_style) = NameStyle::from_name(&name_style.to_utf8_lossy()) else {
+        return Err(make_error_2008(activation, "nameStyle"));
+    };
+
+    let locale = locale_of(this, LOCALE_SLOTS);
+    let names = if months {
+        locale.month_names(name_style)
+    } else {
+        locale.weekday_names(name_style)
+    };
+    set_status(
+        activation,
+        this,
+        slots::_LAST_OPERATION_STATUS,
+        OperationStatus::NoError,
+    )?;
+
+    Ok(string_vector(activation, names))
+}
@@ -0,0 +1,102 @@
+//! `flash.globalization.LocaleID` native methods
+
+use crate::avm2::globals::flash::globalization::{get_string_slot, set_status};
+use crate::avm2::globals::slots::flash_globalization_locale_id as slots;
+use crate::avm2::object::{ScriptObject, TObject as _};
+use crate::avm2::{Activation, Error, Value};
+use crate::globalization::{LocaleId, OperationStatus};
+use crate::string::AvmString;
+
+/// Parses the name of a `LocaleID`, and passes its components to `f`.
+fn with_locale_id<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    f: impl FnOnce(&mut Activation<'_, 'gc>, &LocaleId<'_>) -> Value<'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+    let name = get_string_slot(this, slots::_NAME);
+    let value = f(activation, &LocaleId::parse(&name));
+    set_status(
+        activation,
+        this,
+        slots::_LAST_OPERATION_STATUS,
+        OperationStatus::NoError,
+    )?;
+    Ok(value)
+}
+
+fn subtag<'gc>(activation: &mut Activation<'_, 'gc>, subtag: &str) -> Value<'gc> {
+    AvmString::new_utf8(activation.gc(), subtag).into()
+}
+
+/// Implements `LocaleID.getKeysAndValues`
+pub fn get_keys_and_values<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    with_locale_id(activation, this, |activation, id| {
+        let object = ScriptObject::new_object(activation.context);
+        for &(key, value) in &id.keywords {
+            let key = AvmString::new_utf8(activation.gc(), key);
+            let value = AvmString::new_utf8(activation.gc(), value);
+            object.set_dynamic_property(key, value.into(), activation.gc());
+        }
+        object.into()
+    })
+}
+
+/// Implements `LocaleID.getLanguage`
+pub fn get_language<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    with_locale_id(activation, this, |activation, id| {
+        subtag(activation, id.language)
+    })
+}
+
+/// Implements `LocaleID.getRegion`
+pub fn get_region<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    with_locale_id(activation, this, |activation, id| {
+        subtag(activation, id.region)
+    })
+}
+
+/// Implements `LocaleID.getScript`
+pub fn get_script<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    with_locale_id(activation, this, |activation, id| {
+        subtag(activation, id.script)
+    })
+}
+
+/// Implements `LocaleID.getVariant`
+pub fn get_variant<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    with_locale_id(activation, this, |activation, id| {
+        subtag(activation, id.variant)
+    })
+}
+
+/// Implements `LocaleID.isRightToLeft`
+pub fn is_right_to_left<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    with_locale_id(activation, this, |_activation, id| {
+        id.is_right_to_left().into()
+    })
+}
@@ -0,0 +1,152 @@
+//! `flash.globalization.NumberFormatter` native methods
+
+use crate::avm2::globals::flash::globalization::{
+    LocaleSlots, get_string_slot, init_locale, set_status,
+};
+use crate::avm2::globals::slots::flash_globalization_number_formatter as slots;
+use crate::avm2::globals::slots::flash_globalization_number_parse_result as result_slots;
+use crate::avm2::object::{Object, TObject as _};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Value};
+use crate::globalization::{NumberFormat, OperationStatus};
+use crate::string::AvmString;
+
+const LOCALE_SLOTS: LocaleSlots = LocaleSlots {
+    requested_locale_id_name: slots::_REQUESTED_LOCALE_ID_NAME,
+    actual_locale_id_name: slots::_ACTUAL_LOCALE_ID_NAME,
+    last_operation_status: slots::_LAST_OPERATION_STATUS,
+};
+
+pub use crate::avm2::globals::flash::globalization::get_available_locale_id_names;
+
+/// Implements the private `NumberFormatter.init`
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+    let locale = init_locale(activation, this, LOCALE_SLOTS)?;
+
+    let decimal_separator = AvmString::new_utf8(activation.gc(), locale.decimal_separator);
+    let grouping_separator = AvmString::new_utf8(activation.gc(), locale.grouping_separator);
+    this.set_slot(
+        slots::_DECIMAL_SEPARATOR,
+        decimal_separator.into(),
+        activation,
+    )?;
+    this.set_slot(
+        slots::_GROUPING_SEPARATOR,
+        grouping_separator.into(),
+        activation,
+    )?;
+    this.set_slot(
+        slots::_NEGATIVE_NUMBER_FORMAT,
+        locale.negative_number_format.into(),
+        activation,
+    )?;
+
+    Ok(Value::Undefined)
+}
+
+/// Calls `f` with the formatting settings of a `NumberFormatter`.
+fn with_number_format<R>(this: Object<'_>, f: impl FnOnce(&NumberFormat<'_>, u32) -> R) -> R {
+    let decimal_separator = get_string_slot(this, slots::_DECIMAL_SEPARATOR);
+    let grouping_separator = get_string_slot(this, slots::_GROUPING_SEPARATOR);
+    let grouping_pattern = get_string_slot(this, slots::_GROUPING_PATTERN);
+    let negative_symbol = get_string_slot(this, slots::_NEGATIVE_SYMBOL);
+    let format = NumberFormat {
+        decimal_separator: &decimal_separator,
+        grouping_separator: &grouping_separator,
+        grouping_pattern: &grouping_pattern,
+        digits_type: this.get_slot(slots::_DIGITS_TYPE).as_u32(),
+        fractional_digits: this.get_slot(slots::_FRACTIONAL_DIGITS).as_i32(),
+        leading_zero: this.get_slot(slots::_LEADING_ZERO).coerce_to_boolean(),
+        trailing_zeros: this.get_slot(slots::_TRAILING_ZEROS).coerce_to_boolean(),
+        use_grouping: this.get_slot(slots::_USE_GROUPING).coerce_to_boolean(),
+        negative_symbol: &negative_symbol,
+    };
+    let negative_number_format = this.get_slot(slots::_NEGATIVE_NUMBER_FORMAT).as_u32();
+    f(&format, negative_number_format)
+}
+
+/// Implements `NumberFormatter.formatNumber`
+pub fn format_number<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+    let value = args.get_f64(0);
+
+    let formatted = with_number_format(this, |format, negative_number_format| {
+        format.format_number(value, negative_number_format)
+    });
+    set_status(
+        activation,
+        this,
+        slots::_LAST_OPERATION_STATUS,
+        OperationStatus::NoError,
+    )?;
+
+    Ok(AvmString::new_utf8(activation.gc(), formatted).into())
+}
+
+/// Implements the private `NumberFormatter.parseInternal`
+pub fn parse_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+    let input = args.get_string(activation, 0).to_utf8_lossy().into_owned();
+    let result = args.get_object(activation, 1, "result")?;
+
+    let parsed = with_number_format(this, |format, negative_number_format| {
+        format.parse_number(&input, negative_number_format)
+    });
+    let status = if let Some(parsed) = parsed {
+        result.set_slot(result_slots::_VALUE, parsed.value.into(), activation)?;
+        result.set_slot(
+            result_slots::_START_INDEX,
+            (parsed.start_index as i32).into(),
+            activation,
+        )?;
+        result.set_slot(
+            result_slots::_END_INDEX,
+            (parsed.end_index as i32).into(),
+            activation,
+        )?;
+        OperationStatus::NoError
+    } else {
+        OperationStatus::ParseError
+    };
+    set_status(activation, this, slots::_LAST_OPERATION_STATUS, status)?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NumberFormatter.parseNumber`
+pub fn parse_number<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+    let input = args
+        .get_string_non_null(activation, 0, "parseString")?
+        .to_utf8_lossy()
+        .into_owned();
+
+    let value = with_number_format(this, |format, negative_number_format| {
+        format.parse_whole_number(&input, negative_number_format)
+    });
+    let status = if value.is_some() {
+        OperationStatus::NoError
+    } else {
+        OperationStatus::ParseError
+    };
+    set_status(activation, this, slots::_LAST_OPERATION_STATUS, status)?;
+
+    Ok(value.unwrap_or(f64::NAN).into())
+}
@@ -0,0 +1,71 @@
+//! `flash.globalization.StringTools` native methods
+
+use crate::avm2::globals::flash::globalization::{
+    LocaleSlots, get_string_slot, init_locale, set_status,
+};
+use crate::avm2::globals::slots::flash_globalization_string_tools as slots;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Value};
+use crate::globalization::{self, OperationStatus};
+use crate::string::AvmString;
+
+const LOCALE_SLOTS: LocaleSlots = LocaleSlots {
+    requested_locale_id_name: slots::_REQUESTED_LOCALE_ID_NAME,
+    actual_locale_id_name: slots::_ACTUAL_LOCALE_ID_NAME,
+    last_operation_status: slots::_LAST_OPERATION_STATUS,
+};
+
+pub use crate::avm2::globals::flash::globalization::get_available_locale_id_names;
+
+/// Implements the private `StringTools.init`
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+    init_locale(activation, this, LOCALE_SLOTS)?;
+    Ok(Value::Undefined)
+}
+
+/// Maps the case of a string, following the rules of the requested locale.
+fn map_case<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+    map: fn(&str, &str) -> String,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+    let string = args.get_string_non_null(activation, 0, "s")?;
+
+    // Case mapping only depends on the language, which we may not have any
+    // other data for.
+    let locale = get_string_slot(this, slots::_REQUESTED_LOCALE_ID_NAME);
+    let mapped = map(&string.to_utf8_lossy(), &locale);
+    set_status(
+        activation,
+        this,
+        slots::_LAST_OPERATION_STATUS,
+        OperationStatus::NoError,
+    )?;
+
+    Ok(AvmString::new_utf8(activation.gc(), mapped).into())
+}
+
+/// Implements `StringTools.toLowerCase`
+pub fn to_lower_case<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    map_case(activation, this, args, globalization::to_lower_case)
+}
+
+/// Implements `StringTools.toUpperCase`
+pub fn to_upper_case<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    map_case(activation, this, args, globalization::to_upper_case)
+}
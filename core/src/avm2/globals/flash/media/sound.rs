@@ -3,7 +3,10 @@
 use crate::avm2::Avm2;
 use crate::avm2::Error;
 use crate::avm2::activation::Activation;
-use crate::avm2::error::{make_error_2037, make_error_2084};
+use crate::avm2::error::{
+    Error2004Type, make_error_2004, make_error_2008, make_error_2029, make_error_2037,
+    make_error_2084, make_error_3767,
+};
 use crate::avm2::globals::slots::flash_net_url_request as url_request_slots;
 use crate::avm2::object::{
     EventObject, QueuedPlay, SoundChannelObject, SoundLoadingState, TObject as _,
@@ -14,7 +17,7 @@ use crate::backend::navigator::Request;
 use crate::character::Character;
 use crate::display_object::SoundTransform;
 use crate::{avm2_stub_getter, avm2_stub_method};
-use swf::{SoundEvent, SoundInfo};
+use swf::{AudioCompression, SoundEvent, SoundFormat, SoundInfo};
 
 pub use crate::avm2::object::sound_allocator;
 
@@ -73,25 +76,30 @@ pub fn get_bytes_total<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `Sound.bytesLoaded`
 pub fn get_bytes_loaded<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    // This should have a different value from bytesTotal when the sound is loading.
-    avm2_stub_getter!(activation, "flash.media.Sound", "bytesLoaded");
+    // Sounds are only registered once their whole data has been fetched, so nothing has
+    // been loaded before that.
     get_bytes_total(activation, this, args)
 }
 
 /// Implements `Sound.isBuffering`
 pub fn get_is_buffering<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Value<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.media.Sound", "isBuffering");
-    //STUB: We do not yet support network-loaded sounds.
-    Ok(false.into())
+    let this = this.as_object().unwrap();
+
+    if let Some(sound) = this.as_sound_object() {
+        return Ok(sound.is_buffering().into());
+    }
+
+    Ok(Value::Undefined)
 }
 
 /// Implements `Sound.isURLInaccessible`
@@ -107,12 +115,16 @@ pub fn get_is_url_inaccessible<'gc>(
 
 /// Implements `Sound.url`
 pub fn get_url<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Value<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.media.Sound", "url");
-    //STUB: We do not yet support network-loaded sounds.
+    let this = this.as_object().unwrap();
+
+    if let Some(url) = this.as_sound_object().and_then(|sound| sound.url()) {
+        return Ok(url.into());
+    }
+
     Ok(Value::Null)
 }
 
@@ -191,32 +203,66 @@ pub fn play<'gc>(
 /// `Sound.extract`
 pub fn extract<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Value<'gc>,
+    this: Value<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.media.Sound", "extract");
+    let this = this.as_object().unwrap().as_sound_object().unwrap();
 
-    let bytearray = args.try_get_object(0);
+    let bytearray = args.get_object(activation, 0, "target")?;
     let length = args.get_f64(1);
+    let start_position = args.get_f64(2);
 
-    if let Some(bytearray) = bytearray
-        && let Some(mut bytearray) = bytearray.as_bytearray_mut()
-    {
+    let Some(sound_handle) = this.sound_handle() else {
+        return Ok(0.into());
+    };
+
+    // A negative position continues from where the previous extraction ended.
+    let position = if start_position >= 0.0 {
+        start_position as usize
+    } else {
+        this.extract_position()
+    };
+    let num_frames = if length > 0.0 { length as usize } else { 0 };
+
+    let frames = activation
+        .context
+        .audio
+        .extract_sound(sound_handle, position, num_frames)
+        .unwrap_or_else(|e| {
+            tracing::error!("Sound.extract: Failed to decode sound: {e}");
+            vec![]
+        });
+
+    let mut bytearray = bytearray.as_bytearray_mut().unwrap();
+    for [left, right] in &frames {
+        bytearray
+            .write_float(*left)
+            .map_err(|e| e.to_avm(activation))?;
         bytearray
-            .write_bytes(vec![0u8; length.ceil() as usize].as_slice())
+            .write_float(*right)
             .map_err(|e| e.to_avm(activation))?;
     }
 
-    Ok(Value::Undefined)
+    this.set_extract_position(position + frames.len());
+
+    Ok((frames.len() as f64).into())
 }
 
 /// `Sound.close`
 pub fn close<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Value<'gc>,
+    this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.media.Sound", "close");
+    let this = this.as_object().unwrap().as_sound_object().unwrap();
+
+    if this.loading_state() != SoundLoadingState::Loading {
+        return Err(make_error_2029(activation));
+    }
+
+    // The download keeps running, but its result is discarded once it finishes.
+    this.set_loading_state(SoundLoadingState::Closed);
+
     Ok(Value::Undefined)
 }
 
@@ -249,6 +295,8 @@ pub fn load<'gc>(
         avm2_stub_method!(activation, "flash.media.Sound", "load", "with context");
     }
 
+    this.set_url(activation.gc(), Some(url));
+
     let future = crate::loader::load_sound_avm2(
         activation.context,
         this,
@@ -305,7 +353,7 @@ pub fn load_compressed_data_from_byte_array<'gc>(
 pub fn load_pcm_from_byte_array<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this_object = this.as_object().unwrap();
 
@@ -314,11 +362,67 @@ pub fn load_pcm_from_byte_array<'gc>(
         return Ok(Value::Undefined);
     }
 
-    // TODO Add proper implementation.
-    //   The following line ensures proper behavior
-    //   when calling load multiple times.
-    this.set_loading_state(SoundLoadingState::Loaded);
-    avm2_stub_method!(activation, "flash.media.Sound", "loadPCMFromByteArray");
+    let bytearray = args.get_object(activation, 0, "bytes")?;
+    let num_samples = args.get_u32(1);
+    let format = args.get_string(activation, 2);
+    let is_stereo = args.get_bool(3);
+    let sample_rate = args.get_f64(4);
+
+    let is_float = if &format == b"float" {
+        true
+    } else if &format == b"short" {
+        false
+    } else {
+        return Err(make_error_2008(activation, "format"));
+    };
+
+    if !(1.0..=f64::from(u16::MAX)).contains(&sample_rate) {
+        return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+    }
+
+    if f64::from(num_samples) / sample_rate > 1800.0 {
+        return Err(make_error_3767(activation));
+    }
+
+    let num_channels = if is_stereo { 2 } else { 1 };
+    let num_values = num_samples as usize * num_channels;
+    let bytearray = bytearray.as_bytearray().unwrap();
+    let handle = if is_float {
+        // Float samples are kept as they are, rather than converted to 16-bit PCM.
+        let samples = (0..num_values)
+            .map(|_| bytearray.read_float())
+            .collect::<Result<Vec<f32>, _>>()
+            .map_err(|e| e.to_avm(activation))?;
+        activation
+            .context
+            .audio
+            .register_float_pcm(&samples, sample_rate as u16, is_stereo)
+    } else {
+        let mut data = Vec::with_capacity(num_values * 2);
+        for _ in 0..num_values {
+            let sample = bytearray.read_short().map_err(|e| e.to_avm(activation))?;
+            data.extend_from_slice(&sample.to_le_bytes());
+        }
+
+        let sound = swf::Sound {
+            id: 0,
+            format: SoundFormat {
+                compression: AudioCompression::Uncompressed,
+                sample_rate: sample_rate as u16,
+                is_stereo,
+                is_16_bit: true,
+            },
+            num_samples,
+            data: &data,
+        };
+        activation.context.audio.register_sound(&sound)
+    };
+
+    let handle = handle.map_err(|e| {
+        Error::rust_error(format!("Failed to register sound from bytearray: {e:?}").into())
+    })?;
+
+    this.set_sound(activation.context, handle);
 
     Ok(Value::Undefined)
 }
@@ -19,13 +19,9 @@ package flash.net {
 
         public function NetStream(connection:NetConnection, peer:String = CONNECT_TO_FMS) {}
 
-        public function appendBytes(bytes:ByteArray) {
-            stub_method("flash.net.NetStream", "appendBytes");
-        }
+        public native function appendBytes(bytes:ByteArray);
 
-        public function appendBytesAction(action:String) {
-            stub_method("flash.net.NetStream", "appendBytesAction");
-        }
+        public native function appendBytesAction(action:String);
 
         public function attach(connection:NetConnection) {
             stub_method("flash.net.NetStream", "attach");
@@ -133,10 +129,7 @@ package flash.net {
             stub_setter("flash.net.NetStream", "backBufferTime");
         }
 
-        public function get bufferLength():Number {
-            stub_getter("flash.net.NetStream", "bufferLength");
-            return 0.0;
-        }
+        public native function get bufferLength():Number;
 
         public function get bufferTime():Number {
             stub_getter("flash.net.NetStream", "bufferTime");
@@ -1,9 +1,13 @@
 use crate::avm2::bytearray::ByteArrayStorage;
-use crate::avm2::error::{make_error_2037, make_error_2097, make_error_2174};
+use crate::avm2::error::{
+    filesystem_error_details, make_error_2037, make_error_2097, make_error_2174,
+    make_filesystem_error,
+};
 use crate::avm2::globals::slots::flash_net_file_filter as file_filter_slots;
 use crate::avm2::object::{ByteArrayObject, DateObject, FileReference};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::{Activation, Avm2, Error, EventObject, TObject as _, Value};
+use crate::backend::filesystem::FileSystemError;
 use crate::backend::ui::FileFilter;
 use crate::string::AvmString;
 
@@ -27,6 +31,18 @@ pub fn get_creation_date<'gc>(
                 Value::Null
             }
         }
+        FileReference::AirFile(ref path) => {
+            let metadata = activation
+                .context
+                .filesystem
+                .metadata(path)
+                .map_err(|e| make_filesystem_error(activation, &e))?;
+            if let Some(time) = metadata.creation_time {
+                DateObject::from_date_time(activation.context, time).into()
+            } else {
+                Value::Null
+            }
+        }
     };
 
     Ok(creation_date)
@@ -47,6 +63,13 @@ pub fn get_data<'gc>(
             let storage = ByteArrayStorage::from_vec(activation.context, bytes.to_vec());
             ByteArrayObject::from_storage(activation.context, storage)
         }
+        FileReference::AirFile(ref path) if this.loaded() => {
+            let Ok(bytes) = activation.context.filesystem.read(path) else {
+                return Ok(Value::Null);
+            };
+            let storage = ByteArrayStorage::from_vec(activation.context, bytes);
+            ByteArrayObject::from_storage(activation.context, storage)
+        }
         // Contrary to other getters `data` will return null instead of throwing.
         _ => return Ok(Value::Null),
     };
@@ -72,6 +95,18 @@ pub fn get_modification_date<'gc>(
                 Value::Null
             }
         }
+        FileReference::AirFile(ref path) => {
+            let metadata = activation
+                .context
+                .filesystem
+                .metadata(path)
+                .map_err(|e| make_filesystem_error(activation, &e))?;
+            if let Some(time) = metadata.modification_time {
+                DateObject::from_date_time(activation.context, time).into()
+            } else {
+                Value::Null
+            }
+        }
     };
 
     Ok(modification_date)
@@ -92,6 +127,9 @@ pub fn get_name<'gc>(
             let name = selection.file_name();
             AvmString::new_utf8(activation.gc(), name).into()
         }
+        FileReference::AirFile(ref path) => {
+            AvmString::new_utf8(activation.gc(), path.name()).into()
+        }
     };
 
     Ok(name)
@@ -109,6 +147,14 @@ pub fn get_size<'gc>(
     let size = match *this.file_reference() {
         FileReference::None => return Err(make_error_2037(activation)),
         FileReference::FileDialogSelection(ref selection) => selection.size().unwrap_or(0),
+        FileReference::AirFile(ref path) => {
+            activation
+                .context
+                .filesystem
+                .metadata(path)
+                .map_err(|e| make_filesystem_error(activation, &e))?
+                .size
+        }
     };
 
     Ok(Value::Number(size as f64))
@@ -129,6 +175,10 @@ pub fn get_type<'gc>(
             let type_ = selection.file_type().unwrap_or_default();
             AvmString::new_utf8(activation.gc(), type_).into()
         }
+        FileReference::AirFile(ref path) => match path.extension() {
+            Some(extension) => AvmString::new_utf8(activation.gc(), format!(".{extension}")).into(),
+            None => Value::Null,
+        },
     };
 
     Ok(type_)
@@ -217,6 +267,21 @@ pub fn load<'gc>(
     let size = match *this.file_reference() {
         FileReference::None => return Err(make_error_2037(activation)),
         FileReference::FileDialogSelection(ref selection) => selection.size().unwrap_or(0),
+        FileReference::AirFile(ref path) => match activation.context.filesystem.metadata(path) {
+            Ok(metadata) if !metadata.is_directory => metadata.size,
+            Ok(_) => {
+                return Err(make_filesystem_error(
+                    activation,
+                    &FileSystemError::IsADirectory,
+                ));
+            }
+            Err(e) => {
+                let (code, message) = filesystem_error_details(&e);
+                let io_error_evt = EventObject::io_error_event(activation, &message, code);
+                Avm2::dispatch_event(activation.context, io_error_evt, this.into());
+                return Ok(Value::Undefined);
+            }
+        },
     };
 
     let size = size as usize;
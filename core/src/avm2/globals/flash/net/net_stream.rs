@@ -1,9 +1,70 @@
-use crate::avm2::error::{Error2004Type, make_error_2004};
+use crate::avm2::error::{Error2004Type, make_error_2004, make_error_2008};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::{Activation, Error, Value};
+use crate::streams::AppendBytesAction;
 
 pub use crate::avm2::object::netstream_allocator as net_stream_allocator;
 
+pub fn append_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(ns) = this.as_netstream() {
+        let bytearray = args.get_object(activation, 0, "bytes")?;
+        let mut data = bytearray
+            .as_bytearray()
+            .expect("Parameter must be a bytearray!")
+            .bytes()
+            .to_vec();
+
+        ns.append_bytes(activation.context, &mut data);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn append_bytes_action<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(ns) = this.as_netstream() {
+        let action = args.get_string(activation, 0);
+        let action = if &action == b"resetBegin" {
+            AppendBytesAction::ResetBegin
+        } else if &action == b"resetSeek" {
+            AppendBytesAction::ResetSeek
+        } else if &action == b"endSequence" {
+            AppendBytesAction::EndSequence
+        } else {
+            return Err(make_error_2008(activation, "action"));
+        };
+
+        ns.append_bytes_action(activation.context, action);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_buffer_length<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(ns) = this.as_netstream() {
+        return Ok((ns.buffer_length() / 1000.0).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_bytes_loaded<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
@@ -40,9 +101,15 @@ pub fn play<'gc>(
     let this = this.as_object().unwrap();
 
     if let Some(ns) = this.as_netstream() {
+        if matches!(args.get_optional(0), Some(Value::Null)) {
+            // `play(null)` enables data generation mode, where the movie feeds
+            // the stream itself with `appendBytes`.
+            ns.play_data_generation(activation.context);
+            return Ok(Value::Undefined);
+        }
+
         let name = args
             .get_optional(0)
-            .filter(|v| !matches!(v, Value::Null))
             .map(|v| v.coerce_to_string(activation))
             .transpose()?;
 
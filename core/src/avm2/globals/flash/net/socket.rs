@@ -42,11 +42,22 @@ pub fn connect<'gc>(
     let port = args.get_u32(1);
     let port: u16 = port.try_into().map_err(|_| make_error_2003(activation))?;
 
+    let movie = activation.caller_movie_or_root();
     let UpdateContext {
-        sockets, navigator, ..
+        sockets,
+        navigator,
+        security,
+        ..
     } = activation.context;
 
-    sockets.connect_avm2(*navigator, socket, host.to_utf8_lossy().into_owned(), port);
+    sockets.connect_avm2(
+        *navigator,
+        security,
+        movie,
+        socket,
+        host.to_utf8_lossy().into_owned(),
+        port,
+    );
 
     Ok(Value::Undefined)
 }
@@ -2,9 +2,14 @@
 
 use crate::avm2::Error;
 use crate::avm2::activation::Activation;
+use crate::avm2::error::{make_error_2052, make_error_2121, make_error_2122, make_error_2123};
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2_stub_method;
+use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::security::{self, AccessDenied};
 use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
 use url::Url;
 
 use ruffle_common::sandbox::SandboxType;
@@ -42,7 +47,7 @@ pub fn get_sandbox_type<'gc>(
     let movie = activation
         .caller_movie()
         .expect("Caller movie expected for sandboxType");
-    let sandbox_type = match movie.sandbox_type() {
+    let sandbox_type = match activation.context.security.sandbox_type(&movie) {
         SandboxType::Remote => "remote",
         SandboxType::LocalWithFile => "localWithFile",
         SandboxType::LocalWithNetwork => "localWithNetwork",
@@ -55,30 +60,125 @@ pub fn get_sandbox_type<'gc>(
 pub fn allow_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Value<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "allowDomain");
+    allow_domains(activation, args, false)?;
     Ok(Value::Undefined)
 }
 
 pub fn allow_insecure_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Value<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "allowInsecureDomain");
+    allow_domains(activation, args, true)?;
     Ok(Value::Undefined)
 }
 
+/// Grants the given domains access to the calling movie.
+fn allow_domains<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    domains: &[Value<'gc>],
+    insecure: bool,
+) -> Result<(), Error<'gc>> {
+    let domains = domains
+        .iter()
+        .map(|domain| match domain {
+            Value::String(domain) => Ok(domain.to_utf8_lossy().into_owned()),
+            _ => Err(make_error_2052(activation)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let movie = activation.caller_movie_or_root();
+    for domain in domains {
+        activation
+            .context
+            .security
+            .allow_domain(&movie, &domain, insecure);
+    }
+
+    Ok(())
+}
+
 pub fn load_policy_file<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Value<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "loadPolicyFile");
+    let url = args.get_string(activation, 0).to_utf8_lossy();
+    let url = match activation.context.navigator.resolve_url(&url) {
+        Ok(url) => url,
+        Err(e) => {
+            tracing::warn!("Security.loadPolicyFile: Invalid URL {url}: {e}");
+            return Ok(Value::Undefined);
+        }
+    };
+
+    if url.scheme() == "xmlsocket" {
+        // Socket policies are requested from the socket server itself,
+        // once a movie connects to it.
+        if let (Some(host), Some(port)) = (url.host_str(), url.port()) {
+            activation
+                .context
+                .security
+                .set_socket_policy_port(host.to_owned(), port);
+        }
+    } else {
+        security::load_policy_file(activation.context, url);
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Checks whether the calling movie may read the data of `content`, throwing
+/// a `SecurityError` for `operation` if it may not.
+pub fn check_content_access<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    operation: &str,
+    content: &SwfMovie,
+) -> Result<(), Error<'gc>> {
+    let caller = activation.caller_movie_or_root();
+    match activation
+        .context
+        .security
+        .check_content_access(&caller, content)
+    {
+        Ok(()) => Ok(()),
+        Err(AccessDenied::NotAllowed) => Err(make_error_2121(
+            activation,
+            operation,
+            caller.url(),
+            content.url(),
+        )),
+        Err(AccessDenied::PolicyFileNotChecked) => Err(make_error_2122(
+            activation,
+            operation,
+            caller.url(),
+            content.url(),
+        )),
+        Err(AccessDenied::NoPolicyFileGrant) => Err(make_error_2123(
+            activation,
+            operation,
+            caller.url(),
+            content.url(),
+        )),
+    }
+}
+
+/// Returns the AVM2 object of `target`, if the calling movie may access it.
+///
+/// Every native that hands out display objects which may belong to another
+/// movie (children, parents, loaded content) goes through this, so that
+/// walking the display list can't be used to get around the check.
+pub fn scriptable_display_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    operation: &str,
+    target: DisplayObject<'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    check_content_access(activation, operation, &target.movie())?;
+    Ok(target.object2_or_null())
+}
+
 pub fn show_settings<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Value<'gc>,
@@ -1,8 +1,4 @@
 package flash.text.engine {
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_setter;
-    import __ruffle__.stub_method;
-
     import flash.display.DisplayObject;
     import flash.display.DisplayObjectContainer;
     import flash.errors.IllegalOperationError;
@@ -28,32 +24,17 @@ package flash.text.engine {
 
         public native function get textBlock():TextBlock;
 
-        public function get ascent():Number {
-            stub_getter("flash.text.engine.TextLine", "ascent");
-            return 12.0;
-        }
+        public native function get ascent():Number;
 
         [API("670")]
-        public function get totalAscent():Number {
-            stub_getter("flash.text.engine.TextLine", "totalAscent");
-            return 12.0;
-        }
+        public native function get totalAscent():Number;
 
-        public function get descent():Number {
-            stub_getter("flash.text.engine.TextLine", "descent");
-            return 3.0;
-        }
+        public native function get descent():Number;
 
         [API("670")]
-        public function get totalDescent():Number {
-            stub_getter("flash.text.engine.TextLine", "totalDescent");
-            return 3.0;
-        }
+        public native function get totalDescent():Number;
 
-        public function get unjustifiedTextWidth():Number {
-            stub_getter("flash.text.engine.TextLine", "unjustifiedTextWidth");
-            return this.specifiedWidth;
-        }
+        public native function get unjustifiedTextWidth():Number;
 
         public native function get textWidth():Number;
         public native function get textHeight():Number;
@@ -61,78 +42,36 @@ package flash.text.engine {
         public native function get validity():String;
         public native function set validity(value:String):void;
 
-        public function get hasGraphicElement():Boolean {
-            stub_getter("flash.text.engine.TextLine", "hasGraphicElement");
-            return false;
-        }
+        public native function get hasGraphicElement():Boolean;
 
-        public function get atomCount():int {
-            stub_getter("flash.text.engine.TextLine", "atomCount");
-            return this.rawTextLength;
-        }
+        public native function get atomCount():int;
 
         public native function get previousLine():TextLine;
         public native function get nextLine():TextLine;
 
-        public function getBaselinePosition(baseline:String):Number {
-            stub_method("flash.text.engine.TextLine", "getBaselinePosition");
-            return 0.0;
-        }
+        public native function getBaselinePosition(baseline:String):Number;
 
-        public function get hasTabs():Boolean {
-            stub_getter("flash.text.engine.TextLine", "hasTabs");
-            return false;
-        }
+        public native function get hasTabs():Boolean;
 
-        public function getAtomIndexAtPoint(stageX:Number, stageY:Number):int {
-            stub_method("flash.text.engine.TextLine", "getAtomIndexAtPoint");
-            return -1;
-        }
+        public native function getAtomIndexAtPoint(stageX:Number, stageY:Number):int;
 
-        public function getAtomIndexAtCharIndex(charIndex:int):int {
-            stub_method("flash.text.engine.TextLine", "getAtomIndexAtCharIndex");
-            return -1;
-        }
+        public native function getAtomIndexAtCharIndex(charIndex:int):int;
 
-        public function getAtomBidiLevel(index:int):int {
-            stub_method("flash.text.engine.TextLine", "getAtomBidiLevel");
-            return 0;
-        }
+        public native function getAtomBidiLevel(index:int):int;
 
-        public function getAtomBounds(index:int):Rectangle {
-            stub_method("flash.text.engine.TextLine", "getAtomBounds");
-            return new Rectangle(0, 0, 0, 0);
-        }
+        public native function getAtomBounds(index:int):Rectangle;
 
-        public function getAtomCenter(index:int):Number {
-            stub_method("flash.text.engine.TextLine", "getAtomCenter");
-            return 1.0;
-        }
+        public native function getAtomCenter(index:int):Number;
 
-        public function getAtomGraphic(index:int):DisplayObject {
-            stub_method("flash.text.engine.TextLine", "getAtomGraphic");
-            return null;
-        }
+        public native function getAtomGraphic(index:int):DisplayObject;
 
-        public function getAtomTextBlockBeginIndex(index:int):int {
-            stub_method("flash.text.engine.TextLine", "getAtomTextBlockBeginIndex");
-            return 0;
-        }
+        public native function getAtomTextBlockBeginIndex(index:int):int;
 
-        public function getAtomTextBlockEndIndex(index:int):int {
-            stub_method("flash.text.engine.TextLine", "getAtomTextBlockEndIndex");
-            return 0;
-        }
+        public native function getAtomTextBlockEndIndex(index:int):int;
 
-        public function getAtomTextRotation(index:int):String {
-            stub_method("flash.text.engine.TextLine", "getAtomTextRotation");
-            return TextRotation.ROTATE_0;
-        }
+        public native function getAtomTextRotation(index:int):String;
 
-        public function getAtomWordBoundaryOnLeft(index:int):Boolean {
-            stub_method("flash.text.engine.TextLine", "getAtomWordBoundaryOnLeft");
-            return false;
-        }
+        public native function getAtomWordBoundaryOnLeft(index:int):Boolean;
 
         // This function does nothing in Flash Player 32
         public function flushAtomData():void { }
@@ -62,38 +62,44 @@ pub fn set_element_format<'gc>(
 }
 
 pub fn get_text_block<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Value<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.text.engine.ContentElement", "textBlock");
-    Ok(Value::Null)
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_content_element_object()
+        .unwrap();
+    Ok(this.text_block().map_or(Value::Null, Value::from))
 }
 
 pub fn get_text_block_begin_index<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Value<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(
-        activation,
-        "flash.text.engine.ContentElement",
-        "textBlockBeginIndex"
-    );
-    Ok((-1).into())
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_content_element_object()
+        .unwrap();
+    Ok(this
+        .text_block_begin_index()
+        .map_or((-1).into(), Value::from_usize_lossy))
 }
 
 pub fn get_group_element<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Value<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(
-        activation,
-        "flash.text.engine.ContentElement",
-        "groupElement"
-    );
-    Ok(Value::Null)
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_content_element_object()
+        .unwrap();
+    Ok(this.group_element().map_or(Value::Null, Value::from))
 }
 
 pub fn get_event_mirror<'gc>(
@@ -178,8 +184,16 @@ pub fn set_text_rotation<'gc>(
 pub fn get_raw_text<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
-    args: &[Value<'gc>],
+    _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.text.engine.ContentElement", "rawText");
-    get_text(activation, this, args)
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_content_element_object()
+        .unwrap();
+
+    Ok(match this.raw_text(activation) {
+        Some(s) => s.into(),
+        None => Value::Null,
+    })
 }
@@ -1,6 +1,7 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::error::Error;
 use crate::avm2::object::ElementData;
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 
 pub fn init<'gc>(
@@ -15,7 +16,133 @@ pub fn init<'gc>(
         .unwrap();
 
     let mut data = this.element_data_mut(activation.gc());
-    *data = ElementData::Graphic;
+    *data = ElementData::Graphic {
+        graphic: None,
+        width: 15.0,
+        height: 15.0,
+    };
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_graphic<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_content_element_object()
+        .unwrap();
+
+    let data = this.element_data();
+    let ElementData::Graphic { graphic, .. } = &*data else {
+        unreachable!("Data can only have been set to Graphic");
+    };
+
+    Ok(graphic
+        .and_then(|g| g.object2())
+        .map_or(Value::Null, Value::from))
+}
+
+pub fn set_graphic<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_content_element_object()
+        .unwrap();
+    let value = args.try_get_object(0).and_then(|o| o.as_display_object());
+
+    let mut data = this.element_data_mut(activation.gc());
+    let ElementData::Graphic { graphic, .. } = &mut *data else {
+        unreachable!("Data can only have been set to Graphic");
+    };
+    *graphic = value;
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_element_width<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_content_element_object()
+        .unwrap();
+
+    let data = this.element_data();
+    let ElementData::Graphic { width, .. } = &*data else {
+        unreachable!("Data can only have been set to Graphic");
+    };
+
+    Ok((*width).into())
+}
+
+pub fn set_element_width<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_content_element_object()
+        .unwrap();
+    let value = args.get_f64(0);
+
+    let mut data = this.element_data_mut(activation.gc());
+    let ElementData::Graphic { width, .. } = &mut *data else {
+        unreachable!("Data can only have been set to Graphic");
+    };
+    *width = value;
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_element_height<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_content_element_object()
+        .unwrap();
+
+    let data = this.element_data();
+    let ElementData::Graphic { height, .. } = &*data else {
+        unreachable!("Data can only have been set to Graphic");
+    };
+
+    Ok((*height).into())
+}
+
+pub fn set_element_height<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_content_element_object()
+        .unwrap();
+    let value = args.get_f64(0);
+
+    let mut data = this.element_data_mut(activation.gc());
+    let ElementData::Graphic { height, .. } = &mut *data else {
+        unreachable!("Data can only have been set to Graphic");
+    };
+    *height = value;
 
     Ok(Value::Undefined)
 }
@@ -204,8 +204,23 @@ fn replace_elements_impl<'gc>(
 
     // This crash affects at least both `replaceElements` and `setElements`.
 
+    for element in &new_elements {
+        element.set_group_element(Some(this), activation.gc());
+    }
+
     let removed_elements = elements
         .splice(begin_index..end_index, new_elements)
+        .collect::<Vec<_>>();
+
+    for element in &removed_elements {
+        // An element removed and reinserted in the same call stays in the group
+        if !elements.iter().any(|e| Object::ptr_eq(*e, *element)) {
+            element.set_group_element(None, activation.gc());
+        }
+    }
+
+    let removed_elements = removed_elements
+        .into_iter()
         .map(Value::from)
         .collect::<Vec<_>>();
 
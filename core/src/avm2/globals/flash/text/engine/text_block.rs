@@ -3,14 +3,24 @@ use crate::avm2::Avm2StrRepresentable;
 use crate::avm2::activation::Activation;
 use crate::avm2::error::{Error, Error2004Type, make_error_2004, make_error_2008, make_error_2175};
 use crate::avm2::globals::flash::display::display_object::initialize_for_allocator;
-use crate::avm2::object::{ContentElementObject, ElementData, VectorObject};
+use crate::avm2::object::{
+    ContentElementObject, ElementData, TObject, TextBlockObject, VectorObject,
+};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
-use crate::display_object::{DisplayObject, EditText, TDisplayObject, TextLine};
-use crate::fte::{TextBaselineValue, TextLineCreationResultValue, TextRotationValue};
-use crate::html::{FormatSpans, TextFormat, TextSpan};
-use crate::string::{WStr, WString};
-use crate::{avm2_stub_getter, avm2_stub_setter};
+use crate::avm2_stub_setter;
+use crate::display_object::{DisplayObject, TDisplayObject, TextLine};
+use crate::fte::{
+    BlockContent, BlockSettings, ContentRun, ContentRunKind, GRAPHIC_ELEMENT_CHAR,
+    LineJustificationValue, TabStop, TextBaselineValue, TextLineCreationResultValue,
+    TextLineValidity, TextRotationValue, lay_out_line,
+};
+use crate::html::TextSpan;
+use crate::string::WString;
+use crate::tag_utils::SwfMovie;
+use ruffle_macros::istr;
+use std::sync::Arc;
+use swf::Twips;
 
 pub use crate::avm2::object::text_block_allocator;
 
@@ -127,8 +137,6 @@ pub fn set_baseline_zero<'gc>(
         .filter(|v| !matches!(v, TextBaselineValue::UseDominantBaseline))
         .ok_or_else(|| make_error_2008(activation, "baselineZero"))?;
 
-    avm2_stub_setter!(activation, "flash.text.engine.TextBlock", "baselineZero");
-
     this.set_baseline_zero(value);
 
     Ok(Value::Undefined)
@@ -154,8 +162,6 @@ pub fn set_bidi_level<'gc>(
         return Err(make_error_2004(activation, Error2004Type::ArgumentError));
     }
 
-    avm2_stub_setter!(activation, "flash.text.engine.TextBlock", "bidiLevel");
-
     this.set_bidi_level(value);
 
     Ok(Value::Undefined)
@@ -182,8 +188,6 @@ pub fn set_line_rotation<'gc>(
         .filter(|v| !matches!(v, TextRotationValue::Auto))
         .ok_or_else(|| make_error_2008(activation, "lineRotation"))?;
 
-    avm2_stub_setter!(activation, "flash.text.engine.TextBlock", "lineRotation");
-
     this.set_line_rotation(value);
 
     Ok(Value::Undefined)
@@ -212,8 +216,6 @@ pub fn set_tab_stops<'gc>(
         .and_then(|v| v.as_vector_object())
         .map(|v| VectorObject::from_vector(v.storage().clone(), activation));
 
-    avm2_stub_setter!(activation, "flash.text.engine.TextBlock", "tabStops");
-
     this.set_tab_stops(tab_stops, activation.gc());
 
     Ok(Value::Undefined)
@@ -240,8 +242,6 @@ pub fn set_text_justifier<'gc>(
 
     let justifier = args.get_object(activation, 0, "textJustifier")?;
 
-    avm2_stub_setter!(activation, "flash.text.engine.TextBlock", "textJustifier");
-
     this.set_text_justifier(justifier, activation.gc());
 
     Ok(Value::Undefined)
@@ -277,6 +277,13 @@ pub fn set_content<'gc>(
         return Err(make_error_2004(activation, Error2004Type::ArgumentError));
     }
 
+    if let Some(old_content) = this.content() {
+        old_content.set_text_block(None, activation.gc());
+    }
+    if let Some(content) = content {
+        content.set_text_block(Some(this), activation.gc());
+    }
+
     this.set_content(content, activation.gc());
 
     Ok(Value::Undefined)
@@ -295,17 +302,19 @@ pub fn get_text_line_creation_result<'gc>(
 }
 
 pub fn get_first_invalid_line<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Value<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(
-        activation,
-        "flash.text.engine.TextBlock",
-        "firstInvalidLine"
-    );
+    let this = this.as_object().unwrap().as_text_block_object().unwrap();
+
+    let line = this
+        .lines()
+        .find(|l| l.validity() != TextLineValidity::Valid)
+        .map(|l| l.object2().expect("Already created"))
+        .map(Value::from);
 
-    Ok(Value::Null)
+    Ok(line.unwrap_or(Value::Null))
 }
 
 pub fn get_first_line<'gc>(
@@ -324,14 +333,12 @@ pub fn get_first_line<'gc>(
 }
 
 pub fn get_last_line<'gc>(
-    activation: &mut Activation<'_, 'gc>,
+    _activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.as_object().unwrap().as_text_block_object().unwrap();
 
-    avm2_stub_getter!(activation, "flash.text.engine.TextBlock", "lastLine");
-
     let line = this
         .lines()
         .last()
@@ -446,6 +453,8 @@ pub fn do_create_text_line<'gc>(
         .and_then(|o| o.as_text_line());
 
     let width = args.get_f64(2);
+    let line_offset = args.get_f64(3);
+    let fit_something = args.get_bool(4);
 
     let content = block.content().expect("Guaranteed by AS checks");
 
@@ -455,8 +464,9 @@ pub fn do_create_text_line<'gc>(
         0
     };
 
-    let spans = match get_spans_from_content(content) {
-        Ok(spans) => spans,
+    let mut block_content = BlockContent::default();
+    match add_content_element(content, &mut block_content) {
+        Ok(()) => {}
         Err(HandleContentError::NullElementFormat) => {
             // For some reason, FP handles this error as it handles an uncaught
             // exception, and returns `null` from this method.
@@ -466,24 +476,32 @@ pub fn do_create_text_line<'gc>(
 
             return Ok(Value::Null);
         }
-    };
-
-    // TODO don't discard the formatting
-    let text = spans.text();
+    }
 
-    if previous_position > text.len() {
-        // This can happen when the content is changed after creating a TextLine.
+    if previous_position >= block_content.text.len() {
+        // No more text. This can also happen when the content is changed after
+        // creating a TextLine.
         block.set_text_line_creation_result(Some(TextLineCreationResultValue::Complete));
         return Ok(Value::Null);
     }
 
-    let next_position = next_line_break(text, previous_position);
+    let settings = block_settings(activation, block, line_offset)?;
 
-    if text.is_empty() || next_position == text.len() && previous_position == next_position {
-        // No more text.
-        block.set_text_line_creation_result(Some(TextLineCreationResultValue::Complete));
+    // TODO should we use the caller's movie instead? Does it matter?
+    let movie = activation.context.root_swf.clone();
+
+    let Some((layout, next_position, result)) = lay_out_line(
+        activation.context,
+        &movie,
+        &block_content,
+        &settings,
+        previous_position,
+        Twips::from_pixels(width),
+        fit_something,
+    ) else {
+        block.set_text_line_creation_result(Some(TextLineCreationResultValue::InsufficientWidth));
         return Ok(Value::Null);
-    }
+    };
 
     let line_index = if let Some(previous_text_line) = previous_text_line {
         previous_text_line.line_index() + 1
@@ -502,30 +520,15 @@ pub fn do_create_text_line<'gc>(
         line
     } else {
         // `TextLine.createTextLine` is the caller: create a new `TextLine`.
-        create_text_line(activation, width)
+        create_text_line(activation, movie)
     };
 
     let text_line_instance = text_line.object2().expect("Already created the object2");
-    let fallback = text_line.fallback();
-
-    let subtext = &text[previous_position..next_position];
-
-    fallback.set_text(subtext, activation.context);
-
-    let format = spans
-        .span(0)
-        .expect("Non-empty text should have at least one span")
-        .get_text_format();
-
-    apply_format(activation, fallback, format, line_index);
-
-    // FIXME: This needs to use `intrinsic_bounds` to measure the width
-    // of the provided text, and set the width of the EditText to that.
-    // Some games depend on this (e.g. Realm Grinder).
 
+    text_line.set_layout(layout, activation.gc());
     text_line.set_text_block(Some(block), activation.gc());
     text_line.set_specified_width(width);
-    text_line.set_raw_text_length(subtext.len() as u32);
+    text_line.set_raw_text_length((next_position - previous_position) as u32);
     text_line.set_begin_index(previous_position as u32);
     text_line.set_end_index(next_position as u32);
     text_line.set_line_index(line_index);
@@ -541,123 +544,139 @@ pub fn do_create_text_line<'gc>(
     // TODO correctly set the `validity`, `nextLine`, and `prevLine` properties
     // of the lines coming after this line
 
-    block.set_text_line_creation_result(Some(TextLineCreationResultValue::Success));
+    block.set_text_line_creation_result(Some(result));
 
     Ok(text_line_instance.into())
 }
 
-fn create_text_line<'gc>(activation: &mut Activation<'_, 'gc>, width: f64) -> TextLine<'gc> {
+fn create_text_line<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    movie: Arc<SwfMovie>,
+) -> TextLine<'gc> {
     let class = activation.avm2().classes().textline;
 
-    // TODO should we use the caller's movie instead? Does it matter?
-    let movie = activation.context.root_swf.clone();
-
-    let fallback = EditText::new_fte(activation.context, movie.clone(), 0.0, 0.0, width, 15.0);
-    let text_line = TextLine::new(activation.context, movie, fallback);
+    let text_line = TextLine::new(activation.context, movie);
     initialize_for_allocator(activation.context, text_line.into(), class);
 
     text_line
 }
 
-enum HandleContentError {
-    NullElementFormat,
-}
-
-fn get_spans_from_content<'gc>(
-    content: ContentElementObject<'gc>,
-) -> Result<FormatSpans, HandleContentError> {
-    let mut all_text = WString::new();
-    let mut all_spans = Vec::new();
+/// Collect the properties of the block affecting the layout of its lines.
+fn block_settings<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    block: TextBlockObject<'gc>,
+    line_offset: f64,
+) -> Result<BlockSettings, Error<'gc>> {
+    let tab_stops = block
+        .tab_stops()
+        .map(|tab_stops| {
+            tab_stops
+                .storage()
+                .iter()
+                .filter_map(|v| v.as_object().and_then(|o| o.as_tab_stop_object()))
+                .map(|tab_stop| TabStop {
+                    alignment: tab_stop.alignment(),
+                    position: Twips::from_pixels(tab_stop.position()),
+                    decimal_alignment_token: WString::from_wstr(
+                        &tab_stop.decimal_alignment_token(),
+                    ),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut line_justification = LineJustificationValue::Unjustified;
+    let mut letter_spacing = false;
+    if let Some(justifier) = block.text_justifier() {
+        let value = Value::from(justifier)
+            .get_public_property(istr!("lineJustification"), activation)?
+            .coerce_to_string(activation)?;
+        line_justification =
+            LineJustificationValue::from_avm2_str(&value).unwrap_or(line_justification);
+
+        // `EastAsianJustifier` spreads the space between all characters.
+        letter_spacing = if justifier.has_own_property_string(istr!("letterSpacing"), activation)? {
+            Value::from(justifier)
+                .get_public_property(istr!("letterSpacing"), activation)?
+                .coerce_to_boolean()
+        } else {
+            true
+        };
+    }
 
-    handle_content_element(content, &mut all_text, &mut all_spans)?;
+    Ok(BlockSettings {
+        bidi_level: block.bidi_level(),
+        baseline_zero: block.baseline_zero(),
+        line_rotation: block.line_rotation(),
+        tab_stops,
+        line_offset: Twips::from_pixels(line_offset),
+        line_justification,
+        letter_spacing,
+    })
+}
 
-    let mut spans = FormatSpans::from_str_and_spans(&all_text, &all_spans);
-    spans.normalize();
-    Ok(spans)
+enum HandleContentError {
+    NullElementFormat,
 }
 
-fn handle_content_element<'gc>(
+fn add_content_element<'gc>(
     content: ContentElementObject<'gc>,
-    all_text: &mut WString,
-    all_spans: &mut Vec<TextSpan>,
+    block_content: &mut BlockContent<'gc>,
 ) -> Result<(), HandleContentError> {
     let data = content.element_data();
     let format = content.element_format();
+    let start = block_content.text.len();
 
-    match &*data {
+    let kind = match &*data {
         ElementData::Text { text } => {
             // If `text` is `None`, FP just completely ignores the element. It
             // doesn't even check its `elementFormat`.
-            if let Some(text) = text {
-                all_text.push_str(text);
-
-                let format = format.ok_or(HandleContentError::NullElementFormat)?;
-                let text_format = format.as_text_format();
-
-                let span = TextSpan::with_length_and_format(text.len(), &text_format);
-                all_spans.push(span);
+            let Some(text) = text else {
+                return Ok(());
+            };
+
+            let format = format.ok_or(HandleContentError::NullElementFormat)?;
+            block_content.text.push_str(text);
+
+            ContentRunKind::Text {
+                span: TextSpan::with_length_and_format(text.len(), &format.as_text_format()),
+                font_type: format.font_type(),
+                alpha: format.alpha(),
+                break_opportunity: format.break_opportunity(),
             }
         }
         ElementData::Group { elements } => {
             // TODO: The docs say GroupElement's format has some effects?
             for element in elements {
-                handle_content_element(*element, all_text, all_spans)?;
+                add_content_element(*element, block_content)?;
             }
+            return Ok(());
         }
-        ElementData::Graphic => {
-            // TODO
+        ElementData::Graphic {
+            graphic,
+            width,
+            height,
+        } => {
+            block_content.text.push(GRAPHIC_ELEMENT_CHAR);
+
+            ContentRunKind::Graphic {
+                graphic: *graphic,
+                width: Twips::from_pixels(*width),
+                height: Twips::from_pixels(*height),
+            }
         }
         ElementData::Invalid => {
             unreachable!(
                 "TextBlock and GroupElement prevent holding user subclasses of ContentElement"
             )
         }
-    }
-
-    Ok(())
-}
-
-fn next_line_break(text: &WStr, start: usize) -> usize {
-    let remaining_text = &text[start..];
-    let len = remaining_text
-        .iter()
-        .position(|ch| matches!(ch, 0x0A | 0x0D | 0x2028 | 0x2029))
-        // Include the separator.
-        .map(|pos| {
-            if remaining_text.get(pos) == Some(0x0D) && remaining_text.get(pos + 1) == Some(0x0A) {
-                pos + 2
-            } else {
-                pos + 1
-            }
-        });
-
-    if let Some(len) = len {
-        start + len
-    } else {
-        text.len()
-    }
-}
-
-fn apply_format<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    edit_text: EditText<'gc>,
-    format: TextFormat,
-    line_index: u32,
-) {
-    // TODO: Handle device font/non-device font properly
-    edit_text.set_is_device_font(activation.context, false);
-    edit_text.set_text_format(
-        0,
-        edit_text.text_length(),
-        format.clone(),
-        activation.context,
-    );
-    edit_text.set_new_text_format(format);
-
-    edit_text.set_word_wrap(true, activation.context);
+    };
 
-    let measured_text = edit_text.measure_text(activation.context);
+    block_content.runs.push(ContentRun {
+        range: start..block_content.text.len(),
+        text_rotation: content.text_rotation(),
+        kind,
+    });
 
-    edit_text.set_height(activation.context, measured_text.1.to_pixels());
-    edit_text.set_y(measured_text.1 * line_index as i32);
+    Ok(())
 }
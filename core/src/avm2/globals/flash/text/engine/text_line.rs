@@ -1,13 +1,16 @@
+use crate::avm2::Avm2StrRepresentable;
 use crate::avm2::activation::Activation;
-use crate::avm2::error::{Error, make_error_2008};
+use crate::avm2::error::{Error, Error2006Type, make_error_2006, make_error_2008};
+use crate::avm2::globals::flash::display::display_object::new_rectangle;
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
-use crate::display_object::TDisplayObject;
-use crate::fte::TextLineValidity;
+use crate::display_object::{TDisplayObject, TextLine};
+use crate::fte::{TextBaselineValue, TextLineValidity};
 use ruffle_macros::istr;
+use swf::Point;
 
 pub fn get_text_width<'gc>(
-    activation: &mut Activation<'_, 'gc>,
+    _activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
@@ -17,8 +20,7 @@ pub fn get_text_width<'gc>(
         return Ok(0.0.into());
     };
 
-    let measured_text = text_line.measure_text(activation.context);
-    Ok(measured_text.0.to_pixels().into())
+    Ok(text_line.layout().text_width().to_pixels().into())
 }
 
 pub fn get_validity<'gc>(
@@ -183,7 +185,7 @@ pub fn get_next_line<'gc>(
 }
 
 pub fn get_text_height<'gc>(
-    activation: &mut Activation<'_, 'gc>,
+    _activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
@@ -193,6 +195,374 @@ pub fn get_text_height<'gc>(
         return Ok(0.0.into());
     };
 
-    let measured_text = text_line.measure_text(activation.context);
-    Ok(measured_text.1.to_pixels().into())
+    let layout = text_line.layout();
+    Ok((layout.ascent() + layout.descent()).to_pixels().into())
+}
+
+pub fn get_ascent<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_display_object()
+        .unwrap()
+        .as_text_line()
+        .unwrap();
+
+    Ok(this.layout().ascent().to_pixels().into())
+}
+
+pub fn get_total_ascent<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_display_object()
+        .unwrap()
+        .as_text_line()
+        .unwrap();
+
+    Ok(this.layout().total_ascent().to_pixels().into())
+}
+
+pub fn get_descent<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_display_object()
+        .unwrap()
+        .as_text_line()
+        .unwrap();
+
+    Ok(this.layout().descent().to_pixels().into())
+}
+
+pub fn get_total_descent<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_display_object()
+        .unwrap()
+        .as_text_line()
+        .unwrap();
+
+    Ok(this.layout().total_descent().to_pixels().into())
+}
+
+pub fn get_unjustified_text_width<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_display_object()
+        .unwrap()
+        .as_text_line()
+        .unwrap();
+
+    Ok(this.layout().unjustified_text_width().to_pixels().into())
+}
+
+pub fn get_has_graphic_element<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_display_object()
+        .unwrap()
+        .as_text_line()
+        .unwrap();
+
+    Ok(this.layout().has_graphic_element().into())
+}
+
+pub fn get_has_tabs<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_display_object()
+        .unwrap()
+        .as_text_line()
+        .unwrap();
+
+    Ok(this.layout().has_tabs().into())
+}
+
+pub fn get_atom_count<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_display_object()
+        .unwrap()
+        .as_text_line()
+        .unwrap();
+
+    Ok(Value::from_usize_lossy(this.layout().atom_count()))
+}
+
+pub fn get_baseline_position<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_display_object()
+        .unwrap()
+        .as_text_line()
+        .unwrap();
+
+    let baseline = args.get_string_non_null(activation, 0, "baseline")?;
+    let baseline = TextBaselineValue::from_avm2_str(&baseline)
+        .ok_or_else(|| make_error_2008(activation, "baseline"))?;
+
+    Ok(this.layout().baseline_position(baseline).to_pixels().into())
+}
+
+pub fn get_atom_index_at_point<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_display_object()
+        .unwrap()
+        .as_text_line()
+        .unwrap();
+
+    let stage_point = Point::from_pixels(args.get_f64(0), args.get_f64(1));
+    let index = this
+        .global_to_local(stage_point)
+        .and_then(|point| this.layout().atom_index_at_point(point));
+
+    Ok(index.map_or((-1).into(), Value::from_usize_lossy))
+}
+
+pub fn get_atom_index_at_char_index<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_display_object()
+        .unwrap()
+        .as_text_line()
+        .unwrap();
+
+    let index = usize::try_from(args.get_i32(0))
+        .ok()
+        .and_then(|char_index| this.layout().atom_index_at_char_index(char_index));
+
+    Ok(index.map_or((-1).into(), Value::from_usize_lossy))
+}
+
+/// Validates the atom index passed to the `getAtom*` methods.
+fn atom_index<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    text_line: TextLine<'gc>,
+    args: &[Value<'gc>],
+) -> Result<usize, Error<'gc>> {
+    usize::try_from(args.get_i32(0))
+        .ok()
+        .filter(|&index| index < text_line.layout().atom_count())
+        .ok_or_else(|| make_error_2006(activation, Error2006Type::RangeError))
+}
+
+pub fn get_atom_bidi_level<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_display_object()
+        .unwrap()
+        .as_text_line()
+        .unwrap();
+
+    let index = atom_index(activation, this, args)?;
+    let level = this
+        .layout()
+        .atom_bidi_level(index)
+        .expect("Atom index was checked");
+    Ok(level.into())
+}
+
+pub fn get_atom_bounds<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_display_object()
+        .unwrap()
+        .as_text_line()
+        .unwrap();
+
+    let index = atom_index(activation, this, args)?;
+    let bounds = this
+        .layout()
+        .atom_bounds(index)
+        .expect("Atom index was checked");
+    new_rectangle(activation, bounds)
+}
+
+pub fn get_atom_center<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_display_object()
+        .unwrap()
+        .as_text_line()
+        .unwrap();
+
+    let index = atom_index(activation, this, args)?;
+    let center = this
+        .layout()
+        .atom_center(index)
+        .expect("Atom index was checked");
+    Ok(center.to_pixels().into())
+}
+
+pub fn get_atom_graphic<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_display_object()
+        .unwrap()
+        .as_text_line()
+        .unwrap();
+
+    let index = atom_index(activation, this, args)?;
+    let graphic = this.layout().atom_graphic(index);
+    Ok(graphic
+        .and_then(|graphic| graphic.object2())
+        .map_or(Value::Null, Value::from))
+}
+
+pub fn get_atom_text_block_begin_index<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_display_object()
+        .unwrap()
+        .as_text_line()
+        .unwrap();
+
+    let index = atom_index(activation, this, args)?;
+    let range = this
+        .layout()
+        .atom_range(index)
+        .expect("Atom index was checked");
+    Ok(Value::from_usize_lossy(range.start))
+}
+
+pub fn get_atom_text_block_end_index<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_display_object()
+        .unwrap()
+        .as_text_line()
+        .unwrap();
+
+    let index = atom_index(activation, this, args)?;
+    let range = this
+        .layout()
+        .atom_range(index)
+        .expect("Atom index was checked");
+    Ok(Value::from_usize_lossy(range.end))
+}
+
+pub fn get_atom_text_rotation<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_display_object()
+        .unwrap()
+        .as_text_line()
+        .unwrap();
+
+    let index = atom_index(activation, this, args)?;
+    let rotation = this
+        .layout()
+        .atom_text_rotation(index)
+        .expect("Atom index was checked");
+    Ok(rotation.as_avm2_str(activation).into())
+}
+
+pub fn get_atom_word_boundary_on_left<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .unwrap()
+        .as_display_object()
+        .unwrap()
+        .as_text_line()
+        .unwrap();
+
+    let index = atom_index(activation, this, args)?;
+    let boundary = this
+        .layout()
+        .atom_word_boundary_on_left(index)
+        .expect("Atom index was checked");
+    Ok(boundary.into())
 }
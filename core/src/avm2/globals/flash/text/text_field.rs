@@ -7,7 +7,7 @@ use crate::avm2::object::{ClassObject, Object, TextFormatObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::{ArrayObject, ArrayStorage, Error};
-use crate::display_object::{AutoSizeMode, EditText, TextSelection};
+use crate::display_object::{AutoSizeMode, EditText, TDisplayObject, TextSelection};
 use crate::html::TextFormat;
 use crate::string::AvmString;
 use crate::{avm2_stub_getter, avm2_stub_setter};
@@ -1665,6 +1665,30 @@ pub fn get_first_char_in_paragraph<'gc>(
         .into())
 }
 
+pub fn get_image_reference<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    else {
+        return Ok(Value::Null);
+    };
+
+    let Some(id) = args.try_get_string(0) else {
+        return Ok(Value::Null);
+    };
+
+    Ok(this
+        .image_reference(&id)
+        .map(|image| image.object2_or_null())
+        .unwrap_or(Value::Null))
+}
+
 pub fn get_paragraph_length<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
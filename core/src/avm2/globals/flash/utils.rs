@@ -7,7 +7,6 @@ use crate::avm2::{Activation, Error, Value};
 use crate::string::AvmString;
 use crate::string::WString;
 use std::fmt::Write;
-use web_time::Instant;
 
 pub mod byte_array;
 pub mod dictionary;
@@ -20,7 +19,10 @@ pub fn get_timer<'gc>(
     _this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok((Instant::now()
+    Ok((activation
+        .context
+        .time
+        .instant()
         .duration_since(activation.context.start_time)
         .as_millis() as u32)
         .into())
@@ -39,6 +41,7 @@ pub fn set_interval<'gc>(
     let callback = crate::timer::TimerCallback::Avm2Callback {
         closure,
         params: params.to_vec(),
+        movie: activation.caller_movie(),
     };
 
     Ok(Value::Integer(activation.context.timers.add_timer(
@@ -72,6 +75,7 @@ pub fn set_timeout<'gc>(
     let callback = crate::timer::TimerCallback::Avm2Callback {
         closure,
         params: params.to_vec(),
+        movie: activation.caller_movie(),
     };
 
     Ok(Value::Integer(activation.context.timers.add_timer(
@@ -47,14 +47,12 @@ pub fn start<'gc>(
         // Note - we deliberately do *not* check if currentCount is less than repeatCount.
         // Calling 'start' on a timer that has currentCount >= repeatCount will tick exactly
         // once, and then stop immediately. This is handled by Timer.onUpdate
-        let id = activation.context.timers.add_timer(
-            TimerCallback::Avm2Callback {
-                closure: Some(on_update),
-                params: vec![],
-            },
-            delay,
-            false,
-        );
+        let callback = TimerCallback::Avm2Callback {
+            closure: Some(on_update),
+            params: vec![],
+            movie: activation.caller_movie(),
+        };
+        let id = activation.context.timers.add_timer(callback, delay, false);
         this.set_slot(slots::_TIMER_ID, id.into(), activation)?;
     }
     Ok(Value::Undefined)
@@ -194,6 +194,7 @@ include "flash/events/DRMReturnVoucherCompleteEvent.as"
 include "flash/events/DRMReturnVoucherErrorEvent.as"
 include "flash/events/DRMStatusEvent.as"
 include "flash/events/EventPhase.as"
+include "flash/events/FileListEvent.as"
 include "flash/events/FocusEvent.as"
 include "flash/events/FullScreenEvent.as"
 include "flash/events/GameInputEvent.as"
@@ -355,6 +356,7 @@ include "flash/net/URLVariables.as"
 include "flash/net/XMLSocket.as"
 
 include "flash/filesystem/File.as" // File extends FileReference
+include "flash/filesystem/FileMode.as"
 include "flash/filesystem/FileStream.as"
 
 include "flash/net/drm/AuthenticationMethod.as"
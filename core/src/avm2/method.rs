@@ -311,6 +311,15 @@ impl<'gc> Method<'gc> {
         }
     }
 
+    /// Get the verified info of this method, if it is a bytecode method that
+    /// has already been verified.
+    pub fn verified_info(&self) -> Option<&VerifiedMethodInfo<'gc>> {
+        match &self.0.method_kind {
+            MethodKind::Bytecode { verified_info } => verified_info.get(),
+            MethodKind::Native { .. } => None,
+        }
+    }
+
     /// Resolve the classes used in this method's signature and return type.
     #[inline(never)]
     pub fn resolve_info(self, activation: &mut Activation<'_, 'gc>) -> Result<(), Error<'gc>> {
@@ -2,9 +2,10 @@ use crate::avm2::Error;
 use crate::avm2::activation::Activation;
 use crate::avm2::object::element_format_object::ElementFormatObject;
 use crate::avm2::object::script_object::ScriptObjectData;
-use crate::avm2::object::{ClassObject, Object, TObject};
-use crate::fte::TextRotationValue;
-use crate::string::AvmString;
+use crate::avm2::object::{ClassObject, Object, TObject, TextBlockObject};
+use crate::display_object::DisplayObject;
+use crate::fte::{GRAPHIC_ELEMENT_CHAR, TextRotationValue};
+use crate::string::{AvmString, WString};
 use core::fmt;
 use gc_arena::barrier::unlock;
 use gc_arena::lock::{Lock, RefLock};
@@ -24,6 +25,8 @@ pub fn content_element_allocator<'gc>(
             element_format: Lock::new(None),
             text_rotation: Cell::new(TextRotationValue::Rotate0),
             event_mirror: Lock::new(None),
+            group_element: Lock::new(None),
+            text_block: Lock::new(None),
             element_data: RefLock::new(ElementData::Invalid),
         },
     ))
@@ -62,6 +65,13 @@ pub struct ContentElementObjectData<'gc> {
     /// line created from this `ContentElement`. TODO: implement this
     event_mirror: Lock<Option<Object<'gc>>>,
 
+    /// The `GroupElement` containing this element.
+    group_element: Lock<Option<ContentElementObject<'gc>>>,
+
+    /// The `TextBlock` this element is the content of. Elements nested in a
+    /// `GroupElement` get their block from the outermost group instead.
+    text_block: Lock<Option<TextBlockObject<'gc>>>,
+
     /// Data held by the class extending `ContentElement` (`TextElement`,
     /// `GraphicElement`, and `GroupElement`). User-defined classes that extend
     /// `ContentElement` do not hold any custom data; attempting to set the
@@ -87,8 +97,15 @@ pub enum ElementData<'gc> {
         elements: Vec<ContentElementObject<'gc>>,
     },
 
-    /// Such as for the `GraphicElement` class. TODO
-    Graphic,
+    /// Such as for the `GraphicElement` class.
+    Graphic {
+        /// The object displayed by the element. TODO: render it in text lines
+        graphic: Option<DisplayObject<'gc>>,
+
+        /// The size reserved for the graphic in text lines, in pixels.
+        width: f64,
+        height: f64,
+    },
 
     /// Such as for a user-defined class extending `ContentElement`.
     Invalid,
@@ -142,16 +159,96 @@ impl<'gc> ContentElementObject<'gc> {
         .borrow_mut()
     }
 
+    pub fn group_element(self) -> Option<ContentElementObject<'gc>> {
+        self.0.group_element.get()
+    }
+
+    pub fn set_group_element(self, value: Option<ContentElementObject<'gc>>, mc: &Mutation<'gc>) {
+        unlock!(
+            Gc::write(mc, self.0),
+            ContentElementObjectData,
+            group_element
+        )
+        .set(value);
+    }
+
+    /// The outermost `GroupElement` containing this element, or this
+    /// element itself if it's not in a group.
+    fn root_element(self) -> ContentElementObject<'gc> {
+        let mut element = self;
+        while let Some(group) = element.group_element() {
+            element = group;
+        }
+        element
+    }
+
+    pub fn text_block(self) -> Option<TextBlockObject<'gc>> {
+        self.root_element().0.text_block.get()
+    }
+
+    pub fn set_text_block(self, value: Option<TextBlockObject<'gc>>, mc: &Mutation<'gc>) {
+        unlock!(Gc::write(mc, self.0), ContentElementObjectData, text_block).set(value);
+    }
+
+    /// The position of this element within the raw text of its block.
+    pub fn text_block_begin_index(self) -> Option<usize> {
+        let mut index = 0;
+        let mut element = self;
+        while let Some(group) = element.group_element() {
+            let data = group.element_data();
+            let ElementData::Group { elements } = &*data else {
+                unreachable!("Only groups contain elements");
+            };
+            index += elements
+                .iter()
+                .take_while(|e| !Gc::ptr_eq(e.0, element.0))
+                .map(|e| e.raw_text_length())
+                .sum::<usize>();
+            drop(data);
+            element = group;
+        }
+
+        element.0.text_block.get().map(|_| index)
+    }
+
+    /// The length of this element's contribution to the raw text of its
+    /// block.
+    pub fn raw_text_length(self) -> usize {
+        match &*self.element_data() {
+            ElementData::Text { text } => text.map_or(0, |text| text.len()),
+            ElementData::Group { elements } => elements.iter().map(|e| e.raw_text_length()).sum(),
+            ElementData::Graphic { .. } => 1,
+            ElementData::Invalid => 0,
+        }
+    }
+
+    /// The text of this element, without the characters standing for graphic
+    /// elements.
     pub fn text(self, activation: &mut Activation<'_, 'gc>) -> Option<AvmString<'gc>> {
+        self.text_with_graphics(activation, istr!(""))
+    }
+
+    /// The text of this element, as it appears in its text block.
+    pub fn raw_text(self, activation: &mut Activation<'_, 'gc>) -> Option<AvmString<'gc>> {
+        let graphic_text =
+            AvmString::new(activation.gc(), WString::from_unit(GRAPHIC_ELEMENT_CHAR));
+        self.text_with_graphics(activation, graphic_text)
+    }
+
+    fn text_with_graphics(
+        self,
+        activation: &mut Activation<'_, 'gc>,
+        graphic_text: AvmString<'gc>,
+    ) -> Option<AvmString<'gc>> {
         match &*self.element_data() {
             ElementData::Text { text } => *text,
             ElementData::Group { elements } => {
                 let mut result = None;
 
                 for element in elements {
-                    // Recursively call `text` to concatenate the descendants
+                    // Recursively get the text to concatenate the descendants
                     // of the `GroupElement`
-                    let new_text = element.text(activation);
+                    let new_text = element.text_with_graphics(activation, graphic_text);
 
                     if let Some(new_text) = new_text {
                         if let Some(existing_text) = result {
@@ -171,7 +268,7 @@ impl<'gc> ContentElementObject<'gc> {
 
                 result
             }
-            ElementData::Graphic => Some(istr!("")),
+            ElementData::Graphic { .. } => Some(graphic_text),
             ElementData::Invalid => None,
         }
     }
@@ -3,6 +3,7 @@ use crate::avm2::activation::Activation;
 use crate::avm2::object::font_description_object::FontDescriptionObject;
 use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{ClassObject, Object, TObject};
+use crate::font::FontType;
 use crate::fte::{
     BreakOpportunityValue, DigitCaseValue, DigitWidthValue, FontLookupValue, FontPostureValue,
     FontWeightValue, KerningValue, LigatureLevelValue, TextBaselineValue, TextRotationValue,
@@ -259,18 +260,25 @@ impl<'gc> ElementFormatObject<'gc> {
         let bold = fd.font_weight() == FontWeightValue::Bold;
         let italic = fd.font_posture() == FontPostureValue::Italic;
 
-        // TODO: Support setting `is_device_font` for individual `TextFormat`s
-        let _is_device_font = fd.font_lookup() == FontLookupValue::Device;
-
         TextFormat {
             color: Some(self.color()),
             size: Some(self.font_size()),
             font: Some(font),
             bold: Some(bold),
             italic: Some(italic),
+            kerning: Some(self.kerning() != KerningValue::Off),
+            letter_spacing: Some(self.tracking_left() + self.tracking_right()),
             ..TextFormat::default()
         }
     }
+
+    /// The kind of font text with this format is rendered with.
+    pub fn font_type(self) -> FontType {
+        match self.font_description().font_lookup() {
+            FontLookupValue::Device => FontType::Device,
+            FontLookupValue::EmbeddedCFF => FontType::EmbeddedCFF,
+        }
+    }
 }
 
 impl<'gc> TObject<'gc> for ElementFormatObject<'gc> {
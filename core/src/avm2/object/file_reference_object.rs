@@ -1,6 +1,7 @@
 use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{ClassObject, Object, TObject};
 use crate::avm2::{Activation, Error};
+use crate::backend::filesystem::VirtualPath;
 use crate::backend::ui::FileDialogSelection;
 use crate::context::UpdateContext;
 use gc_arena::{Collect, DynamicRoot, Gc, GcWeak, Rootable};
@@ -62,6 +63,18 @@ impl FileReferenceObject<'_> {
             .replace(FileReference::FileDialogSelection(selection))
     }
 
+    pub fn init_from_air_path(self, path: VirtualPath) -> FileReference {
+        self.0.reference.replace(FileReference::AirFile(path))
+    }
+
+    /// The path of an AIR `File`, if this is one and it points somewhere.
+    pub fn air_path(&self) -> Option<VirtualPath> {
+        match *self.0.reference.borrow() {
+            FileReference::AirFile(ref path) => Some(path.clone()),
+            _ => None,
+        }
+    }
+
     pub fn file_reference(&self) -> Ref<'_, FileReference> {
         self.0.reference.borrow()
     }
@@ -78,6 +91,9 @@ impl FileReferenceObject<'_> {
 pub enum FileReference {
     None,
     FileDialogSelection(Box<dyn FileDialogSelection>),
+
+    /// A path in the sandboxed filesystem, used by the AIR `File` class.
+    AirFile(VirtualPath),
 }
 
 #[derive(Collect, HasPrefixField)]
@@ -98,6 +98,9 @@ pub struct LoaderInfoObjectData<'gc> {
     expose_content: Cell<bool>,
 
     errored: Cell<bool>,
+
+    /// Whether the request for the loaded content was redirected.
+    redirected: Cell<bool>,
 }
 
 impl<'gc> LoaderInfoObject<'gc> {
@@ -142,6 +145,7 @@ impl<'gc> LoaderInfoObject<'gc> {
                 content_type: Cell::new(ContentType::Unknown),
                 expose_content: Cell::new(false),
                 errored: Cell::new(false),
+                redirected: Cell::new(false),
             },
         ));
 
@@ -179,6 +183,14 @@ impl<'gc> LoaderInfoObject<'gc> {
         self.0.errored.get()
     }
 
+    pub fn redirected(self) -> bool {
+        self.0.redirected.get()
+    }
+
+    pub fn set_redirected(self, redirected: bool) {
+        self.0.redirected.set(redirected);
+    }
+
     pub fn init_event_fired(self) -> bool {
         self.0.init_event_fired.get()
     }
@@ -270,6 +282,7 @@ impl<'gc> LoaderInfoObject<'gc> {
         let loader_stream = LoaderStream::NotYetLoaded(empty_swf, None, false);
         self.set_loader_stream(loader_stream, context.gc());
         self.set_errored(false);
+        self.set_redirected(false);
         self.reset_init_and_complete_events();
 
         let mut loader = self
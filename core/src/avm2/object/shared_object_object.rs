@@ -5,7 +5,7 @@ use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{Object, ScriptObject, TObject};
 use crate::context::UpdateContext;
 use gc_arena::barrier::unlock;
-use gc_arena::{Collect, Gc, GcWeak, lock::Lock};
+use gc_arena::{Collect, Gc, GcWeak, Mutation, lock::Lock};
 use ruffle_common::utils::HasPrefixField;
 use std::fmt::Debug;
 
@@ -65,6 +65,10 @@ impl<'gc> SharedObjectObject<'gc> {
         .set(empty_data);
     }
 
+    pub fn set_data(&self, mc: &Mutation<'gc>, data: Object<'gc>) {
+        unlock!(Gc::write(mc, self.0), SharedObjectObjectData, data).set(data);
+    }
+
     pub fn name(&self) -> &String {
         &self.0.name
     }
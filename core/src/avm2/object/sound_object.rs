@@ -41,6 +41,8 @@ pub fn sound_allocator<'gc>(
                 queued_plays: Vec::new(),
             }),
             id3: Lock::new(None),
+            url: Lock::new(None),
+            extract_position: Cell::new(0),
         },
     ))
     .into())
@@ -90,6 +92,12 @@ pub struct SoundObjectData<'gc> {
 
     /// ID3Info Object
     id3: Lock<Option<Object<'gc>>>,
+
+    /// The URL this sound was loaded from, if it was loaded with `Sound.load`.
+    url: Lock<Option<AvmString<'gc>>>,
+
+    /// The sample frame that `Sound.extract` continues from when no position is given.
+    extract_position: Cell<usize>,
 }
 
 #[derive(Collect)]
@@ -120,6 +128,8 @@ pub enum SoundLoadingState {
     New,
     Loading,
     Loaded,
+    /// The download was cancelled with `Sound.close` before it finished.
+    Closed,
 }
 
 impl<'gc> SoundObject<'gc> {
@@ -139,6 +149,33 @@ impl<'gc> SoundObject<'gc> {
         self.0.loading_state.set(value);
     }
 
+    /// Returns `true` if the sound is still loading and has plays waiting for it.
+    pub fn is_buffering(self) -> bool {
+        if self.loading_state() != SoundLoadingState::Loading {
+            return false;
+        }
+        match &*self.0.sound_data.borrow() {
+            SoundData::NotLoaded { queued_plays } => !queued_plays.is_empty(),
+            SoundData::Loaded { .. } => false,
+        }
+    }
+
+    pub fn url(self) -> Option<AvmString<'gc>> {
+        self.0.url.get()
+    }
+
+    pub fn set_url(self, mc: &Mutation<'gc>, url: Option<AvmString<'gc>>) {
+        unlock!(Gc::write(mc, self.0), SoundObjectData, url).set(url);
+    }
+
+    pub fn extract_position(self) -> usize {
+        self.0.extract_position.get()
+    }
+
+    pub fn set_extract_position(self, position: usize) {
+        self.0.extract_position.set(position);
+    }
+
     /// Returns `true` if a `SoundChannel` should be returned back to the AVM2 caller.
     pub fn play(self, queued: QueuedPlay<'gc>, activation: &mut Activation<'_, 'gc>) -> bool {
         let mut sound_data = unlock!(
@@ -406,6 +406,14 @@ impl Op<'_> {
         }
     }
 
+    /// Whether this op only carries debugging information.
+    pub fn is_debug_info(&self) -> bool {
+        matches!(
+            self,
+            Op::Debug { .. } | Op::DebugFile { .. } | Op::DebugLine { .. }
+        )
+    }
+
     /// Whether all this op does is push a single value to the stack, possibly
     /// reading from stack or locals, but never, e.g., throwing an error or
     /// calling a method.
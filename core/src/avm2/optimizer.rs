@@ -48,7 +48,15 @@ pub fn optimize<'gc>(
 
     dce::eliminate_dead_code(code_slice, &jump_targets);
 
-    nop_remover::remove_nops(code, method_exceptions);
+    // Debugging information is needed while a debugger is attached, and for
+    // movies asking to be debugged, which the debugger may attach to later.
+    let keep_debug_info = activation.context.avm2_debugger.is_attached()
+        || activation
+            .context
+            .library
+            .library_for_movie(method.owner_movie())
+            .is_some_and(|library| library.debugger_enabled());
+    nop_remover::remove_nops(code, method_exceptions, keep_debug_info);
 
     Ok(())
 }
@@ -1,7 +1,13 @@
 use crate::avm2::op::Op;
 use crate::avm2::verify::Exception;
 
-pub fn remove_nops<'gc>(code: &mut Vec<Op<'gc>>, exceptions: &mut [Exception<'gc>]) {
+/// Removes nops from the code. Debugging information is kept if
+/// `keep_debug_info` is set, even if it would otherwise be removed.
+pub fn remove_nops<'gc>(
+    code: &mut Vec<Op<'gc>>,
+    exceptions: &mut [Exception<'gc>],
+    keep_debug_info: bool,
+) {
     let mut offset_vec = vec![0; code.len()];
     let mut current_offset = 0;
 
@@ -9,7 +15,7 @@ pub fn remove_nops<'gc>(code: &mut Vec<Op<'gc>>, exceptions: &mut [Exception<'gc
     let mut i = 0;
     while i < code.len() {
         offset_vec[i] = i - current_offset;
-        if code[i].is_nop() {
+        if code[i].is_nop() && !(keep_debug_info && code[i].is_debug_info()) {
             current_offset += 1;
         } else {
             // Shift the ops over the nops
@@ -0,0 +1,1384 @@
+//! Snapshots of the AVM2 heap, used by save states.
+//!
+//! The heap is captured as a flat list of entries, one for each object that
+//! is reachable from the display list or from the globals of the movie's
+//! scripts. Entries refer to each other by their index in that list.
+//!
+//! Objects that belong to the movie itself (classes, script globals, the
+//! stage and display objects placed by the timeline) are matched up with
+//! their live counterparts when the snapshot is restored. Everything else is
+//! allocated anew, without running any constructor, and then has its slots
+//! and dynamic properties filled in.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::Endian;
+use crate::avm2::dynamic_map::DynamicKey;
+use crate::avm2::globals::slots::flash_display_graphics as graphics_slots;
+use crate::avm2::globals::slots::flash_events_event_dispatcher as dispatcher_slots;
+use crate::avm2::method::Method;
+use crate::avm2::object::{
+    ClassObject, DispatchObject, FunctionObject, Object, ScriptObject, SharedObjectObject,
+    StageObject, TObject,
+};
+use crate::avm2::regexp::RegExpFlags;
+use crate::avm2::scope::{Scope, ScopeChain};
+use crate::avm2::script::{Script, TranslationUnit};
+use crate::avm2::{Avm2, Class, Domain, Namespace, QName, Value};
+use crate::bitmap::bitmap_data::{BitmapData, Color};
+use crate::character::Character;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use crate::save_state::{SaveStateError, display, field, json_wstr, wstr_json};
+use crate::string::AvmString;
+use chrono::{DateTime, Utc};
+use fnv::FnvHashMap;
+use ruffle_render::bitmap::BitmapInfo;
+use serde_json::{Map, Value as JsonValue, json};
+use swf::avm2::types::Index;
+
+fn malformed(message: impl Into<String>) -> SaveStateError {
+    SaveStateError::Malformed(message.into())
+}
+
+fn avm2_error(error: crate::avm2::Error<'_>) -> SaveStateError {
+    SaveStateError::Malformed(format!("{error:?}"))
+}
+
+/// The domains whose classes, scripts and methods can be referred to by a
+/// snapshot, along with the names they are stored under.
+fn domains<'gc>(activation: &mut Activation<'_, 'gc>) -> [(&'static str, Domain<'gc>); 2] {
+    let movie = activation.context.root_swf.clone();
+    let movie_domain = activation
+        .context
+        .library
+        .library_for_movie_mut(movie)
+        .avm2_domain();
+    [
+        ("movie", movie_domain),
+        ("playerglobals", activation.avm2().playerglobals_domain()),
+    ]
+}
+
+fn namespace_uri(namespace: Namespace<'_>) -> JsonValue {
+    namespace
+        .as_uri_opt()
+        .map_or_else(|| json!([]), |uri| wstr_json(&uri))
+}
+
+/// Collects the objects of the AVM2 heap into a snapshot.
+pub struct HeapWriter<'gc> {
+    domains: [(&'static str, Domain<'gc>); 2],
+
+    /// Every script of the movie and of the player globals, along with a key
+    /// that identifies it by one of its definitions.
+    scripts: Vec<(Script<'gc>, JsonValue)>,
+
+    /// The keys of every class defined in `domains`.
+    classes: FnvHashMap<Class<'gc>, JsonValue>,
+
+    /// The ID of every object seen so far.
+    ids: FnvHashMap<Object<'gc>, usize>,
+
+    /// All objects seen so far, in the order of their IDs.
+    objects: Vec<Object<'gc>>,
+
+    blockers: Vec<String>,
+}
+
+impl<'gc> HeapWriter<'gc> {
+    pub fn new(activation: &mut Activation<'_, 'gc>) -> Self {
+        let domains = domains(activation);
+
+        let mut scripts: Vec<(Script<'gc>, JsonValue)> = vec![];
+        let mut classes = FnvHashMap::default();
+        for (label, domain) in domains {
+            for (name, ns, script) in domain.defs().iter() {
+                let globals = script.init().1;
+                if !scripts.iter().any(|(other, _)| other.init().1 == globals) {
+                    let key = json!({
+                        "domain": label,
+                        "name": wstr_json(&name),
+                        "ns": namespace_uri(ns),
+                    });
+                    scripts.push((*script, key));
+                }
+            }
+            for (name, ns, class) in domain.classes().iter() {
+                classes.entry(*class).or_insert_with(|| {
+                    json!({
+                        "domain": label,
+                        "name": wstr_json(&name),
+                        "ns": namespace_uri(ns),
+                    })
+                });
+            }
+        }
+
+        Self {
+            domains,
+            scripts,
+            classes,
+            ids: FnvHashMap::default(),
+            objects: vec![],
+            blockers: vec![],
+        }
+    }
+
+    /// Adds the objects that every other object is reachable from: the
+    /// display list, starting at the stage, the globals of every script of
+    /// the movie that has run, and the open shared objects.
+    ///
+    /// Display objects are added parents first, so that a parent is always
+    /// restored before its children.
+    pub fn add_roots(&mut self, activation: &mut Activation<'_, 'gc>) {
+        let stage: DisplayObject<'gc> = activation.context.stage.into();
+        self.add_display_tree(stage);
+
+        let movie_domain = self.domains[0].1;
+        let globals: Vec<Object<'gc>> = self
+            .scripts
+            .iter()
+            .filter(|(script, _)| script.domain() == movie_domain && script.is_initialized())
+            .map(|(script, _)| script.init().1)
+            .collect();
+        for globals in globals {
+            self.object(globals);
+        }
+
+        // Shared objects stay open even when the movie no longer refers to
+        // them, and their data has to be restored along with the heap.
+        let mut shared_objects: Vec<_> = activation
+            .context
+            .avm2_shared_objects
+            .iter()
+            .map(|(name, object)| (name.clone(), *object))
+            .collect();
+        shared_objects.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (_, shared_object) in shared_objects {
+            self.object(shared_object.into());
+        }
+    }
+
+    fn add_display_tree(&mut self, object: DisplayObject<'gc>) {
+        if let Some(object) = object.object2() {
+            self.object(object.into());
+        }
+        if let Some(container) = object.as_container() {
+            for child in container.iter_render_list() {
+                self.add_display_tree(child);
+            }
+        }
+    }
+
+    /// Returns a reference to an object, adding it to the snapshot if it
+    /// wasn't already.
+    pub fn object(&mut self, object: Object<'gc>) -> JsonValue {
+        let id = *self.ids.entry(object).or_insert_with(|| {
+            self.objects.push(object);
+            self.objects.len() - 1
+        });
+        json!({ "ref": id })
+    }
+
+    /// Serializes a value, adding any object it refers to to the snapshot.
+    pub fn value(&mut self, value: Value<'gc>) -> JsonValue {
+        match value {
+            Value::Undefined => json!({ "undefined": true }),
+            Value::Null => JsonValue::Null,
+            Value::Bool(value) => json!(value),
+            Value::Integer(value) => json!(value),
+            Value::Number(value) if value.is_nan() => json!({ "number": "NaN" }),
+            Value::Number(value) if value == f64::INFINITY => json!({ "number": "Infinity" }),
+            Value::Number(value) if value == f64::NEG_INFINITY => {
+                json!({ "number": "-Infinity" })
+            }
+            Value::Number(value) => json!(value),
+            Value::String(value) => json!({ "string": wstr_json(&value) }),
+            Value::Object(object) => self.object(object),
+        }
+    }
+
+    /// Serializes every object added to the snapshot, along with anything
+    /// reachable from them.
+    ///
+    /// Returns the entries of the heap and the reasons why it can't be
+    /// captured, if any.
+    pub fn finish(mut self, activation: &mut Activation<'_, 'gc>) -> (JsonValue, Vec<String>) {
+        let mut entries = vec![];
+        let mut next = 0;
+        while let Some(object) = self.objects.get(next).copied() {
+            entries.push(self.entry(activation, object));
+            next += 1;
+        }
+        (JsonValue::Array(entries), self.blockers)
+    }
+
+    fn block(&mut self, reason: impl Into<String>) -> Map<String, JsonValue> {
+        self.blockers.push(reason.into());
+        Map::new()
+    }
+
+    fn class_key(&self, class: Class<'gc>) -> Option<JsonValue> {
+        if let Some(param) = class.param() {
+            let param = match param {
+                Some(param) => self.class_key(param)?,
+                None => JsonValue::Null,
+            };
+            return Some(json!({ "vector": param }));
+        }
+        self.classes.get(&class).cloned()
+    }
+
+    fn script_key(&self, globals: Object<'gc>) -> Option<&JsonValue> {
+        self.scripts
+            .iter()
+            .find(|(script, _)| script.init().1 == globals)
+            .map(|(_, key)| key)
+    }
+
+    fn method_key(&self, method: Method<'gc>) -> Option<JsonValue> {
+        let unit = method.translation_unit();
+        let (_, script) = self
+            .scripts
+            .iter()
+            .find(|(script, _)| script.translation_unit().same_abc(unit))?;
+        Some(json!({
+            "script": script,
+            "index": method.abc_method_index(),
+            "is_function": method.is_function(),
+        }))
+    }
+
+    /// Finds the method whose activation objects are of the given class.
+    fn activation_method(&self, class: Class<'gc>) -> Option<Method<'gc>> {
+        let mut units: Vec<TranslationUnit<'gc>> = vec![];
+        for (script, _) in &self.scripts {
+            let unit = script.translation_unit();
+            if !units.iter().any(|other| other.same_abc(unit)) {
+                units.push(unit);
+            }
+        }
+        units.into_iter().find_map(|unit| {
+            unit.loaded_methods().find(|method| {
+                method
+                    .verified_info()
+                    .and_then(|info| info.activation_class)
+                    == Some(class)
+            })
+        })
+    }
+
+    fn entry(&mut self, activation: &mut Activation<'_, 'gc>, object: Object<'gc>) -> JsonValue {
+        let movie_domain = self.domains[0].1;
+        let mut capture_properties = true;
+
+        let mut entry = if let Some(class_object) = object.as_class_object() {
+            let class = class_object.inner_class_definition();
+            match self.class_key(class) {
+                Some(key) => {
+                    // The statics of the player's own classes are not ours to restore.
+                    capture_properties =
+                        class.translation_unit().map(|unit| unit.domain()) == Some(movie_domain);
+                    let mut entry = Map::new();
+                    entry.insert("kind".to_string(), json!("class"));
+                    entry.insert("class".to_string(), key);
+                    entry
+                }
+                None => self.block("classes of loaded movies"),
+            }
+        } else if let Some(function) = object.as_function_object() {
+            capture_properties = false;
+            self.function_entry(function)
+        } else if let Some(key) = self.script_key(object).cloned() {
+            capture_properties = key["domain"] == "movie";
+            let mut entry = Map::new();
+            entry.insert("kind".to_string(), json!("globals"));
+            entry.insert("script".to_string(), key);
+            entry
+        } else if let Some(stage_object) = object.as_stage_object() {
+            self.display_entry(activation, object, stage_object)
+        } else if let Some(loader_info) = object.as_loader_info_object() {
+            let is = |other: Option<_>| other.map(Object::from) == Some(loader_info.into());
+            let root = activation.context.stage.root_clip();
+            let of = if is(activation.context.stage.loader_info()) {
+                Some("stage")
+            } else if is(root.and_then(|root| root.loader_info())) {
+                Some("root")
+            } else {
+                None
+            };
+            match of {
+                Some(of) => {
+                    let mut entry = Map::new();
+                    entry.insert("kind".to_string(), json!("loader_info"));
+                    entry.insert("of".to_string(), json!(of));
+                    entry
+                }
+                None => self.block("LoaderInfo objects of loaded movies"),
+            }
+        } else if let Some(dispatch) = object.as_dispatch_object() {
+            capture_properties = false;
+            let listeners: Vec<_> = dispatch.dispatch_mut(activation.gc()).listeners().collect();
+            let listeners: Vec<JsonValue> = listeners
+                .into_iter()
+                .map(|(event, priority, handler, use_capture)| {
+                    json!([
+                        wstr_json(&event),
+                        priority,
+                        self.object(handler.into()),
+                        use_capture
+                    ])
+                })
+                .collect();
+            let mut entry = Map::new();
+            entry.insert("kind".to_string(), json!("dispatch"));
+            entry.insert("listeners".to_string(), JsonValue::Array(listeners));
+            entry
+        } else if object.as_script_object().is_some()
+            && object.instance_class().class_object().is_none()
+        {
+            let class = object.instance_class();
+            match self
+                .activation_method(class)
+                .and_then(|m| self.method_key(m))
+            {
+                Some(method) => {
+                    let mut entry = Map::new();
+                    entry.insert("kind".to_string(), json!("activation"));
+                    entry.insert("method".to_string(), method);
+                    entry
+                }
+                None => self.block("catch scopes"),
+            }
+        } else {
+            self.instance_entry(activation, object)
+        };
+
+        if capture_properties && !entry.is_empty() {
+            let slot_count = object.vtable().slot_count();
+            let slots: Vec<JsonValue> = (0..slot_count)
+                .map(|id| self.value(object.get_slot(id)))
+                .collect();
+            entry.insert("slots".to_string(), JsonValue::Array(slots));
+
+            let dynamic: Vec<_> = object
+                .base()
+                .values()
+                .iter()
+                .map(|(key, property)| (*key, property.value, property.enumerable))
+                .collect();
+            let dynamic: Vec<JsonValue> = dynamic
+                .into_iter()
+                .map(|(key, value, enumerable)| {
+                    let key = match key {
+                        DynamicKey::String(key) => json!({ "string": wstr_json(&key) }),
+                        DynamicKey::Uint(key) => json!(key),
+                        DynamicKey::Object(key) => self.object(key),
+                    };
+                    json!([key, self.value(value), enumerable])
+                })
+                .collect();
+            entry.insert("dynamic".to_string(), JsonValue::Array(dynamic));
+        }
+
+        JsonValue::Object(entry)
+    }
+
+    fn function_entry(&mut self, function: FunctionObject<'gc>) -> Map<String, JsonValue> {
+        let exec = function.executable();
+        let method = exec.as_method();
+
+        // Bound methods are cached by their receiver, and must stay that way
+        // for `removeEventListener` to keep working.
+        if let Some(Value::Object(receiver)) = exec.bound_receiver() {
+            let vtable = receiver.vtable();
+            let disp_id = (0..)
+                .map_while(|disp_id| vtable.get_full_method(disp_id).map(|m| (disp_id, m)))
+                .find(|(disp_id, bound)| {
+                    bound.method == method
+                        && receiver
+                            .get_bound_method(*disp_id)
+                            .is_some_and(|cached| Object::ptr_eq(cached, function))
+                })
+                .map(|(disp_id, _)| disp_id);
+            if let Some(disp_id) = disp_id {
+                let mut entry = Map::new();
+                entry.insert("kind".to_string(), json!("bound_method"));
+                entry.insert("receiver".to_string(), self.object(receiver));
+                entry.insert("disp_id".to_string(), json!(disp_id));
+                return entry;
+            }
+        }
+
+        let Some(method_key) = self.method_key(method) else {
+            return self.block("functions of loaded movies");
+        };
+        let scope_chain = exec.scope();
+        let Some(domain) = self
+            .domains
+            .iter()
+            .find(|(_, domain)| *domain == scope_chain.domain())
+            .map(|(label, _)| *label)
+        else {
+            return self.block("functions of loaded movies");
+        };
+
+        let scopes: Vec<(Value<'gc>, bool)> = (0..)
+            .map_while(|index| scope_chain.get(index))
+            .map(|scope| (scope.values(), scope.with()))
+            .collect();
+        let scopes: Vec<JsonValue> = scopes
+            .into_iter()
+            .map(|(value, with)| json!([self.value(value), with]))
+            .collect();
+
+        let mut entry = Map::new();
+        entry.insert("kind".to_string(), json!("function"));
+        entry.insert("method".to_string(), method_key);
+        entry.insert("domain".to_string(), json!(domain));
+        entry.insert("scope".to_string(), JsonValue::Array(scopes));
+        if let Some(receiver) = exec.bound_receiver() {
+            entry.insert("receiver".to_string(), self.value(receiver));
+        }
+        if let Some(superclass) = exec.bound_superclass() {
+            entry.insert("superclass".to_string(), self.object(superclass.into()));
+        }
+        if let Some(prototype) = function.prototype() {
+            entry.insert("prototype".to_string(), self.object(prototype));
+        }
+        entry
+    }
+
+    fn display_entry(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        object: Object<'gc>,
+        stage_object: StageObject<'gc>,
+    ) -> Map<String, JsonValue> {
+        let display_object = stage_object.display_object();
+        let graphics_class = activation
+            .avm2()
+            .classes()
+            .graphics
+            .inner_class_definition();
+        if object.instance_class() == graphics_class {
+            let mut entry = Map::new();
+            entry.insert("kind".to_string(), json!("graphics"));
+            let owner = match display_object.object2() {
+                Some(owner) => self.object(owner.into()),
+                None => JsonValue::Null,
+            };
+            entry.insert("display_object".to_string(), owner);
+            if let Some(drawing) = display::capture_drawing(display_object) {
+                entry.insert("drawing".to_string(), drawing);
+            }
+            return entry;
+        }
+
+        let stage: DisplayObject<'gc> = activation.context.stage.into();
+        let is_stage = DisplayObject::ptr_eq(display_object, stage);
+        let is_root = activation
+            .context
+            .stage
+            .root_clip()
+            .is_some_and(|root| DisplayObject::ptr_eq(display_object, root));
+
+        match display_object {
+            DisplayObject::LoaderDisplay(_) => return self.block("Loader display objects"),
+            DisplayObject::Video(_) => return self.block("Video display objects"),
+            DisplayObject::TextLine(_) => return self.block("TextLine display objects"),
+            DisplayObject::Avm2Button(_) if display_object.placed_by_avm2_script() => {
+                return self.block("SimpleButtons created by script");
+            }
+            _ if !is_stage && !display_object.movie().is_action_script_3() => {
+                return self.block("AVM1 content in AVM2 movies");
+            }
+            _ => {}
+        }
+
+        let Some(class) = self.class_key(object.instance_class()) else {
+            return self.block("classes of loaded movies");
+        };
+
+        let mut entry = Map::new();
+        if is_stage {
+            entry.insert("kind".to_string(), json!("stage"));
+        } else {
+            entry.insert("kind".to_string(), json!("display_object"));
+            entry.insert("class".to_string(), class);
+            entry.insert(
+                "state".to_string(),
+                display::capture(display_object, false, &mut self.blockers),
+            );
+            if is_root {
+                entry.insert("root".to_string(), json!(true));
+            } else if !display_object.placed_by_avm2_script()
+                && let Some(parent) = display_object.parent().and_then(|p| p.object2())
+            {
+                entry.insert(
+                    "timeline".to_string(),
+                    json!({
+                        "parent": self.object(parent.into()),
+                        "depth": display_object.depth(),
+                    }),
+                );
+            }
+            if let Some(bitmap) = display_object.as_bitmap() {
+                let bitmap_data = match bitmap.bitmap_data().object2() {
+                    Some(bitmap_data) => self.object(bitmap_data.into()),
+                    None => JsonValue::Null,
+                };
+                entry.insert("bitmap_data".to_string(), bitmap_data);
+                entry.insert("smoothing".to_string(), json!(bitmap.smoothing()));
+            }
+        }
+
+        if let Some(container) = display_object.as_container() {
+            let children: Vec<Object<'gc>> = container
+                .iter_render_list()
+                .filter_map(|child| child.object2())
+                .map(Object::from)
+                .collect();
+            let children: Vec<JsonValue> = children
+                .into_iter()
+                .map(|child| self.object(child))
+                .collect();
+            entry.insert("children".to_string(), JsonValue::Array(children));
+        }
+        entry
+    }
+
+    fn instance_entry(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        object: Object<'gc>,
+    ) -> Map<String, JsonValue> {
+        let data = match object {
+            Object::ScriptObject(_)
+            | Object::ProxyObject(_)
+            | Object::ErrorObject(_)
+            | Object::DictionaryObject(_) => JsonValue::Null,
+            Object::ArrayObject(array) => {
+                let storage = array.storage();
+                let values: Vec<(usize, Value<'gc>)> = storage
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, value)| Some((index, value?)))
+                    .collect();
+                let length = storage.length();
+                drop(storage);
+                let values: Vec<JsonValue> = values
+                    .into_iter()
+                    .map(|(index, value)| json!([index, self.value(value)]))
+                    .collect();
+                json!({ "length": length, "values": values })
+            }
+            Object::VectorObject(vector) => {
+                let storage = vector.storage();
+                let values = storage.storage().clone();
+                let fixed = storage.is_fixed();
+                drop(storage);
+                let values: Vec<JsonValue> =
+                    values.into_iter().map(|value| self.value(value)).collect();
+                json!({ "fixed": fixed, "values": values })
+            }
+            Object::ByteArrayObject(byte_array) => {
+                let storage = byte_array.storage();
+                json!({
+                    "bytes": storage.bytes(),
+                    "position": storage.position(),
+                    "little_endian": storage.endian() == Endian::Little,
+                })
+            }
+            Object::DateObject(date) => {
+                json!({ "time": date.date_time().map(|time| time.timestamp_millis()) })
+            }
+            Object::RegExpObject(regexp) => {
+                let regexp = regexp.regexp();
+                json!({
+                    "source": wstr_json(&regexp.source()),
+                    "flags": regexp.flags().bits(),
+                    "last_index": regexp.last_index(),
+                })
+            }
+            Object::XmlObject(xml) => {
+                json!({ "xml": wstr_json(&xml.as_xml_string(activation)) })
+            }
+            Object::XmlListObject(xml_list) => {
+                json!({ "xml": wstr_json(&xml_list.as_xml_string(activation)) })
+            }
+            Object::BitmapDataObject(bitmap_data) => {
+                let bitmap_data = bitmap_data.get_bitmap_data();
+                if bitmap_data.disposed() {
+                    json!({ "disposed": true })
+                } else {
+                    let pixels: Vec<u32> = bitmap_data
+                        .sync(activation.context.renderer)
+                        .borrow()
+                        .pixels()
+                        .iter()
+                        .map(Color::to_bgra_u32)
+                        .collect();
+                    json!({
+                        "width": bitmap_data.width(),
+                        "height": bitmap_data.height(),
+                        "transparent": bitmap_data.transparency(),
+                        "pixels": pixels,
+                    })
+                }
+            }
+            Object::SoundObject(sound) => {
+                let class = object.instance_class();
+                let embedded = activation
+                    .context
+                    .library
+                    .avm2_class_registry()
+                    .class_symbol(class)
+                    .is_some();
+                if sound.sound_handle().is_some() && !embedded {
+                    return self.block("sounds loaded at runtime");
+                }
+                JsonValue::Null
+            }
+            Object::SoundChannelObject(_) => JsonValue::Null,
+            Object::SoundTransformObject(transform) => json!([
+                transform.left_to_left(),
+                transform.left_to_right(),
+                transform.right_to_left(),
+                transform.right_to_right(),
+                transform.volume(),
+            ]),
+            Object::SharedObjectObject(shared_object) => {
+                json!({
+                    "name": shared_object.name(),
+                    "data": self.object(shared_object.data()),
+                })
+            }
+            _ => {
+                let name = object.instance_class().name().local_name();
+                return self.block(format!("{name} objects"));
+            }
+        };
+
+        let Some(class) = self.class_key(object.instance_class()) else {
+            return self.block("classes of loaded movies");
+        };
+        let mut entry = Map::new();
+        entry.insert("kind".to_string(), json!("instance"));
+        entry.insert("class".to_string(), class);
+        if !data.is_null() {
+            entry.insert("data".to_string(), data);
+        }
+        entry
+    }
+}
+
+/// Recreates the AVM2 heap from a snapshot.
+pub struct HeapReader<'gc> {
+    domains: [(&'static str, Domain<'gc>); 2],
+    objects: Vec<Option<Object<'gc>>>,
+}
+
+impl<'gc> HeapReader<'gc> {
+    /// Restores the heap captured by a [`HeapWriter`].
+    pub fn restore(
+        activation: &mut Activation<'_, 'gc>,
+        entries: &JsonValue,
+    ) -> Result<Self, SaveStateError> {
+        let entries = entries
+            .as_array()
+            .ok_or_else(|| malformed("heap is not an array"))?;
+        let mut reader = Self {
+            domains: domains(activation),
+            objects: vec![None; entries.len()],
+        };
+
+        // Allocate, or find, every object. Functions and graphics objects
+        // depend on other objects, so they go last.
+        for (id, entry) in entries.iter().enumerate() {
+            if !matches!(
+                entry["kind"].as_str(),
+                Some("function" | "bound_method" | "graphics")
+            ) {
+                reader.objects[id] = Some(reader.allocate(activation, entry)?);
+            }
+        }
+        for (id, entry) in entries.iter().enumerate() {
+            if entry["kind"] == "function" {
+                reader.objects[id] = Some(reader.allocate_function(activation, entry)?);
+            }
+        }
+        for (id, entry) in entries.iter().enumerate() {
+            match entry["kind"].as_str() {
+                Some("bound_method") => {
+                    let receiver = reader.object(&entry["receiver"])?;
+                    let disp_id: usize = field(entry, "disp_id")?;
+                    let method = match receiver.get_bound_method(disp_id) {
+                        Some(method) => method,
+                        None => {
+                            let method = receiver
+                                .vtable()
+                                .make_bound_method(activation.context, receiver.into(), disp_id)
+                                .ok_or_else(|| malformed("unknown bound method"))?;
+                            receiver.install_bound_method(activation.gc(), disp_id, method);
+                            method
+                        }
+                    };
+                    reader.objects[id] = Some(method.into());
+                }
+                Some("graphics") => {
+                    let display_object = reader
+                        .object(&entry["display_object"])?
+                        .as_display_object()
+                        .ok_or_else(|| malformed("graphics without a display object"))?;
+                    let graphics = StageObject::graphics(activation, display_object);
+                    reader.objects[id] = Some(graphics.into());
+                }
+                _ => {}
+            }
+        }
+
+        // Put the display objects back where they were.
+        for (id, entry) in entries.iter().enumerate() {
+            if let Some(children) = entry["children"].as_array() {
+                reader.restore_children(activation, id, children)?;
+            }
+        }
+
+        // Fill in the contents of every object.
+        for (id, entry) in entries.iter().enumerate() {
+            reader.fill(activation, id, entry)?;
+        }
+
+        // Finally, restore what depends on those contents.
+        for (id, entry) in entries.iter().enumerate() {
+            reader.finish(activation, id, entry)?;
+        }
+
+        Ok(reader)
+    }
+
+    /// Returns the object an entry refers to.
+    pub fn object(&self, reference: &JsonValue) -> Result<Object<'gc>, SaveStateError> {
+        let id = reference["ref"]
+            .as_u64()
+            .ok_or_else(|| malformed(format!("invalid object reference {reference}")))?;
+        self.objects
+            .get(id as usize)
+            .copied()
+            .flatten()
+            .ok_or_else(|| malformed(format!("object {id} is not available yet")))
+    }
+
+    /// Deserializes a value produced by [`HeapWriter::value`].
+    pub fn value(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        value: &JsonValue,
+    ) -> Result<Value<'gc>, SaveStateError> {
+        Ok(match value {
+            JsonValue::Null => Value::Null,
+            JsonValue::Bool(value) => Value::Bool(*value),
+            JsonValue::Number(number) => match number.as_i64() {
+                Some(value) if number.is_i64() && value as i32 as i64 == value => {
+                    Value::Integer(value as i32)
+                }
+                _ => Value::Number(number.as_f64().unwrap_or(f64::NAN)),
+            },
+            JsonValue::Object(object) if object.contains_key("string") => {
+                json_wstr(activation.gc(), &value["string"])?.into()
+            }
+            JsonValue::Object(object) if object.contains_key("ref") => self.object(value)?.into(),
+            JsonValue::Object(object) if object.contains_key("undefined") => Value::Undefined,
+            JsonValue::Object(object) => match object.get("number").and_then(|n| n.as_str()) {
+                Some("NaN") => Value::Number(f64::NAN),
+                Some("Infinity") => Value::Number(f64::INFINITY),
+                Some("-Infinity") => Value::Number(f64::NEG_INFINITY),
+                _ => return Err(malformed(format!("invalid value {value}"))),
+            },
+            JsonValue::String(_) | JsonValue::Array(_) => {
+                return Err(malformed(format!("invalid value {value}")));
+            }
+        })
+    }
+
+    fn domain(&self, key: &JsonValue) -> Result<Domain<'gc>, SaveStateError> {
+        self.domains
+            .iter()
+            .find(|(label, _)| key["domain"] == *label)
+            .map(|(_, domain)| *domain)
+            .ok_or_else(|| malformed(format!("unknown domain in {key}")))
+    }
+
+    fn find_script(&self, key: &JsonValue) -> Result<Script<'gc>, SaveStateError> {
+        let domain = self.domain(key)?;
+        domain
+            .defs()
+            .iter()
+            .find(|(n, namespace, _)| {
+                wstr_json(n) == key["name"] && namespace_uri(*namespace) == key["ns"]
+            })
+            .map(|(_, _, script)| *script)
+            .ok_or_else(|| malformed(format!("no script defines {key}")))
+    }
+
+    fn find_class(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        key: &JsonValue,
+    ) -> Result<ClassObject<'gc>, SaveStateError> {
+        if let Some(param) = key.get("vector") {
+            let param = if param.is_null() {
+                None
+            } else {
+                Some(self.find_class(activation, param)?.inner_class_definition())
+            };
+            let vector = activation.avm2().classes().generic_vector;
+            return Ok(vector.parametrize(activation, param));
+        }
+
+        let domain = self.domain(key)?;
+        let found = domain
+            .classes()
+            .iter()
+            .find(|(n, namespace, _)| {
+                wstr_json(n) == key["name"] && namespace_uri(*namespace) == key["ns"]
+            })
+            .map(|(n, namespace, class)| (QName::new(namespace, n), *class));
+        let (qname, class) = found.ok_or_else(|| malformed(format!("unknown class {key}")))?;
+
+        if let Some(class_object) = class.class_object() {
+            return Ok(class_object);
+        }
+
+        // The class is created when its script runs.
+        if let Some((_, script)) = domain.get_defining_script(&qname.into()) {
+            script.globals(activation.context).map_err(avm2_error)?;
+        }
+        class
+            .class_object()
+            .ok_or_else(|| malformed(format!("class {key} was never initialized")))
+    }
+
+    fn find_method(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        key: &JsonValue,
+    ) -> Result<Method<'gc>, SaveStateError> {
+        let script = self.find_script(&key["script"])?;
+        let index: u32 = field(key, "index")?;
+        let is_function: bool = field(key, "is_function")?;
+        script
+            .translation_unit()
+            .load_method(Index::new(index), is_function, activation)
+            .map_err(avm2_error)
+    }
+
+    fn allocate(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        entry: &JsonValue,
+    ) -> Result<Object<'gc>, SaveStateError> {
+        match entry["kind"].as_str() {
+            Some("class") => Ok(self.find_class(activation, &entry["class"])?.into()),
+            Some("globals") => self
+                .find_script(&entry["script"])?
+                .globals(activation.context)
+                .map_err(avm2_error),
+            Some("activation") => {
+                let method = self.find_method(activation, &entry["method"])?;
+                method.resolve_info(activation).map_err(avm2_error)?;
+                method.verify(activation).map_err(avm2_error)?;
+                let class = method
+                    .verified_info()
+                    .and_then(|info| info.activation_class)
+                    .ok_or_else(|| malformed("method has no activation"))?;
+                Ok(ScriptObject::custom_object(
+                    activation.gc(),
+                    class,
+                    None,
+                    class.vtable(),
+                ))
+            }
+            Some("stage") => activation
+                .context
+                .stage
+                .object2()
+                .map(Object::from)
+                .ok_or_else(|| malformed("stage has no object")),
+            Some("loader_info") => {
+                let loader_info = if entry["of"] == "stage" {
+                    activation.context.stage.loader_info()
+                } else {
+                    activation
+                        .context
+                        .stage
+                        .root_clip()
+                        .and_then(|root| root.loader_info())
+                };
+                loader_info
+                    .map(Object::from)
+                    .ok_or_else(|| malformed("missing LoaderInfo"))
+            }
+            Some("dispatch") => Ok(DispatchObject::empty_list(activation)),
+            Some("display_object") => self.allocate_display_object(activation, entry),
+            Some("instance") => self.allocate_instance(activation, entry),
+            other => Err(malformed(format!("unknown heap entry kind {other:?}"))),
+        }
+    }
+
+    fn allocate_display_object(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        entry: &JsonValue,
+    ) -> Result<Object<'gc>, SaveStateError> {
+        let class = self.find_class(activation, &entry["class"])?;
+
+        let timeline_object = if entry["root"] == true {
+            activation.context.stage.root_clip()
+        } else if entry["timeline"].is_object() {
+            let depth = field(&entry["timeline"], "depth")?;
+            self.object(&entry["timeline"]["parent"])
+                .ok()
+                .and_then(|parent| parent.as_display_object())
+                .and_then(|parent| parent.as_container())
+                .and_then(|parent| parent.child_by_depth(depth))
+        } else {
+            None
+        };
+        let reused = timeline_object
+            .and_then(|object| object.object2())
+            .map(Object::from)
+            .filter(|object| object.instance_class() == class.inner_class_definition());
+
+        let object = match reused {
+            Some(object) => object,
+            None => {
+                let allocator = class.inner_class_definition().instance_allocator().0;
+                allocator(class, activation).map_err(avm2_error)?
+            }
+        };
+        if let Some(display_object) = object.as_display_object() {
+            display::goto(activation.context, display_object, &entry["state"]);
+        }
+        Ok(object)
+    }
+
+    fn allocate_instance(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        entry: &JsonValue,
+    ) -> Result<Object<'gc>, SaveStateError> {
+        let class = self.find_class(activation, &entry["class"])?;
+        let data = &entry["data"];
+
+        // XML is recreated from its source, as its nodes are not objects.
+        if let Some(xml) = data.get("xml") {
+            let xml = json_wstr(activation.gc(), xml)?;
+            return class
+                .construct(activation, &[xml.into()])
+                .map_err(avm2_error)?
+                .as_object()
+                .ok_or_else(|| malformed("XML constructor returned a primitive"));
+        }
+
+        if let Some(name) = data["name"].as_str()
+            && class.inner_class_definition()
+                == activation
+                    .avm2()
+                    .classes()
+                    .sharedobject
+                    .inner_class_definition()
+        {
+            if let Some(shared_object) = activation.context.avm2_shared_objects.get(name) {
+                return Ok((*shared_object).into());
+            }
+            let data = ScriptObject::new_object(activation.context);
+            let shared_object =
+                SharedObjectObject::from_data_and_name(activation, data, name.to_string());
+            activation
+                .context
+                .avm2_shared_objects
+                .insert(name.to_string(), shared_object);
+            return Ok(shared_object.into());
+        }
+
+        let allocator = class.inner_class_definition().instance_allocator().0;
+        let object = allocator(class, activation).map_err(avm2_error)?;
+
+        // Bitmap data must be ready by the time bitmaps are restored.
+        if let Some(bitmap_data_object) = object.as_bitmap_data_object()
+            && data["disposed"] != true
+        {
+            let width = field(data, "width")?;
+            let height = field(data, "height")?;
+            let pixels: Vec<u32> = field(data, "pixels")?;
+            let bitmap_data = BitmapData::new_with_pixels(
+                activation.gc(),
+                width,
+                height,
+                field(data, "transparent")?,
+                pixels.into_iter().map(Color::from).collect(),
+            );
+            bitmap_data_object.init_bitmap_data(activation.gc(), bitmap_data);
+            bitmap_data.init_object2(activation.gc(), bitmap_data_object);
+        }
+
+        Ok(object)
+    }
+
+    fn allocate_function(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        entry: &JsonValue,
+    ) -> Result<Object<'gc>, SaveStateError> {
+        let method = self.find_method(activation, &entry["method"])?;
+        let domain = self.domain(entry)?;
+        let mut scopes = vec![];
+        for scope in field::<Vec<JsonValue>>(entry, "scope")? {
+            let value = self.value(activation, &scope[0])?;
+            scopes.push(if scope[1] == true {
+                Scope::new_with(value)
+            } else {
+                Scope::new(value)
+            });
+        }
+        let scope = ScopeChain::new(domain).chain(activation.gc(), &scopes);
+        let receiver = match entry.get("receiver") {
+            Some(receiver) => Some(self.value(activation, receiver)?),
+            None => None,
+        };
+        let superclass = match entry.get("superclass") {
+            Some(superclass) => self.object(superclass)?.as_class_object(),
+            None => None,
+        };
+        Ok(
+            FunctionObject::from_method(activation.context, method, scope, receiver, superclass)
+                .into(),
+        )
+    }
+
+    fn restore_children(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        id: usize,
+        children: &[JsonValue],
+    ) -> Result<(), SaveStateError> {
+        let Some(mut container) = self.objects[id]
+            .and_then(|object| object.as_display_object())
+            .and_then(|object| object.as_container())
+        else {
+            return Ok(());
+        };
+        let this: DisplayObject<'gc> = container.into();
+
+        let mut expected = vec![];
+        for child in children {
+            if let Some(child) = self.object(child)?.as_display_object() {
+                expected.push(child);
+            }
+        }
+
+        // Remove whatever was added since the snapshot was taken.
+        let current: Vec<DisplayObject<'gc>> = container.iter_render_list().collect();
+        for child in current {
+            if child.object2().is_some()
+                && !expected
+                    .iter()
+                    .any(|other| DisplayObject::ptr_eq(*other, child))
+            {
+                container.remove_child(activation.context, child);
+            }
+        }
+
+        for (index, child) in expected.into_iter().enumerate() {
+            let is_in_place = child
+                .parent()
+                .is_some_and(|parent| DisplayObject::ptr_eq(parent, this))
+                && container
+                    .child_by_index(index)
+                    .is_some_and(|other| DisplayObject::ptr_eq(other, child));
+            if !is_in_place {
+                if !child
+                    .parent()
+                    .is_some_and(|parent| DisplayObject::ptr_eq(parent, this))
+                {
+                    child.set_placed_by_avm2_script(true);
+                }
+                container.insert_at_index(activation.context, child, index);
+            }
+        }
+        Ok(())
+    }
+
+    fn fill(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        id: usize,
+        entry: &JsonValue,
+    ) -> Result<(), SaveStateError> {
+        let Some(object) = self.objects[id] else {
+            return Ok(());
+        };
+
+        if let Some(slots) = entry["slots"].as_array() {
+            let slot_count = object.vtable().slot_count();
+            if slots.len() != slot_count {
+                return Err(malformed(format!("object {id} has {slot_count} slots")));
+            }
+            for (slot_id, value) in slots.iter().enumerate() {
+                let value = self.value(activation, value)?;
+                object.set_slot_no_coerce(slot_id, value, activation.gc());
+            }
+        }
+
+        if let Some(dynamic) = entry["dynamic"].as_array() {
+            let mut properties = vec![];
+            for property in dynamic {
+                let key = match &property[0] {
+                    key if key.get("string").is_some() => {
+                        DynamicKey::String(json_wstr(activation.gc(), &key["string"])?)
+                    }
+                    JsonValue::Number(key) => DynamicKey::Uint(
+                        key.as_u64()
+                            .ok_or_else(|| malformed("invalid property key"))?
+                            as u32,
+                    ),
+                    key => DynamicKey::Object(self.object(key)?),
+                };
+                let value = self.value(activation, &property[1])?;
+                properties.push((key, value, property[2] != false));
+            }
+
+            let base = object.base();
+            let mut values = base.values_mut(activation.gc());
+            let stale: Vec<DynamicKey<'gc>> = values.keys().copied().collect();
+            for key in stale {
+                values.remove(&key);
+            }
+            for (key, value, enumerable) in properties {
+                values.insert(key, value);
+                if !enumerable {
+                    values.set_enumerable(&key, false);
+                }
+            }
+        }
+
+        match entry["kind"].as_str() {
+            Some("function") => {
+                if let (Some(function), Some(prototype)) =
+                    (object.as_function_object(), entry.get("prototype"))
+                {
+                    function.set_prototype(Some(self.object(prototype)?), activation.gc());
+                }
+            }
+            Some("dispatch") => {
+                let mut listeners = vec![];
+                for listener in field::<Vec<JsonValue>>(entry, "listeners")? {
+                    let event = json_wstr(activation.gc(), &listener[0])?;
+                    let handler = self
+                        .object(&listener[2])?
+                        .as_function_object()
+                        .ok_or_else(|| malformed("event listener is not a function"))?;
+                    listeners.push((
+                        event,
+                        listener[1].as_i64().unwrap_or_default() as i32,
+                        handler,
+                        listener[3] == true,
+                    ));
+                }
+                if let Some(mut dispatch) = object.as_dispatch_mut(activation.gc()) {
+                    for (event, priority, handler, use_capture) in listeners {
+                        dispatch.add_event_listener(event, priority, handler, use_capture);
+                    }
+                }
+            }
+            Some("instance") => self.fill_instance(activation, object, &entry["data"])?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn fill_instance(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        object: Object<'gc>,
+        data: &JsonValue,
+    ) -> Result<(), SaveStateError> {
+        match object {
+            Object::ArrayObject(_) => {
+                let mut values = vec![];
+                for value in field::<Vec<JsonValue>>(data, "values")? {
+                    let index = value[0]
+                        .as_u64()
+                        .ok_or_else(|| malformed("invalid array index"))?
+                        as usize;
+                    values.push((index, self.value(activation, &value[1])?));
+                }
+                let length = field(data, "length")?;
+                if let Some(mut storage) = object.as_array_storage_mut(activation.gc()) {
+                    storage.set_length(length);
+                    for (index, value) in values {
+                        storage.set(index, value);
+                    }
+                }
+            }
+            Object::VectorObject(_) => {
+                let mut values = vec![];
+                for value in field::<Vec<JsonValue>>(data, "values")? {
+                    values.push(self.value(activation, &value)?);
+                }
+                if let Some(mut storage) = object.as_vector_storage_mut(activation.gc()) {
+                    storage.replace_storage(values);
+                    storage.set_is_fixed(field(data, "fixed")?);
+                }
+            }
+            Object::ByteArrayObject(_) => {
+                let bytes: Vec<u8> = field(data, "bytes")?;
+                if let Some(mut storage) = object.as_bytearray_mut() {
+                    storage.clear();
+                    storage
+                        .write_bytes(&bytes)
+                        .map_err(|_| malformed("ByteArray is too large"))?;
+                    storage.set_position(field(data, "position")?);
+                    storage.set_endian(if data["little_endian"] == true {
+                        Endian::Little
+                    } else {
+                        Endian::Big
+                    });
+                }
+            }
+            Object::DateObject(date) => {
+                let time: Option<i64> = field(data, "time")?;
+                date.set_date_time(time.and_then(DateTime::<Utc>::from_timestamp_millis));
+            }
+            Object::RegExpObject(regexp) => {
+                let source = json_wstr(activation.gc(), &data["source"])?;
+                let mut regexp = regexp.regexp_mut(activation.gc());
+                regexp.set_source(source);
+                regexp.set_flags(RegExpFlags::from_bits_truncate(field(data, "flags")?));
+                regexp.set_last_index(field(data, "last_index")?);
+            }
+            Object::SoundObject(sound) => {
+                let class = object.instance_class();
+                let symbol = activation
+                    .context
+                    .library
+                    .avm2_class_registry()
+                    .class_symbol(class);
+                if let Some((movie, id)) = symbol
+                    && let Some(Character::Sound(handle)) = activation
+                        .context
+                        .library
+                        .library_for_movie_mut(movie)
+                        .character_by_id(id)
+                {
+                    sound.set_sound(activation.context, handle);
+                }
+            }
+            Object::SoundTransformObject(transform) => {
+                let [
+                    left_to_left,
+                    left_to_right,
+                    right_to_left,
+                    right_to_right,
+                    volume,
+                ]: [f64; 5] = serde_json::from_value(data.clone())
+                    .map_err(|e| malformed(format!("sound transform: {e}")))?;
+                transform.set_left_to_left(left_to_left);
+                transform.set_left_to_right(left_to_right);
+                transform.set_right_to_left(right_to_left);
+                transform.set_right_to_right(right_to_right);
+                transform.set_volume(volume);
+            }
+            Object::SharedObjectObject(shared_object) => {
+                let data = self.object(&data["data"])?;
+                shared_object.set_data(activation.gc(), data);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn finish(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        id: usize,
+        entry: &JsonValue,
+    ) -> Result<(), SaveStateError> {
+        let Some(object) = self.objects[id] else {
+            return Ok(());
+        };
+
+        match entry["kind"].as_str() {
+            Some("display_object") => {
+                let Some(display_object) = object.as_display_object() else {
+                    return Ok(());
+                };
+                display::restore(activation.context, display_object, &entry["state"])?;
+                if let Some(bitmap) = display_object.as_bitmap() {
+                    if let Some(bitmap_data) =
+                        entry.get("bitmap_data").filter(|value| !value.is_null())
+                    {
+                        let bitmap_data = self
+                            .object(bitmap_data)?
+                            .as_bitmap_data()
+                            .ok_or_else(|| malformed("bitmap data is not a BitmapData"))?;
+                        bitmap.set_bitmap_data(activation.context, bitmap_data);
+                    }
+                    bitmap.set_smoothing(field(entry, "smoothing")?);
+                }
+            }
+            Some("graphics") => {
+                if let (Some(drawing), Some(display_object)) = (
+                    entry.get("drawing"),
+                    object.as_stage_object().map(|o| o.display_object()),
+                ) {
+                    let bitmaps = object
+                        .get_slot(graphics_slots::_BITMAPS)
+                        .as_object()
+                        .and_then(|bitmaps| bitmaps.as_array_storage().map(|s| s.clone()));
+                    let bitmaps = bitmaps
+                        .iter()
+                        .flat_map(|storage| storage.iter())
+                        .map(|bitmap| {
+                            let bitmap = bitmap
+                                .and_then(|bitmap| bitmap.as_object())
+                                .and_then(|bitmap| bitmap.as_bitmap_data())
+                                .ok_or_else(|| malformed("bitmap fill without a BitmapData"))?;
+                            Ok(BitmapInfo {
+                                handle: bitmap
+                                    .bitmap_handle(activation.gc(), activation.context.renderer),
+                                width: bitmap.width(),
+                                height: bitmap.height(),
+                            })
+                        })
+                        .collect::<Result<Vec<_>, SaveStateError>>()?;
+                    display::restore_drawing(display_object, drawing, bitmaps)?;
+                    display_object.invalidate_cached_bitmap();
+                }
+            }
+            _ => {}
+        }
+
+        // Broadcast events, such as `enterFrame`, need their listeners to be
+        // registered with the player.
+        let event_dispatcher = activation.avm2().classes().eventdispatcher;
+        if object.as_class_object().is_none()
+            && object.is_of_type(event_dispatcher.inner_class_definition())
+            && let Some(dispatch) = object
+                .get_slot(dispatcher_slots::DISPATCH_LIST)
+                .as_object()
+                .and_then(|list| list.as_dispatch_object())
+        {
+            let events: Vec<AvmString<'gc>> = dispatch
+                .dispatch_mut(activation.gc())
+                .listeners()
+                .map(|(event, _, _, _)| event)
+                .collect();
+            for event in events {
+                Avm2::register_broadcast_listener(activation.context, object, event);
+            }
+        }
+        Ok(())
+    }
+}
@@ -243,6 +243,14 @@ impl<'gc> TranslationUnit<'gc> {
         self.0.scripts.get(index).and_then(|s| s.get()).copied()
     }
 
+    /// Iterates over the methods that were loaded so far.
+    pub fn loaded_methods(self) -> impl Iterator<Item = Method<'gc>> {
+        Gc::as_ref(self.0)
+            .methods
+            .iter()
+            .filter_map(|m| m.get().copied())
+    }
+
     /// Load a string from the ABC's constant pool.
     ///
     /// This function yields an error if no such string index exists.
@@ -657,6 +665,11 @@ impl<'gc> Script<'gc> {
         self.0.translation_unit
     }
 
+    /// Whether the script initializer of this script has run.
+    pub fn is_initialized(self) -> bool {
+        self.0.initialized.get()
+    }
+
     pub fn global_class(self) -> Class<'gc> {
         self.0.globals.instance_class()
     }
@@ -26,6 +26,10 @@ pub struct VerifiedMethodInfo<'gc> {
     pub parsed_code: Vec<Op<'gc>>,
 
     pub exceptions: Vec<Exception<'gc>>,
+
+    /// The class of the activation objects created by this method, if it
+    /// needs one.
+    pub activation_class: Option<Class<'gc>>,
 }
 
 #[derive(Collect)]
@@ -522,6 +526,7 @@ pub fn verify_method<'gc>(
     Ok(VerifiedMethodInfo {
         parsed_code: verified_code,
         exceptions: new_exceptions,
+        activation_class,
     })
 }
 
@@ -1,5 +1,7 @@
 pub mod audio;
+pub mod filesystem;
 pub mod log;
 pub mod navigator;
 pub mod storage;
+pub mod time;
 pub mod ui;
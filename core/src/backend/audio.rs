@@ -104,6 +104,14 @@ pub trait AudioBackend: Any {
     /// Registers MP3 audio from an external source.
     fn register_mp3(&mut self, data: &[u8]) -> Result<SoundHandle, DecodeError>;
 
+    /// Registers 32-bit float PCM samples, interleaved if the sound is stereo.
+    fn register_float_pcm(
+        &mut self,
+        samples: &[f32],
+        sample_rate: u16,
+        is_stereo: bool,
+    ) -> Result<SoundHandle, RegisterError>;
+
     /// Plays a sound.
     fn start_sound(
         &mut self,
@@ -156,6 +164,17 @@ pub trait AudioBackend: Any {
     /// Returns `None` if the sound is not registered.
     fn get_sound_duration(&self, sound: SoundHandle) -> Option<FloatDuration>;
 
+    /// Decodes `num_frames` sample frames of a sound, starting at the sample frame `position`.
+    ///
+    /// The samples are resampled to 44.1 kHz stereo, and `position` is measured at that rate.
+    /// Fewer frames are returned when the end of the sound is reached.
+    fn extract_sound(
+        &mut self,
+        sound: SoundHandle,
+        position: usize,
+        num_frames: usize,
+    ) -> Result<Vec<[f32; 2]>, DecodeError>;
+
     /// Get the size of the data stored within a given sound.
     ///
     /// This is specifically measured in compressed bytes.
@@ -267,6 +286,29 @@ impl AudioBackend for NullAudioBackend {
         }))
     }
 
+    fn register_float_pcm(
+        &mut self,
+        samples: &[f32],
+        sample_rate: u16,
+        is_stereo: bool,
+    ) -> Result<SoundHandle, RegisterError> {
+        let num_channels = if is_stereo { 2 } else { 1 };
+        let num_sample_frames = (samples.len() / num_channels) as f64;
+        let duration =
+            FloatDuration::from_millis(num_sample_frames * 1000.0 / f64::from(sample_rate));
+
+        Ok(self.sounds.insert(NullSound {
+            duration,
+            size: (samples.len() * 4) as u32,
+            format: swf::SoundFormat {
+                compression: swf::AudioCompression::Uncompressed,
+                sample_rate,
+                is_stereo,
+                is_16_bit: false,
+            },
+        }))
+    }
+
     fn start_sound(
         &mut self,
         _sound: SoundHandle,
@@ -304,6 +346,14 @@ impl AudioBackend for NullAudioBackend {
             None
         }
     }
+    fn extract_sound(
+        &mut self,
+        _sound: SoundHandle,
+        _position: usize,
+        _num_frames: usize,
+    ) -> Result<Vec<[f32; 2]>, DecodeError> {
+        Ok(vec![])
+    }
     fn get_sound_size(&self, sound: SoundHandle) -> Option<u32> {
         if let Some(sound) = self.sounds.get(sound) {
             Some(sound.size)
@@ -475,6 +525,7 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object,
                 avm2_object: None,
                 stream_start_frame: None,
+                settings: Some(settings.clone()),
             };
 
             if let Some(transform) = transform {
@@ -579,6 +630,11 @@ impl<'gc> AudioManager<'gc> {
         audio.stop_all_sounds();
     }
 
+    /// The sounds that are currently playing.
+    pub fn sounds(&self) -> &[SoundInstance<'gc>] {
+        &self.sounds
+    }
+
     pub fn is_sound_playing(&self, sound: SoundInstanceHandle) -> bool {
         self.sounds.iter().any(|other| other.instance == sound)
     }
@@ -605,6 +661,7 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object: None,
                 avm2_object: None,
                 stream_start_frame: Some(clip_frame),
+                settings: None,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -631,6 +688,7 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object: None,
                 avm2_object: None,
                 stream_start_frame: None,
+                settings: None,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -872,6 +930,41 @@ pub struct SoundInstance<'gc> {
     avm2_object: Option<SoundChannelObject<'gc>>,
 
     stream_start_frame: Option<u16>,
+
+    /// The settings this sound was started with.
+    /// This will be `None` for stream sounds.
+    #[collect(require_static)]
+    settings: Option<swf::SoundInfo>,
+}
+
+impl<'gc> SoundInstance<'gc> {
+    pub fn instance(&self) -> SoundInstanceHandle {
+        self.instance
+    }
+
+    pub fn sound(&self) -> Option<SoundHandle> {
+        self.sound
+    }
+
+    pub fn display_object(&self) -> Option<DisplayObject<'gc>> {
+        self.display_object
+    }
+
+    pub fn transform(&self) -> &display_object::SoundTransform {
+        &self.transform
+    }
+
+    pub fn avm1_object(&self) -> Option<Avm1Object<'gc>> {
+        self.avm1_object
+    }
+
+    pub fn avm2_object(&self) -> Option<SoundChannelObject<'gc>> {
+        self.avm2_object
+    }
+
+    pub fn settings(&self) -> Option<&swf::SoundInfo> {
+        self.settings.as_ref()
+    }
 }
 
 /// A sound transform for a playing sound, for use by audio backends.
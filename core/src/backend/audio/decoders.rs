@@ -9,6 +9,7 @@ mod mp3;
 #[cfg(feature = "nellymoser")]
 mod nellymoser;
 mod pcm;
+mod speex;
 
 pub use adpcm::AdpcmDecoder;
 pub use g711::{G711ALawDecoder, G711MuLawDecoder};
@@ -16,7 +17,8 @@ pub use g711::{G711ALawDecoder, G711MuLawDecoder};
 pub use mp3::{Mp3Decoder, mp3_metadata};
 #[cfg(feature = "nellymoser")]
 pub use nellymoser::NellymoserDecoder;
-pub use pcm::PcmDecoder;
+pub use pcm::{FloatPcmDecoder, PcmDecoder};
+pub use speex::SpeexDecoder;
 
 use crate::backend::audio::{SoundStreamInfo, SoundStreamWrapping};
 use crate::tag_utils::{ControlFlow, SwfSlice};
@@ -90,6 +92,7 @@ pub fn make_decoder<R: 'static + Read + Send + Sync>(
         }
         AudioCompression::G711ALawPCM => Box::new(G711ALawDecoder::new(data)),
         AudioCompression::G711MuLawPCM => Box::new(G711MuLawDecoder::new(data)),
+        AudioCompression::Speex => Box::new(SpeexDecoder::new(data)),
         _ => return Err(Error::UnhandledCompression(format.compression)),
     };
     Ok(decoder)
@@ -502,6 +505,9 @@ pub fn make_substream_decoder(
         AudioCompression::Adpcm => {
             Box::new(adpcm::AdpcmSubstreamDecoder::new(stream_info, data_stream)?)
         }
+        AudioCompression::Speex => {
+            Box::new(speex::SpeexSubstreamDecoder::new(stream_info, data_stream))
+        }
         _ => Box::new(StandardSubstreamDecoder::new(stream_info, data_stream)?),
     };
     Ok(decoder)
@@ -71,3 +71,69 @@ impl<R: AsRef<[u8]> + Send + Sync> SeekableDecoder for PcmDecoder<Cursor<R>> {
         self.inner.set_position(pos);
     }
 }
+
+/// Decoder for 32-bit float PCM audio data, as loaded by `Sound.loadPCMFromByteArray`.
+///
+/// The samples are stored little-endian. They are converted to 16-bit when decoding for
+/// playback; `read_frame` returns them at full precision.
+pub struct FloatPcmDecoder<R: Read> {
+    inner: R,
+    sample_rate: u16,
+    is_stereo: bool,
+}
+
+impl<R: Read> FloatPcmDecoder<R> {
+    pub fn new(inner: R, is_stereo: bool, sample_rate: u16) -> Self {
+        Self {
+            inner,
+            is_stereo,
+            sample_rate,
+        }
+    }
+
+    /// Reads the next sample frame without converting it.
+    pub fn read_frame(&mut self) -> Option<[f32; 2]> {
+        let left = self.inner.read_f32::<LittleEndian>().ok()?;
+        let right = if self.is_stereo {
+            self.inner.read_f32::<LittleEndian>().ok()?
+        } else {
+            left
+        };
+        Some([left, right])
+    }
+}
+
+impl<R: Read> Iterator for FloatPcmDecoder<R> {
+    type Item = [i16; 2];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let to_i16 = |sample: f32| (sample * 32768.0).clamp(-32768.0, 32767.0) as i16;
+        let [left, right] = self.read_frame()?;
+        Some([to_i16(left), to_i16(right)])
+    }
+}
+
+impl<R: Read + Send + Sync> Decoder for FloatPcmDecoder<R> {
+    #[inline]
+    fn num_channels(&self) -> u8 {
+        if self.is_stereo { 2 } else { 1 }
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u16 {
+        self.sample_rate
+    }
+}
+
+impl<R: AsRef<[u8]> + Send + Sync> SeekableDecoder for FloatPcmDecoder<Cursor<R>> {
+    #[inline]
+    fn reset(&mut self) {
+        self.inner.set_position(0);
+    }
+
+    #[inline]
+    fn seek_to_sample_frame(&mut self, frame: u32) {
+        let pos = u64::from(frame) * u64::from(self.num_channels()) * 4;
+        self.inner.set_position(pos);
+    }
+}
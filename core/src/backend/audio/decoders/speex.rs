@@ -0,0 +1,1578 @@
+//! A Speex decoder, ported from the floating point decoder of libspeex.
+//!
+//! Flash only ever produces wideband Speex, so this always runs the sub-band
+//! decoder: a narrowband CELP decoder for the 0-4kHz band, a lighter CELP
+//! decoder for the 4-8kHz band, and a QMF filter bank to merge both into
+//! 16kHz audio. Narrowband-only frames simply leave the high band empty.
+//!
+//! The arithmetic is kept in the same order and precision as the reference
+//! decoder, which makes the output match it sample for sample.
+
+// Copyright 2002-2008 Xiph.org Foundation, Jean-Marc Valin.
+// Copyright 1993, 2002, 2006 David Rowe.
+// This file is derived from libspeex, which is distributed under the
+// 3-clause BSD license.
+
+mod tables;
+
+use super::{Decoder, SoundStreamInfo, Substream, SubstreamTagReader};
+use byteorder::ReadBytesExt;
+use ruffle_common::buffer::Slice;
+use std::f64::consts::PI;
+use std::io::Read;
+use tables::*;
+
+/// Speex in Flash is always wideband, sampled at 16kHz, regardless of what the
+/// container says.
+const SAMPLE_RATE: u16 = 16_000;
+
+/// Number of samples in one 20ms wideband frame.
+const SAMPLES_PER_FRAME: usize = 320;
+
+/// Number of samples in each band of a frame.
+const BAND_FRAME_SIZE: usize = SAMPLES_PER_FRAME / 2;
+
+/// Number of samples in each of the 4 subframes of a band.
+const SUBFRAME_SIZE: usize = 40;
+
+const NB_SUBFRAMES: usize = BAND_FRAME_SIZE / SUBFRAME_SIZE;
+
+/// Order of the LPC filter of each band.
+const NB_ORDER: usize = 10;
+const WB_ORDER: usize = 8;
+
+const NB_PITCH_START: i32 = 17;
+const NB_PITCH_END: i32 = 144;
+
+/// How many samples of past excitation the narrowband decoder keeps around for the
+/// pitch predictor and the enhancer, in front of the current frame.
+const NB_EXC_OFFSET: usize = 2 * NB_PITCH_END as usize + SUBFRAME_SIZE + 6;
+const NB_EXC_BUFFER: usize = NB_EXC_OFFSET + BAND_FRAME_SIZE + 6;
+
+/// Size in bits of each wideband layer, including the 4-bit header, by submode.
+/// Used to skip over layers that aren't preceded by a narrowband frame.
+const WB_LAYER_BITS: [u32; 5] = [4, 36, 112, 192, 352];
+
+/// Minimum distance kept between LSPs to keep the synthesis filters stable.
+const NB_LSP_MARGIN: f32 = 0.002;
+const WB_LSP_MARGIN: f32 = 0.05;
+
+const EXC_GAIN_QUANT_SCAL3: [f32; 8] = [
+    0.061130, 0.163546, 0.310413, 0.428220, 0.555887, 0.719055, 0.938694, 1.326874,
+];
+const EXC_GAIN_QUANT_SCAL1: [f32; 2] = [0.70469, 1.05127];
+
+const GC_QUANT_BOUND: [f32; 16] = [
+    0.97979, 1.28384, 1.68223, 2.20426, 2.88829, 3.78458, 4.95900, 6.49787, 8.51428, 11.15642,
+    14.61846, 19.15484, 25.09895, 32.88761, 43.09325, 56.46588,
+];
+
+/// Gain applied to the folded low band innovation in the wideband vocoder submode.
+const FOLDING_GAIN: f32 = 0.9;
+
+/// The output highpass filter, as used for the low band of wideband streams.
+const HIGHPASS_NUM: [f32; 3] = [0.98645, -1.97277, 0.98645];
+const HIGHPASS_DEN: [f32; 3] = [1.00000, -1.97226, 0.97332];
+
+/// Fractional delay filters used by the enhancer to refine the pitch period.
+const SHIFT_FILT: [[f32; 7]; 3] = [
+    [
+        -0.011915, 0.046995, -0.152373, 0.614108, 0.614108, -0.152373, 0.046995,
+    ],
+    [
+        -0.0324855, 0.0859768, -0.2042986, 0.9640297, 0.208642, -0.0302054, -0.0063646,
+    ],
+    [
+        -0.0063646, -0.0302054, 0.208642, 0.9640297, -0.2042986, 0.0859768, -0.0324855,
+    ],
+];
+
+/// The QMF filter merging both bands.
+const QMF_COEFS: [f32; 64] = [
+    3.596189e-05,
+    -0.0001123515,
+    -0.0001104587,
+    0.0002790277,
+    0.0002298438,
+    -0.0005953563,
+    -0.0003823631,
+    0.00113826,
+    0.0005308539,
+    -0.001986177,
+    -0.0006243724,
+    0.003235877,
+    0.0005743159,
+    -0.004989147,
+    -0.0002584767,
+    0.007367171,
+    -0.0004857935,
+    -0.01050689,
+    0.001894714,
+    0.01459396,
+    -0.004313674,
+    -0.01994365,
+    0.00828756,
+    0.02716055,
+    -0.01485397,
+    -0.03764973,
+    0.026447,
+    0.05543245,
+    -0.05095487,
+    -0.09779096,
+    0.1382363,
+    0.4600981,
+    0.4600981,
+    0.1382363,
+    -0.09779096,
+    -0.05095487,
+    0.05543245,
+    0.026447,
+    -0.03764973,
+    -0.01485397,
+    0.02716055,
+    0.00828756,
+    -0.01994365,
+    -0.004313674,
+    0.01459396,
+    0.001894714,
+    -0.01050689,
+    -0.0004857935,
+    0.007367171,
+    -0.0002584767,
+    -0.004989147,
+    0.0005743159,
+    0.003235877,
+    -0.0006243724,
+    -0.001986177,
+    0.0005308539,
+    0.00113826,
+    -0.0003823631,
+    -0.0005953563,
+    0.0002298438,
+    0.0002790277,
+    -0.0001104587,
+    -0.0001123515,
+    3.596189e-05,
+];
+
+/// The largest value that comes out of the excitation before it is synthesized.
+/// Anything larger can only come from a corrupted stream.
+const MAX_EXCITATION: f32 = 32000.0;
+
+/// How the LSPs of a narrowband frame are quantized.
+#[derive(Clone, Copy)]
+enum LspQuant {
+    /// Five stages, used by the higher bit-rate modes.
+    Full,
+    /// Three stages, used by the lower bit-rate modes.
+    LowBitRate,
+}
+
+/// How the pitch contribution of a narrowband frame is quantized.
+#[derive(Clone, Copy)]
+enum PitchQuant {
+    /// A single gain and the open-loop pitch, shared by all subframes.
+    Forced,
+    /// A 3-tap predictor per subframe.
+    ThreeTap {
+        gains: &'static [i8],
+        gain_bits: u32,
+        pitch_bits: u32,
+    },
+}
+
+/// A split vector quantizer codebook for the innovation.
+#[derive(Clone, Copy)]
+struct SplitCodebook {
+    subvector_size: usize,
+    num_subvectors: usize,
+    shapes: &'static [i8],
+    shape_bits: u32,
+    has_sign: bool,
+}
+
+/// How the fixed codebook contribution (the innovation) is quantized.
+#[derive(Clone, Copy)]
+enum Innovation {
+    /// No innovation is transmitted, random noise is used instead.
+    Noise,
+    Split(SplitCodebook),
+}
+
+/// The parameters of one of the 8 narrowband modes.
+struct NarrowbandSubmode {
+    /// Whether a single pitch period is transmitted for the whole frame.
+    open_loop_pitch: bool,
+    forced_pitch_gain: bool,
+    subframe_gain_bits: u32,
+    double_codebook: bool,
+    lsp: LspQuant,
+    pitch: PitchQuant,
+    innovation: Innovation,
+    /// Gain of the comb filter used to enhance the pitch, if any.
+    comb_gain: Option<f32>,
+}
+
+/// The parameters of one of the 4 wideband modes.
+struct WidebandSubmode {
+    /// Without an innovation, the high band excitation is folded from the low band.
+    innovation: Option<SplitCodebook>,
+    double_codebook: bool,
+}
+
+const THREE_TAP_NB: PitchQuant = PitchQuant::ThreeTap {
+    gains: &GAIN_CDBK_NB,
+    gain_bits: 7,
+    pitch_bits: 7,
+};
+
+const THREE_TAP_LBR: PitchQuant = PitchQuant::ThreeTap {
+    gains: &GAIN_CDBK_LBR,
+    gain_bits: 5,
+    pitch_bits: 7,
+};
+
+const fn split(
+    subvector_size: usize,
+    num_subvectors: usize,
+    shapes: &'static [i8],
+    shape_bits: u32,
+) -> SplitCodebook {
+    SplitCodebook {
+        subvector_size,
+        num_subvectors,
+        shapes,
+        shape_bits,
+        has_sign: false,
+    }
+}
+
+/// The narrowband modes, by mode ID. Mode 0 doesn't transmit anything.
+static NB_SUBMODES: [Option<NarrowbandSubmode>; 9] = [
+    None,
+    // 2150 bps "vocoder-like" mode for comfort noise.
+    Some(NarrowbandSubmode {
+        open_loop_pitch: true,
+        forced_pitch_gain: true,
+        subframe_gain_bits: 0,
+        double_codebook: false,
+        lsp: LspQuant::LowBitRate,
+        pitch: PitchQuant::Forced,
+        innovation: Innovation::Noise,
+        comb_gain: None,
+    }),
+    // 5.95 kbps
+    Some(NarrowbandSubmode {
+        open_loop_pitch: true,
+        forced_pitch_gain: false,
+        subframe_gain_bits: 0,
+        double_codebook: false,
+        lsp: LspQuant::LowBitRate,
+        pitch: PitchQuant::ThreeTap {
+            gains: &GAIN_CDBK_LBR,
+            gain_bits: 5,
+            pitch_bits: 0,
+        },
+        innovation: Innovation::Split(split(10, 4, &EXC_10_16, 4)),
+        comb_gain: Some(0.6),
+    }),
+    // 8 kbps
+    Some(NarrowbandSubmode {
+        open_loop_pitch: false,
+        forced_pitch_gain: false,
+        subframe_gain_bits: 1,
+        double_codebook: false,
+        lsp: LspQuant::LowBitRate,
+        pitch: THREE_TAP_LBR,
+        innovation: Innovation::Split(split(10, 4, &EXC_10_32, 5)),
+        comb_gain: Some(0.55),
+    }),
+    // 11 kbps
+    Some(NarrowbandSubmode {
+        open_loop_pitch: false,
+        forced_pitch_gain: false,
+        subframe_gain_bits: 1,
+        double_codebook: false,
+        lsp: LspQuant::LowBitRate,
+        pitch: THREE_TAP_LBR,
+        innovation: Innovation::Split(split(8, 5, &EXC_8_128, 7)),
+        comb_gain: Some(0.45),
+    }),
+    // 15 kbps
+    Some(NarrowbandSubmode {
+        open_loop_pitch: false,
+        forced_pitch_gain: false,
+        subframe_gain_bits: 3,
+        double_codebook: false,
+        lsp: LspQuant::Full,
+        pitch: THREE_TAP_NB,
+        innovation: Innovation::Split(split(5, 8, &EXC_5_64, 6)),
+        comb_gain: Some(0.25),
+    }),
+    // 18.2 kbps
+    Some(NarrowbandSubmode {
+        open_loop_pitch: false,
+        forced_pitch_gain: false,
+        subframe_gain_bits: 3,
+        double_codebook: false,
+        lsp: LspQuant::Full,
+        pitch: THREE_TAP_NB,
+        innovation: Innovation::Split(split(5, 8, &EXC_5_256, 8)),
+        comb_gain: Some(0.15),
+    }),
+    // 24.6 kbps
+    Some(NarrowbandSubmode {
+        open_loop_pitch: false,
+        forced_pitch_gain: false,
+        subframe_gain_bits: 3,
+        double_codebook: true,
+        lsp: LspQuant::Full,
+        pitch: THREE_TAP_NB,
+        innovation: Innovation::Split(split(5, 8, &EXC_5_64, 6)),
+        comb_gain: Some(0.05),
+    }),
+    // 3.95 kbps
+    Some(NarrowbandSubmode {
+        open_loop_pitch: true,
+        forced_pitch_gain: true,
+        subframe_gain_bits: 0,
+        double_codebook: false,
+        lsp: LspQuant::LowBitRate,
+        pitch: PitchQuant::Forced,
+        innovation: Innovation::Split(split(20, 2, &EXC_20_32, 5)),
+        comb_gain: Some(0.5),
+    }),
+];
+
+/// The wideband modes, by mode ID. Mode 0 doesn't transmit anything.
+static WB_SUBMODES: [Option<WidebandSubmode>; 8] = [
+    None,
+    Some(WidebandSubmode {
+        innovation: None,
+        double_codebook: false,
+    }),
+    Some(WidebandSubmode {
+        innovation: Some(split(10, 4, &HEXC_10_32, 5)),
+        double_codebook: false,
+    }),
+    Some(WidebandSubmode {
+        innovation: Some(SplitCodebook {
+            has_sign: true,
+            ..split(8, 5, &HEXC_8_128, 7)
+        }),
+        double_codebook: false,
+    }),
+    Some(WidebandSubmode {
+        innovation: Some(SplitCodebook {
+            has_sign: true,
+            ..split(8, 5, &HEXC_8_128, 7)
+        }),
+        double_codebook: true,
+    }),
+    None,
+    None,
+    None,
+];
+
+/// Reads the bitstream of a single Speex packet, MSB first.
+///
+/// A byte of lookahead is kept so we know when the packet is about to end,
+/// as the encoder pads the last byte of each packet with garbage bits.
+struct BitReader<R: Read> {
+    reader: R,
+    byte: u8,
+    bits_left: u32,
+    next_byte: Option<u8>,
+}
+
+impl<R: Read> BitReader<R> {
+    fn new(mut reader: R) -> Self {
+        let next_byte = reader.read_u8().ok();
+        Self {
+            reader,
+            byte: 0,
+            bits_left: 0,
+            next_byte,
+        }
+    }
+
+    /// Whether at least `num_bits` bits remain in the packet, for `num_bits <= 8`.
+    fn has_bits(&self, num_bits: u32) -> bool {
+        self.bits_left >= num_bits || self.next_byte.is_some()
+    }
+
+    fn peek_bit(&self) -> Option<bool> {
+        if self.bits_left == 0 {
+            Some(self.next_byte? & 0x80 != 0)
+        } else {
+            Some(self.byte & (1 << (self.bits_left - 1)) != 0)
+        }
+    }
+
+    fn read_bit(&mut self) -> Option<bool> {
+        if self.bits_left == 0 {
+            self.byte = self.next_byte.take()?;
+            self.next_byte = self.reader.read_u8().ok();
+            self.bits_left = 8;
+        }
+        self.bits_left -= 1;
+        Some(self.byte & (1 << self.bits_left) != 0)
+    }
+
+    fn read_bits(&mut self, num_bits: u32) -> Option<u32> {
+        let mut value = 0;
+        for _ in 0..num_bits {
+            value = (value << 1) | u32::from(self.read_bit()?);
+        }
+        Some(value)
+    }
+
+    fn read_index(&mut self, num_bits: u32) -> Option<usize> {
+        self.read_bits(num_bits).map(|value| value as usize)
+    }
+
+    fn skip_bits(&mut self, num_bits: u32) -> Option<()> {
+        for _ in 0..num_bits {
+            self.read_bit()?;
+        }
+        Some(())
+    }
+
+    /// Skips to the start of the next byte.
+    fn align(&mut self) {
+        self.bits_left = 0;
+    }
+}
+
+/// Decodes Speex audio.
+///
+/// The decoder state carries over from frame to frame. Packets may contain any
+/// number of frames, and packets ending with a terminator may be concatenated.
+pub struct SpeexDecoder<R: Read> {
+    bits: BitReader<R>,
+    state: Box<WidebandDecoder>,
+    frame: [i16; SAMPLES_PER_FRAME],
+    position: usize,
+}
+
+impl<R: Read> SpeexDecoder<R> {
+    /// Creates a decoder reading Speex packets from `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            bits: BitReader::new(reader),
+            state: Box::new(WidebandDecoder::new()),
+            frame: [0; SAMPLES_PER_FRAME],
+            position: SAMPLES_PER_FRAME,
+        }
+    }
+
+    /// Continues decoding with the next packet of the stream.
+    fn next_packet(&mut self, reader: R) {
+        self.bits = BitReader::new(reader);
+    }
+
+    /// Finds the header of the next narrowband frame, skipping over in-band
+    /// signalling and stray wideband layers. Returns `None` when the packet
+    /// is exhausted.
+    fn next_mode(&mut self) -> Option<usize> {
+        loop {
+            // Anything shorter than a frame header is just padding.
+            if !self.bits.has_bits(5) {
+                return None;
+            }
+
+            if self.bits.read_bit()? {
+                // A wideband layer, which should have followed a narrowband frame.
+                let submode = self.bits.read_index(3)?;
+                let layer_bits = WB_LAYER_BITS.get(submode)?;
+                self.bits.skip_bits(layer_bits - 4)?;
+                continue;
+            }
+
+            match self.bits.read_bits(4)? {
+                mode @ 0..=8 => return Some(mode as usize),
+                13 => {
+                    // User in-band request.
+                    let size = self.bits.read_bits(4)?;
+                    self.bits.skip_bits(5 + 8 * size)?;
+                }
+                14 => {
+                    // In-band signal.
+                    let size = match self.bits.read_bits(4)? {
+                        0..=1 => 1,
+                        2..=7 => 4,
+                        8..=9 => 8,
+                        10..=11 => 16,
+                        12..=13 => 32,
+                        _ => 64,
+                    };
+                    self.bits.skip_bits(size)?;
+                }
+                15 => {
+                    // Terminator: the rest of the byte is padding.
+                    self.bits.align();
+                }
+                mode => {
+                    tracing::warn!("Invalid Speex mode {mode}");
+                    return None;
+                }
+            }
+        }
+    }
+
+    fn decode_frame(&mut self) -> Option<()> {
+        let mode = self.next_mode()?;
+        let mut output = [0.0; SAMPLES_PER_FRAME];
+        self.state.decode(&mut self.bits, mode, &mut output)?;
+        for (sample, value) in self.frame.iter_mut().zip(output) {
+            *sample = if value > 32767.0 {
+                i16::MAX
+            } else if value < -32768.0 {
+                i16::MIN
+            } else {
+                (0.5 + f64::from(value)).floor() as i16
+            };
+        }
+        Some(())
+    }
+}
+
+impl<R: Read + Send + Sync> Decoder for SpeexDecoder<R> {
+    #[inline]
+    fn num_channels(&self) -> u8 {
+        1
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u16 {
+        SAMPLE_RATE
+    }
+}
+
+impl<R: Read> Iterator for SpeexDecoder<R> {
+    type Item = [i16; 2];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position == SAMPLES_PER_FRAME {
+            self.decode_frame()?;
+            self.position = 0;
+        }
+        let sample = self.frame[self.position];
+        self.position += 1;
+        Some([sample, sample])
+    }
+}
+
+/// Decodes Speex audio out of an FLV `Substream`.
+///
+/// Each audio tag holds a separate Speex packet with its own padding,
+/// so the bit reader is recreated for every chunk of the substream.
+pub struct SpeexSubstreamDecoder {
+    tag_reader: SubstreamTagReader,
+    decoder: SpeexDecoder<Slice>,
+}
+
+impl SpeexSubstreamDecoder {
+    pub fn new(stream_info: &SoundStreamInfo, data_stream: Substream) -> Self {
+        let empty_buffer = data_stream.buffer().to_empty_slice();
+        let mut tag_reader = SubstreamTagReader::new(stream_info, data_stream);
+        let audio_data = tag_reader.next().unwrap_or(empty_buffer);
+        Self {
+            tag_reader,
+            decoder: SpeexDecoder::new(audio_data),
+        }
+    }
+}
+
+impl Decoder for SpeexSubstreamDecoder {
+    fn num_channels(&self) -> u8 {
+        self.decoder.num_channels()
+    }
+    fn sample_rate(&self) -> u16 {
+        self.decoder.sample_rate()
+    }
+}
+
+impl Iterator for SpeexSubstreamDecoder {
+    type Item = [i16; 2];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(sample_frame) = self.decoder.next() {
+                return Some(sample_frame);
+            }
+            // Move on to the next packet, skipping over any empty ones.
+            self.decoder.next_packet(self.tag_reader.next()?);
+        }
+    }
+}
+
+/// The decoder state of the 4-8kHz band, which also drives the decoder of the
+/// 0-4kHz band.
+struct WidebandDecoder {
+    low_band: NarrowbandDecoder,
+    /// The excitation of the previous subframe, which is what gets synthesized.
+    excitation: [f32; SUBFRAME_SIZE],
+    old_qlsp: [f32; WB_ORDER],
+    interp_qlpc: [f32; WB_ORDER],
+    synthesis_memory: [f32; WB_ORDER],
+    low_qmf_memory: [f32; 32],
+    high_qmf_memory: [f32; 32],
+    /// RMS of the last frame's excitation, used for comfort noise.
+    last_energy: f32,
+    first: bool,
+    seed: u32,
+}
+
+impl WidebandDecoder {
+    fn new() -> Self {
+        Self {
+            low_band: NarrowbandDecoder::new(),
+            excitation: [0.0; SUBFRAME_SIZE],
+            old_qlsp: [0.0; WB_ORDER],
+            interp_qlpc: [0.0; WB_ORDER],
+            synthesis_memory: [0.0; WB_ORDER],
+            low_qmf_memory: [0.0; 32],
+            high_qmf_memory: [0.0; 32],
+            last_energy: 0.0,
+            first: true,
+            seed: 1000,
+        }
+    }
+
+    fn decode<R: Read>(
+        &mut self,
+        bits: &mut BitReader<R>,
+        low_mode: usize,
+        output: &mut [f32; SAMPLES_PER_FRAME],
+    ) -> Option<()> {
+        let mut low = [0.0; BAND_FRAME_SIZE];
+        let mut high = [0.0; BAND_FRAME_SIZE];
+        self.low_band.decode(bits, low_mode, &mut low)?;
+
+        let mode = if bits.has_bits(1) && bits.peek_bit()? {
+            bits.read_bit()?;
+            bits.read_index(3)?
+        } else {
+            0
+        };
+        if mode >= WB_SUBMODES.len() || (mode != 0 && WB_SUBMODES[mode].is_none()) {
+            tracing::warn!("Invalid Speex wideband mode {mode}");
+            return None;
+        }
+
+        let Some(submode) = &WB_SUBMODES[mode] else {
+            // Nothing was transmitted for the high band: either fill it with
+            // comfort noise, or let the synthesis filter ring out.
+            for sample in &mut high {
+                *sample = if self.low_band.dtx_enabled {
+                    random_sample(self.last_energy, &mut self.seed)
+                } else {
+                    1e-15
+                };
+            }
+            self.first = true;
+            iir_mem(&mut high, &self.interp_qlpc, &mut self.synthesis_memory);
+            qmf_synth(
+                &low,
+                &high,
+                output,
+                &mut self.low_qmf_memory,
+                &mut self.high_qmf_memory,
+            );
+            return Some(());
+        };
+
+        let mut qlsp = [0.0; WB_ORDER];
+        for (i, lsp) in qlsp.iter_mut().enumerate() {
+            *lsp = (0.3125 * i as f64 + 0.75) as f32;
+        }
+        add_lsp_stage(&mut qlsp, &LSP_CDBK_HIGH, 0.0039062, bits)?;
+        add_lsp_stage(&mut qlsp, &LSP_CDBK_HIGH2, 0.0019531, bits)?;
+        if self.first {
+            self.old_qlsp = qlsp;
+        }
+
+        let mut interp_qlsp = [0.0; WB_ORDER];
+        let mut ak = [0.0; WB_ORDER];
+        let mut energy_sum = 0.0f32;
+        for (sub, &low_pi_gain) in self.low_band.pi_gain.iter().enumerate() {
+            let offset = sub * SUBFRAME_SIZE;
+            lsp_interpolate(&self.old_qlsp, &qlsp, &mut interp_qlsp, sub, WB_LSP_MARGIN);
+            lsp_to_lpc(&interp_qlsp, &mut ak);
+
+            // Compare the response of the low and high band filters at 4kHz.
+            let mut high_pi_gain = 1.0f32;
+            for pair in ak.chunks_exact(2) {
+                high_pi_gain += pair[1] - pair[0];
+            }
+            let filter_ratio =
+                ((f64::from(low_pi_gain) + 0.01) / (f64::from(high_pi_gain) + 0.01)) as f32;
+
+            let mut excitation = [0.0; SUBFRAME_SIZE];
+            if let Some(codebook) = &submode.innovation {
+                let quant = bits.read_index(4)?;
+                let gc = (0.87360 * f64::from(GC_QUANT_BOUND[quant])) as f32;
+                let start = NB_EXC_OFFSET + offset;
+                let low_excitation = &self.low_band.excitation[start..start + SUBFRAME_SIZE];
+                let scale = gc * compute_rms(low_excitation) / filter_ratio;
+                split_codebook_unquant(&mut excitation, codebook, bits)?;
+                for sample in &mut excitation {
+                    *sample *= scale;
+                }
+                if submode.double_codebook {
+                    let mut excitation2 = [0.0; SUBFRAME_SIZE];
+                    split_codebook_unquant(&mut excitation2, codebook, bits)?;
+                    let scale2 = 0.4 * scale;
+                    for (sample, sample2) in excitation.iter_mut().zip(excitation2) {
+                        *sample += scale2 * sample2;
+                    }
+                }
+            } else {
+                // Fold the low band innovation over into the high band.
+                let quant = bits.read_bits(5)? as i32;
+                let g = f64::from(0.125 * (quant - 10) as f32).exp() as f32 / filter_ratio;
+                let innovation = &self.low_band.innovation[offset..offset + SUBFRAME_SIZE];
+                for (i, sample) in excitation.iter_mut().enumerate() {
+                    let folded = FOLDING_GAIN * innovation[i] * g;
+                    *sample = if i % 2 == 0 { folded } else { -folded };
+                }
+            }
+
+            let synthesized = &mut high[offset..offset + SUBFRAME_SIZE];
+            synthesized.copy_from_slice(&self.excitation);
+            iir_mem(synthesized, &self.interp_qlpc, &mut self.synthesis_memory);
+            self.excitation = excitation;
+            self.interp_qlpc = ak;
+            let rms = compute_rms(&self.excitation);
+            energy_sum += rms * rms / NB_SUBFRAMES as f32;
+        }
+        self.last_energy = f64::from(energy_sum).sqrt() as f32;
+
+        qmf_synth(
+            &low,
+            &high,
+            output,
+            &mut self.low_qmf_memory,
+            &mut self.high_qmf_memory,
+        );
+        self.old_qlsp = qlsp;
+        self.first = false;
+        Some(())
+    }
+}
+
+/// The decoder state of the 0-4kHz band.
+struct NarrowbandDecoder {
+    /// The past excitation, followed by the excitation of the current frame
+    /// at `NB_EXC_OFFSET`.
+    excitation: [f32; NB_EXC_BUFFER],
+    /// The innovation of the current frame, which the high band may fold over.
+    innovation: [f32; BAND_FRAME_SIZE],
+    old_qlsp: [f32; NB_ORDER],
+    interp_qlpc: [f32; NB_ORDER],
+    synthesis_memory: [f32; NB_ORDER],
+    highpass_memory: [f32; 2],
+    /// Gain of the LPC filter of each subframe at 4kHz.
+    pi_gain: [f32; NB_SUBFRAMES],
+    /// Whether the encoder signalled that it's sending comfort noise.
+    dtx_enabled: bool,
+    first: bool,
+    seed: u32,
+    vocoder_offset: i32,
+    vocoder_memory1: f32,
+    vocoder_memory2: f32,
+    vocoder_mean: f32,
+}
+
+impl NarrowbandDecoder {
+    fn new() -> Self {
+        Self {
+            excitation: [0.0; NB_EXC_BUFFER],
+            innovation: [0.0; BAND_FRAME_SIZE],
+            old_qlsp: [0.0; NB_ORDER],
+            interp_qlpc: [0.0; NB_ORDER],
+            synthesis_memory: [0.0; NB_ORDER],
+            highpass_memory: [0.0; 2],
+            pi_gain: [0.0; NB_SUBFRAMES],
+            dtx_enabled: false,
+            first: true,
+            seed: 1000,
+            vocoder_offset: 0,
+            vocoder_memory1: 0.0,
+            vocoder_memory2: 0.0,
+            vocoder_mean: 0.0,
+        }
+    }
+
+    fn decode<R: Read>(
+        &mut self,
+        bits: &mut BitReader<R>,
+        mode: usize,
+        output: &mut [f32; BAND_FRAME_SIZE],
+    ) -> Option<()> {
+        // Shift the excitation history by one frame.
+        self.excitation
+            .copy_within(BAND_FRAME_SIZE..NB_EXC_OFFSET + 6 + BAND_FRAME_SIZE, 0);
+        let frame = NB_EXC_OFFSET..NB_EXC_OFFSET + BAND_FRAME_SIZE;
+
+        let Some(submode) = &NB_SUBMODES[mode] else {
+            // Nothing was transmitted: keep the previous spectrum going with noise.
+            let mut lpc = [0.0; NB_ORDER];
+            bandwidth_expand(0.93, &self.interp_qlpc, &mut lpc);
+            let innovation_gain = compute_rms(&self.excitation[frame.clone()]);
+            for sample in &mut self.excitation[frame.clone()] {
+                *sample = random_sample(innovation_gain, &mut self.seed);
+            }
+            self.innovation = [0.0; BAND_FRAME_SIZE];
+            self.first = true;
+            output.copy_from_slice(&self.excitation[frame]);
+            iir_mem(output, &lpc, &mut self.synthesis_memory);
+            return Some(());
+        };
+
+        let mut qlsp = [0.0; NB_ORDER];
+        for (i, lsp) in qlsp.iter_mut().enumerate() {
+            *lsp = (0.25 * i as f64 + 0.25) as f32;
+        }
+        add_lsp_stage(&mut qlsp, &LSP_CDBK_NB, 0.0039062, bits)?;
+        match submode.lsp {
+            LspQuant::Full => {
+                add_lsp_stage(&mut qlsp[..5], &LSP_CDBK_NB_LOW1, 0.0019531, bits)?;
+                add_lsp_stage(&mut qlsp[..5], &LSP_CDBK_NB_LOW2, 0.00097656, bits)?;
+                add_lsp_stage(&mut qlsp[5..], &LSP_CDBK_NB_HIGH1, 0.0019531, bits)?;
+                add_lsp_stage(&mut qlsp[5..], &LSP_CDBK_NB_HIGH2, 0.00097656, bits)?;
+            }
+            LspQuant::LowBitRate => {
+                add_lsp_stage(&mut qlsp[..5], &LSP_CDBK_NB_LOW1, 0.0019531, bits)?;
+                add_lsp_stage(&mut qlsp[5..], &LSP_CDBK_NB_HIGH1, 0.0019531, bits)?;
+            }
+        }
+        if self.first {
+            self.old_qlsp = qlsp;
+        }
+
+        let open_loop_pitch = if submode.open_loop_pitch {
+            NB_PITCH_START + bits.read_bits(7)? as i32
+        } else {
+            0
+        };
+        let open_loop_pitch_coef = if submode.forced_pitch_gain {
+            (0.066667 * f64::from(bits.read_bits(4)?)) as f32
+        } else {
+            0.0
+        };
+        let open_loop_gain = (f64::from(bits.read_bits(5)?) / 3.5).exp() as f32;
+
+        if mode == 1 {
+            self.dtx_enabled = bits.read_bits(4)? == 15;
+        } else {
+            self.dtx_enabled = false;
+        }
+
+        let mut best_pitch = 40;
+        let mut best_pitch_gain = 0.0f32;
+        for sub in 0..NB_SUBFRAMES {
+            let offset = sub * SUBFRAME_SIZE;
+            let start = NB_EXC_OFFSET + offset;
+            self.excitation[start..start + SUBFRAME_SIZE].fill(0.0);
+
+            // Adaptive codebook contribution.
+            let pitch_min = if submode.open_loop_pitch {
+                open_loop_pitch
+            } else {
+                NB_PITCH_START
+            };
+            let mut pitch_excitation = [0.0; SUBFRAME_SIZE];
+            let (pitch, pitch_gain) = match submode.pitch {
+                PitchQuant::Forced => forced_pitch_unquant(
+                    &mut self.excitation,
+                    start,
+                    &mut pitch_excitation,
+                    pitch_min,
+                    open_loop_pitch_coef,
+                ),
+                PitchQuant::ThreeTap {
+                    gains,
+                    gain_bits,
+                    pitch_bits,
+                } => {
+                    let pitch = pitch_min + bits.read_bits(pitch_bits)? as i32;
+                    let index = bits.read_index(gain_bits)? * 4;
+                    let gain = std::array::from_fn(|i| {
+                        (0.015625 * f64::from(gains[index + i]) + 0.5) as f32
+                    });
+                    three_tap_pitch_unquant(
+                        &self.excitation,
+                        start,
+                        &mut pitch_excitation,
+                        pitch,
+                        gain,
+                    );
+                    (pitch, gain)
+                }
+            };
+
+            // Don't let a crafted stream blow up the synthesis.
+            for sample in &mut pitch_excitation {
+                if sample.is_nan() {
+                    *sample = 0.0;
+                } else {
+                    *sample = sample.clamp(-MAX_EXCITATION, MAX_EXCITATION);
+                }
+            }
+
+            let gain = gain_3tap_to_1tap(pitch_gain);
+            let close_to_multiple = |a: i32, b: i32| {
+                (a * 2 - b).abs() < 3 || (a * 3 - b).abs() < 4 || (a * 4 - b).abs() < 5
+            };
+            if (gain > best_pitch_gain && !close_to_multiple(best_pitch, pitch))
+                || (f64::from(gain) > 0.6 * f64::from(best_pitch_gain)
+                    && close_to_multiple(pitch, best_pitch))
+                || (0.67 * f64::from(gain) > f64::from(best_pitch_gain)
+                    && close_to_multiple(best_pitch, pitch))
+            {
+                best_pitch = pitch;
+                if gain > best_pitch_gain {
+                    best_pitch_gain = gain;
+                }
+            }
+
+            // Fixed codebook contribution.
+            let energy = match submode.subframe_gain_bits {
+                3 => EXC_GAIN_QUANT_SCAL3[bits.read_index(3)?] * open_loop_gain,
+                1 => EXC_GAIN_QUANT_SCAL1[bits.read_index(1)?] * open_loop_gain,
+                _ => open_loop_gain,
+            };
+            let mut innovation = [0.0; SUBFRAME_SIZE];
+            match &submode.innovation {
+                Innovation::Noise => {
+                    for sample in &mut innovation {
+                        *sample = random_sample(1.0, &mut self.seed);
+                    }
+                }
+                Innovation::Split(codebook) => {
+                    split_codebook_unquant(&mut innovation, codebook, bits)?;
+                }
+            }
+            for sample in &mut innovation {
+                *sample *= energy;
+            }
+            if submode.double_codebook {
+                let Innovation::Split(codebook) = &submode.innovation else {
+                    unreachable!("Only split codebooks are used twice");
+                };
+                let mut innovation2 = [0.0; SUBFRAME_SIZE];
+                split_codebook_unquant(&mut innovation2, codebook, bits)?;
+                let scale = 0.454545 * energy;
+                for (sample, sample2) in innovation.iter_mut().zip(innovation2) {
+                    *sample += scale * sample2;
+                }
+            }
+
+            let excitation = &mut self.excitation[start..start + SUBFRAME_SIZE];
+            for ((sample, pitch), innovation) in
+                excitation.iter_mut().zip(pitch_excitation).zip(innovation)
+            {
+                *sample = pitch + innovation;
+            }
+            self.innovation[offset..offset + SUBFRAME_SIZE].copy_from_slice(&innovation);
+
+            if mode == 1 {
+                self.vocoder(
+                    start,
+                    &innovation,
+                    open_loop_pitch,
+                    open_loop_pitch_coef,
+                    open_loop_gain,
+                );
+            }
+        }
+
+        // The output lags the excitation by one subframe, giving the enhancer
+        // some lookahead.
+        match submode.comb_gain {
+            Some(comb_gain) => {
+                let (first_half, second_half) = output.split_at_mut(2 * SUBFRAME_SIZE);
+                let start = NB_EXC_OFFSET - SUBFRAME_SIZE;
+                multicomb(&self.excitation, start, first_half, best_pitch, comb_gain);
+                let start = NB_EXC_OFFSET + SUBFRAME_SIZE;
+                multicomb(&self.excitation, start, second_half, best_pitch, comb_gain);
+            }
+            None => {
+                let start = NB_EXC_OFFSET - SUBFRAME_SIZE;
+                output.copy_from_slice(&self.excitation[start..start + BAND_FRAME_SIZE]);
+            }
+        }
+
+        let mut interp_qlsp = [0.0; NB_ORDER];
+        let mut ak = [0.0; NB_ORDER];
+        for (sub, subframe) in output.chunks_exact_mut(SUBFRAME_SIZE).enumerate() {
+            lsp_interpolate(&self.old_qlsp, &qlsp, &mut interp_qlsp, sub, NB_LSP_MARGIN);
+            lsp_to_lpc(&interp_qlsp, &mut ak);
+
+            // The response of the filter at 4kHz, which the high band matches.
+            let mut pi_gain = 1.0f32;
+            for pair in ak.chunks_exact(2) {
+                pi_gain += pair[1] - pair[0];
+            }
+            self.pi_gain[sub] = pi_gain;
+
+            iir_mem(subframe, &self.interp_qlpc, &mut self.synthesis_memory);
+            self.interp_qlpc = ak;
+        }
+
+        highpass(output, &mut self.highpass_memory);
+
+        self.old_qlsp = qlsp;
+        self.first = false;
+        Some(())
+    }
+
+    /// Replaces the excitation of a subframe with a pulse train at the pitch
+    /// period mixed with noise, for the comfort noise mode.
+    fn vocoder(
+        &mut self,
+        start: usize,
+        innovation: &[f32; SUBFRAME_SIZE],
+        pitch: i32,
+        pitch_coef: f32,
+        gain: f32,
+    ) {
+        let g = (1.5f32 * (pitch_coef - 0.2)).clamp(0.0, 1.0);
+        let excitation = &mut self.excitation[start..start + SUBFRAME_SIZE];
+        excitation.fill(0.0);
+        while self.vocoder_offset < SUBFRAME_SIZE as i32 {
+            if self.vocoder_offset >= 0 {
+                let pulse = f64::from(2 * pitch).sqrt() as f32;
+                excitation[self.vocoder_offset as usize] = pulse * (g * gain);
+            }
+            self.vocoder_offset += pitch;
+        }
+        self.vocoder_offset -= SUBFRAME_SIZE as i32;
+
+        for (sample, &innovation) in excitation.iter_mut().zip(innovation) {
+            let pulse = *sample;
+            *sample = (0.7 * pulse + 0.3 * self.vocoder_memory1)
+                + ((1.0 - 0.85 * g) * innovation - (0.15 * g) * self.vocoder_memory2);
+            self.vocoder_memory1 = pulse;
+            self.vocoder_memory2 = innovation;
+            self.vocoder_mean = 0.8 * self.vocoder_mean + 0.2 * *sample;
+            *sample -= self.vocoder_mean;
+        }
+    }
+}
+
+/// Adds one stage of a multi-stage LSP codebook, read from the bitstream.
+fn add_lsp_stage<R: Read>(
+    lsp: &mut [f32],
+    codebook: &[i8],
+    scale: f64,
+    bits: &mut BitReader<R>,
+) -> Option<()> {
+    let index = bits.read_index(6)? * lsp.len();
+    for (lsp, &value) in lsp.iter_mut().zip(&codebook[index..]) {
+        *lsp = (f64::from(*lsp) + scale * f64::from(value)) as f32;
+    }
+    Some(())
+}
+
+fn split_codebook_unquant<R: Read>(
+    innovation: &mut [f32; SUBFRAME_SIZE],
+    codebook: &SplitCodebook,
+    bits: &mut BitReader<R>,
+) -> Option<()> {
+    let mut vectors = [(false, 0); 8];
+    for (negative, index) in &mut vectors[..codebook.num_subvectors] {
+        *negative = codebook.has_sign && bits.read_bit()?;
+        *index = bits.read_index(codebook.shape_bits)?;
+    }
+    let size = codebook.subvector_size;
+    for (subvector, (negative, index)) in innovation.chunks_exact_mut(size).zip(vectors) {
+        let sign = if negative { -1.0 } else { 1.0 };
+        let shape = &codebook.shapes[index * size..(index + 1) * size];
+        for (sample, &value) in subvector.iter_mut().zip(shape) {
+            *sample = sign * f32::from(value) / 32.0;
+        }
+    }
+    Some(())
+}
+
+fn three_tap_pitch_unquant(
+    excitation: &[f32],
+    start: usize,
+    output: &mut [f32; SUBFRAME_SIZE],
+    pitch: i32,
+    gain: [f32; 3],
+) {
+    let pitch = pitch as usize;
+    for i in 0..3 {
+        let delay = pitch + 1 - i;
+        let end = SUBFRAME_SIZE.min(delay);
+        for (j, sample) in output.iter_mut().enumerate().take(end) {
+            *sample += gain[2 - i] * excitation[start + j - delay];
+        }
+        // Periods shorter than the subframe repeat themselves.
+        let end2 = SUBFRAME_SIZE.min(delay + pitch);
+        for (j, sample) in output.iter_mut().enumerate().take(end2).skip(end) {
+            *sample += gain[2 - i] * excitation[start + j - delay - pitch];
+        }
+    }
+}
+
+fn forced_pitch_unquant(
+    excitation: &mut [f32],
+    start: usize,
+    output: &mut [f32; SUBFRAME_SIZE],
+    pitch: i32,
+    coef: f32,
+) -> (i32, [f32; 3]) {
+    let coef = if f64::from(coef) > 0.99 { 0.99 } else { coef };
+    for (i, sample) in output.iter_mut().enumerate() {
+        *sample = excitation[start + i - pitch as usize] * coef;
+        excitation[start + i] = *sample;
+    }
+    (pitch, [0.0, coef, 0.0])
+}
+
+fn gain_3tap_to_1tap(gain: [f32; 3]) -> f32 {
+    let side = |gain: f32| {
+        if gain > 0.0 {
+            f64::from(gain)
+        } else {
+            -0.5 * f64::from(gain)
+        }
+    };
+    (f64::from(gain[1].abs()) + side(gain[0]) + side(gain[2])) as f32
+}
+
+/// Enhances the periodicity of the excitation with a comb filter at the pitch
+/// period, keeping its energy the same.
+fn multicomb(buffer: &[f32], start: usize, output: &mut [f32], pitch: i32, comb_gain: f32) {
+    const LEN: usize = 2 * SUBFRAME_SIZE;
+    const MAX_PITCH: i32 = 40;
+    let excitation = &buffer[start..start + LEN];
+
+    let mut interp0 = [0.0; LEN];
+    let mut interp1 = [0.0; LEN];
+    interp_pitch(buffer, start, &mut interp0, pitch);
+    if pitch > MAX_PITCH {
+        interp_pitch(buffer, start, &mut interp1, 2 * pitch);
+    } else {
+        interp_pitch(buffer, start, &mut interp1, -pitch);
+    }
+
+    let magnitude = |energy: f32| f64::from(energy).sqrt() as f32;
+    let interp0_mag = magnitude(1000.0 + inner_prod(&interp0, &interp0));
+    let interp1_mag = magnitude(1000.0 + inner_prod(&interp1, &interp1));
+    let excitation_mag = magnitude(1.0 + inner_prod(excitation, excitation));
+    let corr0 = inner_prod(&interp0, excitation).max(0.0);
+    let corr1 = inner_prod(&interp1, excitation).max(0.0);
+    let pitch_gain = |corr: f32, interp_mag: f32| {
+        if corr > interp_mag * excitation_mag {
+            1.0
+        } else {
+            corr / excitation_mag / interp_mag
+        }
+    };
+    let pgain1 = pitch_gain(corr0, interp0_mag);
+    let pgain2 = pitch_gain(corr1, interp1_mag);
+    let gg1 = excitation_mag / interp0_mag;
+    let gg2 = excitation_mag / interp1_mag;
+
+    let c1 = (0.4 * f64::from(comb_gain) + 0.07) as f32;
+    let c2 = (0.5 + 1.72 * (f64::from(c1) - 0.07)) as f32;
+    let g1 = c1 / (1.0 - c2 * pgain1 * pgain1).max(c1);
+    let g2 = c1 / (1.0 - c2 * pgain2 * pgain2).max(c1);
+    let (gain0, gain1) = if pitch > MAX_PITCH {
+        (0.7 * f64::from(g1 * gg1), 0.3 * f64::from(g2 * gg2))
+    } else {
+        (0.6 * f64::from(g1 * gg1), 0.6 * f64::from(g2 * gg2))
+    };
+    let (gain0, gain1) = (gain0 as f32, gain1 as f32);
+
+    for (i, sample) in output.iter_mut().enumerate() {
+        *sample = excitation[i] + (gain0 * interp0[i] + gain1 * interp1[i]);
+    }
+
+    let new_energy = compute_rms(output).max(1.0);
+    let old_energy = compute_rms(excitation).max(1.0).min(new_energy);
+    let gain = old_energy / new_energy;
+    for sample in output {
+        *sample *= gain;
+    }
+}
+
+/// Finds the fractional pitch period around `pitch` that best matches the
+/// excitation at `start`, and writes the excitation delayed by that period.
+/// A negative `pitch` looks ahead instead.
+fn interp_pitch(buffer: &[f32], start: usize, output: &mut [f32], pitch: i32) {
+    let len = output.len();
+    let excitation = &buffer[start..start + len];
+    let at = |offset: isize| (start as isize + offset) as usize;
+
+    let mut corr = [[0.0f32; 7]; 4];
+    for (i, corr) in corr[0].iter_mut().enumerate() {
+        let delayed = at(i as isize - pitch as isize - 3);
+        *corr = inner_prod(excitation, &buffer[delayed..delayed + len]);
+    }
+    for i in 0..3 {
+        for j in 0..7 {
+            let mut sum = 0.0f32;
+            for k in 3usize.saturating_sub(j)..(10 - j).min(7) {
+                sum += SHIFT_FILT[i][k] * corr[0][j + k - 3];
+            }
+            corr[i + 1][j] = sum;
+        }
+    }
+
+    let (mut max_i, mut max_j) = (0, 0);
+    let mut max_corr = corr[0][0];
+    for (i, row) in corr.iter().enumerate() {
+        for (j, &value) in row.iter().enumerate() {
+            if value > max_corr {
+                max_corr = value;
+                max_i = i;
+                max_j = j;
+            }
+        }
+    }
+
+    let delayed = at(max_j as isize - pitch as isize - 3);
+    for (i, sample) in output.iter_mut().enumerate() {
+        *sample = if max_i > 0 {
+            let mut sum = 0.0f32;
+            for (k, &coef) in SHIFT_FILT[max_i - 1].iter().enumerate() {
+                sum += buffer[delayed + i + k - 3] * coef;
+            }
+            sum
+        } else {
+            buffer[delayed + i]
+        };
+    }
+}
+
+fn inner_prod(x: &[f32], y: &[f32]) -> f32 {
+    let mut sum = 0.0f32;
+    for (x, y) in x.chunks_exact(4).zip(y.chunks_exact(4)) {
+        let mut part = 0.0f32;
+        for (x, y) in x.iter().zip(y) {
+            part += x * y;
+        }
+        sum += part;
+    }
+    sum
+}
+
+fn compute_rms(samples: &[f32]) -> f32 {
+    let mut sum = 0.0f32;
+    for sample in samples {
+        sum += sample * sample;
+    }
+    (0.1 + f64::from(sum / samples.len() as f32)).sqrt() as f32
+}
+
+/// Returns white noise with the given standard deviation.
+fn random_sample(std_dev: f32, seed: &mut u32) -> f32 {
+    *seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+    let value = f32::from_bits(0x3f80_0000 | (*seed & 0x007f_ffff)) - 1.5;
+    (3.4642 * f64::from(std_dev) * f64::from(value)) as f32
+}
+
+/// Widens the bandwidth of the formants of an LPC filter.
+fn bandwidth_expand(gamma: f32, input: &[f32], output: &mut [f32]) {
+    let mut factor = gamma;
+    for (output, input) in output.iter_mut().zip(input) {
+        *output = factor * input;
+        factor *= gamma;
+    }
+}
+
+/// Applies the all-pole synthesis filter `1 / A(z)` in place.
+fn iir_mem(samples: &mut [f32], den: &[f32], memory: &mut [f32]) {
+    let order = den.len();
+    for sample in samples {
+        let value = *sample + memory[0];
+        for j in 0..order - 1 {
+            memory[j] = memory[j + 1] + den[j] * -value;
+        }
+        memory[order - 1] = den[order - 1] * -value;
+        *sample = value;
+    }
+}
+
+fn highpass(samples: &mut [f32], memory: &mut [f32; 2]) {
+    for sample in samples {
+        let input = *sample;
+        let output = HIGHPASS_NUM[0] * input + memory[0];
+        memory[0] = (memory[1] + HIGHPASS_NUM[1] * input) + -HIGHPASS_DEN[1] * output;
+        memory[1] = HIGHPASS_NUM[2] * input + -HIGHPASS_DEN[2] * output;
+        *sample = output;
+    }
+}
+
+/// Merges the low and high bands into a signal at twice their sample rate.
+fn qmf_synth(
+    low: &[f32; BAND_FRAME_SIZE],
+    high: &[f32; BAND_FRAME_SIZE],
+    output: &mut [f32; SAMPLES_PER_FRAME],
+    low_memory: &mut [f32; 32],
+    high_memory: &mut [f32; 32],
+) {
+    const N2: usize = BAND_FRAME_SIZE;
+    const M2: usize = QMF_COEFS.len() / 2;
+    let mut xx1 = [0.0f32; N2 + M2];
+    let mut xx2 = [0.0f32; N2 + M2];
+    for i in 0..N2 {
+        xx1[i] = low[N2 - 1 - i];
+        xx2[i] = high[N2 - 1 - i];
+    }
+    xx1[N2..].copy_from_slice(low_memory);
+    xx2[N2..].copy_from_slice(high_memory);
+
+    let a = &QMF_COEFS;
+    for i in (0..N2).step_by(2) {
+        let (mut y0, mut y1, mut y2, mut y3) = (0.0f32, 0.0f32, 0.0f32, 0.0f32);
+        let mut x10 = xx1[N2 - 2 - i];
+        let mut x20 = xx2[N2 - 2 - i];
+        for j in (0..M2).step_by(2) {
+            let (a0, a1) = (a[2 * j], a[2 * j + 1]);
+            let x11 = xx1[N2 - 1 + j - i];
+            let x21 = xx2[N2 - 1 + j - i];
+            y0 += a0 * (x11 - x21);
+            y1 += a1 * (x11 + x21);
+            y2 += a0 * (x10 - x20);
+            y3 += a1 * (x10 + x20);
+            let (a0, a1) = (a[2 * j + 2], a[2 * j + 3]);
+            x10 = xx1[N2 + j - i];
+            x20 = xx2[N2 + j - i];
+            y0 += a0 * (x10 - x20);
+            y1 += a1 * (x10 + x20);
+            y2 += a0 * (x11 - x21);
+            y3 += a1 * (x11 + x21);
+        }
+        output[2 * i] = 2.0 * y0;
+        output[2 * i + 1] = 2.0 * y1;
+        output[2 * i + 2] = 2.0 * y2;
+        output[2 * i + 3] = 2.0 * y3;
+    }
+
+    low_memory.copy_from_slice(&xx1[..M2]);
+    high_memory.copy_from_slice(&xx2[..M2]);
+}
+
+/// Interpolates between the LSPs of the previous and current frames, keeping
+/// them apart from each other to keep the filter stable.
+fn lsp_interpolate(old: &[f32], new: &[f32], output: &mut [f32], subframe: usize, margin: f32) {
+    let factor = (1.0 + subframe as f32) / NB_SUBFRAMES as f32;
+    for ((output, old), new) in output.iter_mut().zip(old).zip(new) {
+        *output = (1.0 - factor) * old + factor * new;
+    }
+
+    let len = output.len();
+    if output[0] < margin {
+        output[0] = margin;
+    }
+    if f64::from(output[len - 1]) > PI - f64::from(margin) {
+        output[len - 1] = (PI - f64::from(margin)) as f32;
+    }
+    for i in 1..len - 1 {
+        if output[i] < output[i - 1] + margin {
+            output[i] = output[i - 1] + margin;
+        }
+        if output[i] > output[i + 1] - margin {
+            output[i] = 0.5 * (output[i] + output[i + 1] - margin);
+        }
+    }
+}
+
+/// A polynomial approximation of the cosine, as used by the reference decoder.
+fn lsp_cos(x: f32) -> f32 {
+    const C1: f32 = 0.9999933;
+    const C2: f32 = -0.49991244;
+    const C3: f32 = 0.041487746;
+    const C4: f32 = -0.0012712095;
+    if x < std::f32::consts::FRAC_PI_2 {
+        let x = x * x;
+        C1 + x * (C2 + x * (C3 + C4 * x))
+    } else {
+        let x = (PI - f64::from(x)) as f32;
+        let x = x * x;
+        -(C1 + x * (C2 + x * (C3 + C4 * x)))
+    }
+}
+
+/// Converts line spectral pairs to the coefficients of the LPC filter.
+fn lsp_to_lpc(freq: &[f32], ak: &mut [f32]) {
+    let order = freq.len();
+    let m = order / 2;
+    let mut x_freq = [0.0f32; NB_ORDER];
+    for (x, &freq) in x_freq.iter_mut().zip(freq) {
+        *x = lsp_cos(freq);
+    }
+
+    let mut w = [0.0f32; 2 * NB_ORDER + 2];
+    let mut xin1 = 1.0f32;
+    let mut xin2 = 1.0f32;
+    for j in 0..=order {
+        for i in 0..m {
+            let n = 4 * i;
+            let xout1 = xin1 - 2.0 * x_freq[2 * i] * w[n] + w[n + 1];
+            let xout2 = xin2 - 2.0 * x_freq[2 * i + 1] * w[n + 2] + w[n + 3];
+            w[n + 1] = w[n];
+            w[n + 3] = w[n + 2];
+            w[n] = xin1;
+            w[n + 2] = xin2;
+            xin1 = xout1;
+            xin2 = xout2;
+        }
+        let xout1 = xin1 + w[4 * m];
+        let xout2 = xin2 - w[4 * m + 1];
+        if j > 0 {
+            ak[j - 1] = (xout1 + xout2) * 0.5;
+        }
+        w[4 * m] = xin1;
+        w[4 * m + 1] = xin2;
+        xin1 = 0.0;
+        xin2 = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[derive(Default)]
+    struct BitWriter {
+        data: Vec<u8>,
+        num_bits: usize,
+    }
+
+    impl BitWriter {
+        fn write(&mut self, value: u32, num_bits: u32) {
+            for i in (0..num_bits).rev() {
+                if self.num_bits.is_multiple_of(8) {
+                    self.data.push(0);
+                }
+                if value.checked_shr(i).unwrap_or(0) & 1 != 0 {
+                    *self.data.last_mut().unwrap() |= 0x80 >> (self.num_bits % 8);
+                }
+                self.num_bits += 1;
+            }
+        }
+
+        /// Pads the packet like `speex_bits_insert_terminator` does.
+        fn finish(mut self) -> Vec<u8> {
+            if !self.num_bits.is_multiple_of(8) {
+                self.write(0, 1);
+            }
+            while !self.num_bits.is_multiple_of(8) {
+                self.write(1, 1);
+            }
+            self.data
+        }
+    }
+
+    /// Decodes a stream of packets that are each prefixed with their size in bytes,
+    /// the same way the packets of separate FLV audio tags get decoded.
+    fn decode_packets(mut data: &[u8]) -> Vec<i16> {
+        let mut decoder: Option<SpeexDecoder<Cursor<&[u8]>>> = None;
+        let mut samples = vec![];
+        while let Some((&size, rest)) = data.split_first() {
+            let (packet, rest) = rest.split_at(size.into());
+            data = rest;
+            match &mut decoder {
+                Some(decoder) => decoder.next_packet(Cursor::new(packet)),
+                None => decoder = Some(SpeexDecoder::new(Cursor::new(packet))),
+            }
+            samples.extend(decoder.as_mut().unwrap().by_ref().map(|[sample, _]| sample));
+        }
+        samples
+    }
+
+    /// Compares the output with the output of the libspeex floating point decoder,
+    /// which encoded the fixtures in the first place. The samples may be off by one
+    /// to leave some room for differences in the platform's `exp`.
+    fn assert_matches_reference(speex: &[u8], pcm: &[u8]) {
+        let expected: Vec<i16> = pcm
+            .chunks_exact(2)
+            .map(|bytes| i16::from_le_bytes([bytes[0], bytes[1]]))
+            .collect();
+        let samples = decode_packets(speex);
+        assert_eq!(samples.len(), expected.len());
+        for (i, (sample, expected)) in samples.into_iter().zip(expected).enumerate() {
+            assert!(
+                sample.abs_diff(expected) <= 1,
+                "Sample {i} is {sample}, expected {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn decodes_wideband() {
+        assert_matches_reference(
+            include_bytes!("speex/testdata/wideband_q6.speex"),
+            include_bytes!("speex/testdata/wideband_q6.pcm"),
+        );
+    }
+
+    #[test]
+    fn decodes_wideband_vbr() {
+        assert_matches_reference(
+            include_bytes!("speex/testdata/wideband_q8_vbr.speex"),
+            include_bytes!("speex/testdata/wideband_q8_vbr.pcm"),
+        );
+    }
+
+    #[test]
+    fn decodes_multiple_frames_per_packet() {
+        assert_matches_reference(
+            include_bytes!("speex/testdata/wideband_q10_two_frames.speex"),
+            include_bytes!("speex/testdata/wideband_q10_two_frames.pcm"),
+        );
+    }
+
+    #[test]
+    fn decodes_comfort_noise() {
+        assert_matches_reference(
+            include_bytes!("speex/testdata/wideband_q2_dtx.speex"),
+            include_bytes!("speex/testdata/wideband_q2_dtx.pcm"),
+        );
+    }
+
+    #[test]
+    fn decodes_narrowband() {
+        assert_matches_reference(
+            include_bytes!("speex/testdata/narrowband_q3.speex"),
+            include_bytes!("speex/testdata/narrowband_q3.pcm"),
+        );
+    }
+
+    #[test]
+    fn skips_in_band_signalling() {
+        let mut writer = BitWriter::default();
+        // Narrowband frame with nothing transmitted.
+        writer.write(0, 5);
+        // In-band signal with a 4-bit payload.
+        writer.write(14, 5);
+        writer.write(4, 4);
+        writer.write(0b1010, 4);
+        // User in-band request with a 1-byte payload.
+        writer.write(13, 5);
+        writer.write(1, 4);
+        writer.write(0, 5 + 8);
+        writer.write(0, 5);
+        // Terminator, followed by another frame.
+        writer.write(15, 5);
+        while !writer.num_bits.is_multiple_of(8) {
+            writer.write(1, 1);
+        }
+        writer.write(0, 5);
+        let decoder = SpeexDecoder::new(Cursor::new(writer.finish()));
+        assert_eq!(decoder.sample_rate(), 16_000);
+        assert_eq!(decoder.count(), 3 * SAMPLES_PER_FRAME);
+    }
+}
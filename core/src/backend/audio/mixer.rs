@@ -1,5 +1,6 @@
 use super::decoders::{
-    self, AdpcmDecoder, Decoder, G711ALawDecoder, G711MuLawDecoder, PcmDecoder, SeekableDecoder,
+    self, AdpcmDecoder, Decoder, FloatPcmDecoder, G711ALawDecoder, G711MuLawDecoder, PcmDecoder,
+    SeekableDecoder,
 };
 use super::{SoundHandle, SoundInstanceHandle, SoundStreamInfo, SoundTransform};
 use crate::backend::audio::{DecodeError, RegisterError};
@@ -149,9 +150,14 @@ struct Sound {
 
     /// The audio data of this sound.
     ///
-    /// This will be compressed in the format indicated by `format.compression`.
+    /// This will be compressed in the format indicated by `format.compression`, unless
+    /// `is_float_pcm` is set.
     data: Arc<[u8]>,
 
+    /// Whether `data` holds little-endian 32-bit float PCM samples, which no SWF sound format
+    /// can store without losing precision.
+    is_float_pcm: bool,
+
     /// Number of samples in this audio.
     /// This does not include `skip_sample_frames`.
     num_sample_frames: u32,
@@ -162,6 +168,20 @@ struct Sound {
     /// `skip_sample_frames` indicates how many sample frames to skip to bypass the delay.
     /// This is `0` unless `format.compression` is `AudioCompression::Mp3`.
     skip_sample_frames: u16,
+
+    /// The stream used by the last extraction of this sound.
+    ///
+    /// This is kept so that consecutive extractions don't have to decode the sound from the start.
+    extractor: Option<SoundExtractor>,
+}
+
+/// A decoded stream of a sound used to extract its samples.
+struct SoundExtractor {
+    /// The stream, resampled to `AudioMixer::EXTRACT_SAMPLE_RATE`.
+    stream: Box<dyn dasp::signal::Signal<Frame = [f32; 2]> + Send + Sync>,
+
+    /// The position of the next sample frame of `stream`.
+    position: usize,
 }
 
 /// An actively playing instance of a sound.
@@ -239,6 +259,9 @@ impl SoundInstance {
 }
 
 impl AudioMixer {
+    /// The sample rate of the samples returned by `AudioMixer::extract_sound`.
+    pub const EXTRACT_SAMPLE_RATE: u32 = 44100;
+
     /// Creates a new `AudioMixer` with the given number of channels and sample rate.
     pub fn new(num_output_channels: u8, output_sample_rate: u32) -> Self {
         Self {
@@ -336,18 +359,39 @@ impl AudioMixer {
         Ok(decoder)
     }
 
+    /// Instantiate a seekable decoder for the audio data of a registered sound.
+    fn make_sound_decoder(
+        sound: &Sound,
+        data: Cursor<ArcAsRef>,
+    ) -> Result<Box<dyn SeekableDecoder>, decoders::Error> {
+        if sound.is_float_pcm {
+            Ok(Box::new(FloatPcmDecoder::new(
+                data,
+                sound.format.is_stereo,
+                sound.format.sample_rate,
+            )))
+        } else {
+            Self::make_seekable_decoder(&sound.format, data)
+        }
+    }
+
     /// Transforms a `Stream` into a new `Stream` that matches the output sample rate.
-    fn make_resampler<S: Stream>(&self, mut stream: S) -> impl Stream + use<S> {
+    fn make_resampler<S: Stream>(&self, stream: S) -> impl Stream + use<S> {
+        Self::resample(stream, self.output_sample_rate)
+    }
+
+    /// Transforms a `Stream` into a new `Stream` with the given sample rate.
+    fn resample<S: Stream>(mut stream: S, sample_rate: u32) -> impl Stream + use<S> {
         // TODO: Allow interpolator to be user-configurable?
         let left = stream.next();
         let right = stream.next();
         let interpolator = dasp::interpolate::linear::Linear::new(left, right);
-        let sample_rate = stream.source_sample_rate().into();
+        let source_sample_rate = stream.source_sample_rate().into();
         ConverterStream(dasp::signal::interpolate::Converter::from_hz_to_hz(
             stream,
             interpolator,
-            sample_rate,
-            self.output_sample_rate.into(),
+            source_sample_rate,
+            sample_rate.into(),
         ))
     }
 
@@ -362,7 +406,7 @@ impl AudioMixer {
         data: Cursor<ArcAsRef>,
     ) -> Result<Box<dyn Stream>, DecodeError> {
         // Instantiate a decoder for the compression that the sound data uses.
-        let decoder = Self::make_seekable_decoder(&sound.format, data)?;
+        let decoder = Self::make_sound_decoder(sound, data)?;
 
         // Wrap the decoder into an event sound stream (controls looping/envelope)
         let stream = EventSoundStream::new_with_settings(
@@ -525,8 +569,10 @@ impl AudioMixer {
         let sound = Sound {
             format: swf_sound.format.clone(),
             data: Arc::from(data),
+            is_float_pcm: false,
             num_sample_frames: swf_sound.num_samples,
             skip_sample_frames,
+            extractor: None,
         };
         Ok(self.sounds.insert(sound))
     }
@@ -545,8 +591,10 @@ impl AudioMixer {
                 is_16_bit: true,
             },
             data,
+            is_float_pcm: false,
             num_sample_frames: metadata.num_sample_frames,
             skip_sample_frames: 0,
+            extractor: None,
         };
         Ok(self.sounds.insert(sound))
     }
@@ -556,6 +604,36 @@ impl AudioMixer {
         Err(decoders::Error::UnhandledCompression(AudioCompression::Mp3))
     }
 
+    /// Registers 32-bit float PCM samples with the audio mixer.
+    ///
+    /// The samples of stereo sounds are interleaved.
+    pub fn register_float_pcm(
+        &mut self,
+        samples: &[f32],
+        sample_rate: u16,
+        is_stereo: bool,
+    ) -> Result<SoundHandle, RegisterError> {
+        let num_channels = if is_stereo { 2 } else { 1 };
+        let data: Vec<u8> = samples
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect();
+        let sound = Sound {
+            format: swf::SoundFormat {
+                compression: AudioCompression::Uncompressed,
+                sample_rate,
+                is_stereo,
+                is_16_bit: false,
+            },
+            data: Arc::from(data),
+            is_float_pcm: true,
+            num_sample_frames: (samples.len() / num_channels) as u32,
+            skip_sample_frames: 0,
+            extractor: None,
+        };
+        Ok(self.sounds.insert(sound))
+    }
+
     /// Starts a timeline audio stream.
     pub fn start_stream(
         &mut self,
@@ -587,6 +665,7 @@ impl AudioMixer {
         let data = Cursor::new(ArcAsRef(Arc::clone(&sound.data)));
         // Create a stream that decodes and resamples the sound.
         let stream = if sound.skip_sample_frames == 0
+            && !sound.is_float_pcm
             && settings.in_sample.is_none()
             && settings.out_sample.is_none()
             && settings.num_loops <= 1
@@ -683,6 +762,97 @@ impl AudioMixer {
         }
     }
 
+    /// Decodes `num_frames` sample frames of the given sound, starting at `position`.
+    ///
+    /// Both `position` and the returned samples are at `Self::EXTRACT_SAMPLE_RATE`, in stereo.
+    /// Fewer frames are returned if the end of the sound is reached.
+    pub fn extract_sound(
+        &mut self,
+        sound: SoundHandle,
+        position: usize,
+        num_frames: usize,
+    ) -> Result<Vec<[f32; 2]>, DecodeError> {
+        use dasp::signal::Signal;
+
+        let Some(sound) = self.sounds.get_mut(sound) else {
+            return Ok(vec![]);
+        };
+        if sound.format.sample_rate == 0 {
+            return Ok(vec![]);
+        }
+        let length = (u64::from(sound.num_sample_frames) * u64::from(Self::EXTRACT_SAMPLE_RATE)
+            / u64::from(sound.format.sample_rate)) as usize;
+        let end = length.min(position.saturating_add(num_frames));
+        if position >= end {
+            return Ok(vec![]);
+        }
+
+        // Reuse the previous extractor unless it has already passed the requested position.
+        let mut extractor = match sound.extractor.take() {
+            Some(extractor) if extractor.position <= position => extractor,
+            _ => SoundExtractor {
+                stream: Self::make_extraction_stream(sound)?,
+                position: 0,
+            },
+        };
+        for _ in extractor.position..position {
+            extractor.stream.next();
+        }
+        let frames = (position..end).map(|_| extractor.stream.next()).collect();
+        extractor.position = end;
+        sound.extractor = Some(extractor);
+        Ok(frames)
+    }
+
+    /// Creates a `Stream` that decodes the whole sound once, resampled for extraction.
+    fn make_extraction_stream(
+        sound: &Sound,
+    ) -> Result<Box<dyn dasp::signal::Signal<Frame = [f32; 2]> + Send + Sync>, DecodeError> {
+        use dasp::Sample;
+        use dasp::signal::Signal;
+
+        let data = Cursor::new(ArcAsRef(Arc::clone(&sound.data)));
+
+        if sound.is_float_pcm {
+            // Resample the float samples as they are, rather than through the 16-bit
+            // samples used for playback.
+            let mut decoder =
+                FloatPcmDecoder::new(data, sound.format.is_stereo, sound.format.sample_rate);
+            let mut signal =
+                dasp::signal::from_iter(std::iter::from_fn(move || decoder.read_frame()));
+            let left = signal.next();
+            let right = signal.next();
+            let interpolator = dasp::interpolate::linear::Linear::new(left, right);
+            return Ok(Box::new(
+                dasp::signal::interpolate::Converter::from_hz_to_hz(
+                    signal,
+                    interpolator,
+                    sound.format.sample_rate.into(),
+                    Self::EXTRACT_SAMPLE_RATE.into(),
+                ),
+            ));
+        }
+
+        let decoder = Self::make_seekable_decoder(&sound.format, data)?;
+        let settings = swf::SoundInfo {
+            event: swf::SoundEvent::Start,
+            in_sample: None,
+            out_sample: None,
+            num_loops: 1,
+            envelope: None,
+        };
+        let stream = EventSoundStream::new_with_settings(
+            decoder,
+            &settings,
+            sound.num_sample_frames,
+            sound.skip_sample_frames,
+        );
+        let stream = Self::resample(stream, Self::EXTRACT_SAMPLE_RATE);
+        Ok(Box::new(stream.map(|[left, right]: [i16; 2]| {
+            [left.to_sample::<f32>(), right.to_sample::<f32>()]
+        })))
+    }
+
     pub fn get_sound_size(&self, sound: SoundHandle) -> Option<u32> {
         self.sounds.get(sound).map(|s| s.data.len() as u32)
     }
@@ -1083,6 +1253,17 @@ macro_rules! impl_audio_mixer_backend {
             self.$mixer.register_mp3(data)
         }
 
+        #[inline]
+        fn register_float_pcm(
+            &mut self,
+            samples: &[f32],
+            sample_rate: u16,
+            is_stereo: bool,
+        ) -> Result<SoundHandle, RegisterError> {
+            self.$mixer
+                .register_float_pcm(samples, sample_rate, is_stereo)
+        }
+
         #[inline]
         fn start_stream(
             &mut self,
@@ -1130,6 +1311,16 @@ macro_rules! impl_audio_mixer_backend {
             self.$mixer.get_sound_duration(sound)
         }
 
+        #[inline]
+        fn extract_sound(
+            &mut self,
+            sound: SoundHandle,
+            position: usize,
+            num_frames: usize,
+        ) -> Result<Vec<[f32; 2]>, DecodeError> {
+            self.$mixer.extract_sound(sound, position, num_frames)
+        }
+
         #[inline]
         fn get_sound_size(&self, sound: SoundHandle) -> Option<u32> {
             self.$mixer.get_sound_size(sound)
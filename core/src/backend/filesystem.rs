@@ -0,0 +1,518 @@
+//! Sandboxed filesystem used by the AIR `flash.filesystem` APIs.
+//!
+//! Content never sees host paths. Every path is relative to one of the
+//! well-known AIR directories, and frontends decide where (and whether)
+//! those directories live on the host.
+
+use chrono::{DateTime, Utc};
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt;
+use thiserror::Error;
+
+/// One of the well-known directories exposed by `flash.filesystem.File`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AirDirectory {
+    /// `File.applicationDirectory`, the installed application. Read-only.
+    Application,
+
+    /// `File.applicationStorageDirectory`, private storage of the application.
+    ApplicationStorage,
+
+    /// `File.documentsDirectory`.
+    Documents,
+
+    /// `File.desktopDirectory`.
+    Desktop,
+
+    /// `File.userDirectory`.
+    User,
+}
+
+impl AirDirectory {
+    pub const ALL: [AirDirectory; 5] = [
+        AirDirectory::Application,
+        AirDirectory::ApplicationStorage,
+        AirDirectory::Documents,
+        AirDirectory::Desktop,
+        AirDirectory::User,
+    ];
+
+    /// The name of the root of this directory, as used in native paths.
+    pub fn root_name(self) -> &'static str {
+        match self {
+            AirDirectory::Application => "app",
+            AirDirectory::ApplicationStorage => "app-storage",
+            AirDirectory::Documents => "documents",
+            AirDirectory::Desktop => "desktop",
+            AirDirectory::User => "user",
+        }
+    }
+
+    /// Returns `true` if content may not modify this directory.
+    pub fn is_read_only(self) -> bool {
+        self == AirDirectory::Application
+    }
+
+    fn from_root_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|directory| directory.root_name() == name)
+    }
+}
+
+/// A normalized path inside of one of the [`AirDirectory`] roots.
+///
+/// The components never contain separators, and never contain `.` or `..`,
+/// so a path cannot escape its root.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VirtualPath {
+    directory: AirDirectory,
+    components: Vec<String>,
+}
+
+impl VirtualPath {
+    /// The root of the given directory.
+    pub fn root(directory: AirDirectory) -> Self {
+        Self {
+            directory,
+            components: Vec::new(),
+        }
+    }
+
+    /// Parses either a URL (`app:/`, `app-storage:/` or `file:///`) or a
+    /// native path, as returned by [`VirtualPath::native_path`].
+    pub fn parse(path: &str) -> Option<Self> {
+        let (directory, rest) = if let Some(rest) = path.strip_prefix("app:") {
+            (AirDirectory::Application, rest)
+        } else if let Some(rest) = path.strip_prefix("app-storage:") {
+            (AirDirectory::ApplicationStorage, rest)
+        } else {
+            let rest = path.strip_prefix("file://").unwrap_or(path);
+            let rest = rest.trim_start_matches(['/', '\\']);
+            if rest.len() == path.len() {
+                // Relative paths are not valid on their own.
+                return None;
+            }
+            let (root, rest) = rest.split_once(['/', '\\']).unwrap_or((rest, ""));
+            (AirDirectory::from_root_name(root)?, rest)
+        };
+        Some(Self::root(directory).resolve(rest))
+    }
+
+    pub fn directory(&self) -> AirDirectory {
+        self.directory
+    }
+
+    pub fn components(&self) -> &[String] {
+        &self.components
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// Resolves a path relative to this one. `..` never goes further up than
+    /// the root of the directory.
+    pub fn resolve(&self, relative: &str) -> Self {
+        let mut components = self.components.clone();
+        for component in relative.split(['/', '\\']) {
+            match component {
+                "" | "." => {}
+                ".." => {
+                    components.pop();
+                }
+                component => components.push(component.to_owned()),
+            }
+        }
+        Self {
+            directory: self.directory,
+            components,
+        }
+    }
+
+    /// Returns a path to the entry called `name` in this directory.
+    pub fn join(&self, name: &str) -> Self {
+        let mut components = self.components.clone();
+        components.push(name.to_owned());
+        Self {
+            directory: self.directory,
+            components,
+        }
+    }
+
+    pub fn parent(&self) -> Option<Self> {
+        let (_, components) = self.components.split_last()?;
+        Some(Self {
+            directory: self.directory,
+            components: components.to_vec(),
+        })
+    }
+
+    /// The last component of the path, or the name of the root directory.
+    pub fn name(&self) -> &str {
+        self.components
+            .last()
+            .map_or(self.directory.root_name(), String::as_str)
+    }
+
+    /// The extension of the file, without the dot.
+    pub fn extension(&self) -> Option<&str> {
+        let (stem, extension) = self.components.last()?.rsplit_once('.')?;
+        (!stem.is_empty()).then_some(extension)
+    }
+
+    /// Returns `true` if `self` is `other`, or is contained in `other`.
+    pub fn starts_with(&self, other: &VirtualPath) -> bool {
+        self.directory == other.directory && self.components.starts_with(&other.components)
+    }
+
+    /// The path as shown to content in `File.nativePath`.
+    pub fn native_path(&self) -> String {
+        let mut path = format!("/{}", self.directory.root_name());
+        for component in &self.components {
+            path.push('/');
+            path.push_str(component);
+        }
+        path
+    }
+
+    /// The path as shown to content in `File.url`.
+    pub fn url(&self) -> String {
+        let mut url = match self.directory {
+            AirDirectory::Application => "app:".to_owned(),
+            AirDirectory::ApplicationStorage => "app-storage:".to_owned(),
+            directory => format!("file:///{}", directory.root_name()),
+        };
+        if self.components.is_empty() && url.ends_with(':') {
+            url.push('/');
+        }
+        for component in &self.components {
+            url.push('/');
+            url.push_str(component);
+        }
+        url
+    }
+}
+
+impl fmt::Display for VirtualPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.url())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum FileSystemError {
+    #[error("File or directory not found")]
+    NotFound,
+
+    #[error("File or directory already exists")]
+    AlreadyExists,
+
+    #[error("Not a directory")]
+    NotADirectory,
+
+    #[error("Is a directory")]
+    IsADirectory,
+
+    #[error("Directory is not empty")]
+    DirectoryNotEmpty,
+
+    #[error("Directory is read-only")]
+    ReadOnly,
+
+    #[error("Cannot copy or move a directory into itself")]
+    InvalidDestination,
+
+    #[error("IO error: {0}")]
+    Io(String),
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct FileMetadata {
+    pub is_directory: bool,
+    pub size: u64,
+    pub creation_time: Option<DateTime<Utc>>,
+    pub modification_time: Option<DateTime<Utc>>,
+}
+
+/// A backend for the AIR filesystem APIs.
+///
+/// Implementations don't need to check [`AirDirectory::is_read_only`], this
+/// is done by the player before writing.
+pub trait FileSystemBackend: Any {
+    fn metadata(&self, path: &VirtualPath) -> Result<FileMetadata, FileSystemError>;
+
+    fn read(&self, path: &VirtualPath) -> Result<Vec<u8>, FileSystemError>;
+
+    /// Replaces the contents of a file, creating it and its parent
+    /// directories if needed.
+    fn write(&mut self, path: &VirtualPath, data: &[u8]) -> Result<(), FileSystemError>;
+
+    /// Returns the names of the entries of a directory.
+    fn list_directory(&self, path: &VirtualPath) -> Result<Vec<String>, FileSystemError>;
+
+    /// Creates a directory and its parent directories if needed.
+    fn create_directory(&mut self, path: &VirtualPath) -> Result<(), FileSystemError>;
+
+    /// Deletes a file or a directory. A directory that isn't empty is only
+    /// deleted when `recursive` is set.
+    fn delete(&mut self, path: &VirtualPath, recursive: bool) -> Result<(), FileSystemError>;
+
+    fn rename(&mut self, from: &VirtualPath, to: &VirtualPath) -> Result<(), FileSystemError>;
+
+    /// Copies a file, or a directory with all of its contents.
+    fn copy(&mut self, from: &VirtualPath, to: &VirtualPath) -> Result<(), FileSystemError> {
+        if to.starts_with(from) {
+            return Err(FileSystemError::InvalidDestination);
+        }
+        if !self.metadata(from)?.is_directory {
+            let data = self.read(from)?;
+            return self.write(to, &data);
+        }
+        self.create_directory(to)?;
+        for name in self.list_directory(from)? {
+            self.copy(&from.join(&name), &to.join(&name))?;
+        }
+        Ok(())
+    }
+}
+
+enum MemoryEntry {
+    Directory,
+    File(Vec<u8>),
+}
+
+/// A filesystem that only lives as long as the player, in which every
+/// directory starts out empty.
+#[derive(Default)]
+pub struct MemoryFileSystemBackend {
+    entries: BTreeMap<VirtualPath, MemoryEntry>,
+}
+
+impl MemoryFileSystemBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn entry(&self, path: &VirtualPath) -> Option<&MemoryEntry> {
+        if path.is_root() {
+            Some(&MemoryEntry::Directory)
+        } else {
+            self.entries.get(path)
+        }
+    }
+
+    fn descendants(&self, path: &VirtualPath) -> Vec<VirtualPath> {
+        self.entries
+            .keys()
+            .filter(|entry| *entry != path && entry.starts_with(path))
+            .cloned()
+            .collect()
+    }
+}
+
+impl FileSystemBackend for MemoryFileSystemBackend {
+    fn metadata(&self, path: &VirtualPath) -> Result<FileMetadata, FileSystemError> {
+        match self.entry(path) {
+            Some(MemoryEntry::Directory) => Ok(FileMetadata {
+                is_directory: true,
+                ..Default::default()
+            }),
+            Some(MemoryEntry::File(data)) => Ok(FileMetadata {
+                size: data.len() as u64,
+                ..Default::default()
+            }),
+            None => Err(FileSystemError::NotFound),
+        }
+    }
+
+    fn read(&self, path: &VirtualPath) -> Result<Vec<u8>, FileSystemError> {
+        match self.entry(path) {
+            Some(MemoryEntry::Directory) => Err(FileSystemError::IsADirectory),
+            Some(MemoryEntry::File(data)) => Ok(data.clone()),
+            None => Err(FileSystemError::NotFound),
+        }
+    }
+
+    fn write(&mut self, path: &VirtualPath, data: &[u8]) -> Result<(), FileSystemError> {
+        if let Some(MemoryEntry::Directory) = self.entry(path) {
+            return Err(FileSystemError::IsADirectory);
+        }
+        if let Some(parent) = path.parent() {
+            self.create_directory(&parent)?;
+        }
+        self.entries
+            .insert(path.clone(), MemoryEntry::File(data.to_vec()));
+        Ok(())
+    }
+
+    fn list_directory(&self, path: &VirtualPath) -> Result<Vec<String>, FileSystemError> {
+        match self.entry(path) {
+            Some(MemoryEntry::Directory) => Ok(self
+                .entries
+                .keys()
+                .filter(|entry| entry.parent().as_ref() == Some(path))
+                .map(|entry| entry.name().to_owned())
+                .collect()),
+            Some(MemoryEntry::File(_)) => Err(FileSystemError::NotADirectory),
+            None => Err(FileSystemError::NotFound),
+        }
+    }
+
+    fn create_directory(&mut self, path: &VirtualPath) -> Result<(), FileSystemError> {
+        match self.entry(path) {
+            Some(MemoryEntry::Directory) => Ok(()),
+            Some(MemoryEntry::File(_)) => Err(FileSystemError::AlreadyExists),
+            None => {
+                if let Some(parent) = path.parent() {
+                    self.create_directory(&parent)?;
+                }
+                self.entries.insert(path.clone(), MemoryEntry::Directory);
+                Ok(())
+            }
+        }
+    }
+
+    fn delete(&mut self, path: &VirtualPath, recursive: bool) -> Result<(), FileSystemError> {
+        if self.entry(path).is_none() {
+            return Err(FileSystemError::NotFound);
+        }
+        let descendants = self.descendants(path);
+        if !descendants.is_empty() && !recursive {
+            return Err(FileSystemError::DirectoryNotEmpty);
+        }
+        for descendant in descendants {
+            self.entries.remove(&descendant);
+        }
+        self.entries.remove(path);
+        Ok(())
+    }
+
+    fn rename(&mut self, from: &VirtualPath, to: &VirtualPath) -> Result<(), FileSystemError> {
+        if from.is_root() {
+            return Err(FileSystemError::ReadOnly);
+        }
+        if to.starts_with(from) {
+            return Err(FileSystemError::InvalidDestination);
+        }
+        if self.entry(from).is_none() {
+            return Err(FileSystemError::NotFound);
+        }
+        if self.entry(to).is_some() {
+            return Err(FileSystemError::AlreadyExists);
+        }
+        if let Some(parent) = to.parent() {
+            self.create_directory(&parent)?;
+        }
+
+        let mut moved = self.descendants(from);
+        moved.push(from.clone());
+        for old_path in moved {
+            let entry = self.entries.remove(&old_path).expect("Entry exists");
+            let mut new_path = to.clone();
+            new_path
+                .components
+                .extend_from_slice(&old_path.components[from.components.len()..]);
+            self.entries.insert(new_path, entry);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(path: &str) -> VirtualPath {
+        VirtualPath::parse(path).unwrap()
+    }
+
+    #[test]
+    fn parse_paths() {
+        assert_eq!(
+            path("app-storage:/saves/./slot1.dat").native_path(),
+            "/app-storage/saves/slot1.dat"
+        );
+        assert_eq!(path("app:/").url(), "app:/");
+        assert_eq!(path("app:/data/level.xml").url(), "app:/data/level.xml");
+        assert_eq!(
+            path("file:///documents/a/b").native_path(),
+            "/documents/a/b"
+        );
+        assert_eq!(path("/user/a").url(), "file:///user/a");
+        assert_eq!(path("\\desktop\\a.txt").url(), "file:///desktop/a.txt");
+        assert!(VirtualPath::parse("relative/path").is_none());
+        assert!(VirtualPath::parse("/etc/passwd").is_none());
+        assert!(VirtualPath::parse("file:///tmp").is_none());
+    }
+
+    #[test]
+    fn paths_cannot_escape_root() {
+        let root = VirtualPath::root(AirDirectory::ApplicationStorage);
+        assert_eq!(
+            root.resolve("../../etc/passwd").native_path(),
+            "/app-storage/etc/passwd"
+        );
+        assert_eq!(path("app:/../../secret").url(), "app:/secret");
+        assert_eq!(root.parent(), None);
+        assert_eq!(root.name(), "app-storage");
+    }
+
+    #[test]
+    fn names_and_extensions() {
+        let file = path("app-storage:/saves/slot1.dat");
+        assert_eq!(file.name(), "slot1.dat");
+        assert_eq!(file.extension(), Some("dat"));
+        assert_eq!(path("app-storage:/.hidden").extension(), None);
+        assert_eq!(file.parent(), Some(path("app-storage:/saves")));
+    }
+
+    #[test]
+    fn memory_backend() {
+        let mut fs = MemoryFileSystemBackend::new();
+        let file = path("app-storage:/saves/slot1.dat");
+        fs.write(&file, b"hello").unwrap();
+        assert_eq!(fs.read(&file).unwrap(), b"hello");
+        assert!(
+            fs.metadata(&path("app-storage:/saves"))
+                .unwrap()
+                .is_directory
+        );
+        assert_eq!(
+            fs.list_directory(&path("app-storage:/")).unwrap(),
+            vec!["saves".to_owned()]
+        );
+        assert!(matches!(
+            fs.delete(&path("app-storage:/saves"), false),
+            Err(FileSystemError::DirectoryNotEmpty)
+        ));
+
+        fs.rename(&path("app-storage:/saves"), &path("app-storage:/old/saves"))
+            .unwrap();
+        assert_eq!(
+            fs.read(&path("app-storage:/old/saves/slot1.dat")).unwrap(),
+            b"hello"
+        );
+        assert!(matches!(fs.read(&file), Err(FileSystemError::NotFound)));
+
+        fs.copy(&path("app-storage:/old"), &path("app-storage:/new"))
+            .unwrap();
+        assert_eq!(
+            fs.read(&path("app-storage:/new/saves/slot1.dat")).unwrap(),
+            b"hello"
+        );
+        assert!(matches!(
+            fs.copy(&path("app-storage:/new"), &path("app-storage:/new/inner")),
+            Err(FileSystemError::InvalidDestination)
+        ));
+
+        fs.delete(&path("app-storage:/old"), true).unwrap();
+        fs.delete(&path("app-storage:/new"), true).unwrap();
+        assert!(
+            fs.list_directory(&path("app-storage:/"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use crate::locale::{get_current_date_time, get_timezone};
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+use std::time::Duration;
+use web_time::Instant;
+
+/// The source of all time observed by a running movie.
+///
+/// This is consulted for `Date`, `getTimer()`, the seed of `Math.random()`,
+/// double click detection and the text caret blink.
+pub trait TimeBackend {
+    /// The current date and time.
+    fn now(&self) -> DateTime<Utc>;
+
+    /// The offset of the local timezone from UTC.
+    fn timezone(&self) -> FixedOffset;
+
+    /// A monotonic instant, used to measure elapsed time.
+    fn instant(&self) -> Instant;
+
+    /// Called by the player when `dt` of playback time has passed.
+    ///
+    /// Backends following the system clock can ignore this.
+    fn advance(&mut self, _dt: Duration) {}
+}
+
+/// Time backend that follows the system clock.
+pub struct SystemTimeBackend {}
+
+impl SystemTimeBackend {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl TimeBackend for SystemTimeBackend {
+    fn now(&self) -> DateTime<Utc> {
+        get_current_date_time()
+    }
+
+    fn timezone(&self) -> FixedOffset {
+        get_timezone()
+    }
+
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+impl Default for SystemTimeBackend {
+    fn default() -> Self {
+        SystemTimeBackend::new()
+    }
+}
+
+/// Time backend that only moves forward when the player advances it.
+///
+/// Two runs of the same movie with the same inputs observe exactly the same
+/// dates, timer values and random numbers, which makes this suitable for
+/// exporting and regression testing.
+pub struct VirtualTimeBackend {
+    start: DateTime<Utc>,
+    timezone: FixedOffset,
+    origin: Instant,
+    elapsed: Duration,
+}
+
+impl VirtualTimeBackend {
+    /// Creates a virtual clock starting at the given date, in the given timezone.
+    pub fn new(start: DateTime<Utc>, timezone: FixedOffset) -> Self {
+        Self {
+            start,
+            timezone,
+            origin: Instant::now(),
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// The amount of virtual time that has passed since this clock was created.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+impl TimeBackend for VirtualTimeBackend {
+    fn now(&self) -> DateTime<Utc> {
+        self.start + self.elapsed
+    }
+
+    fn timezone(&self) -> FixedOffset {
+        self.timezone
+    }
+
+    fn instant(&self) -> Instant {
+        self.origin + self.elapsed
+    }
+
+    fn advance(&mut self, dt: Duration) {
+        self.elapsed += dt;
+    }
+}
+
+impl Default for VirtualTimeBackend {
+    /// Starts at 2001-02-03 04:05:06 in Nepal (+5:45), matching the mocked
+    /// time used by the `deterministic` feature.
+    fn default() -> Self {
+        let timezone = FixedOffset::east_opt(20700).expect("Unambiguous mock timezone");
+        let start = timezone
+            .with_ymd_and_hms(2001, 2, 3, 4, 5, 6)
+            .single()
+            .expect("Unambiguous mock time")
+            .into();
+        Self::new(start, timezone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn virtual_time_only_moves_when_advanced() {
+        let mut time = VirtualTimeBackend::default();
+        let date = time.now();
+        let instant = time.instant();
+        assert_eq!(time.now(), date);
+        assert_eq!(time.instant(), instant);
+
+        time.advance(Duration::from_millis(1500));
+        assert_eq!(time.now() - date, chrono::TimeDelta::milliseconds(1500));
+        assert_eq!(time.instant().duration_since(instant).as_millis(), 1500);
+        assert_eq!(time.elapsed(), Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn virtual_time_default_matches_mock_time() {
+        let time = VirtualTimeBackend::default();
+        assert_eq!(time.timezone().local_minus_utc(), 20700);
+        assert_eq!(time.now().timestamp(), 981_152_406);
+    }
+}
@@ -0,0 +1,1985 @@
+//! A JPEG XR (ITU-T T.832) encoder, used by `BitmapData.encode`.
+//!
+//! This is a port of the spatial mode encoder of the jxrlib reference codec, limited to what
+//! `JPEGXREncoderOptions` can ask for: 8-bit RGB sources with optional interleaved alpha, a
+//! single tile, uniform quantization and no overlap filtering. Given the same parameters, the
+//! output is byte for byte identical to what jxrlib produces.
+
+/// The chroma subsampling requested by `JPEGXREncoderOptions.colorSpace`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JpegXrColorSpace {
+    /// Lossless encodings keep full resolution chroma, lossy ones use 4:2:0.
+    Auto,
+    Yuv444,
+    Yuv422,
+    Yuv420,
+}
+
+impl JpegXrColorSpace {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "4:4:4" => Self::Yuv444,
+            "4:2:2" => Self::Yuv422,
+            "4:2:0" => Self::Yuv420,
+            _ => Self::Auto,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct JpegXrOptions {
+    /// The quantization parameter index, where 0 is lossless.
+    pub quantization: u8,
+    pub color_space: JpegXrColorSpace,
+    /// How many of the least significant "flexbits" of each coefficient to drop.
+    pub trim_flex_bits: u8,
+}
+
+/// Encodes an image into a JPEG XR file.
+///
+/// `pixels` holds un-multiplied RGB or RGBA (when `with_alpha` is set) samples, row by row.
+pub fn encode(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    with_alpha: bool,
+    options: JpegXrOptions,
+) -> Vec<u8> {
+    let image = SourceImage::new(pixels, width as usize, height as usize, with_alpha);
+    let qp_index = options.quantization;
+    let format = match options.color_space {
+        JpegXrColorSpace::Auto if qp_index <= 1 => ColorFormat::Yuv444,
+        JpegXrColorSpace::Auto | JpegXrColorSpace::Yuv420 => ColorFormat::Yuv420,
+        JpegXrColorSpace::Yuv422 => ColorFormat::Yuv422,
+        JpegXrColorSpace::Yuv444 => ColorFormat::Yuv444,
+    };
+    let trim_flex_bits = u32::from(options.trim_flex_bits.min(15));
+
+    let mut primary = PlaneEncoder::new(format, qp_index, &image, trim_flex_bits);
+    let mut alpha =
+        with_alpha.then(|| PlaneEncoder::new(ColorFormat::YOnly, qp_index, &image, trim_flex_bits));
+
+    let mut writer = BitWriter::default();
+    write_image_header(&mut writer, &image, trim_flex_bits > 0, with_alpha);
+    primary.write_plane_header(&mut writer);
+    if let Some(alpha) = &alpha {
+        alpha.write_plane_header(&mut writer);
+    }
+    // An empty index table, as no tiles are used.
+    writer.align();
+    writer.put(4, 16);
+    writer.put(111, 8);
+    writer.put(255, 8);
+    writer.put(1, 16);
+
+    let planes = primary.source_planes(&image);
+    let alpha_planes = alpha.as_ref().map(|alpha| alpha.source_planes(&image));
+    for mb_y in 0..image.mb_height {
+        for mb_x in 0..image.mb_width {
+            primary.load_macroblock(&planes, mb_x, mb_y);
+            primary.encode_macroblock(&mut writer, mb_x, mb_y, image.mb_width, true);
+            if let (Some(alpha), Some(planes)) = (&mut alpha, &alpha_planes) {
+                alpha.load_macroblock(planes, mb_x, mb_y);
+                alpha.encode_macroblock(&mut writer, mb_x, mb_y, image.mb_width, false);
+            }
+        }
+        primary.next_row();
+        if let Some(alpha) = &mut alpha {
+            alpha.next_row();
+        }
+    }
+    writer.align();
+
+    write_container(writer.data, width, height, with_alpha)
+}
+
+/// Wraps a codestream into a JPEG XR container with the minimal set of IFD entries.
+fn write_container(codestream: Vec<u8>, width: u32, height: u32, with_alpha: bool) -> Vec<u8> {
+    const IFD_OFFSET: u32 = 0x20;
+    const PIXEL_FORMAT_OFFSET: u32 = 8;
+    const DPI_96: u32 = 0x42c0_0000;
+    // The codestream follows the IFD: its entry count, 8 entries and the next IFD offset.
+    const IMAGE_OFFSET: u32 = IFD_OFFSET + 2 + 8 * 12 + 4;
+    // GUID_PKPixelFormat24bppBGR and GUID_PKPixelFormat32bppBGRA.
+    #[rustfmt::skip]
+    let pixel_format = [
+        0x24, 0xc3, 0xdd, 0x6f, 0x03, 0x4e, 0xfe, 0x4b,
+        0xb1, 0x85, 0x3d, 0x77, 0x76, 0x8d, 0xc9, if with_alpha { 0x0f } else { 0x0c },
+    ];
+
+    let entries: [(u16, u16, u32); 8] = [
+        (0xbc01, 1, PIXEL_FORMAT_OFFSET),
+        (0xbc02, 4, 0),
+        (0xbc80, 4, width),
+        (0xbc81, 4, height),
+        (0xbc82, 11, DPI_96),
+        (0xbc83, 11, DPI_96),
+        (0xbcc0, 4, IMAGE_OFFSET),
+        (0xbcc1, 4, codestream.len() as u32),
+    ];
+
+    let mut output = Vec::with_capacity(IMAGE_OFFSET as usize + codestream.len());
+    output.extend_from_slice(b"II");
+    output.extend_from_slice(&0x01bcu16.to_le_bytes());
+    output.extend_from_slice(&IFD_OFFSET.to_le_bytes());
+    output.extend_from_slice(&pixel_format);
+    output.resize(IFD_OFFSET as usize, 0);
+    output.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    for (tag, field_type, value) in entries {
+        let count: u32 = if tag == 0xbc01 { 16 } else { 1 };
+        output.extend_from_slice(&tag.to_le_bytes());
+        output.extend_from_slice(&field_type.to_le_bytes());
+        output.extend_from_slice(&count.to_le_bytes());
+        output.extend_from_slice(&value.to_le_bytes());
+    }
+    output.extend_from_slice(&0u32.to_le_bytes());
+    output.extend_from_slice(&codestream);
+    output
+}
+
+fn write_image_header(writer: &mut BitWriter, image: &SourceImage, trim: bool, alpha: bool) {
+    const CF_RGB: u32 = 7;
+    const BD_8: u32 = 1;
+
+    let abbreviated = image.mb_width <= 255 && image.mb_height <= 255;
+    for &byte in b"WMPHOTO\0" {
+        writer.put(u32::from(byte), 8);
+    }
+    writer.put(1, 4); // Version
+    writer.put(1, 4); // Subversion, with soft tile boundaries
+    writer.put(0, 1); // No tiling
+    writer.put(0, 1); // Spatial bitstream layout
+    writer.put(0, 3); // No rotation or flip
+    writer.put(0, 1); // No index table
+    writer.put(0, 2); // No overlap filtering
+    writer.put(abbreviated.into(), 1);
+    writer.put(1, 1); // Long words
+    writer.put(0, 1); // No windowing
+    writer.put(trim.into(), 1);
+    writer.put(0, 1); // No tile stretching
+    writer.put(0, 2); // Reserved
+    writer.put(alpha.into(), 1);
+    writer.put(CF_RGB, 4);
+    writer.put(BD_8, 4);
+    let size_bits = if abbreviated { 16 } else { 32 };
+    writer.put(image.width as u32 - 1, size_bits);
+    writer.put(image.height as u32 - 1, size_bits);
+    writer.align();
+}
+
+/// A most-significant-bit first bit writer.
+#[derive(Default)]
+struct BitWriter {
+    data: Vec<u8>,
+    accumulator: u64,
+    bits: u32,
+}
+
+impl BitWriter {
+    /// Writes the lowest `count` bits of `value`, with `count` at most 32.
+    fn put(&mut self, value: u32, count: u32) {
+        if count == 0 {
+            return;
+        }
+        let value = u64::from(value) & ((1 << count) - 1);
+        self.accumulator = (self.accumulator << count) | value;
+        self.bits += count;
+        while self.bits >= 8 {
+            self.bits -= 8;
+            self.data.push((self.accumulator >> self.bits) as u8);
+        }
+        self.accumulator &= (1 << self.bits) - 1;
+    }
+
+    fn put_code(&mut self, (code, length): (u8, u8)) {
+        self.put(code.into(), length.into());
+    }
+
+    /// Pads the current byte with zero bits.
+    fn align(&mut self) {
+        if self.bits > 0 {
+            self.put(0, 8 - self.bits);
+        }
+    }
+}
+
+struct SourceImage<'a> {
+    pixels: &'a [u8],
+    width: usize,
+    height: usize,
+    bytes_per_pixel: usize,
+    mb_width: usize,
+    mb_height: usize,
+}
+
+impl<'a> SourceImage<'a> {
+    fn new(pixels: &'a [u8], width: usize, height: usize, with_alpha: bool) -> Self {
+        Self {
+            pixels,
+            width,
+            height,
+            bytes_per_pixel: if with_alpha { 4 } else { 3 },
+            mb_width: width.div_ceil(16),
+            mb_height: height.div_ceil(16),
+        }
+    }
+
+    /// Returns the pixel at the given position, repeating the last row and column for positions
+    /// in the padding up to the next macroblock boundary.
+    fn pixel(&self, x: usize, y: usize) -> &[u8] {
+        let x = x.min(self.width - 1);
+        let y = y.min(self.height - 1);
+        let offset = (y * self.width + x) * self.bytes_per_pixel;
+        &self.pixels[offset..offset + self.bytes_per_pixel]
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ColorFormat {
+    YOnly,
+    Yuv420,
+    Yuv422,
+    Yuv444,
+}
+
+impl ColorFormat {
+    fn header_value(self) -> u32 {
+        match self {
+            ColorFormat::YOnly => 0,
+            ColorFormat::Yuv420 => 1,
+            ColorFormat::Yuv422 => 2,
+            ColorFormat::Yuv444 => 3,
+        }
+    }
+
+    fn is_subsampled(self) -> bool {
+        matches!(self, ColorFormat::Yuv420 | ColorFormat::Yuv422)
+    }
+
+    /// The block offsets of a chroma channel, in coding order.
+    fn chroma_blocks(self) -> &'static [usize] {
+        match self {
+            ColorFormat::Yuv420 => &BLOCK_OFFSETS_420,
+            ColorFormat::Yuv422 => &BLOCK_OFFSETS_422,
+            _ => &BLOCK_OFFSETS,
+        }
+    }
+}
+
+/// The offsets of the 4x4 blocks of a macroblock, in coding order.
+const BLOCK_OFFSETS: [usize; 16] = [
+    0, 64, 16, 80, 128, 192, 144, 208, 32, 96, 48, 112, 160, 224, 176, 240,
+];
+const BLOCK_OFFSETS_420: [usize; 4] = [0, 32, 16, 48];
+const BLOCK_OFFSETS_422: [usize; 8] = [0, 64, 16, 80, 32, 96, 48, 112];
+
+/// Where each coefficient of a 4x4 block ends up after the transform.
+const AC_INDEX: [usize; 16] = [0, 5, 1, 6, 10, 12, 8, 14, 2, 4, 3, 7, 9, 13, 11, 15];
+/// Where the DC coefficient of each block ends up after the second stage transform.
+const DC_INDEX: [usize; 16] = [
+    0, 128, 64, 208, 32, 240, 48, 224, 16, 192, 80, 144, 112, 176, 96, 160,
+];
+
+/// The position of a pixel of a 4x4 block within the transform buffer.
+const BLOCK_PIXEL_INDEX: [[usize; 4]; 4] =
+    [[0, 1, 5, 4], [2, 3, 7, 6], [10, 11, 15, 14], [8, 9, 13, 12]];
+
+/// The position of pixel (`x`, `y`) of a 16 pixel high macroblock in its transform buffer.
+fn macroblock_index(x: usize, y: usize) -> usize {
+    ((x >> 2) << 6) + ((y >> 2) << 4) + BLOCK_PIXEL_INDEX[y & 3][x & 3]
+}
+
+/// The position of pixel (`x`, `y`) of an 8x8 chroma macroblock in its transform buffer.
+fn macroblock_index_420(x: usize, y: usize) -> usize {
+    ((x >> 2) << 5) + ((y >> 2) << 4) + BLOCK_PIXEL_INDEX[y & 3][x & 3]
+}
+
+/// A plane of samples, padded to whole macroblocks.
+struct Plane {
+    samples: Vec<i32>,
+    width: usize,
+}
+
+impl Plane {
+    fn get(&self, x: usize, y: usize) -> i32 {
+        self.samples[y * self.width + x]
+    }
+}
+
+/// Halves the width of a plane, with a symmetric 5-tap filter.
+fn downsample_horizontally(plane: &Plane, height: usize) -> Plane {
+    let width = plane.width / 2;
+    let mut samples = Vec::with_capacity(width * height);
+    for y in 0..height {
+        let row = &plane.samples[y * plane.width..(y + 1) * plane.width];
+        for x in 0..width {
+            samples.push(downsample_filter(row, 2 * x));
+        }
+    }
+    Plane { samples, width }
+}
+
+/// Halves the height of a plane, with a symmetric 5-tap filter.
+fn downsample_vertically(plane: &Plane, height: usize) -> Plane {
+    let mut samples = vec![0; plane.width * height / 2];
+    let mut column = vec![0; height];
+    for x in 0..plane.width {
+        for (y, sample) in column.iter_mut().enumerate() {
+            *sample = plane.get(x, y);
+        }
+        for y in 0..height / 2 {
+            samples[y * plane.width + x] = downsample_filter(&column, 2 * y);
+        }
+    }
+    Plane {
+        samples,
+        width: plane.width,
+    }
+}
+
+fn downsample_filter(samples: &[i32], center: usize) -> i32 {
+    let last = samples.len() - 1;
+    let at = |i: isize| {
+        let i = if i < 0 {
+            -i as usize
+        } else if i as usize > last {
+            2 * last - i as usize
+        } else {
+            i as usize
+        };
+        samples[i]
+    };
+    let center = center as isize;
+    let (d0, d1, d2) = (at(center - 2), at(center - 1), at(center));
+    let (d3, d4) = (at(center + 1), at(center + 2));
+    (((d1 + d2 + d3) << 2) + (d2 << 1) + d0 + d4 + 8) >> 4
+}
+
+/// Forward transforms of the JPEG XR photo core transform.
+mod transform {
+    fn dct_2x2(p: &mut [i32], [ia, ib, ic, id]: [usize; 4], round: i32) {
+        let (mut a, mut b, big_c, mut d) = (p[ia], p[ib], p[ic], p[id]);
+        a += d;
+        b -= big_c;
+        let t = (a - b + round) >> 1;
+        let c = t - d;
+        d = t - big_c;
+        a -= d;
+        b += c;
+        (p[ia], p[ib], p[ic], p[id]) = (a, b, c, d);
+    }
+
+    pub fn dct_2x2_down(p: &mut [i32], indices: [usize; 4]) {
+        dct_2x2(p, indices, 0);
+    }
+
+    fn dct_2x2_up(p: &mut [i32], indices: [usize; 4]) {
+        dct_2x2(p, indices, 1);
+    }
+
+    /// A 2x2 transform that halves its inputs, used on the DC coefficients of subsampled chroma.
+    pub fn dct_2x2_down_scaled(p: &mut [i32], indices: [usize; 4]) {
+        for index in indices {
+            p[index] >>= 1;
+        }
+        dct_2x2(p, indices, 0);
+    }
+
+    fn rotate(a: &mut i32, b: &mut i32) {
+        *b -= (*a * 3 + 4) >> 3;
+        *a += (*b * 3 + 4) >> 3;
+    }
+
+    fn odd_odd(p: &mut [i32], [ia, ib, ic, id]: [usize; 4]) {
+        let (mut a, mut b, mut c, mut d) = (p[ia], -p[ib], -p[ic], p[id]);
+        d += a;
+        c -= b;
+        let t1 = d >> 1;
+        a -= t1;
+        let t2 = c >> 1;
+        b += t2;
+        a += (b * 3 + 4) >> 3;
+        b -= (a * 3 + 3) >> 2;
+        a += (b * 3 + 3) >> 3;
+        b -= t2;
+        a += t1;
+        c += b;
+        d -= a;
+        (p[ia], p[ib], p[ic], p[id]) = (a, b, c, d);
+    }
+
+    fn odd(p: &mut [i32], [ia, ib, ic, id]: [usize; 4]) {
+        let (mut a, mut b, mut c, mut d) = (p[ia], p[ib], p[ic], p[id]);
+        b -= c;
+        a += d;
+        c += (b + 1) >> 1;
+        d = ((a + 1) >> 1) - d;
+        rotate(&mut a, &mut b);
+        rotate(&mut c, &mut d);
+        d += b >> 1;
+        c -= (a + 1) >> 1;
+        b -= d;
+        a += c;
+        (p[ia], p[ib], p[ic], p[id]) = (a, b, c, d);
+    }
+
+    /// Transforms the 4x4 block starting at `base`.
+    pub fn first_stage(p: &mut [i32], base: usize) {
+        let p = &mut p[base..base + 16];
+        for i in 0..4 {
+            dct_2x2_down(p, [i, i + 4, i + 8, i + 12]);
+        }
+        dct_2x2_up(p, [0, 1, 2, 3]);
+        odd_odd(p, [15, 14, 13, 12]);
+        odd(p, [5, 4, 7, 6]);
+        odd(p, [10, 8, 11, 9]);
+    }
+
+    /// Transforms the DC coefficients of the 16 blocks of a macroblock.
+    pub fn second_stage(p: &mut [i32]) {
+        dct_2x2_down(p, [0, 192, 48, 240]);
+        dct_2x2_down(p, [64, 128, 112, 176]);
+        dct_2x2_down(p, [16, 208, 32, 224]);
+        dct_2x2_down(p, [80, 144, 96, 160]);
+        dct_2x2_up(p, [0, 64, 16, 80]);
+        odd_odd(p, [160, 224, 176, 240]);
+        odd(p, [128, 192, 144, 208]);
+        odd(p, [32, 48, 96, 112]);
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct Quantizer {
+    index: u8,
+    qp: i32,
+    mantissa: u32,
+    exponent: u32,
+    offset: i32,
+}
+
+impl Quantizer {
+    /// Reciprocals of the quantizer mantissas, as (multiplier, shift) pairs.
+    #[rustfmt::skip]
+    const RECIPROCALS: [(u32, u32); 32] = [
+        (0x0, 0), (0x0, 0), (0x0, 1), (0xaaaaaaab, 1),
+        (0x0, 2), (0xcccccccd, 2), (0xaaaaaaab, 2), (0x92492493, 2),
+        (0x0, 3), (0xe38e38e4, 3), (0xcccccccd, 3), (0xba2e8ba3, 3),
+        (0xaaaaaaab, 3), (0x9d89d89e, 3), (0x92492493, 3), (0x88888889, 3),
+        (0x0, 4), (0xf0f0f0f1, 4), (0xe38e38e4, 4), (0xd79435e6, 4),
+        (0xcccccccd, 4), (0xc30c30c4, 4), (0xba2e8ba3, 4), (0xb21642c9, 4),
+        (0xaaaaaaab, 4), (0xa3d70a3e, 4), (0x9d89d89e, 4), (0x97b425ee, 4),
+        (0x92492493, 4), (0x8d3dcb09, 4), (0x88888889, 4), (0x84210843, 4),
+    ];
+
+    /// Creates the quantizer for a QP index. Indices other than 0 (lossless) are only
+    /// used with scaled arithmetic.
+    fn new(index: u8, shift: u32) -> Self {
+        if index == 0 {
+            return Self {
+                qp: 1,
+                ..Default::default()
+            };
+        }
+        let (mantissa, exponent) = if index < 16 {
+            (index as usize, shift)
+        } else {
+            (16 + (index as usize & 0xf), (index as u32 >> 4) - 1 + shift)
+        };
+        let qp = (mantissa as i32) << exponent;
+        let (reciprocal, reciprocal_shift) = Self::RECIPROCALS[mantissa];
+        Self {
+            index,
+            qp,
+            mantissa: reciprocal,
+            exponent: reciprocal_shift + exponent,
+            offset: (qp * 3 + 1) >> 3,
+        }
+    }
+
+    fn quantize(&self, value: i32) -> i32 {
+        let sign = value >> 31;
+        let magnitude = (value ^ sign).wrapping_sub(sign).wrapping_add(self.offset);
+        let quantized = if self.mantissa == 0 {
+            magnitude >> self.exponent
+        } else {
+            ((u64::from(magnitude as u32) * u64::from(self.mantissa) >> 32) as u32 >> self.exponent)
+                as i32
+        };
+        (quantized ^ sign).wrapping_sub(sign)
+    }
+}
+
+/// Per-macroblock information kept for the prediction of the next row and column.
+#[derive(Clone, Copy, Default)]
+struct PredictionInfo {
+    dc: i32,
+    ad: [i32; 6],
+    cbp: i32,
+}
+
+/// Variable length code tables, as (code, length) pairs, grouped by table index.
+#[rustfmt::skip]
+const CODES_4: [[(u8, u8); 4]; 1] = [[(1, 1), (1, 2), (0, 3), (1, 3)]];
+#[rustfmt::skip]
+const CODES_5: [[(u8, u8); 5]; 2] = [
+    [(1, 1), (1, 2), (1, 3), (0, 4), (1, 4)],
+    [(1, 1), (0, 3), (1, 3), (2, 3), (3, 3)],
+];
+const DELTAS_5: [i32; 5] = [0, -1, 0, 1, 1];
+#[rustfmt::skip]
+const CODES_6: [[(u8, u8); 6]; 4] = [
+    [(1, 1), (0, 5), (1, 3), (1, 5), (1, 2), (1, 4)],
+    [(1, 2), (0, 4), (2, 2), (1, 4), (3, 2), (1, 3)],
+    [(0, 4), (1, 4), (1, 2), (2, 2), (3, 2), (1, 3)],
+    [(0, 5), (1, 5), (1, 2), (1, 1), (1, 4), (1, 3)],
+];
+const DELTAS_6: [[i32; 6]; 3] = [
+    [-1, 1, 1, 1, 0, 1],
+    [-2, 0, 0, 2, 0, 0],
+    [-1, -1, 0, 1, -2, 0],
+];
+#[rustfmt::skip]
+const CODES_7: [[(u8, u8); 7]; 2] = [
+    [(1, 2), (2, 2), (3, 2), (1, 3), (1, 4), (0, 5), (1, 5)],
+    [(1, 1), (1, 2), (1, 3), (1, 4), (1, 5), (0, 6), (1, 6)],
+];
+const DELTAS_7: [i32; 7] = [1, 0, -1, -1, -1, -1, -1];
+#[rustfmt::skip]
+const CODES_8: [[(u8, u8); 8]; 1] = [
+    [(2, 2), (1, 3), (1, 5), (1, 4), (3, 2), (2, 3), (0, 5), (3, 3)],
+];
+#[rustfmt::skip]
+const CODES_9: [[(u8, u8); 9]; 2] = [
+    [(2, 3), (0, 5), (2, 4), (1, 5), (2, 5), (1, 1), (3, 3), (3, 5), (3, 4)],
+    [(1, 1), (1, 3), (2, 3), (1, 4), (1, 6), (3, 3), (1, 5), (0, 7), (1, 7)],
+];
+const DELTAS_9: [i32; 9] = [2, 2, 1, 1, -1, -2, -2, -2, -3];
+#[rustfmt::skip]
+const CODES_12: [[(u8, u8); 12]; 5] = [
+    [
+        (1, 5), (1, 6), (0, 7), (1, 7), (4, 5), (2, 3),
+        (5, 5), (1, 1), (6, 5), (1, 4), (7, 5), (3, 3),
+    ],
+    [
+        (2, 4), (2, 5), (0, 6), (1, 6), (3, 4), (2, 3),
+        (3, 5), (3, 2), (3, 3), (4, 3), (1, 5), (5, 3),
+    ],
+    [
+        (3, 2), (1, 3), (0, 7), (1, 7), (1, 5), (2, 3),
+        (2, 7), (3, 3), (4, 3), (5, 3), (3, 7), (1, 4),
+    ],
+    [
+        (1, 3), (3, 2), (0, 7), (1, 5), (2, 5), (2, 3),
+        (1, 7), (3, 3), (3, 5), (4, 3), (1, 6), (5, 3),
+    ],
+    [
+        (2, 3), (1, 1), (1, 7), (1, 4), (2, 7), (3, 3),
+        (0, 8), (2, 4), (3, 7), (3, 4), (1, 8), (1, 5),
+    ],
+];
+const DELTAS_12: [[i32; 12]; 4] = [
+    [1, 1, 1, 1, 1, 0, 0, -1, 2, 1, 0, 0],
+    [2, 2, -1, -1, -1, 0, -2, -1, 0, 0, -2, -1],
+    [-1, 1, 0, 2, 0, 0, 0, 0, -2, 0, 1, 1],
+    [0, 1, 0, 1, -2, 0, -1, -1, -2, -1, -2, -2],
+];
+
+/// A variable length code that switches between tables based on the symbols it has seen.
+struct AdaptiveHuffman {
+    symbols: usize,
+    initialized: bool,
+    table_index: usize,
+    discriminant: i32,
+    discriminant1: i32,
+    lower_bound: i32,
+    upper_bound: i32,
+    codes: &'static [(u8, u8)],
+    delta: &'static [i32],
+    delta1: &'static [i32],
+}
+
+impl AdaptiveHuffman {
+    fn new(symbols: usize) -> Self {
+        Self {
+            symbols,
+            initialized: false,
+            table_index: 0,
+            discriminant: 0,
+            discriminant1: 0,
+            lower_bound: 0,
+            upper_bound: 0,
+            codes: &[],
+            delta: &[],
+            delta1: &[],
+        }
+    }
+
+    fn table_count(&self) -> usize {
+        match self.symbols {
+            4 => 1,
+            6 => 4,
+            12 => 5,
+            _ => 2,
+        }
+    }
+
+    fn has_second_discriminant(&self) -> bool {
+        matches!(self.symbols, 6 | 12)
+    }
+
+    /// Writes `symbol`, updating the discriminant that picks the next table.
+    fn put(&mut self, writer: &mut BitWriter, symbol: usize) {
+        if let Some(delta) = self.delta.get(symbol) {
+            self.discriminant += delta;
+        }
+        writer.put_code(self.codes[symbol]);
+    }
+
+    /// Writes `symbol` followed by a sign bit, updating both discriminants.
+    fn put_with_sign(&mut self, writer: &mut BitWriter, symbol: usize, negative: bool) {
+        self.discriminant += self.delta[symbol];
+        self.discriminant1 += self.delta1[symbol];
+        let (code, length) = self.codes[symbol];
+        writer.put(
+            u32::from(code) * 2 + u32::from(negative),
+            u32::from(length) + 1,
+        );
+    }
+
+    /// Switches to a neighbouring table if the recent symbols favour it.
+    fn adapt(&mut self) {
+        const THRESHOLD: i32 = 8;
+        const MEMORY: i32 = 8;
+
+        if !self.initialized {
+            self.initialized = true;
+            self.discriminant = 0;
+            self.discriminant1 = 0;
+            self.table_index = self.has_second_discriminant().into();
+        }
+
+        let low = self.discriminant;
+        let high = if self.has_second_discriminant() {
+            self.discriminant1
+        } else {
+            self.discriminant
+        };
+        let changed = if low < self.lower_bound {
+            self.table_index -= 1;
+            true
+        } else if high > self.upper_bound {
+            self.table_index += 1;
+            true
+        } else {
+            false
+        };
+        if changed {
+            self.discriminant = 0;
+            self.discriminant1 = 0;
+        }
+        let limit = THRESHOLD * MEMORY;
+        self.discriminant = self.discriminant.clamp(-limit, limit);
+        self.discriminant1 = self.discriminant1.clamp(-limit, limit);
+
+        let t = self.table_index;
+        let max = self.table_count();
+        self.lower_bound = if t == 0 { i32::MIN } else { -THRESHOLD };
+        self.upper_bound = if t == max - 1 { 1 << 30 } else { THRESHOLD };
+
+        (self.codes, self.delta, self.delta1) = match self.symbols {
+            4 => (&CODES_4[0][..], &[][..], &[][..]),
+            5 => (&CODES_5[t][..], &DELTAS_5[..], &[][..]),
+            6 => (
+                &CODES_6[t][..],
+                &DELTAS_6[t.saturating_sub(1)][..],
+                &DELTAS_6[t - usize::from(t + 1 == max)][..],
+            ),
+            7 => (&CODES_7[t][..], &DELTAS_7[..], &[][..]),
+            8 => (&CODES_8[0][..], &[][..], &[][..]),
+            9 => (&CODES_9[t][..], &DELTAS_9[..], &[][..]),
+            12 => (
+                &CODES_12[t][..],
+                &DELTAS_12[t.saturating_sub(1)][..],
+                &DELTAS_12[t - usize::from(t + 1 == max)][..],
+            ),
+            _ => unreachable!("No code tables for {} symbols", self.symbols),
+        };
+    }
+}
+
+/// Picks how many of the least significant bits of a band are sent without entropy coding.
+#[derive(Clone, Copy)]
+struct AdaptiveModel {
+    flc_bits: [u32; 2],
+    flc_state: [i32; 2],
+    band: usize,
+}
+
+impl AdaptiveModel {
+    const DC: usize = 0;
+    const LOWPASS: usize = 1;
+    const HIGHPASS: usize = 2;
+
+    fn new(band: usize) -> Self {
+        let bits = [8, 4, 0][band];
+        Self {
+            flc_bits: [bits, bits],
+            flc_state: [0, 0],
+            band,
+        }
+    }
+
+    fn update(&mut self, format: ColorFormat, channels: usize, mut laplacian_mean: [i32; 2]) {
+        const MODEL_WEIGHT: i32 = 70;
+        const LUMA_WEIGHTS: [i32; 3] = [240, 12, 1];
+        const CHROMA_WEIGHTS: [[i32; 16]; 3] = [
+            [
+                0, 240, 120, 80, 60, 48, 40, 34, 30, 27, 24, 22, 20, 18, 17, 16,
+            ],
+            [0, 12, 6, 4, 3, 2, 2, 2, 2, 1, 1, 1, 1, 1, 1, 1],
+            [0, 16, 8, 5, 4, 3, 3, 2, 2, 2, 2, 1, 1, 1, 1, 1],
+        ];
+
+        laplacian_mean[0] *= LUMA_WEIGHTS[self.band];
+        match format {
+            ColorFormat::Yuv420 => laplacian_mean[1] *= [120, 37, 2][self.band],
+            ColorFormat::Yuv422 => laplacian_mean[1] *= [120, 18, 1][self.band],
+            _ => {
+                laplacian_mean[1] *= CHROMA_WEIGHTS[self.band][channels - 1];
+                if self.band == Self::HIGHPASS {
+                    laplacian_mean[1] >>= 4;
+                }
+            }
+        }
+
+        let models = if format == ColorFormat::YOnly { 1 } else { 2 };
+        for j in 0..models {
+            let mut state = self.flc_state[j];
+            let delta = (laplacian_mean[j] - MODEL_WEIGHT) >> 2;
+            if delta <= -8 {
+                state += (delta + 4).max(-16);
+                if state < -8 {
+                    if self.flc_bits[j] == 0 {
+                        state = -8;
+                    } else {
+                        state = 0;
+                        self.flc_bits[j] -= 1;
+                    }
+                }
+            } else if delta >= 8 {
+                state += (delta - 4).min(15);
+                if state > 8 {
+                    if self.flc_bits[j] >= 15 {
+                        self.flc_bits[j] = 15;
+                        state = 8;
+                    } else {
+                        state = 0;
+                        self.flc_bits[j] += 1;
+                    }
+                }
+            }
+            self.flc_state[j] = state;
+        }
+    }
+}
+
+/// A coefficient scan order that adapts to which positions are most often significant.
+#[derive(Clone, Copy, Default)]
+struct ScanEntry {
+    total: u32,
+    position: usize,
+}
+
+fn new_scan(order: &[usize; 16], map: impl Fn(usize) -> usize) -> [ScanEntry; 16] {
+    order.map(|i| ScanEntry {
+        total: 0,
+        position: map(i),
+    })
+}
+
+fn reset_scan_totals(scan: &mut [ScanEntry; 16]) {
+    scan[0].total = 32767;
+    for (k, entry) in scan.iter_mut().enumerate().skip(1) {
+        entry.total = 34 - 2 * k as u32;
+    }
+}
+
+/// The adaptive state of the entropy coder.
+struct CodingContext {
+    cbpcy: AdaptiveHuffman,
+    cbpcy1: AdaptiveHuffman,
+    /// Tables 0 to 4 code runs, DC values and chroma CBPs, 5 to 12 code lowpass blocks and 13
+    /// to 20 highpass blocks.
+    expt: [AdaptiveHuffman; 21],
+    scan_lowpass: [ScanEntry; 16],
+    scan_horizontal: [ScanEntry; 16],
+    scan_vertical: [ScanEntry; 16],
+    model_dc: AdaptiveModel,
+    model_lp: AdaptiveModel,
+    model_ac: AdaptiveModel,
+    cbp_count_max: i32,
+    cbp_count_zero: i32,
+    cbp_count0: [i32; 2],
+    cbp_count1: [i32; 2],
+    cbp_state: [i32; 2],
+    trim_flex_bits: u32,
+}
+
+impl CodingContext {
+    const ALPHABET_SIZES: [usize; 21] = [
+        5, 4, 8, 7, 7, 12, 6, 6, 12, 6, 6, 7, 7, 12, 6, 6, 12, 6, 6, 7, 7,
+    ];
+    const LOWPASS_OFFSET: usize = 5;
+    const HIGHPASS_OFFSET: usize = 13;
+
+    fn new(format: ColorFormat, trim_flex_bits: u32) -> Self {
+        const ZIGZAG: [usize; 16] = [0, 1, 4, 5, 2, 8, 6, 9, 3, 12, 10, 7, 13, 11, 14, 15];
+        const ZIGZAG_VERTICAL: [usize; 16] = [0, 4, 8, 5, 1, 12, 9, 6, 2, 13, 3, 15, 7, 10, 14, 11];
+
+        let mut context = Self {
+            cbpcy: AdaptiveHuffman::new(if format == ColorFormat::YOnly { 5 } else { 9 }),
+            cbpcy1: AdaptiveHuffman::new(5),
+            expt: std::array::from_fn(|i| AdaptiveHuffman::new(Self::ALPHABET_SIZES[i])),
+            scan_lowpass: new_scan(&ZIGZAG, |i| i),
+            scan_horizontal: new_scan(&ZIGZAG, |i| AC_INDEX[i]),
+            scan_vertical: new_scan(&ZIGZAG_VERTICAL, |i| AC_INDEX[i]),
+            model_dc: AdaptiveModel::new(AdaptiveModel::DC),
+            model_lp: AdaptiveModel::new(AdaptiveModel::LOWPASS),
+            model_ac: AdaptiveModel::new(AdaptiveModel::HIGHPASS),
+            cbp_count_max: 1,
+            cbp_count_zero: 1,
+            cbp_count0: [-4, -4],
+            cbp_count1: [4, 4],
+            cbp_state: [0, 0],
+            trim_flex_bits,
+        };
+        context.adapt_lowpass();
+        context.adapt_highpass();
+        context
+    }
+
+    fn adapt_lowpass(&mut self) {
+        for table in &mut self.expt[..Self::HIGHPASS_OFFSET] {
+            table.adapt();
+        }
+    }
+
+    fn adapt_highpass(&mut self) {
+        self.cbpcy.adapt();
+        self.cbpcy1.adapt();
+        for table in &mut self.expt[Self::HIGHPASS_OFFSET..] {
+            table.adapt();
+        }
+    }
+}
+
+fn put_significant_abs_level(writer: &mut BitWriter, table: &mut AdaptiveHuffman, level: u32) {
+    const INDICES: [usize; 16] = [0, 1, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5];
+    const FIXED_LENGTHS: [u32; 6] = [0, 0, 1, 2, 2, 2];
+
+    let level = level - 1;
+    if level >= 16 {
+        let fixed = 4 + (32 - (level >> 5).leading_zeros());
+        table.put(writer, 6);
+        if fixed > 18 {
+            writer.put(15, 4);
+            if fixed > 21 {
+                writer.put(3, 2);
+                writer.put(fixed - 22, 3);
+            } else {
+                writer.put(fixed - 19, 2);
+            }
+        } else {
+            writer.put(fixed - 4, 4);
+        }
+        writer.put(level, fixed);
+    } else {
+        let index = INDICES[level as usize];
+        table.put(writer, index);
+        writer.put(level, FIXED_LENGTHS[index]);
+    }
+}
+
+fn put_significant_run(
+    writer: &mut BitWriter,
+    table: &AdaptiveHuffman,
+    run: usize,
+    max_run: usize,
+) {
+    const INDICES: [usize; 34] = [
+        0, 1, 2, 2, 3, 3, 4, 4, 4, 4, 4, 4, 4, 4, 0, 1, 2, 2, 3, 3, 4, 4, 4, 4, 0, 0, 0, 0, 0, 1,
+        2, 3, 4, 4,
+    ];
+    const BINS: [usize; 15] = [0, 0, 0, 0, 2, 2, 2, 1, 1, 1, 1, 0, 0, 0, 0];
+    const FIXED_LENGTHS: [u32; 15] = [0, 0, 1, 1, 3, 0, 0, 1, 1, 2, 0, 0, 0, 0, 1];
+
+    if max_run < 5 {
+        const LENGTHS: [u32; 4] = [3, 3, 2, 1];
+        if max_run > 1 {
+            let length = LENGTHS[max_run - run] - (4 - max_run as u32);
+            writer.put((max_run != run).into(), length);
+        }
+        return;
+    }
+    let bin = BINS[max_run];
+    let index = INDICES[run + bin * 14 - 1];
+    // The run table never adapts, so its discriminant isn't updated.
+    writer.put_code(table.codes[index]);
+    writer.put(run as u32 + 1, FIXED_LENGTHS[index + bin * 5]);
+}
+
+/// Entropy codes the (run, level) pairs of a block. `location` is the position of the first
+/// coefficient that the runs are counted from.
+fn put_block(
+    writer: &mut BitWriter,
+    tables: &mut [AdaptiveHuffman; 21],
+    chroma: bool,
+    run_levels: &[(usize, i32)],
+    offset: usize,
+    mut location: usize,
+) {
+    let chroma_offset = usize::from(chroma) * 3;
+    // 0 if this is the last coefficient, 1 if the next one follows it immediately, 2 otherwise.
+    let next_run_state = |k: usize| match run_levels.get(k + 1) {
+        None => 0,
+        Some(&(run, _)) if run > 0 => 2,
+        Some(_) => 1,
+    };
+
+    let (run, level) = run_levels[0];
+    let short_run = usize::from(run == 0);
+    let large_level = usize::from(level.unsigned_abs() > 1);
+    let mut run_state = next_run_state(0);
+    let index = run_state * 4 + large_level * 2 + short_run;
+    tables[offset + chroma_offset].put_with_sign(writer, index, level < 0);
+    // Levels are coded in a separate context while the coefficients are contiguous.
+    let mut context = short_run & usize::from(run_state == 1);
+    if large_level != 0 {
+        put_significant_abs_level(
+            writer,
+            &mut tables[6 + offset + context],
+            level.unsigned_abs() - 1,
+        );
+    }
+    if short_run == 0 {
+        put_significant_run(writer, &tables[0], run, 15 - location);
+    }
+    location += run + 1;
+
+    for k in 1..run_levels.len() {
+        let (run, level) = run_levels[k];
+        if run_state == 2 {
+            put_significant_run(writer, &tables[0], run, 15 - location);
+        }
+        location += run + 1;
+        run_state = next_run_state(k);
+        let large_level = usize::from(level.unsigned_abs() > 1);
+        let index = run_state * 2 + large_level;
+        let negative = level < 0;
+        if location < 15 {
+            tables[offset + context + 1 + chroma_offset].put_with_sign(writer, index, negative);
+        } else if location == 15 {
+            const CODES: [(u32, u32); 4] = [(0, 1), (6, 3), (2, 2), (7, 3)];
+            let (code, length) = CODES[index];
+            writer.put(code * 2 + u32::from(negative), length + 1);
+        } else {
+            writer.put(index as u32 * 2 + u32::from(negative), 2);
+        }
+        context &= usize::from(run_state == 1);
+        if large_level != 0 {
+            put_significant_abs_level(
+                writer,
+                &mut tables[6 + offset + context],
+                level.unsigned_abs() - 1,
+            );
+        }
+    }
+}
+
+/// Collects the (run, level) pairs of the significant coefficients of a block in scan order,
+/// and the refinement bits of the others in `residuals`, indexed by position.
+fn adaptive_scan(
+    coefficients: &[i32],
+    residuals: &mut [i32; 16],
+    scan: &mut [ScanEntry; 16],
+    model_bits: u32,
+    trim_bits: u32,
+    run_levels: &mut Vec<(usize, i32)>,
+) {
+    run_levels.clear();
+    let threshold = (1 << model_bits) - 1;
+    let mut run = 0;
+    for k in 1..16 {
+        let position = scan[k].position;
+        let value = coefficients[position];
+        if value.abs() > threshold {
+            let magnitude = value.abs() >> model_bits;
+            if model_bits > trim_bits {
+                residuals[position] = ((value.abs() & threshold) >> trim_bits) * 2;
+            }
+            scan[k].total += 1;
+            if k > 1 && scan[k].total > scan[k - 1].total {
+                scan.swap(k, k - 1);
+            }
+            run_levels.push((run, if value < 0 { -magnitude } else { magnitude }));
+            run = 0;
+        } else {
+            run += 1;
+            if model_bits > trim_bits {
+                let sign = -i32::from(value < 0);
+                let level = ((value + sign) >> trim_bits) - sign;
+                residuals[position] =
+                    level.abs() * 4 + i32::from(level < 0) * 2 + i32::from(level != 0);
+            }
+        }
+    }
+}
+
+/// Encodes one plane of an image: the colour channels, or the alpha channel.
+struct PlaneEncoder {
+    format: ColorFormat,
+    channels: usize,
+    scaled: bool,
+    /// The DC, lowpass and highpass quantizers of each channel.
+    quantizers: [[Quantizer; 3]; 3],
+    context: CodingContext,
+    prediction: [Vec<PredictionInfo>; 3],
+    previous_row_prediction: [Vec<PredictionInfo>; 3],
+    coefficients: [[i32; 256]; 3],
+    block_dc: [[i32; 16]; 3],
+    cbp: [i32; 3],
+    diff_cbp: [i32; 3],
+    orientation: i32,
+    ctx_left: bool,
+    ctx_top: bool,
+    reset_context: bool,
+    reset_totals: bool,
+}
+
+impl PlaneEncoder {
+    fn new(format: ColorFormat, qp_index: u8, image: &SourceImage, trim_flex_bits: u32) -> Self {
+        let channels = if format == ColorFormat::YOnly { 1 } else { 3 };
+        let scaled = qp_index > 1 || format.is_subsampled();
+        let index = if qp_index < 2 { 0 } else { qp_index };
+        let quantizers = std::array::from_fn(|channel| {
+            // Chroma DC and lowpass coefficients get twice the precision of the others.
+            let shift = if channel > 0 { 0 } else { 1 };
+            let mut dc = Quantizer::new(index, shift);
+            dc.offset = dc.qp >> 1;
+            [dc, Quantizer::new(index, shift), Quantizer::new(index, 1)]
+        });
+        let row = vec![PredictionInfo::default(); image.mb_width];
+        Self {
+            format,
+            channels,
+            scaled,
+            quantizers,
+            context: CodingContext::new(format, trim_flex_bits),
+            prediction: std::array::from_fn(|_| row.clone()),
+            previous_row_prediction: std::array::from_fn(|_| row.clone()),
+            coefficients: [[0; 256]; 3],
+            block_dc: [[0; 16]; 3],
+            cbp: [0; 3],
+            diff_cbp: [0; 3],
+            orientation: 0,
+            ctx_left: false,
+            ctx_top: false,
+            reset_context: false,
+            reset_totals: false,
+        }
+    }
+
+    fn write_plane_header(&self, writer: &mut BitWriter) {
+        writer.put(self.format.header_value(), 3);
+        writer.put(self.scaled.into(), 1);
+        writer.put(0, 4); // All subbands
+        if self.format != ColorFormat::YOnly {
+            writer.put(0, 4); // No chroma centering
+            writer.put(0, 4);
+        }
+        for band in 0..3 {
+            if band > 0 {
+                writer.put(0, 1); // Not reusing the quantizer of the previous band
+            }
+            writer.put(1, 1); // Uniform across tiles
+            if self.channels > 1 {
+                writer.put(2, 2); // Independent quantizer per channel
+            }
+            for channel in 0..self.channels {
+                writer.put(self.quantizers[channel][band].index.into(), 8);
+            }
+        }
+        writer.align();
+    }
+
+    /// Converts the image to the samples of each channel, padded to whole macroblocks and with
+    /// chroma downsampled as needed.
+    fn source_planes(&self, image: &SourceImage) -> Vec<Plane> {
+        let width = image.mb_width * 16;
+        let height = image.mb_height * 16;
+        let shift = if self.scaled { 3 } else { 0 };
+        let mut planes: Vec<_> = (0..self.channels)
+            .map(|_| Plane {
+                samples: Vec::with_capacity(width * height),
+                width,
+            })
+            .collect();
+        for y in 0..height {
+            for x in 0..width {
+                let pixel = image.pixel(x, y);
+                if self.format == ColorFormat::YOnly {
+                    planes[0].samples.push((i32::from(pixel[3]) - 128) << shift);
+                    continue;
+                }
+                let mut r = i32::from(pixel[0]) << shift;
+                let mut g = i32::from(pixel[1]) << shift;
+                let mut b = i32::from(pixel[2]) << shift;
+                b -= r;
+                r += ((b + 1) >> 1) - g;
+                g += r >> 1;
+                planes[0].samples.push(g - (128 << shift));
+                planes[1].samples.push(-r);
+                planes[2].samples.push(b);
+            }
+        }
+        if self.format.is_subsampled() {
+            for plane in &mut planes[1..] {
+                *plane = downsample_horizontally(plane, height);
+                if self.format == ColorFormat::Yuv420 {
+                    *plane = downsample_vertically(plane, height);
+                }
+            }
+        }
+        planes
+    }
+
+    /// Copies a macroblock into the transform buffers and applies the forward transform.
+    fn load_macroblock(&mut self, planes: &[Plane], mb_x: usize, mb_y: usize) {
+        for (channel, plane) in planes.iter().enumerate() {
+            let buffer = &mut self.coefficients[channel];
+            let chroma = channel > 0;
+            let (width, height) = match self.format {
+                ColorFormat::Yuv420 if chroma => (8, 8),
+                ColorFormat::Yuv422 if chroma => (8, 16),
+                _ => (16, 16),
+            };
+            for y in 0..height {
+                for x in 0..width {
+                    let index = if height == 8 {
+                        macroblock_index_420(x, y)
+                    } else {
+                        macroblock_index(x, y)
+                    };
+                    buffer[index] = plane.get(mb_x * width + x, mb_y * height + y);
+                }
+            }
+
+            let blocks = width * height / 16;
+            for block in 0..blocks {
+                transform::first_stage(buffer, block * 16);
+            }
+            match blocks {
+                4 if self.scaled => transform::dct_2x2_down_scaled(buffer, [0, 32, 16, 48]),
+                4 => transform::dct_2x2_down(buffer, [0, 32, 16, 48]),
+                8 => {
+                    for indices in [[0, 64, 16, 80], [32, 96, 48, 112]] {
+                        if self.scaled {
+                            transform::dct_2x2_down_scaled(buffer, indices);
+                        } else {
+                            transform::dct_2x2_down(buffer, indices);
+                        }
+                    }
+                    buffer[32] -= buffer[0];
+                    buffer[0] += (buffer[32] + 1) >> 1;
+                }
+                _ => {
+                    if self.scaled && chroma {
+                        for i in (0..256).step_by(16) {
+                            buffer[i] >>= 1;
+                        }
+                    }
+                    transform::second_stage(buffer);
+                }
+            }
+        }
+    }
+
+    fn next_row(&mut self) {
+        std::mem::swap(&mut self.prediction, &mut self.previous_row_prediction);
+    }
+
+    /// The block offsets of a channel, in coding order.
+    fn blocks(&self, channel: usize) -> &'static [usize] {
+        if channel > 0 {
+            self.format.chroma_blocks()
+        } else {
+            &BLOCK_OFFSETS
+        }
+    }
+
+    fn encode_macroblock(
+        &mut self,
+        writer: &mut BitWriter,
+        mb_x: usize,
+        mb_y: usize,
+        mb_width: usize,
+        primary: bool,
+    ) {
+        self.ctx_left = mb_x == 0;
+        self.ctx_top = mb_y == 0;
+        self.reset_totals = mb_x & 15 == 0;
+        self.reset_context = self.reset_totals || mb_x + 1 == mb_width;
+
+        if primary && self.ctx_left && self.ctx_top {
+            // The header of the only packet.
+            writer.put(0, 8);
+            writer.put(0, 8);
+            writer.put(1, 8);
+            writer.put(0, 8);
+            if self.context.trim_flex_bits > 0 {
+                writer.put(self.context.trim_flex_bits, 4);
+            }
+        }
+
+        self.quantize();
+        self.predict(mb_x);
+        self.encode_dc(writer);
+        self.encode_lowpass(writer);
+        self.encode_highpass(writer, mb_x);
+    }
+
+    fn quantize(&mut self) {
+        for channel in 0..self.channels {
+            let [dc, lp, hp] = self.quantizers[channel];
+            let blocks = self.blocks(channel);
+            let data = &mut self.coefficients[channel];
+            for (j, &offset) in blocks.iter().enumerate() {
+                let block = &mut data[offset..offset + 16];
+                block[0] = if j == 0 { dc } else { lp }.quantize(block[0]);
+                for value in &mut block[1..] {
+                    *value = hp.quantize(*value);
+                }
+            }
+            let dc_indices: &[usize] = if channel > 0 && self.format.is_subsampled() {
+                blocks
+            } else {
+                &DC_INDEX
+            };
+            for (i, &index) in dc_indices.iter().enumerate() {
+                self.block_dc[channel][i] = data[index];
+            }
+        }
+    }
+
+    fn dc_ac_prediction_mode(&self, mb_x: usize) -> (i32, i32) {
+        let dc_mode = if self.ctx_left && self.ctx_top {
+            3
+        } else if self.ctx_left {
+            1
+        } else if self.ctx_top {
+            0
+        } else {
+            let current = &self.prediction;
+            let previous = &self.previous_row_prediction;
+            let difference = |channel: usize, other: &PredictionInfo| {
+                (previous[channel][mb_x - 1].dc - other.dc).abs()
+            };
+            let mut horizontal = difference(0, &current[0][mb_x - 1]);
+            let mut vertical = difference(0, &previous[0][mb_x]);
+            if self.format != ColorFormat::YOnly {
+                let scale = match self.format {
+                    ColorFormat::Yuv420 => 8,
+                    ColorFormat::Yuv422 => 4,
+                    _ => 2,
+                };
+                horizontal = horizontal * scale
+                    + difference(1, &current[1][mb_x - 1])
+                    + difference(2, &current[2][mb_x - 1]);
+                vertical = vertical * scale
+                    + difference(1, &previous[1][mb_x])
+                    + difference(2, &previous[2][mb_x]);
+            }
+            orientation(horizontal, vertical)
+        };
+        let ad_mode = if dc_mode < 2 { dc_mode } else { 2 };
+        (dc_mode, ad_mode)
+    }
+
+    fn ac_prediction_mode(&self) -> i32 {
+        let y = &self.block_dc[0];
+        let mut horizontal = y[1].abs() + y[2].abs() + y[3].abs();
+        let mut vertical = y[4].abs() + y[8].abs() + y[12].abs();
+        if self.format != ColorFormat::YOnly {
+            let (u, v) = (&self.block_dc[1], &self.block_dc[2]);
+            horizontal += u[1].abs() + v[1].abs();
+            match self.format {
+                ColorFormat::Yuv420 => vertical += u[2].abs() + v[2].abs(),
+                ColorFormat::Yuv422 => {
+                    vertical += u[2].abs() + v[2].abs() + u[6].abs() + v[6].abs();
+                    horizontal += u[5].abs() + v[5].abs();
+                }
+                _ => vertical += u[4].abs() + v[4].abs(),
+            }
+        }
+        orientation(horizontal, vertical)
+    }
+
+    /// Predicts the DC and lowpass coefficients from the neighbouring macroblocks, and the
+    /// highpass coefficients from the neighbouring blocks.
+    fn predict(&mut self, mb_x: usize) {
+        let (dc_mode, ad_mode) = self.dc_ac_prediction_mode(mb_x);
+        let ac_mode = self.ac_prediction_mode();
+        self.orientation = 2 - ac_mode;
+
+        for channel in 0..self.channels {
+            let dc = self.block_dc[channel];
+            let info = &mut self.prediction[channel][mb_x];
+            info.dc = dc[0];
+            match self.format {
+                ColorFormat::Yuv420 if channel > 0 => info.ad[..2].copy_from_slice(&dc[1..3]),
+                ColorFormat::Yuv422 if channel > 0 => {
+                    info.ad[..5].copy_from_slice(&[dc[1], dc[2], dc[5], dc[6], dc[4]]);
+                }
+                _ => info.ad = [dc[1], dc[2], dc[3], dc[4], dc[8], dc[12]],
+            }
+        }
+
+        for channel in 0..self.channels {
+            let chroma = channel > 0 && self.format.is_subsampled();
+            let left = self.prediction[channel].get(mb_x.wrapping_sub(1)).copied();
+            let top = self.previous_row_prediction[channel][mb_x];
+            let dc = &mut self.block_dc[channel];
+            match dc_mode {
+                0 => dc[0] -= left.unwrap_or_default().dc,
+                1 => dc[0] -= top.dc,
+                2 => {
+                    let left = left.unwrap_or_default().dc;
+                    dc[0] -= (left + top.dc + i32::from(chroma)) >> 1;
+                }
+                _ => {}
+            }
+
+            match (self.format, ad_mode) {
+                (ColorFormat::Yuv420, 1) if chroma => dc[2] -= top.ad[1],
+                (ColorFormat::Yuv420, 0) if chroma => dc[1] -= left.unwrap_or_default().ad[0],
+                (ColorFormat::Yuv422, 1) if chroma => {
+                    dc[4] -= top.ad[4];
+                    dc[6] -= dc[2];
+                    dc[2] -= top.ad[3];
+                }
+                (ColorFormat::Yuv422, 0) if chroma => {
+                    let left = left.unwrap_or_default();
+                    dc[4] -= left.ad[4];
+                    dc[1] -= left.ad[0];
+                    dc[5] -= left.ad[2];
+                }
+                (_, 1) if !chroma => {
+                    dc[4] -= top.ad[3];
+                    dc[8] -= top.ad[4];
+                    dc[12] -= top.ad[5];
+                }
+                (_, 0) if !chroma => {
+                    let left = left.unwrap_or_default();
+                    dc[1] -= left.ad[0];
+                    dc[2] -= left.ad[1];
+                    dc[3] -= left.ad[2];
+                }
+                _ => {}
+            }
+
+            // Predicts the first row of a block from the one above, or its first column from
+            // the one `distance` to the left.
+            let p = &mut self.coefficients[channel];
+            match (self.format, ac_mode) {
+                (ColorFormat::Yuv420, 1) if chroma => {
+                    for j in [16, 48] {
+                        predict_top(p, j);
+                    }
+                }
+                (ColorFormat::Yuv420, 0) if chroma => {
+                    for j in [32, 48] {
+                        predict_left(p, j, 32);
+                    }
+                }
+                (ColorFormat::Yuv422, 1) if chroma => {
+                    for j in [48, 32, 16] {
+                        for k in [0, 64] {
+                            predict_top(p, j + k);
+                        }
+                    }
+                }
+                (ColorFormat::Yuv422, 0) if chroma => {
+                    for j in (64..=112).step_by(16) {
+                        predict_left(p, j, 64);
+                    }
+                }
+                (_, 1) if !chroma => {
+                    for k in (0..=192).step_by(64) {
+                        for j in [48, 32, 16] {
+                            predict_top(p, k + j);
+                        }
+                    }
+                }
+                (_, 0) if !chroma => {
+                    for k in (0..64).step_by(16) {
+                        for j in [192, 128, 64] {
+                            predict_left(p, k + j, 64);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn encode_dc(&mut self, writer: &mut BitWriter) {
+        let context = &mut self.context;
+        let model = &context.model_dc;
+        let mut laplacian_mean = [0; 2];
+        let put_value = |writer: &mut BitWriter, value: i32, model_bits: u32| {
+            writer.put(value.unsigned_abs(), model_bits);
+            if value != 0 {
+                writer.put((value < 0).into(), 1);
+            }
+        };
+
+        if self.format == ColorFormat::YOnly {
+            let dc = self.block_dc[0][0];
+            let quotient = dc.unsigned_abs() >> model.flc_bits[0];
+            if quotient != 0 {
+                writer.put(1, 1);
+                put_significant_abs_level(writer, &mut context.expt[3], quotient);
+                laplacian_mean[0] += 1;
+            } else {
+                writer.put(0, 1);
+            }
+            put_value(writer, dc, model.flc_bits[0]);
+        } else {
+            let [y, u, v] = [0, 1, 2].map(|channel| self.block_dc[channel][0]);
+            let qy = y.unsigned_abs() >> model.flc_bits[0];
+            let qu = u.unsigned_abs() >> model.flc_bits[1];
+            let qv = v.unsigned_abs() >> model.flc_bits[1];
+            let index = usize::from(qy != 0) * 4 + usize::from(qu != 0) * 2 + usize::from(qv != 0);
+            writer.put_code(context.expt[2].codes[index]);
+            if qy != 0 {
+                put_significant_abs_level(writer, &mut context.expt[3], qy);
+                laplacian_mean[0] += 1;
+            }
+            put_value(writer, y, model.flc_bits[0]);
+            for (value, quotient) in [(u, qu), (v, qv)] {
+                if quotient != 0 {
+                    put_significant_abs_level(writer, &mut context.expt[4], quotient);
+                    laplacian_mean[1] += 1;
+                }
+                put_value(writer, value, model.flc_bits[1]);
+            }
+        }
+        context
+            .model_dc
+            .update(self.format, self.channels, laplacian_mean);
+    }
+
+    fn encode_lowpass(&mut self, writer: &mut BitWriter) {
+        let format = self.format;
+        let context = &mut self.context;
+        let model_bits = context.model_lp.flc_bits;
+        let mut laplacian_mean = [0; 2];
+        let mut run_levels: [Vec<(usize, i32)>; 3] = Default::default();
+        let mut residuals = [[0; 16]; 3];
+
+        if self.reset_totals {
+            reset_scan_totals(&mut context.scan_lowpass);
+        }
+
+        let mut full_channels = if format.is_subsampled() {
+            1
+        } else {
+            self.channels
+        };
+        for channel in 0..full_channels {
+            adaptive_scan(
+                &self.block_dc[channel],
+                &mut residuals[channel],
+                &mut context.scan_lowpass,
+                model_bits[channel.min(1)],
+                0,
+                &mut run_levels[channel],
+            );
+        }
+
+        // The chroma channels are interleaved and coded as a single one.
+        let mut quotients = [[0; 8]; 2];
+        if format.is_subsampled() {
+            const REMAP: [usize; 7] = [4, 1, 2, 3, 5, 6, 7];
+            let (remap, count) = if format == ColorFormat::Yuv420 {
+                (&REMAP[1..], 6)
+            } else {
+                (&REMAP[..], 14)
+            };
+            let mut run = 0;
+            for k in 0..count {
+                let index = remap[k >> 1];
+                let dc = self.block_dc[(k & 1) + 1][index];
+                let quotient = dc.abs() >> model_bits[1];
+                quotients[k & 1][index] = quotient;
+                if quotient != 0 {
+                    run_levels[1].push((run, if dc < 0 { -quotient } else { quotient }));
+                    run = 0;
+                } else {
+                    run += 1;
+                }
+            }
+            full_channels = 2;
+        }
+
+        if format == ColorFormat::YOnly {
+            writer.put((!run_levels[0].is_empty()).into(), 1);
+        } else {
+            let max = full_channels as i32 * 4 - 5;
+            let mut cbp =
+                i32::from(!run_levels[0].is_empty()) + i32::from(!run_levels[1].is_empty()) * 2;
+            if full_channels == 3 {
+                cbp += i32::from(!run_levels[2].is_empty()) * 4;
+            }
+            let (count_max, count_zero) = (context.cbp_count_max, context.cbp_count_zero);
+            let bits = full_channels as u32;
+            if count_zero <= 0 || count_max < 0 {
+                let value = if count_max < count_zero {
+                    max - cbp
+                } else {
+                    cbp
+                };
+                match value {
+                    0 => writer.put(0, 1),
+                    1 => writer.put((bits + 1) & 6, bits),
+                    _ => writer.put((value + max + 1) as u32, bits + 1),
+                }
+            } else {
+                writer.put(cbp as u32, bits);
+            }
+            let update = |count: i32, hit: bool| (count + 1 - 4 * i32::from(hit)).clamp(-8, 7);
+            context.cbp_count_max = update(count_max, cbp == max);
+            context.cbp_count_zero = update(count_zero, cbp == 0);
+        }
+
+        for channel in 0..full_channels {
+            let bits = model_bits[channel.min(1)];
+            let chroma = channel > 0;
+            if !run_levels[channel].is_empty() {
+                laplacian_mean[channel.min(1)] += run_levels[channel].len() as i32;
+                let location = match format {
+                    ColorFormat::Yuv420 if chroma => 10,
+                    ColorFormat::Yuv422 if chroma => 2,
+                    _ => 1,
+                };
+                put_block(
+                    writer,
+                    &mut context.expt,
+                    chroma,
+                    &run_levels[channel],
+                    CodingContext::LOWPASS_OFFSET,
+                    location,
+                );
+            }
+            if bits == 0 {
+                continue;
+            }
+            if format.is_subsampled() && chroma {
+                let count = if format == ColorFormat::Yuv420 { 4 } else { 8 };
+                for k in 1..count {
+                    for (i, dc) in [&self.block_dc[1], &self.block_dc[2]].iter().enumerate() {
+                        writer.put(dc[k].unsigned_abs(), bits);
+                        if quotients[i][k] == 0 && dc[k] != 0 {
+                            writer.put((dc[k] < 0).into(), 1);
+                        }
+                    }
+                }
+            } else {
+                for &residual in &residuals[channel][1..] {
+                    writer.put((residual >> 1) as u32, bits + (residual & 1) as u32);
+                }
+            }
+        }
+
+        context
+            .model_lp
+            .update(format, self.channels, laplacian_mean);
+        if self.reset_context {
+            context.adapt_lowpass();
+        }
+    }
+
+    /// Computes the coded block patterns of each channel, and predicts them from the
+    /// neighbouring blocks.
+    fn predict_cbp(&mut self, mb_x: usize) {
+        for channel in 0..self.channels {
+            let chroma = channel > 0;
+            let threshold = (1 << self.context.model_ac.flc_bits[usize::from(chroma)]) - 1;
+            let blocks = self.blocks(channel);
+            let data = &self.coefficients[channel];
+            let mut cbp = 0;
+            for (j, &offset) in blocks.iter().enumerate() {
+                if data[offset + 1..offset + 16]
+                    .iter()
+                    .any(|v| v.abs() > threshold)
+                {
+                    cbp |= 1 << j;
+                }
+            }
+            self.cbp[channel] = cbp;
+            self.prediction[channel][mb_x].cbp = cbp;
+
+            let neighbour_bit = |left: u32, top: u32| {
+                if self.ctx_left {
+                    if self.ctx_top {
+                        1
+                    } else {
+                        (self.previous_row_prediction[channel][mb_x].cbp >> top) & 1
+                    }
+                } else {
+                    (self.prediction[channel][mb_x - 1].cbp >> left) & 1
+                }
+            };
+            let (predicted, ones, mask) = match blocks.len() {
+                16 => (
+                    neighbour_bit(5, 10)
+                        | (cbp & 0x3300) << 2
+                        | (cbp & 0xcc) << 6
+                        | (cbp & 0x33) << 2
+                        | (cbp & 0x11) << 1
+                        | (cbp & 0x2) << 3,
+                    cbp.count_ones() as i32,
+                    0xffff,
+                ),
+                8 => (
+                    neighbour_bit(1, 6)
+                        | (cbp & 0x1) << 1
+                        | (cbp & 0x3) << 2
+                        | (cbp & 0xc) << 2
+                        | (cbp & 0x30) << 2,
+                    cbp.count_ones() as i32 * 2,
+                    0xff,
+                ),
+                _ => (
+                    neighbour_bit(1, 2) | (cbp & 0x1) << 1 | (cbp & 0x3) << 2,
+                    cbp.count_ones() as i32 * 4,
+                    0xf,
+                ),
+            };
+
+            let context = &mut self.context;
+            let c = usize::from(chroma);
+            self.diff_cbp[channel] = match context.cbp_state[c] {
+                0 => predicted ^ cbp,
+                1 => cbp,
+                _ => cbp ^ mask,
+            };
+            const AVERAGE_DIFFERENCE: i32 = 3;
+            let saturate = |count: i32| {
+                if !(-16..16).contains(&count) {
+                    if count < 0 { -16 } else { 15 }
+                } else {
+                    count
+                }
+            };
+            context.cbp_count0[c] = saturate(context.cbp_count0[c] + ones - AVERAGE_DIFFERENCE);
+            context.cbp_count1[c] =
+                saturate(context.cbp_count1[c] + 16 - ones - AVERAGE_DIFFERENCE);
+            context.cbp_state[c] = if context.cbp_count0[c] < 0 {
+                if context.cbp_count0[c] < context.cbp_count1[c] {
+                    1
+                } else {
+                    2
+                }
+            } else if context.cbp_count1[c] < 0 {
+                2
+            } else {
+                0
+            };
+        }
+    }
+
+    fn encode_cbp(&mut self, writer: &mut BitWriter) {
+        const ONES: [usize; 16] = [0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4];
+        const PATTERN_LENGTHS: [u32; 16] = [0, 2, 2, 2, 2, 2, 3, 2, 2, 3, 3, 2, 3, 2, 2, 0];
+        const PATTERN_CODES: [u32; 16] = [0, 0, 1, 0, 2, 1, 4, 3, 3, 5, 6, 2, 7, 1, 0, 0];
+        const BLOCK_COUNTS: [i32; 16] = [0, 1, 1, 2, 1, 3, 3, 4, 1, 3, 3, 4, 2, 4, 4, 5];
+        const BLOCK_LENGTHS: [u32; 16] = [0, 2, 2, 1, 2, 2, 2, 2, 2, 2, 2, 2, 1, 2, 2, 0];
+        const BLOCK_CODES: [u32; 16] = [0, 0, 1, 0, 2, 0, 1, 0, 3, 2, 3, 1, 1, 2, 3, 0];
+
+        let format = self.format;
+        let [mut diff_y, mut diff_u, mut diff_v] = self.diff_cbp;
+        let (u, v) = (diff_u, diff_v);
+        match format {
+            ColorFormat::Yuv420 => {
+                diff_y = (diff_y & 0xf)
+                    + ((u & 1) << 4)
+                    + ((v & 1) << 5)
+                    + ((diff_y & 0xf0) << 2)
+                    + ((u & 2) << 9)
+                    + ((v & 2) << 10)
+                    + ((diff_y & 0xf00) << 4)
+                    + ((u & 4) << 14)
+                    + ((v & 4) << 15)
+                    + ((diff_y & 0xf000) << 6)
+                    + ((u & 8) << 19)
+                    + ((v & 8) << 20);
+            }
+            ColorFormat::Yuv422 => {
+                diff_y = (diff_y & 0xf)
+                    + ((u & 1) << 4)
+                    + ((u & 4) << 3)
+                    + ((v & 1) << 6)
+                    + ((v & 4) << 5)
+                    + ((diff_y & 0xf0) << 4)
+                    + ((u & 2) << 11)
+                    + ((u & 8) << 10)
+                    + ((v & 2) << 13)
+                    + ((v & 8) << 12)
+                    + ((diff_y & 0xf00) << 8)
+                    + ((u & 16) << 16)
+                    + ((u & 64) << 15)
+                    + ((v & 16) << 18)
+                    + ((v & 64) << 17)
+                    + ((diff_y & 0xf000) << 12)
+                    + ((u & 32) << 23)
+                    + ((u & 128) << 22)
+                    + ((v & 32) << 25)
+                    + ((v & 128) << 24);
+            }
+            _ => {}
+        }
+
+        let (group_bits, group_mask) = match format {
+            ColorFormat::Yuv422 => (8, 0xff),
+            ColorFormat::Yuv420 => (6, 0x3f),
+            _ => (4, 0xf),
+        };
+        let mut any = diff_y;
+        if format == ColorFormat::Yuv444 {
+            any |= diff_u | diff_v;
+        }
+        let mut pattern = 0;
+        for group in 0..4 {
+            pattern |= usize::from((any >> (group * group_bits)) & group_mask != 0) << group;
+        }
+
+        let context = &mut self.context;
+        context.cbpcy1.put(writer, ONES[pattern]);
+        if PATTERN_LENGTHS[pattern] != 0 {
+            writer.put(PATTERN_CODES[pattern], PATTERN_LENGTHS[pattern]);
+        }
+
+        for _ in 0..4 {
+            let mut code;
+            let (mut code_u, mut code_v) = (0, 0);
+            if format == ColorFormat::Yuv444 {
+                code_u = (diff_u & 0xf) as usize;
+                code_v = (diff_v & 0xf) as usize;
+                code = (diff_y & 0xf) as usize | usize::from(code_u != 0) << 4;
+                code |= usize::from(code_v != 0) << 5;
+                diff_u >>= 4;
+                diff_v >>= 4;
+            } else {
+                code = (diff_y & group_mask) as usize;
+            }
+            diff_y >>= group_bits;
+            if code == 0 {
+                continue;
+            }
+
+            let mut chroma = code >> 4;
+            code &= 0xf;
+            if format == ColorFormat::Yuv422 {
+                code_u = chroma & 3;
+                code_v = (chroma >> 2) & 3;
+                chroma = usize::from(code_u != 0) + 2 * usize::from(code_v != 0);
+            }
+            let count = BLOCK_COUNTS[code];
+            let symbol = if chroma != 0 {
+                if count > 2 { 8 } else { count as usize + 5 }
+            } else {
+                count as usize - 1
+            };
+            context.cbpcy.put(writer, symbol);
+            if chroma == 1 {
+                writer.put(1, 1);
+            } else if chroma != 0 {
+                writer.put(3 - chroma as u32, 2);
+            }
+            if symbol == 8 {
+                if count == 3 {
+                    writer.put(1, 1);
+                } else {
+                    writer.put(5 - count as u32, 2);
+                }
+            }
+            if BLOCK_LENGTHS[code] != 0 {
+                writer.put(BLOCK_CODES[code], BLOCK_LENGTHS[code]);
+            }
+            match format {
+                ColorFormat::Yuv444 => {
+                    for chroma_pattern in [code_u, code_v] {
+                        if chroma_pattern != 0 {
+                            writer.put_code(context.expt[1].codes[ONES[chroma_pattern] - 1]);
+                            if PATTERN_LENGTHS[chroma_pattern] != 0 {
+                                writer.put(
+                                    PATTERN_CODES[chroma_pattern],
+                                    PATTERN_LENGTHS[chroma_pattern],
+                                );
+                            }
+                        }
+                    }
+                }
+                ColorFormat::Yuv422 => {
+                    for chroma_pattern in [code_u, code_v] {
+                        if chroma_pattern == 1 {
+                            writer.put(1, 1);
+                        } else if chroma_pattern != 0 {
+                            writer.put(3 - chroma_pattern as u32, 2);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn encode_highpass(&mut self, writer: &mut BitWriter, mb_x: usize) {
+        if self.reset_totals {
+            reset_scan_totals(&mut self.context.scan_horizontal);
+            reset_scan_totals(&mut self.context.scan_vertical);
+        }
+        self.predict_cbp(mb_x);
+        self.encode_cbp(writer);
+        self.encode_coefficients(writer);
+        if self.reset_context {
+            self.context.adapt_highpass();
+        }
+    }
+
+    fn encode_coefficients(&mut self, writer: &mut BitWriter) {
+        let format = self.format;
+        let context = &mut self.context;
+        let trim = context.trim_flex_bits;
+        // Returns the number of bits that are trimmed and the number that are sent as is.
+        let flex_bits = |model_bits: u32| {
+            if trim <= model_bits {
+                (trim, model_bits - trim)
+            } else {
+                (0, 0)
+            }
+        };
+        let mut model_bits = context.model_ac.flc_bits[0];
+        let (mut trim_bits, mut flex) = flex_bits(model_bits);
+        let mut laplacian_mean = [0; 2];
+        let mut chroma = false;
+        let mut run_levels = Vec::with_capacity(15);
+        let scan = if self.orientation == 1 {
+            &mut context.scan_vertical
+        } else {
+            &mut context.scan_horizontal
+        };
+
+        let (planes, block_count) = match format {
+            ColorFormat::Yuv420 => (1, 6),
+            ColorFormat::Yuv422 => (1, 8),
+            _ => (self.channels, 4),
+        };
+        for plane in 0..planes {
+            let mut pattern = self.cbp[plane];
+            match format {
+                ColorFormat::Yuv420 => pattern += (self.cbp[1] << 16) + (self.cbp[2] << 20),
+                ColorFormat::Yuv422 => pattern += (self.cbp[1] << 16) + (self.cbp[2] << 24),
+                _ => {}
+            }
+            let mut index = 0;
+            for block in 0..block_count {
+                for subblock in 0..4 {
+                    let (channel, offset) = if block < 4 {
+                        (plane, BLOCK_OFFSETS[index])
+                    } else if format == ColorFormat::Yuv420 {
+                        (block - 3, BLOCK_OFFSETS_420[subblock])
+                    } else {
+                        let offset = BLOCK_OFFSETS_422[(block & 1) * 4 + subblock];
+                        (1 + ((block - 4) >> 1), offset)
+                    };
+                    let coefficients = &self.coefficients[channel][offset..offset + 16];
+                    if pattern & 1 == 0 {
+                        if flex > 0 {
+                            let mask = (1 << flex) - 1;
+                            for &position in &AC_INDEX[1..] {
+                                let value = coefficients[position];
+                                let trimmed = value.unsigned_abs() >> trim_bits;
+                                let mut word = trimmed & mask;
+                                let mut length = flex;
+                                if trimmed != 0 {
+                                    word = word * 2 + u32::from(value < 0);
+                                    length += 1;
+                                }
+                                writer.put(word, length);
+                            }
+                        }
+                    } else {
+                        let mut residuals = [0; 16];
+                        adaptive_scan(
+                            coefficients,
+                            &mut residuals,
+                            scan,
+                            model_bits,
+                            trim_bits,
+                            &mut run_levels,
+                        );
+                        laplacian_mean[usize::from(chroma)] += run_levels.len() as i32;
+                        put_block(
+                            writer,
+                            &mut context.expt,
+                            chroma,
+                            &run_levels,
+                            CodingContext::HIGHPASS_OFFSET,
+                            1,
+                        );
+                        if flex > 0 {
+                            for &position in &AC_INDEX[1..] {
+                                let residual = residuals[position];
+                                writer.put((residual >> 1) as u32, flex + (residual & 1) as u32);
+                            }
+                        }
+                    }
+                    pattern >>= 1;
+                    index += 1;
+                }
+                if block == 3 {
+                    model_bits = context.model_ac.flc_bits[1];
+                    (trim_bits, flex) = flex_bits(model_bits);
+                    chroma = true;
+                }
+            }
+        }
+        context
+            .model_ac
+            .update(format, self.channels, laplacian_mean);
+    }
+}
+
+fn predict_top(p: &mut [i32], block: usize) {
+    for i in [block + 10, block + 2, block + 9] {
+        p[i] -= p[i - 16];
+    }
+}
+
+fn predict_left(p: &mut [i32], block: usize, distance: usize) {
+    for i in [block + 5, block + 1, block + 6] {
+        p[i] -= p[i - distance];
+    }
+}
+
+fn orientation(horizontal: i32, vertical: i32) -> i32 {
+    if horizontal * 4 < vertical {
+        1
+    } else if vertical * 4 < horizontal {
+        0
+    } else {
+        2
+    }
+}
@@ -6,6 +6,7 @@ use crate::bitmap::bitmap_data::{
     BitmapData, BitmapDataDrawError, BitmapRawData, ChannelOptions, Color, IBitmapDrawable,
     LehmerRng, ThresholdOperation,
 };
+use crate::bitmap::jpegxr::{self, JpegXrOptions};
 use crate::bitmap::turbulence::Turbulence;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{RenderOptions, TDisplayObject};
@@ -1525,6 +1526,7 @@ pub fn draw<'gc>(
     }
 
     let mut transform_stack = ruffle_render::transform::TransformStack::new();
+    transform_stack.set_projection_width(context.stage.stage_size().0 as f32);
     transform_stack.push(&transform);
 
     let mut cache_draws = vec![];
@@ -1538,6 +1540,7 @@ pub fn draw<'gc>(
         is_offscreen: true,
         use_bitmap_cache: false,
         stage: context.stage,
+        time: &*context.time,
     };
 
     // Make the screen opacity match the opacity of this bitmap
@@ -1715,6 +1718,94 @@ pub fn get_pixels_as_byte_array<'gc>(
     Ok(())
 }
 
+/// An image format that a `BitmapData` can be encoded into.
+#[derive(Clone, Copy, Debug)]
+pub enum BitmapEncoding {
+    Png { fast_compression: bool },
+    Jpeg { quality: u8 },
+    JpegXr(JpegXrOptions),
+}
+
+/// Encodes a region of a `BitmapData` into an image file.
+///
+/// Returns an empty buffer if the region doesn't contain any pixels.
+pub fn encode(
+    target: BitmapData,
+    renderer: &mut dyn RenderBackend,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+    encoding: BitmapEncoding,
+) -> Result<Vec<u8>, image::ImageError> {
+    use image::codecs::jpeg::JpegEncoder;
+    use image::codecs::png::{CompressionType, FilterType, PngEncoder};
+    use image::{ExtendedColorType, ImageEncoder};
+
+    let mut region = PixelRegion::for_region_i32(x, y, width, height);
+    region.clamp(target.width(), target.height());
+    if region.width() == 0 || region.height() == 0 {
+        return Ok(vec![]);
+    }
+
+    // JPEG has no alpha channel, and opaque bitmaps don't need one.
+    let with_alpha = target.transparency()
+        && matches!(
+            encoding,
+            BitmapEncoding::Png { .. } | BitmapEncoding::JpegXr(_)
+        );
+    let bytes_per_pixel = if with_alpha { 4 } else { 3 };
+    let mut pixels =
+        Vec::with_capacity(region.width() as usize * region.height() as usize * bytes_per_pixel);
+
+    let read = target.read_area(region, renderer);
+    for y in region.y_min..region.y_max {
+        for x in region.x_min..region.x_max {
+            let color = read.get_pixel32_raw(x, y).to_un_multiplied_alpha();
+            pixels.extend_from_slice(&[color.red(), color.green(), color.blue()]);
+            if with_alpha {
+                pixels.push(color.alpha());
+            }
+        }
+    }
+
+    let color_type = if with_alpha {
+        ExtendedColorType::Rgba8
+    } else {
+        ExtendedColorType::Rgb8
+    };
+    let mut output = vec![];
+    match encoding {
+        BitmapEncoding::Png { fast_compression } => {
+            let compression = if fast_compression {
+                CompressionType::Fast
+            } else {
+                CompressionType::Best
+            };
+            PngEncoder::new_with_quality(&mut output, compression, FilterType::Adaptive)
+                .write_image(&pixels, region.width(), region.height(), color_type)?;
+        }
+        BitmapEncoding::Jpeg { quality } => {
+            JpegEncoder::new_with_quality(&mut output, quality).write_image(
+                &pixels,
+                region.width(),
+                region.height(),
+                color_type,
+            )?;
+        }
+        BitmapEncoding::JpegXr(options) => {
+            output = jpegxr::encode(
+                &pixels,
+                region.width(),
+                region.height(),
+                with_alpha,
+                options,
+            );
+        }
+    }
+    Ok(output)
+}
+
 #[expect(clippy::too_many_arguments)]
 pub fn set_pixels_from_byte_array<'gc>(
     mc: &Mutation<'gc>,
@@ -7,12 +7,14 @@ use crate::avm1::Avm1;
 use crate::avm1::{Object as Avm1Object, Value as Avm1Value};
 use crate::avm2::Activation as Avm2Activation;
 use crate::avm2::api_version::ApiVersion;
-use crate::avm2::{Avm2, LoaderInfoObject, SharedObjectObject, SoundChannelObject};
+use crate::avm2::{Avm2, Avm2Debugger, LoaderInfoObject, SharedObjectObject, SoundChannelObject};
 use crate::backend::{
     audio::{AudioBackend, AudioManager, SoundHandle, SoundInstanceHandle},
+    filesystem::FileSystemBackend,
     log::LogBackend,
     navigator::NavigatorBackend,
     storage::StorageBackend,
+    time::TimeBackend,
     ui::UiBackend,
 };
 use crate::context_menu::ContextMenuState;
@@ -30,6 +32,7 @@ use crate::orphan_manager::OrphanManager;
 use crate::player::PostFrameCallback;
 use crate::player::{MouseData, Player};
 use crate::prelude::*;
+use crate::security::SecurityManager;
 use crate::socket::Sockets;
 use crate::streams::StreamManager;
 use crate::string::HasStringContext;
@@ -110,6 +113,9 @@ pub struct UpdateContext<'gc> {
     /// The storage backend, used for storing persistent state
     pub storage: &'gc mut dyn StorageBackend,
 
+    /// The filesystem backend, used by the AIR `flash.filesystem` APIs.
+    pub filesystem: &'gc mut dyn FileSystemBackend,
+
     /// The logging backend, used for trace output capturing.
     ///
     /// **DO NOT** use this field directly, use the `avm_trace` method instead.
@@ -118,6 +124,9 @@ pub struct UpdateContext<'gc> {
     /// The video backend, used for video decoding
     pub video: &'gc mut dyn VideoBackend,
 
+    /// The time backend, used for dates, timers and the RNG seed.
+    pub time: &'gc mut dyn TimeBackend,
+
     /// The RNG, used by the AVM `RandomNumber` opcode, `Math.random(),` and `random()`.
     pub rng: &'gc mut AvmRng,
 
@@ -152,6 +161,12 @@ pub struct UpdateContext<'gc> {
 
     pub page_url: &'gc mut Option<String>,
 
+    /// The permissions movies have been granted by each other and by policy files.
+    pub security: &'gc mut SecurityManager,
+
+    /// The debugger for AVM2 code.
+    pub avm2_debugger: &'gc mut Avm2Debugger,
+
     /// The current instance ID. Used to generate default `instanceN` names.
     pub instance_counter: &'gc mut i32,
 
@@ -178,10 +193,13 @@ pub struct UpdateContext<'gc> {
     /// External interface for (for example) JavaScript <-> ActionScript interaction
     pub external_interface: &'gc mut ExternalInterface<'gc>,
 
-    /// The instant at which the SWF was launched.
+    /// The instant at which the SWF was launched, according to the time backend.
     pub start_time: Instant,
 
     /// The instant at which the current update started.
+    ///
+    /// This always follows the system clock rather than the time backend,
+    /// so that execution limits still apply when time is virtual.
     pub update_start: Instant,
 
     /// The maximum amount of time that can be called before a `Error::ExecutionTimeout`
@@ -594,6 +612,9 @@ pub struct RenderContext<'a, 'gc> {
 
     /// The current player's stage (including all loaded levels)
     pub stage: Stage<'gc>,
+
+    /// The time backend, used to animate the text caret.
+    pub time: &'a dyn TimeBackend,
 }
 
 impl<'gc> RenderContext<'_, 'gc> {
@@ -1,6 +1,7 @@
 mod avm1;
 mod avm2;
 mod avm2_class;
+mod avm2_debugger;
 mod common;
 mod display_object;
 mod domain;
@@ -12,6 +13,7 @@ use crate::context::{RenderContext, UpdateContext};
 use crate::debug_ui::avm1::Avm1ObjectWindow;
 use crate::debug_ui::avm2::Avm2ObjectWindow;
 use crate::debug_ui::avm2_class::Avm2ClassWindow;
+use crate::debug_ui::avm2_debugger::Avm2DebuggerWindow;
 use crate::debug_ui::display_object::{DisplayObjectSearchWindow, DisplayObjectWindow};
 use crate::debug_ui::domain::DomainListWindow;
 use crate::debug_ui::font::FontWindow;
@@ -43,6 +45,7 @@ pub struct DebugUi {
     movie_list: Option<MovieListWindow>,
     domain_list: Option<DomainListWindow>,
     display_object_search: Option<DisplayObjectSearchWindow>,
+    avm2_debugger: Option<Avm2DebuggerWindow>,
 }
 
 #[derive(Debug)]
@@ -58,6 +61,7 @@ pub enum Message {
     TrackTopLevelMovie,
     ShowKnownMovies,
     ShowDomains,
+    ShowAvm2Debugger,
     SaveFile(ItemToSave),
     SearchForDisplayObject,
     TrackRootMovieClip,
@@ -118,6 +122,12 @@ impl DebugUi {
             self.display_object_search = Some(search);
         }
 
+        if let Some(mut debugger) = self.avm2_debugger.take()
+            && debugger.show(egui_ctx, context)
+        {
+            self.avm2_debugger = Some(debugger);
+        }
+
         for message in messages {
             match message {
                 Message::TrackDisplayObject(object) => {
@@ -157,6 +167,9 @@ impl DebugUi {
                 Message::ShowDomains => {
                     self.domain_list = Some(Default::default());
                 }
+                Message::ShowAvm2Debugger => {
+                    self.avm2_debugger = Some(Default::default());
+                }
                 Message::SearchForDisplayObject => {
                     self.display_object_search = Some(Default::default());
                 }
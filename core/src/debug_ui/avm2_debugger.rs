@@ -0,0 +1,261 @@
+use crate::avm2::StepKind;
+use crate::avm2::debugger::{DebugFrame, DebugScope, DebugVariable, PauseReason};
+use crate::context::UpdateContext;
+use egui::{Button, CollapsingHeader, DragValue, Grid, TextEdit, Ui, Window};
+
+#[derive(Debug)]
+pub struct Avm2DebuggerWindow {
+    breakpoint_file: String,
+    breakpoint_line: u32,
+    watch_expression: String,
+    selected_frame: usize,
+}
+
+impl Default for Avm2DebuggerWindow {
+    fn default() -> Self {
+        Self {
+            breakpoint_file: String::new(),
+            breakpoint_line: 1,
+            watch_expression: String::new(),
+            selected_frame: 0,
+        }
+    }
+}
+
+impl Avm2DebuggerWindow {
+    pub fn show(&mut self, egui_ctx: &egui::Context, context: &mut UpdateContext) -> bool {
+        let mut keep_open = true;
+
+        Window::new("AVM2 Debugger")
+            .open(&mut keep_open)
+            .show(egui_ctx, |ui| {
+                self.show_controls(ui, context);
+                ui.separator();
+
+                CollapsingHeader::new("Breakpoints")
+                    .default_open(true)
+                    .show(ui, |ui| self.show_breakpoints(ui, context));
+
+                CollapsingHeader::new("Watches")
+                    .default_open(true)
+                    .show(ui, |ui| self.show_watches(ui, context));
+
+                CollapsingHeader::new("Call Stack")
+                    .default_open(true)
+                    .show(ui, |ui| self.show_call_stack(ui, context));
+            });
+
+        keep_open
+    }
+
+    fn show_controls(&mut self, ui: &mut Ui, context: &mut UpdateContext) {
+        let debugger = &mut *context.avm2_debugger;
+
+        let mut attached = debugger.is_attached();
+        if ui
+            .checkbox(&mut attached, "Attached")
+            .on_hover_text(
+                "Only code that first runs after attaching, or code of movies with an \
+                 EnableDebugger tag, can be debugged.",
+            )
+            .changed()
+        {
+            if attached {
+                debugger.attach();
+            } else {
+                debugger.detach();
+            }
+        }
+
+        ui.weak(
+            "Execution isn't suspended: a break records every line until the script \
+             finishes, then pauses the player. Stepping replays that trace, so the code \
+             shown has already run.",
+        );
+
+        let status = match debugger.paused_state().map(|state| state.reason) {
+            Some(PauseReason::Breakpoint) => "Replaying trace: breakpoint",
+            Some(PauseReason::Pause) => "Replaying trace: pause",
+            Some(PauseReason::Step) => "Replaying trace: step",
+            None if debugger.is_attached() => "Running",
+            None => "Detached",
+        };
+        match debugger.trace_position() {
+            Some((position, len)) => ui.label(format!(
+                "{status} (recorded line {} of {len})",
+                position + 1
+            )),
+            None => ui.label(status),
+        };
+
+        let paused = debugger.is_paused();
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(attached && !paused, Button::new("Pause"))
+                .clicked()
+            {
+                debugger.pause();
+            }
+            if ui
+                .add_enabled(paused, Button::new("Continue"))
+                .on_hover_text("Replay to the next recorded breakpoint, or resume the player.")
+                .clicked()
+            {
+                debugger.resume();
+                self.selected_frame = 0;
+            }
+            for (label, kind) in [
+                ("Step Into", StepKind::Into),
+                ("Step Over", StepKind::Over),
+                ("Step Out", StepKind::Out),
+            ] {
+                if ui
+                    .add_enabled(paused, Button::new(label))
+                    .on_hover_text("Replay the trace, resuming the player past its end.")
+                    .clicked()
+                {
+                    debugger.step(kind);
+                    self.selected_frame = 0;
+                }
+            }
+        });
+    }
+
+    fn show_breakpoints(&mut self, ui: &mut Ui, context: &mut UpdateContext) {
+        let debugger = &mut *context.avm2_debugger;
+
+        let mut to_remove = None;
+        Grid::new(ui.id().with("breakpoints"))
+            .num_columns(2)
+            .show(ui, |ui| {
+                for (index, breakpoint) in debugger.breakpoints().iter().enumerate() {
+                    ui.label(format!("{}:{}", breakpoint.file, breakpoint.line));
+                    if ui.button("Remove").clicked() {
+                        to_remove = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = to_remove {
+            debugger.remove_breakpoint(index);
+        }
+
+        ui.horizontal(|ui| {
+            TextEdit::singleline(&mut self.breakpoint_file)
+                .hint_text("File")
+                .show(ui);
+            ui.add(DragValue::new(&mut self.breakpoint_line).range(1..=u32::MAX));
+            if ui
+                .add_enabled(!self.breakpoint_file.is_empty(), Button::new("Add"))
+                .clicked()
+            {
+                debugger.add_breakpoint(self.breakpoint_file.trim(), self.breakpoint_line);
+            }
+        });
+    }
+
+    fn show_watches(&mut self, ui: &mut Ui, context: &mut UpdateContext) {
+        let debugger = &mut *context.avm2_debugger;
+
+        let values = debugger.paused_state().map(|state| &state.watches);
+        let mut to_remove = None;
+        Grid::new(ui.id().with("watches"))
+            .num_columns(3)
+            .show(ui, |ui| {
+                for (index, expression) in debugger.watches().iter().enumerate() {
+                    ui.label(expression);
+                    match values.and_then(|values| values.get(index)) {
+                        Some(watch) => match &watch.value {
+                            Ok(value) => ui.label(value),
+                            Err(error) => ui.label(format!("<{error}>")),
+                        },
+                        None => ui.weak("(not paused)"),
+                    };
+                    if ui.button("Remove").clicked() {
+                        to_remove = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = to_remove {
+            debugger.remove_watch(index);
+        }
+
+        ui.horizontal(|ui| {
+            TextEdit::singleline(&mut self.watch_expression)
+                .hint_text("Expression")
+                .show(ui);
+            if ui
+                .add_enabled(!self.watch_expression.is_empty(), Button::new("Add"))
+                .clicked()
+            {
+                debugger.add_watch(std::mem::take(&mut self.watch_expression));
+            }
+        });
+    }
+
+    fn show_call_stack(&mut self, ui: &mut Ui, context: &mut UpdateContext) {
+        let Some(state) = context.avm2_debugger.paused_state() else {
+            ui.weak("Not paused");
+            return;
+        };
+
+        for (index, frame) in state.frames.iter().enumerate() {
+            let location = match (&frame.file, frame.line) {
+                (Some(file), Some(line)) => format!("{} ({file}:{line})", frame.function),
+                _ => frame.function.clone(),
+            };
+            if ui
+                .selectable_label(self.selected_frame == index, location)
+                .clicked()
+            {
+                self.selected_frame = index;
+            }
+        }
+
+        if let Some(frame) = state.frames.get(self.selected_frame) {
+            ui.separator();
+            show_frame(ui, frame);
+        }
+    }
+}
+
+fn show_frame(ui: &mut Ui, frame: &DebugFrame) {
+    CollapsingHeader::new("Locals")
+        .default_open(true)
+        .show(ui, |ui| show_variables(ui, "locals", &frame.locals));
+
+    CollapsingHeader::new("Scope Chain")
+        .default_open(true)
+        .show(ui, |ui| {
+            for (index, scope) in frame.scopes.iter().enumerate() {
+                show_scope(ui, index, scope);
+            }
+        });
+}
+
+fn show_scope(ui: &mut Ui, index: usize, scope: &DebugScope) {
+    let title = if scope.with {
+        format!("with {}", scope.object)
+    } else {
+        scope.object.clone()
+    };
+
+    if scope.properties.is_empty() {
+        ui.label(title);
+    } else {
+        CollapsingHeader::new(title)
+            .id_salt(index)
+            .show(ui, |ui| show_variables(ui, index, &scope.properties));
+    }
+}
+
+fn show_variables(ui: &mut Ui, id: impl std::hash::Hash, variables: &[DebugVariable]) {
+    Grid::new(ui.id().with(id)).num_columns(2).show(ui, |ui| {
+        for variable in variables {
+            ui.label(&variable.name);
+            ui.label(&variable.value);
+            ui.end_row();
+        }
+    });
+}
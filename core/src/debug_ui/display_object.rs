@@ -328,14 +328,6 @@ impl DisplayObjectWindow {
         Grid::new(ui.id().with("edittext"))
             .num_columns(2)
             .show(ui, |ui| {
-                ui.label("Is FTE");
-                if object.is_fte() {
-                    ui.label("Yes");
-                } else {
-                    ui.label("No");
-                }
-                ui.end_row();
-
                 ui.label("Border");
                 ui.horizontal(|ui| {
                     let mut has_border = object.has_border();
@@ -809,6 +801,7 @@ impl DisplayObjectWindow {
             LayoutContent::Text { .. } => "Text box",
             LayoutContent::Bullet { .. } => "Bullet box",
             LayoutContent::Drawing { .. } => "Drawing box",
+            LayoutContent::Image { .. } => "Image box",
         };
 
         let box_text = serde_json::to_string(
@@ -18,8 +18,10 @@ use gc_arena::barrier::{Write, unlock};
 use gc_arena::lock::Lock;
 use gc_arena::{Collect, Gc, Mutation};
 use ruffle_macros::{enum_trait_object, istr};
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::perspective_projection::PerspectiveProjection;
 use ruffle_render::pixel_bender::PixelBenderShaderHandle;
+use ruffle_render::shape_utils::ScalingGrid;
 use ruffle_render::transform::{Transform, TransformStack};
 use std::cell::{Cell, Ref, RefCell, RefMut};
 use std::fmt::Debug;
@@ -272,6 +274,7 @@ pub struct DisplayObjectBase<'gc> {
     matrix: Cell<Matrix>,
     color_transform: Cell<ColorTransform>,
     perspective_projection: Cell<Option<PerspectiveProjection>>,
+    matrix3d: Cell<Option<Matrix3D>>,
 
     // Cached transform properties `_xscale`, `_yscale`, `_rotation`.
     // These are expensive to calculate, so they will be calculated and cached
@@ -348,6 +351,7 @@ impl Default for DisplayObjectBase<'_> {
             matrix: Default::default(),
             color_transform: Default::default(),
             perspective_projection: Default::default(),
+            matrix3d: Cell::new(None),
             rotation: Cell::new(Degrees::from_radians(0.0)),
             scale_x: Cell::new(Percent::from_unit(1.0)),
             scale_y: Cell::new(Percent::from_unit(1.0)),
@@ -406,6 +410,7 @@ impl<'gc> DisplayObjectBase<'gc> {
             } else {
                 Matrix::IDENTITY
             },
+            matrix3d: if apply_matrix { self.matrix3d() } else { None },
             color_transform: self.color_transform.get(),
             perspective_projection: self.perspective_projection.get(),
         }
@@ -440,6 +445,47 @@ impl<'gc> DisplayObjectBase<'gc> {
         perspective_projection != old
     }
 
+    /// The 3D transformation matrix of this object, or `None` if this object
+    /// isn't transformed in 3D.
+    ///
+    /// While an object is transformed in 3D, its 2D matrix is kept in sync
+    /// with the 2D part of this one.
+    pub fn matrix3d(&self) -> Option<Matrix3D> {
+        self.matrix3d.get()
+    }
+
+    /// Sets the 3D transformation matrix of this object.
+    /// Setting `None` turns this object back into a 2D one, with an identity matrix.
+    pub fn set_matrix3d(&self, matrix3d: Option<Matrix3D>) {
+        self.matrix3d.set(matrix3d);
+        self.set_matrix(matrix3d.map_or(Matrix::IDENTITY, Matrix3D::to_matrix));
+    }
+
+    /// Turns this object into a 3D one, keeping its current 2D transformation.
+    pub fn ensure_matrix3d(&self) -> Matrix3D {
+        let matrix3d = self
+            .matrix3d
+            .get()
+            .unwrap_or_else(|| Matrix3D::from_matrix(self.matrix.get()));
+        self.matrix3d.set(Some(matrix3d));
+        matrix3d
+    }
+
+    /// Updates the components of the 3D transformation matrix of this object,
+    /// turning it into a 3D one if needed.
+    pub fn update_matrix3d_components(
+        &self,
+        update: impl FnOnce(&mut [f64; 3], &mut [f64; 3], &mut [f64; 3]),
+    ) {
+        let (mut translation, mut rotation, mut scale) = self.ensure_matrix3d().to_components();
+        update(&mut translation, &mut rotation, &mut scale);
+        self.set_matrix3d(Some(Matrix3D::from_components(
+            translation,
+            rotation,
+            scale,
+        )));
+    }
+
     fn x(&self) -> Twips {
         self.matrix.get().tx
     }
@@ -449,6 +495,10 @@ impl<'gc> DisplayObjectBase<'gc> {
         let changed = matrix.tx != x;
         matrix.tx = x;
         self.matrix.set(matrix);
+        if let Some(mut matrix3d) = self.matrix3d.get() {
+            matrix3d.raw_data[12] = x.to_pixels() as f32;
+            self.matrix3d.set(Some(matrix3d));
+        }
         self.set_transformed_by_script(true);
         changed
     }
@@ -462,6 +512,10 @@ impl<'gc> DisplayObjectBase<'gc> {
         let changed = matrix.ty != y;
         matrix.ty = y;
         self.matrix.set(matrix);
+        if let Some(mut matrix3d) = self.matrix3d.get() {
+            matrix3d.raw_data[13] = y.to_pixels() as f32;
+            self.matrix3d.set(Some(matrix3d));
+        }
         self.set_transformed_by_script(true);
         changed
     }
@@ -539,10 +593,24 @@ impl<'gc> DisplayObjectBase<'gc> {
         matrix.c = (scale_y * -sin_y) as f32;
         matrix.d = (scale_y * cos_y) as f32;
         self.matrix.set(matrix);
+        self.sync_matrix3d(|_, rotation, _| rotation[2] = degrees.into_radians());
 
         changed
     }
 
+    /// Applies a change of the 2D rotation or scale of a 3D object to its 3D matrix.
+    fn sync_matrix3d(&self, update: impl FnOnce(&mut [f64; 3], &mut [f64; 3], &mut [f64; 3])) {
+        if let Some(matrix3d) = self.matrix3d.get() {
+            let (mut translation, mut rotation, mut scale) = matrix3d.to_components();
+            update(&mut translation, &mut rotation, &mut scale);
+            self.matrix3d.set(Some(Matrix3D::from_components(
+                translation,
+                rotation,
+                scale,
+            )));
+        }
+    }
+
     fn scale_x(&self) -> Percent {
         self.cache_scale_rotation();
         self.scale_x.get()
@@ -574,6 +642,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         matrix.a = (cos * value.unit()) as f32;
         matrix.b = (sin * value.unit()) as f32;
         self.matrix.set(matrix);
+        self.sync_matrix3d(|_, _, scale| scale[0] = value.unit());
 
         changed
     }
@@ -610,6 +679,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         matrix.c = (-sin * value.unit()) as f32;
         matrix.d = (cos * value.unit()) as f32;
         self.matrix.set(matrix);
+        self.sync_matrix3d(|_, _, scale| scale[1] = value.unit());
 
         changed
     }
@@ -898,14 +968,6 @@ impl<'gc> DisplayObjectBase<'gc> {
     fn set_meta_data(this: &Write<Self>, value: Avm2Object<'gc>) {
         unlock!(this, Self, meta_data).set(Some(value));
     }
-
-    pub fn has_matrix3d_stub(&self) -> bool {
-        self.contains_flag(DisplayObjectFlags::HAS_MATRIX3D_STUB)
-    }
-
-    pub fn set_has_matrix3d_stub(&self, value: bool) {
-        self.set_flag(DisplayObjectFlags::HAS_MATRIX3D_STUB, value)
-    }
 }
 
 /// Indicates which kind of bounds should be returned by `self_bounds`.
@@ -939,6 +1001,12 @@ struct DrawCacheInfo {
     handle: BitmapHandle,
     dirty: bool,
     base_transform: Transform,
+
+    /// The matrix the contents are drawn with into the cached bitmap.
+    ///
+    /// This is the object's matrix, unless it's transformed in 3D: such
+    /// objects are drawn flat, and the bitmap is projected when drawn back.
+    cache_matrix: Matrix,
     bounds: Rectangle<Twips>,
     draw_offset: Point<i32>,
     filters: Vec<Filter>,
@@ -969,8 +1037,14 @@ pub fn render_base<'gc>(
     let cache_info = if context.use_bitmap_cache && this.is_bitmap_cached() {
         let mut cache_info: Option<DrawCacheInfo> = None;
         let base_transform = context.transform_stack.transform();
+        let cache_matrix = if base_transform.matrix3d.is_some() {
+            let stage_matrix = context.stage.view_matrix();
+            Matrix::scale(stage_matrix.a, stage_matrix.d)
+        } else {
+            base_transform.matrix
+        };
         let bounds: Rectangle<Twips> = this.render_bounds_with_transform(
-            &base_transform.matrix,
+            &cache_matrix,
             false, // we want to do the filter growth for this object ourselves, to know the offsets
             &context.stage.view_matrix(),
         );
@@ -1004,10 +1078,10 @@ pub fn render_base<'gc>(
                     y_max: filter_rect.y_max.to_pixels().ceil() as i32,
                 };
                 let draw_offset = Point::new(filter_rect.x_min, filter_rect.y_min);
-                if cache.is_dirty(&base_transform.matrix, width, height) {
+                if cache.is_dirty(&cache_matrix, width, height) {
                     cache.update(
                         context.renderer,
-                        base_transform.matrix,
+                        cache_matrix,
                         width,
                         height,
                         filter_rect.width() as u32,
@@ -1019,6 +1093,7 @@ pub fn render_base<'gc>(
                         handle,
                         dirty: true,
                         base_transform,
+                        cache_matrix,
                         bounds,
                         draw_offset,
                         filters,
@@ -1028,6 +1103,7 @@ pub fn render_base<'gc>(
                         handle,
                         dirty: false,
                         base_transform,
+                        cache_matrix,
                         bounds,
                         draw_offset,
                         filters,
@@ -1055,20 +1131,22 @@ pub fn render_base<'gc>(
         // In order to render an object to a texture, we need to draw its entire bounds.
         // Calculate the offset from tx/ty in order to accommodate any drawings that extend the bounds
         // negatively
-        let offset_x = cache_info.bounds.x_min - cache_info.base_transform.matrix.tx
+        let offset_x = cache_info.bounds.x_min - cache_info.cache_matrix.tx
             + Twips::from_pixels_i32(cache_info.draw_offset.x);
-        let offset_y = cache_info.bounds.y_min - cache_info.base_transform.matrix.ty
+        let offset_y = cache_info.bounds.y_min - cache_info.cache_matrix.ty
             + Twips::from_pixels_i32(cache_info.draw_offset.y);
 
         if cache_info.dirty {
             let mut transform_stack = TransformStack::new();
+            transform_stack.set_projection_width(context.stage.stage_size().0 as f32);
             transform_stack.push(&Transform {
                 color_transform: Default::default(),
                 matrix: Matrix {
                     tx: -offset_x,
                     ty: -offset_y,
-                    ..cache_info.base_transform.matrix
+                    ..cache_info.cache_matrix
                 },
+                matrix3d: None,
                 perspective_projection: cache_info.base_transform.perspective_projection,
             });
             let mut offscreen_context = RenderContext {
@@ -1081,6 +1159,7 @@ pub fn render_base<'gc>(
                 is_offscreen: true,
                 use_bitmap_cache: true,
                 stage: context.stage,
+                time: context.time,
             };
             this.render_self(&mut offscreen_context);
             offscreen_context.cache_draws.push(BitmapCacheEntry {
@@ -1096,14 +1175,36 @@ pub fn render_base<'gc>(
             this,
             context,
             |context| {
+                let transform = context.transform_stack.transform();
+                // Objects transformed in 3D were drawn flat, so the image is
+                // transformed the same way their contents would have been.
+                let (matrix, matrix3d) = if let Some(matrix3d) = transform.matrix3d {
+                    let cache_matrix = cache_info.cache_matrix;
+                    let (scale_x, scale_y) = (1.0 / cache_matrix.a, 1.0 / cache_matrix.d);
+                    let (x, y) = (offset_x.to_pixels() as f32, offset_y.to_pixels() as f32);
+                    (
+                        transform.matrix
+                            * Matrix::scale(scale_x, scale_y)
+                            * Matrix::translate(offset_x, offset_y),
+                        Some(
+                            matrix3d
+                                .multiply(&Matrix3D::scale(scale_x, scale_y, 1.0))
+                                .multiply(&Matrix3D::translate(x, y, 0.0)),
+                        ),
+                    )
+                } else {
+                    let matrix = Matrix {
+                        tx: transform.matrix.tx + offset_x,
+                        ty: transform.matrix.ty + offset_y,
+                        ..Default::default()
+                    };
+                    (matrix, None)
+                };
                 context.commands.render_bitmap(
                     cache_info.handle,
                     Transform {
-                        matrix: Matrix {
-                            tx: context.transform_stack.transform().matrix.tx + offset_x,
-                            ty: context.transform_stack.transform().matrix.ty + offset_y,
-                            ..Default::default()
-                        },
+                        matrix,
+                        matrix3d,
                         color_transform: cache_info.base_transform.color_transform,
                         perspective_projection: cache_info.base_transform.perspective_projection,
                     },
@@ -1190,6 +1291,7 @@ pub fn apply_standard_mask_and_scroll<'gc, F>(
         // Translate everything that we render (including DisplayObject.mask)
         context.transform_stack.push(&Transform {
             matrix: Matrix::translate(-rect.x_min, -rect.y_min),
+            matrix3d: None,
             color_transform: Default::default(),
             perspective_projection: None,
         });
@@ -1511,6 +1613,45 @@ pub trait TDisplayObject<'gc>:
         self.local_to_global_matrix_without_own_scroll_rect() * matrix
     }
 
+    /// Returns the matrix for transforming from this object's local space to global stage space,
+    /// projecting 3D content the same way it is rendered.
+    #[no_dynamic]
+    fn local_to_global_matrix3d(self, stage: Stage<'gc>) -> Matrix3D {
+        let mut ancestors = vec![];
+        let mut node = self.parent();
+        while let Some(display_object) = node {
+            if display_object.as_stage().is_some() {
+                break;
+            }
+            ancestors.push(display_object);
+            node = display_object.parent();
+        }
+
+        let mut transform_stack = TransformStack::new();
+        transform_stack.set_projection_width(stage.stage_size().0 as f32);
+        // We want to transform to Stage-local coordinates,
+        // so only apply the Stage's projection and not its matrix
+        transform_stack.push(&Transform {
+            perspective_projection: stage.base().perspective_projection(),
+            ..Default::default()
+        });
+        for display_object in ancestors.into_iter().rev() {
+            transform_stack.push(&display_object.base().transform(true));
+            if let Some(rect) = display_object.scroll_rect() {
+                transform_stack.push(&Transform {
+                    matrix: Matrix::translate(-rect.x_min, -rect.y_min),
+                    ..Default::default()
+                });
+            }
+        }
+        transform_stack.push(&self.base().transform(true));
+
+        let transform = transform_stack.transform();
+        transform
+            .matrix3d
+            .unwrap_or_else(|| Matrix3D::from_matrix(transform.matrix))
+    }
+
     /// Returns the matrix for transforming from global stage to this object's local space.
     /// `None` is returned if the object has zero scale.
     #[no_dynamic]
@@ -2027,6 +2168,22 @@ pub trait TDisplayObject<'gc>:
     #[no_dynamic]
     fn set_scaling_grid(self, rect: Rectangle<Twips>) {
         self.base().scaling_grid.set(rect);
+        self.invalidate_cached_bitmap();
+    }
+
+    /// The 9-slice scaling to apply to the shapes drawn by this object and
+    /// by its child shapes, or `None` if it has no effect.
+    #[no_dynamic]
+    fn scaling_grid_mapping(self) -> Option<ScalingGrid> {
+        let grid = self.scaling_grid();
+        if !grid.is_valid() {
+            return None;
+        }
+
+        let matrix = self.base().matrix();
+        let scale_x = f64::from(matrix.a).hypot(matrix.b.into());
+        let scale_y = f64::from(matrix.c).hypot(matrix.d.into());
+        ScalingGrid::new(grid, self.bounds(BoundsMode::Engine), scale_x, scale_y)
     }
 
     #[no_dynamic]
@@ -2388,11 +2545,10 @@ pub trait TDisplayObject<'gc>:
             if let Some(child) = self.object2()
                 && let Some(name) = self.name()
             {
-                let domain = context
-                    .library
-                    .library_for_movie(self.movie())
-                    .unwrap()
-                    .avm2_domain();
+                let domain = context.library.library_for_movie(self.movie()).map_or_else(
+                    || context.avm2.stage_domain(),
+                    |library| library.avm2_domain(),
+                );
 
                 let mut activation = Avm2Activation::from_domain(context, domain);
                 let multiname = Avm2Multiname::new(activation.avm2().find_public_namespace(), name);
@@ -2913,6 +3069,7 @@ impl<'gc> DisplayObject<'gc> {
         pub fn as_morph_shape for MorphShape;
         pub fn as_video for Video;
         pub fn as_bitmap for Bitmap;
+        pub fn as_loader_display for LoaderDisplay;
     }
 
     pub fn as_interactive(self) -> Option<InteractiveObject<'gc>> {
@@ -2995,9 +3152,6 @@ bitflags! {
         /// If this AVM1 object is pending removal (will be removed on the next frame).
         const AVM1_PENDING_REMOVAL     = 1 << 13;
 
-        /// Whether this object has matrix3D (used for stubbing).
-        const HAS_MATRIX3D_STUB        = 1 << 14;
-
         /// Whether this object has been placed by an AVM1 method,
         /// i.e. attachMovie, createEmptyMovieClip, duplicateMovieClip.
         // TODO [KJ] Can this be merged with PLACED_BY_AVM2_SCRIPT?
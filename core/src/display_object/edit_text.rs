@@ -4,17 +4,19 @@ use crate::avm1::{
     Activation as Avm1Activation, ActivationIdentifier, Avm1, ExecutionReason,
     NativeObject as Avm1NativeObject, Object as Avm1Object, Value as Avm1Value,
 };
+use crate::avm2::globals::flash::display::loader::create_loader;
 use crate::avm2::object::{
     ClassObject as Avm2ClassObject, EventObject as Avm2EventObject, StageObject as Avm2StageObject,
     StyleSheetObject as Avm2StyleSheetObject,
 };
-use crate::avm2::{Activation as Avm2Activation, Avm2};
+use crate::avm2::{Activation as Avm2Activation, Avm2, Value as Avm2Value};
+use crate::backend::navigator::Request;
 use crate::backend::ui::MouseCursor;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::interactive::{
     InteractiveObject, InteractiveObjectBase, TInteractiveObject,
 };
-use crate::display_object::{Avm1TextFieldBinding, BoundsMode, DisplayObjectBase};
+use crate::display_object::{Avm1TextFieldBinding, BoundsMode, DisplayObjectBase, MovieClip};
 use crate::events::{
     ClipEvent, ClipEventResult, ImeCursorArea, ImeEvent, ImeNotification, ImePurpose,
     PlayerNotification, TextControlCode,
@@ -25,12 +27,12 @@ use crate::html::StyleSheet;
 use crate::html::{
     FormatSpans, Layout, LayoutBox, LayoutContent, LayoutLine, LayoutMetrics, Position, TextFormat,
 };
+use crate::loader::MovieLoaderVMData;
+use crate::player::PostFrameCallback;
 use crate::prelude::*;
 use crate::string::{AvmString, SwfStrExt as _, WStr, WString, utils as string_utils};
 use crate::tag_utils::SwfMovie;
 use crate::vminterface::{AvmObject, Instantiator};
-use chrono::DateTime;
-use chrono::Utc;
 use core::fmt;
 use gc_arena::barrier::unlock;
 use gc_arena::lock::{Lock, RefLock};
@@ -47,6 +49,7 @@ use std::collections::VecDeque;
 use std::sync::Arc;
 use swf::ColorTransform;
 use unicode_segmentation::UnicodeSegmentation;
+use web_time::Instant;
 
 use super::interactive::Avm2MousePick;
 
@@ -119,6 +122,13 @@ pub struct EditTextData<'gc> {
     /// The calculated layout.
     layout: RefLock<Layout<'gc>>,
 
+    /// The images embedded in the text, in the same order as in `text_spans`.
+    images: RefLock<Vec<EditTextImage<'gc>>>,
+
+    /// Whether we're waiting for the content of images to load in order to
+    /// lay out the text again.
+    awaiting_image_loads: Cell<bool>,
+
     /// Style sheet used when parsing HTML.
     style_sheet: Lock<EditTextStyleSheet<'gc>>,
 
@@ -187,15 +197,6 @@ pub struct EditTextData<'gc> {
 
     /// Flags specifying how layout debug boxes should be drawn.
     layout_debug_boxes_flags: Cell<LayoutDebugBoxesFlag>,
-
-    /// Whether this EditText represents an AVM2 TextLine.
-    ///
-    /// FTE (Flash Text Engine) is a low-level API for sophisticated text control.
-    ///
-    /// See <https://docs.ruffle.rs/en_US/FlashPlatform/reference/actionscript/3/flash/text/engine/TextLine.html>
-    /// See <https://docs.ruffle.rs/en_US/FlashPlatform/reference/actionscript/3/flash/text/engine/package-detail.html>
-    /// See <https://docs.ruffle.rs/en_US/as3/dev/WS9dd7ed846a005b294b857bfa122bd808ea6-8000.html>
-    is_fte: Cell<bool>,
 }
 
 impl EditTextData<'_> {
@@ -217,8 +218,6 @@ impl EditTextData<'_> {
     fn font_type(&self) -> FontType {
         if !self.flags.get().contains(EditTextFlag::USE_OUTLINES) {
             FontType::Device
-        } else if self.is_fte.get() {
-            FontType::EmbeddedCFF
         } else {
             FontType::Embedded
         }
@@ -332,6 +331,8 @@ impl<'gc> EditText<'gc> {
                 border_color: Cell::new(Color::BLACK),
                 object: Lock::new(None),
                 layout: RefLock::new(Default::default()),
+                images: RefLock::new(Vec::new()),
+                awaiting_image_loads: Cell::new(false),
                 bounds: Cell::new(*swf_tag.bounds()),
                 autosize_lazy_bounds: Cell::new(None),
                 autosize: Cell::new(autosize),
@@ -344,7 +345,6 @@ impl<'gc> EditText<'gc> {
                 scroll: Cell::new(1),
                 max_chars: Cell::new(swf_tag.max_length().unwrap_or_default() as i32),
                 mouse_wheel_enabled: Cell::new(true),
-                is_fte: Cell::new(false),
                 restrict: RefCell::new(EditTextRestrict::allow_all()),
                 last_click: Cell::new(None),
                 layout_debug_boxes_flags: Cell::new(LayoutDebugBoxesFlag::empty()),
@@ -393,22 +393,6 @@ impl<'gc> EditText<'gc> {
         text_field
     }
 
-    /// Create a new, dynamic `EditText` representing an AVM2 TextLine.
-    pub fn new_fte(
-        context: &mut UpdateContext<'gc>,
-        swf_movie: Arc<SwfMovie>,
-        x: f64,
-        y: f64,
-        width: f64,
-        height: f64,
-    ) -> Self {
-        let text = Self::new(context, swf_movie, x, y, width, height);
-        text.set_is_fte(true);
-        text.set_selectable(false);
-
-        text
-    }
-
     pub fn instantiate(self, mc: &Mutation<'gc>) -> Self {
         Self(Gc::new(mc, (*self.0).clone()))
     }
@@ -742,14 +726,6 @@ impl<'gc> EditText<'gc> {
         self.relayout(context);
     }
 
-    pub fn is_fte(self) -> bool {
-        self.0.is_fte.get()
-    }
-
-    pub fn set_is_fte(self, is_fte: bool) {
-        self.0.is_fte.set(is_fte);
-    }
-
     pub fn layout_debug_boxes_flag(self, flag: LayoutDebugBoxesFlag) -> bool {
         self.0.layout_debug_boxes_flags.get().contains(flag)
     }
@@ -773,14 +749,20 @@ impl<'gc> EditText<'gc> {
         m.d / m.a
     }
 
-    /// Returns the matrix for transforming from layout
-    /// coordinate space into this object's local space.
-    fn layout_to_local_matrix(self) -> Matrix {
+    /// Returns the matrix moving the origin of the layout to where it's
+    /// shown in this object's local space, taking scrolling into account.
+    fn layout_origin_matrix(self) -> Matrix {
         let bounds = self.0.bounds.get();
-        let matrix = Matrix::translate(
+        Matrix::translate(
             bounds.x_min + Self::GUTTER - Twips::from_pixels(self.0.hscroll.get()),
             bounds.y_min + Self::GUTTER - self.0.vertical_scroll_offset(),
-        );
+        )
+    }
+
+    /// Returns the matrix for transforming from layout
+    /// coordinate space into this object's local space.
+    fn layout_to_local_matrix(self) -> Matrix {
+        let matrix = self.layout_origin_matrix();
 
         if self.font_type() == FontType::Device {
             // Device text cannot be scaled independently in x/y.
@@ -883,6 +865,8 @@ impl<'gc> EditText<'gc> {
         let movie = self.0.shared.swf.clone();
         let padding = Self::GUTTER * 2;
 
+        self.update_images(context);
+
         let mut text_spans = self.0.text_spans.borrow_mut();
         if self.0.flags.get().contains(EditTextFlag::PASSWORD) {
             // If the text is a password, hide the text
@@ -899,8 +883,10 @@ impl<'gc> EditText<'gc> {
             None
         };
 
+        let image_sizes = self.image_sizes(&text_spans);
         let new_layout = html::lower_from_text_spans(
             &text_spans,
+            &image_sizes,
             context,
             movie,
             content_width,
@@ -914,6 +900,7 @@ impl<'gc> EditText<'gc> {
         // reset scroll
         self.0.hscroll.set(0.0);
         self.0.scroll.set(1);
+        self.position_images(context);
 
         let text_size = self.0.layout.borrow().text_size();
 
@@ -945,6 +932,276 @@ impl<'gc> EditText<'gc> {
         self.invalidate_cached_bitmap();
     }
 
+    /// Make sure every image embedded in the text has a display object,
+    /// reusing the ones created for the previous text.
+    fn update_images(self, context: &mut UpdateContext<'gc>) {
+        let text_images = self.0.text_spans.borrow().images().to_vec();
+        let mut old_images = std::mem::take(
+            &mut *unlock!(Gc::write(context.gc(), self.0), EditTextData, images).borrow_mut(),
+        );
+
+        let mut images = Vec::with_capacity(text_images.len());
+        for text_image in text_images {
+            let old_image = old_images
+                .iter()
+                .position(|image| image.src == text_image.src && image.id == text_image.id)
+                .map(|i| old_images.remove(i));
+            let image = old_image.unwrap_or_else(|| {
+                let object =
+                    self.load_image(context, &text_image.src, text_image.check_policy_file);
+                if let Some(object) = object {
+                    object.set_parent(context, Some(self.into()));
+                    if !text_image.id.is_empty() {
+                        object.set_name(
+                            context.gc(),
+                            AvmString::new(context.gc(), text_image.id.clone()),
+                        );
+                    }
+                }
+                EditTextImage {
+                    src: text_image.src,
+                    id: text_image.id,
+                    object,
+                    content_bounds: Default::default(),
+                }
+            });
+            images.push(image);
+        }
+
+        for old_image in old_images {
+            if let Some(object) = old_image.object {
+                object.set_parent(context, None);
+            }
+        }
+
+        *unlock!(Gc::write(context.gc(), self.0), EditTextData, images).borrow_mut() = images;
+    }
+
+    /// Create a display object showing the given image source.
+    ///
+    /// The source is either the name of a library symbol, or a URL of an
+    /// image or a movie to load.
+    fn load_image(
+        self,
+        context: &mut UpdateContext<'gc>,
+        src: &WStr,
+        check_policy_file: bool,
+    ) -> Option<DisplayObject<'gc>> {
+        let movie = self.movie();
+        let url = src.to_utf8_lossy().into_owned();
+
+        if movie.is_action_script_3() {
+            let domain = context.library.library_for_movie(movie)?.avm2_domain();
+            let mut activation = Avm2Activation::from_domain(context, domain);
+            let name = AvmString::new(activation.gc(), src);
+
+            let result = if domain.has_defined_value_handling_vector(&mut activation, name) {
+                domain
+                    .get_defined_value_handling_vector(&mut activation, name)
+                    .and_then(
+                        |class| match class.as_object().and_then(|o| o.as_class_object()) {
+                            Some(class) => class.construct(&mut activation, &[]),
+                            None => Ok(Avm2Value::Null),
+                        },
+                    )
+                    .map(|instance| instance.as_object())
+            } else {
+                create_loader(&mut activation, Request::get(url), check_policy_file).map(Some)
+            };
+
+            match result {
+                Ok(instance) => instance.and_then(|instance| instance.as_display_object()),
+                Err(e) => {
+                    tracing::error!("Failed to create image {src} embedded in text: {e:?}");
+                    None
+                }
+            }
+        } else {
+            // Access to content loaded by AVM1 movies isn't checked, so
+            // there's no policy file to wait for.
+            if let Some(symbol) = context
+                .library
+                .library_for_movie(movie.clone())
+                .filter(|library| library.character_by_export_name(src).is_some())
+                .and_then(|library| library.instantiate_by_export_name(src, context.gc()))
+            {
+                symbol.post_instantiation(context, None, Instantiator::Avm1, false);
+                return Some(symbol);
+            }
+
+            let clip = MovieClip::new(movie, context.gc());
+            clip.post_instantiation(context, None, Instantiator::Avm1, false);
+            let future = context.load_manager.load_movie_into_clip(
+                context.player_handle(),
+                clip.into(),
+                Request::get(url),
+                None,
+                MovieLoaderVMData::Avm1 {
+                    broadcaster: None,
+                    base_clip: clip.into(),
+                },
+            );
+            context.navigator.spawn_future(future);
+            Some(clip.into())
+        }
+    }
+
+    /// Calculate the size of each image embedded in the text.
+    ///
+    /// Images without an explicit size take the size of their content.
+    fn image_sizes(self, text_spans: &FormatSpans) -> Vec<(Twips, Twips)> {
+        let images = self.0.images.borrow();
+        text_spans
+            .images()
+            .iter()
+            .zip(images.iter())
+            .map(|(text_image, image)| {
+                let (content_width, content_height) = image
+                    .object
+                    .map(|object| object.bounds(BoundsMode::Script))
+                    .filter(Rectangle::is_valid)
+                    .map(|bounds| (bounds.width(), bounds.height()))
+                    .unwrap_or_default();
+                (
+                    text_image.width.map_or(content_width, Twips::from_pixels),
+                    text_image.height.map_or(content_height, Twips::from_pixels),
+                )
+            })
+            .collect()
+    }
+
+    /// Measure the content of images and lay out their display objects.
+    fn position_images(self, context: &mut UpdateContext<'gc>) {
+        let mut images =
+            unlock!(Gc::write(context.gc(), self.0), EditTextData, images).borrow_mut();
+        for image in images.iter_mut() {
+            image.content_bounds = image
+                .object
+                .map(|object| object.bounds(BoundsMode::Script))
+                .unwrap_or_default();
+        }
+        drop(images);
+
+        self.update_image_matrices();
+
+        let is_loading = self
+            .0
+            .images
+            .borrow()
+            .iter()
+            .any(|image| image.object.is_some() && !image.content_bounds.is_valid());
+        if is_loading {
+            self.await_image_loads(context);
+        }
+    }
+
+    /// Move and scale the display objects of images to fit their layout
+    /// boxes, which scroll along with the text.
+    fn update_image_matrices(self) {
+        let origin = self.layout_origin_matrix();
+        let layout = self.0.layout.borrow();
+        let images = self.0.images.borrow();
+
+        for image_box in layout.images() {
+            let LayoutContent::Image { index, .. } = image_box.content() else {
+                continue;
+            };
+            let Some(image) = images.get(*index) else {
+                continue;
+            };
+            let Some(object) = image.object else {
+                continue;
+            };
+            let content_bounds = image.content_bounds;
+            if !content_bounds.is_valid() {
+                continue;
+            }
+
+            let box_bounds = image_box.bounds();
+            let scale = |size: Twips, content_size: Twips| {
+                if content_size > Twips::ZERO {
+                    size.get() as f32 / content_size.get() as f32
+                } else {
+                    1.0
+                }
+            };
+            let scale_x = scale(box_bounds.width(), content_bounds.width());
+            let scale_y = scale(box_bounds.height(), content_bounds.height());
+
+            object.set_matrix(
+                origin
+                    * Matrix::translate(box_bounds.offset_x(), box_bounds.offset_y())
+                    * Matrix::scale(scale_x, scale_y)
+                    * Matrix::translate(-content_bounds.x_min, -content_bounds.y_min),
+            );
+        }
+    }
+
+    /// Lay out the text again once the content of its images has loaded.
+    ///
+    /// Stops waiting once no image is being loaded anymore, so that images
+    /// which failed to load or have no content keep their empty size.
+    fn await_image_loads(self, context: &mut UpdateContext<'gc>) {
+        if self.0.awaiting_image_loads.replace(true) {
+            return;
+        }
+
+        context.post_frame_callbacks.push(PostFrameCallback {
+            callback: Box::new(|context, display_object: DisplayObject<'_>| {
+                let Some(this) = display_object.as_edit_text() else {
+                    return;
+                };
+                this.0.awaiting_image_loads.set(false);
+
+                let has_loaded = this.0.images.borrow().iter().any(|image| {
+                    image.object.is_some_and(|object| {
+                        object.bounds(BoundsMode::Script) != image.content_bounds
+                    })
+                });
+                if has_loaded {
+                    this.relayout(context);
+                } else if this.is_loading_images(context) {
+                    this.await_image_loads(context);
+                }
+            }),
+            data: self.into(),
+        });
+    }
+
+    /// The display objects of the images embedded in the text.
+    ///
+    /// They are children of the text field, even though they are not part
+    /// of a display list.
+    fn image_objects(self) -> Vec<DisplayObject<'gc>> {
+        self.0
+            .images
+            .borrow()
+            .iter()
+            .filter_map(|image| image.object)
+            .collect()
+    }
+
+    /// Whether the content of any image embedded in the text is still
+    /// being downloaded or parsed.
+    fn is_loading_images(self, context: &UpdateContext<'gc>) -> bool {
+        self.0.images.borrow().iter().any(|image| {
+            image
+                .object
+                .is_some_and(|object| context.load_manager.is_loading_into(object))
+        })
+    }
+
+    /// Returns the display object showing the image embedded in the text
+    /// with the given ID.
+    pub fn image_reference(self, id: &WStr) -> Option<DisplayObject<'gc>> {
+        self.0
+            .images
+            .borrow()
+            .iter()
+            .find(|image| image.id == id)
+            .and_then(|image| image.object)
+    }
+
     /// Apply lazily calculated autosize bounds.
     ///
     /// They should be applied only in specific places, as they influence
@@ -969,6 +1226,7 @@ impl<'gc> EditText<'gc> {
     pub fn apply_autosize_bounds(self) {
         if let Some(bounds) = self.0.autosize_lazy_bounds.take() {
             self.0.bounds.set(bounds);
+            self.update_image_matrices();
             // Note: We do not have to invalidate cache here.
             //   Cache has already been invalidated on relayout, and
             //   we will apply this anyway before render.
@@ -1120,6 +1378,15 @@ impl<'gc> EditText<'gc> {
         }
     }
 
+    /// Render the images embedded in the text.
+    fn render_images(self, context: &mut RenderContext<'_, 'gc>) {
+        for image in self.0.images.borrow().iter() {
+            if let Some(object) = image.object {
+                object.render(context);
+            }
+        }
+    }
+
     /// Render the visible text along with selection and the caret.
     fn render_text(
         self,
@@ -1173,14 +1440,22 @@ impl<'gc> EditText<'gc> {
             line.leading()
         };
 
-        let x_start = line
-            .char_x_bounds(local_start)
-            .map(|b| b.0)
-            .unwrap_or_else(|| line_bounds.offset_x());
-        let x_end = line
-            .char_x_bounds(local_end - 1)
-            .map(|b| b.1)
-            .unwrap_or_else(|| line_bounds.extent_x());
+        let start_bounds = line.char_x_bounds(local_start);
+        let end_bounds = line.char_x_bounds(local_end - 1);
+
+        // Right-to-left text is selected from right to left, and selections
+        // spanning both directions are not contiguous, so we draw the area
+        // containing both ends of the selection.
+        let x_start = match (start_bounds, end_bounds) {
+            (Some(start), Some(end)) => start.0.min(end.0),
+            (Some(start), None) => start.0,
+            _ => line_bounds.offset_x(),
+        };
+        let x_end = match (start_bounds, end_bounds) {
+            (Some(start), Some(end)) => start.1.max(end.1),
+            (None, Some(end)) => end.1,
+            _ => line_bounds.extent_x(),
+        };
 
         let width = x_end - x_start;
         let height = line_bounds.height() + leading;
@@ -1249,7 +1524,7 @@ impl<'gc> EditText<'gc> {
                     && !self.0.flags.get().contains(EditTextFlag::READ_ONLY)
                     && visible_selection.start() >= *start
                     && (visible_selection.end() < *end || *end == text_len)
-                    && !visible_selection.blinks_now()
+                    && !visible_selection.blinks_now(context.time.instant())
                 {
                     Some(visible_selection.start() - start)
                 } else {
@@ -1291,6 +1566,7 @@ impl<'gc> EditText<'gc> {
                             // Set text color to white
                             context.transform_stack.push(&Transform {
                                 matrix: transform.matrix,
+                                matrix3d: transform.matrix3d,
                                 color_transform: ColorTransform::IDENTITY,
                                 perspective_projection: transform.perspective_projection,
                             });
@@ -1303,11 +1579,17 @@ impl<'gc> EditText<'gc> {
 
                     // Update caret position
                     if let Some(caret) = caret {
+                        // Right-to-left characters start on their right side.
+                        let (char_start_x, char_end_x) = if params.is_rtl {
+                            (x + advance, x)
+                        } else {
+                            (x, x + advance)
+                        };
                         if pos == caret {
-                            caret_x = x;
+                            caret_x = char_start_x;
                         } else if caret > 0 && pos == caret - 1 {
                             // The caret may be rendered at the end, after all glyphs.
-                            caret_x = x + advance;
+                            caret_x = char_end_x;
                         }
                     }
                 },
@@ -1558,6 +1840,7 @@ impl<'gc> EditText<'gc> {
 
     pub fn set_hscroll(self, hscroll: f64) {
         self.0.hscroll.set(hscroll);
+        self.update_image_matrices();
         self.invalidate_cached_bitmap();
     }
 
@@ -1579,6 +1862,7 @@ impl<'gc> EditText<'gc> {
         if self.0.scroll.replace(clamped) == clamped {
             false
         } else {
+            self.update_image_matrices();
             self.invalidate_cached_bitmap();
             true
         }
@@ -1611,16 +1895,12 @@ impl<'gc> EditText<'gc> {
         let line = layout.lines().get(line_index)?;
 
         // ...then find the box within that line that is the closest match to the X position.
-        let mut closest_layout_box: Option<&LayoutBox<'gc>> = None;
-        for layout_box in line.boxes_iter() {
-            if layout_box.is_text_box() {
-                if position.x >= layout_box.bounds().offset_x() || closest_layout_box.is_none() {
-                    closest_layout_box = Some(layout_box);
-                } else {
-                    break;
-                }
-            }
-        }
+        // Boxes are not sorted by their position when the line contains right-to-left text.
+        let text_boxes = || line.boxes_iter().filter(|b| b.is_text_box());
+        let closest_layout_box = text_boxes()
+            .filter(|b| position.x >= b.bounds().offset_x())
+            .max_by_key(|b| b.bounds().offset_x())
+            .or_else(|| text_boxes().min_by_key(|b| b.bounds().offset_x()));
 
         if let Some(layout_box) = closest_layout_box {
             let origin = layout_box.bounds().origin();
@@ -1632,13 +1912,17 @@ impl<'gc> EditText<'gc> {
                 layout_box.as_renderable_text(self.0.text_spans.borrow().text())
             {
                 let mut result = 0;
+                let mut closest_x = None;
                 font.evaluate(
                     text,
                     self.text_transform(color),
                     params,
                     |pos, _transform, _glyph, advance, x| {
-                        if local_position.x >= x {
-                            if local_position.x > x + (advance / 2) {
+                        if local_position.x >= x && closest_x.is_none_or(|closest_x| x >= closest_x)
+                        {
+                            closest_x = Some(x);
+                            // Right-to-left characters start on their right side.
+                            if (local_position.x > x + (advance / 2)) != params.is_rtl {
                                 result = string_utils::next_char_boundary(text, pos);
                             } else {
                                 result = pos;
@@ -2252,7 +2536,7 @@ impl<'gc> EditText<'gc> {
                     first_format = Some(text_format);
                     break;
                 }
-                LayoutContent::Drawing { .. } => {}
+                LayoutContent::Drawing { .. } | LayoutContent::Image { .. } => {}
             }
         }
 
@@ -2566,12 +2850,28 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         self.0.shared.swf.clone()
     }
 
+    fn enter_frame(self, context: &mut UpdateContext<'gc>) {
+        for image in self.image_objects() {
+            image.enter_frame(context);
+        }
+    }
+
     /// Construct objects placed on this frame.
     fn construct_frame(self, context: &mut UpdateContext<'gc>) {
         if self.movie().is_action_script_3() && self.object2().is_none() {
             self.construct_as_avm2_object(context, self.into());
             self.on_construction_complete(context);
         }
+
+        for image in self.image_objects() {
+            image.construct_frame(context);
+        }
+    }
+
+    fn run_frame_scripts(self, context: &mut UpdateContext<'gc>) {
+        for image in self.image_objects() {
+            image.run_frame_scripts(context);
+        }
     }
 
     fn post_instantiation(
@@ -2749,11 +3049,21 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         );
         context.commands.activate_mask();
 
+        // Images are children of the text field, positioned in its local space.
+        self.render_images(context);
+
         context.transform_stack.push(&Transform {
             matrix: self.layout_to_local_matrix(),
             ..Default::default()
         });
 
+        if let Some(mut selection) = self.0.selection.get()
+            && selection.blink_epoch.is_none()
+        {
+            selection.blink_epoch = Some(context.time.instant());
+            self.0.selection.set(Some(selection));
+        }
+
         let mut render_state = Default::default();
         self.render_text(context, &mut render_state);
 
@@ -3079,13 +3389,25 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
         self,
         context: &mut UpdateContext<'gc>,
         point: Point<Twips>,
-        _require_button_mode: bool,
+        require_button_mode: bool,
     ) -> Option<InteractiveObject<'gc>> {
         // Don't do anything if run in an AVM2 context.
         if self.as_displayobject().movie().is_action_script_3() {
             return None;
         }
 
+        // Images embedded in the text are picked before the text itself.
+        if self.visible() {
+            for image in self.image_objects().into_iter().rev() {
+                if let Some(result) = image
+                    .as_interactive()
+                    .and_then(|image| image.mouse_pick_avm1(context, point, require_button_mode))
+                {
+                    return Some(result);
+                }
+            }
+        }
+
         // The text is hovered if the mouse is over any child nodes.
         if self.visible()
             && self.mouse_enabled()
@@ -3102,13 +3424,30 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
         self,
         context: &mut UpdateContext<'gc>,
         point: Point<Twips>,
-        _require_button_mode: bool,
+        require_button_mode: bool,
     ) -> Avm2MousePick<'gc> {
         // Don't do anything if run in an AVM1 context.
         if !self.as_displayobject().movie().is_action_script_3() {
             return Avm2MousePick::Miss;
         }
 
+        // Images embedded in the text are picked before the text itself.
+        if self.visible() {
+            for image in self.image_objects().into_iter().rev() {
+                let Some(image) = image.as_interactive() else {
+                    continue;
+                };
+                match image.mouse_pick_avm2(context, point, require_button_mode) {
+                    Avm2MousePick::Hit(target) => return Avm2MousePick::Hit(target),
+                    Avm2MousePick::PropagateToParent if self.mouse_enabled() => {
+                        return Avm2MousePick::Hit(self.into());
+                    }
+                    Avm2MousePick::PropagateToParent => return Avm2MousePick::PropagateToParent,
+                    Avm2MousePick::Miss => {}
+                }
+            }
+        }
+
         // The text is hovered if the mouse is over any child nodes.
         if self.visible() && self.hit_test_shape(context, point, HitTestOptions::MOUSE_PICK) {
             // Note - for mouse-enabled selectable text, we consider this to be a hit (which
@@ -3224,6 +3563,26 @@ bitflags::bitflags! {
     }
 }
 
+/// A display object showing an image embedded in the text.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+struct EditTextImage<'gc> {
+    /// The source the image was created from.
+    #[collect(require_static)]
+    src: WString,
+
+    /// The ID of the image, as given in HTML.
+    #[collect(require_static)]
+    id: WString,
+
+    /// The display object showing the image, unless it couldn't be created.
+    object: Option<DisplayObject<'gc>>,
+
+    /// The bounds of the content of the image at the time of the last layout.
+    #[collect(require_static)]
+    content_bounds: Rectangle<Twips>,
+}
+
 /// Data shared between all instances of a text object.
 #[derive(Debug, Clone, Collect)]
 #[collect(require_static)]
@@ -3282,8 +3641,10 @@ pub struct TextSelection {
     from: usize,
     to: usize,
 
-    /// The time the caret should begin blinking
-    blink_epoch: DateTime<Utc>,
+    /// The time the caret should begin blinking.
+    ///
+    /// This is `None` until the selection is first rendered.
+    blink_epoch: Option<Instant>,
 }
 
 impl PartialEq for TextSelection {
@@ -3301,7 +3662,7 @@ impl TextSelection {
         Self {
             from: position,
             to: position,
-            blink_epoch: Utc::now(),
+            blink_epoch: None,
         }
     }
 
@@ -3309,7 +3670,7 @@ impl TextSelection {
         Self {
             from,
             to,
-            blink_epoch: Utc::now(),
+            blink_epoch: None,
         }
     }
 
@@ -3379,12 +3740,15 @@ impl TextSelection {
     }
 
     pub fn reset_blinking(&mut self) {
-        self.blink_epoch = Utc::now();
+        self.blink_epoch = None;
     }
 
-    /// Returns true if the caret should not be visible now due to blinking.
-    pub fn blinks_now(&self) -> bool {
-        let millis = (Utc::now() - self.blink_epoch).num_milliseconds() as u32;
+    /// Returns true if the caret should not be visible at `now` due to blinking.
+    pub fn blinks_now(&self, now: Instant) -> bool {
+        let Some(blink_epoch) = self.blink_epoch else {
+            return false;
+        };
+        let millis = now.duration_since(blink_epoch).as_millis() as u32;
         2 * (millis % Self::BLINK_CYCLE_DURATION_MS) >= Self::BLINK_CYCLE_DURATION_MS
     }
 }
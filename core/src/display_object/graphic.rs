@@ -18,7 +18,8 @@ use gc_arena::{Collect, Gc, Mutation};
 use ruffle_common::utils::HasPrefixField;
 use ruffle_render::backend::ShapeHandle;
 use ruffle_render::commands::CommandHandler;
-use std::cell::{OnceCell, RefCell, RefMut};
+use ruffle_render::shape_utils::{DistilledShape, ScalingGrid};
+use std::cell::{OnceCell, Ref, RefCell, RefMut};
 use std::sync::Arc;
 
 #[derive(Clone, Collect, Copy)]
@@ -44,6 +45,10 @@ pub struct GraphicData<'gc> {
     /// This is lazily allocated on demand, to make `GraphicData` smaller in the common case.
     #[collect(require_static)]
     drawing: OnceCell<Box<RefCell<Drawing>>>,
+    /// The handle of the shape distorted by the `scale9Grid` of the parent,
+    /// along with the grid and matrix it was distorted with.
+    #[collect(require_static)]
+    scaling_grid_handle: RefCell<Option<(ScalingGrid, Matrix, ShapeHandle)>>,
 }
 
 impl<'gc> Graphic<'gc> {
@@ -53,16 +58,21 @@ impl<'gc> Graphic<'gc> {
         swf_shape: swf::Shape,
         movie: Arc<SwfMovie>,
     ) -> Self {
-        let library = context.library.library_for_movie(movie.clone()).unwrap();
+        // The library may already be gone if the movie was unloaded while one of
+        // its symbols was still being instantiated; such a shape isn't rendered.
+        let render_handle = context
+            .library
+            .library_for_movie(movie.clone())
+            .map(|library| {
+                context
+                    .renderer
+                    .register_shape((&swf_shape).into(), &MovieLibrarySource { library })
+            });
         let shared = GraphicShared {
             id: swf_shape.id,
             shape_bounds: swf_shape.shape_bounds,
             edge_bounds: swf_shape.edge_bounds,
-            render_handle: Some(
-                context
-                    .renderer
-                    .register_shape((&swf_shape).into(), &MovieLibrarySource { library }),
-            ),
+            render_handle,
             shape: swf_shape,
             movie,
             scaled_handle: RefCell::new(TessellationCache::new()),
@@ -76,6 +86,7 @@ impl<'gc> Graphic<'gc> {
                 class: Lock::new(None),
                 avm2_object: Lock::new(None),
                 drawing: OnceCell::new(),
+                scaling_grid_handle: RefCell::new(None),
             },
         ))
     }
@@ -111,6 +122,7 @@ impl<'gc> Graphic<'gc> {
                 class: Lock::new(None),
                 avm2_object: Lock::new(None),
                 drawing: OnceCell::new(),
+                scaling_grid_handle: RefCell::new(None),
             },
         ))
     }
@@ -123,6 +135,16 @@ impl<'gc> Graphic<'gc> {
         self.0.drawing.get_or_init(Default::default).borrow_mut()
     }
 
+    pub fn drawing(&self) -> Option<Ref<'_, Drawing>> {
+        self.0.drawing.get().map(|d| d.borrow())
+    }
+
+    /// The shape this graphic was defined with, which is displayed unless
+    /// it was drawn on using the `Graphics` API.
+    pub fn swf_shape(self) -> &'gc swf::Shape {
+        &Gc::as_ref(self.0.shared.get()).shape
+    }
+
     pub fn set_avm2_class(self, mc: &Mutation<'gc>, class: Avm2ClassObject<'gc>) {
         unlock!(Gc::write(mc, self.0), GraphicData, class).set(Some(class));
     }
@@ -131,6 +153,35 @@ impl<'gc> Graphic<'gc> {
         unlock!(Gc::write(mc, self.0), GraphicData, shared).set(shared);
     }
 
+    /// Returns a handle of the shape distorted by the `scale9Grid` of the parent.
+    fn get_or_register_scaling_grid_handle(
+        self,
+        context: &mut RenderContext,
+        base_handle: &ShapeHandle,
+        scaling_grid: ScalingGrid,
+    ) -> ShapeHandle {
+        let matrix = self.base().matrix();
+        let mut cached = self.0.scaling_grid_handle.borrow_mut();
+        if let Some((grid, grid_matrix, handle)) = &*cached
+            && *grid == scaling_grid
+            && *grid_matrix == matrix
+        {
+            return handle.clone();
+        }
+
+        let shared = self.0.shared.get();
+        let Some(library) = context.library.library_for_movie(shared.movie.clone()) else {
+            return base_handle.clone();
+        };
+        let mut shape: DistilledShape = (&shared.shape).into();
+        scaling_grid.apply(&mut shape.paths, matrix);
+        let handle = context
+            .renderer
+            .register_shape(shape, &MovieLibrarySource { library });
+        *cached = Some((scaling_grid, matrix, handle.clone()));
+        handle
+    }
+
     /// Returns the best shape handle for the current scale, retessellating if necessary.
     fn get_or_retessellate_handle(
         self,
@@ -237,6 +288,7 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
             .get_graphic(id)
         {
             self.set_shared(context.gc(), new_graphic.0.shared.get());
+            self.0.scaling_grid_handle.take();
         } else {
             tracing::warn!("PlaceObject: expected Graphic at character ID {}", id);
         }
@@ -253,11 +305,30 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
             return;
         }
 
+        // Shapes are affected by the `scale9Grid` of their parent.
+        let scaling_grid = self
+            .parent()
+            .and_then(|parent| parent.scaling_grid_mapping());
+
         if let Some(drawing) = self.0.drawing.get() {
-            drawing.borrow().render(context);
+            match scaling_grid {
+                Some(scaling_grid) => drawing.borrow().render_with_scaling_grid(
+                    context,
+                    scaling_grid,
+                    self.base().matrix(),
+                ),
+                None => drawing.borrow().render(context),
+            }
         } else if let Some(base_handle) = self.0.shared.get().render_handle.clone() {
             let transform = context.transform_stack.transform();
 
+            if let Some(scaling_grid) = scaling_grid {
+                let handle =
+                    self.get_or_register_scaling_grid_handle(context, &base_handle, scaling_grid);
+                context.commands.render_shape(handle, transform);
+                return;
+            }
+
             // Calculate the current scale from the transform, to determine if
             // we can reuse a cached tessellation or need to retessellate.
             let matrix = &transform.matrix;
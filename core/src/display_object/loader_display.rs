@@ -62,6 +62,34 @@ impl<'gc> LoaderDisplay<'gc> {
         obj
     }
 
+    /// Stops everything playing inside of this Loader's content, as done by
+    /// `Loader.unloadAndStop`.
+    ///
+    /// This stops every timeline, timeline sound and attached `NetStream`
+    /// within the content.
+    pub fn stop_content(self, context: &mut UpdateContext<'gc>) {
+        fn stop_recursively<'gc>(context: &mut UpdateContext<'gc>, dobj: DisplayObject<'gc>) {
+            if let Some(mc) = dobj.as_movie_clip() {
+                mc.stop(context);
+            }
+
+            if let Some(stream) = dobj.as_video().and_then(|video| video.netstream()) {
+                stream.stop(context);
+            }
+
+            if let Some(container) = dobj.as_container() {
+                for child in container.iter_render_list() {
+                    stop_recursively(context, child);
+                }
+            }
+        }
+
+        if let Some(content) = self.child_by_index(0) {
+            stop_recursively(context, content);
+            context.stop_sounds_on_parent_and_children(content);
+        }
+    }
+
     pub fn downgrade(self) -> LoaderDisplayWeak<'gc> {
         LoaderDisplayWeak(Gc::downgrade(self.0))
     }
@@ -114,10 +114,11 @@ impl<'gc> TDisplayObject<'gc> for MorphShape<'gc> {
     fn render_self(self, context: &mut RenderContext) {
         let ratio = self.ratio();
         let shared = self.0.shared.get();
-        let shape_handle = shared.get_shape(context, context.library, ratio);
-        context
-            .commands
-            .render_shape(shape_handle, context.transform_stack.transform());
+        if let Some(shape_handle) = shared.get_shape(context, context.library, ratio) {
+            context
+                .commands
+                .render_shape(shape_handle, context.transform_stack.transform());
+        }
     }
 
     fn self_bounds(self, mode: BoundsMode) -> Rectangle<Twips> {
@@ -226,22 +227,25 @@ impl MorphShapeShared {
 
     /// Retrieves the `ShapeHandle` for the given ratio.
     /// Lazily initializes and tessellates the shape if it does not yet exist.
+    ///
+    /// Returns `None` if the shape isn't tessellated yet and the library of its
+    /// movie has already been unloaded.
     fn get_shape<'gc>(
         &self,
         context: &mut RenderContext<'_, 'gc>,
         library: &Library<'gc>,
         ratio: u16,
-    ) -> ShapeHandle {
+    ) -> Option<ShapeHandle> {
         let mut frame = self.get_frame(ratio);
         if let Some(handle) = frame.shape_handle.clone() {
-            handle
+            Some(handle)
         } else {
-            let library = library.library_for_movie(self.movie.clone()).unwrap();
+            let library = library.library_for_movie(self.movie.clone())?;
             let handle = context
                 .renderer
                 .register_shape((&frame.shape).into(), &MovieLibrarySource { library });
             frame.shape_handle = Some(handle.clone());
-            handle
+            Some(handle)
         }
     }
 
@@ -561,6 +561,9 @@ impl<'gc> MovieClip<'gc> {
                 TagCode::DefineText2 => shared.define_text(context, reader, 2),
                 TagCode::DoInitAction => self.do_init_action(context, reader),
                 TagCode::DefineSceneAndFrameLabelData => shared.scene_and_frame_labels(reader),
+                TagCode::EnableDebugger | TagCode::EnableDebugger2 => {
+                    shared.enable_debugger(context)
+                }
                 TagCode::ExportAssets => shared.export_assets(context, reader),
                 TagCode::FrameLabel => shared.frame_label(reader),
                 TagCode::JpegTables => shared.jpeg_tables(context, reader),
@@ -2441,11 +2444,10 @@ impl<'gc> MovieClip<'gc> {
                         .set_flag(MovieClipFlags::EXECUTING_AVM2_FRAME_SCRIPT, true);
 
                     let movie = self.movie();
-                    let domain = context
-                        .library
-                        .library_for_movie(movie)
-                        .unwrap()
-                        .avm2_domain();
+                    let domain = context.library.library_for_movie(movie).map_or_else(
+                        || context.avm2.stage_domain(),
+                        |library| library.avm2_domain(),
+                    );
 
                     let mut activation = Avm2Activation::from_domain(context, domain);
 
@@ -2630,7 +2632,12 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
 
     fn render_self(self, context: &mut RenderContext<'_, 'gc>) {
         if let Some(drawing) = self.drawing() {
-            drawing.render(context);
+            match self.scaling_grid_mapping() {
+                Some(scaling_grid) => {
+                    drawing.render_with_scaling_grid(context, scaling_grid, Matrix::IDENTITY)
+                }
+                None => drawing.render(context),
+            }
         }
         self.render_children(context);
     }
@@ -3448,12 +3455,12 @@ impl<'gc, 'a> MovieClipShared<'gc> {
     ) -> Result<(), Error> {
         let id = reader.read_u16()?;
         let rect = reader.read_rectangle()?;
-        if let Some(character) = self.library_mut(context).character_by_id(id) {
-            if let Character::MovieClip(clip) = character {
-                clip.set_scaling_grid(rect);
-            } else {
-                tracing::warn!("DefineScalingGrid for invalid ID {}", id);
-            }
+        match self.library_mut(context).character_by_id(id) {
+            Some(Character::MovieClip(clip)) => clip.set_scaling_grid(rect),
+            Some(Character::Avm1Button(button)) => button.set_scaling_grid(rect),
+            Some(Character::Avm2Button(button)) => button.set_scaling_grid(rect),
+            Some(_) => tracing::warn!("DefineScalingGrid for invalid ID {}", id),
+            None => {}
         }
         Ok(())
     }
@@ -4139,6 +4146,11 @@ impl<'gc, 'a> MovieClipShared<'gc> {
         Ok(())
     }
 
+    fn enable_debugger(&self, context: &mut UpdateContext<'gc>) -> Result<(), Error> {
+        self.library_mut(context).set_debugger_enabled();
+        Ok(())
+    }
+
     #[inline]
     fn show_frame(&self, #[allow(unused)] reader: &mut SwfStream<'a>) -> Result<(), Error> {
         let progress = &self.preload_progress;
@@ -616,8 +616,8 @@ impl<'gc> Stage<'gc> {
     pub fn render_viewport(self, context: &mut RenderContext<'_, 'gc>) {
         context.transform_stack.push(&Transform {
             matrix: self.0.viewport_matrix.get(),
+            matrix3d: None,
             color_transform: Default::default(),
-            // TODO: Verify perspective_projection when its rendering is implemented.
             perspective_projection: self.as_displayobject().base().perspective_projection(),
         });
 
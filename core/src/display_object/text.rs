@@ -92,8 +92,7 @@ impl<'gc> Text<'gc> {
             let font = context
                 .library
                 .library_for_movie(self.movie())
-                .unwrap()
-                .get_font(font_id?)?;
+                .and_then(|library| library.get_font(font_id?))?;
 
             for glyph in &block.glyphs {
                 if let Some(g) = font.get_glyph(glyph.index as usize) {
@@ -161,8 +160,7 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
             if let Some(font) = context
                 .library
                 .library_for_movie(self.movie())
-                .unwrap()
-                .get_font(font_id)
+                .and_then(|library| library.get_font(font_id))
             {
                 let scale = (height.get() as f32) / font.scale();
                 transform.matrix.a = scale;
@@ -229,8 +227,7 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
                 if let Some(font) = context
                     .library
                     .library_for_movie(self.movie())
-                    .unwrap()
-                    .get_font(font_id)
+                    .and_then(|library| library.get_font(font_id))
                 {
                     let scale = (height.get() as f32) / font.scale();
                     glyph_matrix.a = scale;
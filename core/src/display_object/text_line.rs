@@ -6,20 +6,18 @@ use crate::avm2::object::TextBlockObject;
 use crate::backend::ui::MouseCursor;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::interactive::{InteractiveObjectBase, TInteractiveObject};
-use crate::display_object::{
-    Avm2MousePick, BoundsMode, DisplayObjectBase, EditText, InteractiveObject,
-};
+use crate::display_object::{Avm2MousePick, BoundsMode, DisplayObjectBase, InteractiveObject};
 use crate::events::{ClipEvent, ClipEventResult};
-use crate::fte::TextLineValidity;
+use crate::fte::{LineLayout, TextLineValidity};
 use crate::prelude::*;
 use crate::tag_utils::SwfMovie;
 use crate::vminterface::Instantiator;
 use core::fmt;
 use gc_arena::barrier::unlock;
-use gc_arena::lock::Lock;
+use gc_arena::lock::{Lock, RefLock};
 use gc_arena::{Collect, Gc, Mutation};
 use ruffle_common::utils::HasPrefixField;
-use std::cell::Cell;
+use std::cell::{Cell, Ref};
 use std::sync::Arc;
 use swf::Twips;
 
@@ -41,7 +39,7 @@ impl fmt::Debug for TextLine<'_> {
 pub struct TextLineData<'gc> {
     base: InteractiveObjectBase<'gc>,
     avm2_object: Lock<Option<Avm2StageObject<'gc>>>,
-    fallback: EditText<'gc>,
+    layout: RefLock<LineLayout<'gc>>,
     #[collect(require_static)]
     movie: Arc<SwfMovie>,
 
@@ -63,17 +61,13 @@ pub struct TextLineData<'gc> {
 }
 
 impl<'gc> TextLine<'gc> {
-    pub fn new(
-        context: &mut UpdateContext<'gc>,
-        movie: Arc<SwfMovie>,
-        fallback: EditText<'gc>,
-    ) -> Self {
+    pub fn new(context: &mut UpdateContext<'gc>, movie: Arc<SwfMovie>) -> Self {
         TextLine(Gc::new(
             context.gc(),
             TextLineData {
                 base: Default::default(),
                 avm2_object: Lock::new(None),
-                fallback,
+                layout: RefLock::new(Default::default()),
                 movie,
                 validity: Lock::new(TextLineValidity::Valid),
                 text_block: Lock::new(None),
@@ -97,6 +91,7 @@ impl<'gc> TextLine<'gc> {
         self.set_y(Twips::ZERO);
 
         // Reset text line properties
+        self.set_layout(Default::default(), mc);
         self.set_validity(TextLineValidity::Valid, mc);
         self.set_text_block(None, mc);
         self.set_hide_block_from_script(false);
@@ -150,12 +145,12 @@ impl<'gc> TextLine<'gc> {
         self.set_next_line(None, mc);
     }
 
-    pub fn measure_text(self, context: &mut UpdateContext<'gc>) -> (Twips, Twips) {
-        self.0.fallback.measure_text(context)
+    pub fn layout(&self) -> Ref<'_, LineLayout<'gc>> {
+        self.0.layout.borrow()
     }
 
-    pub fn fallback(self) -> EditText<'gc> {
-        self.0.fallback
+    pub fn set_layout(self, layout: LineLayout<'gc>, mc: &Mutation<'gc>) {
+        *unlock!(Gc::write(mc, self.0), TextLineData, layout).borrow_mut() = layout;
     }
 
     pub fn validity(self) -> TextLineValidity<'gc> {
@@ -277,11 +272,11 @@ impl<'gc> TDisplayObject<'gc> for TextLine<'gc> {
     fn replace_with(self, _context: &mut UpdateContext<'gc>, _id: CharacterId) {}
 
     fn render_self(self, context: &mut RenderContext<'_, 'gc>) {
-        self.0.fallback.render_self(context);
+        self.layout().render(context);
     }
 
-    fn self_bounds(self, mode: BoundsMode) -> Rectangle<Twips> {
-        self.0.fallback.self_bounds(mode)
+    fn self_bounds(self, _mode: BoundsMode) -> Rectangle<Twips> {
+        self.layout().bounds()
     }
 
     fn hit_test_shape(
@@ -210,6 +210,14 @@ impl<'gc> Video<'gc> {
         self.0.keyframes.replace(BTreeSet::new());
     }
 
+    /// The `NetStream` attached to this Video, if any.
+    pub fn netstream(self) -> Option<NetStream<'gc>> {
+        match self.0.source.get() {
+            VideoSource::NetStream { stream } => Some(stream),
+            _ => None,
+        }
+    }
+
     /// Preload frame data from an SWF.
     ///
     /// This function yields an error if this video player is not playing an
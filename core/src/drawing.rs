@@ -2,15 +2,20 @@ use crate::context::RenderContext;
 use ruffle_render::backend::{RenderBackend, ShapeHandle};
 use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, BitmapSize, BitmapSource};
 use ruffle_render::commands::CommandHandler;
+use ruffle_render::matrix::Matrix;
 use ruffle_render::shape_utils::{
-    DistilledShape, DrawCommand, DrawPath, FillRule, cubic_curve_bounds, quadratic_curve_bounds,
+    DistilledShape, DrawCommand, DrawPath, FillRule, ScalingGrid, TexturedTriangle,
+    cubic_curve_bounds, quadratic_curve_bounds,
 };
-use std::cell::OnceCell;
+use std::cell::{OnceCell, RefCell};
 use swf::{FillStyle, LineStyle, Point, Rectangle, Twips};
 
 #[derive(Clone, Debug)]
 pub struct Drawing {
     render_handle: OnceCell<ShapeHandle>,
+    /// The handle of this drawing distorted by a `scale9Grid`, along with
+    /// the grid and matrix it was distorted with.
+    scaling_grid_handle: RefCell<Option<(ScalingGrid, Matrix, ShapeHandle)>>,
     shape_bounds: Rectangle<Twips>,
     edge_bounds: Rectangle<Twips>,
     paths: Vec<DrawingPath>,
@@ -35,6 +40,7 @@ impl Drawing {
     pub fn new() -> Self {
         Self {
             render_handle: OnceCell::new(),
+            scaling_grid_handle: RefCell::new(None),
             shape_bounds: Default::default(),
             edge_bounds: Default::default(),
             paths: Vec::new(),
@@ -52,6 +58,7 @@ impl Drawing {
     pub fn from_swf_shape(shape: &swf::Shape) -> Self {
         let mut this = Self {
             render_handle: OnceCell::new(),
+            scaling_grid_handle: RefCell::new(None),
             shape_bounds: shape.shape_bounds,
             edge_bounds: shape.edge_bounds,
             paths: Vec::new(),
@@ -71,42 +78,64 @@ impl Drawing {
 
         let shape: DistilledShape = shape.into();
         for path in shape.paths {
-            match path {
-                DrawPath::Stroke {
-                    style,
-                    is_closed: _,
-                    commands,
-                } => {
-                    this.set_line_style(Some(style.clone()));
-
-                    for command in commands {
-                        this.draw_command(command);
-                    }
+            this.draw_path(path);
+        }
+
+        this
+    }
 
-                    this.set_line_style(None);
+    /// Draws a path of a distilled shape, such as those returned by
+    /// [`Drawing::distilled_shape`].
+    pub fn draw_path(&mut self, path: DrawPath<'_>) {
+        match path {
+            DrawPath::Stroke {
+                style,
+                is_closed: _,
+                commands,
+            } => {
+                self.set_line_style(Some(style.clone()));
+
+                for command in commands {
+                    self.draw_command(command);
                 }
-                DrawPath::Fill {
-                    style,
-                    commands,
-                    winding_rule,
-                } => {
-                    this.new_fill(Some(style.clone()), Some(winding_rule));
-
-                    for command in commands {
-                        this.draw_command(command);
-                    }
 
-                    this.set_fill_style(None);
+                self.set_line_style(None);
+            }
+            DrawPath::Fill {
+                style,
+                commands,
+                winding_rule,
+            } => {
+                self.new_fill(Some(style.clone()), Some(winding_rule));
+
+                for command in commands {
+                    self.draw_command(command);
                 }
+
+                self.set_fill_style(None);
+            }
+            DrawPath::TexturedTriangles {
+                bitmap_id,
+                is_smoothed,
+                is_repeating,
+                triangles,
+            } => {
+                self.paths
+                    .push(DrawingPath::TexturedTriangles(DrawingTexturedTriangles {
+                        bitmap_id,
+                        is_smoothed,
+                        is_repeating,
+                        triangles,
+                    }));
+                self.mark_dirty();
             }
         }
-
-        this
     }
 
     fn mark_dirty(&mut self) {
         self.is_empty = false;
         self.render_handle.take();
+        self.scaling_grid_handle.take();
     }
 
     /// Set fill style and reset fill rule to default.
@@ -163,6 +192,7 @@ impl Drawing {
 
         // An empty drawing doesn't need to hold onto a `ShapeHandle`.
         self.render_handle.take();
+        self.scaling_grid_handle.take();
     }
 
     pub fn set_line_style(&mut self, style: Option<LineStyle>) {
@@ -233,86 +263,165 @@ impl Drawing {
         self.mark_dirty()
     }
 
+    /// Draws triangles with the current fill and line styles.
+    ///
+    /// Unlike other paths, overlapping triangles are filled using the
+    /// non-zero winding rule.
+    pub fn draw_triangles(&mut self, triangles: impl IntoIterator<Item = [Point<Twips>; 3]>) {
+        let rule = self.current_fill.as_ref().map(|fill| fill.rule);
+        if rule.is_some() {
+            self.set_fill_rule(Some(FillRule::NonZero));
+        }
+
+        for triangle in triangles {
+            self.draw_triangle_outline(triangle);
+        }
+
+        if rule.is_some() {
+            self.set_fill_rule(rule);
+        }
+    }
+
+    /// Draws triangles mapping the bitmap of the current fill onto each of them.
+    ///
+    /// If the current fill isn't a bitmap fill, the coordinates are ignored
+    /// and the triangles are drawn as in [`Drawing::draw_triangles`].
+    pub fn draw_textured_triangles(&mut self, triangles: Vec<TexturedTriangle>) {
+        let Some(DrawingFill {
+            style:
+                FillStyle::Bitmap {
+                    id,
+                    is_smoothed,
+                    is_repeating,
+                    ..
+                },
+            rule,
+            ..
+        }) = self.current_fill
+        else {
+            self.draw_triangles(triangles.into_iter().map(|triangle| triangle.points));
+            return;
+        };
+
+        // Flush the current fill, so that it's drawn below the triangles.
+        self.set_fill_rule(Some(rule));
+
+        // The triangles are still outlined by the current line style, but must
+        // not be added to the current fill.
+        let fill = self.current_fill.take();
+        for triangle in &triangles {
+            self.draw_triangle_outline(triangle.points);
+        }
+        self.paths
+            .push(DrawingPath::TexturedTriangles(DrawingTexturedTriangles {
+                bitmap_id: id,
+                is_smoothed,
+                is_repeating,
+                triangles,
+            }));
+        self.current_fill = fill;
+        self.draw_command(DrawCommand::MoveTo(self.cursor));
+    }
+
+    fn draw_triangle_outline(&mut self, [a, b, c]: [Point<Twips>; 3]) {
+        self.draw_command(DrawCommand::MoveTo(a));
+        self.draw_command(DrawCommand::LineTo(b));
+        self.draw_command(DrawCommand::LineTo(c));
+        self.draw_command(DrawCommand::LineTo(a));
+    }
+
     pub fn add_bitmap(&mut self, bitmap: BitmapInfo) -> u16 {
         let id = self.bitmaps.len() as u16;
         self.bitmaps.push(bitmap);
         id
     }
 
-    /// Obtain a `ShapeHandle` that represents this `Drawing`, or `None` if it is empty.
-    pub fn register_or_replace(&self, renderer: &mut dyn RenderBackend) -> Option<ShapeHandle> {
-        if self.is_empty {
-            return None;
-        }
+    /// Builds the shape to register with the renderer.
+    pub fn distilled_shape(&self) -> DistilledShape<'_> {
+        let mut paths = Vec::with_capacity(self.paths.len());
 
-        let handle = self.render_handle.get_or_init(|| {
-            let mut paths = Vec::with_capacity(self.paths.len());
-
-            for path in &self.paths {
-                match path {
-                    DrawingPath::Fill(fill) => {
-                        paths.push(DrawPath::Fill {
-                            style: &fill.style,
-                            commands: fill.commands.to_owned(),
-                            winding_rule: fill.rule,
-                        });
-                    }
-                    DrawingPath::Line(line) => {
-                        paths.push(DrawPath::Stroke {
-                            style: &line.style,
-                            commands: line.commands.to_owned(),
-                            is_closed: line.is_closed,
-                        });
-                    }
+        for path in &self.paths {
+            match path {
+                DrawingPath::Fill(fill) => {
+                    paths.push(DrawPath::Fill {
+                        style: &fill.style,
+                        commands: fill.commands.to_owned(),
+                        winding_rule: fill.rule,
+                    });
+                }
+                DrawingPath::Line(line) => {
+                    paths.push(DrawPath::Stroke {
+                        style: &line.style,
+                        commands: line.commands.to_owned(),
+                        is_closed: line.is_closed,
+                    });
+                }
+                DrawingPath::TexturedTriangles(textured) => {
+                    paths.push(DrawPath::TexturedTriangles {
+                        bitmap_id: textured.bitmap_id,
+                        is_smoothed: textured.is_smoothed,
+                        is_repeating: textured.is_repeating,
+                        triangles: textured.triangles.to_owned(),
+                    });
                 }
             }
+        }
 
-            if let Some(fill) = &self.current_fill {
-                paths.push(DrawPath::Fill {
-                    style: &fill.style,
-                    commands: fill.commands.to_owned(),
-                    winding_rule: fill.rule,
-                })
-            }
-
-            for line in &self.pending_lines {
-                let mut commands = line.commands.to_owned();
-                let is_closed = if self.current_fill.is_some() {
-                    commands.push(DrawCommand::LineTo(self.fill_start));
-                    true
-                } else {
-                    self.cursor == self.fill_start
-                };
-                paths.push(DrawPath::Stroke {
-                    style: &line.style,
-                    commands,
-                    is_closed,
-                })
-            }
+        if let Some(fill) = &self.current_fill {
+            paths.push(DrawPath::Fill {
+                style: &fill.style,
+                commands: fill.commands.to_owned(),
+                winding_rule: fill.rule,
+            })
+        }
 
-            if let Some(line) = &self.current_line {
-                let mut commands = line.commands.to_owned();
-                let is_closed = if self.current_fill.is_some() {
-                    commands.push(DrawCommand::LineTo(self.fill_start));
-                    true
-                } else {
-                    self.cursor == self.fill_start
-                };
-                paths.push(DrawPath::Stroke {
-                    style: &line.style,
-                    commands,
-                    is_closed,
-                })
-            }
+        for line in &self.pending_lines {
+            let mut commands = line.commands.to_owned();
+            let is_closed = if self.current_fill.is_some() {
+                commands.push(DrawCommand::LineTo(self.fill_start));
+                true
+            } else {
+                self.cursor == self.fill_start
+            };
+            paths.push(DrawPath::Stroke {
+                style: &line.style,
+                commands,
+                is_closed,
+            })
+        }
 
-            let shape = DistilledShape {
-                paths,
-                shape_bounds: self.shape_bounds,
-                edge_bounds: self.edge_bounds,
-                id: 0,
+        if let Some(line) = &self.current_line {
+            let mut commands = line.commands.to_owned();
+            let is_closed = if self.current_fill.is_some() {
+                commands.push(DrawCommand::LineTo(self.fill_start));
+                true
+            } else {
+                self.cursor == self.fill_start
             };
-            renderer.register_shape(shape, self)
-        });
+            paths.push(DrawPath::Stroke {
+                style: &line.style,
+                commands,
+                is_closed,
+            })
+        }
+
+        DistilledShape {
+            paths,
+            shape_bounds: self.shape_bounds,
+            edge_bounds: self.edge_bounds,
+            id: 0,
+        }
+    }
+
+    /// Obtain a `ShapeHandle` that represents this `Drawing`, or `None` if it is empty.
+    pub fn register_or_replace(&self, renderer: &mut dyn RenderBackend) -> Option<ShapeHandle> {
+        if self.is_empty {
+            return None;
+        }
+
+        let handle = self
+            .render_handle
+            .get_or_init(|| renderer.register_shape(self.distilled_shape(), self));
 
         Some(handle.clone())
     }
@@ -325,6 +434,41 @@ impl Drawing {
         }
     }
 
+    /// Renders this drawing distorted by a `scale9Grid`.
+    ///
+    /// `matrix` transforms this drawing into the local space of the object
+    /// owning the grid.
+    pub fn render_with_scaling_grid(
+        &self,
+        context: &mut RenderContext,
+        scaling_grid: ScalingGrid,
+        matrix: Matrix,
+    ) {
+        if self.is_empty {
+            return;
+        }
+
+        let mut cached = self.scaling_grid_handle.borrow_mut();
+        let handle = match &*cached {
+            Some((grid, grid_matrix, handle))
+                if *grid == scaling_grid && *grid_matrix == matrix =>
+            {
+                handle.clone()
+            }
+            _ => {
+                let mut shape = self.distilled_shape();
+                scaling_grid.apply(&mut shape.paths, matrix);
+                let handle = context.renderer.register_shape(shape, self);
+                *cached = Some((scaling_grid, matrix, handle.clone()));
+                handle
+            }
+        };
+
+        context
+            .commands
+            .render_shape(handle, context.transform_stack.transform());
+    }
+
     pub fn self_bounds(&self, include_strokes: bool) -> Rectangle<Twips> {
         if include_strokes {
             self.shape_bounds
@@ -356,6 +500,23 @@ impl Drawing {
                         return true;
                     }
                 }
+                DrawingPath::TexturedTriangles(textured) => {
+                    if textured.triangles.iter().any(|triangle| {
+                        let [a, b, c] = triangle.points;
+                        shape_utils::draw_command_fill_hit_test(
+                            &[
+                                DrawCommand::MoveTo(a),
+                                DrawCommand::LineTo(b),
+                                DrawCommand::LineTo(c),
+                                DrawCommand::LineTo(a),
+                            ],
+                            FillRule::NonZero,
+                            point,
+                        )
+                    }) {
+                        return true;
+                    }
+                }
             }
         }
 
@@ -447,10 +608,19 @@ struct DrawingLine {
     is_closed: bool,
 }
 
+#[derive(Debug, Clone)]
+struct DrawingTexturedTriangles {
+    bitmap_id: u16,
+    is_smoothed: bool,
+    is_repeating: bool,
+    triangles: Vec<TexturedTriangle>,
+}
+
 #[derive(Debug, Clone)]
 enum DrawingPath {
     Fill(DrawingFill),
     Line(DrawingLine),
+    TexturedTriangles(DrawingTexturedTriangles),
 }
 
 fn stretch_bounds(
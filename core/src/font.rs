@@ -7,7 +7,7 @@ mod glyph;
 mod text_render_settings;
 
 pub use font_descriptor::FontDescriptor;
-pub use font_face::{FontFace, FontFileData};
+pub use font_face::{FontFace, FontFileData, ShapedGlyph};
 pub use font_like::{EvalParameters, FontLike, GlyphResolution};
 pub use font_renderer::FontRenderer;
 pub use font_set::FontSet;
@@ -194,6 +194,26 @@ impl GlyphSource {
         }
     }
 
+    pub fn get_by_glyph_id(&self, glyph_id: u16, character: char) -> Option<GlyphRef<'_>> {
+        match self {
+            GlyphSource::FontFace { face, .. } => face
+                .get_glyph_by_id(glyph_id, character)
+                .map(GlyphRef::Direct),
+            GlyphSource::Memory { .. } => None, // Unsupported.
+            GlyphSource::ExternalRenderer { .. } => None, // Unsupported.
+            GlyphSource::Empty => None,
+        }
+    }
+
+    pub fn shape(&self, text: &WStr, is_rtl: bool) -> Option<Vec<ShapedGlyph>> {
+        match self {
+            GlyphSource::FontFace { face, .. } => Some(face.shape(text, is_rtl)),
+            GlyphSource::Memory { .. } => None, // Unsupported.
+            GlyphSource::ExternalRenderer { .. } => None, // Unsupported.
+            GlyphSource::Empty => None,
+        }
+    }
+
     pub fn has_kerning_info(&self) -> bool {
         match self {
             GlyphSource::Memory { kerning_pairs, .. } => !kerning_pairs.is_empty(),
@@ -535,6 +555,23 @@ impl<'gc> Font<'gc> {
         self.0.glyphs.get_by_code_point(c)
     }
 
+    /// Returns a glyph entry by its ID, as produced by [`Font::shape`].
+    pub fn get_glyph_by_id(&self, glyph_id: u16, character: char) -> Option<GlyphRef<'_>> {
+        self.0.glyphs.get_by_glyph_id(glyph_id, character)
+    }
+
+    /// Returns whether this font is able to shape text, see [`Font::shape`].
+    pub fn can_shape(self) -> bool {
+        matches!(self.0.glyphs, GlyphSource::FontFace { .. })
+    }
+
+    /// Shape the given text, producing glyphs in visual order.
+    ///
+    /// Returns `None` when this font does not support shaping.
+    pub fn shape(&self, text: &WStr, is_rtl: bool) -> Option<Vec<ShapedGlyph>> {
+        self.0.glyphs.shape(text, is_rtl)
+    }
+
     /// Determine if this font contains all the glyphs within a given string.
     pub fn has_glyphs_for_str(self, target_str: &WStr) -> bool {
         for character in target_str.chars() {
@@ -581,4 +618,8 @@ impl<'gc> FontLike<'gc> for Font<'gc> {
     fn font_type(&self) -> FontType {
         self.0.font_type
     }
+
+    fn shaping_font(&self, text: &WStr) -> Option<Font<'gc>> {
+        (self.can_shape() && self.has_glyphs_for_str(text)).then_some(*self)
+    }
 }
@@ -1,6 +1,7 @@
 use crate::drawing::Drawing;
 use crate::font::{FontMetrics, Glyph};
 use crate::prelude::*;
+use crate::string::WStr;
 use ruffle_render::shape_utils::{DrawCommand, FillRule};
 
 use std::cell::OnceCell;
@@ -47,6 +48,27 @@ impl ttf_parser::OutlineBuilder for GlyphToDrawing<'_> {
     }
 }
 
+/// A glyph produced by shaping text, see [`FontFace::shape`].
+///
+/// All values are in font units.
+#[derive(Debug, Clone, Copy)]
+pub struct ShapedGlyph {
+    /// The ID of the glyph within the font face.
+    pub glyph_id: u16,
+
+    /// The position of the first character this glyph was produced from.
+    pub cluster: usize,
+
+    /// The distance to advance after drawing this glyph.
+    pub x_advance: i32,
+
+    /// The horizontal offset of this glyph, not affecting the advance.
+    pub x_offset: i32,
+
+    /// The vertical offset of this glyph, upwards.
+    pub y_offset: i32,
+}
+
 pub struct FontFileData(Arc<dyn AsRef<[u8]>>);
 
 impl FontFileData {
@@ -155,33 +177,87 @@ impl FontFace {
     pub fn get_glyph(&self, character: char) -> Option<&Glyph> {
         let face = ttf_parser::Face::parse(&self.data, self.font_index)
             .expect("Font was already checked to be valid");
-        if let Some(glyph_id) = face.glyph_index(character) {
-            return self.glyphs[glyph_id.0 as usize]
-                .get_or_init(|| {
-                    let mut drawing = Drawing::new();
-                    // TTF uses NonZero
-                    drawing.new_fill(
-                        Some(FillStyle::Color(Color::WHITE)),
-                        Some(FillRule::NonZero),
+        let glyph_id = face.glyph_index(character)?;
+        self.load_glyph(&face, glyph_id, character)
+    }
+
+    /// Returns a glyph by its ID, e.g. as produced by [`FontFace::shape`].
+    ///
+    /// The character is only used when the glyph hasn't been loaded yet.
+    pub fn get_glyph_by_id(&self, glyph_id: u16, character: char) -> Option<&Glyph> {
+        let face = ttf_parser::Face::parse(&self.data, self.font_index)
+            .expect("Font was already checked to be valid");
+        self.load_glyph(&face, ttf_parser::GlyphId(glyph_id), character)
+    }
+
+    fn load_glyph(
+        &self,
+        face: &ttf_parser::Face<'_>,
+        glyph_id: ttf_parser::GlyphId,
+        character: char,
+    ) -> Option<&Glyph> {
+        self.glyphs
+            .get(glyph_id.0 as usize)?
+            .get_or_init(|| {
+                let mut drawing = Drawing::new();
+                // TTF uses NonZero
+                drawing.new_fill(
+                    Some(FillStyle::Color(Color::WHITE)),
+                    Some(FillRule::NonZero),
+                );
+                if face
+                    .outline_glyph(glyph_id, &mut GlyphToDrawing(&mut drawing))
+                    .is_some()
+                {
+                    let advance = face.glyph_hor_advance(glyph_id).map_or_else(
+                        || drawing.self_bounds(true).width(),
+                        |a| Twips::new(a as i32),
                     );
-                    if face
-                        .outline_glyph(glyph_id, &mut GlyphToDrawing(&mut drawing))
-                        .is_some()
-                    {
-                        let advance = face.glyph_hor_advance(glyph_id).map_or_else(
-                            || drawing.self_bounds(true).width(),
-                            |a| Twips::new(a as i32),
-                        );
-                        Some(Glyph::from_drawing(character, advance, drawing))
-                    } else {
-                        let advance = Twips::new(face.glyph_hor_advance(glyph_id)? as i32);
-                        // If we have advance, then this is either an image, SVG or simply missing (ie whitespace)
-                        Some(Glyph::whitespace(character, advance))
-                    }
-                })
-                .as_ref();
+                    Some(Glyph::from_drawing(character, advance, drawing))
+                } else {
+                    let advance = Twips::new(face.glyph_hor_advance(glyph_id)? as i32);
+                    // If we have advance, then this is either an image, SVG or simply missing (ie whitespace)
+                    Some(Glyph::whitespace(character, advance))
+                }
+            })
+            .as_ref()
+    }
+
+    /// Shape the given text using OpenType layout features of this font.
+    ///
+    /// This applies contextual forms, ligatures and mark positioning, which
+    /// are required by complex scripts such as Arabic or Devanagari.
+    /// Glyphs are returned in visual order, i.e. right-to-left text
+    /// produces glyphs in reverse logical order.
+    pub fn shape(&self, text: &WStr, is_rtl: bool) -> Vec<ShapedGlyph> {
+        let Some(face) = rustybuzz::Face::from_slice(&self.data, self.font_index) else {
+            return Vec::new();
+        };
+
+        let mut buffer = rustybuzz::UnicodeBuffer::new();
+        for (pos, c) in text.char_indices() {
+            buffer.add(c.unwrap_or(char::REPLACEMENT_CHARACTER), pos as u32);
         }
-        None
+        buffer.set_direction(if is_rtl {
+            rustybuzz::Direction::RightToLeft
+        } else {
+            rustybuzz::Direction::LeftToRight
+        });
+        buffer.guess_segment_properties();
+
+        let output = rustybuzz::shape(&face, &[], buffer);
+        output
+            .glyph_infos()
+            .iter()
+            .zip(output.glyph_positions())
+            .map(|(info, position)| ShapedGlyph {
+                glyph_id: info.glyph_id as u16,
+                cluster: info.cluster as usize,
+                x_advance: position.x_advance,
+                x_offset: position.x_offset,
+                y_offset: position.y_offset,
+            })
+            .collect()
     }
 
     pub fn has_kerning_info(&self) -> bool {
@@ -21,6 +21,12 @@ pub struct EvalParameters {
     /// pairs of letters, separate from the ordinary width between glyphs. This
     /// parameter allows enabling or disabling that feature.
     pub kerning: bool,
+
+    /// Whether the text is right-to-left.
+    ///
+    /// Characters are still evaluated in logical order, but their glyphs are
+    /// positioned from right to left.
+    pub is_rtl: bool,
 }
 
 impl EvalParameters {
@@ -31,6 +37,7 @@ impl EvalParameters {
             height: Twips::from_pixels(span.font.size),
             letter_spacing: Twips::from_pixels(span.font.letter_spacing),
             kerning: span.font.kerning,
+            is_rtl: false,
         }
     }
 
@@ -72,6 +79,9 @@ pub trait FontLike<'gc> {
 
     fn font_type(&self) -> FontType;
 
+    /// Find a font able to shape the whole given text, see [`Font::shape`].
+    fn shaping_font(&self, text: &WStr) -> Option<Font<'gc>>;
+
     /// Evaluate this font against a particular string on a glyph-by-glyph
     /// basis.
     ///
@@ -81,11 +91,53 @@ pub trait FontLike<'gc> {
     /// closure. This corresponds to the series of drawing operations necessary
     /// to render the text on a single horizontal line.
     ///
+    /// The closure receives the position of the character, the transform and
+    /// advance of the glyph, and the X coordinate of the character within
+    /// the line.
+    ///
     /// It's guaranteed that this function will iterate over all characters
-    /// from the text, irrespectively of whether they have a glyph or not.
+    /// from the text in logical order, irrespectively of whether they have a
+    /// glyph or not. Shaped text may render a character using several
+    /// glyphs, in which case only the first glyph carries the advance.
     fn evaluate(
         &self,
         text: &WStr, // TODO: take an `IntoIterator<Item=char>`, to not depend on string representation?
+        transform: Transform,
+        params: EvalParameters,
+        mut glyph_func: impl FnMut(usize, &Transform, GlyphRef, Twips, Twips),
+    ) {
+        if requires_shaping(text)
+            && let Some(font) = self.shaping_font(text)
+        {
+            evaluate_shaped(font, text, transform, params, glyph_func);
+        } else if params.is_rtl {
+            // Glyphs are evaluated left-to-right, and then mirrored within the text.
+            let params = EvalParameters {
+                is_rtl: false,
+                ..params
+            };
+            let width = self.measure(text, params);
+            let origin_x = transform.matrix.tx;
+            self.evaluate_unshaped(
+                text,
+                transform,
+                params,
+                |pos, transform, glyph, advance, x| {
+                    let x = width - x - advance;
+                    let mut transform = transform.clone();
+                    transform.matrix.tx = origin_x + x;
+                    glyph_func(pos, &transform, glyph, advance, x);
+                },
+            );
+        } else {
+            self.evaluate_unshaped(text, transform, params, glyph_func);
+        }
+    }
+
+    /// Evaluate this font left-to-right, with one glyph per character.
+    fn evaluate_unshaped(
+        &self,
+        text: &WStr,
         mut transform: Transform,
         params: EvalParameters,
         mut glyph_func: impl FnMut(usize, &Transform, GlyphRef, Twips, Twips),
@@ -167,6 +219,266 @@ pub trait FontLike<'gc> {
     }
 }
 
+/// Returns whether the text contains characters of scripts which cannot be
+/// rendered correctly glyph-by-glyph, e.g. Arabic letters which join
+/// depending on their neighbours, or right-to-left text with mirrored glyphs.
+fn requires_shaping(text: &WStr) -> bool {
+    text.iter().any(|c| {
+        matches!(c,
+            // Hebrew, Arabic, Syriac, Thaana, NKo, Samaritan, Mandaic
+            0x0590..=0x08FF
+            // Indic scripts, Sinhala, Thai, Lao, Tibetan, Myanmar
+            | 0x0900..=0x109F
+            // Khmer, Mongolian
+            | 0x1780..=0x18AF
+            // Hebrew and Arabic presentation forms
+            | 0xFB1D..=0xFDFF
+            | 0xFE70..=0xFEFF
+        )
+    })
+}
+
+/// Evaluate text shaped by the given font, see [`FontLike::evaluate`].
+fn evaluate_shaped(
+    font: Font<'_>,
+    text: &WStr,
+    mut transform: Transform,
+    params: EvalParameters,
+    mut glyph_func: impl FnMut(usize, &Transform, GlyphRef, Twips, Twips),
+) {
+    let baseline = font.metrics().ascent(params.height);
+    let scale = params.height.get() as f32 / font.scale();
+    let to_twips = |units: i32| Twips::new((units as f32 * scale) as i32);
+    let origin_x = transform.matrix.tx;
+
+    let mut glyphs = font.shape(text, params.is_rtl).unwrap_or_default();
+
+    // Clusters are easier to handle in logical order, while the glyph
+    // positions depend on the visual order.
+    let mut glyph_x = Vec::with_capacity(glyphs.len());
+    let mut x = Twips::ZERO;
+    let mut previous_cluster = None;
+    for glyph in &glyphs {
+        if previous_cluster.is_some_and(|cluster| cluster != glyph.cluster) {
+            x += params.letter_spacing;
+        }
+        previous_cluster = Some(glyph.cluster);
+        glyph_x.push(x);
+        x += to_twips(glyph.x_advance);
+    }
+    if params.is_rtl {
+        glyphs.reverse();
+        glyph_x.reverse();
+    }
+
+    let mut index = 0;
+    while index < glyphs.len() {
+        let cluster = glyphs[index].cluster;
+        let cluster_len = glyphs[index..]
+            .iter()
+            .take_while(|glyph| glyph.cluster == cluster)
+            .count();
+        let cluster_glyphs = &glyphs[index..index + cluster_len];
+        let cluster_glyph_x = &glyph_x[index..index + cluster_len];
+        index += cluster_len;
+
+        let cluster_end = glyphs
+            .get(index)
+            .map_or(text.len(), |glyph| glyph.cluster)
+            .max(cluster);
+        let cluster_advance = cluster_glyphs
+            .iter()
+            .fold(params.letter_spacing, |advance, glyph| {
+                advance + to_twips(glyph.x_advance)
+            });
+        let cluster_x = cluster_glyph_x.iter().copied().min().unwrap_or_default();
+
+        let mut cluster_chars = text[cluster..cluster_end]
+            .char_indices()
+            .map(|(pos, c)| (cluster + pos, c.unwrap_or(char::REPLACEMENT_CHARACTER)));
+        let character = cluster_chars
+            .next()
+            .map_or(char::REPLACEMENT_CHARACTER, |(_, c)| c);
+        for (i, (glyph, &x)) in cluster_glyphs.iter().zip(cluster_glyph_x).enumerate() {
+            let advance = if i == 0 { cluster_advance } else { Twips::ZERO };
+            let Some(glyph_ref) = font.get_glyph_by_id(glyph.glyph_id, character) else {
+                glyph_func(
+                    cluster,
+                    &transform,
+                    Glyph::empty(character).as_ref(),
+                    advance,
+                    cluster_x,
+                );
+                continue;
+            };
+
+            transform.matrix.a = scale;
+            transform.matrix.d = scale;
+            transform.matrix.tx = origin_x + x + to_twips(glyph.x_offset);
+            transform.matrix.ty = if glyph_ref.rendered_at_baseline() {
+                baseline
+            } else {
+                Twips::ZERO
+            } - to_twips(glyph.y_offset);
+
+            glyph_func(cluster, &transform, glyph_ref, advance, cluster_x);
+        }
+
+        // The remaining characters of the cluster are rendered by the glyphs
+        // above, so they only mark the end of the cluster.
+        let end_x = if params.is_rtl {
+            cluster_x
+        } else {
+            cluster_x + cluster_advance
+        };
+        for (pos, c) in cluster_chars {
+            glyph_func(
+                pos,
+                &transform,
+                Glyph::empty(c).as_ref(),
+                Twips::ZERO,
+                end_x,
+            );
+        }
+    }
+}
+
 fn round_to_pixel(t: Twips) -> Twips {
     Twips::from_pixels(t.to_pixels().round())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font::{FontDescriptor, FontFileData};
+    use crate::string::WString;
+    use gc_arena::{Mutation, arena::rootless_mutate};
+
+    /// A font with one unit per twip at a height of 1000 twips, where `a` and
+    /// `b` advance by 800, `U+05D0` by 600, `U+05D1` by 400, and the marks
+    /// `U+0301` and `U+05B8` by 0.
+    const TEST_FONT: &[u8] =
+        include_bytes!("../../../tests/tests/swfs/fonts/device_font_bidi/TestFontBidi.ttf");
+
+    fn with_test_font<F>(callback: F)
+    where
+        F: for<'gc> FnOnce(&Mutation<'gc>, Font<'gc>),
+    {
+        rootless_mutate(|mc| {
+            let descriptor = FontDescriptor::from_parts("TestFontBidi", false, false);
+            let font = Font::from_font_file(
+                mc,
+                descriptor,
+                FontFileData::new(TEST_FONT.to_vec()),
+                0,
+                FontType::Device,
+            )
+            .unwrap();
+            callback(mc, font);
+        })
+    }
+
+    /// A glyph reported by `evaluate_shaped`: the position of its character,
+    /// the advance of the glyph in the font, the X translation of its
+    /// transform, and the advance and X coordinate of its character.
+    type EvaluatedGlyph = (usize, i32, i32, i32, i32);
+
+    fn evaluate(font: Font<'_>, text: &str, is_rtl: bool) -> Vec<EvaluatedGlyph> {
+        let params = EvalParameters {
+            height: Twips::new(1000),
+            letter_spacing: Twips::ZERO,
+            kerning: false,
+            is_rtl,
+        };
+        let mut glyphs = vec![];
+        evaluate_shaped(
+            font,
+            &WString::from_utf8(text),
+            Default::default(),
+            params,
+            |pos, transform, glyph, advance, x| {
+                glyphs.push((
+                    pos,
+                    glyph.advance().get(),
+                    transform.matrix.tx.get(),
+                    advance.get(),
+                    x.get(),
+                ));
+            },
+        );
+        glyphs
+    }
+
+    #[test]
+    fn evaluate_shaped_ltr() {
+        with_test_font(|_mc, font| {
+            assert_eq!(
+                vec![(0, 800, 0, 800, 0), (1, 800, 800, 800, 800)],
+                evaluate(font, "ab", false)
+            );
+        });
+    }
+
+    #[test]
+    fn evaluate_shaped_rtl() {
+        with_test_font(|_mc, font| {
+            // Characters are reported in logical order, positioned from the right.
+            assert_eq!(
+                vec![(0, 600, 400, 600, 400), (1, 400, 0, 400, 0)],
+                evaluate(font, "\u{5D0}\u{5D1}", true)
+            );
+        });
+    }
+
+    #[test]
+    fn evaluate_shaped_mixed_directions() {
+        with_test_font(|_mc, font| {
+            // Runs are reordered by the layout, a single run keeps its direction.
+            assert_eq!(
+                vec![
+                    (0, 800, 0, 800, 0),
+                    (1, 600, 800, 600, 800),
+                    (2, 800, 1400, 800, 1400),
+                ],
+                evaluate(font, "a\u{5D0}b", false)
+            );
+            assert_eq!(
+                vec![
+                    (0, 800, 1400, 800, 1400),
+                    (1, 600, 800, 600, 800),
+                    (2, 800, 0, 800, 0),
+                ],
+                evaluate(font, "a\u{5D0}b", true)
+            );
+        });
+    }
+
+    #[test]
+    fn evaluate_shaped_clusters() {
+        with_test_font(|_mc, font| {
+            // The mark is shaped along with its base character, and the
+            // cluster's advance is carried by the first glyph.
+            let glyphs = evaluate(font, "a\u{301}b", false);
+            assert_eq!(4, glyphs.len());
+            assert_eq!((0, 800, 0, 800, 0), glyphs[0]);
+            assert_eq!((0, 0), (glyphs[1].0, glyphs[1].1));
+            assert_eq!((0, 0), (glyphs[1].3, glyphs[1].4));
+            assert_eq!(
+                (1, 0, 0, 800),
+                (glyphs[2].0, glyphs[2].1, glyphs[2].3, glyphs[2].4)
+            );
+            assert_eq!((2, 800, 800, 800, 800), glyphs[3]);
+
+            // In right-to-left text, the mark is also part of its base's
+            // cluster, and the end of the cluster is on its left.
+            let glyphs = evaluate(font, "\u{5D1}\u{5B8}a", true);
+            let cluster: Vec<_> = glyphs.iter().filter(|glyph| glyph.0 == 0).collect();
+            assert_eq!(2, cluster.len());
+            assert_eq!(400, cluster.iter().map(|glyph| glyph.3).sum::<i32>());
+            assert!(cluster.iter().all(|glyph| glyph.4 == 800));
+            let mark = glyphs[2];
+            assert_eq!((1, 0, 0, 800), (mark.0, mark.1, mark.3, mark.4));
+            assert_eq!((2, 800, 0, 800, 0), glyphs[3]);
+        });
+    }
+}
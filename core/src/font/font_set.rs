@@ -2,6 +2,7 @@ use gc_arena::{Collect, Gc, Mutation};
 use swf::Twips;
 
 use crate::font::{Font, FontLike, FontMetrics, FontType, GlyphResolution};
+use crate::string::WStr;
 
 /// Font set contains a set of fonts used to render text.
 ///
@@ -93,4 +94,10 @@ impl<'gc> FontLike<'gc> for FontSet<'gc> {
     fn font_type(&self) -> FontType {
         self.0.main_font.font_type()
     }
+
+    fn shaping_font(&self, text: &WStr) -> Option<Font<'gc>> {
+        std::iter::once(&self.0.main_font)
+            .chain(&self.0.fallback_fonts)
+            .find_map(|font| font.shaping_font(text))
+    }
 }
@@ -1,5 +1,12 @@
 //! Various structs related to FTE used across the whole codebase.
 
+mod layout;
+
+pub use layout::{
+    BlockContent, BlockSettings, ContentRun, ContentRunKind, GRAPHIC_ELEMENT_CHAR, LineLayout,
+    TabStop, lay_out_line,
+};
+
 use crate::string::AvmString;
 
 use gc_arena::Collect;
@@ -215,3 +222,16 @@ pub enum TextLineCreationResultValue {
     #[avm2_variant("success")]
     Success,
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Collect, Avm2Enum)]
+#[collect(require_static)]
+pub enum LineJustificationValue {
+    #[avm2_variant("allButLast")]
+    AllButLast,
+    #[avm2_variant("allButMandatoryBreak")]
+    AllButMandatoryBreak,
+    #[avm2_variant("allIncludingLast")]
+    AllIncludingLast,
+    #[avm2_variant("unjustified")]
+    Unjustified,
+}
@@ -0,0 +1,1018 @@
+//! Line breaking and layout of FTE text blocks.
+
+use crate::context::{RenderContext, UpdateContext};
+use crate::display_object::DisplayObject;
+use crate::font::{EvalParameters, FontLike, FontSet, FontType};
+use crate::fte::{
+    BreakOpportunityValue, LineJustificationValue, TabAlignmentValue, TextBaselineValue,
+    TextLineCreationResultValue, TextRotationValue,
+};
+use crate::html::{TextSpan, resolve_bidi_levels, resolve_font};
+use crate::prelude::*;
+use crate::string::{WStr, WString};
+use crate::tag_utils::SwfMovie;
+use gc_arena::Collect;
+use ruffle_render::transform::Transform;
+use std::ops::Range;
+use std::sync::Arc;
+use unicode_bidi::{BidiInfo, Level};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The character standing for a graphic element in the raw text of a block.
+pub const GRAPHIC_ELEMENT_CHAR: u16 = 0xFDEF;
+
+/// The interval between tab stops following the last specified tab stop.
+const DEFAULT_TAB_INTERVAL: Twips = Twips::from_pixels_i32(48);
+
+/// The content of a text block, flattened into its raw text and the runs of
+/// text and graphics the elements of the block produce.
+#[derive(Default)]
+pub struct BlockContent<'gc> {
+    pub text: WString,
+    pub runs: Vec<ContentRun<'gc>>,
+}
+
+/// The part of the raw text of a block produced by a single element.
+pub struct ContentRun<'gc> {
+    /// The range of the run within the raw text of the block.
+    pub range: Range<usize>,
+
+    pub text_rotation: TextRotationValue,
+
+    pub kind: ContentRunKind<'gc>,
+}
+
+pub enum ContentRunKind<'gc> {
+    /// Text produced by a `TextElement`.
+    Text {
+        /// The format of the text, with the font and its size, color, and
+        /// spacing.
+        span: TextSpan,
+        font_type: FontType,
+        alpha: f64,
+        break_opportunity: BreakOpportunityValue,
+    },
+
+    /// A single [`GRAPHIC_ELEMENT_CHAR`] produced by a `GraphicElement`.
+    Graphic {
+        graphic: Option<DisplayObject<'gc>>,
+        width: Twips,
+        height: Twips,
+    },
+}
+
+/// Properties of a text block affecting the layout of its lines.
+pub struct BlockSettings {
+    pub bidi_level: i32,
+    pub baseline_zero: TextBaselineValue,
+    pub line_rotation: TextRotationValue,
+    pub tab_stops: Vec<TabStop>,
+
+    /// The difference between the origin of the line and the origin of the
+    /// tab stops.
+    pub line_offset: Twips,
+
+    pub line_justification: LineJustificationValue,
+
+    /// Whether justification spreads the extra space between all letters
+    /// instead of between words only.
+    pub letter_spacing: bool,
+}
+
+pub struct TabStop {
+    pub alignment: TabAlignmentValue,
+    pub position: Twips,
+    pub decimal_alignment_token: WString,
+}
+
+impl BlockSettings {
+    /// The tab stop a tab located at `x` advances to.
+    fn next_tab_stop(&self, x: Twips) -> (TabAlignmentValue, Twips, &WStr) {
+        let x = x + self.line_offset;
+        if let Some(tab_stop) = self.tab_stops.iter().find(|t| t.position > x) {
+            return (
+                tab_stop.alignment,
+                tab_stop.position - self.line_offset,
+                &tab_stop.decimal_alignment_token,
+            );
+        }
+
+        let last_position = self
+            .tab_stops
+            .last()
+            .map_or(Twips::ZERO, |tab_stop| tab_stop.position);
+        let intervals = (x - last_position).get() / DEFAULT_TAB_INTERVAL.get() + 1;
+        let position = last_position + DEFAULT_TAB_INTERVAL * intervals;
+        (
+            TabAlignmentValue::Start,
+            position - self.line_offset,
+            WStr::empty(),
+        )
+    }
+}
+
+/// A line laid out from a text block, ready to be rendered and queried by
+/// a `TextLine`.
+///
+/// All positions are relative to the origin of the line, i.e. the start of
+/// its `baselineZero` baseline, before the line rotation is applied.
+#[derive(Clone, Default, Collect)]
+#[collect(no_drop)]
+pub struct LineLayout<'gc> {
+    /// The runs of the line, in visual order.
+    runs: Vec<LineRun<'gc>>,
+
+    /// The atoms of the line, in logical order.
+    atoms: Vec<Atom<'gc>>,
+
+    #[collect(require_static)]
+    metrics: LineMetrics,
+}
+
+#[derive(Clone, Copy, Default)]
+struct LineMetrics {
+    /// The extent of the text of the line, excluding graphic elements.
+    ascent: Twips,
+    descent: Twips,
+
+    /// The extent of the whole line, including graphic elements.
+    total_ascent: Twips,
+    total_descent: Twips,
+
+    text_width: Twips,
+    unjustified_text_width: Twips,
+    has_tabs: bool,
+    baseline_zero: Option<TextBaselineValue>,
+    rotation: Option<TextRotationValue>,
+}
+
+/// A piece of a line rendered with a single format and direction.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+struct LineRun<'gc> {
+    #[collect(require_static)]
+    text: WString,
+
+    #[collect(require_static)]
+    x: Twips,
+
+    #[collect(require_static)]
+    ascent: Twips,
+
+    font_set: FontSet<'gc>,
+
+    #[collect(require_static)]
+    params: EvalParameters,
+
+    #[collect(require_static)]
+    color: Color,
+
+    /// The offset applied to each glyph of the run by justification.
+    #[collect(require_static)]
+    glyph_shifts: Vec<Twips>,
+}
+
+/// The smallest indivisible unit of a line, e.g. a grapheme cluster or a
+/// graphic element.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+struct Atom<'gc> {
+    /// The range of the atom within the raw text of the block.
+    #[collect(require_static)]
+    range: Range<usize>,
+
+    /// The bounds of the atom relative to the roman baseline.
+    #[collect(require_static)]
+    bounds: Rectangle<Twips>,
+
+    #[collect(require_static)]
+    bidi_level: Level,
+
+    word_boundary_on_left: bool,
+
+    text_rotation: TextRotationValue,
+
+    graphic: Option<DisplayObject<'gc>>,
+}
+
+/// A piece of the text of a block with a single content run and bidi level.
+///
+/// Tabs, line separators, and graphics each get a piece of their own.
+struct Piece {
+    range: Range<usize>,
+    run_index: usize,
+    level: Level,
+    kind: PieceKind,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PieceKind {
+    Text,
+    Tab,
+    LineBreak,
+    Graphic,
+}
+
+/// The measurements of the characters of a range of text.
+struct CharMetrics {
+    start: usize,
+
+    /// The X coordinate of each character, relative to the piece it's in.
+    x: Vec<Twips>,
+
+    advances: Vec<Twips>,
+}
+
+impl CharMetrics {
+    fn new(range: Range<usize>) -> Self {
+        Self {
+            start: range.start,
+            x: vec![Twips::ZERO; range.len()],
+            advances: vec![Twips::ZERO; range.len()],
+        }
+    }
+
+    fn width(&self, range: Range<usize>) -> Twips {
+        self.advances[range.start - self.start..range.end - self.start]
+            .iter()
+            .fold(Twips::ZERO, |width, &advance| width + advance)
+    }
+}
+
+struct LayoutContext<'a, 'gc> {
+    content: &'a BlockContent<'gc>,
+    settings: &'a BlockSettings,
+
+    /// The fonts of each content run, resolved on demand.
+    font_sets: Vec<Option<FontSet<'gc>>>,
+
+    paragraph_start: usize,
+
+    /// The bidi levels of the paragraph, see [`resolve_bidi_levels`].
+    levels: Vec<Level>,
+}
+
+impl<'a, 'gc> LayoutContext<'a, 'gc> {
+    fn text(&self) -> &'a WStr {
+        let content: &'a BlockContent<'gc> = self.content;
+        &content.text
+    }
+
+    fn level(&self, pos: usize) -> Level {
+        self.levels
+            .get(pos - self.paragraph_start)
+            .copied()
+            .unwrap_or(Level::ltr())
+    }
+
+    fn font_set(
+        &mut self,
+        context: &mut UpdateContext<'gc>,
+        movie: &Arc<SwfMovie>,
+        run_index: usize,
+    ) -> Option<(FontSet<'gc>, &'a TextSpan)> {
+        let content: &'a BlockContent<'gc> = self.content;
+        let ContentRunKind::Text {
+            span, font_type, ..
+        } = &content.runs[run_index].kind
+        else {
+            return None;
+        };
+
+        let font_set = *self.font_sets[run_index]
+            .get_or_insert_with(|| resolve_font(context, span, *font_type, movie));
+        Some((font_set, span))
+    }
+
+    /// Split the given range of text into pieces.
+    fn pieces(&self, range: Range<usize>) -> Vec<Piece> {
+        let text = self.text();
+        let mut pieces = Vec::new();
+        for (run_index, run) in self.content.runs.iter().enumerate() {
+            let start = run.range.start.max(range.start);
+            let end = run.range.end.min(range.end);
+            if start >= end {
+                continue;
+            }
+
+            if let ContentRunKind::Graphic { .. } = run.kind {
+                pieces.push(Piece {
+                    range: start..end,
+                    run_index,
+                    level: self.level(start),
+                    kind: PieceKind::Graphic,
+                });
+                continue;
+            }
+
+            let mut piece_start = start;
+            for pos in start..end {
+                let kind = match text.at(pos) {
+                    0x09 => PieceKind::Tab,
+                    c if is_line_break(c) => PieceKind::LineBreak,
+                    _ => PieceKind::Text,
+                };
+
+                if kind == PieceKind::Text && self.level(pos) == self.level(piece_start) {
+                    continue;
+                }
+
+                if piece_start < pos {
+                    pieces.push(Piece {
+                        range: piece_start..pos,
+                        run_index,
+                        level: self.level(piece_start),
+                        kind: PieceKind::Text,
+                    });
+                }
+
+                if kind == PieceKind::Text {
+                    piece_start = pos;
+                } else {
+                    pieces.push(Piece {
+                        range: pos..pos + 1,
+                        run_index,
+                        level: self.level(pos),
+                        kind,
+                    });
+                    piece_start = pos + 1;
+                }
+            }
+
+            if piece_start < end {
+                pieces.push(Piece {
+                    range: piece_start..end,
+                    run_index,
+                    level: self.level(piece_start),
+                    kind: PieceKind::Text,
+                });
+            }
+        }
+        pieces
+    }
+
+    /// Measure the characters of the given pieces, tabs excepted.
+    fn measure(
+        &mut self,
+        context: &mut UpdateContext<'gc>,
+        movie: &Arc<SwfMovie>,
+        range: Range<usize>,
+        pieces: &[Piece],
+    ) -> CharMetrics {
+        let mut metrics = CharMetrics::new(range);
+        for piece in pieces {
+            let offset = piece.range.start - metrics.start;
+            match piece.kind {
+                PieceKind::Text => {
+                    let Some((font_set, span)) = self.font_set(context, movie, piece.run_index)
+                    else {
+                        continue;
+                    };
+                    let params = EvalParameters {
+                        is_rtl: piece.level.is_rtl(),
+                        ..EvalParameters::from_span(span)
+                    };
+                    font_set.evaluate(
+                        &self.text()[piece.range.clone()],
+                        Default::default(),
+                        params,
+                        |pos, _transform, _glyph, advance, x| {
+                            metrics.x[offset + pos] = x;
+                            metrics.advances[offset + pos] = advance;
+                        },
+                    );
+                }
+                PieceKind::Graphic => {
+                    if let ContentRunKind::Graphic { width, .. } =
+                        self.content.runs[piece.run_index].kind
+                    {
+                        metrics.advances[offset] = width;
+                    }
+                }
+                PieceKind::Tab | PieceKind::LineBreak => {}
+            }
+        }
+        metrics
+    }
+
+    /// Whether the line may be broken between the atom at `range` and the
+    /// next one.
+    fn is_break_opportunity(&self, range: Range<usize>) -> bool {
+        let text = self.text();
+        let Some(run) = self
+            .content
+            .runs
+            .iter()
+            .find(|run| run.range.contains(&range.start))
+        else {
+            return false;
+        };
+
+        let break_opportunity = match run.kind {
+            ContentRunKind::Text {
+                break_opportunity, ..
+            } => break_opportunity,
+            ContentRunKind::Graphic { .. } => return true,
+        };
+
+        match break_opportunity {
+            BreakOpportunityValue::All | BreakOpportunityValue::Any => true,
+            BreakOpportunityValue::None => false,
+            BreakOpportunityValue::Auto => {
+                let c = text.at(range.start);
+                let next = text.get(range.end);
+                is_whitespace(c)
+                    || c == u16::from(b'-') && next.is_some_and(|next| !is_whitespace(next))
+                    || is_ideographic(c)
+                    || next.is_some_and(is_ideographic)
+            }
+        }
+    }
+}
+
+/// Lay out the line of a text block starting at the given position of its
+/// raw text.
+///
+/// The line is broken at the last break opportunity fitting in `width`, or
+/// in the middle of a word when there is none. Returns the layout, the end
+/// of the line in the raw text, and how the line was broken, or `None` when
+/// not even the first atom fits and `fit_something` is false.
+pub fn lay_out_line<'gc>(
+    context: &mut UpdateContext<'gc>,
+    movie: &Arc<SwfMovie>,
+    content: &BlockContent<'gc>,
+    settings: &BlockSettings,
+    begin: usize,
+    width: Twips,
+    fit_something: bool,
+) -> Option<(LineLayout<'gc>, usize, TextLineCreationResultValue)> {
+    let text = &content.text[..];
+    let paragraph_start = text[..begin].rfind(is_line_break).map_or(0, |pos| pos + 1);
+    let paragraph_end = next_line_break(text, begin);
+    let paragraph_level =
+        Level::new(settings.bidi_level.clamp(0, 125) as u8).unwrap_or_else(|_| Level::ltr());
+
+    let mut layout_context = LayoutContext {
+        content,
+        settings,
+        font_sets: vec![None; content.runs.len()],
+        paragraph_start,
+        levels: resolve_bidi_levels(&text[paragraph_start..paragraph_end], Some(paragraph_level)),
+    };
+
+    let atom_ranges = atom_ranges(text, begin..paragraph_end);
+    let pieces = layout_context.pieces(begin..paragraph_end);
+    let char_metrics = layout_context.measure(context, movie, begin..paragraph_end, &pieces);
+
+    // Find where to break the line.
+    let mut end = paragraph_end;
+    let mut result = TextLineCreationResultValue::Success;
+    let mut last_break_opportunity = None;
+    let mut x = Twips::ZERO;
+    for (i, atom_range) in atom_ranges.iter().enumerate() {
+        let c = text.at(atom_range.start);
+        let atom_width = if c == 0x09 {
+            settings.next_tab_stop(x).1 - x
+        } else {
+            char_metrics.width(atom_range.clone())
+        };
+
+        if !is_whitespace(c) && !is_line_break(c) && x + atom_width > width {
+            if let Some(break_opportunity) = last_break_opportunity {
+                end = break_opportunity;
+            } else if i > 0 {
+                end = atom_range.start;
+                result = TextLineCreationResultValue::Emergency;
+            } else if fit_something {
+                end = atom_range.end;
+                result = TextLineCreationResultValue::Emergency;
+            } else {
+                return None;
+            }
+            break;
+        }
+
+        x += atom_width;
+        if layout_context.is_break_opportunity(atom_range.clone()) {
+            last_break_opportunity = Some(atom_range.end);
+        }
+    }
+
+    let atom_ranges = &atom_ranges[..atom_ranges.partition_point(|r| r.end <= end)];
+    let pieces = layout_context.pieces(begin..end);
+    let mut char_metrics = layout_context.measure(context, movie, begin..end, &pieces);
+
+    // Justify the line by adding space after some atoms.
+    let justify = match settings.line_justification {
+        LineJustificationValue::Unjustified => false,
+        LineJustificationValue::AllIncludingLast => true,
+        LineJustificationValue::AllButLast => end < text.len(),
+        LineJustificationValue::AllButMandatoryBreak => end < paragraph_end,
+    };
+    let mut extra_spacing = vec![Twips::ZERO; end - begin];
+    let trailing_whitespace = atom_ranges
+        .iter()
+        .rev()
+        .take_while(|r| {
+            let c = text.at(r.start);
+            is_whitespace(c) || is_line_break(c)
+        })
+        .count();
+    let justified_atoms = &atom_ranges[..atom_ranges.len() - trailing_whitespace];
+    if justify && !pieces.iter().any(|p| p.kind == PieceKind::Tab) {
+        let content_width = justified_atoms
+            .last()
+            .map_or(Twips::ZERO, |last| char_metrics.width(begin..last.end));
+        let extra = width - content_width;
+        let expandable: Vec<_> = if settings.letter_spacing {
+            justified_atoms
+                .split_last()
+                .map_or(&[][..], |(_, rest)| rest)
+                .iter()
+                .collect()
+        } else {
+            justified_atoms
+                .iter()
+                .filter(|r| matches!(text.at(r.start), 0x20 | 0x3000))
+                .collect()
+        };
+        if extra > Twips::ZERO && !expandable.is_empty() {
+            let count = expandable.len() as i32;
+            for (i, atom_range) in expandable.into_iter().enumerate() {
+                let i = i as i32;
+                let spacing = Twips::new(extra.get() * (i + 1) / count - extra.get() * i / count);
+                extra_spacing[atom_range.start - begin] = spacing;
+                char_metrics.advances[atom_range.start - begin] += spacing;
+            }
+        }
+    }
+
+    // Position the pieces in visual order.
+    let levels: Vec<_> = pieces.iter().map(|piece| piece.level).collect();
+    let visual_order = BidiInfo::reorder_visual(&levels);
+    let mut piece_x = vec![Twips::ZERO; pieces.len()];
+    let mut x = Twips::ZERO;
+    for (visual_index, &logical_index) in visual_order.iter().enumerate() {
+        let piece = &pieces[logical_index];
+        piece_x[logical_index] = x;
+        if piece.kind == PieceKind::Tab {
+            let following = visual_order[visual_index + 1..]
+                .iter()
+                .map(|&i| &pieces[i])
+                .take_while(|piece| piece.kind != PieceKind::Tab);
+            let tab_width = tab_width(&layout_context, &char_metrics, x, following);
+            char_metrics.advances[piece.range.start - begin] = tab_width;
+            x += tab_width;
+        } else {
+            x += char_metrics.width(piece.range.clone());
+        }
+    }
+    let text_width = x;
+    let unjustified_text_width = extra_spacing
+        .iter()
+        .fold(text_width, |width, &spacing| width - spacing);
+
+    // Build the runs, with the position of every character in the line.
+    let mut char_x = vec![Twips::ZERO; end - begin];
+    let mut char_ascent = vec![Twips::ZERO; end - begin];
+    let mut char_descent = vec![Twips::ZERO; end - begin];
+    let mut text_ascent = Twips::ZERO;
+    let mut text_descent = Twips::ZERO;
+    let mut runs = Vec::new();
+    for &logical_index in &visual_order {
+        let piece = &pieces[logical_index];
+        let x = piece_x[logical_index];
+        let range = piece.range.start - begin..piece.range.end - begin;
+
+        let (ascent, descent) = match layout_context.content.runs[piece.run_index].kind {
+            ContentRunKind::Graphic { height, .. } => (height, Twips::ZERO),
+            ContentRunKind::Text { .. } => {
+                let Some((font_set, span)) =
+                    layout_context.font_set(context, movie, piece.run_index)
+                else {
+                    continue;
+                };
+                let params = EvalParameters {
+                    is_rtl: piece.level.is_rtl(),
+                    ..EvalParameters::from_span(span)
+                };
+                let metrics = font_set.metrics();
+                let ascent = metrics.ascent(params.height());
+                let descent = metrics.descent(params.height());
+                text_ascent = text_ascent.max(ascent);
+                text_descent = text_descent.max(descent);
+
+                if piece.kind == PieceKind::Text {
+                    // Glyphs are shifted by the spacing added to the
+                    // characters before them, in the direction of the text.
+                    let spacing = &extra_spacing[range.clone()];
+                    let glyph_shifts = (0..spacing.len())
+                        .map(|i| {
+                            let before = if piece.level.is_rtl() {
+                                &spacing[i + 1..]
+                            } else {
+                                &spacing[..i]
+                            };
+                            before.iter().fold(Twips::ZERO, |sum, &s| sum + s)
+                        })
+                        .collect::<Vec<_>>();
+                    for (i, shift) in glyph_shifts.iter().enumerate() {
+                        char_metrics.x[range.start + i] += *shift;
+                    }
+
+                    let ContentRunKind::Text {
+                        span: TextSpan { font, .. },
+                        alpha,
+                        ..
+                    } = &layout_context.content.runs[piece.run_index].kind
+                    else {
+                        unreachable!();
+                    };
+                    let color = Color {
+                        a: (alpha.clamp(0.0, 1.0) * 255.0) as u8,
+                        ..font.color
+                    };
+                    runs.push(LineRun {
+                        text: WString::from_wstr(&text[piece.range.clone()]),
+                        x,
+                        ascent,
+                        font_set,
+                        params,
+                        color,
+                        glyph_shifts,
+                    });
+                }
+                (ascent, descent)
+            }
+        };
+
+        for i in range {
+            char_x[i] = x + char_metrics.x[i];
+            char_ascent[i] = ascent;
+            char_descent[i] = descent;
+        }
+    }
+
+    let line_ascent = char_ascent.iter().copied().max().unwrap_or_default();
+    let line_descent = char_descent.iter().copied().max().unwrap_or_default();
+
+    // Build the atoms.
+    let word_boundaries = word_boundaries(text, begin..end);
+    let atoms = atom_ranges
+        .iter()
+        .map(|atom_range| {
+            let chars = atom_range.start - begin..atom_range.end - begin;
+            let x_min = chars.clone().map(|i| char_x[i]).min().unwrap_or_default();
+            let x_max = chars
+                .clone()
+                .map(|i| char_x[i] + char_metrics.advances[i])
+                .max()
+                .unwrap_or_default();
+            let (ascent, descent) = if is_line_break(text.at(atom_range.start)) {
+                (line_ascent, line_descent)
+            } else {
+                (char_ascent[chars.start], char_descent[chars.start])
+            };
+            let run = layout_context
+                .content
+                .runs
+                .iter()
+                .find(|run| run.range.contains(&atom_range.start));
+            let graphic = run.and_then(|run| match run.kind {
+                ContentRunKind::Graphic { graphic, .. } => graphic,
+                ContentRunKind::Text { .. } => None,
+            });
+
+            Atom {
+                range: atom_range.clone(),
+                bounds: Rectangle {
+                    x_min,
+                    x_max,
+                    y_min: -ascent,
+                    y_max: descent,
+                },
+                bidi_level: layout_context.level(atom_range.start),
+                word_boundary_on_left: word_boundaries.binary_search(&atom_range.start).is_ok(),
+                text_rotation: run.map_or(TextRotationValue::Rotate0, |run| run.text_rotation),
+                graphic,
+            }
+        })
+        .collect();
+
+    let layout = LineLayout {
+        runs,
+        atoms,
+        metrics: LineMetrics {
+            ascent: text_ascent,
+            descent: text_descent,
+            total_ascent: line_ascent,
+            total_descent: line_descent,
+            text_width,
+            unjustified_text_width,
+            has_tabs: pieces.iter().any(|p| p.kind == PieceKind::Tab),
+            baseline_zero: Some(settings.baseline_zero),
+            rotation: Some(settings.line_rotation),
+        },
+    };
+    Some((layout, end, result))
+}
+
+/// The width of a tab at `x` followed by the given pieces, until the next tab.
+fn tab_width<'a>(
+    layout_context: &LayoutContext<'_, '_>,
+    char_metrics: &CharMetrics,
+    x: Twips,
+    following: impl Iterator<Item = &'a Piece>,
+) -> Twips {
+    let (alignment, position, decimal_alignment_token) = layout_context.settings.next_tab_stop(x);
+    let text = layout_context.text();
+
+    let mut aligned_width = Twips::ZERO;
+    for piece in following {
+        let piece_text = &text[piece.range.clone()];
+        if alignment == TabAlignmentValue::Decimal
+            && !decimal_alignment_token.is_empty()
+            && let Some(token_pos) = piece_text.find(decimal_alignment_token)
+        {
+            let token_start = piece.range.start + token_pos;
+            aligned_width += char_metrics.width(piece.range.start..token_start);
+            break;
+        }
+        aligned_width += char_metrics.width(piece.range.clone());
+    }
+
+    let tab_end = match alignment {
+        TabAlignmentValue::Start => position,
+        TabAlignmentValue::Center => position - aligned_width / 2,
+        TabAlignmentValue::End | TabAlignmentValue::Decimal => position - aligned_width,
+    };
+    (tab_end - x).max(Twips::ZERO)
+}
+
+impl<'gc> LineLayout<'gc> {
+    pub fn ascent(&self) -> Twips {
+        self.metrics.ascent
+    }
+
+    pub fn descent(&self) -> Twips {
+        self.metrics.descent
+    }
+
+    pub fn total_ascent(&self) -> Twips {
+        self.metrics.total_ascent
+    }
+
+    pub fn total_descent(&self) -> Twips {
+        self.metrics.total_descent
+    }
+
+    pub fn text_width(&self) -> Twips {
+        self.metrics.text_width
+    }
+
+    pub fn unjustified_text_width(&self) -> Twips {
+        self.metrics.unjustified_text_width
+    }
+
+    pub fn has_tabs(&self) -> bool {
+        self.metrics.has_tabs
+    }
+
+    pub fn has_graphic_element(&self) -> bool {
+        self.atoms.iter().any(|atom| atom.graphic.is_some())
+    }
+
+    /// The position of the given baseline relative to the `baselineZero`
+    /// baseline of the line.
+    pub fn baseline_position(&self, baseline: TextBaselineValue) -> Twips {
+        let zero = self
+            .metrics
+            .baseline_zero
+            .map_or(Twips::ZERO, |zero| self.roman_baseline_position(zero));
+        self.roman_baseline_position(baseline) - zero
+    }
+
+    /// The position of the given baseline relative to the roman baseline.
+    fn roman_baseline_position(&self, baseline: TextBaselineValue) -> Twips {
+        let LineMetrics {
+            ascent, descent, ..
+        } = self.metrics;
+        match baseline {
+            TextBaselineValue::Roman | TextBaselineValue::UseDominantBaseline => Twips::ZERO,
+            TextBaselineValue::Ascent | TextBaselineValue::IdeographicTop => -ascent,
+            TextBaselineValue::Descent | TextBaselineValue::IdeographicBottom => descent,
+            TextBaselineValue::IdeographicCenter => (descent - ascent) / 2,
+        }
+    }
+
+    /// The transformation from the coordinates of the layout, relative to
+    /// the roman baseline, to the coordinates of the line.
+    fn matrix(&self) -> Matrix {
+        let rotation = match self.metrics.rotation {
+            Some(TextRotationValue::Rotate90) => Matrix {
+                a: 0.0,
+                b: 1.0,
+                c: -1.0,
+                d: 0.0,
+                ..Default::default()
+            },
+            Some(TextRotationValue::Rotate180) => Matrix {
+                a: -1.0,
+                d: -1.0,
+                ..Default::default()
+            },
+            Some(TextRotationValue::Rotate270) => Matrix {
+                a: 0.0,
+                b: -1.0,
+                c: 1.0,
+                d: 0.0,
+                ..Default::default()
+            },
+            _ => Matrix::IDENTITY,
+        };
+        rotation
+            * Matrix::translate(
+                Twips::ZERO,
+                self.baseline_position(TextBaselineValue::Roman),
+            )
+    }
+
+    /// The bounds of the line.
+    pub fn bounds(&self) -> Rectangle<Twips> {
+        self.matrix()
+            * Rectangle {
+                x_min: Twips::ZERO,
+                x_max: self.metrics.text_width,
+                y_min: -self.metrics.total_ascent,
+                y_max: self.metrics.total_descent,
+            }
+    }
+
+    pub fn atom_count(&self) -> usize {
+        self.atoms.len()
+    }
+
+    /// The range of the raw text of the block covered by the given atom.
+    pub fn atom_range(&self, index: usize) -> Option<Range<usize>> {
+        self.atoms.get(index).map(|atom| atom.range.clone())
+    }
+
+    pub fn atom_bounds(&self, index: usize) -> Option<Rectangle<Twips>> {
+        self.atoms
+            .get(index)
+            .map(|atom| self.matrix() * atom.bounds)
+    }
+
+    /// The center of the given atom along the baseline.
+    pub fn atom_center(&self, index: usize) -> Option<Twips> {
+        self.atoms
+            .get(index)
+            .map(|atom| (atom.bounds.x_min + atom.bounds.x_max) / 2)
+    }
+
+    pub fn atom_bidi_level(&self, index: usize) -> Option<u8> {
+        self.atoms.get(index).map(|atom| atom.bidi_level.number())
+    }
+
+    pub fn atom_text_rotation(&self, index: usize) -> Option<TextRotationValue> {
+        self.atoms.get(index).map(|atom| atom.text_rotation)
+    }
+
+    pub fn atom_word_boundary_on_left(&self, index: usize) -> Option<bool> {
+        self.atoms.get(index).map(|atom| atom.word_boundary_on_left)
+    }
+
+    pub fn atom_graphic(&self, index: usize) -> Option<DisplayObject<'gc>> {
+        self.atoms.get(index).and_then(|atom| atom.graphic)
+    }
+
+    /// The index of the atom containing the given position of the raw text
+    /// of the block.
+    pub fn atom_index_at_char_index(&self, pos: usize) -> Option<usize> {
+        self.atoms.iter().position(|atom| atom.range.contains(&pos))
+    }
+
+    /// The index of the atom under the given point, in line coordinates.
+    pub fn atom_index_at_point(&self, point: Point<Twips>) -> Option<usize> {
+        let point = self.matrix().inverse()? * point;
+        if point.y < -self.metrics.total_ascent || point.y > self.metrics.total_descent {
+            return None;
+        }
+        self.atoms
+            .iter()
+            .position(|atom| atom.bounds.x_min <= point.x && point.x < atom.bounds.x_max)
+    }
+
+    pub fn render(&self, context: &mut RenderContext<'_, 'gc>) {
+        context.transform_stack.push(&Transform {
+            matrix: self.matrix(),
+            ..Default::default()
+        });
+
+        for run in &self.runs {
+            // Glyphs are positioned from the top of the run.
+            context.transform_stack.push(&Transform {
+                matrix: Matrix::translate(run.x, -run.ascent),
+                ..Default::default()
+            });
+
+            let mut transform = Transform::default();
+            transform.color_transform.set_mult_color(run.color);
+            run.font_set.evaluate(
+                &run.text,
+                transform,
+                run.params,
+                |pos, transform, glyph, _advance, _x| {
+                    if glyph.renderable(context) {
+                        let mut transform = transform.clone();
+                        transform.matrix.tx += run.glyph_shifts[pos];
+                        context.transform_stack.push(&transform);
+                        glyph.render(context);
+                        context.transform_stack.pop();
+                    }
+                },
+            );
+
+            context.transform_stack.pop();
+        }
+
+        context.transform_stack.pop();
+    }
+}
+
+/// The ranges of the grapheme clusters of the given range of text.
+fn atom_ranges(text: &WStr, range: Range<usize>) -> Vec<Range<usize>> {
+    let utf8 = Utf8Text::new(&text[range.clone()]);
+    let mut starts: Vec<_> = utf8
+        .text
+        .grapheme_indices(true)
+        .map(|(i, _)| range.start + utf8.positions[i])
+        .collect();
+    starts.push(range.end);
+    starts.windows(2).map(|w| w[0]..w[1]).collect()
+}
+
+/// The positions of the word boundaries of the given range of text.
+fn word_boundaries(text: &WStr, range: Range<usize>) -> Vec<usize> {
+    let utf8 = Utf8Text::new(&text[range.clone()]);
+    utf8.text
+        .split_word_bound_indices()
+        .map(|(i, _)| range.start + utf8.positions[i])
+        .collect()
+}
+
+/// Text converted to UTF-8, with the original position of each byte.
+struct Utf8Text {
+    text: String,
+    positions: Vec<usize>,
+}
+
+impl Utf8Text {
+    fn new(text: &WStr) -> Self {
+        let mut utf8_text = String::with_capacity(text.len());
+        let mut positions = Vec::with_capacity(text.len());
+        for (pos, c) in text.char_indices() {
+            utf8_text.push(c.unwrap_or(char::REPLACEMENT_CHARACTER));
+            positions.resize(utf8_text.len(), pos);
+        }
+        Self {
+            text: utf8_text,
+            positions,
+        }
+    }
+}
+
+/// Find the end of the paragraph starting at `start`, including its line
+/// break.
+fn next_line_break(text: &WStr, start: usize) -> usize {
+    let remaining_text = &text[start..];
+    let len = remaining_text
+        .iter()
+        .position(is_line_break)
+        // Include the separator.
+        .map(|pos| {
+            if remaining_text.get(pos) == Some(0x0D) && remaining_text.get(pos + 1) == Some(0x0A) {
+                pos + 2
+            } else {
+                pos + 1
+            }
+        });
+
+    if let Some(len) = len {
+        start + len
+    } else {
+        text.len()
+    }
+}
+
+fn is_line_break(c: u16) -> bool {
+    matches!(c, 0x0A | 0x0D | 0x2028 | 0x2029)
+}
+
+fn is_whitespace(c: u16) -> bool {
+    matches!(c, 0x09 | 0x20 | 0x200B | 0x3000)
+}
+
+fn is_ideographic(c: u16) -> bool {
+    matches!(c, 0x3040..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF)
+}
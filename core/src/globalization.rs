@@ -0,0 +1,1021 @@
+//! Locale-aware formatting, parsing and collation, as used by the
+//! `flash.globalization` package.
+
+mod locale_data;
+
+pub use locale_data::LocaleData;
+
+use chrono::{DateTime, Datelike, Offset, TimeZone, Timelike};
+use locale_data::LOCALES;
+use std::cmp::Ordering;
+use std::fmt::Write;
+
+/// The locale ID name that stands for the user's default locale, `LocaleID.DEFAULT`.
+pub const DEFAULT_LOCALE_NAME: &str = "i-default";
+
+/// The outcome of the last operation of a `flash.globalization` object, as
+/// reported by its `lastOperationStatus` property.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OperationStatus {
+    NoError,
+    UsingDefaultWarning,
+    UsingFallbackWarning,
+    ParseError,
+}
+
+impl OperationStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OperationStatus::NoError => "noError",
+            OperationStatus::UsingDefaultWarning => "usingDefaultWarning",
+            OperationStatus::UsingFallbackWarning => "usingFallbackWarning",
+            OperationStatus::ParseError => "parseError",
+        }
+    }
+}
+
+/// The components of a locale ID name, such as `zh-Hant-TW@collation=stroke`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct LocaleId<'a> {
+    pub language: &'a str,
+    pub script: &'a str,
+    pub region: &'a str,
+    pub variant: &'a str,
+    pub keywords: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> LocaleId<'a> {
+    pub fn parse(name: &'a str) -> Self {
+        let (tags, keywords) = name.split_once('@').unwrap_or((name, ""));
+        let mut id = Self {
+            keywords: keywords
+                .split(';')
+                .filter_map(|keyword| keyword.split_once('='))
+                .map(|(key, value)| (key.trim(), value.trim()))
+                .collect(),
+            ..Default::default()
+        };
+
+        let mut position = 0;
+        for (i, subtag) in tags.split(['-', '_']).enumerate() {
+            let is_alphabetic = subtag.bytes().all(|b| b.is_ascii_alphabetic());
+            if i == 0 {
+                id.language = subtag;
+            } else if id.script.is_empty()
+                && id.region.is_empty()
+                && subtag.len() == 4
+                && is_alphabetic
+            {
+                id.script = subtag;
+            } else if id.region.is_empty()
+                && ((subtag.len() == 2 && is_alphabetic)
+                    || (subtag.len() == 3 && subtag.bytes().all(|b| b.is_ascii_digit())))
+            {
+                id.region = subtag;
+            } else {
+                // Everything that follows is part of the variant.
+                id.variant = &tags[position..];
+                break;
+            }
+            position += subtag.len() + 1;
+        }
+
+        id
+    }
+
+    pub fn is_right_to_left(&self) -> bool {
+        if !self.script.is_empty() {
+            return ["Arab", "Hebr", "Syrc", "Thaa", "Nkoo", "Adlm", "Rohg"]
+                .iter()
+                .any(|script| script.eq_ignore_ascii_case(self.script));
+        }
+        [
+            "ar", "ckb", "dv", "fa", "he", "iw", "ji", "ps", "sd", "ug", "ur", "yi",
+        ]
+        .iter()
+        .any(|language| language.eq_ignore_ascii_case(self.language))
+    }
+}
+
+/// The names of all the locales we have data for.
+pub fn available_locale_names() -> impl Iterator<Item = &'static str> {
+    LOCALES.iter().map(|locale| locale.name)
+}
+
+/// Finds the data of the locale closest to the requested locale ID name.
+///
+/// `default_name` is used for `LocaleID.DEFAULT`, and should be the language
+/// of the user interface.
+pub fn resolve_locale(
+    requested: &str,
+    default_name: &str,
+) -> (&'static LocaleData, OperationStatus) {
+    let (name, status) = if requested == DEFAULT_LOCALE_NAME {
+        (default_name, OperationStatus::UsingDefaultWarning)
+    } else {
+        (requested, OperationStatus::NoError)
+    };
+
+    let id = LocaleId::parse(name);
+    let mut same_language = None;
+    for locale in LOCALES {
+        let locale_id = LocaleId::parse(locale.name);
+        if !locale_id.language.eq_ignore_ascii_case(id.language) {
+            continue;
+        }
+        if locale_id.region.eq_ignore_ascii_case(id.region) {
+            return (locale, status);
+        }
+        same_language.get_or_insert(locale);
+    }
+
+    match same_language {
+        // Not asking for any region in particular is fine.
+        Some(locale) if id.region.is_empty() => (locale, status),
+        Some(locale) => (locale, OperationStatus::UsingFallbackWarning),
+        None => (&LOCALES[0], OperationStatus::UsingFallbackWarning),
+    }
+}
+
+/// The settings of a `NumberFormatter` or `CurrencyFormatter`.
+#[derive(Debug)]
+pub struct NumberFormat<'a> {
+    pub decimal_separator: &'a str,
+    pub grouping_separator: &'a str,
+    pub grouping_pattern: &'a str,
+    pub digits_type: u32,
+    pub fractional_digits: i32,
+    pub leading_zero: bool,
+    pub trailing_zeros: bool,
+    pub use_grouping: bool,
+    pub negative_symbol: &'a str,
+}
+
+/// A number found by `NumberFormat::parse_number`.
+#[derive(Debug, PartialEq)]
+pub struct ParsedNumber {
+    pub value: f64,
+
+    /// The UTF-16 index of the first character of the number.
+    pub start_index: usize,
+
+    /// The UTF-16 index following the last character of the number.
+    pub end_index: usize,
+}
+
+impl NumberFormat<'_> {
+    /// Formats a number, using the given `negativeNumberFormat` for negative values.
+    pub fn format_number(&self, value: f64, negative_format: u32) -> String {
+        if value.is_nan() {
+            return "NaN".to_string();
+        }
+
+        let (n, is_zero) = self.format_absolute(value);
+        if value > 0.0 || is_zero {
+            return n;
+        }
+
+        let m = self.negative_symbol;
+        match negative_format {
+            0 => format!("({n})"),
+            2 => format!("{m} {n}"),
+            3 => format!("{n}{m}"),
+            4 => format!("{n} {m}"),
+            _ => format!("{m}{n}"),
+        }
+    }
+
+    /// Formats an amount of money, using the given `positiveCurrencyFormat`
+    /// or `negativeCurrencyFormat` to place the currency symbol and the sign.
+    pub fn format_currency(
+        &self,
+        value: f64,
+        symbol: &str,
+        positive_format: u32,
+        negative_format: u32,
+    ) -> String {
+        if value.is_nan() {
+            return "NaN".to_string();
+        }
+
+        let (n, is_zero) = self.format_absolute(value);
+        let s = symbol;
+        if value > 0.0 || is_zero {
+            return match positive_format {
+                1 => format!("{n}{s}"),
+                2 => format!("{s} {n}"),
+                3 => format!("{n} {s}"),
+                _ => format!("{s}{n}"),
+            };
+        }
+
+        let m = self.negative_symbol;
+        match negative_format {
+            0 => format!("({s}{n})"),
+            2 => format!("{s}{m}{n}"),
+            3 => format!("{s}{n}{m}"),
+            4 => format!("({n}{s})"),
+            5 => format!("{m}{n}{s}"),
+            6 => format!("{n}{m}{s}"),
+            7 => format!("{n}{s}{m}"),
+            8 => format!("{m}{n} {s}"),
+            9 => format!("{m}{s} {n}"),
+            10 => format!("{n} {s}{m}"),
+            11 => format!("{s} {n}{m}"),
+            12 => format!("{s} {m}{n}"),
+            13 => format!("{n}{m} {s}"),
+            14 => format!("({s} {n})"),
+            15 => format!("({n} {s})"),
+            _ => format!("{m}{s}{n}"),
+        }
+    }
+
+    /// Formats the absolute value of a number, without any sign.
+    ///
+    /// Also returns whether the number was rounded to zero.
+    fn format_absolute(&self, value: f64) -> (String, bool) {
+        if value.is_infinite() {
+            return ("Infinity".to_string(), false);
+        }
+
+        let fractional_digits = self.fractional_digits.clamp(0, 20) as usize;
+        let formatted = format!("{:.*}", fractional_digits, value.abs());
+        let is_zero = !formatted.bytes().any(|b| matches!(b, b'1'..=b'9'));
+
+        let (integer, fraction) = formatted.split_once('.').unwrap_or((&formatted, ""));
+        let fraction = if self.trailing_zeros {
+            fraction
+        } else {
+            fraction.trim_end_matches('0')
+        };
+
+        let mut result = String::new();
+        if integer != "0" || self.leading_zero || fraction.is_empty() {
+            result.push_str(&self.group_digits(integer));
+        }
+        if !fraction.is_empty() {
+            result.push_str(self.decimal_separator);
+            result.push_str(fraction);
+        }
+
+        (self.localize_digits(&result), is_zero)
+    }
+
+    /// Inserts grouping separators in a string of digits, according to the
+    /// grouping pattern, e.g. `3;*` or `3;2;*`.
+    fn group_digits(&self, digits: &str) -> String {
+        if !self.use_grouping {
+            return digits.to_string();
+        }
+
+        let mut sizes = vec![];
+        let mut repeat = false;
+        for size in self.grouping_pattern.split(';') {
+            match size.trim() {
+                "*" => {
+                    repeat = true;
+                    break;
+                }
+                size => match size.parse::<usize>() {
+                    Ok(size) if size > 0 => sizes.push(size),
+                    _ => return digits.to_string(),
+                },
+            }
+        }
+
+        let mut groups = vec![];
+        let mut rest = digits;
+        let mut index = 0;
+        while let Some(&size) = sizes
+            .get(index)
+            .or(if repeat { sizes.last() } else { None })
+        {
+            if rest.len() <= size {
+                break;
+            }
+            let (head, group) = rest.split_at(rest.len() - size);
+            groups.push(group);
+            rest = head;
+            index += 1;
+        }
+        groups.push(rest);
+        groups.reverse();
+        groups.join(self.grouping_separator)
+    }
+
+    /// Replaces European digits with the digits of `digits_type`.
+    fn localize_digits(&self, text: &str) -> String {
+        text.chars()
+            .map(|c| match c.to_digit(10) {
+                Some(digit) if c.is_ascii_digit() => {
+                    char::from_u32(self.digits_type + digit).unwrap_or(c)
+                }
+                _ => c,
+            })
+            .collect()
+    }
+
+    /// Returns the value of a digit, either European or of `digits_type`.
+    fn digit_value(&self, c: char) -> Option<u32> {
+        if let Some(digit) = c.to_digit(10) {
+            return Some(digit);
+        }
+        let digit = (c as u32).checked_sub(self.digits_type)?;
+        (digit < 10).then_some(digit)
+    }
+
+    /// Finds the first number in `input`.
+    pub fn parse_number(&self, input: &str, negative_format: u32) -> Option<ParsedNumber> {
+        let starts_number = |text: &str| match text.chars().next() {
+            Some(c) if self.digit_value(c).is_some() => true,
+            _ => {
+                !self.decimal_separator.is_empty()
+                    && text
+                        .strip_prefix(self.decimal_separator)
+                        .and_then(|rest| rest.chars().next())
+                        .is_some_and(|c| self.digit_value(c).is_some())
+            }
+        };
+
+        let digits_start = input
+            .char_indices()
+            .map(|(i, _)| i)
+            .find(|&i| starts_number(&input[i..]))?;
+
+        // A sign may precede the digits.
+        let mut start = digits_start;
+        let mut is_negative = false;
+        let mut in_parentheses = false;
+        let before = input[..digits_start].trim_end();
+        if !self.negative_symbol.is_empty() && before.ends_with(self.negative_symbol) {
+            start = before.len() - self.negative_symbol.len();
+            is_negative = true;
+        } else if before.ends_with('(') {
+            start = before.len() - 1;
+            in_parentheses = true;
+        }
+
+        let mut number = String::new();
+        let mut position = digits_start;
+        let mut in_fraction = false;
+        while position < input.len() {
+            let rest = &input[position..];
+            let c = rest.chars().next()?;
+            if let Some(digit) = self.digit_value(c) {
+                number.push(char::from_digit(digit, 10)?);
+                position += c.len_utf8();
+                continue;
+            }
+
+            let separator_len = if !in_fraction
+                && !self.decimal_separator.is_empty()
+                && rest.starts_with(self.decimal_separator)
+            {
+                in_fraction = true;
+                number.push('.');
+                self.decimal_separator.len()
+            } else if !in_fraction
+                && !self.grouping_separator.is_empty()
+                && rest.starts_with(self.grouping_separator)
+            {
+                self.grouping_separator.len()
+            } else {
+                break;
+            };
+
+            // A separator only belongs to the number if a digit follows.
+            match input[position + separator_len..].chars().next() {
+                Some(c) if self.digit_value(c).is_some() => position += separator_len,
+                _ => {
+                    if number.ends_with('.') {
+                        number.pop();
+                    }
+                    break;
+                }
+            }
+        }
+
+        let mut end = position;
+        let after = &input[end..];
+        let after_trimmed = after.trim_start();
+        if in_parentheses {
+            if !after_trimmed.starts_with(')') {
+                return None;
+            }
+            end += after.len() - after_trimmed.len() + 1;
+            is_negative = true;
+        } else if !is_negative
+            && matches!(negative_format, 3 | 4)
+            && !self.negative_symbol.is_empty()
+            && after_trimmed.starts_with(self.negative_symbol)
+        {
+            end += after.len() - after_trimmed.len() + self.negative_symbol.len();
+            is_negative = true;
+        }
+
+        let value: f64 = number.parse().ok()?;
+        Some(ParsedNumber {
+            value: if is_negative { -value } else { value },
+            start_index: utf16_index(input, start),
+            end_index: utf16_index(input, end),
+        })
+    }
+
+    /// Parses a string that contains nothing but a number, and possibly whitespace.
+    pub fn parse_whole_number(&self, input: &str, negative_format: u32) -> Option<f64> {
+        let parsed = self.parse_number(input, negative_format)?;
+        let start = byte_index(input, parsed.start_index);
+        let end = byte_index(input, parsed.end_index);
+        (input[..start].trim().is_empty() && input[end..].trim().is_empty()).then_some(parsed.value)
+    }
+
+    /// Parses an amount of money, which may contain a currency symbol or code.
+    ///
+    /// Returns the amount, and the currency string that was found in the input.
+    pub fn parse_currency<'i>(
+        &self,
+        input: &'i str,
+        symbol: &str,
+        iso_code: &str,
+        negative_format: u32,
+    ) -> Option<(f64, &'i str)> {
+        // Only whether the negative sign trails the number matters here.
+        let negative_format = if matches!(negative_format, 3 | 6 | 7 | 10 | 11 | 13) {
+            3
+        } else {
+            1
+        };
+
+        let currency = [iso_code, symbol]
+            .into_iter()
+            .filter(|currency| !currency.is_empty())
+            .find_map(|currency| input.find(currency).map(|index| (index, currency.len())));
+
+        let (value, currency_string) = if let Some((index, len)) = currency {
+            let remaining = format!("{}{}", &input[..index], &input[index + len..]);
+            let value = self.parse_whole_number(&remaining, negative_format)?;
+            (value, &input[index..index + len])
+        } else {
+            (self.parse_whole_number(input, negative_format)?, "")
+        };
+
+        Some((value, currency_string))
+    }
+}
+
+fn utf16_index(text: &str, byte_index: usize) -> usize {
+    text[..byte_index].encode_utf16().count()
+}
+
+fn byte_index(text: &str, utf16_index: usize) -> usize {
+    let mut units = 0;
+    for (i, c) in text.char_indices() {
+        if units >= utf16_index {
+            return i;
+        }
+        units += c.len_utf16();
+    }
+    text.len()
+}
+
+/// The styles of a `DateTimeFormatter`, see `flash.globalization.DateTimeStyle`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateTimeStyle {
+    Long,
+    Medium,
+    Short,
+    None,
+}
+
+impl DateTimeStyle {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "long" => Some(DateTimeStyle::Long),
+            "medium" => Some(DateTimeStyle::Medium),
+            "short" => Some(DateTimeStyle::Short),
+            "none" => Some(DateTimeStyle::None),
+            _ => None,
+        }
+    }
+
+    fn index(self) -> Option<usize> {
+        match self {
+            DateTimeStyle::Long => Some(0),
+            DateTimeStyle::Medium => Some(1),
+            DateTimeStyle::Short => Some(2),
+            DateTimeStyle::None => None,
+        }
+    }
+}
+
+/// The length of month and weekday names, see `flash.globalization.DateTimeNameStyle`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NameStyle {
+    Full,
+    LongAbbreviation,
+    ShortAbbreviation,
+}
+
+impl NameStyle {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "full" => Some(NameStyle::Full),
+            "longAbbreviation" => Some(NameStyle::LongAbbreviation),
+            "shortAbbreviation" => Some(NameStyle::ShortAbbreviation),
+            _ => None,
+        }
+    }
+}
+
+impl LocaleData {
+    fn is_cjk(&self) -> bool {
+        self.name.starts_with("ja") || self.name.starts_with("zh")
+    }
+
+    /// The date-time pattern used for the given styles.
+    pub fn date_time_pattern(
+        &self,
+        date_style: DateTimeStyle,
+        time_style: DateTimeStyle,
+    ) -> String {
+        let date = date_style.index().map(|i| self.date_patterns[i]);
+        let time = time_style.index().map(|i| self.time_patterns[i]);
+        match (date, time) {
+            (Some(date), Some(time)) => format!("{date} {time}"),
+            (Some(pattern), None) | (None, Some(pattern)) => pattern.to_string(),
+            (None, None) => String::new(),
+        }
+    }
+
+    pub fn month_names(&self, style: NameStyle) -> Vec<String> {
+        (0..12)
+            .map(|i| match style {
+                NameStyle::Full => self.months[i].to_string(),
+                NameStyle::LongAbbreviation => self.months_abbreviated[i].to_string(),
+                NameStyle::ShortAbbreviation if self.is_cjk() => (i + 1).to_string(),
+                NameStyle::ShortAbbreviation => initial(self.months[i]),
+            })
+            .collect()
+    }
+
+    pub fn weekday_names(&self, style: NameStyle) -> Vec<String> {
+        (0..7)
+            .map(|i| match style {
+                NameStyle::Full => self.weekdays[i].to_string(),
+                NameStyle::LongAbbreviation => self.weekdays_abbreviated[i].to_string(),
+                NameStyle::ShortAbbreviation if self.is_cjk() => self.weekdays_abbreviated[i]
+                    .chars()
+                    .last()
+                    .map(String::from)
+                    .unwrap_or_default(),
+                NameStyle::ShortAbbreviation => initial(self.weekdays[i]),
+            })
+            .collect()
+    }
+
+    /// Formats a date with a Unicode TR35 date-time pattern, such as `yyyy-MM-dd HH:mm`.
+    pub fn format_date_time<Tz: TimeZone>(&self, date: &DateTime<Tz>, pattern: &str) -> String {
+        let mut result = String::new();
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '\'' {
+                // `''` is a quote, anything else between quotes is literal text.
+                if chars.next_if_eq(&'\'').is_some() {
+                    result.push('\'');
+                    continue;
+                }
+                while let Some(c) = chars.next() {
+                    if c != '\'' {
+                        result.push(c);
+                    } else if chars.next_if_eq(&'\'').is_some() {
+                        result.push('\'');
+                    } else {
+                        break;
+                    }
+                }
+            } else if c.is_ascii_alphabetic() {
+                let mut count = 1;
+                while chars.next_if_eq(&c).is_some() {
+                    count += 1;
+                }
+                self.format_date_field(&mut result, date, c, count);
+            } else {
+                result.push(c);
+            }
+        }
+        result
+    }
+
+    fn format_date_field<Tz: TimeZone>(
+        &self,
+        out: &mut String,
+        date: &DateTime<Tz>,
+        field: char,
+        count: usize,
+    ) {
+        let weekday = date.weekday().num_days_from_sunday();
+        let _ = match field {
+            'G' => write!(out, "{}", if date.year() > 0 { "AD" } else { "BC" }),
+            'y' if count == 2 => write!(out, "{:02}", date.year().rem_euclid(100)),
+            'y' => write!(out, "{:0count$}", date.year()),
+            'M' | 'L' => match count {
+                1 | 2 => write!(out, "{:0count$}", date.month()),
+                3 => write!(out, "{}", self.months_abbreviated[date.month0() as usize]),
+                _ => write!(out, "{}", self.months[date.month0() as usize]),
+            },
+            'Q' if count <= 2 => write!(out, "{:0count$}", date.month0() / 3 + 1),
+            'Q' => write!(out, "Q{}", date.month0() / 3 + 1),
+            'd' => write!(out, "{:0count$}", date.day()),
+            'D' => write!(out, "{:0count$}", date.ordinal()),
+            'F' => write!(out, "{:0count$}", date.day0() / 7 + 1),
+            'E' if count <= 3 => write!(out, "{}", self.weekdays_abbreviated[weekday as usize]),
+            'E' => write!(out, "{}", self.weekdays[weekday as usize]),
+            'w' => {
+                let week = week_number(date.ordinal0(), weekday, self.first_weekday);
+                write!(out, "{week:0count$}")
+            }
+            'W' => {
+                let week = week_number(date.day0(), weekday, self.first_weekday);
+                write!(out, "{week:0count$}")
+            }
+            'a' => write!(out, "{}", if date.hour() < 12 { self.am } else { self.pm }),
+            'h' => write!(out, "{:0count$}", (date.hour() + 11) % 12 + 1),
+            'H' => write!(out, "{:0count$}", date.hour()),
+            'K' => write!(out, "{:0count$}", date.hour() % 12),
+            'k' => write!(
+                out,
+                "{:0count$}",
+                if date.hour() == 0 { 24 } else { date.hour() }
+            ),
+            'm' => write!(out, "{:0count$}", date.minute()),
+            's' => write!(out, "{:0count$}", date.second()),
+            'S' => {
+                let millis = format!("{:03}", date.timestamp_subsec_millis());
+                write!(out, "{:0<count$.count$}", millis)
+            }
+            'z' | 'Z' | 'v' | 'V' => {
+                let offset = date.offset().fix().local_minus_utc();
+                let sign = if offset < 0 { '-' } else { '+' };
+                let (hours, minutes) = (offset.abs() / 3600, offset.abs() / 60 % 60);
+                if field == 'Z' && count <= 3 {
+                    write!(out, "{sign}{hours:02}{minutes:02}")
+                } else {
+                    write!(out, "GMT{sign}{hours:02}:{minutes:02}")
+                }
+            }
+            field => {
+                out.extend(std::iter::repeat_n(field, count));
+                Ok(())
+            }
+        };
+    }
+}
+
+/// The first letter of a name, as an uppercase letter.
+fn initial(name: &str) -> String {
+    name.chars()
+        .next()
+        .into_iter()
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+/// The 1-based number of the week containing the given 0-based day of a
+/// period, such as a year or a month.
+fn week_number(day0: u32, weekday: u32, first_weekday: u32) -> u32 {
+    // The weekday of the first day of the period, relative to the first day of the week.
+    let first_day_weekday = (weekday + 7 - day0 % 7) % 7;
+    let offset = (first_day_weekday + 7 - first_weekday) % 7;
+    (day0 + offset) / 7 + 1
+}
+
+/// The options of a `Collator`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CollatorOptions {
+    pub ignore_case: bool,
+    pub ignore_character_width: bool,
+    pub ignore_diacritics: bool,
+    pub ignore_kana_type: bool,
+    pub ignore_symbols: bool,
+    pub numeric_comparison: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum PrimaryWeight {
+    Space,
+    Symbol(char),
+    Digit(char),
+    /// A run of digits, compared numerically: the number of significant digits, then the digits.
+    Number(usize, String),
+    Letter(char),
+}
+
+#[derive(Debug)]
+struct CollationElement {
+    /// The base character, ignoring case and diacritics.
+    primary: PrimaryWeight,
+
+    /// The character, ignoring case.
+    secondary: char,
+
+    /// Whether the character is uppercase.
+    tertiary: bool,
+}
+
+impl CollatorOptions {
+    /// Compares two strings, in the order in which they would be sorted.
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        let a = self.collation_elements(a);
+        let b = self.collation_elements(b);
+
+        let primary = a
+            .iter()
+            .map(|e| &e.primary)
+            .cmp(b.iter().map(|e| &e.primary));
+        let secondary = if self.ignore_diacritics {
+            Ordering::Equal
+        } else {
+            a.iter()
+                .map(|e| e.secondary)
+                .cmp(b.iter().map(|e| e.secondary))
+        };
+        let tertiary = if self.ignore_case {
+            Ordering::Equal
+        } else {
+            a.iter()
+                .map(|e| e.tertiary)
+                .cmp(b.iter().map(|e| e.tertiary))
+        };
+
+        primary.then(secondary).then(tertiary)
+    }
+
+    fn fold(&self, c: char) -> char {
+        let code = c as u32;
+        let code = match code {
+            0xFF01..=0xFF5E if self.ignore_character_width => code - 0xFEE0,
+            0x3000 if self.ignore_character_width => 0x20,
+            0x30A1..=0x30F6 if self.ignore_kana_type => code - 0x60,
+            code => code,
+        };
+        char::from_u32(code).unwrap_or(c)
+    }
+
+    fn collation_elements(&self, text: &str) -> Vec<CollationElement> {
+        let mut elements = vec![];
+        let mut chars = text.chars().map(|c| self.fold(c)).peekable();
+        while let Some(c) = chars.next() {
+            if self.numeric_comparison && c.is_ascii_digit() {
+                let mut digits = String::from(c);
+                while let Some(c) = chars.next_if(char::is_ascii_digit) {
+                    digits.push(c);
+                }
+                let digits = digits.trim_start_matches('0').to_string();
+                elements.push(CollationElement {
+                    primary: PrimaryWeight::Number(digits.len(), digits),
+                    secondary: '0',
+                    tertiary: false,
+                });
+                continue;
+            }
+
+            if self.ignore_symbols && !c.is_alphanumeric() {
+                continue;
+            }
+
+            let lowercase = c.to_lowercase().next().unwrap_or(c);
+            let base = strip_diacritic(lowercase);
+            let primary = if c.is_whitespace() {
+                PrimaryWeight::Space
+            } else if c.is_numeric() {
+                PrimaryWeight::Digit(base)
+            } else if c.is_alphabetic() {
+                PrimaryWeight::Letter(base)
+            } else {
+                PrimaryWeight::Symbol(base)
+            };
+            elements.push(CollationElement {
+                primary,
+                secondary: lowercase,
+                tertiary: c != lowercase,
+            });
+        }
+        elements
+    }
+}
+
+/// Returns the base letter of a lowercase Latin letter with a diacritic.
+fn strip_diacritic(c: char) -> char {
+    match c {
+        'à'..='å' | '\u{100}'..='\u{105}' => 'a',
+        'ç' | '\u{106}'..='\u{10d}' => 'c',
+        '\u{10e}'..='\u{111}' => 'd',
+        'è'..='ë' | '\u{112}'..='\u{11b}' => 'e',
+        '\u{11c}'..='\u{123}' => 'g',
+        '\u{124}'..='\u{127}' => 'h',
+        'ì'..='ï' | '\u{128}'..='\u{131}' => 'i',
+        '\u{134}'..='\u{135}' => 'j',
+        '\u{136}'..='\u{138}' => 'k',
+        '\u{139}'..='\u{142}' => 'l',
+        'ñ' | '\u{143}'..='\u{149}' => 'n',
+        'ò'..='ö' | 'ø' | '\u{14c}'..='\u{151}' => 'o',
+        '\u{154}'..='\u{159}' => 'r',
+        '\u{15a}'..='\u{161}' => 's',
+        '\u{162}'..='\u{167}' => 't',
+        'ù'..='ü' | '\u{168}'..='\u{173}' => 'u',
+        '\u{174}'..='\u{175}' => 'w',
+        'ý' | 'ÿ' | '\u{176}'..='\u{178}' => 'y',
+        '\u{179}'..='\u{17e}' => 'z',
+        c => c,
+    }
+}
+
+fn is_turkic(locale: &str) -> bool {
+    let language = LocaleId::parse(locale).language;
+    language.eq_ignore_ascii_case("tr") || language.eq_ignore_ascii_case("az")
+}
+
+/// Converts a string to lowercase, following the rules of the given locale.
+pub fn to_lower_case(text: &str, locale: &str) -> String {
+    if !is_turkic(locale) {
+        return text.to_lowercase();
+    }
+    text.chars()
+        .flat_map(|c| match c {
+            'I' => "ı".chars().collect::<Vec<_>>(),
+            'İ' => vec!['i'],
+            c => c.to_lowercase().collect(),
+        })
+        .collect()
+}
+
+/// Converts a string to uppercase, following the rules of the given locale.
+pub fn to_upper_case(text: &str, locale: &str) -> String {
+    if !is_turkic(locale) {
+        return text.to_uppercase();
+    }
+    text.chars()
+        .flat_map(|c| match c {
+            'i' => vec!['İ'],
+            'ı' => vec!['I'],
+            c => c.to_uppercase().collect(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, Utc};
+
+    fn number_format(locale: &LocaleData) -> NumberFormat<'_> {
+        NumberFormat {
+            decimal_separator: locale.decimal_separator,
+            grouping_separator: locale.grouping_separator,
+            grouping_pattern: "3;*",
+            digits_type: '0' as u32,
+            fractional_digits: 2,
+            leading_zero: true,
+            trailing_zeros: true,
+            use_grouping: true,
+            negative_symbol: "-",
+        }
+    }
+
+    #[test]
+    fn parses_locale_ids() {
+        let id = LocaleId::parse("zh-Hant-TW-posix@collation=stroke;calendar=chinese");
+        assert_eq!(id.language, "zh");
+        assert_eq!(id.script, "Hant");
+        assert_eq!(id.region, "TW");
+        assert_eq!(id.variant, "posix");
+        assert_eq!(
+            id.keywords,
+            vec![("collation", "stroke"), ("calendar", "chinese")]
+        );
+
+        let id = LocaleId::parse("es_419");
+        assert_eq!(id.language, "es");
+        assert_eq!(id.region, "419");
+        assert!(LocaleId::parse("ar-EG").is_right_to_left());
+        assert!(!LocaleId::parse("az-Latn").is_right_to_left());
+    }
+
+    #[test]
+    fn resolves_locales() {
+        assert_eq!(resolve_locale("de-DE", "en-US").0.name, "de-DE");
+        let (locale, status) = resolve_locale("fr-CA", "en-US");
+        assert_eq!(locale.name, "fr-FR");
+        assert_eq!(status, OperationStatus::UsingFallbackWarning);
+        let (locale, status) = resolve_locale(DEFAULT_LOCALE_NAME, "ja-JP");
+        assert_eq!(locale.name, "ja-JP");
+        assert_eq!(status, OperationStatus::UsingDefaultWarning);
+        let (locale, status) = resolve_locale("xx", "ja-JP");
+        assert_eq!(locale.name, "en-US");
+        assert_eq!(status, OperationStatus::UsingFallbackWarning);
+    }
+
+    #[test]
+    fn formats_numbers() {
+        let (en, _) = resolve_locale("en-US", "en-US");
+        let (de, _) = resolve_locale("de-DE", "en-US");
+        let mut format = number_format(en);
+        assert_eq!(format.format_number(1234567.891, 1), "1,234,567.89");
+        assert_eq!(format.format_number(-0.5, 0), "(0.50)");
+        assert_eq!(format.format_number(-0.001, 1), "0.00");
+        assert_eq!(number_format(de).format_number(-1234.5, 3), "1.234,50-");
+
+        format.leading_zero = false;
+        format.trailing_zeros = false;
+        format.grouping_pattern = "3;2;*";
+        assert_eq!(format.format_number(0.5, 1), ".5");
+        assert_eq!(format.format_number(12345678.0, 1), "1,23,45,678");
+
+        format.digits_type = 0x0660;
+        assert_eq!(format.format_number(-12.25, 1), "-١٢.٢٥");
+    }
+
+    #[test]
+    fn formats_currencies() {
+        let (en, _) = resolve_locale("en-US", "en-US");
+        let (fr, _) = resolve_locale("fr-FR", "en-US");
+        assert_eq!(
+            number_format(en).format_currency(-1234.5, "$", 0, 1),
+            "-$1,234.50"
+        );
+        assert_eq!(
+            number_format(fr).format_currency(1234.5, "€", 3, 8),
+            "1\u{a0}234,50 €"
+        );
+    }
+
+    #[test]
+    fn parses_numbers() {
+        let (en, _) = resolve_locale("en-US", "en-US");
+        let format = number_format(en);
+        assert_eq!(
+            format.parse_number("total: -1,234.5 items", 1),
+            Some(ParsedNumber {
+                value: -1234.5,
+                start_index: 7,
+                end_index: 15,
+            })
+        );
+        assert_eq!(format.parse_whole_number(" (12) ", 0), Some(-12.0));
+        assert_eq!(format.parse_whole_number("12 apples", 1), None);
+        assert_eq!(
+            format.parse_currency("USD 1,000.25", "$", "USD", 1),
+            Some((1000.25, "USD"))
+        );
+    }
+
+    #[test]
+    fn formats_dates() {
+        let (en, _) = resolve_locale("en-US", "en-US");
+        let (de, _) = resolve_locale("de-DE", "en-US");
+        let date = FixedOffset::east_opt(20700)
+            .unwrap()
+            .with_ymd_and_hms(2001, 2, 3, 16, 5, 6)
+            .unwrap();
+
+        let pattern = en.date_time_pattern(DateTimeStyle::Long, DateTimeStyle::Short);
+        assert_eq!(
+            en.format_date_time(&date, &pattern),
+            "Saturday, February 3, 2001 4:05 PM"
+        );
+        let pattern = de.date_time_pattern(DateTimeStyle::Long, DateTimeStyle::None);
+        assert_eq!(
+            de.format_date_time(&date, &pattern),
+            "Samstag, 3. Februar 2001"
+        );
+        assert_eq!(
+            en.format_date_time(
+                &date.with_timezone(&Utc),
+                "yy-MM-dd'T'HH:mm '('Z')' ''QQQ''"
+            ),
+            "01-02-03T10:20 (+0000) 'Q1'"
+        );
+    }
+
+    #[test]
+    fn compares_strings() {
+        let mut options = CollatorOptions::default();
+        assert_eq!(options.compare("a", "B"), Ordering::Less);
+        assert_eq!(options.compare("a", "A"), Ordering::Less);
+        assert_eq!(options.compare("resume", "résumé"), Ordering::Less);
+        assert_eq!(options.compare("file10", "file9"), Ordering::Less);
+
+        options.ignore_case = true;
+        options.ignore_diacritics = true;
+        options.numeric_comparison = true;
+        assert_eq!(options.compare("RESUME", "résumé"), Ordering::Equal);
+        assert_eq!(options.compare("file10", "file9"), Ordering::Greater);
+    }
+
+    #[test]
+    fn maps_case_per_locale() {
+        assert_eq!(to_upper_case("istanbul", "tr-TR"), "İSTANBUL");
+        assert_eq!(to_upper_case("istanbul", "en-US"), "ISTANBUL");
+        assert_eq!(to_lower_case("DIŞ", "tr"), "dış");
+    }
+}
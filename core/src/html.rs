@@ -10,6 +10,7 @@ pub use dimensions::Position;
 pub use layout::{
     Layout, LayoutBox, LayoutContent, LayoutLine, LayoutMetrics, lower_from_text_spans,
 };
+pub(crate) use layout::{resolve_bidi_levels, resolve_font};
 pub use line_wrapping::wrap_line;
 pub use style_sheet::{CssStream, StyleSheet, parse_font_list, transform_dashes_to_camel_case};
 pub use text_format::{FormatSpans, TextDisplay, TextFormat, TextSpan};
@@ -4,7 +4,7 @@ use crate::context::UpdateContext;
 use crate::drawing::Drawing;
 use crate::font::{DefaultFont, EvalParameters, Font, FontLike, FontSet, FontType};
 use crate::html::dimensions::{BoxBounds, Position, Size};
-use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
+use crate::html::text_format::{FormatSpans, TextFormat, TextImage, TextImageAlign, TextSpan};
 use crate::html::wrap_line;
 use crate::string::WStr;
 use crate::tag_utils::SwfMovie;
@@ -16,6 +16,7 @@ use std::ops::Range;
 use std::slice::Iter;
 use std::sync::Arc;
 use swf::{Rectangle, Twips};
+use unicode_bidi::{BidiInfo, Level};
 
 /// Contains information relating to the current layout operation.
 pub struct LayoutContext<'a, 'gc> {
@@ -99,6 +100,40 @@ pub struct LayoutContext<'a, 'gc> {
 
     /// The total width of the text field being laid out.
     max_bounds: Twips,
+
+    /// The images embedded in the text being laid out.
+    images: &'a [TextImage],
+
+    /// The size of each embedded image.
+    image_sizes: &'a [(Twips, Twips)],
+
+    /// The index of the next image to be placed.
+    next_image: usize,
+
+    /// The space taken by placed images, which text flows around.
+    floats: Vec<LayoutFloat>,
+
+    /// Layout boxes of placed images.
+    image_boxes: Vec<LayoutBox<'gc>>,
+
+    /// The bidi embedding level of each character of the text.
+    ///
+    /// This is empty when the whole text is left-to-right.
+    bidi_levels: Vec<Level>,
+}
+
+/// The space taken by an image floating on one side of the text.
+struct LayoutFloat {
+    align: TextImageAlign,
+
+    /// The top of the first line the image floats next to.
+    top: Twips,
+
+    /// The bottom of the image, including its vertical spacing.
+    bottom: Twips,
+
+    /// The width of the image, including its horizontal spacing.
+    width: Twips,
 }
 
 impl<'a, 'gc> LayoutContext<'a, 'gc> {
@@ -106,6 +141,8 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         movie: Arc<SwfMovie>,
         max_bounds: Twips,
         text: &'a WStr,
+        images: &'a [TextImage],
+        image_sizes: &'a [(Twips, Twips)],
         is_input: bool,
         is_word_wrap: bool,
         font_type: FontType,
@@ -131,10 +168,17 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             is_input,
             is_word_wrap,
             font_type,
+            images,
+            image_sizes,
+            next_image: 0,
+            floats: Vec::new(),
+            image_boxes: Vec::new(),
+            bidi_levels: resolve_bidi_levels(text, None),
         }
     }
 
     fn lay_out_spans(&mut self, context: &mut UpdateContext<'gc>, fs: &'a FormatSpans) {
+        self.place_images(0, Twips::ZERO);
         for (span_start, _end, span_text, span) in fs.iter_spans() {
             self.lay_out_span(context, span_start, span_text, span);
         }
@@ -297,6 +341,7 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             .first()
             .expect("each line must have at least one box");
         let is_line_empty = first_box.start() == end;
+        let visual_indices = self.reorder_line();
 
         let mut line_size_bounds = None;
         let mut box_count: i32 = 0;
@@ -318,9 +363,12 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
 
         let mut line_size_bounds = line_size_bounds.unwrap_or_default();
 
+        let (left_floats_width, right_floats_width) = self.floats_width(self.cursor.y());
         let left_adjustment =
-            Self::left_alignment_offset(&self.current_line_span, self.is_first_line);
-        let right_adjustment = Twips::from_pixels(self.current_line_span.right_margin);
+            Self::left_alignment_offset(&self.current_line_span, self.is_first_line)
+                + left_floats_width;
+        let right_adjustment =
+            Twips::from_pixels(self.current_line_span.right_margin) + right_floats_width;
 
         let misalignment =
             self.max_bounds - left_adjustment - right_adjustment - line_size_bounds.width();
@@ -347,11 +395,11 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
 
         let baseline_adjustment = self.max_ascent;
 
-        box_count = 0;
-        for layout_box in self.boxes.iter_mut() {
+        for (i, layout_box) in self.boxes.iter_mut().enumerate() {
             if layout_box.is_text_box() {
+                let visual_index = visual_indices.get(i).copied().unwrap_or(i as i32);
                 let position = Position::from((
-                    left_adjustment + align_adjustment + (interim_adjustment * box_count),
+                    left_adjustment + align_adjustment + (interim_adjustment * visual_index),
                     baseline_adjustment,
                 ));
                 layout_box.bounds += position;
@@ -359,8 +407,6 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
                 let position = Position::from((Twips::ZERO, baseline_adjustment));
                 layout_box.bounds += position;
             }
-
-            box_count += 1;
         }
 
         line_size_bounds +=
@@ -394,6 +440,7 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             .iter()
             .filter(|b| b.is_text_box())
             .fold(first_box.bounds, |bounds, b| bounds + b.bounds);
+        let (left_floats_width, right_floats_width) = self.floats_width(self.cursor.y());
 
         // Update last line's end position to take into account the delimiter.
         // It's easier to do it here, but maybe after some refactors this update
@@ -411,6 +458,7 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             ascent: self.max_ascent,
             descent: self.max_descent,
             leading: self.max_leading,
+            floats_width: left_floats_width + right_floats_width,
         });
         self.current_line_index += 1;
 
@@ -456,6 +504,9 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         self.is_first_line = end_of_para;
         self.has_line_break = true;
 
+        let next_line_start = if end_of_para { end + 1 } else { end };
+        self.place_images(next_line_start, self.cursor.y());
+
         let font_size = Twips::from_pixels(self.current_line_span.font.size);
         let metrics = self.font_set.unwrap().metrics();
         self.max_font_size = font_size;
@@ -510,140 +561,8 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         }
     }
 
-    fn resolve_font(&mut self, context: &mut UpdateContext<'gc>, span: &TextSpan) -> FontSet<'gc> {
-        fn new_empty_font<'gc>(
-            context: &mut UpdateContext<'gc>,
-            span: &TextSpan,
-            font_type: FontType,
-        ) -> FontSet<'gc> {
-            let font = Font::empty_font(
-                context.gc(),
-                &span.font.face.to_utf8_lossy(),
-                span.style.bold,
-                span.style.italic,
-                font_type,
-            );
-            FontSet::from_one_font(context.gc(), font)
-        }
-
-        fn describe_font(span: &TextSpan) -> String {
-            let bold_suffix = if span.style.bold { ", bold" } else { "" };
-            let italic_suffix = if span.style.italic { ", italic" } else { "" };
-            format!(
-                "{}{}{}",
-                span.font.face.to_utf8_lossy(),
-                bold_suffix,
-                italic_suffix
-            )
-        }
-
-        let font_name = span.font.face.to_utf8_lossy();
-
-        // Note that the SWF can still contain a DefineFont tag with no glyphs/layout info in this case (see #451).
-        // In an ideal world, device fonts would search for a matching font on the system and render it in some way.
-        if self.font_type.is_embedded()
-            && let Some(font) = context
-                .library
-                .get_embedded_font_by_name(
-                    &font_name,
-                    self.font_type,
-                    span.style.bold,
-                    span.style.italic,
-                    Some(self.movie.clone()),
-                )
-                .filter(|f| f.has_glyphs())
-        {
-            return FontSet::from_one_font(context.gc(), font);
-        }
-        // TODO: If set to use embedded fonts and we couldn't find any matching font, show nothing
-        // However - at time of writing, we don't support DefineFont4. If we matched this behaviour,
-        // then a bunch of SWFs would just show no text suddenly.
-        // return new_empty_font(context, span, self.font_type);
-
-        // Specifying multiple font names is supported only for device fonts.
-        let font_names: Vec<&str> = font_name.split(",").collect();
-        for font_name in &font_names {
-            let font_name = font_name.trim();
-
-            // Check if the font name is one of the known default fonts.
-            if let Some(default_font) = DefaultFont::from_name(font_name) {
-                let fonts = context.library.default_font(
-                    default_font,
-                    span.style.bold,
-                    span.style.italic,
-                    context.ui,
-                    context.renderer,
-                    context.gc_context,
-                );
-                if let Some(font_sort) = FontSet::from_fonts(context.gc(), &fonts) {
-                    return font_sort;
-                } else {
-                    let font_desc = describe_font(span);
-                    tracing::error!(
-                        "Known default device font not found: {font_desc}, text will be missing"
-                    );
-                    return new_empty_font(context, span, self.font_type);
-                }
-            }
-
-            let fonts = context.library.get_or_sort_device_fonts(
-                font_name,
-                span.style.bold,
-                span.style.italic,
-                context.ui,
-                context.renderer,
-                context.gc_context,
-            );
-            if let Some(font_sort) = FontSet::from_fonts(context.gc(), &fonts) {
-                return font_sort;
-            }
-        }
-
-        // TODO We fall back to the default font based on the first font in the list.
-        //   This is mainly to preserve old behavior, that might change when we
-        //   implement a proper fallback.
-        let font_name = font_names.first().copied().unwrap_or("");
-
-        // TODO: handle multiple fonts for a definition, each covering different sets of glyphs
-
-        // At this point, the font name was neither one of the default
-        // fonts nor matched any device font. We explicitly handle some of the
-        // well-known aliases for the default fonts for better compatibility
-        // with devices that don't have those fonts installed. As a last resort
-        // we fall back to using sans (like Flash).
-        let default_font = match font_name {
-            "Times New Roman" => DefaultFont::Serif,
-            "Arial" => DefaultFont::Sans,
-            "Consolas" => DefaultFont::Typewriter,
-            "Courier" => DefaultFont::Typewriter,
-            "Courier New" => DefaultFont::Typewriter,
-            "NSimSun" => DefaultFont::Typewriter,
-            _ => {
-                if font_name.contains("Ming") || font_name.contains('明') {
-                    DefaultFont::JapaneseMincho
-                } else {
-                    DefaultFont::Sans
-                }
-            }
-        };
-
-        let fonts = context.library.default_font(
-            default_font,
-            span.style.bold,
-            span.style.italic,
-            context.ui,
-            context.renderer,
-            context.gc_context,
-        );
-        if let Some(font_sort) = FontSet::from_fonts(context.gc(), &fonts) {
-            font_sort
-        } else {
-            let font_desc = describe_font(span);
-            tracing::error!(
-                "Fallback font not found ({default_font:?}) for: {font_desc}, text will be missing"
-            );
-            new_empty_font(context, span, self.font_type)
-        }
+    fn resolve_font(&self, context: &mut UpdateContext<'gc>, span: &TextSpan) -> FontSet<'gc> {
+        resolve_font(context, span, self.font_type, &self.movie)
     }
 
     /// Append text to the current line of the ongoing layout operation.
@@ -679,6 +598,38 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
     /// This function bypasses the text fragmentation necessary for justify to
     /// work, and it should only be called internally.
     fn append_text_fragment(&mut self, text: &'a WStr, start: usize, end: usize, span: &TextSpan) {
+        // Each box has a single direction, so fragments containing text
+        // of different directions are split into runs.
+        let mut run_start = start;
+        loop {
+            let bidi_level = self.bidi_level(run_start);
+            let run_end = (run_start..end)
+                .find(|&pos| self.bidi_level(pos) != bidi_level)
+                .unwrap_or(end);
+            self.append_text_run(
+                &text[run_start - start..run_end - start],
+                run_start,
+                run_end,
+                bidi_level,
+                span,
+            );
+
+            if run_end >= end {
+                break;
+            }
+            run_start = run_end;
+        }
+    }
+
+    /// Append a run of text with a single bidi level to the current line.
+    fn append_text_run(
+        &mut self,
+        text: &'a WStr,
+        start: usize,
+        end: usize,
+        bidi_level: Level,
+        span: &TextSpan,
+    ) {
         let font_set = self.font_set.expect("text fragment requires a font");
         let params = EvalParameters::from_span(span);
         let metrics = font_set.metrics();
@@ -686,7 +637,7 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         let descent = metrics.descent(params.height());
         let box_origin = self.cursor - (Twips::ZERO, ascent).into();
 
-        let mut new_box = LayoutBox::from_text(text, start, end, font_set, span);
+        let mut new_box = LayoutBox::from_text(text, start, end, font_set, span, bidi_level);
         let text_width = new_box.text_width();
         new_box.bounds = BoxBounds::from_position_and_size(
             box_origin,
@@ -708,7 +659,8 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
 
         bullet_cursor.set_x(
             Twips::from_pixels(18.0)
-                + Self::left_alignment_offset_without_bullet(span, self.is_first_line),
+                + Self::left_alignment_offset_without_bullet(span, self.is_first_line)
+                + self.floats_width(self.cursor.y()).0,
         );
 
         let params = EvalParameters::from_span(span);
@@ -778,22 +730,154 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
     ///
     /// Offsets returned by this function should not be considered final;
     fn wrap_dimensions(&self, current_span: &TextSpan) -> (Twips, Twips) {
-        let width = self.max_bounds - Twips::from_pixels(self.current_line_span.right_margin);
-        let offset = Self::left_alignment_offset(current_span, self.is_first_line);
+        let (left_floats_width, right_floats_width) = self.floats_width(self.cursor.y());
+        let width = self.max_bounds
+            - Twips::from_pixels(self.current_line_span.right_margin)
+            - right_floats_width;
+        let offset =
+            Self::left_alignment_offset(current_span, self.is_first_line) + left_floats_width;
 
         (width, offset + self.cursor.x())
     }
 
+    /// Get the bidi embedding level of the character at the given position.
+    fn bidi_level(&self, position: usize) -> Level {
+        self.bidi_levels
+            .get(position)
+            .copied()
+            .unwrap_or_else(Level::ltr)
+    }
+
+    /// Reorder text boxes of the current line from logical to visual order.
+    ///
+    /// The boxes are kept in logical order, only their positions change.
+    /// Returns the visual index of each box.
+    fn reorder_line(&mut self) -> Vec<i32> {
+        let mut visual_indices: Vec<i32> = (0..self.boxes.len() as i32).collect();
+        if self.bidi_levels.is_empty() {
+            return visual_indices;
+        }
+
+        let text_boxes: Vec<usize> = (0..self.boxes.len())
+            .filter(|&i| self.boxes[i].is_text_box())
+            .collect();
+        let levels: Vec<Level> = text_boxes
+            .iter()
+            .map(|&i| self.boxes[i].bidi_level())
+            .collect();
+        if levels.iter().all(|level| level.is_ltr()) {
+            return visual_indices;
+        }
+
+        // Each box takes the space up to the next box, in order to preserve tabs.
+        let advances: Vec<Twips> = text_boxes
+            .iter()
+            .enumerate()
+            .map(|(i, &box_index)| {
+                let bounds = self.boxes[box_index].bounds;
+                text_boxes.get(i + 1).map_or(bounds.width(), |&next_index| {
+                    self.boxes[next_index].bounds.offset_x() - bounds.offset_x()
+                })
+            })
+            .collect();
+
+        // TODO Trailing whitespace should be reset to the paragraph level (rule L1).
+        let Some(start_x) = text_boxes.first().map(|&i| self.boxes[i].bounds.offset_x()) else {
+            return visual_indices;
+        };
+        let (offsets, visual_order) = reorder_visually(start_x, &advances, &levels);
+        for (&box_index, &x) in text_boxes.iter().zip(&offsets) {
+            let bounds = &mut self.boxes[box_index].bounds;
+            *bounds += Position::from((x - bounds.offset_x(), Twips::ZERO));
+        }
+        for (visual_index, &logical_index) in visual_order.iter().enumerate() {
+            visual_indices[text_boxes[logical_index]] = text_boxes[visual_index] as i32;
+        }
+
+        visual_indices
+    }
+
+    /// Calculate the space taken by floating images on the left and right
+    /// side of a line starting at the given Y coordinate.
+    fn floats_width(&self, y: Twips) -> (Twips, Twips) {
+        let mut left = Twips::ZERO;
+        let mut right = Twips::ZERO;
+        for float in self.floats.iter().filter(|f| f.top <= y && y < f.bottom) {
+            match float.align {
+                TextImageAlign::Left => left += float.width,
+                TextImageAlign::Right => right += float.width,
+            }
+        }
+        (left, right)
+    }
+
+    /// Place all images anchored at or before the given position, so that
+    /// they float next to lines starting at the given Y coordinate.
+    fn place_images(&mut self, position: usize, y: Twips) {
+        while let Some(image) = self
+            .images
+            .get(self.next_image)
+            .filter(|image| image.position <= position)
+        {
+            let (width, height) = self
+                .image_sizes
+                .get(self.next_image)
+                .copied()
+                .unwrap_or_default();
+            let hspace = Twips::from_pixels(image.hspace);
+            let vspace = Twips::from_pixels(image.vspace);
+
+            let (left_floats_width, right_floats_width) = self.floats_width(y);
+            let x = match image.align {
+                TextImageAlign::Left => left_floats_width + hspace,
+                TextImageAlign::Right => self.max_bounds - right_floats_width - hspace - width,
+            };
+
+            let mut image_box = LayoutBox::from_image(image.position, self.next_image);
+            image_box.bounds = BoxBounds::from_position_and_size(
+                Position::from((x, y + vspace)),
+                Size::from((width, height)),
+            );
+            self.image_boxes.push(image_box);
+            self.floats.push(LayoutFloat {
+                align: image.align,
+                top: y,
+                bottom: y + height + vspace * 2,
+                width: width + hspace * 2,
+            });
+            self.next_image += 1;
+        }
+    }
+
     /// Destroy the layout context, returning the newly constructed layout list.
     fn end_layout(mut self, context: &mut UpdateContext<'gc>, fs: &'a FormatSpans) -> Layout<'gc> {
+        let end = fs.displayed_text().len();
         let last_span = fs.last_span().expect("At least one span should be present");
-        self.fixup_line(context, true, true, fs.displayed_text().len(), last_span);
+
+        // Images anchored in the last line float below it, unless it's empty.
+        if self
+            .boxes
+            .first()
+            .is_none_or(|first_box| first_box.start() == end)
+        {
+            self.place_images(end, self.cursor.y());
+        }
+        self.fixup_line(context, true, true, end, last_span);
+        let next_line_y =
+            self.cursor.y() + self.max_ascent + self.max_descent + self.line_leading_adjustment();
+        self.place_images(end, next_line_y);
+
+        for image_box in &self.image_boxes {
+            Self::extend_bounds(&mut self.bounds, image_box.bounds);
+            Self::extend_bounds(&mut self.text_size_bounds, image_box.bounds);
+        }
 
         let text_size = self.text_size_bounds.unwrap_or_default();
         Layout {
             bounds: self.bounds.unwrap_or_default(),
             text_size: Size::from((text_size.width(), text_size.height())),
             lines: self.lines,
+            images: self.image_boxes,
         }
     }
 
@@ -802,9 +886,209 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
     }
 }
 
+/// Find the fonts used to render the given span.
+pub(crate) fn resolve_font<'gc>(
+    context: &mut UpdateContext<'gc>,
+    span: &TextSpan,
+    font_type: FontType,
+    movie: &Arc<SwfMovie>,
+) -> FontSet<'gc> {
+    fn new_empty_font<'gc>(
+        context: &mut UpdateContext<'gc>,
+        span: &TextSpan,
+        font_type: FontType,
+    ) -> FontSet<'gc> {
+        let font = Font::empty_font(
+            context.gc(),
+            &span.font.face.to_utf8_lossy(),
+            span.style.bold,
+            span.style.italic,
+            font_type,
+        );
+        FontSet::from_one_font(context.gc(), font)
+    }
+
+    fn describe_font(span: &TextSpan) -> String {
+        let bold_suffix = if span.style.bold { ", bold" } else { "" };
+        let italic_suffix = if span.style.italic { ", italic" } else { "" };
+        format!(
+            "{}{}{}",
+            span.font.face.to_utf8_lossy(),
+            bold_suffix,
+            italic_suffix
+        )
+    }
+
+    let font_name = span.font.face.to_utf8_lossy();
+
+    // Note that the SWF can still contain a DefineFont tag with no glyphs/layout info in this case (see #451).
+    // In an ideal world, device fonts would search for a matching font on the system and render it in some way.
+    if font_type.is_embedded()
+        && let Some(font) = context
+            .library
+            .get_embedded_font_by_name(
+                &font_name,
+                font_type,
+                span.style.bold,
+                span.style.italic,
+                Some(movie.clone()),
+            )
+            .filter(|f| f.has_glyphs())
+    {
+        return FontSet::from_one_font(context.gc(), font);
+    }
+    // TODO: If set to use embedded fonts and we couldn't find any matching font, show nothing
+    // However - at time of writing, we don't support DefineFont4. If we matched this behaviour,
+    // then a bunch of SWFs would just show no text suddenly.
+    // return new_empty_font(context, span, font_type);
+
+    // Specifying multiple font names is supported only for device fonts.
+    let font_names: Vec<&str> = font_name.split(",").collect();
+    for font_name in &font_names {
+        let font_name = font_name.trim();
+
+        // Check if the font name is one of the known default fonts.
+        if let Some(default_font) = DefaultFont::from_name(font_name) {
+            let fonts = context.library.default_font(
+                default_font,
+                span.style.bold,
+                span.style.italic,
+                context.ui,
+                context.renderer,
+                context.gc_context,
+            );
+            if let Some(font_sort) = FontSet::from_fonts(context.gc(), &fonts) {
+                return font_sort;
+            } else {
+                let font_desc = describe_font(span);
+                tracing::error!(
+                    "Known default device font not found: {font_desc}, text will be missing"
+                );
+                return new_empty_font(context, span, font_type);
+            }
+        }
+
+        let fonts = context.library.get_or_sort_device_fonts(
+            font_name,
+            span.style.bold,
+            span.style.italic,
+            context.ui,
+            context.renderer,
+            context.gc_context,
+        );
+        if let Some(font_sort) = FontSet::from_fonts(context.gc(), &fonts) {
+            return font_sort;
+        }
+    }
+
+    // TODO We fall back to the default font based on the first font in the list.
+    //   This is mainly to preserve old behavior, that might change when we
+    //   implement a proper fallback.
+    let font_name = font_names.first().copied().unwrap_or("");
+
+    // TODO: handle multiple fonts for a definition, each covering different sets of glyphs
+
+    // At this point, the font name was neither one of the default
+    // fonts nor matched any device font. We explicitly handle some of the
+    // well-known aliases for the default fonts for better compatibility
+    // with devices that don't have those fonts installed. As a last resort
+    // we fall back to using sans (like Flash).
+    let default_font = match font_name {
+        "Times New Roman" => DefaultFont::Serif,
+        "Arial" => DefaultFont::Sans,
+        "Consolas" => DefaultFont::Typewriter,
+        "Courier" => DefaultFont::Typewriter,
+        "Courier New" => DefaultFont::Typewriter,
+        "NSimSun" => DefaultFont::Typewriter,
+        _ => {
+            if font_name.contains("Ming") || font_name.contains('明') {
+                DefaultFont::JapaneseMincho
+            } else {
+                DefaultFont::Sans
+            }
+        }
+    };
+
+    let fonts = context.library.default_font(
+        default_font,
+        span.style.bold,
+        span.style.italic,
+        context.ui,
+        context.renderer,
+        context.gc_context,
+    );
+    if let Some(font_sort) = FontSet::from_fonts(context.gc(), &fonts) {
+        font_sort
+    } else {
+        let font_desc = describe_font(span);
+        tracing::error!(
+            "Fallback font not found ({default_font:?}) for: {font_desc}, text will be missing"
+        );
+        new_empty_font(context, span, font_type)
+    }
+}
+
+/// Resolve the bidi embedding level of each character of the text.
+///
+/// The paragraph level is detected from the text unless specified. Returns an
+/// empty list when the whole text is at level 0.
+pub(crate) fn resolve_bidi_levels(text: &WStr, paragraph_level: Option<Level>) -> Vec<Level> {
+    // Right-to-left characters and bidi control characters are all above U+0590.
+    if paragraph_level.is_none_or(|level| level.is_ltr()) && text.iter().all(|c| c < 0x0590) {
+        return Vec::new();
+    }
+
+    // Map each character position to its UTF-8 offset.
+    let mut utf8_text = String::with_capacity(text.len());
+    let mut utf8_offsets = Vec::with_capacity(text.len());
+    for (pos, c) in text.char_indices() {
+        let previous_offset = utf8_offsets.last().copied().unwrap_or_default();
+        utf8_offsets.resize(pos, previous_offset);
+        utf8_offsets.push(utf8_text.len());
+        utf8_text.push(c.unwrap_or(char::REPLACEMENT_CHARACTER));
+    }
+    let previous_offset = utf8_offsets.last().copied().unwrap_or_default();
+    utf8_offsets.resize(text.len(), previous_offset);
+
+    let bidi_info = BidiInfo::new(&utf8_text, paragraph_level);
+    if bidi_info.levels.iter().all(|level| *level == Level::ltr()) {
+        return Vec::new();
+    }
+
+    utf8_offsets
+        .into_iter()
+        .map(|offset| bidi_info.levels[offset])
+        .collect()
+}
+
+/// Place runs of text from logical to visual order, starting at `start_x`.
+///
+/// Takes the advance and bidi level of each run in logical order. Returns
+/// the X offset of each run in logical order, along with the logical index
+/// of each run in visual order.
+pub(super) fn reorder_visually(
+    start_x: Twips,
+    advances: &[Twips],
+    levels: &[Level],
+) -> (Vec<Twips>, Vec<usize>) {
+    let visual_order = BidiInfo::reorder_visual(levels);
+    let mut offsets = vec![Twips::ZERO; advances.len()];
+    let mut x = start_x;
+    for &logical_index in &visual_order {
+        offsets[logical_index] = x;
+        x += advances[logical_index];
+    }
+    (offsets, visual_order)
+}
+
 /// Construct a new layout from text spans.
+///
+/// The `image_sizes` parameter contains the size of each image embedded in
+/// the text spans, in the same order.
+#[expect(clippy::too_many_arguments)]
 pub fn lower_from_text_spans<'gc>(
     fs: &FormatSpans,
+    image_sizes: &[(Twips, Twips)],
     context: &mut UpdateContext<'gc>,
     movie: Arc<SwfMovie>,
     requested_width: Option<Twips>,
@@ -818,6 +1102,7 @@ pub fn lower_from_text_spans<'gc>(
         // the second time to lay out text knowing the proper width.
         let layout = lower_from_text_spans_known_width(
             fs,
+            image_sizes,
             context,
             movie.clone(),
             Twips::ZERO,
@@ -825,15 +1110,24 @@ pub fn lower_from_text_spans<'gc>(
             false,
             font_type,
         );
-        let max_width = layout
+        let max_line_width = layout
             .lines()
             .iter()
-            .map(|line| line.bounds().width())
+            .map(|line| line.bounds().width() + line.floats_width)
             .max();
-        max_width.unwrap_or_default()
+        let max_image_width = layout
+            .images()
+            .iter()
+            .zip(fs.images())
+            .map(|(image_box, image)| {
+                image_box.bounds().width() + Twips::from_pixels(image.hspace) * 2
+            })
+            .max();
+        max_line_width.max(max_image_width).unwrap_or_default()
     });
     lower_from_text_spans_known_width(
         fs,
+        image_sizes,
         context,
         movie,
         requested_width,
@@ -843,8 +1137,10 @@ pub fn lower_from_text_spans<'gc>(
     )
 }
 
+#[expect(clippy::too_many_arguments)]
 fn lower_from_text_spans_known_width<'gc>(
     fs: &FormatSpans,
+    image_sizes: &[(Twips, Twips)],
     context: &mut UpdateContext<'gc>,
     movie: Arc<SwfMovie>,
     bounds: Twips,
@@ -856,6 +1152,8 @@ fn lower_from_text_spans_known_width<'gc>(
         movie,
         bounds,
         fs.displayed_text(),
+        fs.images(),
+        image_sizes,
         is_input,
         is_word_wrap,
         font_type,
@@ -878,6 +1176,9 @@ pub struct Layout<'gc> {
     text_size: Size<Twips>,
 
     lines: Vec<LayoutLine<'gc>>,
+
+    /// Layout boxes of images floating around the text.
+    images: Vec<LayoutBox<'gc>>,
 }
 
 impl<'gc> Layout<'gc> {
@@ -895,6 +1196,13 @@ impl<'gc> Layout<'gc> {
         &self.lines
     }
 
+    /// Layout boxes of images embedded in the text.
+    ///
+    /// Those boxes are not part of any line.
+    pub fn images(&self) -> &[LayoutBox<'gc>] {
+        &self.images
+    }
+
     pub fn boxes_iter(&self) -> LayoutBoxIter<'_, 'gc> {
         LayoutBoxIter {
             lines_iter: self.lines.iter(),
@@ -992,6 +1300,10 @@ pub struct LayoutLine<'gc> {
     #[collect(require_static)]
     leading: Twips,
 
+    /// The horizontal space taken by images floating next to this line.
+    #[collect(require_static)]
+    floats_width: Twips,
+
     /// Layout boxes contained within this line.
     boxes: Vec<LayoutBox<'gc>>,
 }
@@ -1103,7 +1415,7 @@ pub struct LayoutBox<'gc> {
     /// The width of those bounds is equal to the width of the glyphs inside,
     /// whereas the height is equal to the font height (ascent + descent).
     ///
-    /// TODO Currently, only text boxes have meaningful bounds.
+    /// TODO Currently, only text and image boxes have meaningful bounds.
     #[collect(require_static)]
     bounds: BoxBounds<Twips>,
 
@@ -1113,7 +1425,7 @@ pub struct LayoutBox<'gc> {
 
 /// Represents different content modes of a given `LayoutBox`.
 ///
-/// Currently, a `LayoutBox` can contain `Text`, `Bullet`s, or a `Drawing`.
+/// Currently, a `LayoutBox` can contain `Text`, `Bullet`s, a `Drawing`, or an `Image`.
 #[derive(Clone, Collect)]
 #[collect(no_drop)]
 pub enum LayoutContent<'gc> {
@@ -1161,6 +1473,12 @@ pub enum LayoutContent<'gc> {
 
         /// Whether this text should be underlined.
         underline: bool,
+
+        /// The bidi embedding level of the text.
+        ///
+        /// Odd levels are right-to-left.
+        #[collect(require_static)]
+        bidi_level: Level,
     },
 
     /// A layout box containing a bullet.
@@ -1200,6 +1518,18 @@ pub enum LayoutContent<'gc> {
         #[collect(require_static)]
         drawing: Drawing,
     },
+
+    /// A layout box containing an image embedded in HTML text.
+    ///
+    /// Image boxes float next to the text and are not part of any line.
+    Image {
+        /// The position in text the image is anchored at.
+        position: usize,
+
+        /// The index of the image within the `FormatSpans` that generated
+        /// this layout box.
+        index: usize,
+    },
 }
 
 impl Debug for LayoutContent<'_> {
@@ -1218,6 +1548,11 @@ impl Debug for LayoutContent<'_> {
                 .debug_struct("Drawing")
                 .field("position", position)
                 .finish(),
+            LayoutContent::Image { position, index } => f
+                .debug_struct("Image")
+                .field("position", position)
+                .field("index", index)
+                .finish(),
         }
     }
 }
@@ -1230,13 +1565,26 @@ impl<'gc> LayoutBox<'gc> {
         end: usize,
         font_set: FontSet<'gc>,
         span: &TextSpan,
+        bidi_level: Level,
     ) -> Self {
-        let params = EvalParameters::from_span(span);
+        let params = EvalParameters {
+            is_rtl: bidi_level.is_rtl(),
+            ..EvalParameters::from_span(span)
+        };
         let mut char_end_pos = Vec::with_capacity(end - start);
 
-        font_set.evaluate(text, Default::default(), params, |_, _, _, advance, x| {
-            char_end_pos.push(x + advance);
+        // Characters are evaluated in logical order, but their advances may
+        // be split between several glyphs.
+        let mut width = Twips::ZERO;
+        font_set.evaluate(text, Default::default(), params, |pos, _, _, advance, _| {
+            char_end_pos.resize(pos, width);
+            width += advance;
+            match char_end_pos.get_mut(pos) {
+                Some(end_pos) => *end_pos = width,
+                None => char_end_pos.push(width),
+            }
         });
+        char_end_pos.resize(text.len(), width);
 
         Self {
             bounds: Default::default(),
@@ -1249,6 +1597,7 @@ impl<'gc> LayoutBox<'gc> {
                 color: span.font.color,
                 char_end_pos,
                 underline: span.style.underline,
+                bidi_level,
             },
         }
     }
@@ -1281,6 +1630,14 @@ impl<'gc> LayoutBox<'gc> {
         }
     }
 
+    /// Construct an image.
+    pub fn from_image(position: usize, index: usize) -> Self {
+        Self {
+            bounds: Default::default(),
+            content: LayoutContent::Image { position, index },
+        }
+    }
+
     pub fn bounds(&self) -> BoxBounds<Twips> {
         self.bounds
     }
@@ -1344,7 +1701,7 @@ impl<'gc> LayoutBox<'gc> {
                 *params,
                 swf::Color::from_rgb(color.to_rgb(), 0xFF),
             )),
-            LayoutContent::Drawing { .. } => None,
+            LayoutContent::Drawing { .. } | LayoutContent::Image { .. } => None,
         }
     }
 
@@ -1354,6 +1711,7 @@ impl<'gc> LayoutBox<'gc> {
             LayoutContent::Text { .. } => None,
             LayoutContent::Bullet { .. } => None,
             LayoutContent::Drawing { drawing, .. } => Some(drawing),
+            LayoutContent::Image { .. } => None,
         }
     }
 
@@ -1361,6 +1719,14 @@ impl<'gc> LayoutBox<'gc> {
         matches!(&self.content, LayoutContent::Text { .. })
     }
 
+    /// Returns the bidi embedding level of this box.
+    pub fn bidi_level(&self) -> Level {
+        match &self.content {
+            LayoutContent::Text { bidi_level, .. } => *bidi_level,
+            _ => Level::ltr(),
+        }
+    }
+
     pub fn is_bullet(&self) -> bool {
         matches!(&self.content, LayoutContent::Bullet { .. })
     }
@@ -1370,6 +1736,7 @@ impl<'gc> LayoutBox<'gc> {
             LayoutContent::Text { start, .. } => *start,
             LayoutContent::Bullet { position, .. } => *position,
             LayoutContent::Drawing { position, .. } => *position,
+            LayoutContent::Image { position, .. } => *position,
         }
     }
 
@@ -1378,6 +1745,7 @@ impl<'gc> LayoutBox<'gc> {
             LayoutContent::Text { end, .. } => *end,
             LayoutContent::Bullet { position, .. } => *position,
             LayoutContent::Drawing { position, .. } => *position,
+            LayoutContent::Image { position, .. } => *position,
         }
     }
 
@@ -1404,13 +1772,20 @@ impl<'gc> LayoutBox<'gc> {
 
         let origin_x = self.bounds().origin().x();
 
-        Some(if relative_position == 0 {
-            (origin_x, origin_x + *char_end_pos.get(0)?)
+        let (start, end) = if relative_position == 0 {
+            (Twips::ZERO, *char_end_pos.get(0)?)
         } else {
             (
-                origin_x + *char_end_pos.get(relative_position - 1)?,
-                origin_x + *char_end_pos.get(relative_position)?,
+                *char_end_pos.get(relative_position - 1)?,
+                *char_end_pos.get(relative_position)?,
             )
+        };
+
+        Some(if self.bidi_level().is_rtl() {
+            let width = self.text_width();
+            (origin_x + width - end, origin_x + width - start)
+        } else {
+            (origin_x + start, origin_x + end)
         })
     }
 }
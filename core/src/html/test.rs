@@ -1,9 +1,11 @@
 //! Tests for HTML module
 
 use crate::html::dimensions::{BoxBounds, Position, Size};
-use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
+use crate::html::layout::{reorder_visually, resolve_bidi_levels};
+use crate::html::text_format::{FormatSpans, TextFormat, TextImageAlign, TextSpan};
 use crate::string::{WStr, WString};
 use swf::{Rectangle, Twips};
+use unicode_bidi::Level;
 
 #[test]
 fn position_add() {
@@ -868,3 +870,129 @@ fn formatspans_replace_text_degenerate() {
     assert_eq!((0, 1), fs.get_span_boundaries(0, 5));
     assert_eq!((1, 2), fs.get_span_boundaries(5, 9));
 }
+
+#[test]
+fn formatspans_from_html_img() {
+    let fs = FormatSpans::from_html(
+        WStr::from_units(
+            br#"<p>ab<img src="pic.png" id="pic" width="20" align="right" hspace="2">cd</p>"#,
+        ),
+        Default::default(),
+        None,
+        true,
+        false,
+        10,
+    );
+
+    assert_eq!(WStr::from_units(b"abcd\r"), fs.text());
+
+    let images = fs.images();
+    assert_eq!(1, images.len());
+    assert_eq!(2, images[0].position);
+    assert_eq!(WStr::from_units(b"pic.png"), images[0].src);
+    assert_eq!(WStr::from_units(b"pic"), images[0].id);
+    assert_eq!(Some(20.0), images[0].width);
+    assert_eq!(None, images[0].height);
+    assert_eq!(TextImageAlign::Right, images[0].align);
+    assert_eq!(2.0, images[0].hspace);
+    assert_eq!(8.0, images[0].vspace);
+}
+
+#[test]
+fn formatspans_replace_text_images() {
+    let mut fs = FormatSpans::from_html(
+        WStr::from_units(br#"a<img src="1">bcd<img src="2">e<img src="3">"#),
+        Default::default(),
+        None,
+        true,
+        false,
+        10,
+    );
+
+    assert_eq!(WStr::from_units(b"abcde"), fs.text());
+    let positions = |fs: &FormatSpans| fs.images().iter().map(|i| i.position).collect::<Vec<_>>();
+    assert_eq!(vec![1, 4, 5], positions(&fs));
+
+    // Images anchored within the replaced text are removed.
+    fs.replace_text(3, 5, WStr::from_units(b"123"));
+    assert_eq!(WStr::from_units(b"abc123"), fs.text());
+    assert_eq!(vec![1, 6], positions(&fs));
+
+    // Images anchored at the start of the replaced text stay in place.
+    fs.replace_text(1, 1, WStr::from_units(b"xy"));
+    assert_eq!(WStr::from_units(b"axybc123"), fs.text());
+    assert_eq!(vec![1, 8], positions(&fs));
+}
+
+#[test]
+fn resolve_bidi_levels_ltr() {
+    let text = WString::from_utf8("abc def");
+    assert!(resolve_bidi_levels(&text, None).is_empty());
+    assert!(resolve_bidi_levels(&text, Some(Level::ltr())).is_empty());
+    assert_eq!(
+        Level::vec(&[2, 2, 2, 2, 2, 2, 2]),
+        resolve_bidi_levels(&text, Some(Level::rtl()))
+    );
+}
+
+#[test]
+fn resolve_bidi_levels_mixed() {
+    // The paragraph level is detected from the first strong character.
+    let text = WString::from_utf8("ab \u{5D0}\u{5D1}");
+    assert_eq!(
+        Level::vec(&[0, 0, 0, 1, 1]),
+        resolve_bidi_levels(&text, None)
+    );
+
+    let text = WString::from_utf8("\u{5D0}\u{5D1} ab");
+    assert_eq!(
+        Level::vec(&[1, 1, 1, 2, 2]),
+        resolve_bidi_levels(&text, None)
+    );
+    assert_eq!(
+        Level::vec(&[1, 1, 0, 0, 0]),
+        resolve_bidi_levels(&text, Some(Level::ltr()))
+    );
+}
+
+#[test]
+fn resolve_bidi_levels_paragraphs_and_surrogates() {
+    // Both code units of a surrogate pair get the level of the character.
+    let text = WString::from_utf8("a\u{1F600}\u{5D0}");
+    assert_eq!(4, text.len());
+    assert_eq!(Level::vec(&[0, 0, 0, 1]), resolve_bidi_levels(&text, None));
+
+    // Each paragraph detects its own level.
+    let text = WString::from_utf8("\u{5D0}\rab");
+    assert_eq!(Level::vec(&[1, 1, 0, 0]), resolve_bidi_levels(&text, None));
+}
+
+#[test]
+fn reorder_visually_ltr() {
+    let advances = [Twips::new(10), Twips::new(20)];
+    let (offsets, visual_order) = reorder_visually(Twips::new(5), &advances, &Level::vec(&[0, 0]));
+    assert_eq!(vec![Twips::new(5), Twips::new(15)], offsets);
+    assert_eq!(vec![0, 1], visual_order);
+}
+
+#[test]
+fn reorder_visually_rtl_runs() {
+    // Adjacent right-to-left runs are swapped, the left-to-right run stays.
+    let advances = [Twips::new(10), Twips::new(20), Twips::new(30)];
+    let (offsets, visual_order) = reorder_visually(Twips::ZERO, &advances, &Level::vec(&[0, 1, 1]));
+    assert_eq!(vec![Twips::new(0), Twips::new(40), Twips::new(10)], offsets);
+    assert_eq!(vec![0, 2, 1], visual_order);
+}
+
+#[test]
+fn reorder_visually_nested() {
+    // Left-to-right runs embedded in a right-to-left paragraph keep their order.
+    let advances = [Twips::new(1), Twips::new(2), Twips::new(3), Twips::new(4)];
+    let (offsets, visual_order) =
+        reorder_visually(Twips::ZERO, &advances, &Level::vec(&[1, 2, 2, 1]));
+    assert_eq!(
+        vec![Twips::new(9), Twips::new(4), Twips::new(6), Twips::new(0)],
+        offsets
+    );
+    assert_eq!(vec![3, 1, 2, 0], visual_order);
+}
@@ -638,6 +638,66 @@ impl TextSpan {
     }
 }
 
+/// The side of the text an image floats on.
+#[derive(Default, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TextImageAlign {
+    #[default]
+    Left,
+    Right,
+}
+
+/// An image embedded in HTML text using an `<img>` tag.
+///
+/// Images do not occupy any characters of the text. Instead, they are
+/// anchored at a position in the text and float on one side of the lines
+/// following that position, with text flowing around them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextImage {
+    /// The position in the text this image is anchored at.
+    pub position: usize,
+
+    /// The URL or library symbol to display.
+    pub src: WString,
+
+    /// The identifier used to access the image from ActionScript.
+    pub id: WString,
+
+    /// The width of the image, or `None` to use the width of its content.
+    pub width: Option<f64>,
+
+    /// The height of the image, or `None` to use the height of its content.
+    pub height: Option<f64>,
+
+    pub align: TextImageAlign,
+
+    /// The horizontal space around the image where no text appears.
+    pub hspace: f64,
+
+    /// The vertical space around the image where no text appears.
+    pub vspace: f64,
+
+    pub check_policy_file: bool,
+}
+
+impl TextImage {
+    /// The space Flash Player leaves around images by default, in pixels.
+    const DEFAULT_SPACE: f64 = 8.0;
+
+    fn new(position: usize, src: WString) -> Self {
+        Self {
+            position,
+            src,
+            id: WString::new(),
+            width: None,
+            height: None,
+            align: TextImageAlign::Left,
+            hspace: Self::DEFAULT_SPACE,
+            vspace: Self::DEFAULT_SPACE,
+            check_policy_file: false,
+        }
+    }
+}
+
 /// Struct which contains text formatted by `TextSpan`s.
 #[derive(Clone, Debug)]
 pub struct FormatSpans {
@@ -645,6 +705,9 @@ pub struct FormatSpans {
     displayed_text: WString,
     spans: Vec<TextSpan>,
     default_format: TextFormat,
+
+    /// Images embedded in the text, ordered by their position.
+    images: Vec<TextImage>,
 }
 
 impl Default for FormatSpans {
@@ -660,6 +723,7 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans: vec![TextSpan::default()],
             default_format: TextFormat::default(),
+            images: Vec::new(),
         }
     }
 
@@ -670,6 +734,7 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans: spans.to_vec(),
             default_format: Default::default(),
+            images: Vec::new(),
         }
     }
 
@@ -680,6 +745,7 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans: vec![TextSpan::with_length_and_format(len, &format)],
             default_format: format,
+            images: Vec::new(),
         }
     }
 
@@ -710,6 +776,7 @@ impl FormatSpans {
         let mut format_stack = vec![default_format.clone()];
         let mut text = WString::new();
         let mut spans: Vec<TextSpan> = Vec::new();
+        let mut images: Vec<TextImage> = Vec::new();
 
         // quick_xml::Reader requires a [u8] slice, but doesn't actually care about Unicode;
         // this means we can pass the raw buffer in the Latin1 case.
@@ -811,6 +878,44 @@ impl FormatSpans {
                             // Skip push to `format_stack`.
                             continue;
                         }
+                        b"img" => {
+                            // Images without a source are ignored.
+                            if let Some(src) = attribute(b"src") {
+                                let mut image = TextImage::new(text.len(), src);
+                                if let Some(id) = attribute(b"id") {
+                                    image.id = id;
+                                }
+                                if let Some(width) = attribute(b"width") {
+                                    image.width = width.parse().ok();
+                                }
+                                if let Some(height) = attribute(b"height") {
+                                    image.height = height.parse().ok();
+                                }
+                                if let Some(align) = attribute(b"align")
+                                    && align.eq_ignore_case(WStr::from_units(b"right"))
+                                {
+                                    image.align = TextImageAlign::Right;
+                                }
+                                if let Some(hspace) = attribute(b"hspace")
+                                    && let Ok(hspace) = hspace.parse()
+                                {
+                                    image.hspace = hspace;
+                                }
+                                if let Some(vspace) = attribute(b"vspace")
+                                    && let Ok(vspace) = vspace.parse()
+                                {
+                                    image.vspace = vspace;
+                                }
+                                if let Some(check_policy_file) = attribute(b"checkPolicyFile") {
+                                    image.check_policy_file =
+                                        check_policy_file.eq_ignore_case(WStr::from_units(b"true"));
+                                }
+                                images.push(image);
+                            }
+
+                            // Skip push to `format_stack`.
+                            continue;
+                        }
                         b"sbr" => {
                             // TODO: <sbr> tags do not add a newline, but rather only break
                             // the format span.
@@ -1052,7 +1157,7 @@ impl FormatSpans {
                     }
 
                     match tag_name {
-                        b"br" | b"sbr" => {
+                        b"br" | b"sbr" | b"img" => {
                             // Skip pop from `format_stack`.
                             continue;
                         }
@@ -1112,6 +1217,7 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans,
             default_format,
+            images,
         };
         if condense_white && swf_version >= 8 {
             ret.condense_white_swf8();
@@ -1167,6 +1273,11 @@ impl FormatSpans {
         &self.text
     }
 
+    /// Retrieve the images embedded in the text, ordered by their position.
+    pub fn images(&self) -> &[TextImage] {
+        &self.images
+    }
+
     pub fn displayed_text(&self) -> &WStr {
         if self.has_displayed_text() {
             &self.displayed_text
@@ -1453,7 +1564,8 @@ impl FormatSpans {
             return;
         }
 
-        if from < self.text.len() {
+        let old_len = self.text.len();
+        if from < old_len {
             self.ensure_span_break_at(from);
             self.ensure_span_break_at(to);
 
@@ -1492,6 +1604,19 @@ impl FormatSpans {
 
         self.text = new_string;
 
+        // Images anchored within the replaced text are removed, and the ones
+        // anchored after it move along with the text.
+        let removed = from.min(old_len)..to.min(old_len);
+        self.images.retain_mut(|image| {
+            if removed.start < image.position && image.position < removed.end {
+                return false;
+            }
+            if image.position > removed.start && image.position >= removed.end {
+                image.position = image.position - removed.len() + with.len();
+            }
+            true
+        });
+
         self.normalize();
     }
 
@@ -1510,7 +1635,7 @@ impl FormatSpans {
     }
 
     pub fn to_html(&self) -> WString {
-        if self.text.is_empty() {
+        if self.text.is_empty() && self.images.is_empty() {
             return WString::new();
         }
 
@@ -1522,10 +1647,28 @@ impl FormatSpans {
         };
 
         let spans = self.iter_spans();
-
-        for (_start, _end, text, span) in spans {
+        let mut images = self.images.iter().peekable();
+
+        for (start, end, text, span) in spans {
+            let mut pos = start;
+            while let Some(image) = images.next_if(|image| image.position < end) {
+                let image_pos = image.position.max(pos);
+                state.set_span(span);
+                state.push_text(&text[pos - start..image_pos - start]);
+                state.set_span(span);
+                state.push_image(image);
+                pos = image_pos;
+            }
             state.set_span(span);
-            state.push_text(text);
+            state.push_text(&text[pos - start..]);
+        }
+
+        // Images anchored at the end of the text.
+        if let Some(span) = self.last_span() {
+            for image in images {
+                state.set_span(span);
+                state.push_image(image);
+            }
         }
 
         state.close_all_tags();
@@ -1843,6 +1986,34 @@ impl<'a> FormatState<'a> {
         }
     }
 
+    fn push_image(&mut self, image: &TextImage) {
+        let _ = write!(self.result, "<IMG SRC=\"{}\"", image.src);
+        if let Some(width) = image.width {
+            let _ = write!(self.result, " WIDTH=\"{width}\"");
+        }
+        if let Some(height) = image.height {
+            let _ = write!(self.result, " HEIGHT=\"{height}\"");
+        }
+        let _ = write!(
+            self.result,
+            " ALIGN=\"{}\" HSPACE=\"{}\" VSPACE=\"{}\"",
+            match image.align {
+                TextImageAlign::Left => "left",
+                TextImageAlign::Right => "right",
+            },
+            image.hspace,
+            image.vspace,
+        );
+        if !image.id.is_empty() {
+            let _ = write!(self.result, " ID=\"{}\"", image.id);
+        }
+        if image.check_policy_file {
+            self.result
+                .push_str(WStr::from_units(b" CHECKPOLICYFILE=\"true\""));
+        }
+        self.result.push_byte(b'>');
+    }
+
     fn push_line(&mut self, line: &WStr) {
         if line.is_empty() {
             return;
@@ -133,7 +133,7 @@ impl InputManager {
         }
     }
 
-    pub fn process_event(&mut self, event: PlayerEvent) -> Option<InputEvent> {
+    pub fn process_event(&mut self, event: PlayerEvent, now: DateTime<Utc>) -> Option<InputEvent> {
         let event = match event {
             // Optionally transform gamepad button events into key events.
             PlayerEvent::GamepadButtonDown { button } => {
@@ -217,7 +217,7 @@ impl InputManager {
                 x,
                 y,
                 button,
-                index: self.update_last_click(x, y, index),
+                index: self.update_last_click(x, y, index, now),
             },
             PlayerEvent::MouseLeave => InputEvent::MouseLeave,
             PlayerEvent::MouseWheel { delta } => InputEvent::MouseWheel { delta },
@@ -280,8 +280,13 @@ impl InputManager {
         }
     }
 
-    fn update_last_click(&mut self, x: f64, y: f64, index: Option<usize>) -> usize {
-        let time = Utc::now();
+    fn update_last_click(
+        &mut self,
+        x: f64,
+        y: f64,
+        index: Option<usize>,
+        time: DateTime<Utc>,
+    ) -> usize {
         let index = index.unwrap_or_else(|| {
             let Some(last_click) = self.last_click.as_ref() else {
                 return 0;
@@ -596,45 +601,60 @@ mod tests {
         // Spurious KeyUp for 'a' - no preceding KeyDown
         assert!(
             input
-                .process_event(PlayerEvent::KeyUp {
-                    key: key('a', PhysicalKey::KeyA)
-                })
+                .process_event(
+                    PlayerEvent::KeyUp {
+                        key: key('a', PhysicalKey::KeyA)
+                    },
+                    DateTime::UNIX_EPOCH
+                )
                 .is_none()
         );
 
         // Normal KeyDown 'w'
         assert!(
             input
-                .process_event(PlayerEvent::KeyDown {
-                    key: key('w', PhysicalKey::KeyW)
-                })
+                .process_event(
+                    PlayerEvent::KeyDown {
+                        key: key('w', PhysicalKey::KeyW)
+                    },
+                    DateTime::UNIX_EPOCH
+                )
                 .is_some()
         );
 
         // Spurious KeyUp for 'b' - different key, never pressed
         assert!(
             input
-                .process_event(PlayerEvent::KeyUp {
-                    key: key('b', PhysicalKey::KeyB)
-                })
+                .process_event(
+                    PlayerEvent::KeyUp {
+                        key: key('b', PhysicalKey::KeyB)
+                    },
+                    DateTime::UNIX_EPOCH
+                )
                 .is_none()
         );
 
         // Valid KeyUp for 'w'
         assert!(
             input
-                .process_event(PlayerEvent::KeyUp {
-                    key: key('w', PhysicalKey::KeyW)
-                })
+                .process_event(
+                    PlayerEvent::KeyUp {
+                        key: key('w', PhysicalKey::KeyW)
+                    },
+                    DateTime::UNIX_EPOCH
+                )
                 .is_some()
         );
 
         // Duplicate KeyUp for 'w' - already released
         assert!(
             input
-                .process_event(PlayerEvent::KeyUp {
-                    key: key('w', PhysicalKey::KeyW)
-                })
+                .process_event(
+                    PlayerEvent::KeyUp {
+                        key: key('w', PhysicalKey::KeyW)
+                    },
+                    DateTime::UNIX_EPOCH
+                )
                 .is_none()
         );
     }
@@ -15,6 +15,7 @@ extern crate num_derive;
 #[macro_use]
 mod avm1;
 mod avm2;
+pub use avm2::{Avm2Debugger, PauseReason, StepKind};
 mod avm_rng;
 mod binary_data;
 pub mod bitmap;
@@ -28,6 +29,7 @@ pub mod focus_tracker;
 pub mod font;
 mod frame_lifecycle;
 pub mod fte;
+mod globalization;
 mod html;
 mod input;
 mod library;
@@ -40,6 +42,8 @@ mod orphan_manager;
 pub mod pixel_bender;
 mod player;
 mod prelude;
+pub mod save_state;
+mod security;
 pub mod socket;
 mod streams;
 pub mod string;
@@ -127,6 +127,10 @@ pub struct MovieLibrary<'gc> {
     jpeg_tables: Option<Vec<u8>>,
     fonts: FontMap<'gc>,
     avm2_domain: Option<Avm2Domain<'gc>>,
+
+    /// Whether the movie has an `EnableDebugger` tag, and so should keep its
+    /// debugging information.
+    debugger_enabled: bool,
 }
 
 impl<'gc> MovieLibrary<'gc> {
@@ -139,6 +143,7 @@ impl<'gc> MovieLibrary<'gc> {
             jpeg_tables: None,
             fonts: Default::default(),
             avm2_domain: None,
+            debugger_enabled: false,
         }
     }
 
@@ -353,6 +358,14 @@ impl<'gc> MovieLibrary<'gc> {
         self.jpeg_tables.as_ref().map(|data| &data[..])
     }
 
+    pub fn set_debugger_enabled(&mut self) {
+        self.debugger_enabled = true;
+    }
+
+    pub fn debugger_enabled(&self) -> bool {
+        self.debugger_enabled
+    }
+
     pub fn set_avm2_domain(&mut self, avm2_domain: Avm2Domain<'gc>) {
         self.avm2_domain = Some(avm2_domain);
     }
@@ -426,6 +439,10 @@ impl<'gc> MovieLibraries<'gc> {
             .or_insert_with(|| MovieLibrary::new(movie))
     }
 
+    fn remove(&mut self, key: &Arc<SwfMovie>) -> Option<MovieLibrary<'gc>> {
+        self.0.remove(key)
+    }
+
     fn known_movies(&self) -> impl Iterator<Item = Arc<SwfMovie>> {
         self.0.keys()
     }
@@ -486,6 +503,13 @@ impl<'gc> Library<'gc> {
         self.movie_libraries.get_or_insert_mut(movie)
     }
 
+    /// Frees the library of a movie that is being unloaded.
+    ///
+    /// Returns the removed library, if the movie had one.
+    pub fn remove_library_for_movie(&mut self, movie: &Arc<SwfMovie>) -> Option<MovieLibrary<'gc>> {
+        self.movie_libraries.remove(movie)
+    }
+
     pub fn known_movies(&self) -> impl Iterator<Item = Arc<SwfMovie>> {
         self.movie_libraries.known_movies()
     }
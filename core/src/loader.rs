@@ -6,6 +6,8 @@ use crate::avm1::{ExecutionReason, NativeObject};
 use crate::avm1::{Object, ObjectHandle, Value};
 use crate::avm2::bytearray::ByteArrayStorage;
 use crate::avm2::globals::flash::utils::byte_array::strip_bom;
+use crate::avm2::globals::slots::flash_events_event_dispatcher as dispatcher_slots;
+use crate::avm2::globals::slots::flash_system_loader_context as loader_context_slots;
 use crate::avm2::object::{
     ByteArrayObject, EventObject as Avm2EventObject, FileReferenceObject,
     FileReferenceObjectHandle, LoaderInfoObject, LoaderStream, ScriptObject as Avm2ScriptObject,
@@ -25,6 +27,7 @@ use crate::backend::ui::{
 };
 use crate::bitmap::bitmap_data::BitmapData;
 use crate::bitmap::bitmap_data::Color;
+use crate::character::Character;
 use crate::context::{ActionQueue, ActionType, UpdateContext};
 use crate::display_object::{
     DisplayObject, MovieClip, MovieClipHandle, TDisplayObject, TDisplayObjectContainer,
@@ -33,10 +36,12 @@ use crate::display_object::{
 use crate::events::ClipEvent;
 use crate::limits::ExecutionLimit;
 use crate::player::{Player, PostFrameCallback};
+use crate::security::{self, PolicyFile, PolicyFileState};
 use crate::streams::{NetStream, NetStreamHandle};
 use crate::string::{AvmString, StringContext};
 use crate::tag_utils::SwfMovie;
 use crate::vminterface::Instantiator;
+use async_channel::Receiver;
 use chardetng::EncodingDetector;
 use encoding_rs::{UTF_8, WINDOWS_1252};
 use gc_arena::Collect;
@@ -243,12 +248,59 @@ impl<'gc> LoadManager<'gc> {
         handle
     }
 
+    /// Whether there are no loads in progress.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether any movie is still being downloaded or parsed.
+    pub fn has_pending_loads(&self) -> bool {
+        self.0.values().any(|loader| {
+            matches!(
+                loader.loader_status,
+                LoaderStatus::Pending | LoaderStatus::Parsing
+            )
+        })
+    }
+
+    /// Whether a movie is still being downloaded or parsed into `target`.
+    ///
+    /// For AVM2 loads, `target` is the `Loader` rather than its content.
+    pub fn is_loading_into(&self, target: DisplayObject<'gc>) -> bool {
+        self.0.values().any(|loader| {
+            let loads_into_target = match loader.vm_data {
+                MovieLoaderVMData::Avm1 { .. } => DisplayObject::ptr_eq(loader.target_clip, target),
+                MovieLoaderVMData::Avm2 { loader_info, .. } => loader_info
+                    .loader()
+                    .is_some_and(|l| DisplayObject::ptr_eq(l.display_object(), target)),
+            };
+            loads_into_target
+                && matches!(
+                    loader.loader_status,
+                    LoaderStatus::Pending | LoaderStatus::Parsing
+                )
+        })
+    }
+
     /// Remove a completed loader.
     /// This is used to remove a loader after the loading or unloading process has completed.
     pub fn remove_loader(&mut self, handle: LoaderHandle) {
         self.0.remove(handle);
     }
 
+    /// Cancel every in-progress load into the `Loader` owning `loader_info`.
+    ///
+    /// The futures driving these loads will notice that their loader is gone
+    /// and stop without touching the `Loader` again.
+    pub fn cancel_avm2_loads(&mut self, loader_info: LoaderInfoObject<'gc>) {
+        self.0.retain(|_, loader| match loader.vm_data {
+            MovieLoaderVMData::Avm2 {
+                loader_info: other, ..
+            } => !Avm2Object::ptr_eq(loader_info, other),
+            _ => true,
+        });
+    }
+
     /// Retrieve a loader by handle.
     pub fn get_loader(&self, handle: LoaderHandle) -> Option<&MovieLoader<'gc>> {
         self.0.get(handle)
@@ -744,6 +796,20 @@ impl<'gc> MovieLoader<'gc> {
             })?;
 
             let response = wait_for_full_response(fetch).await;
+
+            // Content loaded with `checkPolicyFile` set only completes once the
+            // policy file of the server it was served from is resolved.
+            if let Ok((_, url, _, _)) = &response {
+                let policy_file = player
+                    .lock()
+                    .unwrap()
+                    .update(|uc| MovieLoader::check_policy_file(handle, uc, url));
+                if let Some(policy_file) = policy_file {
+                    // Nothing is sent: the channel is closed once the policy file is resolved.
+                    let _ = policy_file.recv().await;
+                }
+            }
+
             let player = player.lock().unwrap();
             match response {
                 Ok((body, url, status, redirected)) if replacing_root_movie => {
@@ -763,6 +829,43 @@ impl<'gc> MovieLoader<'gc> {
         })
     }
 
+    /// Requests the master policy file of the server that served the
+    /// content of the given loader from `url`, if the loader was given a
+    /// `LoaderContext` with `checkPolicyFile` set.
+    ///
+    /// Returns a receiver that is closed once the policy file is resolved,
+    /// or `None` if there's nothing to wait for.
+    fn check_policy_file(
+        handle: LoaderHandle,
+        uc: &mut UpdateContext<'gc>,
+        url: &str,
+    ) -> Option<Receiver<()>> {
+        let context = match uc.load_manager.get_loader(handle) {
+            Some(MovieLoader {
+                vm_data:
+                    MovieLoaderVMData::Avm2 {
+                        context: Some(context),
+                        ..
+                    },
+                ..
+            }) => *context,
+            _ => return None,
+        };
+        if !context
+            .get_slot(loader_context_slots::CHECK_POLICY_FILE)
+            .coerce_to_boolean()
+        {
+            return None;
+        }
+
+        // Policy files are checked against the final URL of the content, as
+        // a redirect may lead to another server.
+        let url = Url::parse(url).ok()?;
+        let policy_url = uc.security.check_policy_file(&url)?;
+        security::load_policy_file(uc, policy_url.clone());
+        uc.security.wait_for_policy_file(policy_url.as_str())
+    }
+
     fn on_success_root_movie(
         mut player: MutexGuard<'_, Player>,
         handle: LoaderHandle,
@@ -915,6 +1018,67 @@ impl<'gc> MovieLoader<'gc> {
     }
 }
 
+/// Unloads the content of the `Loader` owning `loader_info`.
+///
+/// This removes the loaded content and fires `unload` on the `LoaderInfo` if
+/// content had been loaded. Loads still in progress are left alone; use
+/// `LoadManager::cancel_avm2_loads` to cancel them.
+///
+/// If `stop` is set, as with `Loader.unloadAndStop`, the content is torn down
+/// as well: its timelines, sounds, streams and timers are stopped, the
+/// listeners it registered on the stage are removed and its library is freed.
+/// Otherwise, the content is left intact so that scripts still referencing it
+/// keep working, and its library is freed once the movie is dropped.
+pub fn unload_avm2_loader<'gc>(
+    context: &mut UpdateContext<'gc>,
+    loader_info: LoaderInfoObject<'gc>,
+    stop: bool,
+) {
+    let had_content = loader_info.init_event_fired();
+    let content_movie = match &*loader_info.loader_stream() {
+        LoaderStream::Swf(movie, _) if had_content => Some(movie.clone()),
+        _ => None,
+    };
+
+    let loader_display = loader_info
+        .loader()
+        .and_then(|loader| loader.display_object().as_loader_display());
+
+    if stop && let Some(loader_display) = loader_display {
+        loader_display.stop_content(context);
+    }
+
+    loader_info.unload(context);
+
+    if stop && let Some(movie) = &content_movie {
+        context.timers.remove_for_movie(movie);
+
+        let stage_dispatch_list = context.stage.object2().and_then(|stage| {
+            Avm2Object::from(stage)
+                .get_slot(dispatcher_slots::DISPATCH_LIST)
+                .as_object()
+        });
+        if let Some(dispatch_list) = stage_dispatch_list
+            && let Some(mut dispatch_list) = dispatch_list.as_dispatch_mut(context.gc())
+        {
+            dispatch_list.remove_listeners_for_movie(movie);
+        }
+
+        if let Some(library) = context.library.remove_library_for_movie(movie) {
+            for character in library.characters().values() {
+                if let Character::Sound(sound) = character {
+                    context.stop_sounds_with_handle(*sound);
+                }
+            }
+        }
+    }
+
+    if had_content {
+        let unload_evt = Avm2EventObject::bare_default_event(context, "unload");
+        Avm2::dispatch_event(context, unload_evt, loader_info.into());
+    }
+}
+
 /// Kick off the root movie load.
 ///
 /// The root movie is special because it determines a few bits of player
@@ -1203,6 +1367,44 @@ pub fn load_stylesheet<'gc>(
     })
 }
 
+/// Kick off a cross-domain policy file load.
+///
+/// Returns the loader's async process, which you will need to spawn.
+pub fn load_policy_file(uc: &UpdateContext<'_>, url: String) -> OwnedFuture<(), Error> {
+    let player = uc.player_handle();
+
+    Box::pin(async move {
+        let request = Request::get(url.clone());
+        let fetch = player.lock().unwrap().fetch(request, FetchReason::Other);
+        let response = wait_for_full_response(fetch).await;
+
+        let state = match response {
+            Ok((body, _, _, _)) => match PolicyFile::parse(&body) {
+                Some(policy) => PolicyFileState::Loaded(policy),
+                None => {
+                    tracing::warn!("Ignoring invalid policy file {url}");
+                    PolicyFileState::Failed
+                }
+            },
+            Err(response) => {
+                tracing::warn!(
+                    "Error during policy file load of {:?}: {:?}",
+                    response.url,
+                    response.error
+                );
+                PolicyFileState::Failed
+            }
+        };
+
+        player
+            .lock()
+            .unwrap()
+            .update(|uc| uc.security.set_policy_file(url, state));
+
+        Ok(())
+    })
+}
+
 /// Kick off a data load into a `URLLoader`, updating
 /// its `data` property when the load completes.
 ///
@@ -1480,8 +1682,8 @@ pub fn load_sound_avm2<'gc>(
             let sound = sound.fetch(uc);
             let sound_object = Avm2Object::from(sound);
 
-            if sound.loading_state() == SoundLoadingState::Loaded {
-                // Sound has already been loaded.
+            if sound.loading_state() != SoundLoadingState::Loading {
+                // Sound has already been loaded, or its download was closed.
                 return Ok(());
             }
 
@@ -1676,28 +1878,6 @@ impl<'gc> MovieLoader<'gc> {
             return Ok(());
         }
 
-        let domain = if let MovieLoaderVMData::Avm2 {
-            context,
-            default_domain,
-            ..
-        } = vm_data
-        {
-            use crate::avm2::globals::slots::flash_system_loader_context as loader_context_slots;
-
-            context
-                .map(|o| o.get_slot(loader_context_slots::APPLICATION_DOMAIN))
-                .and_then(|v| v.as_object())
-                .and_then(|o| o.as_application_domain())
-                .unwrap_or_else(|| {
-                    let parent_domain = default_domain;
-                    Avm2Domain::movie_domain(uc, parent_domain)
-                })
-        } else {
-            // This is necessary when the MovieLoaderData is AVM1,
-            // but loaded an AVM2 SWF (mixed AVM).
-            uc.avm2.stage_domain()
-        };
-
         let movie = match sniffed_type {
             ContentType::Swf => {
                 let mut movie =
@@ -1726,6 +1906,43 @@ impl<'gc> MovieLoader<'gc> {
             ContentType::Unknown => Arc::new(SwfMovie::error_movie(url.clone())),
         };
 
+        let domain = if let MovieLoaderVMData::Avm2 {
+            loader_info,
+            context,
+            default_domain,
+            ..
+        } = vm_data
+        {
+            use crate::avm2::globals::slots::flash_system_loader_context as loader_context_slots;
+
+            // Movies from another security domain can't be loaded into the
+            // domain of the loading movie, which would share definitions
+            // with it. They get a domain of their own instead.
+            let is_same_security_domain = loader_info.loader().is_none_or(|loader| {
+                uc.security
+                    .can_script(&loader.display_object().movie(), &movie)
+            });
+
+            let requested_domain = context
+                .filter(|_| is_same_security_domain)
+                .map(|o| o.get_slot(loader_context_slots::APPLICATION_DOMAIN))
+                .and_then(|v| v.as_object())
+                .and_then(|o| o.as_application_domain());
+
+            requested_domain.unwrap_or_else(|| {
+                let parent_domain = if is_same_security_domain {
+                    default_domain
+                } else {
+                    uc.avm2.playerglobals_domain()
+                };
+                Avm2Domain::movie_domain(uc, parent_domain)
+            })
+        } else {
+            // This is necessary when the MovieLoaderData is AVM1,
+            // but loaded an AVM2 SWF (mixed AVM).
+            uc.avm2.stage_domain()
+        };
+
         match uc.load_manager.get_loader_mut(handle) {
             Some(Self {
                 movie: old,
@@ -1856,7 +2073,9 @@ impl<'gc> MovieLoader<'gc> {
                 return Ok(());
             }
             ContentType::Gif | ContentType::Jpeg | ContentType::JpegXr | ContentType::Png => {
-                let mut activation = Avm2Activation::from_nothing(uc);
+                // The content belongs to the image, not to the loading movie,
+                // which matters for security checks.
+                let mut activation = Avm2Activation::from_movie(uc, movie.clone());
 
                 let library = activation.context.library.library_for_movie_mut(movie);
 
@@ -2197,6 +2416,7 @@ impl<'gc> MovieLoader<'gc> {
                 let current_movie = { loader_info.loader_stream().movie().clone() };
                 loader_info
                     .set_loader_stream(LoaderStream::Swf(current_movie, dobj.unwrap()), uc.gc());
+                loader_info.set_redirected(redirected);
 
                 if let Some(dobj) = dobj
                     && dobj.as_movie_clip().is_none()
@@ -7,7 +7,9 @@ use crate::avm1::Value;
 use crate::avm1::VariableDumper;
 use crate::avm1::{Activation, ActivationIdentifier};
 use crate::avm2::object::EventObject as Avm2EventObject;
-use crate::avm2::{Activation as Avm2Activation, Avm2, CallStack, SharedObjectObject};
+use crate::avm2::{
+    Activation as Avm2Activation, Avm2, Avm2Debugger, CallStack, SharedObjectObject,
+};
 use crate::backend::navigator::ErrorResponse;
 use crate::backend::navigator::FetchReason;
 use crate::backend::navigator::OwnedFuture;
@@ -15,9 +17,11 @@ use crate::backend::navigator::SuccessResponse;
 use crate::backend::ui::FontDefinition;
 use crate::backend::{
     audio::{AudioBackend, AudioManager},
+    filesystem::FileSystemBackend,
     log::LogBackend,
     navigator::{NavigatorBackend, Request},
     storage::StorageBackend,
+    time::TimeBackend,
     ui::{MouseCursor, UiBackend},
 };
 use crate::compatibility_rules::CompatibilityRules;
@@ -49,6 +53,8 @@ use crate::local_connection::LocalConnections;
 use crate::net_connection::NetConnections;
 use crate::orphan_manager::OrphanManager;
 use crate::prelude::*;
+use crate::save_state::{self, PlayerState, SaveStateError};
+use crate::security::SecurityManager;
 use crate::socket::Sockets;
 use crate::streams::StreamManager;
 use crate::string::{AvmStringInterner, StringContext};
@@ -77,6 +83,7 @@ use std::str::FromStr;
 use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
 use tracing::instrument;
+use url::Url;
 use web_time::Instant;
 
 #[cfg(feature = "default_font")]
@@ -313,9 +320,11 @@ pub struct Player {
     audio: Box<dyn AudioBackend>,
     navigator: Box<dyn NavigatorBackend>,
     storage: Box<dyn StorageBackend>,
+    filesystem: Box<dyn FileSystemBackend>,
     log: Box<dyn LogBackend>,
     ui: Box<dyn UiBackend>,
     video: Box<dyn VideoBackend>,
+    time: Box<dyn TimeBackend>,
 
     transform_stack: TransformStack,
 
@@ -354,6 +363,10 @@ pub struct Player {
 
     page_url: Option<String>,
 
+    security: SecurityManager,
+
+    avm2_debugger: Avm2Debugger,
+
     /// The current instance ID. Used to generate default `instanceN` names.
     instance_counter: i32,
 
@@ -520,7 +533,9 @@ impl Player {
     }
 
     pub fn tick(&mut self, dt: FloatDuration) {
-        if !self.is_playing() {
+        self.time.advance(dt.to_std());
+
+        if !self.is_playing() || self.avm2_debugger.is_paused() {
             return;
         }
 
@@ -552,6 +567,12 @@ impl Player {
                 self.set_run_state(RunState::Suspended);
                 break;
             }
+
+            // If the frame hit a breakpoint, don't run any more until the
+            // debugger resumes.
+            if self.avm2_debugger.is_paused() {
+                break;
+            }
         }
 
         // Now that we're done running code,
@@ -578,6 +599,10 @@ impl Player {
         });
         self.frame_accumulator += FloatDuration::from_secs(audio_skew);
 
+        if self.avm2_debugger.is_paused() {
+            return;
+        }
+
         self.update_sockets();
         self.update_net_connections();
         self.update_timers(dt);
@@ -629,6 +654,11 @@ impl Player {
         self.mouse_cursor
     }
 
+    /// Returns the stubs encountered so far while running content.
+    pub fn stub_tracker(&self) -> &StubCollection {
+        &self.stub_tracker
+    }
+
     /// Returns the master volume of the player. 1.0 is 100% volume.
     ///
     /// The volume is linear and not adapted for logarithmic hearing.
@@ -1065,7 +1095,7 @@ impl Player {
     fn handle_input_event(&mut self, event: PlayerEvent) -> bool {
         let mut player_event_handled = false;
         let prev_mouse_buttons = self.input.get_mouse_down_buttons();
-        let Some(event) = self.input.process_event(event) else {
+        let Some(event) = self.input.process_event(event, self.time.now()) else {
             return false;
         };
 
@@ -2054,6 +2084,8 @@ impl Player {
             let stage = gc_root.stage;
 
             let mut cache_draws = vec![];
+            this.transform_stack
+                .set_projection_width(stage.stage_size().0 as f32);
             let mut render_context = RenderContext {
                 renderer: this.renderer.deref_mut(),
                 commands: CommandList::new(),
@@ -2064,6 +2096,7 @@ impl Player {
                 is_offscreen: false,
                 use_bitmap_cache: true,
                 stage,
+                time: &*this.time,
             };
 
             stage.render_viewport(&mut render_context);
@@ -2141,6 +2174,14 @@ impl Player {
         std::mem::swap(&mut self.storage, storage);
     }
 
+    pub fn filesystem(&self) -> &dyn FileSystemBackend {
+        &*self.filesystem
+    }
+
+    pub fn filesystem_mut(&mut self) -> &mut dyn FileSystemBackend {
+        &mut *self.filesystem
+    }
+
     pub fn ui(&self) -> &dyn UiBackend {
         &*self.ui
     }
@@ -2149,6 +2190,14 @@ impl Player {
         &mut *self.ui
     }
 
+    pub fn time(&self) -> &dyn TimeBackend {
+        &*self.time
+    }
+
+    pub fn time_mut(&mut self) -> &mut dyn TimeBackend {
+        &mut *self.time
+    }
+
     pub fn run_actions(context: &mut UpdateContext<'_>) {
         // Note that actions can queue further actions, so a while loop is necessary here.
         while let Some(action) = context.action_queue.pop_action() {
@@ -2285,10 +2334,14 @@ impl Player {
                 load_manager,
                 system: &mut this.system,
                 page_url: &mut this.page_url,
+                security: &mut this.security,
+                avm2_debugger: &mut this.avm2_debugger,
                 instance_counter: &mut this.instance_counter,
                 storage: this.storage.deref_mut(),
+                filesystem: this.filesystem.deref_mut(),
                 log: this.log.deref_mut(),
                 video: this.video.deref_mut(),
+                time: this.time.deref_mut(),
                 avm1_shared_objects,
                 avm2_shared_objects,
                 unbound_text_fields,
@@ -2333,6 +2386,10 @@ impl Player {
                     .set_frame_rate(*update_context.frame_rate);
             }
 
+            // Control is returning to the player, so a paused debugger has
+            // seen all the code it can step through.
+            update_context.avm2_debugger.finish_recording();
+
             this.current_frame = update_context
                 .stage
                 .root_clip()
@@ -2359,6 +2416,12 @@ impl Player {
         self.debug_ui.borrow_mut()
     }
 
+    /// The debugger for AVM2 code, used to set breakpoints and to inspect
+    /// and step through execution while it is paused.
+    pub fn avm2_debugger(&mut self) -> &mut Avm2Debugger {
+        &mut self.avm2_debugger
+    }
+
     /// Update the current state of the player.
     ///
     /// The given function will be called with the current stage root, current
@@ -2420,6 +2483,21 @@ impl Player {
         });
     }
 
+    /// Takes a snapshot of the running movie, which can later be restored
+    /// with `load_state`.
+    ///
+    /// Fails if the movie uses anything whose state can't be captured.
+    pub fn save_state(&mut self) -> Result<PlayerState, SaveStateError> {
+        self.mutate_with_update_context(save_state::capture)
+    }
+
+    /// Restores a snapshot previously taken with `save_state`.
+    pub fn load_state(&mut self, state: &PlayerState) -> Result<(), SaveStateError> {
+        let result = self.update(|context| save_state::restore(context, state));
+        self.needs_render = true;
+        result
+    }
+
     /// Update all AVM-based timers (such as created via setInterval).
     /// Returns the approximate amount of time until the next timer tick.
     pub fn update_timers(&mut self, dt: FloatDuration) {
@@ -2576,8 +2654,10 @@ pub struct PlayerBuilder {
     navigator: Option<Box<dyn NavigatorBackend>>,
     renderer: Option<Box<dyn RenderBackend>>,
     storage: Option<Box<dyn StorageBackend>>,
+    filesystem: Option<Box<dyn FileSystemBackend>>,
     ui: Option<Box<dyn UiBackend>>,
     video: Option<Box<dyn VideoBackend>>,
+    time: Option<Box<dyn TimeBackend>>,
 
     // Notifications
     notification_sender: Option<Sender<PlayerNotification>>,
@@ -2604,6 +2684,7 @@ pub struct PlayerBuilder {
     player_mode: PlayerMode,
     quality: StageQuality,
     page_url: Option<String>,
+    trusted_locations: Vec<Url>,
     frame_rate: Option<f64>,
     external_interface_provider: Option<Box<dyn ExternalInterfaceProvider>>,
     fs_command_provider: Box<dyn FsCommandProvider>,
@@ -2629,8 +2710,10 @@ impl PlayerBuilder {
             navigator: None,
             renderer: None,
             storage: None,
+            filesystem: None,
             ui: None,
             video: None,
+            time: None,
 
             notification_sender: None,
 
@@ -2660,6 +2743,7 @@ impl PlayerBuilder {
             player_mode: PlayerMode::default(),
             quality: StageQuality::High,
             page_url: None,
+            trusted_locations: Vec::new(),
             frame_rate: None,
             external_interface_provider: None,
             fs_command_provider: Box::new(NullFsCommandProvider),
@@ -2727,6 +2811,13 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the filesystem backend of the player, used by the AIR `flash.filesystem` APIs.
+    #[inline]
+    pub fn with_filesystem(mut self, filesystem: Box<dyn FileSystemBackend>) -> Self {
+        self.filesystem = Some(filesystem);
+        self
+    }
+
     /// Sets the UI backend of the player.
     #[inline]
     pub fn with_ui(mut self, ui: impl 'static + UiBackend) -> Self {
@@ -2741,6 +2832,13 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the time backend of the player.
+    #[inline]
+    pub fn with_time(mut self, time: impl 'static + TimeBackend) -> Self {
+        self.time = Some(Box::new(time));
+        self
+    }
+
     /// Sets the channel for player notifications.
     #[inline]
     pub fn with_notification_sender(mut self, sender: Sender<PlayerNotification>) -> Self {
@@ -2853,6 +2951,12 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the local locations whose movies run in the `localTrusted` sandbox.
+    pub fn with_trusted_locations(mut self, trusted_locations: Vec<Url>) -> Self {
+        self.trusted_locations = trusted_locations;
+        self
+    }
+
     /// Sets and locks the player's frame rate. If None is provided, this has no effect.
     pub fn with_frame_rate(mut self, frame_rate: Option<f64>) -> Self {
         self.frame_rate = frame_rate;
@@ -2979,15 +3083,22 @@ impl PlayerBuilder {
         let storage = self
             .storage
             .unwrap_or_else(|| Box::new(storage::MemoryStorageBackend::new()));
+        let filesystem = self
+            .filesystem
+            .unwrap_or_else(|| Box::new(filesystem::MemoryFileSystemBackend::new()));
         let ui = self
             .ui
             .unwrap_or_else(|| Box::new(ui::NullUiBackend::new()));
         let video = self
             .video
             .unwrap_or_else(|| Box::new(null::NullVideoBackend::new()));
+        let time = self
+            .time
+            .unwrap_or_else(|| Box::new(time::SystemTimeBackend::new()));
 
         let player_version = self.player_version.unwrap_or(DEFAULT_PLAYER_VERSION);
         let language = ui.language();
+        let start_time = time.instant();
 
         // Instantiate the player.
         let fake_movie = Arc::new(SwfMovie::empty(player_version, None));
@@ -3001,8 +3112,10 @@ impl PlayerBuilder {
                 navigator,
                 renderer,
                 storage,
+                filesystem,
                 ui,
                 video,
+                time,
 
                 // SWF info
                 swf: fake_movie.clone(),
@@ -3014,7 +3127,7 @@ impl PlayerBuilder {
                 frame_phase: Default::default(),
                 frame_accumulator: FloatDuration::ZERO,
                 recent_run_frame_timings: VecDeque::with_capacity(10),
-                start_time: Instant::now(),
+                start_time,
                 time_offset: 0,
                 time_til_next_timer: None,
                 max_execution_duration: self.max_execution_duration,
@@ -3033,6 +3146,8 @@ impl PlayerBuilder {
                 rng: AvmRng::default(),
                 system: SystemProperties::new(language),
                 page_url: self.page_url.clone(),
+                security: SecurityManager::new(self.trusted_locations.clone()),
+                avm2_debugger: Avm2Debugger::default(),
                 transform_stack: TransformStack::new(),
                 instance_counter: 0,
                 player_version,
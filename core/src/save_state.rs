@@ -0,0 +1,493 @@
+//! Snapshots of a running movie, which can be restored later.
+//!
+//! A snapshot records the position of every timeline, the contents of every
+//! text field and drawing, the state of the scripts, all timers, the
+//! position of every playing event sound, the data of all open local shared
+//! objects and the state of the random number generator.
+//!
+//! The scripts of AVM1 movies are captured as the variables stored on their
+//! timelines. For AVM2 movies the whole heap reachable from the display list
+//! and from the script globals is captured, see [`crate::avm2::save_state`].
+//!
+//! Anything that cannot be faithfully recreated, such as open sockets or
+//! streams that are still loading, blocks the snapshot instead of being
+//! silently dropped; see [`SaveStateError::Unsupported`].
+
+pub(crate) mod display;
+mod drawing;
+mod sounds;
+mod timers;
+
+use crate::avm1::globals::shared_object::{deserialize_lso, new_lso};
+use crate::avm1::{
+    Activation, ActivationIdentifier, Attribute, NativeObject, Object as Avm1Object,
+    Value as Avm1Value,
+};
+use crate::avm2::Activation as Avm2Activation;
+use crate::avm2::save_state::{HeapReader, HeapWriter};
+use crate::context::UpdateContext;
+use crate::display_object::DisplayObjectContainer;
+use crate::player::Player;
+use crate::prelude::*;
+use crate::string::{AvmString, WStr, WString};
+use flash_lso::read::Reader;
+use gc_arena::Mutation;
+use ruffle_macros::istr;
+use serde_json::{Value as JsonValue, json};
+use thiserror::Error;
+
+/// The version of the snapshot format, bumped whenever it changes incompatibly.
+const FORMAT_VERSION: u64 = 3;
+
+#[derive(Debug, Error)]
+pub enum SaveStateError {
+    /// The movie uses features whose state can't be captured.
+    #[error("Unable to save state, unsupported: {}", .0.join(", "))]
+    Unsupported(Vec<String>),
+
+    /// The snapshot was taken from a different movie.
+    #[error("Snapshot was taken from {0}, not from this movie")]
+    MovieMismatch(String),
+
+    #[error("Invalid snapshot: {0}")]
+    Malformed(String),
+}
+
+/// A snapshot of a running movie, created by [`Player::save_state`].
+#[derive(Clone, Debug)]
+pub struct PlayerState {
+    movie_url: String,
+    rng_state: u32,
+    /// The display objects of an AVM1 movie.
+    display_objects: Vec<DisplayObjectState>,
+    /// The heap of an AVM2 movie, display objects included.
+    avm2_heap: Option<JsonValue>,
+    timers: JsonValue,
+    sounds: JsonValue,
+    shared_objects: Vec<(String, Vec<u8>)>,
+}
+
+#[derive(Clone, Debug)]
+struct DisplayObjectState {
+    /// The depths leading from the stage to this object.
+    path: Vec<Depth>,
+    /// The state captured by [`display::capture`].
+    state: JsonValue,
+    /// Serializable AVM1 variables of this object, stored as an LSO.
+    variables: Option<Vec<u8>>,
+}
+
+impl PlayerState {
+    /// Serializes this snapshot, to be stored on disk.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let display_objects: Vec<JsonValue> = self
+            .display_objects
+            .iter()
+            .map(|object| {
+                let mut value = json!({
+                    "path": object.path,
+                    "state": object.state,
+                });
+                if let Some(variables) = &object.variables {
+                    value["variables"] = json!(variables);
+                }
+                value
+            })
+            .collect();
+        let shared_objects: Vec<JsonValue> = self
+            .shared_objects
+            .iter()
+            .map(|(name, data)| json!({ "name": name, "data": data }))
+            .collect();
+
+        let state = json!({
+            "version": FORMAT_VERSION,
+            "movie_url": self.movie_url,
+            "rng_state": self.rng_state,
+            "display_objects": display_objects,
+            "avm2_heap": self.avm2_heap,
+            "timers": self.timers,
+            "sounds": self.sounds,
+            "shared_objects": shared_objects,
+        });
+        serde_json::to_vec(&state).expect("Snapshot should serialize")
+    }
+
+    /// Deserializes a snapshot previously produced by [`PlayerState::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SaveStateError> {
+        let mut state: JsonValue =
+            serde_json::from_slice(bytes).map_err(|e| SaveStateError::Malformed(e.to_string()))?;
+
+        let version = state["version"].as_u64();
+        if version != Some(FORMAT_VERSION) {
+            return Err(SaveStateError::Malformed(format!(
+                "unsupported version {version:?}"
+            )));
+        }
+
+        let movie_url = field(&state, "movie_url")?;
+        let rng_state = field(&state, "rng_state")?;
+
+        let mut display_objects = vec![];
+        for object in array(&state, "display_objects")? {
+            let variables = if object["variables"].is_null() {
+                None
+            } else {
+                Some(field(object, "variables")?)
+            };
+            display_objects.push(DisplayObjectState {
+                path: field(object, "path")?,
+                state: object["state"].clone(),
+                variables,
+            });
+        }
+
+        let mut shared_objects = vec![];
+        for shared_object in array(&state, "shared_objects")? {
+            shared_objects.push((field(shared_object, "name")?, field(shared_object, "data")?));
+        }
+
+        let avm2_heap = match state["avm2_heap"].take() {
+            JsonValue::Null => None,
+            heap => Some(heap),
+        };
+
+        Ok(Self {
+            movie_url,
+            rng_state,
+            display_objects,
+            avm2_heap,
+            timers: state["timers"].take(),
+            sounds: state["sounds"].take(),
+            shared_objects,
+        })
+    }
+
+    /// The URL of the movie this snapshot was taken from.
+    pub fn movie_url(&self) -> &str {
+        &self.movie_url
+    }
+}
+
+pub(crate) fn field<T: serde::de::DeserializeOwned>(
+    value: &JsonValue,
+    name: &str,
+) -> Result<T, SaveStateError> {
+    serde_json::from_value(value[name].clone())
+        .map_err(|e| SaveStateError::Malformed(format!("field `{name}`: {e}")))
+}
+
+/// Serializes a string as its raw UTF-16 code units, so that strings that
+/// aren't valid Unicode (such as ones with unpaired surrogates) are restored
+/// unchanged.
+pub(crate) fn wstr_json(string: &WStr) -> JsonValue {
+    json!(string.iter().collect::<Vec<u16>>())
+}
+
+/// Deserializes a string serialized by [`wstr_json`].
+pub(crate) fn json_wstr<'gc>(
+    mc: &Mutation<'gc>,
+    value: &JsonValue,
+) -> Result<AvmString<'gc>, SaveStateError> {
+    let units: Vec<u16> = serde_json::from_value(value.clone())
+        .map_err(|e| SaveStateError::Malformed(format!("string: {e}")))?;
+    Ok(AvmString::new(mc, WString::from_buf(units)))
+}
+
+pub(crate) fn array<'a>(
+    value: &'a JsonValue,
+    name: &str,
+) -> Result<&'a Vec<JsonValue>, SaveStateError> {
+    value[name]
+        .as_array()
+        .ok_or_else(|| SaveStateError::Malformed(format!("field `{name}` is not an array")))
+}
+
+/// Captures the state of the running movie.
+pub fn capture(context: &mut UpdateContext<'_>) -> Result<PlayerState, SaveStateError> {
+    let mut blockers = vec![];
+    if !context.sockets.is_empty() {
+        blockers.push("open sockets".to_string());
+    }
+    if context.stream_manager.has_active_streams() {
+        blockers.push("playing NetStreams".to_string());
+    }
+    if !context.load_manager.is_empty() {
+        blockers.push("loads in progress".to_string());
+    }
+
+    let mut display_objects = vec![];
+    let (avm2_heap, timers, sounds) = if context.root_swf.is_action_script_3() {
+        let mut activation = Avm2Activation::from_nothing(context);
+        let mut heap = HeapWriter::new(&mut activation);
+        heap.add_roots(&mut activation);
+        let timers = timers::capture(activation.context, Some(&mut heap), &mut blockers);
+        let sounds = sounds::capture(activation.context, Some(&mut heap), &mut blockers);
+        let (entries, heap_blockers) = heap.finish(&mut activation);
+        blockers.extend(heap_blockers);
+        (Some(entries), timers, sounds)
+    } else {
+        let mut activation =
+            Activation::try_from_stub(context, ActivationIdentifier::root("[Save State]"))
+                .ok_or_else(|| {
+                    SaveStateError::Unsupported(vec!["movie without a root".to_string()])
+                })?;
+        let stage = activation.context.stage;
+        capture_children(
+            &mut activation,
+            stage.into(),
+            &mut vec![],
+            &mut display_objects,
+            &mut blockers,
+        );
+        let timers = timers::capture(activation.context, None, &mut blockers);
+        let sounds = sounds::capture(activation.context, None, &mut blockers);
+        (None, timers, sounds)
+    };
+
+    if !blockers.is_empty() {
+        blockers.sort();
+        blockers.dedup();
+        return Err(SaveStateError::Unsupported(blockers));
+    }
+
+    let shared_objects = capture_shared_objects(context);
+
+    Ok(PlayerState {
+        movie_url: context.root_swf.url().to_string(),
+        rng_state: context.rng.state(),
+        display_objects,
+        avm2_heap,
+        timers,
+        sounds,
+        shared_objects,
+    })
+}
+
+/// Serializes the data of the open AVM1 shared objects, the same way they're
+/// flushed, but without writing them to storage.
+///
+/// The data of AVM2 shared objects is part of the heap.
+fn capture_shared_objects(context: &mut UpdateContext<'_>) -> Vec<(String, Vec<u8>)> {
+    let mut shared_objects: Vec<_> = context
+        .avm1_shared_objects
+        .iter()
+        .map(|(name, object)| (name.clone(), *object))
+        .collect();
+    shared_objects.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let Some(mut activation) =
+        Activation::try_from_stub(context, ActivationIdentifier::root("[Save State]"))
+    else {
+        return vec![];
+    };
+    shared_objects
+        .into_iter()
+        .filter_map(|(name, shared_object)| {
+            let data = shared_object
+                .get_stored_property(&mut activation, istr!("data"))?
+                .as_object(&mut activation)?;
+            let mut lso = new_lso(&mut activation, &name, data);
+            let bytes = flash_lso::write::write_to_bytes(&mut lso).ok()?;
+            Some((name, bytes))
+        })
+        .collect()
+}
+
+fn capture_children<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    container: DisplayObjectContainer<'gc>,
+    path: &mut Vec<Depth>,
+    out: &mut Vec<DisplayObjectState>,
+    blockers: &mut Vec<String>,
+) {
+    for child in container.iter_render_list() {
+        if child.movie().is_action_script_3() {
+            blockers.push("AVM2 content in AVM1 movies".to_string());
+            continue;
+        }
+
+        path.push(child.depth());
+        let state = display::capture(child, true, blockers);
+        let variables = child
+            .object1()
+            .map(|object| capture_variables(activation, object));
+        out.push(DisplayObjectState {
+            path: path.clone(),
+            state,
+            variables,
+        });
+
+        if let Some(container) = child.as_container() {
+            capture_children(activation, container, path, out, blockers);
+        }
+        path.pop();
+    }
+}
+
+/// Serializes the plain data stored on an AVM1 object.
+///
+/// Functions and display objects are skipped, as those are recreated by the
+/// timeline when the snapshot is restored.
+fn capture_variables<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Avm1Object<'gc>,
+) -> Vec<u8> {
+    let data = Avm1Object::new(
+        &activation.context.strings,
+        Some(activation.prototypes().object),
+    );
+    for (name, value) in object.own_properties() {
+        let is_data = match value {
+            Avm1Value::MovieClip(_) => false,
+            Avm1Value::Object(object) => {
+                object.as_function().is_none() && object.as_display_object().is_none()
+            }
+            _ => true,
+        };
+        if is_data {
+            data.define_value(activation.gc(), name, value, Attribute::empty());
+        }
+    }
+
+    let mut lso = new_lso(activation, "variables", data);
+    flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default()
+}
+
+/// Restores a snapshot created by [`capture`] onto the running movie.
+pub fn restore(context: &mut UpdateContext<'_>, state: &PlayerState) -> Result<(), SaveStateError> {
+    if context.root_swf.url() != state.movie_url {
+        return Err(SaveStateError::MovieMismatch(state.movie_url.clone()));
+    }
+
+    context.rng.restore_state(state.rng_state);
+
+    if let Some(entries) = &state.avm2_heap {
+        let mut activation = Avm2Activation::from_nothing(context);
+        let heap = HeapReader::restore(&mut activation, entries)?;
+        timers::restore(activation.context, &state.timers, Some(&heap))?;
+        sounds::restore(activation.context, &state.sounds, Some(&heap))?;
+    } else {
+        restore_display_objects(context, &state.display_objects)?;
+        timers::restore(context, &state.timers, None)?;
+        sounds::restore(context, &state.sounds, None)?;
+    }
+
+    // The data of AVM2 shared objects is part of the heap, while the data of
+    // AVM1 shared objects is replaced here. Neither is written to storage, as
+    // that only happens when the movie flushes them.
+    let Some(mut activation) =
+        Activation::try_from_stub(context, ActivationIdentifier::root("[Load State]"))
+    else {
+        return Ok(());
+    };
+    for (name, data) in &state.shared_objects {
+        let Some(shared_object) = activation.context.avm1_shared_objects.get(name).copied() else {
+            continue;
+        };
+        if !matches!(shared_object.native(), NativeObject::SharedObject(_)) {
+            continue;
+        }
+        let mut reader = Reader::default();
+        if let Ok(lso) = reader.parse(data)
+            && let Ok(data) = deserialize_lso(&mut activation, &lso, &reader.amf0_decoder)
+        {
+            shared_object.define_value(
+                activation.gc(),
+                istr!("data"),
+                data.into(),
+                Attribute::DONT_DELETE,
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Restores the display objects of an AVM1 movie, along with the variables
+/// stored on them.
+fn restore_display_objects(
+    context: &mut UpdateContext<'_>,
+    display_objects: &[DisplayObjectState],
+) -> Result<(), SaveStateError> {
+    // Move the timelines first, parents before children, so that the objects
+    // placed by the timeline exist before we try to restore them.
+    for object_state in display_objects {
+        if let Some(object) = find_display_object(context, &object_state.path) {
+            display::goto(context, object, &object_state.state);
+            Player::run_actions(context);
+        }
+    }
+
+    // Then restore the contents, overwriting whatever the frame scripts did.
+    let mut activation =
+        Activation::try_from_stub(context, ActivationIdentifier::root("[Load State]"))
+            .ok_or_else(|| SaveStateError::Malformed("movie has no root".to_string()))?;
+    for object_state in display_objects {
+        let Some(object) = find_display_object(activation.context, &object_state.path) else {
+            tracing::warn!(
+                "Unable to restore display object at {:?}, it no longer exists",
+                object_state.path
+            );
+            continue;
+        };
+        display::restore(activation.context, object, &object_state.state)?;
+
+        if let (Some(variables), Some(target)) = (&object_state.variables, object.object1()) {
+            let mut reader = Reader::default();
+            let Ok(lso) = reader.parse(variables) else {
+                return Err(SaveStateError::Malformed(format!(
+                    "variables of {:?}",
+                    object_state.path
+                )));
+            };
+            let data = deserialize_lso(&mut activation, &lso, &reader.amf0_decoder)
+                .map_err(|e| SaveStateError::Malformed(format!("{e:?}")))?;
+            for (name, value) in data.own_properties() {
+                target.define_value(activation.gc(), name, value, Attribute::empty());
+            }
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn find_display_object<'gc>(
+    context: &UpdateContext<'gc>,
+    path: &[Depth],
+) -> Option<DisplayObject<'gc>> {
+    let mut object: DisplayObject<'gc> = context.stage.into();
+    for depth in path {
+        object = object.as_container()?.child_by_depth(*depth)?;
+    }
+    Some(object)
+}
+
+/// Returns the depths leading from the stage to a display object, if it is
+/// on the display list.
+pub(crate) fn display_object_path<'gc>(
+    context: &UpdateContext<'gc>,
+    object: DisplayObject<'gc>,
+) -> Option<Vec<Depth>> {
+    let mut path = vec![];
+    let mut current = object;
+    while let Some(parent) = current.parent() {
+        path.push(current.depth());
+        current = parent;
+    }
+    if !DisplayObject::ptr_eq(current, context.stage.into()) {
+        return None;
+    }
+    path.reverse();
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_unknown_version() {
+        let result = PlayerState::from_bytes(br#"{"version": 9999}"#);
+        assert!(matches!(result, Err(SaveStateError::Malformed(_))));
+    }
+}
@@ -0,0 +1,202 @@
+//! Snapshots of the state that every display object has, regardless of the
+//! AVM it is used from.
+
+use super::{SaveStateError, drawing, field, json_wstr, wstr_json};
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::drawing::Drawing;
+use ruffle_render::bitmap::BitmapInfo;
+use ruffle_render::matrix::Matrix;
+use serde_json::{Map, Value as JsonValue, json};
+use swf::{ColorTransform, Fixed8, Twips};
+
+/// Captures the state of a display object.
+///
+/// The drawing of the object is only captured when `with_drawing` is set,
+/// as AVM2 movies store it along with the `Graphics` object instead.
+pub(crate) fn capture(
+    object: DisplayObject<'_>,
+    with_drawing: bool,
+    blockers: &mut Vec<String>,
+) -> JsonValue {
+    let mut state = Map::new();
+
+    if let Some(clip) = object.as_movie_clip() {
+        state.insert("frame".to_string(), json!(clip.current_frame()));
+        state.insert("playing".to_string(), json!(clip.playing()));
+    } else if let Some(text) = object.as_edit_text() {
+        let html = text.is_html();
+        let text = if html { text.html_text() } else { text.text() };
+        state.insert("html".to_string(), json!(html));
+        state.insert("text".to_string(), wstr_json(&text));
+    }
+
+    let matrix = object.base().matrix();
+    state.insert(
+        "matrix".to_string(),
+        json!([
+            matrix.a,
+            matrix.b,
+            matrix.c,
+            matrix.d,
+            matrix.tx.get(),
+            matrix.ty.get()
+        ]),
+    );
+    let color = object.base().color_transform();
+    state.insert(
+        "color_transform".to_string(),
+        json!([
+            color.r_multiply.get(),
+            color.g_multiply.get(),
+            color.b_multiply.get(),
+            color.a_multiply.get(),
+            color.r_add,
+            color.g_add,
+            color.b_add,
+            color.a_add
+        ]),
+    );
+    state.insert("visible".to_string(), json!(object.visible()));
+    state.insert(
+        "transformed_by_script".to_string(),
+        json!(object.transformed_by_script()),
+    );
+    if let Some(name) = object.name() {
+        state.insert("name".to_string(), wstr_json(&name));
+    }
+
+    if with_drawing {
+        match with_drawing_of(object, |drawing| {
+            (!drawing::uses_bitmaps(drawing)).then(|| drawing::capture(drawing))
+        }) {
+            Some(Some(drawing)) => {
+                state.insert("drawing".to_string(), drawing);
+            }
+            Some(None) => blockers.push("bitmap fills drawn by AVM1".to_string()),
+            None => {}
+        }
+    }
+
+    JsonValue::Object(state)
+}
+
+/// Moves the timeline of a display object to its captured frame.
+///
+/// This is done before the rest of the state is restored, so that the
+/// children placed by the timeline exist by then.
+pub(crate) fn goto<'gc>(
+    context: &mut UpdateContext<'gc>,
+    object: DisplayObject<'gc>,
+    state: &JsonValue,
+) {
+    if let Some(clip) = object.as_movie_clip()
+        && let (Some(frame), Some(playing)) = (state["frame"].as_u64(), state["playing"].as_bool())
+    {
+        clip.goto_frame(context, frame as u16, !playing);
+    }
+}
+
+/// Restores the captured state of a display object, except for its timeline.
+pub(crate) fn restore<'gc>(
+    context: &mut UpdateContext<'gc>,
+    object: DisplayObject<'gc>,
+    state: &JsonValue,
+) -> Result<(), SaveStateError> {
+    // Frame scripts run by the goto may have started or stopped the clip.
+    if let Some(clip) = object.as_movie_clip()
+        && let Some(playing) = state["playing"].as_bool()
+    {
+        if playing {
+            clip.play();
+        } else {
+            clip.stop(context);
+        }
+    }
+
+    if let Some(edit_text) = object.as_edit_text()
+        && !state["text"].is_null()
+    {
+        let text = json_wstr(context.gc(), &state["text"])?;
+        if state["html"].as_bool() == Some(true) {
+            edit_text.set_html_text(&text, context);
+        } else {
+            edit_text.set_text(&text, context);
+        }
+    }
+
+    let [a, b, c, d, tx, ty]: [f64; 6] = field(state, "matrix")?;
+    object.set_matrix(Matrix {
+        a: a as f32,
+        b: b as f32,
+        c: c as f32,
+        d: d as f32,
+        tx: Twips::new(tx as i32),
+        ty: Twips::new(ty as i32),
+    });
+    let [
+        r_multiply,
+        g_multiply,
+        b_multiply,
+        a_multiply,
+        r_add,
+        g_add,
+        b_add,
+        a_add,
+    ]: [i16; 8] = field(state, "color_transform")?;
+    object.set_color_transform(ColorTransform {
+        r_multiply: Fixed8::from_bits(r_multiply),
+        g_multiply: Fixed8::from_bits(g_multiply),
+        b_multiply: Fixed8::from_bits(b_multiply),
+        a_multiply: Fixed8::from_bits(a_multiply),
+        r_add,
+        g_add,
+        b_add,
+        a_add,
+    });
+    object.set_visible(context, field(state, "visible")?);
+    object.set_transformed_by_script(field(state, "transformed_by_script")?);
+    if !state["name"].is_null() {
+        object.set_name(context.gc(), json_wstr(context.gc(), &state["name"])?);
+    }
+
+    if !state["drawing"].is_null() {
+        restore_drawing(object, &state["drawing"], vec![])?;
+    }
+
+    object.invalidate_cached_bitmap();
+    Ok(())
+}
+
+/// Calls `f` with the drawing of a display object, if the drawing API was
+/// used on it.
+fn with_drawing_of<R>(object: DisplayObject<'_>, f: impl FnOnce(&Drawing) -> R) -> Option<R> {
+    match object {
+        DisplayObject::MovieClip(clip) => clip.drawing().map(|drawing| f(&drawing)),
+        DisplayObject::Graphic(graphic) => graphic.drawing().map(|drawing| f(&drawing)),
+        _ => None,
+    }
+}
+
+/// Captures the drawing of a display object, bitmap fills included.
+///
+/// The bitmaps of the fills are not captured, see [`restore_drawing`].
+pub(crate) fn capture_drawing(object: DisplayObject<'_>) -> Option<JsonValue> {
+    with_drawing_of(object, drawing::capture)
+}
+
+/// Replaces the drawing of a display object with a captured one.
+///
+/// `bitmaps` are the bitmaps used by the fills of the drawing, in the order
+/// they were originally added to it.
+pub(crate) fn restore_drawing(
+    object: DisplayObject<'_>,
+    drawing: &JsonValue,
+    bitmaps: Vec<BitmapInfo>,
+) -> Result<(), SaveStateError> {
+    let drawing = drawing::restore(drawing, bitmaps)?;
+    if let Some(mut target) = object.as_drawing() {
+        *target = drawing;
+    }
+    Ok(())
+}
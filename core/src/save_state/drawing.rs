@@ -0,0 +1,383 @@
+//! Snapshots of shapes drawn with the drawing API.
+
+use super::{SaveStateError, array, field};
+use crate::drawing::Drawing;
+use ruffle_render::bitmap::BitmapInfo;
+use ruffle_render::shape_utils::{DrawCommand, DrawPath, FillRule, TexturedTriangle};
+use serde_json::{Value as JsonValue, json};
+use swf::{
+    Color, FillStyle, Fixed8, Fixed16, Gradient, GradientInterpolation, GradientRecord,
+    GradientSpread, LineCapStyle, LineJoinStyle, LineStyle, Matrix, Point, Twips,
+};
+
+/// Whether the drawing has bitmap fills, which refer to bitmaps that are
+/// stored outside of the drawing.
+pub(super) fn uses_bitmaps(drawing: &Drawing) -> bool {
+    drawing
+        .distilled_shape()
+        .paths
+        .iter()
+        .any(|path| match path {
+            DrawPath::Fill { style, .. } => matches!(style, FillStyle::Bitmap { .. }),
+            DrawPath::Stroke { style, .. } => {
+                matches!(style.fill_style(), FillStyle::Bitmap { .. })
+            }
+            DrawPath::TexturedTriangles { .. } => true,
+        })
+}
+
+pub(super) fn capture(drawing: &Drawing) -> JsonValue {
+    let paths: Vec<JsonValue> = drawing
+        .distilled_shape()
+        .paths
+        .iter()
+        .map(|path| match path {
+            DrawPath::Fill {
+                style,
+                commands,
+                winding_rule,
+            } => json!({
+                "type": "fill",
+                "style": fill_style_to_json(style),
+                "commands": commands_to_json(commands),
+                "non_zero": *winding_rule == FillRule::NonZero,
+            }),
+            DrawPath::Stroke {
+                style,
+                commands,
+                is_closed,
+            } => json!({
+                "type": "stroke",
+                "style": line_style_to_json(style),
+                "commands": commands_to_json(commands),
+                "closed": is_closed,
+            }),
+            DrawPath::TexturedTriangles {
+                bitmap_id,
+                is_smoothed,
+                is_repeating,
+                triangles,
+            } => json!({
+                "type": "triangles",
+                "bitmap": bitmap_id,
+                "smoothed": is_smoothed,
+                "repeating": is_repeating,
+                "triangles": triangles
+                    .iter()
+                    .map(|triangle| json!({
+                        "points": triangle
+                            .points
+                            .iter()
+                            .flat_map(|point| [point.x.get(), point.y.get()])
+                            .collect::<Vec<_>>(),
+                        "uvt": triangle.uvt,
+                    }))
+                    .collect::<Vec<_>>(),
+            }),
+        })
+        .collect();
+    json!(paths)
+}
+
+/// Recreates a drawing, with `bitmaps` being the bitmaps its fills refer to.
+pub(super) fn restore(
+    value: &JsonValue,
+    bitmaps: Vec<BitmapInfo>,
+) -> Result<Drawing, SaveStateError> {
+    let mut drawing = Drawing::new();
+    for bitmap in bitmaps {
+        drawing.add_bitmap(bitmap);
+    }
+
+    let paths = value
+        .as_array()
+        .ok_or_else(|| SaveStateError::Malformed("drawing is not an array".to_string()))?;
+    for path in paths {
+        let commands = || -> Result<Vec<DrawCommand>, SaveStateError> {
+            array(path, "commands")?
+                .iter()
+                .map(command_from_json)
+                .collect()
+        };
+        match path["type"].as_str() {
+            Some("fill") => {
+                let non_zero: bool = field(path, "non_zero")?;
+                drawing.draw_path(DrawPath::Fill {
+                    style: &fill_style_from_json(&path["style"])?,
+                    commands: commands()?,
+                    winding_rule: if non_zero {
+                        FillRule::NonZero
+                    } else {
+                        FillRule::EvenOdd
+                    },
+                });
+            }
+            Some("stroke") => drawing.draw_path(DrawPath::Stroke {
+                style: &line_style_from_json(&path["style"])?,
+                commands: commands()?,
+                is_closed: field(path, "closed")?,
+            }),
+            Some("triangles") => {
+                let mut triangles = vec![];
+                for triangle in array(path, "triangles")? {
+                    let points: [i32; 6] = field(triangle, "points")?;
+                    triangles.push(TexturedTriangle {
+                        points: [0, 2, 4]
+                            .map(|i| Point::new(Twips::new(points[i]), Twips::new(points[i + 1]))),
+                        uvt: field(triangle, "uvt")?,
+                    });
+                }
+                drawing.draw_path(DrawPath::TexturedTriangles {
+                    bitmap_id: field(path, "bitmap")?,
+                    is_smoothed: field(path, "smoothed")?,
+                    is_repeating: field(path, "repeating")?,
+                    triangles,
+                });
+            }
+            other => {
+                return Err(SaveStateError::Malformed(format!(
+                    "unknown drawing path type {other:?}"
+                )));
+            }
+        }
+    }
+    Ok(drawing)
+}
+
+fn commands_to_json(commands: &[DrawCommand]) -> JsonValue {
+    let commands: Vec<JsonValue> = commands
+        .iter()
+        .map(|command| match command {
+            DrawCommand::MoveTo(point) => json!(["M", point.x.get(), point.y.get()]),
+            DrawCommand::LineTo(point) => json!(["L", point.x.get(), point.y.get()]),
+            DrawCommand::QuadraticCurveTo { control, anchor } => json!([
+                "Q",
+                control.x.get(),
+                control.y.get(),
+                anchor.x.get(),
+                anchor.y.get()
+            ]),
+            DrawCommand::CubicCurveTo {
+                control_a,
+                control_b,
+                anchor,
+            } => json!([
+                "C",
+                control_a.x.get(),
+                control_a.y.get(),
+                control_b.x.get(),
+                control_b.y.get(),
+                anchor.x.get(),
+                anchor.y.get()
+            ]),
+        })
+        .collect();
+    json!(commands)
+}
+
+fn command_from_json(value: &JsonValue) -> Result<DrawCommand, SaveStateError> {
+    let malformed = || SaveStateError::Malformed(format!("invalid drawing command {value}"));
+    let parts = value.as_array().ok_or_else(malformed)?;
+    let coordinates: Vec<i32> = parts
+        .iter()
+        .skip(1)
+        .map(|part| part.as_i64().map(|n| n as i32))
+        .collect::<Option<_>>()
+        .ok_or_else(malformed)?;
+    let point = |i: usize| Point::new(Twips::new(coordinates[i]), Twips::new(coordinates[i + 1]));
+
+    match (
+        parts.first().and_then(|kind| kind.as_str()),
+        coordinates.len(),
+    ) {
+        (Some("M"), 2) => Ok(DrawCommand::MoveTo(point(0))),
+        (Some("L"), 2) => Ok(DrawCommand::LineTo(point(0))),
+        (Some("Q"), 4) => Ok(DrawCommand::QuadraticCurveTo {
+            control: point(0),
+            anchor: point(2),
+        }),
+        (Some("C"), 6) => Ok(DrawCommand::CubicCurveTo {
+            control_a: point(0),
+            control_b: point(2),
+            anchor: point(4),
+        }),
+        _ => Err(malformed()),
+    }
+}
+
+fn color_to_json(color: &Color) -> JsonValue {
+    json!([color.r, color.g, color.b, color.a])
+}
+
+fn color_from_json(value: &JsonValue) -> Result<Color, SaveStateError> {
+    let [r, g, b, a]: [u8; 4] = serde_json::from_value(value.clone())
+        .map_err(|e| SaveStateError::Malformed(format!("color: {e}")))?;
+    Ok(Color { r, g, b, a })
+}
+
+fn matrix_to_json(matrix: &Matrix) -> JsonValue {
+    json!([
+        matrix.a.get(),
+        matrix.b.get(),
+        matrix.c.get(),
+        matrix.d.get(),
+        matrix.tx.get(),
+        matrix.ty.get()
+    ])
+}
+
+fn matrix_from_json(value: &JsonValue) -> Result<Matrix, SaveStateError> {
+    let [a, b, c, d, tx, ty]: [i32; 6] = serde_json::from_value(value.clone())
+        .map_err(|e| SaveStateError::Malformed(format!("matrix: {e}")))?;
+    Ok(Matrix {
+        a: Fixed16::from_bits(a),
+        b: Fixed16::from_bits(b),
+        c: Fixed16::from_bits(c),
+        d: Fixed16::from_bits(d),
+        tx: Twips::new(tx),
+        ty: Twips::new(ty),
+    })
+}
+
+fn gradient_to_json(gradient: &Gradient) -> JsonValue {
+    json!({
+        "matrix": matrix_to_json(&gradient.matrix),
+        "spread": gradient.spread as u8,
+        "linear_rgb": gradient.interpolation == GradientInterpolation::LinearRgb,
+        "records": gradient
+            .records
+            .iter()
+            .map(|record| json!([record.ratio, color_to_json(&record.color)]))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn gradient_from_json(value: &JsonValue) -> Result<Gradient, SaveStateError> {
+    let mut records = vec![];
+    for record in array(value, "records")? {
+        records.push(GradientRecord {
+            ratio: serde_json::from_value(record[0].clone())
+                .map_err(|e| SaveStateError::Malformed(format!("gradient ratio: {e}")))?,
+            color: color_from_json(&record[1])?,
+        });
+    }
+    let linear_rgb: bool = field(value, "linear_rgb")?;
+    Ok(Gradient {
+        matrix: matrix_from_json(&value["matrix"])?,
+        spread: GradientSpread::from_u8(field(value, "spread")?).unwrap_or(GradientSpread::Pad),
+        interpolation: if linear_rgb {
+            GradientInterpolation::LinearRgb
+        } else {
+            GradientInterpolation::Rgb
+        },
+        records,
+    })
+}
+
+fn fill_style_to_json(style: &FillStyle) -> JsonValue {
+    match style {
+        FillStyle::Color(color) => json!({ "type": "color", "color": color_to_json(color) }),
+        FillStyle::LinearGradient(gradient) => {
+            json!({ "type": "linear", "gradient": gradient_to_json(gradient) })
+        }
+        FillStyle::RadialGradient(gradient) => {
+            json!({ "type": "radial", "gradient": gradient_to_json(gradient) })
+        }
+        FillStyle::FocalGradient {
+            gradient,
+            focal_point,
+        } => json!({
+            "type": "focal",
+            "gradient": gradient_to_json(gradient),
+            "focal_point": focal_point.get(),
+        }),
+        FillStyle::Bitmap {
+            id,
+            matrix,
+            is_smoothed,
+            is_repeating,
+        } => json!({
+            "type": "bitmap",
+            "bitmap": id,
+            "matrix": matrix_to_json(matrix),
+            "smoothed": is_smoothed,
+            "repeating": is_repeating,
+        }),
+    }
+}
+
+fn fill_style_from_json(value: &JsonValue) -> Result<FillStyle, SaveStateError> {
+    Ok(match value["type"].as_str() {
+        Some("color") => FillStyle::Color(color_from_json(&value["color"])?),
+        Some("linear") => FillStyle::LinearGradient(gradient_from_json(&value["gradient"])?),
+        Some("radial") => FillStyle::RadialGradient(gradient_from_json(&value["gradient"])?),
+        Some("focal") => FillStyle::FocalGradient {
+            gradient: gradient_from_json(&value["gradient"])?,
+            focal_point: Fixed8::from_bits(field(value, "focal_point")?),
+        },
+        Some("bitmap") => FillStyle::Bitmap {
+            id: field(value, "bitmap")?,
+            matrix: matrix_from_json(&value["matrix"])?,
+            is_smoothed: field(value, "smoothed")?,
+            is_repeating: field(value, "repeating")?,
+        },
+        other => {
+            return Err(SaveStateError::Malformed(format!(
+                "unknown fill style {other:?}"
+            )));
+        }
+    })
+}
+
+fn cap_to_json(cap: LineCapStyle) -> &'static str {
+    match cap {
+        LineCapStyle::Round => "round",
+        LineCapStyle::None => "none",
+        LineCapStyle::Square => "square",
+    }
+}
+
+fn cap_from_json(value: &JsonValue) -> LineCapStyle {
+    match value.as_str() {
+        Some("none") => LineCapStyle::None,
+        Some("square") => LineCapStyle::Square,
+        _ => LineCapStyle::Round,
+    }
+}
+
+fn line_style_to_json(style: &LineStyle) -> JsonValue {
+    let join = match style.join_style() {
+        LineJoinStyle::Round => json!("round"),
+        LineJoinStyle::Bevel => json!("bevel"),
+        LineJoinStyle::Miter(limit) => json!(limit.get()),
+    };
+    json!({
+        "width": style.width().get(),
+        "fill": fill_style_to_json(style.fill_style()),
+        "start_cap": cap_to_json(style.start_cap()),
+        "end_cap": cap_to_json(style.end_cap()),
+        "join": join,
+        "allow_close": style.allow_close(),
+        "allow_scale_x": style.allow_scale_x(),
+        "allow_scale_y": style.allow_scale_y(),
+        "pixel_hinted": style.is_pixel_hinted(),
+    })
+}
+
+fn line_style_from_json(value: &JsonValue) -> Result<LineStyle, SaveStateError> {
+    let join = match &value["join"] {
+        JsonValue::String(join) if join == "bevel" => LineJoinStyle::Bevel,
+        JsonValue::String(_) => LineJoinStyle::Round,
+        _ => LineJoinStyle::Miter(Fixed8::from_bits(field(value, "join")?)),
+    };
+    Ok(LineStyle::new()
+        .with_width(Twips::new(field(value, "width")?))
+        .with_fill_style(fill_style_from_json(&value["fill"])?)
+        .with_start_cap(cap_from_json(&value["start_cap"]))
+        .with_end_cap(cap_from_json(&value["end_cap"]))
+        .with_join_style(join)
+        .with_allow_close(field(value, "allow_close")?)
+        .with_allow_scale_x(field(value, "allow_scale_x")?)
+        .with_allow_scale_y(field(value, "allow_scale_y")?)
+        .with_is_pixel_hinted(field(value, "pixel_hinted")?))
+}
@@ -0,0 +1,171 @@
+//! Snapshots of the event sounds that are playing.
+//!
+//! Stream sounds are not recorded, as they are restarted by their timeline.
+
+use super::{SaveStateError, array, display_object_path, field, find_display_object};
+use crate::avm2::save_state::{HeapReader, HeapWriter};
+use crate::character::Character;
+use crate::context::UpdateContext;
+use crate::display_object::SoundTransform;
+use crate::prelude::*;
+use serde_json::{Value as JsonValue, json};
+use swf::{SoundEnvelopePoint, SoundEvent, SoundInfo};
+
+/// Captures the position and settings of every playing event sound.
+pub(super) fn capture<'gc>(
+    context: &mut UpdateContext<'gc>,
+    mut heap: Option<&mut HeapWriter<'gc>>,
+    blockers: &mut Vec<String>,
+) -> JsonValue {
+    let library = context.library.library_for_movie(context.root_swf.clone());
+    let mut captured = vec![];
+    for sound in context.audio_manager.sounds() {
+        let Some(settings) = sound.settings() else {
+            continue;
+        };
+        let id = library.and_then(|library| {
+            library
+                .characters()
+                .iter()
+                .find_map(|(id, character)| match character {
+                    Character::Sound(handle) if Some(*handle) == sound.sound() => Some(*id),
+                    _ => None,
+                })
+        });
+        let Some(id) = id else {
+            blockers.push("sounds loaded at runtime".to_string());
+            continue;
+        };
+        if sound.avm1_object().is_some() {
+            blockers.push("sounds started by AVM1 Sound objects".to_string());
+            continue;
+        }
+
+        let display_object = match sound.display_object() {
+            Some(object) => match display_object_path(context, object) {
+                Some(path) => json!(path),
+                None => {
+                    blockers.push("sounds of removed display objects".to_string());
+                    continue;
+                }
+            },
+            None => JsonValue::Null,
+        };
+        let channel = match (sound.avm2_object(), heap.as_deref_mut()) {
+            (Some(channel), Some(heap)) => heap.object(channel.into()),
+            _ => JsonValue::Null,
+        };
+
+        let transform = sound.transform();
+        let envelope = settings.envelope.as_ref().map(|envelope| {
+            envelope
+                .iter()
+                .map(|point| json!([point.sample, point.left_volume, point.right_volume]))
+                .collect::<Vec<_>>()
+        });
+        captured.push(json!({
+            "character": id,
+            "position": context.audio.get_sound_position(sound.instance()).unwrap_or(0.0),
+            "transform": [
+                transform.volume,
+                transform.left_to_left,
+                transform.left_to_right,
+                transform.right_to_left,
+                transform.right_to_right,
+            ],
+            "display_object": display_object,
+            "channel": channel,
+            "out_sample": settings.out_sample,
+            "num_loops": settings.num_loops,
+            "envelope": envelope,
+        }));
+    }
+    JsonValue::Array(captured)
+}
+
+/// Stops all sounds, then restarts the captured ones from their position.
+///
+/// A looping sound restarts all of its loops from the captured position, as
+/// the mixer doesn't report which loop a sound was in.
+pub(super) fn restore<'gc>(
+    context: &mut UpdateContext<'gc>,
+    state: &JsonValue,
+    heap: Option<&HeapReader<'gc>>,
+) -> Result<(), SaveStateError> {
+    context.stop_all_sounds();
+
+    let sounds = state
+        .as_array()
+        .ok_or_else(|| SaveStateError::Malformed("sounds are not an array".to_string()))?;
+    for sound in sounds {
+        let id: CharacterId = field(sound, "character")?;
+        let Some(Character::Sound(handle)) = context
+            .library
+            .library_for_movie_mut(context.root_swf.clone())
+            .character_by_id(id)
+        else {
+            return Err(SaveStateError::Malformed(format!("no sound with ID {id}")));
+        };
+
+        // Positions are in milliseconds, while `in_sample` is always counted
+        // at 44.1kHz.
+        let position: f64 = field(sound, "position")?;
+        let envelope = if sound["envelope"].is_null() {
+            None
+        } else {
+            let mut points = vec![];
+            for point in array(sound, "envelope")? {
+                let (sample, left_volume, right_volume) = serde_json::from_value(point.clone())
+                    .map_err(|e| SaveStateError::Malformed(format!("envelope: {e}")))?;
+                points.push(SoundEnvelopePoint {
+                    sample,
+                    left_volume,
+                    right_volume,
+                });
+            }
+            Some(points)
+        };
+        let settings = SoundInfo {
+            event: SoundEvent::Event,
+            in_sample: Some((position * 44.1) as u32),
+            out_sample: field(sound, "out_sample")?,
+            num_loops: field(sound, "num_loops")?,
+            envelope,
+        };
+
+        let [
+            volume,
+            left_to_left,
+            left_to_right,
+            right_to_left,
+            right_to_right,
+        ]: [i32; 5] = field(sound, "transform")?;
+        let transform = SoundTransform {
+            volume,
+            left_to_left,
+            left_to_right,
+            right_to_left,
+            right_to_right,
+        };
+
+        let owner = if sound["display_object"].is_null() {
+            None
+        } else {
+            let path: Vec<Depth> = field(sound, "display_object")?;
+            find_display_object(context, &path)
+        };
+
+        let Some(instance) = context.start_sound(handle, &settings, Some(transform), owner, None)
+        else {
+            continue;
+        };
+        if let (Some(heap), false) = (heap, sound["channel"].is_null()) {
+            let channel = heap.object(&sound["channel"])?;
+            if let Some(channel) = channel.as_sound_channel() {
+                context.attach_avm2_sound_channel(instance, channel);
+                channel.set_sound_instance(context, instance);
+            }
+        }
+    }
+    Ok(())
+}
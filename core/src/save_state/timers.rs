@@ -0,0 +1,304 @@
+//! Snapshots of the timers created by `setInterval`, `setTimeout` and
+//! `flash.utils.Timer`.
+//!
+//! AVM1 callbacks are stored by where they can be found again: as a
+//! variable of a timeline or of `_global`. AVM2 callbacks are stored as part
+//! of the AVM2 heap.
+
+use super::{
+    SaveStateError, array, display_object_path, field, find_display_object, json_wstr, wstr_json,
+};
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier, MovieClipReference, Object as Avm1Object,
+    Value as Avm1Value,
+};
+use crate::avm2::Activation as Avm2Activation;
+use crate::avm2::save_state::{HeapReader, HeapWriter};
+use crate::context::UpdateContext;
+use crate::prelude::*;
+use crate::timer::TimerCallback;
+use serde_json::{Value as JsonValue, json};
+
+/// Captures every active timer.
+pub(super) fn capture<'gc>(
+    context: &mut UpdateContext<'gc>,
+    mut heap: Option<&mut HeapWriter<'gc>>,
+    blockers: &mut Vec<String>,
+) -> JsonValue {
+    let timers: Vec<_> = context
+        .timers
+        .iter()
+        .map(|timer| {
+            (
+                timer.id(),
+                timer.callback().clone(),
+                context.timers.time_until(timer),
+                timer.interval(),
+                timer.is_timeout(),
+            )
+        })
+        .collect();
+
+    let mut captured = vec![];
+    for (id, callback, delay, interval, is_timeout) in timers {
+        let callback = match callback {
+            TimerCallback::Avm1Function { func, params } => {
+                let Some(func) = find_avm1_object(context, func) else {
+                    blockers.push("intervals of unnamed AVM1 functions".to_string());
+                    continue;
+                };
+                json!({
+                    "avm1_function": func,
+                    "params": capture_avm1_params(context, &params, blockers),
+                })
+            }
+            TimerCallback::Avm1Method {
+                this,
+                method_name,
+                params,
+            } => {
+                let Some(this) = find_avm1_object(context, this) else {
+                    blockers.push("intervals of unnamed AVM1 objects".to_string());
+                    continue;
+                };
+                json!({
+                    "avm1_method": this,
+                    "name": wstr_json(&method_name),
+                    "params": capture_avm1_params(context, &params, blockers),
+                })
+            }
+            TimerCallback::Avm2Callback {
+                closure,
+                params,
+                movie,
+            } => {
+                let Some(heap) = heap.as_deref_mut() else {
+                    blockers.push("AVM2 timers in AVM1 movies".to_string());
+                    continue;
+                };
+                let closure = match closure {
+                    Some(closure) => heap.object(closure.into()),
+                    None => JsonValue::Null,
+                };
+                let params: Vec<JsonValue> =
+                    params.into_iter().map(|param| heap.value(param)).collect();
+                json!({
+                    "avm2_closure": closure,
+                    "params": params,
+                    "has_movie": movie.is_some(),
+                })
+            }
+        };
+        captured.push(json!({
+            "id": id,
+            "delay": delay,
+            "interval": interval,
+            "is_timeout": is_timeout,
+            "callback": callback,
+        }));
+    }
+
+    json!({
+        "counter": context.timers.timer_counter(),
+        "timers": captured,
+    })
+}
+
+/// Replaces all timers with the captured ones.
+///
+/// This must run after the display list and the AVM2 heap were restored, as
+/// the callbacks are looked up from them.
+pub(super) fn restore<'gc>(
+    context: &mut UpdateContext<'gc>,
+    state: &JsonValue,
+    heap: Option<&HeapReader<'gc>>,
+) -> Result<(), SaveStateError> {
+    let mut timers = vec![];
+    for timer in array(state, "timers")? {
+        let callback = &timer["callback"];
+        let callback = if let Some(func) = callback.get("avm1_function") {
+            let func = restore_avm1_object(context, func)?;
+            TimerCallback::Avm1Function {
+                func,
+                params: restore_avm1_params(context, callback)?,
+            }
+        } else if let Some(this) = callback.get("avm1_method") {
+            TimerCallback::Avm1Method {
+                this: restore_avm1_object(context, this)?,
+                method_name: json_wstr(context.gc(), &callback["name"])?,
+                params: restore_avm1_params(context, callback)?,
+            }
+        } else {
+            let heap = heap.ok_or_else(|| {
+                SaveStateError::Malformed("AVM2 timer without an AVM2 heap".to_string())
+            })?;
+            let mut activation = Avm2Activation::from_nothing(context);
+            let closure = match &callback["avm2_closure"] {
+                JsonValue::Null => None,
+                closure => heap.object(closure)?.as_function_object(),
+            };
+            let mut params = vec![];
+            for param in array(callback, "params")? {
+                params.push(heap.value(&mut activation, param)?);
+            }
+            TimerCallback::Avm2Callback {
+                closure,
+                params,
+                movie: (callback["has_movie"] == true).then(|| activation.context.root_swf.clone()),
+            }
+        };
+        timers.push((
+            field(timer, "id")?,
+            callback,
+            field(timer, "delay")?,
+            field(timer, "interval")?,
+            field(timer, "is_timeout")?,
+        ));
+    }
+
+    context.timers.replace_all(timers, field(state, "counter")?);
+    Ok(())
+}
+
+/// Finds where an AVM1 object can be found again: either a display object,
+/// or a variable of a timeline or of `_global`.
+fn find_avm1_object<'gc>(
+    context: &UpdateContext<'gc>,
+    object: Avm1Object<'gc>,
+) -> Option<JsonValue> {
+    if let Some(display_object) = object.as_display_object() {
+        return display_object_path(context, display_object).map(|path| json!({ "path": path }));
+    }
+
+    let swf_version = context.root_swf.version();
+    let global = context.avm1.global_scope(swf_version).locals_cell();
+    if let Some(name) = find_property(global, object) {
+        return Some(json!({ "global": name }));
+    }
+
+    let mut found = None;
+    visit_display_objects(context.stage.into(), &mut vec![], &mut |path, child| {
+        if found.is_none()
+            && let Some(name) = child.object1().and_then(|o| find_property(o, object))
+        {
+            found = Some(json!({ "path": path, "property": name }));
+        }
+    });
+    found
+}
+
+fn find_property<'gc>(holder: Avm1Object<'gc>, object: Avm1Object<'gc>) -> Option<JsonValue> {
+    holder
+        .own_properties()
+        .into_iter()
+        .find(|(_, value)| {
+            matches!(value, Avm1Value::Object(value) if Avm1Object::ptr_eq(*value, object))
+        })
+        .map(|(name, _)| wstr_json(&name))
+}
+
+fn visit_display_objects<'gc>(
+    object: DisplayObject<'gc>,
+    path: &mut Vec<Depth>,
+    f: &mut impl FnMut(&[Depth], DisplayObject<'gc>),
+) {
+    f(path, object);
+    if let Some(container) = object.as_container() {
+        for child in container.iter_render_list() {
+            path.push(child.depth());
+            visit_display_objects(child, path, f);
+            path.pop();
+        }
+    }
+}
+
+fn restore_avm1_object<'gc>(
+    context: &mut UpdateContext<'gc>,
+    location: &JsonValue,
+) -> Result<Avm1Object<'gc>, SaveStateError> {
+    let missing = || SaveStateError::Malformed(format!("no AVM1 object at {location}"));
+
+    let mut activation =
+        Avm1Activation::try_from_stub(context, ActivationIdentifier::root("[Load State]"))
+            .ok_or_else(|| SaveStateError::Malformed("movie has no root".to_string()))?;
+
+    let holder = if location.get("global").is_some() {
+        activation.global_object()
+    } else {
+        let path: Vec<Depth> = field(location, "path")?;
+        let object = find_display_object(activation.context, &path).ok_or_else(missing)?;
+        if location.get("property").is_none() {
+            return object.object1().ok_or_else(missing);
+        }
+        object.object1().ok_or_else(missing)?
+    };
+
+    let name = location
+        .get("global")
+        .or_else(|| location.get("property"))
+        .ok_or_else(missing)?;
+    let name = json_wstr(activation.gc(), name)?;
+    holder
+        .get_stored_property(&mut activation, name)
+        .and_then(|value| value.as_object(&mut activation))
+        .ok_or_else(missing)
+}
+
+/// Captures the parameters of an AVM1 timer, which can be primitives or
+/// display objects.
+fn capture_avm1_params<'gc>(
+    context: &UpdateContext<'gc>,
+    params: &[Avm1Value<'gc>],
+    blockers: &mut Vec<String>,
+) -> Vec<JsonValue> {
+    params
+        .iter()
+        .map(|param| match param {
+            Avm1Value::Undefined => json!({ "undefined": true }),
+            Avm1Value::Null => JsonValue::Null,
+            Avm1Value::Bool(value) => json!(value),
+            Avm1Value::Number(value) => json!(value.to_string()),
+            Avm1Value::String(value) => json!({ "string": wstr_json(value) }),
+            Avm1Value::Object(object) => {
+                let path = object
+                    .as_display_object()
+                    .and_then(|object| display_object_path(context, object));
+                if path.is_none() {
+                    blockers.push("AVM1 timers with object parameters".to_string());
+                }
+                json!({ "path": path })
+            }
+            Avm1Value::MovieClip(clip) => json!({ "clip": wstr_json(&clip.path()) }),
+        })
+        .collect()
+}
+
+fn restore_avm1_params<'gc>(
+    context: &mut UpdateContext<'gc>,
+    callback: &JsonValue,
+) -> Result<Vec<Avm1Value<'gc>>, SaveStateError> {
+    let mut params = vec![];
+    for param in array(callback, "params")? {
+        params.push(match param {
+            JsonValue::Null => Avm1Value::Null,
+            JsonValue::Bool(value) => Avm1Value::Bool(*value),
+            JsonValue::String(value) => Avm1Value::Number(value.parse().unwrap_or(f64::NAN)),
+            param if param.get("undefined").is_some() => Avm1Value::Undefined,
+            param if param.get("string").is_some() => {
+                json_wstr(context.gc(), &param["string"])?.into()
+            }
+            param if param.get("clip").is_some() => {
+                let path = json_wstr(context.gc(), &param["clip"])?;
+                Avm1Value::MovieClip(MovieClipReference::from_path(context.gc(), &path))
+            }
+            param => {
+                let path: Vec<Depth> = field(param, "path")?;
+                find_display_object(context, &path)
+                    .and_then(|object| object.object1())
+                    .map(Avm1Value::from)
+                    .unwrap_or(Avm1Value::Undefined)
+            }
+        });
+    }
+    Ok(params)
+}
@@ -0,0 +1,604 @@
+//! Flash Player's security sandbox model, see
+//! https://help.adobe.com/en_US/as3/dev/WS5b3ccc516d4fbf351e63e3d118a9b90204-7e3f.html
+//!
+//! This keeps track of the permissions that movies grant each other through
+//! `Security.allowDomain`, and of the cross-domain policy files
+//! (`crossdomain.xml`) that servers use to grant movies access to their data.
+
+use crate::context::UpdateContext;
+use crate::tag_utils::SwfMovie;
+use async_channel::{Receiver, Sender, bounded};
+use quick_xml::{Reader, events::Event};
+use ruffle_common::sandbox::SandboxType;
+use std::collections::{HashMap, HashSet};
+use url::Url;
+
+/// The port that socket policy files are requested from, unless a movie
+/// specified another one with `Security.loadPolicyFile`.
+pub const DEFAULT_SOCKET_POLICY_PORT: u16 = 843;
+
+/// The data a movie sends to a socket policy server to request its policy.
+pub const SOCKET_POLICY_REQUEST: &[u8] = b"<policy-file-request/>\0";
+
+/// The meta-policy of a server, declared by the `<site-control>` tag of its
+/// master policy file.
+///
+/// It decides which policy files on the server are allowed to grant access.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetaPolicy {
+    /// No policy file grants access, not even the master policy file.
+    None,
+
+    /// Only the master policy file (`/crossdomain.xml`) grants access.
+    MasterOnly,
+
+    /// Any policy file on the server grants access.
+    ///
+    /// This also covers the `by-content-type` and `by-ftp-filename`
+    /// meta-policies, as we can't tell which content types were served.
+    All,
+}
+
+impl MetaPolicy {
+    fn from_attribute(value: &str) -> Option<Self> {
+        match value {
+            "none" | "none-this-response" => Some(Self::None),
+            "master-only" => Some(Self::MasterOnly),
+            "by-content-type" | "by-ftp-filename" | "all" => Some(Self::All),
+            _ => None,
+        }
+    }
+}
+
+/// A single `<allow-access-from>` entry of a policy file.
+#[derive(Clone, Debug)]
+struct AccessRule {
+    /// The domain pattern that is granted access, such as `*`,
+    /// `*.example.com` or `www.example.com`.
+    domain: String,
+
+    /// Whether movies served over HTTP are denied access to a policy
+    /// served over HTTPS.
+    secure: bool,
+
+    /// The port ranges that are accessible, for socket policy files.
+    ///
+    /// `None` means that every port is accessible.
+    to_ports: Option<Vec<(u16, u16)>>,
+}
+
+impl AccessRule {
+    fn allows_port(&self, port: u16) -> bool {
+        match &self.to_ports {
+            Some(ranges) => ranges
+                .iter()
+                .any(|(start, end)| (*start..=*end).contains(&port)),
+            None => true,
+        }
+    }
+}
+
+/// A parsed cross-domain policy file.
+#[derive(Clone, Debug, Default)]
+pub struct PolicyFile {
+    meta_policy: Option<MetaPolicy>,
+    rules: Vec<AccessRule>,
+}
+
+impl PolicyFile {
+    /// Parses a policy file, returning `None` if it's not well-formed or
+    /// lacks a `<cross-domain-policy>` root element.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let mut reader = Reader::from_reader(data);
+        let mut policy = Self::default();
+        let mut depth = 0;
+        let mut has_root = false;
+
+        loop {
+            let (event, is_empty) = match reader.read_event() {
+                Ok(Event::Start(e)) => (e, false),
+                Ok(Event::Empty(e)) => (e, true),
+                Ok(Event::End(_)) => {
+                    depth -= 1;
+                    continue;
+                }
+                Ok(Event::Eof) => break,
+                Ok(_) => continue,
+                Err(e) => {
+                    tracing::warn!("Error while parsing policy file: {e}");
+                    return None;
+                }
+            };
+
+            let name = event.name();
+            let attribute = |key: &[u8]| {
+                let attribute = event
+                    .attributes()
+                    .with_checks(false)
+                    .flatten()
+                    .find(|attribute| attribute.key.as_ref() == key)?;
+                Some(String::from_utf8_lossy(&attribute.value).trim().to_owned())
+            };
+
+            match (depth, name.as_ref()) {
+                (0, b"cross-domain-policy") => has_root = true,
+                (1, b"site-control") if has_root => {
+                    policy.meta_policy = attribute(b"permitted-cross-domain-policies")
+                        .and_then(|value| MetaPolicy::from_attribute(&value));
+                }
+                (1, b"allow-access-from") if has_root => {
+                    if let Some(domain) = attribute(b"domain") {
+                        let secure = attribute(b"secure").as_deref() != Some("false");
+                        let to_ports = attribute(b"to-ports").and_then(|ports| parse_ports(&ports));
+                        policy.rules.push(AccessRule {
+                            domain,
+                            secure,
+                            to_ports,
+                        });
+                    }
+                }
+                _ => {}
+            }
+
+            if !is_empty {
+                depth += 1;
+            }
+        }
+
+        has_root.then_some(policy)
+    }
+
+    /// Whether this policy grants access to a movie from `accessor`.
+    ///
+    /// `policy_is_secure` tells whether the policy itself was served over
+    /// HTTPS, and `port` is the port being accessed for socket policies.
+    fn allows(&self, accessor: &Url, policy_is_secure: bool, port: Option<u16>) -> bool {
+        let accessor_is_secure = accessor.scheme() == "https";
+        let host = accessor.host_str().unwrap_or_default();
+
+        self.rules.iter().any(|rule| {
+            domain_matches(&rule.domain, host)
+                && !(policy_is_secure && rule.secure && !accessor_is_secure)
+                && port.is_none_or(|port| rule.allows_port(port))
+        })
+    }
+}
+
+/// Parses the `to-ports` attribute of a socket policy, such as `*` or
+/// `507,516-523`.
+///
+/// Returns `None` if every port is allowed.
+fn parse_ports(ports: &str) -> Option<Vec<(u16, u16)>> {
+    if ports == "*" {
+        return None;
+    }
+
+    Some(
+        ports
+            .split(',')
+            .filter_map(|range| {
+                let range = range.trim();
+                match range.split_once('-') {
+                    Some((start, end)) => {
+                        Some((start.trim().parse().ok()?, end.trim().parse().ok()?))
+                    }
+                    None => {
+                        let port = range.parse().ok()?;
+                        Some((port, port))
+                    }
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Whether a domain pattern from a policy file or `Security.allowDomain`
+/// matches the given host.
+fn domain_matches(pattern: &str, host: &str) -> bool {
+    if pattern == "*" {
+        return true;
+    }
+    if host.is_empty() {
+        return false;
+    }
+
+    let pattern = pattern.to_ascii_lowercase();
+    let host = host.to_ascii_lowercase();
+    match pattern.strip_prefix("*.") {
+        Some(suffix) => {
+            host == suffix
+                || host
+                    .strip_suffix(suffix)
+                    .is_some_and(|prefix| prefix.ends_with('.'))
+        }
+        None => pattern == host,
+    }
+}
+
+/// Whether two URLs belong to the same security domain.
+///
+/// Remote URLs must share their protocol, host and port. Local URLs all
+/// belong to the same domain.
+fn same_origin(a: &Url, b: &Url) -> bool {
+    if a.scheme() == "file" || b.scheme() == "file" {
+        return a.scheme() == b.scheme();
+    }
+
+    a.scheme() == b.scheme()
+        && a.host_str() == b.host_str()
+        && a.port_or_known_default() == b.port_or_known_default()
+}
+
+/// The loading state of a policy file.
+#[derive(Clone, Debug)]
+pub enum PolicyFileState {
+    Loading,
+    Loaded(PolicyFile),
+    Failed,
+}
+
+/// A permission granted by `Security.allowDomain` or
+/// `Security.allowInsecureDomain`.
+#[derive(Clone, Debug)]
+struct DomainGrant {
+    domain: String,
+
+    /// Whether movies served over HTTP may access a movie served over HTTPS.
+    insecure: bool,
+}
+
+/// The reason why a movie can't access some content.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessDenied {
+    /// The content is a movie that didn't call `Security.allowDomain` for
+    /// the accessing movie.
+    NotAllowed,
+
+    /// The content requires a policy file, but it wasn't loaded with
+    /// `checkPolicyFile` set.
+    PolicyFileNotChecked,
+
+    /// None of the policy files of the content's server grant access.
+    NoPolicyFileGrant,
+}
+
+/// Keeps track of everything movies have been granted access to.
+#[derive(Default)]
+pub struct SecurityManager {
+    /// Local locations whose movies are trusted by the user, and run in the
+    /// `localTrusted` sandbox.
+    trusted_locations: Vec<Url>,
+
+    /// Policy files, by the URL they were loaded from.
+    policy_files: HashMap<String, PolicyFileState>,
+
+    /// Loads waiting for policy files to be resolved, by the URL of the
+    /// policy file. Dropping the senders wakes the loads up.
+    policy_file_waiters: HashMap<String, Vec<Sender<()>>>,
+
+    /// Socket policy files, by the host they were requested from.
+    socket_policy_files: HashMap<String, PolicyFileState>,
+
+    /// Ports to request socket policy files from, as set by
+    /// `Security.loadPolicyFile("xmlsocket://host:port")`.
+    socket_policy_ports: HashMap<String, u16>,
+
+    /// Permissions granted by `Security.allowDomain`, by the URL of the
+    /// granting movie.
+    domain_grants: HashMap<String, Vec<DomainGrant>>,
+
+    /// Final URLs, after redirects, of media that were loaded with
+    /// `checkPolicyFile` set.
+    policy_checked_urls: HashSet<String>,
+}
+
+impl SecurityManager {
+    pub fn new(trusted_locations: Vec<Url>) -> Self {
+        Self {
+            trusted_locations,
+            ..Default::default()
+        }
+    }
+
+    /// The sandbox a movie runs in.
+    ///
+    /// This is the sandbox inferred when loading the movie, unless it's a
+    /// local movie from a trusted location.
+    pub fn sandbox_type(&self, movie: &SwfMovie) -> SandboxType {
+        let sandbox_type = movie.sandbox_type();
+        if !matches!(
+            sandbox_type,
+            SandboxType::LocalWithFile | SandboxType::LocalWithNetwork
+        ) {
+            return sandbox_type;
+        }
+
+        let Ok(url) = Url::parse(movie.url()) else {
+            return sandbox_type;
+        };
+        let is_trusted = self.trusted_locations.iter().any(|location| {
+            location.scheme() == url.scheme()
+                && location.host_str() == url.host_str()
+                && url.path().starts_with(location.path())
+        });
+        if is_trusted {
+            SandboxType::LocalTrusted
+        } else {
+            sandbox_type
+        }
+    }
+
+    /// Whether two movies come from the same security domain.
+    pub fn same_domain(a: &SwfMovie, b: &SwfMovie) -> bool {
+        match (Url::parse(a.url()), Url::parse(b.url())) {
+            (Ok(a), Ok(b)) => same_origin(&a, &b),
+            _ => a.url() == b.url(),
+        }
+    }
+
+    /// Grants movies from `domain` access to `movie`, as done by
+    /// `Security.allowDomain` and `Security.allowInsecureDomain`.
+    pub fn allow_domain(&mut self, movie: &SwfMovie, domain: &str, insecure: bool) {
+        // Both bare domains and URLs are accepted.
+        let domain = match Url::parse(domain) {
+            Ok(url) if url.has_host() => url.host_str().unwrap_or_default().to_owned(),
+            _ => domain.trim().to_owned(),
+        };
+
+        self.domain_grants
+            .entry(movie.url().to_owned())
+            .or_default()
+            .push(DomainGrant { domain, insecure });
+    }
+
+    /// Whether `target` called `Security.allowDomain` for the domain of
+    /// `accessor`.
+    fn is_granted(&self, target: &SwfMovie, accessor: &SwfMovie) -> bool {
+        let Some(grants) = self.domain_grants.get(target.url()) else {
+            return false;
+        };
+        let Ok(accessor_url) = Url::parse(accessor.url()) else {
+            return false;
+        };
+        let target_is_secure = target.url().starts_with("https:");
+        let accessor_is_secure = accessor_url.scheme() == "https";
+        let host = accessor_url.host_str().unwrap_or_default();
+
+        grants.iter().any(|grant| {
+            domain_matches(&grant.domain, host)
+                && (grant.insecure || !target_is_secure || accessor_is_secure)
+        })
+    }
+
+    /// Whether code from `accessor` may script `target`.
+    pub fn can_script(&self, accessor: &SwfMovie, target: &SwfMovie) -> bool {
+        // A movie can always script itself, and this is by far the most
+        // common case, so don't bother parsing any URLs for it.
+        if accessor.url() == target.url() {
+            return true;
+        }
+
+        let accessor_sandbox = self.sandbox_type(accessor);
+        let target_sandbox = self.sandbox_type(target);
+
+        match (accessor_sandbox, target_sandbox) {
+            (SandboxType::LocalTrusted | SandboxType::Application, _) => true,
+            // Flash Player refuses to load local movies across local sandboxes
+            // (error #2140), so local movies that do meet only ever share one.
+            (SandboxType::Remote, SandboxType::Remote) => {
+                Self::same_domain(accessor, target) || self.is_granted(target, accessor)
+            }
+            (SandboxType::Remote, _) | (_, SandboxType::Remote) => {
+                self.is_granted(target, accessor)
+            }
+            _ => true,
+        }
+    }
+
+    /// Whether code from `accessor` may read the pixels or data of
+    /// `content`, which is either a movie or loaded media like an image.
+    pub fn check_content_access(
+        &self,
+        accessor: &SwfMovie,
+        content: &SwfMovie,
+    ) -> Result<(), AccessDenied> {
+        if self.can_script(accessor, content) {
+            return Ok(());
+        }
+        if content.is_movie() {
+            return Err(AccessDenied::NotAllowed);
+        }
+        if !self.policy_checked_urls.contains(content.url()) {
+            return Err(AccessDenied::PolicyFileNotChecked);
+        }
+
+        match (Url::parse(content.url()), Url::parse(accessor.url())) {
+            (Ok(content_url), Ok(accessor_url))
+                if self.policy_permits(&content_url, &accessor_url) =>
+            {
+                Ok(())
+            }
+            _ => Err(AccessDenied::NoPolicyFileGrant),
+        }
+    }
+
+    /// Whether any loaded policy file covering `content` grants access to
+    /// `accessor`.
+    fn policy_permits(&self, content: &Url, accessor: &Url) -> bool {
+        let Some(master_url) = master_policy_file_url(content) else {
+            return false;
+        };
+        let meta_policy = match self.policy_files.get(master_url.as_str()) {
+            Some(PolicyFileState::Loaded(policy)) => {
+                policy.meta_policy.unwrap_or(MetaPolicy::MasterOnly)
+            }
+            _ => MetaPolicy::MasterOnly,
+        };
+
+        self.policy_files.iter().any(|(url, state)| {
+            let PolicyFileState::Loaded(policy) = state else {
+                return false;
+            };
+            let Ok(url) = Url::parse(url) else {
+                return false;
+            };
+            let is_permitted = match meta_policy {
+                MetaPolicy::None => false,
+                MetaPolicy::MasterOnly => url == master_url,
+                MetaPolicy::All => true,
+            };
+
+            // A policy file only covers its own directory and the ones below.
+            let directory = &url.path()[..url.path().rfind('/').map_or(0, |i| i + 1)];
+            is_permitted
+                && same_origin(&url, content)
+                && content.path().starts_with(directory)
+                && policy.allows(accessor, url.scheme() == "https", None)
+        })
+    }
+
+    /// Records that content was served from `url` with `checkPolicyFile`
+    /// set, and returns the URL of the master policy file covering it.
+    ///
+    /// `url` is the final URL of the content, after redirects.
+    pub fn check_policy_file(&mut self, url: &Url) -> Option<Url> {
+        self.policy_checked_urls.insert(url.to_string());
+        master_policy_file_url(url)
+    }
+
+    /// Marks the policy file at `url` as loading, returning `false` if it
+    /// was already requested.
+    pub fn start_policy_file_load(&mut self, url: &str) -> bool {
+        if self.policy_files.contains_key(url) {
+            return false;
+        }
+        self.policy_files
+            .insert(url.to_owned(), PolicyFileState::Loading);
+        true
+    }
+
+    pub fn set_policy_file(&mut self, url: String, state: PolicyFileState) {
+        self.policy_file_waiters.remove(&url);
+        self.policy_files.insert(url, state);
+    }
+
+    /// Returns a receiver that is closed once the policy file at `url` has
+    /// loaded or failed to, or `None` if it's not loading.
+    pub fn wait_for_policy_file(&mut self, url: &str) -> Option<Receiver<()>> {
+        if !matches!(self.policy_files.get(url), Some(PolicyFileState::Loading)) {
+            return None;
+        }
+
+        let (sender, receiver) = bounded(1);
+        self.policy_file_waiters
+            .entry(url.to_owned())
+            .or_default()
+            .push(sender);
+        Some(receiver)
+    }
+
+    /// Sets the port to request socket policy files of `host` from.
+    pub fn set_socket_policy_port(&mut self, host: String, port: u16) {
+        self.socket_policy_ports.insert(host, port);
+    }
+
+    /// The port to request socket policy files of `host` from.
+    pub fn socket_policy_port(&self, host: &str) -> u16 {
+        self.socket_policy_ports
+            .get(host)
+            .copied()
+            .unwrap_or(DEFAULT_SOCKET_POLICY_PORT)
+    }
+
+    /// Whether `accessor` needs a socket policy before connecting to sockets.
+    pub fn requires_socket_policy(&self, accessor: &SwfMovie) -> bool {
+        self.sandbox_type(accessor) == SandboxType::Remote
+    }
+
+    /// Marks the socket policy of `host` as loading, returning `false` if it
+    /// was already requested.
+    pub fn start_socket_policy_load(&mut self, host: &str) -> bool {
+        if self.socket_policy_files.contains_key(host) {
+            return false;
+        }
+        self.socket_policy_files
+            .insert(host.to_owned(), PolicyFileState::Loading);
+        true
+    }
+
+    pub fn set_socket_policy(&mut self, host: String, state: PolicyFileState) {
+        self.socket_policy_files.insert(host, state);
+    }
+
+    /// Whether the socket policy of `host` allows `accessor` to connect to
+    /// `port`, or `None` if the policy is still loading.
+    pub fn socket_permitted(&self, accessor: &SwfMovie, host: &str, port: u16) -> Option<bool> {
+        match self.socket_policy_files.get(host)? {
+            PolicyFileState::Loading => None,
+            PolicyFileState::Failed => Some(false),
+            PolicyFileState::Loaded(policy) => Some(
+                Url::parse(accessor.url())
+                    .is_ok_and(|accessor| policy.allows(&accessor, false, Some(port))),
+            ),
+        }
+    }
+}
+
+/// The URL of the master policy file of the server hosting `url`.
+fn master_policy_file_url(url: &Url) -> Option<Url> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    url.join("/crossdomain.xml").ok()
+}
+
+/// Starts loading the policy file at `url`, unless it was already requested.
+pub fn load_policy_file(context: &mut UpdateContext<'_>, url: Url) {
+    if context.security.start_policy_file_load(url.as_str()) {
+        let future = crate::loader::load_policy_file(context, url.to_string());
+        context.navigator.spawn_future(future);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_policy_file() {
+        let policy = PolicyFile::parse(
+            br#"<?xml version="1.0"?>
+            <!DOCTYPE cross-domain-policy SYSTEM "http://www.adobe.com/xml/dtds/cross-domain-policy.dtd">
+            <cross-domain-policy>
+                <site-control permitted-cross-domain-policies="all"/>
+                <allow-access-from domain="*.example.com" secure="false"/>
+                <allow-access-from domain="sockets.test" to-ports="507,516-523"/>
+            </cross-domain-policy>"#,
+        )
+        .unwrap();
+
+        assert_eq!(policy.meta_policy, Some(MetaPolicy::All));
+        let url = |url| Url::parse(url).unwrap();
+        assert!(policy.allows(&url("http://www.example.com/a.swf"), true, None));
+        assert!(policy.allows(&url("http://example.com/a.swf"), false, None));
+        assert!(!policy.allows(&url("http://badexample.com/a.swf"), false, None));
+        assert!(policy.allows(&url("http://sockets.test/a.swf"), false, Some(520)));
+        assert!(!policy.allows(&url("http://sockets.test/a.swf"), false, Some(524)));
+        assert!(!policy.allows(&url("http://sockets.test/a.swf"), true, None));
+    }
+
+    #[test]
+    fn reject_invalid_policy_file() {
+        assert!(PolicyFile::parse(b"<allow-access-from domain=\"*\"/>").is_none());
+        assert!(PolicyFile::parse(b"<cross-domain-policy><oops></cross-domain-policy>").is_none());
+    }
+
+    #[test]
+    fn match_domains() {
+        assert!(domain_matches("*", ""));
+        assert!(domain_matches("Example.com", "example.COM"));
+        assert!(domain_matches("*.example.com", "a.b.example.com"));
+        assert!(!domain_matches("*.example.com", "notexample.com"));
+        assert!(!domain_matches("example.com", ""));
+    }
+}
@@ -6,7 +6,11 @@ use crate::avm2::object::{EventObject, SocketObject};
 use crate::avm2::{Activation as Avm2Activation, Avm2};
 use crate::backend::navigator::NavigatorBackend;
 use crate::context::UpdateContext;
+use crate::security::{
+    DEFAULT_SOCKET_POLICY_PORT, PolicyFile, PolicyFileState, SOCKET_POLICY_REQUEST, SecurityManager,
+};
 use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
 
 use async_channel::{Receiver, Sender, unbounded};
 use gc_arena::Collect;
@@ -14,6 +18,8 @@ use ruffle_macros::istr;
 use slotmap::{SlotMap, new_key_type};
 use std::{
     cell::{Cell, RefCell},
+    collections::HashMap,
+    sync::Arc,
     time::Duration,
 };
 
@@ -26,6 +32,8 @@ new_key_type! {
 enum SocketKind<'gc> {
     Avm2(SocketObject<'gc>),
     Avm1(Avm1Object<'gc>),
+    /// A connection to a socket policy server, see [`SocketPolicyRequest`].
+    Policy,
 }
 
 #[derive(Collect)]
@@ -53,6 +61,30 @@ pub enum ConnectionState {
     TimedOut,
 }
 
+/// A request for the socket policy of a host, made before a movie in the
+/// remote sandbox may connect to it.
+struct SocketPolicyRequest {
+    host: String,
+
+    /// The port to request the policy from next, if this request fails.
+    fallback_port: Option<u16>,
+
+    timeout: Duration,
+
+    /// The data received from the policy server so far.
+    data: Vec<u8>,
+}
+
+/// A connection that waits for the socket policy of its host.
+struct PendingConnection {
+    /// The movie that requested the connection.
+    movie: Arc<SwfMovie>,
+    host: String,
+    port: u16,
+    timeout: Duration,
+    receiver: Receiver<Vec<u8>>,
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum SocketAction {
     Connect(SocketHandle, ConnectionState),
@@ -66,6 +98,17 @@ pub enum SocketAction {
 pub struct Sockets<'gc> {
     sockets: SlotMap<SocketHandle, Socket<'gc>>,
 
+    #[collect(require_static)]
+    policy_requests: HashMap<SocketHandle, SocketPolicyRequest>,
+
+    #[collect(require_static)]
+    pending_connections: HashMap<SocketHandle, PendingConnection>,
+
+    /// Connections that were refused by the socket policy of their host,
+    /// and that still need to report a security error.
+    #[collect(require_static)]
+    denied_connections: Vec<(SocketHandle, PendingConnection)>,
+
     #[collect(require_static)]
     receiver: Receiver<SocketAction>,
     #[collect(require_static)]
@@ -78,14 +121,24 @@ impl<'gc> Sockets<'gc> {
 
         Self {
             sockets: SlotMap::with_key(),
+            policy_requests: HashMap::new(),
+            pending_connections: HashMap::new(),
+            denied_connections: Vec::new(),
             receiver,
             sender,
         }
     }
 
+    /// Whether there are no sockets open.
+    pub fn is_empty(&self) -> bool {
+        self.sockets.is_empty()
+    }
+
     pub fn connect_avm2(
         &mut self,
         backend: &mut dyn NavigatorBackend,
+        security: &mut SecurityManager,
+        movie: Arc<SwfMovie>,
         target: SocketObject<'gc>,
         host: String,
         port: u16,
@@ -95,15 +148,47 @@ impl<'gc> Sockets<'gc> {
         let socket = Socket::new(SocketKind::Avm2(target), sender);
         let handle = self.sockets.insert(socket);
 
-        // NOTE: This call will send SocketAction::Connect to sender with connection status.
-        backend.connect_socket(
-            sanitize_host(&host).to_string(),
-            port,
-            Duration::from_millis(target.timeout().into()),
-            handle,
-            receiver,
-            self.sender.clone(),
-        );
+        let host = sanitize_host(&host).to_string();
+        let timeout = Duration::from_millis(target.timeout().into());
+        let permitted = if security.requires_socket_policy(&movie) {
+            security.socket_permitted(&movie, &host, port)
+        } else {
+            Some(true)
+        };
+
+        if permitted == Some(true) {
+            // NOTE: This call will send SocketAction::Connect to sender with connection status.
+            backend.connect_socket(host, port, timeout, handle, receiver, self.sender.clone());
+        } else {
+            let pending = PendingConnection {
+                movie,
+                host,
+                port,
+                timeout,
+                receiver,
+            };
+
+            if permitted == Some(false) {
+                self.denied_connections.push((handle, pending));
+            } else {
+                if security.start_socket_policy_load(&pending.host) {
+                    let policy_port = security.socket_policy_port(&pending.host);
+                    // Without a policy server on the default port, the policy
+                    // is requested from the destination port itself.
+                    let fallback_port = (policy_port == DEFAULT_SOCKET_POLICY_PORT
+                        && port != policy_port)
+                        .then_some(port);
+                    self.request_socket_policy(
+                        backend,
+                        pending.host.clone(),
+                        policy_port,
+                        fallback_port,
+                        timeout,
+                    );
+                }
+                self.pending_connections.insert(handle, pending);
+            }
+        }
 
         if let Some(existing_handle) = target.set_handle(handle) {
             // As written in the AS3 docs, we are supposed to close the existing connection,
@@ -146,6 +231,123 @@ impl<'gc> Sockets<'gc> {
         }
     }
 
+    /// Connects to the socket policy server of `host` to request its policy.
+    fn request_socket_policy(
+        &mut self,
+        backend: &mut dyn NavigatorBackend,
+        host: String,
+        port: u16,
+        fallback_port: Option<u16>,
+        timeout: Duration,
+    ) {
+        let (sender, receiver) = unbounded();
+
+        let socket = Socket::new(SocketKind::Policy, sender);
+        let handle = self.sockets.insert(socket);
+        self.policy_requests.insert(
+            handle,
+            SocketPolicyRequest {
+                host: host.clone(),
+                fallback_port,
+                timeout,
+                data: Vec::new(),
+            },
+        );
+
+        backend.connect_socket(host, port, timeout, handle, receiver, self.sender.clone());
+    }
+
+    /// Stores the socket policy received by a policy request, and resolves
+    /// the connections that were waiting for it.
+    fn finish_socket_policy_request(context: &mut UpdateContext<'gc>, handle: SocketHandle) {
+        let Some(request) = context.sockets.policy_requests.remove(&handle) else {
+            return;
+        };
+        context.sockets.close(handle);
+
+        let policy = match request.data.iter().position(|&b| b == 0) {
+            Some(end) => &request.data[..end],
+            None => &request.data[..],
+        };
+        let state = match (PolicyFile::parse(policy), request.fallback_port) {
+            (Some(policy), _) => PolicyFileState::Loaded(policy),
+            (None, Some(fallback_port)) => {
+                // The connections keep waiting for the next request.
+                context.sockets.request_socket_policy(
+                    context.navigator,
+                    request.host,
+                    fallback_port,
+                    None,
+                    request.timeout,
+                );
+                return;
+            }
+            (None, None) => {
+                tracing::warn!("No valid socket policy received from {}", request.host);
+                PolicyFileState::Failed
+            }
+        };
+        context
+            .security
+            .set_socket_policy(request.host.clone(), state);
+
+        let handles: Vec<_> = context
+            .sockets
+            .pending_connections
+            .iter()
+            .filter(|(_, pending)| pending.host == request.host)
+            .map(|(handle, _)| *handle)
+            .collect();
+        for handle in handles {
+            let Some(pending) = context.sockets.pending_connections.remove(&handle) else {
+                continue;
+            };
+
+            let permitted =
+                context
+                    .security
+                    .socket_permitted(&pending.movie, &pending.host, pending.port);
+            if permitted == Some(true) {
+                context.navigator.connect_socket(
+                    pending.host,
+                    pending.port,
+                    pending.timeout,
+                    handle,
+                    pending.receiver,
+                    context.sockets.sender.clone(),
+                );
+            } else {
+                Self::deny_connection(context, handle, &pending);
+            }
+        }
+    }
+
+    /// Reports a connection refused by the socket policy of its host.
+    fn deny_connection(
+        context: &mut UpdateContext<'gc>,
+        handle: SocketHandle,
+        connection: &PendingConnection,
+    ) {
+        let Some(socket) = context.sockets.sockets.remove(handle) else {
+            return;
+        };
+
+        if let SocketKind::Avm2(target) = socket.target {
+            let mut activation = Avm2Activation::from_nothing(context);
+
+            let message = format!(
+                "Error #2048: Security sandbox violation: {} cannot load data from {}:{}.",
+                connection.movie.url(),
+                connection.host,
+                connection.port
+            );
+            let security_error_evt =
+                EventObject::security_error_event(&mut activation, &message, 2048);
+
+            Avm2::dispatch_event(activation.context, security_error_evt, target.into());
+        }
+    }
+
     pub fn is_connected(&self, handle: SocketHandle) -> bool {
         if let Some(socket) = self.sockets.get(handle) {
             socket.connected.get()
@@ -165,12 +367,22 @@ impl<'gc> Sockets<'gc> {
     }
 
     pub fn close_all(&mut self) {
-        for (_, socket) in self.sockets.drain() {
-            Self::close_internal(socket);
+        self.pending_connections.clear();
+        self.denied_connections.clear();
+
+        // Policy requests are kept alive, as their hosts wait for the response.
+        let handles: Vec<_> = self
+            .sockets
+            .keys()
+            .filter(|handle| !self.policy_requests.contains_key(handle))
+            .collect();
+        for handle in handles {
+            self.close(handle);
         }
     }
 
     pub fn close(&mut self, handle: SocketHandle) {
+        self.pending_connections.remove(&handle);
         if let Some(socket) = self.sockets.remove(handle) {
             Self::close_internal(socket);
         }
@@ -196,10 +408,15 @@ impl<'gc> Sockets<'gc> {
                 target.read_buffer().clear();
                 target.write_buffer().clear();
             }
+            SocketKind::Policy => {}
         }
     }
 
     pub fn update_sockets(context: &mut UpdateContext<'gc>) {
+        for (handle, connection) in std::mem::take(&mut context.sockets.denied_connections) {
+            Self::deny_connection(context, handle, &connection);
+        }
+
         let mut actions = vec![];
 
         while let Ok(action) = context.sockets.receiver.try_recv() {
@@ -239,6 +456,9 @@ impl<'gc> Sockets<'gc> {
                                 ExecutionReason::Special,
                             );
                         }
+                        SocketKind::Policy => {
+                            context.sockets.send(handle, SOCKET_POLICY_REQUEST.to_vec());
+                        }
                     }
                 }
                 SocketAction::Connect(
@@ -277,6 +497,9 @@ impl<'gc> Sockets<'gc> {
                                 ExecutionReason::Special,
                             );
                         }
+                        SocketKind::Policy => {
+                            Self::finish_socket_policy_request(context, handle);
+                        }
                     }
                 }
                 SocketAction::Data(handle, data) => {
@@ -351,6 +574,19 @@ impl<'gc> Sockets<'gc> {
                                 }
                             }
                         }
+                        SocketKind::Policy => {
+                            let Some(request) = context.sockets.policy_requests.get_mut(&handle)
+                            else {
+                                continue;
+                            };
+
+                            // Policy servers terminate their response with a null byte.
+                            let is_complete = data.contains(&0);
+                            request.data.extend(data);
+                            if is_complete {
+                                Self::finish_socket_policy_request(context, handle);
+                            }
+                        }
                     }
                 }
                 SocketAction::Close(handle) => {
@@ -391,6 +627,9 @@ impl<'gc> Sockets<'gc> {
                                 ExecutionReason::Special,
                             );
                         }
+                        SocketKind::Policy => {
+                            Self::finish_socket_policy_request(context, handle);
+                        }
                     }
                 }
             }
@@ -105,6 +105,11 @@ impl<'gc> StreamManager<'gc> {
         }
     }
 
+    /// Whether any `NetStream` is currently active.
+    pub fn has_active_streams(&self) -> bool {
+        !self.active_streams.is_empty()
+    }
+
     /// Deactivate a `NetStream`.
     ///
     /// This should only ever be called at tick time if the stream itself has
@@ -181,6 +186,23 @@ impl NetStreamHandle {
     }
 }
 
+/// An action to take on a `NetStream` in data generation mode.
+///
+/// Corresponds to the values of the AS3 `NetStreamAppendBytesAction` class.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AppendBytesAction {
+    /// Discard all appended data. The next appended data starts a new stream,
+    /// including its header.
+    ResetBegin,
+
+    /// Discard all appended data. The next appended data continues the
+    /// current stream from a new position, starting at a tag boundary.
+    ResetSeek,
+
+    /// No more data will be appended to the stream.
+    EndSequence,
+}
+
 /// The current type of the data in the stream buffer.
 #[derive(Clone, Debug)]
 pub enum NetStreamType {
@@ -245,6 +267,12 @@ pub struct NetStreamSource {
 
     /// The currently playing sound stream
     sound_instance: Cell<Option<SoundInstanceHandle>>,
+
+    /// True if the stream time should jump to the timestamp of the next tag.
+    ///
+    /// Data appended in data generation mode may start anywhere on the
+    /// stream's timeline, e.g. after a seek.
+    resync_time: Cell<bool>,
 }
 
 #[derive(Clone, Debug, Collect)]
@@ -277,6 +305,10 @@ pub struct NetStreamData<'gc> {
 
     /// True if the stream should play when ticked.
     playing: Cell<bool>,
+
+    /// True if the stream is in data generation mode, i.e. its data is
+    /// appended by the movie itself rather than downloaded from a URL.
+    data_generation: Cell<bool>,
 }
 
 impl Default for NetStreamSource {
@@ -291,6 +323,7 @@ impl Default for NetStreamSource {
             queued_seek_time: Cell::new(None),
             audio_stream: RefCell::new(None),
             sound_instance: Cell::new(None),
+            resync_time: Cell::new(false),
         }
     }
 }
@@ -321,6 +354,7 @@ impl<'gc> NetStream<'gc> {
                 url: RefCell::new(None),
                 attached_to: Lock::new(None),
                 playing: Cell::new(false),
+                data_generation: Cell::new(false),
             },
         ))
     }
@@ -403,6 +437,59 @@ impl<'gc> NetStream<'gc> {
         );
     }
 
+    /// Append data generated by the movie to the `NetStream`'s buffer.
+    ///
+    /// The data is ignored unless the stream is in data generation mode, see
+    /// `play_data_generation`.
+    pub fn append_bytes(self, context: &mut UpdateContext<'gc>, data: &mut Vec<u8>) {
+        if !self.0.data_generation.get() {
+            tracing::warn!("NetStream.appendBytes called outside of data generation mode");
+            return;
+        }
+
+        self.source().buffer.borrow_mut().append(data);
+
+        StreamManager::activate(context, self);
+    }
+
+    /// Execute an `appendBytesAction` in data generation mode.
+    ///
+    /// Like `append_bytes`, this does nothing outside of data generation mode.
+    pub fn append_bytes_action(self, context: &mut UpdateContext<'gc>, action: AppendBytesAction) {
+        if !self.0.data_generation.get() {
+            tracing::warn!("NetStream.appendBytesAction called outside of data generation mode");
+            return;
+        }
+
+        match action {
+            AppendBytesAction::ResetBegin => self.flush_appended_data(context, false),
+            AppendBytesAction::ResetSeek => self.flush_appended_data(context, true),
+            AppendBytesAction::EndSequence => self.finish_buffer(),
+        }
+    }
+
+    /// Discard all data appended to the buffer so far.
+    ///
+    /// If `keep_stream_type` is true, the next appended data is expected to
+    /// continue the current container rather than start with a new header.
+    /// Either way, the stream time will jump to the first tag appended next.
+    fn flush_appended_data(self, context: &mut UpdateContext<'gc>, keep_stream_type: bool) {
+        let source = self.source();
+        let stream_type = if keep_stream_type {
+            source.stream_type.take()
+        } else {
+            None
+        };
+        let stream_time = source.stream_time.get();
+
+        self.reset_buffer(context);
+
+        let source = self.source();
+        source.stream_type.replace(stream_type);
+        source.stream_time.set(stream_time);
+        source.resync_time.set(true);
+    }
+
     /// Indicate that the buffer has finished loading and that no further data
     /// is expected to be downloaded to it.
     pub fn finish_buffer(self) {
@@ -427,6 +514,37 @@ impl<'gc> NetStream<'gc> {
         self.source().stream_time.get()
     }
 
+    /// The amount of data buffered ahead of the playhead, in milliseconds.
+    pub fn buffer_length(self) -> f64 {
+        let source = self.source();
+        if !matches!(
+            &*source.stream_type.borrow(),
+            Some(NetStreamType::Flv { .. })
+        ) {
+            return 0.0;
+        }
+
+        let slice = source.buffer.borrow().to_full_slice();
+        let buffer = slice.data();
+        let mut reader = FlvReader::from_parts(&buffer, source.offset.get());
+        let mut first_timestamp = None;
+        let mut last_timestamp = None;
+        while let Ok(tag) = FlvTag::parse(&mut reader) {
+            first_timestamp.get_or_insert(tag.timestamp as f64);
+            last_timestamp = Some(tag.timestamp as f64);
+        }
+
+        let Some(last_timestamp) = last_timestamp else {
+            return 0.0;
+        };
+        let playhead = if source.resync_time.get() {
+            first_timestamp.unwrap_or(last_timestamp)
+        } else {
+            source.stream_time.get()
+        };
+        (last_timestamp - playhead).max(0.0)
+    }
+
     pub fn buffer_time(self) -> f64 {
         self.0.buffer_time.get()
     }
@@ -439,6 +557,13 @@ impl<'gc> NetStream<'gc> {
     ///
     /// `offset` is in milliseconds.
     pub fn seek(self, context: &mut UpdateContext<'gc>, offset: f64, notify: bool) {
+        if self.0.data_generation.get() {
+            // The buffer is flushed right away, as the movie is expected to
+            // append data from the new position before the seek executes.
+            self.flush_appended_data(context, true);
+            self.source().stream_time.set(offset);
+        }
+
         self.source().queued_seek_time.set(Some(offset));
         StreamManager::activate(context, self);
 
@@ -477,6 +602,11 @@ impl<'gc> NetStream<'gc> {
             [("code", "NetStream.Seek.Notify"), ("level", "status")],
         );
 
+        if self.0.data_generation.get() {
+            // The buffer was already flushed when the seek was queued.
+            return;
+        }
+
         let source = self.source();
 
         // Ensure the container stream type is known before continuing.
@@ -600,6 +730,7 @@ impl<'gc> NetStream<'gc> {
                 Request::get(name.to_string())
             };
             self.0.url.replace(Some(request.url().to_string()));
+            self.0.data_generation.set(false);
             self.source().preload_offset.set(0);
             self.reset_buffer(context);
 
@@ -617,6 +748,17 @@ impl<'gc> NetStream<'gc> {
         );
     }
 
+    /// Start playing media in data generation mode.
+    ///
+    /// Rather than being downloaded, the media data is appended by the movie
+    /// itself with `append_bytes`, starting with a container header.
+    pub fn play_data_generation(self, context: &mut UpdateContext<'gc>) {
+        self.0.url.replace(None);
+        self.0.data_generation.set(true);
+        self.reset_buffer(context);
+        self.play(context, None);
+    }
+
     /// Pause stream playback.
     pub fn pause(self, context: &mut UpdateContext<'gc>, notify: bool) {
         // NOTE: We do not deactivate the stream here as there may be other
@@ -635,6 +777,22 @@ impl<'gc> NetStream<'gc> {
         }
     }
 
+    /// Stop stream playback and silence any audio the stream is playing.
+    ///
+    /// Unlike `pause`, this also deactivates the stream. This is used when
+    /// the content that owns this stream gets unloaded.
+    pub fn stop(self, context: &mut UpdateContext<'gc>) {
+        self.pause(context, false);
+
+        if let Some(sound_instance) = self.source().sound_instance.take() {
+            context
+                .audio_manager
+                .stop_sound(context.audio, sound_instance);
+        }
+
+        StreamManager::deactivate(context, self);
+    }
+
     /// Resume stream playback.
     pub fn resume(self, context: &mut UpdateContext<'gc>) {
         self.0.playing.set(true);
@@ -726,6 +884,8 @@ impl<'gc> NetStream<'gc> {
                         (FlvSoundFormat::G711ALawPCM, _)
                         | (FlvSoundFormat::G711MuLawPCM, _)
                         | (FlvSoundFormat::MP38kHz, _) => 8_000,
+                        // Speex is always wideband, whatever the rate field says.
+                        (FlvSoundFormat::Speex, _) => 16_000,
                         (_, FlvSoundRate::R5_500) => 5_500,
                         (_, FlvSoundRate::R11_000) => 11_000,
                         (_, FlvSoundRate::R22_000) => 22_000,
@@ -1207,7 +1367,7 @@ impl<'gc> NetStream<'gc> {
         let slice = source.buffer.borrow().to_full_slice();
         let buffer = slice.data();
 
-        let max_time = source.stream_time.get() + dt.as_millis();
+        let mut max_time = source.stream_time.get() + dt.as_millis();
         let mut buffer_underrun = false;
         let mut error = false;
         let mut max_lookahead_audio_tags = 5;
@@ -1239,6 +1399,10 @@ impl<'gc> NetStream<'gc> {
                 }
 
                 let tag = tag.expect("valid tag");
+                if source.resync_time.take() {
+                    source.stream_time.set(tag.timestamp as f64);
+                    max_time = tag.timestamp as f64 + dt.as_millis();
+                }
                 is_lookahead_tag = tag.timestamp as f64 >= max_time; //FLV timestamps are also ms
                 if is_lookahead_tag && max_lookahead_audio_tags == 0 {
                     break;
@@ -14,9 +14,11 @@ use crate::avm2::{
 use crate::context::UpdateContext;
 use crate::display_object::{DisplayObject, TDisplayObject};
 use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
 use gc_arena::Collect;
 use ruffle_common::duration::FloatDuration;
 use std::collections::{BinaryHeap, binary_heap::PeekMut};
+use std::sync::Arc;
 
 /// Manages the collection of timers.
 #[derive(Collect)]
@@ -140,7 +142,9 @@ impl<'gc> Timers<'gc> {
                         true
                     }
                 }
-                TimerCallback::Avm2Callback { closure, params } => {
+                TimerCallback::Avm2Callback {
+                    closure, params, ..
+                } => {
                     let domain = context.avm2.stage_domain();
                     let mut avm2_activation = Avm2Activation::from_domain(context, domain);
 
@@ -268,6 +272,43 @@ impl<'gc> Timers<'gc> {
         id
     }
 
+    /// Iterates over all active timers, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &Timer<'gc>> {
+        self.timers.iter()
+    }
+
+    /// The ID of the most recently created timer.
+    pub fn timer_counter(&self) -> i32 {
+        self.timer_counter
+    }
+
+    /// The time left until the given timer fires, in microseconds.
+    pub fn time_until(&self, timer: &Timer<'gc>) -> u64 {
+        timer.tick_time.saturating_sub(self.cur_time)
+    }
+
+    /// Replaces all timers, e.g. when a save state is restored.
+    ///
+    /// Each timer is given as its ID, callback, the time left until it fires
+    /// and its interval (both in microseconds), and whether it's a timeout.
+    pub fn replace_all(
+        &mut self,
+        timers: impl IntoIterator<Item = (i32, TimerCallback<'gc>, u64, u64, bool)>,
+        timer_counter: i32,
+    ) {
+        self.timers = timers
+            .into_iter()
+            .map(|(id, callback, delay, interval, is_timeout)| Timer {
+                id,
+                callback,
+                tick_time: self.cur_time + delay,
+                interval,
+                is_timeout,
+            })
+            .collect();
+        self.timer_counter = timer_counter;
+    }
+
     /// Removes a timer.
     pub fn remove(&mut self, id: i32) -> bool {
         let old_len = self.timers.len();
@@ -282,6 +323,16 @@ impl<'gc> Timers<'gc> {
         self.timers.clear()
     }
 
+    /// Removes all AVM2 timers registered by code from the given movie.
+    pub fn remove_for_movie(&mut self, movie: &Arc<SwfMovie>) {
+        self.timers.retain(|t| match &t.callback {
+            TimerCallback::Avm2Callback {
+                movie: Some(owner), ..
+            } => !Arc::ptr_eq(owner, movie),
+            _ => true,
+        });
+    }
+
     /// Changes the delay of a timer.
     pub fn set_delay(&mut self, id: i32, interval: i32) {
         // SANITY: Set a minimum interval so we don't spam too much.
@@ -347,6 +398,28 @@ pub struct Timer<'gc> {
     is_timeout: bool,
 }
 
+impl<'gc> Timer<'gc> {
+    /// The ID of the timer.
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    /// The callback that this timer runs when it fires.
+    pub fn callback(&self) -> &TimerCallback<'gc> {
+        &self.callback
+    }
+
+    /// The interval between timer ticks, in microseconds.
+    pub fn interval(&self) -> u64 {
+        self.interval
+    }
+
+    /// Whether this timer only fires once.
+    pub fn is_timeout(&self) -> bool {
+        self.is_timeout
+    }
+}
+
 // Implement `Ord` so that timers can be stored in the BinaryHeap (as a min-heap).
 impl PartialEq for Timer<'_> {
     fn eq(&self, other: &Self) -> bool {
@@ -387,5 +460,8 @@ pub enum TimerCallback<'gc> {
     Avm2Callback {
         closure: Option<Avm2FunctionObject<'gc>>,
         params: Vec<Avm2Value<'gc>>,
+        /// The movie whose code registered this timer, if known.
+        #[collect(require_static)]
+        movie: Option<Arc<SwfMovie>>,
     },
 }
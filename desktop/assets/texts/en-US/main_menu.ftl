@@ -42,6 +42,7 @@ debug-menu-open-root-movie-clip = View Root MovieClip
 debug-menu-open-movie = View Movie
 debug-menu-open-movie-list = Show Known Movies
 debug-menu-open-domain-list = Show Domains
+debug-menu-open-avm2-debugger = Show AVM2 Debugger
 debug-menu-search-display-objects = Search Display Objects...
 
 view-menu = View
@@ -147,6 +147,15 @@ pub struct Opt {
     #[clap(long = "socket-allow", number_of_values = 1, action = clap::ArgAction::Append)]
     pub socket_allow: Vec<String>,
 
+    /// Trust a local directory or file, like Flash Player's `FlashPlayerTrust`
+    /// files. Movies inside it run in the `localTrusted` sandbox, so they may
+    /// load and script remote content.
+    ///
+    /// The `[[trusted_location]]` entries of the preferences are trusted too,
+    /// as is the movie that was opened, if it's a local file.
+    #[clap(long = "trusted-location", number_of_values = 1, action = clap::ArgAction::Append)]
+    pub trusted_location: Vec<std::path::PathBuf>,
+
     /// Define how to deal with TCP Socket connections.
     #[clap(long = "tcp-connections")]
     pub tcp_connections: Option<SocketMode>,
@@ -168,6 +168,12 @@ impl MenuBar {
                                 player.debug_ui().queue_message(DebugMessage::ShowDomains);
                             }
                         }
+                        if Button::new(text(locale, "debug-menu-open-avm2-debugger")).ui(ui).clicked() {
+                            ui.close();
+                            if let Some(player) = &mut player {
+                                player.debug_ui().queue_message(DebugMessage::ShowAvm2Debugger);
+                            }
+                        }
                         ui.separator();
                         if Button::new(text(locale, "debug-menu-search-display-objects")).ui(ui).clicked() {
                             ui.close();
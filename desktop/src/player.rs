@@ -9,12 +9,14 @@ use crate::gui::{FilePicker, MovieView};
 use crate::preferences::GlobalPreferences;
 use crate::{CALLSTACK, RENDER_INFO, SWF_INFO};
 use anyhow::anyhow;
+use ruffle_core::backend::filesystem::AirDirectory;
 use ruffle_core::backend::navigator::{OwnedFuture, SocketMode};
 use ruffle_core::config::Letterbox;
 use ruffle_core::events::{GamepadButton, KeyCode};
 use ruffle_core::font::DefaultFont;
 use ruffle_core::{LoadBehavior, Player, PlayerBuilder, PlayerEvent};
 use ruffle_frontend_utils::backends::audio::CpalAudioBackend;
+use ruffle_frontend_utils::backends::filesystem::DiskFileSystemBackend;
 use ruffle_frontend_utils::backends::navigator::{ExternalNavigatorBackend, FutureSpawner};
 use ruffle_frontend_utils::bundle::source::BundleSourceError;
 use ruffle_frontend_utils::bundle::{Bundle, BundleError};
@@ -29,7 +31,7 @@ use ruffle_render_wgpu::descriptors::Descriptors;
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::Duration;
@@ -44,6 +46,8 @@ pub struct LaunchOptions {
     pub player: PlayerOptions,
     pub proxy: Option<Url>,
     pub socket_allowed: HashSet<String>,
+    /// Local locations whose movies run in the `localTrusted` sandbox.
+    pub trusted_locations: Vec<Url>,
     pub tcp_connections: Option<SocketMode>,
     pub fullscreen: bool,
     pub save_directory: PathBuf,
@@ -98,6 +102,17 @@ impl From<&GlobalPreferences> for LaunchOptions {
             cache_directory: value.cli.cache_directory.clone(),
             filesystem_access_mode: value.cli.filesystem_access_mode,
             socket_allowed: HashSet::from_iter(value.cli.socket_allow.iter().cloned()),
+            trusted_locations: value
+                .trusted_locations()
+                .iter()
+                .filter_map(|path| {
+                    let url = crate::util::trusted_location_url(path);
+                    if url.is_none() {
+                        tracing::warn!("Ignoring trusted location {}", path.display());
+                    }
+                    url
+                })
+                .collect(),
             tcp_connections: value.cli.tcp_connections,
             gamepad_button_mapping: HashMap::from_iter(value.cli.gamepad_button.iter().cloned()),
             avm2_optimizer_enabled: !value.cli.no_avm2_optimizer,
@@ -192,6 +207,7 @@ impl ActivePlayer {
                     player,
                     proxy: opt.proxy.clone(),
                     socket_allowed: opt.socket_allowed.clone(),
+                    trusted_locations: opt.trusted_locations.clone(),
                     tcp_connections: opt.tcp_connections,
                     fullscreen: opt.fullscreen,
                     save_directory: opt.save_directory.clone(),
@@ -209,6 +225,7 @@ impl ActivePlayer {
         };
         let movie_url = content.initial_swf_url().clone();
         let readable_name = content.name();
+        let filesystem = create_filesystem_backend(&content, &opt.save_directory);
         let initial_allow_list = PathAllowList::new(content_descriptor);
         let navigator = ExternalNavigatorBackend::new(
             opt.player
@@ -299,10 +316,18 @@ impl ActivePlayer {
             }
         });
 
+        // Like in the standalone Flash Player, a movie that the user opened
+        // from disk is trusted, so it may load and script remote content.
+        let mut trusted_locations = opt.trusted_locations.clone();
+        if movie_url.scheme() == "file" {
+            trusted_locations.push(movie_url.clone());
+        }
+
         builder = builder
             .with_navigator(navigator)
             .with_renderer(renderer)
             .with_storage(preferences.storage_backend().create_backend(&opt))
+            .with_filesystem(Box::new(filesystem))
             .with_notification_sender(notification_sender)
             .with_fs_commands(Box::new(DesktopFSCommandProvider {
                 event_loop: event_loop.clone(),
@@ -336,7 +361,8 @@ impl ActivePlayer {
             .with_player_version(opt.player.player_version)
             .with_player_runtime(opt.player.player_runtime.unwrap_or_default())
             .with_frame_rate(opt.player.frame_rate)
-            .with_avm2_optimizer_enabled(opt.avm2_optimizer_enabled);
+            .with_avm2_optimizer_enabled(opt.avm2_optimizer_enabled)
+            .with_trusted_locations(trusted_locations);
         let player = builder.build();
 
         window.set_title(&format!("Ruffle - {readable_name}"));
@@ -423,6 +449,39 @@ impl ActivePlayer {
     }
 }
 
+/// Creates the filesystem used by AIR content.
+///
+/// Every piece of content gets its own set of writable directories in the save
+/// directory, and can read its own files through `File.applicationDirectory`.
+fn create_filesystem_backend(
+    content: &PlayingContent,
+    save_directory: &Path,
+) -> DiskFileSystemBackend {
+    let name: String = content
+        .name()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let root = save_directory.join("air").join(name);
+
+    let mut backend = DiskFileSystemBackend::new();
+    for directory in AirDirectory::ALL {
+        if !directory.is_read_only() {
+            backend = backend.with_directory(directory, root.join(directory.root_name()));
+        }
+    }
+    if let Some(application_directory) = content.application_directory() {
+        backend = backend.with_directory(AirDirectory::Application, application_directory);
+    }
+    backend
+}
+
 /// Owner of a Ruffle Player (via ActivePlayer),
 /// responsible for either creating, destroying or communicating with that player.
 pub struct PlayerController {
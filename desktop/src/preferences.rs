@@ -15,6 +15,7 @@ use ruffle_frontend_utils::bookmarks::{Bookmarks, BookmarksWriter, read_bookmark
 use ruffle_frontend_utils::parse::DocumentHolder;
 use ruffle_frontend_utils::recents::{Recents, RecentsWriter, read_recents};
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use sys_locale::get_locale;
 use tokio::sync::broadcast;
@@ -237,6 +238,21 @@ impl GlobalPreferences {
             .device_font_renderer
     }
 
+    /// The local locations whose movies run in the `localTrusted` sandbox,
+    /// both from the CLI and from the saved preferences.
+    pub fn trusted_locations(&self) -> Vec<PathBuf> {
+        let preferences = self
+            .preferences
+            .lock()
+            .expect("Preferences is not reentrant");
+        self.cli
+            .trusted_location
+            .iter()
+            .chain(&preferences.trusted_locations)
+            .cloned()
+            .collect()
+    }
+
     pub fn recents<R>(&self, fun: impl FnOnce(&Recents) -> R) -> R {
         fun(&self.recents.lock().expect("Recents is not reentrant"))
     }
@@ -296,6 +312,7 @@ pub struct SavedGlobalPreferences {
     pub open_url_mode: OpenUrlMode,
     pub ime_enabled: Option<bool>,
     pub device_font_renderer: Option<DeviceFontRenderer>,
+    pub trusted_locations: Vec<PathBuf>,
 }
 
 impl Default for SavedGlobalPreferences {
@@ -321,6 +338,7 @@ impl Default for SavedGlobalPreferences {
             open_url_mode: Default::default(),
             ime_enabled: None,
             device_font_renderer: None,
+            trusted_locations: Vec::new(),
         }
     }
 }
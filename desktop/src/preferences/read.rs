@@ -2,6 +2,7 @@ use crate::preferences::SavedGlobalPreferences;
 use ruffle_frontend_utils::parse::{
     DocumentHolder, ParseContext, ParseDetails, ParseWarning, ReadExt,
 };
+use std::path::PathBuf;
 use toml_edit::DocumentMut;
 
 /// Read the given preferences into a **guaranteed valid** `SavedGlobalPreferences`,
@@ -91,6 +92,14 @@ pub fn read_preferences(input: &str) -> ParseDetails<SavedGlobalPreferences> {
         result.ime_enabled = ime.get_bool(cx, "enabled");
     });
 
+    document.get_array_of_tables(&mut cx, "trusted_location", |cx, locations| {
+        for location in locations.iter() {
+            if let Some(path) = location.parse_from_str::<String>(cx, "path") {
+                result.trusted_locations.push(PathBuf::from(path));
+            }
+        }
+    });
+
     ParseDetails {
         warnings: cx.warnings,
         result: DocumentHolder::new(result, document),
@@ -825,4 +834,30 @@ mod tests {
             result.warnings
         );
     }
+
+    #[test]
+    fn trusted_locations() {
+        let result = read_preferences(
+            "[[trusted_location]]\npath = \"/games\"\n\n[[trusted_location]]\npath = \"/movie.swf\"\n",
+        );
+        assert_eq!(
+            &SavedGlobalPreferences {
+                trusted_locations: vec![PathBuf::from("/games"), PathBuf::from("/movie.swf")],
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("[[trusted_location]]\npath = 5\n");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "string",
+                actual: "integer",
+                path: "trusted_location.path".to_string(),
+            }],
+            result.warnings
+        );
+    }
 }
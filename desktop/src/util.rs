@@ -393,6 +393,17 @@ pub fn parse_url(path: &Path) -> Result<Url, Error> {
     }
 }
 
+/// Converts a trusted location to the URL that the URLs of the movies inside
+/// it start with, in the same form as [`parse_url`] produces.
+pub fn trusted_location_url(path: &Path) -> Option<Url> {
+    let absolute_path = path.canonicalize().ok()?;
+    if absolute_path.is_dir() {
+        Url::from_directory_path(absolute_path).ok()
+    } else {
+        Url::from_file_path(absolute_path).ok()
+    }
+}
+
 #[cfg(not(feature = "tracy"))]
 pub fn mark_tracy_frame() {}
 
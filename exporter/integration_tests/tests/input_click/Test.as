@@ -0,0 +1,26 @@
+package {
+import flash.display.*;
+import flash.events.*;
+
+// Fills the stage with red, and turns it green when clicked.
+// The click is injected after the first frame, so only the second frame is green.
+[SWF(width="20", height="10")]
+public class Test extends MovieClip {
+    private var box:Sprite = new Sprite();
+
+    public function Test() {
+        fill(0xff0000);
+        box.addEventListener(MouseEvent.CLICK, function(e:MouseEvent):void {
+            fill(0x00ff00);
+        });
+        addChild(box);
+    }
+
+    private function fill(color:uint):void {
+        box.graphics.clear();
+        box.graphics.beginFill(color);
+        box.graphics.drawRect(-10, -10, 40, 30);
+        box.graphics.endFill();
+    }
+}
+}
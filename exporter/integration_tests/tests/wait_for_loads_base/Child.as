@@ -0,0 +1,12 @@
+package {
+import flash.display.*;
+
+[SWF(width="20", height="10")]
+public class Child extends MovieClip {
+    public function Child() {
+        graphics.beginFill(0x00ff00);
+        graphics.drawRect(-10, -10, 40, 30);
+        graphics.endFill();
+    }
+}
+}
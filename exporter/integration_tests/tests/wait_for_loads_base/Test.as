@@ -0,0 +1,32 @@
+package {
+import flash.display.*;
+import flash.events.*;
+import flash.net.URLRequest;
+
+// Loads "child.swf" relative to the base directory, which fills the stage with green.
+// The stage turns red if more than a few frames ran before the exported one,
+// e.g. if the exporter kept waiting after the load finished.
+[SWF(width="20", height="10")]
+public class Test extends MovieClip {
+    private var frames:int = 0;
+    private var overlay:Sprite = new Sprite();
+
+    public function Test() {
+        var loader:Loader = new Loader();
+        loader.load(new URLRequest("child.swf"));
+        addChild(loader);
+        addChild(overlay);
+        addEventListener(Event.ENTER_FRAME, onEnterFrame);
+    }
+
+    private function onEnterFrame(e:Event):void {
+        frames++;
+        if (frames > 10) {
+            overlay.graphics.clear();
+            overlay.graphics.beginFill(0xff0000);
+            overlay.graphics.drawRect(-10, -10, 40, 30);
+            overlay.graphics.endFill();
+        }
+    }
+}
+}
@@ -0,0 +1,108 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use ruffle_core::backend::audio::{
+    AudioBackend, AudioMixer, DecodeError, RegisterError, SoundHandle, SoundInstanceHandle,
+    SoundStreamInfo, SoundTransform, swf,
+};
+use ruffle_core::impl_audio_mixer_backend;
+
+/// An audio backend that doesn't play anything, but mixes the sound of every
+/// exported frame into a buffer instead.
+pub struct ExportAudioBackend {
+    mixer: AudioMixer,
+    frame_rate: f64,
+
+    /// The number of frames mixed since the frame rate last changed.
+    frames_mixed: u64,
+
+    /// The number of samples mixed since the frame rate last changed.
+    samples_mixed: u64,
+
+    /// The interleaved samples mixed since they were last taken.
+    samples: Vec<i16>,
+}
+
+impl Default for ExportAudioBackend {
+    fn default() -> Self {
+        Self {
+            mixer: AudioMixer::new(Self::NUM_CHANNELS, Self::SAMPLE_RATE),
+            frame_rate: 1.0,
+            frames_mixed: 0,
+            samples_mixed: 0,
+            samples: vec![],
+        }
+    }
+}
+
+impl ExportAudioBackend {
+    pub const NUM_CHANNELS: u8 = 2;
+    pub const SAMPLE_RATE: u32 = 44100;
+
+    /// Takes the samples mixed since the last call.
+    pub fn take_samples(&mut self) -> Vec<i16> {
+        std::mem::take(&mut self.samples)
+    }
+}
+
+impl AudioBackend for ExportAudioBackend {
+    impl_audio_mixer_backend!(mixer);
+    fn play(&mut self) {}
+    fn pause(&mut self) {}
+
+    fn set_frame_rate(&mut self, frame_rate: f64) {
+        self.frame_rate = frame_rate;
+        self.frames_mixed = 0;
+        self.samples_mixed = 0;
+    }
+
+    fn tick(&mut self) {
+        // Frame durations rarely are a whole number of samples, so the amount of
+        // samples per frame varies to avoid drifting away from the video.
+        self.frames_mixed += 1;
+        let total_samples =
+            (self.frames_mixed as f64 * Self::SAMPLE_RATE as f64 / self.frame_rate).round() as u64;
+        let num_samples = (total_samples - self.samples_mixed) as usize;
+        self.samples_mixed = total_samples;
+
+        let start = self.samples.len();
+        self.samples
+            .resize(start + num_samples * Self::NUM_CHANNELS as usize, 0);
+        self.mixer.mix::<i16>(&mut self.samples[start..]);
+    }
+}
+
+/// Writes interleaved samples as a 16-bit PCM WAV file.
+pub fn write_wav(path: &Path, samples: &[i16]) -> io::Result<()> {
+    const BYTES_PER_SAMPLE: u16 = 2;
+    let num_channels = u16::from(ExportAudioBackend::NUM_CHANNELS);
+    let sample_rate = ExportAudioBackend::SAMPLE_RATE;
+    let block_align = num_channels * BYTES_PER_SAMPLE;
+    let too_long = || io::Error::other("Audio is too long for a WAV file");
+    let data_len =
+        u32::try_from(samples.len() * BYTES_PER_SAMPLE as usize).map_err(|_| too_long())?;
+    let riff_len = data_len.checked_add(36).ok_or_else(too_long)?;
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    writer.write_all(b"RIFF")?;
+    writer.write_all(&riff_len.to_le_bytes())?;
+    writer.write_all(b"WAVE")?;
+
+    writer.write_all(b"fmt ")?;
+    writer.write_all(&16u32.to_le_bytes())?;
+    // PCM
+    writer.write_all(&1u16.to_le_bytes())?;
+    writer.write_all(&num_channels.to_le_bytes())?;
+    writer.write_all(&sample_rate.to_le_bytes())?;
+    writer.write_all(&(sample_rate * u32::from(block_align)).to_le_bytes())?;
+    writer.write_all(&block_align.to_le_bytes())?;
+    writer.write_all(&(BYTES_PER_SAMPLE * 8).to_le_bytes())?;
+
+    writer.write_all(b"data")?;
+    writer.write_all(&data_len.to_le_bytes())?;
+    for sample in samples {
+        writer.write_all(&sample.to_le_bytes())?;
+    }
+    writer.flush()
+}
@@ -23,6 +23,16 @@ pub struct SizeOpt {
     pub height: Option<u32>,
 }
 
+/// Which render backend to capture frames with.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Eq, PartialEq, Default)]
+pub enum Renderer {
+    /// Render on the GPU with wgpu.
+    #[default]
+    Wgpu,
+    /// Render on the CPU. Slower, but doesn't require any graphics device.
+    Software,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum FrameSelection {
     All,
@@ -99,6 +109,31 @@ pub struct Opt {
     #[clap(long)]
     pub force_play: bool,
 
+    /// A JSON file of input events to inject while exporting, in the same format as the
+    /// "input.json" files of Ruffle's tests. The events up to each "Wait" are injected after a frame.
+    #[clap(long)]
+    pub input: Option<PathBuf>,
+
+    /// The directory that relative URLs loaded by the movie are resolved against.
+    /// Defaults to the directory containing the SWF.
+    #[clap(long)]
+    pub base: Option<PathBuf>,
+
+    /// Before running each frame, wait until the movies being loaded have finished loading.
+    /// The frames run while waiting aren't exported.
+    #[clap(long, action)]
+    pub wait_for_loads: bool,
+
+    /// Also export the sound of the movie as a WAV file, in sync with the captured frames.
+    /// It's saved next to the frames as "audio.wav", or with the ".wav" extension when capturing a single frame.
+    #[clap(long, action)]
+    pub audio: bool,
+
+    /// Which renderer to use. The software renderer works on machines without a GPU,
+    /// in which case the graphics and power options are ignored.
+    #[clap(long, default_value = "wgpu")]
+    pub renderer: Renderer,
+
     /// Type of graphics backend to use. Not all options may be supported by your current system.
     /// Default will attempt to pick the most supported graphics backend.
     #[clap(long, short, default_value = "default")]
@@ -1,13 +1,18 @@
 use std::panic::catch_unwind;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::Duration;
 
 use image::RgbaImage;
 use ruffle_core::Player;
 use ruffle_core::PlayerBuilder;
+use ruffle_core::backend::navigator::{NullExecutor, NullNavigatorBackend};
+use ruffle_core::backend::time::VirtualTimeBackend;
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::movie_from_path;
+use ruffle_input_format::{InputInjector, inject_event, validate_event};
+use ruffle_render_software::SoftwareRenderBackend;
 use ruffle_render_wgpu::backend::{
     WgpuRenderBackend, create_wgpu_instance, request_adapter_and_device,
 };
@@ -18,31 +23,69 @@ use anyhow::anyhow;
 use ruffle_render_wgpu::target::TextureTarget;
 use ruffle_render_wgpu::wgpu;
 
+use crate::audio::ExportAudioBackend;
 use crate::cli::FrameSelection;
 use crate::cli::Opt;
+use crate::cli::Renderer;
 use crate::cli::SizeOpt;
 use crate::player_ext::PlayerExporterExt;
 
+/// The maximum amount of frames to wait for loads before running a frame,
+/// so that loads that never finish don't stall the export.
+const MAX_LOAD_WAIT_FRAMES: u32 = 1000;
+
 pub struct Exporter {
-    descriptors: Arc<Descriptors>,
+    /// The wgpu device to render with, or `None` when using the software renderer.
+    descriptors: Option<Arc<Descriptors>>,
     size: SizeOpt,
     skipframes: u32,
     frames: FrameSelection,
     force_play: bool,
+    audio: bool,
+    base: Option<PathBuf>,
+    wait_for_loads: bool,
+
+    /// The contents of the input file, injected into every exported movie.
+    input: Option<Vec<u8>>,
 }
 
 impl Exporter {
     pub fn new(opt: &Opt) -> Result<Self> {
-        let instance = create_wgpu_instance(opt.graphics.into(), wgpu::BackendOptions::default());
-        let (adapter, device, queue) = futures::executor::block_on(request_adapter_and_device(
-            opt.graphics.into(),
-            &instance,
-            None,
-            opt.power.into(),
-        ))
-        .map_err(|e| anyhow!(e.to_string()))?;
-
-        let descriptors = Arc::new(Descriptors::new(instance, adapter, device, queue));
+        let input = opt
+            .input
+            .as_ref()
+            .map(|path| -> Result<Vec<u8>> {
+                let input = std::fs::read(path)
+                    .map_err(|e| anyhow!("Couldn't read {}: {e}", path.to_string_lossy()))?;
+                // Report invalid files before exporting anything.
+                let injector = InputInjector::from_reader(&input[..])
+                    .map_err(|e| anyhow!("Invalid input file {}: {e}", path.to_string_lossy()))?;
+                for event in injector.events() {
+                    validate_event(event).map_err(|e| {
+                        anyhow!("Invalid input file {}: {e}", path.to_string_lossy())
+                    })?;
+                }
+                Ok(input)
+            })
+            .transpose()?;
+
+        let descriptors = match opt.renderer {
+            Renderer::Wgpu => {
+                let instance =
+                    create_wgpu_instance(opt.graphics.into(), wgpu::BackendOptions::default());
+                let (adapter, device, queue) =
+                    futures::executor::block_on(request_adapter_and_device(
+                        opt.graphics.into(),
+                        &instance,
+                        None,
+                        opt.power.into(),
+                    ))
+                    .map_err(|e| anyhow!(e.to_string()))?;
+
+                Some(Arc::new(Descriptors::new(instance, adapter, device, queue)))
+            }
+            Renderer::Software => None,
+        };
 
         Ok(Self {
             descriptors,
@@ -50,6 +93,10 @@ impl Exporter {
             skipframes: opt.skipframes,
             frames: opt.frames,
             force_play: opt.force_play,
+            audio: opt.audio,
+            base: opt.base.clone(),
+            wait_for_loads: opt.wait_for_loads,
+            input,
         })
     }
 
@@ -70,31 +117,64 @@ impl Exporter {
             .unwrap_or_else(|| movie.height().to_pixels());
         let height = (height * self.size.scale).round() as u32;
 
-        let target = TextureTarget::new(&self.descriptors.device, (width, height))
-            .map_err(|e| anyhow!(e.to_string()))?;
-        let player = PlayerBuilder::new()
-            .with_renderer(
-                WgpuRenderBackend::new(self.descriptors.clone(), target)
+        let executor = NullExecutor::new();
+        let base_path = match &self.base {
+            Some(base) => base.as_path(),
+            None => swf_path
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+                .unwrap_or(Path::new(".")),
+        };
+        let navigator = NullNavigatorBackend::with_base_path(base_path, &executor)
+            .map_err(|e| anyhow!("Invalid base path {}: {e}", base_path.to_string_lossy()))?;
+
+        let builder = PlayerBuilder::new().with_navigator(navigator);
+        let builder = if let Some(descriptors) = &self.descriptors {
+            let target = TextureTarget::new(&descriptors.device, (width, height))
+                .map_err(|e| anyhow!(e.to_string()))?;
+            builder.with_renderer(
+                WgpuRenderBackend::new(descriptors.clone(), target)
                     .map_err(|e| anyhow!(e.to_string()))?,
             )
+        } else {
+            builder.with_renderer(SoftwareRenderBackend::new(width, height))
+        };
+        let builder = if self.audio {
+            builder.with_audio(ExportAudioBackend::default())
+        } else {
+            builder
+        };
+        let player = builder
             .with_movie(movie)
             .with_viewport_dimensions(width, height, self.size.scale)
+            .with_time(VirtualTimeBackend::default())
             .build();
 
+        let injector = match &self.input {
+            Some(input) => InputInjector::from_reader(&input[..])?,
+            None => InputInjector::empty(),
+        };
+
         Ok(MovieExport {
             player,
+            executor,
+            injector,
             skipframes: self.skipframes,
             frames: self.frames,
             force_play: self.force_play,
+            wait_for_loads: self.wait_for_loads,
         })
     }
 }
 
 pub struct MovieExport {
     player: Arc<Mutex<Player>>,
+    executor: NullExecutor,
+    injector: InputInjector,
     skipframes: u32,
     frames: FrameSelection,
     force_play: bool,
+    wait_for_loads: bool,
 }
 
 impl MovieExport {
@@ -102,7 +182,27 @@ impl MovieExport {
         self.frames.total_frames(&self.player, self.skipframes)
     }
 
-    pub fn run_frame(&self) {
+    pub fn run_frame(&mut self) {
+        if self.wait_for_loads {
+            let mut waited_frames = 0;
+            while waited_frames < MAX_LOAD_WAIT_FRAMES && self.player.has_pending_loads() {
+                self.step();
+                // What plays while waiting isn't exported.
+                let _ = self.player.capture_audio();
+                waited_frames += 1;
+            }
+        }
+
+        self.step();
+
+        let mut player = self.player.lock().unwrap();
+        self.injector.next(|event, _buttons| {
+            inject_event(event, &mut player).expect("input events are validated when read");
+        });
+    }
+
+    /// Runs a single frame, along with the loads it started.
+    fn step(&mut self) {
         if self.force_play {
             self.player.force_root_clip_play();
         }
@@ -112,7 +212,23 @@ impl MovieExport {
             .unwrap()
             .preload(&mut ExecutionLimit::none());
 
-        self.player.lock().unwrap().run_frame();
+        let mut player = self.player.lock().unwrap();
+        player.run_frame();
+        // Mix exactly one frame's worth of sound.
+        player.audio_mut().tick();
+
+        // Time only passes between exported frames, so output is reproducible.
+        let frame_duration = Duration::from_secs_f64(1.0 / player.frame_rate());
+        player.time_mut().advance(frame_duration);
+        drop(player);
+
+        // Loaded content calls back into the player, so this can't hold the lock.
+        self.executor.run();
+    }
+
+    /// Takes the sound mixed since the last call, if audio is being exported.
+    pub fn capture_audio(&self) -> Option<Vec<i16>> {
+        self.player.capture_audio()
     }
 
     pub fn capture_frame(&self) -> Result<RgbaImage> {
@@ -1,3 +1,4 @@
+mod audio;
 pub mod cli;
 mod exporter;
 mod player_ext;
@@ -12,10 +13,26 @@ use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use walkdir::{DirEntry, WalkDir};
 
+use crate::audio::write_wav;
 use crate::cli::{FrameSelection, Opt};
 use crate::exporter::Exporter;
 use crate::progress::ExporterProgress;
 
+/// The frames captured from a movie, along with its sound when exporting audio.
+struct MovieCapture {
+    frames: Vec<RgbaImage>,
+    audio: Option<Vec<i16>>,
+}
+
+impl MovieCapture {
+    fn save_audio(&self, path: &Path) -> Result<()> {
+        if let Some(audio) = &self.audio {
+            write_wav(path, audio)?;
+        }
+        Ok(())
+    }
+}
+
 /// Captures a screenshot. The resulting image uses straight alpha
 fn take_screenshot(
     exporter: &Exporter,
@@ -23,10 +40,11 @@ fn take_screenshot(
     frames: FrameSelection, // TODO Figure out a way to get framecount before calling take_screenshot, so that we can have accurate progress bars when using --frames all
     skipframes: u32,
     progress: &ExporterProgress,
-) -> Result<Vec<RgbaImage>> {
-    let movie_export = exporter.start_exporting_movie(swf_path)?;
+) -> Result<MovieCapture> {
+    let mut movie_export = exporter.start_exporting_movie(swf_path)?;
 
     let mut result = Vec::new();
+    let mut audio: Option<Vec<i16>> = None;
     let totalframes = movie_export.total_frames();
 
     for i in 0..totalframes {
@@ -38,7 +56,12 @@ fn take_screenshot(
 
         movie_export.run_frame();
 
+        // The sound of skipped frames is dropped, so that it stays in sync with the frames.
+        let frame_audio = movie_export.capture_audio();
         if i >= skipframes {
+            if let Some(frame_audio) = frame_audio {
+                audio.get_or_insert_default().extend(frame_audio);
+            }
             match movie_export.capture_frame() {
                 Ok(image) => result.push(image),
                 Err(e) => {
@@ -56,7 +79,10 @@ fn take_screenshot(
             progress.inc(1);
         }
     }
-    Ok(result)
+    Ok(MovieCapture {
+        frames: result,
+        audio,
+    })
 }
 
 fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
@@ -90,6 +116,10 @@ fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
 }
 
 fn capture_single_swf(exporter: &Exporter, opt: &Opt) -> Result<()> {
+    if opt.audio && opt.output_path == Some(PathBuf::from("-")) {
+        return Err(anyhow!("Audio can't be exported when writing to stdout."));
+    }
+
     let is_single_frame = opt.frames.is_single_frame();
     let output = opt.output_path.clone().unwrap_or_else(|| {
         let mut result = PathBuf::new();
@@ -106,7 +136,8 @@ fn capture_single_swf(exporter: &Exporter, opt: &Opt) -> Result<()> {
 
     let progress = ExporterProgress::new(opt, 1);
 
-    let frames = take_screenshot(exporter, &opt.swf, opt.frames, opt.skipframes, &progress)?;
+    let capture = take_screenshot(exporter, &opt.swf, opt.frames, opt.skipframes, &progress)?;
+    let frames = &capture.frames;
 
     progress.set_message(opt.swf.file_stem().unwrap().to_string_lossy().into_owned());
 
@@ -122,6 +153,7 @@ fn capture_single_swf(exporter: &Exporter, opt: &Opt) -> Result<()> {
                 .expect("Writing to stdout failed");
         } else {
             image.save(&output)?;
+            capture.save_audio(&output.with_extension("wav"))?;
         }
     } else {
         let digits = frames.len().to_string().len();
@@ -130,6 +162,7 @@ fn capture_single_swf(exporter: &Exporter, opt: &Opt) -> Result<()> {
             path.push(format!("{frame:0digits$}.png"));
             image.save(&path)?;
         }
+        capture.save_audio(&output.join("audio.wav"))?;
     }
 
     let message = if frames.len() == 1 {
@@ -172,9 +205,10 @@ fn capture_multiple_swfs(exporter: &Exporter, opt: &Opt) -> Result<()> {
                 .to_string_lossy()
                 .into_owned(),
         );
-        if let Ok(frames) =
+        if let Ok(capture) =
             take_screenshot(exporter, file.path(), opt.frames, opt.skipframes, &progress)
         {
+            let frames = &capture.frames;
             let mut relative_path = file
                 .path()
                 .strip_prefix(&opt.swf)
@@ -189,6 +223,7 @@ fn capture_multiple_swfs(exporter: &Exporter, opt: &Opt) -> Result<()> {
                     let _ = create_dir_all(parent);
                 }
                 frames.first().unwrap().save(&destination)?;
+                capture.save_audio(&destination.with_extension("wav"))?;
             } else {
                 let mut parent: PathBuf = (&output).into();
                 relative_path.set_extension("");
@@ -200,6 +235,7 @@ fn capture_multiple_swfs(exporter: &Exporter, opt: &Opt) -> Result<()> {
                     destination.push(format!("{frame:0digits$}.png"));
                     image.save(&destination)?;
                 }
+                capture.save_audio(&parent.join("audio.wav"))?;
             }
         }
 
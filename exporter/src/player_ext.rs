@@ -3,32 +3,54 @@ use std::{
     sync::{Arc, Mutex},
 };
 
+use crate::audio::ExportAudioBackend;
 use ruffle_core::Player;
+use ruffle_render_software::SoftwareRenderBackend;
 use ruffle_render_wgpu::{backend::WgpuRenderBackend, target::TextureTarget};
 
 pub trait PlayerExporterExt {
     fn capture_frame(&self) -> Option<image::RgbaImage>;
 
+    fn capture_audio(&self) -> Option<Vec<i16>>;
+
     fn header_frames(&self) -> u16;
 
+    fn has_pending_loads(&self) -> bool;
+
     fn force_root_clip_play(&self);
 }
 
 impl PlayerExporterExt for Arc<Mutex<Player>> {
     fn capture_frame(&self) -> Option<image::RgbaImage> {
         let mut player = self.lock().unwrap();
+        let renderer = <dyn Any>::downcast_mut::<SoftwareRenderBackend>(player.renderer_mut());
+        if let Some(renderer) = renderer {
+            return renderer.capture_frame();
+        }
         let renderer =
             <dyn Any>::downcast_mut::<WgpuRenderBackend<TextureTarget>>(player.renderer_mut())
                 .unwrap();
         renderer.capture_frame()
     }
 
+    fn capture_audio(&self) -> Option<Vec<i16>> {
+        let mut player = self.lock().unwrap();
+        <dyn Any>::downcast_mut::<ExportAudioBackend>(player.audio_mut())
+            .map(|audio| audio.take_samples())
+    }
+
     fn header_frames(&self) -> u16 {
         self.lock()
             .unwrap()
             .mutate_with_update_context(|ctx| ctx.root_swf.num_frames())
     }
 
+    fn has_pending_loads(&self) -> bool {
+        self.lock()
+            .unwrap()
+            .mutate_with_update_context(|ctx| ctx.load_manager.has_pending_loads())
+    }
+
     fn force_root_clip_play(&self) {
         let mut player = self.lock().unwrap();
 
@@ -1,5 +1,7 @@
 #[cfg(feature = "cpal")]
 pub mod audio;
+#[cfg(feature = "fs")]
+pub mod filesystem;
 #[cfg(feature = "navigator")]
 pub mod navigator;
 #[cfg(feature = "fs")]
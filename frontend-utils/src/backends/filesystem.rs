@@ -0,0 +1,182 @@
+use ruffle_core::backend::filesystem::{
+    AirDirectory, FileMetadata, FileSystemBackend, FileSystemError, VirtualPath,
+};
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+
+/// A filesystem backend mapping the AIR directories onto host directories.
+///
+/// Directories that weren't given a host directory behave as if they were
+/// empty and read-only.
+#[derive(Default)]
+pub struct DiskFileSystemBackend {
+    directories: HashMap<AirDirectory, PathBuf>,
+}
+
+impl DiskFileSystemBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps `directory` onto the host directory at `path`.
+    pub fn with_directory(mut self, directory: AirDirectory, path: PathBuf) -> Self {
+        self.directories.insert(directory, path);
+        self
+    }
+
+    /// Verifies that every component is a plain file name, so that the path
+    /// cannot point outside of the host directory.
+    fn is_component_allowed(component: &str) -> bool {
+        let mut components = Path::new(component).components();
+        matches!(
+            (components.next(), components.next()),
+            (Some(Component::Normal(_)), None)
+        )
+    }
+
+    fn host_path(&self, path: &VirtualPath) -> Result<PathBuf, FileSystemError> {
+        let root = self
+            .directories
+            .get(&path.directory())
+            .ok_or(FileSystemError::NotFound)?;
+        let mut host_path = root.clone();
+        for component in path.components() {
+            if !Self::is_component_allowed(component) {
+                return Err(FileSystemError::NotFound);
+            }
+            host_path.push(component);
+        }
+        Ok(host_path)
+    }
+
+    /// Like [`Self::host_path`], but for paths that are about to be modified.
+    fn host_path_mut(&self, path: &VirtualPath) -> Result<PathBuf, FileSystemError> {
+        if !self.directories.contains_key(&path.directory()) {
+            return Err(FileSystemError::ReadOnly);
+        }
+        self.host_path(path)
+    }
+}
+
+fn to_error(error: std::io::Error) -> FileSystemError {
+    match error.kind() {
+        ErrorKind::NotFound => FileSystemError::NotFound,
+        ErrorKind::AlreadyExists => FileSystemError::AlreadyExists,
+        ErrorKind::NotADirectory => FileSystemError::NotADirectory,
+        ErrorKind::IsADirectory => FileSystemError::IsADirectory,
+        ErrorKind::DirectoryNotEmpty => FileSystemError::DirectoryNotEmpty,
+        ErrorKind::PermissionDenied | ErrorKind::ReadOnlyFilesystem => FileSystemError::ReadOnly,
+        _ => FileSystemError::Io(error.to_string()),
+    }
+}
+
+impl FileSystemBackend for DiskFileSystemBackend {
+    fn metadata(&self, path: &VirtualPath) -> Result<FileMetadata, FileSystemError> {
+        let metadata = fs::metadata(self.host_path(path)?).map_err(to_error)?;
+        Ok(FileMetadata {
+            is_directory: metadata.is_dir(),
+            size: if metadata.is_dir() { 0 } else { metadata.len() },
+            creation_time: metadata.created().ok().map(Into::into),
+            modification_time: metadata.modified().ok().map(Into::into),
+        })
+    }
+
+    fn read(&self, path: &VirtualPath) -> Result<Vec<u8>, FileSystemError> {
+        fs::read(self.host_path(path)?).map_err(to_error)
+    }
+
+    fn write(&mut self, path: &VirtualPath, data: &[u8]) -> Result<(), FileSystemError> {
+        let host_path = self.host_path_mut(path)?;
+        if let Some(parent) = host_path.parent() {
+            fs::create_dir_all(parent).map_err(to_error)?;
+        }
+        fs::write(host_path, data).map_err(to_error)
+    }
+
+    fn list_directory(&self, path: &VirtualPath) -> Result<Vec<String>, FileSystemError> {
+        let mut names = Vec::new();
+        for entry in fs::read_dir(self.host_path(path)?).map_err(to_error)? {
+            let entry = entry.map_err(to_error)?;
+            // Content could not refer to entries that aren't valid Unicode.
+            if let Ok(name) = entry.file_name().into_string() {
+                names.push(name);
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    fn create_directory(&mut self, path: &VirtualPath) -> Result<(), FileSystemError> {
+        fs::create_dir_all(self.host_path_mut(path)?).map_err(to_error)
+    }
+
+    fn delete(&mut self, path: &VirtualPath, recursive: bool) -> Result<(), FileSystemError> {
+        if path.is_root() {
+            return Err(FileSystemError::ReadOnly);
+        }
+        let host_path = self.host_path_mut(path)?;
+        let metadata = fs::metadata(&host_path).map_err(to_error)?;
+        let result = match (metadata.is_dir(), recursive) {
+            (false, _) => fs::remove_file(host_path),
+            (true, false) => fs::remove_dir(host_path),
+            (true, true) => fs::remove_dir_all(host_path),
+        };
+        result.map_err(to_error)
+    }
+
+    fn rename(&mut self, from: &VirtualPath, to: &VirtualPath) -> Result<(), FileSystemError> {
+        if from.is_root() {
+            return Err(FileSystemError::ReadOnly);
+        }
+        if to.starts_with(from) {
+            return Err(FileSystemError::InvalidDestination);
+        }
+        let from = self.host_path_mut(from)?;
+        let to = self.host_path_mut(to)?;
+        if to.exists() {
+            return Err(FileSystemError::AlreadyExists);
+        }
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent).map_err(to_error)?;
+        }
+        fs::rename(from, to).map_err(to_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn path(path: &str) -> VirtualPath {
+        VirtualPath::parse(path).unwrap()
+    }
+
+    #[test]
+    fn read_and_write() {
+        let tmp_dir = tempdir().unwrap();
+        let mut backend = DiskFileSystemBackend::new()
+            .with_directory(AirDirectory::ApplicationStorage, tmp_dir.path().into());
+
+        let write = backend.write(&path("app-storage:/saves/slot1.dat"), b"hello");
+        let on_disk = fs::read(tmp_dir.path().join("saves").join("slot1.dat"));
+        let listing = backend.list_directory(&path("app-storage:/saves"));
+        let unmapped = backend.write(&path("file:///documents/a.txt"), b"hello");
+        drop(tmp_dir);
+
+        assert!(write.is_ok());
+        assert_eq!(on_disk.unwrap(), b"hello");
+        assert_eq!(listing.unwrap(), vec!["slot1.dat".to_owned()]);
+        assert!(matches!(unmapped, Err(FileSystemError::ReadOnly)));
+    }
+
+    #[test]
+    fn cannot_escape_directory() {
+        assert!(DiskFileSystemBackend::is_component_allowed("slot1.dat"));
+        assert!(!DiskFileSystemBackend::is_component_allowed(".."));
+        assert!(!DiskFileSystemBackend::is_component_allowed("/etc"));
+        assert!(!DiskFileSystemBackend::is_component_allowed(""));
+    }
+}
@@ -90,6 +90,25 @@ impl PlayingContent {
         }
     }
 
+    /// The host directory containing the files of the content, which AIR
+    /// content sees as `File.applicationDirectory`.
+    #[cfg(feature = "fs")]
+    pub fn application_directory(&self) -> Option<std::path::PathBuf> {
+        use crate::bundle::source::BundleSource;
+
+        match self {
+            PlayingContent::DirectFile(desc) => desc.root_content_path.clone().or_else(|| {
+                let path = desc.url.to_file_path().ok()?;
+                Some(path.parent()?.to_owned())
+            }),
+            PlayingContent::Bundle(_, bundle) => match bundle.source() {
+                BundleSource::Directory(directory) => Some(directory.join("content")),
+                // Zip bundles can't be browsed from the host filesystem.
+                BundleSource::ZipFile(_) => None,
+            },
+        }
+    }
+
     #[cfg(feature = "navigator")]
     pub async fn get_local_file(
         &self,
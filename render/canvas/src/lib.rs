@@ -1079,6 +1079,50 @@ fn swf_shape_to_canvas_commands(
                     },
                 });
             }
+            DrawPath::TexturedTriangles {
+                bitmap_id,
+                is_smoothed,
+                is_repeating,
+                triangles,
+            } => {
+                let Some(size) = bitmap_source.bitmap_size(*bitmap_id) else {
+                    continue;
+                };
+                // Canvas patterns can't be drawn with perspective, so each triangle
+                // is filled with an affine mapping of the bitmap instead.
+                for triangle in triangles {
+                    let Some(matrix) = triangle.bitmap_matrix(size.width, size.height) else {
+                        continue;
+                    };
+                    let Some(bitmap) = create_bitmap_pattern(
+                        *bitmap_id,
+                        matrix,
+                        *is_smoothed,
+                        *is_repeating,
+                        bitmap_source,
+                        backend,
+                    ) else {
+                        continue;
+                    };
+
+                    let [a, b, c] = triangle.points;
+                    let commands = [
+                        DrawCommand::MoveTo(a),
+                        DrawCommand::LineTo(b),
+                        DrawCommand::LineTo(c),
+                    ];
+                    let canvas_path = Path2d::new().expect("Path2d constructor must succeed");
+                    canvas_path.add_path_with_transformation(
+                        &draw_commands_to_path2d(&commands, true),
+                        bounds_viewbox_matrix.unchecked_ref(),
+                    );
+
+                    canvas_data.push(CanvasDrawCommand::Fill {
+                        path: canvas_path,
+                        fill_style: CanvasFillStyle::Bitmap(bitmap),
+                    });
+                }
+            }
         }
     }
 
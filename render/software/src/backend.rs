@@ -0,0 +1,318 @@
+use crate::bitmap::{SoftwareBitmap, SoftwareSyncHandle, as_bitmap};
+use crate::filters::apply_filter;
+use crate::mesh::{Draw, Gradient, Mesh};
+use crate::pixmap::Pixmap;
+use crate::surface::Surface;
+use ruffle_render::backend::{
+    BitmapCacheEntry, Context3D, Context3DProfile, PixelBenderOutput, PixelBenderTarget,
+    RenderBackend, ShapeHandle, ViewportDimensions,
+};
+use ruffle_render::bitmap::{
+    Bitmap, BitmapHandle, BitmapSource, PixelRegion, RgbaBufRead, SyncHandle,
+};
+use ruffle_render::commands::CommandList;
+use ruffle_render::error::Error;
+use ruffle_render::filters::Filter;
+use ruffle_render::pixel_bender::{PixelBenderShader, PixelBenderShaderHandle};
+use ruffle_render::pixel_bender_support::PixelBenderShaderArgument;
+use ruffle_render::quality::StageQuality;
+use ruffle_render::shape_utils::DistilledShape;
+use ruffle_render::tessellator::ShapeTessellator;
+use std::any::Any;
+use std::borrow::Cow;
+use std::num::NonZeroU32;
+use std::sync::Arc;
+use swf::Color;
+
+/// A render backend that rasterizes everything on the CPU.
+///
+/// It's much slower than the wgpu backend, but works on any machine, with or without a GPU.
+pub struct SoftwareRenderBackend {
+    viewport_dimensions: ViewportDimensions,
+    quality: StageQuality,
+    target: Pixmap,
+    shape_tessellator: ShapeTessellator,
+}
+
+impl SoftwareRenderBackend {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            viewport_dimensions: ViewportDimensions {
+                width,
+                height,
+                scale_factor: 1.0,
+            },
+            quality: StageQuality::default(),
+            target: Pixmap::new(width, height),
+            shape_tessellator: ShapeTessellator::new(),
+        }
+    }
+
+    /// Returns the last frame submitted to this backend, with straight alpha.
+    pub fn capture_frame(&self) -> Option<image::RgbaImage> {
+        let mut data = self.target.data().to_vec();
+        ruffle_render::utils::unmultiply_alpha_rgba(&mut data);
+        image::RgbaImage::from_raw(self.target.width(), self.target.height(), data)
+    }
+
+    /// Renders `commands` on top of the pixels of the given bitmap.
+    fn render_to_bitmap(
+        &self,
+        handle: &BitmapHandle,
+        clear: Option<Color>,
+        commands: CommandList,
+        quality: StageQuality,
+    ) -> Pixmap {
+        // Work on a copy, as the commands may very well draw the bitmap being rendered to.
+        let mut pixmap = as_bitmap(handle).pixmap().clone();
+        if let Some(clear) = clear {
+            pixmap.fill(clear);
+        }
+        commands.execute(&mut Surface::new(&mut pixmap, quality));
+        pixmap
+    }
+}
+
+impl RenderBackend for SoftwareRenderBackend {
+    fn viewport_dimensions(&self) -> ViewportDimensions {
+        self.viewport_dimensions
+    }
+
+    fn set_viewport_dimensions(&mut self, dimensions: ViewportDimensions) {
+        self.viewport_dimensions = dimensions;
+        self.target = Pixmap::new(dimensions.width, dimensions.height);
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
+        let lyon_mesh = self
+            .shape_tessellator
+            .tessellate_shape(shape, bitmap_source);
+        let draws = lyon_mesh
+            .draws
+            .into_iter()
+            .filter_map(|draw| Draw::new(draw, bitmap_source, self))
+            .collect();
+        let gradients = lyon_mesh.gradients.into_iter().map(Gradient::new).collect();
+        ShapeHandle(Arc::new(Mesh { draws, gradients }))
+    }
+
+    fn render_offscreen(
+        &mut self,
+        handle: BitmapHandle,
+        commands: CommandList,
+        quality: StageQuality,
+        bounds: PixelRegion,
+    ) -> Option<Box<dyn SyncHandle>> {
+        let pixmap = self.render_to_bitmap(&handle, None, commands, quality);
+        *as_bitmap(&handle).pixmap_mut() = pixmap;
+        Some(Box::new(SoftwareSyncHandle {
+            handle,
+            area: bounds,
+        }))
+    }
+
+    fn apply_filter(
+        &mut self,
+        source: BitmapHandle,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        destination: BitmapHandle,
+        dest_point: (i32, i32),
+        filter: Filter,
+    ) -> Option<Box<dyn SyncHandle>> {
+        let region = as_bitmap(&source).pixmap().region(
+            source_point.0,
+            source_point.1,
+            source_size.0,
+            source_size.1,
+        );
+        let filtered = apply_filter(&region, &filter)?;
+
+        let mut dest = as_bitmap(&destination).pixmap_mut();
+        dest.copy_from(&filtered, (0, 0), dest_point, source_size);
+        let area = PixelRegion::for_whole_size(dest.width(), dest.height());
+        drop(dest);
+
+        Some(Box::new(SoftwareSyncHandle {
+            handle: destination,
+            area,
+        }))
+    }
+
+    fn is_filter_supported(&self, filter: &Filter) -> bool {
+        !matches!(filter, Filter::ShaderFilter(_))
+    }
+
+    fn is_offscreen_supported(&self) -> bool {
+        true
+    }
+
+    fn submit_frame(
+        &mut self,
+        clear: Color,
+        commands: CommandList,
+        cache_entries: Vec<BitmapCacheEntry>,
+    ) {
+        for entry in cache_entries {
+            let mut pixmap = self.render_to_bitmap(
+                &entry.handle,
+                Some(entry.clear),
+                entry.commands,
+                self.quality,
+            );
+            for filter in &entry.filters {
+                if let Some(filtered) = apply_filter(&pixmap, filter) {
+                    pixmap = filtered;
+                }
+            }
+            *as_bitmap(&entry.handle).pixmap_mut() = pixmap;
+        }
+
+        self.target.fill(clear);
+        commands.execute(&mut Surface::new(&mut self.target, self.quality));
+    }
+
+    fn create_empty_texture(
+        &mut self,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<BitmapHandle, Error> {
+        Ok(BitmapHandle(Arc::new(SoftwareBitmap::new(Pixmap::new(
+            width.get(),
+            height.get(),
+        )))))
+    }
+
+    fn register_bitmap(&mut self, bitmap: Bitmap<'_>) -> Result<BitmapHandle, Error> {
+        let bitmap = bitmap.to_rgba();
+        let pixmap = Pixmap::from_rgba(bitmap.width(), bitmap.height(), bitmap.data().to_vec());
+        Ok(BitmapHandle(Arc::new(SoftwareBitmap::new(pixmap))))
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: &BitmapHandle,
+        bitmap: Bitmap<'_>,
+        region: PixelRegion,
+    ) -> Result<(), Error> {
+        let bitmap = bitmap.to_rgba();
+        let source = Pixmap::from_rgba(bitmap.width(), bitmap.height(), bitmap.data().to_vec());
+        as_bitmap(handle).pixmap_mut().copy_from(
+            &source,
+            (region.x_min, region.y_min),
+            (region.x_min as i32, region.y_min as i32),
+            (region.width(), region.height()),
+        );
+        Ok(())
+    }
+
+    fn create_context3d(
+        &mut self,
+        _profile: Context3DProfile,
+    ) -> Result<Box<dyn Context3D>, Error> {
+        Err(Error::Unimplemented("createContext3D".into()))
+    }
+
+    fn debug_info(&self) -> Cow<'static, str> {
+        Cow::Borrowed("Renderer: Software")
+    }
+
+    fn name(&self) -> &'static str {
+        "software"
+    }
+
+    fn set_quality(&mut self, quality: StageQuality) {
+        self.quality = quality;
+    }
+
+    fn compile_pixelbender_shader(
+        &mut self,
+        _shader: PixelBenderShader,
+    ) -> Result<PixelBenderShaderHandle, Error> {
+        Err(Error::Unimplemented(
+            "Pixel bender shader compilation".into(),
+        ))
+    }
+
+    fn run_pixelbender_shader(
+        &mut self,
+        _shader: PixelBenderShaderHandle,
+        _arguments: &[PixelBenderShaderArgument],
+        _target: &PixelBenderTarget,
+    ) -> Result<PixelBenderOutput, Error> {
+        Err(Error::Unimplemented("Pixel bender shader".into()))
+    }
+
+    fn resolve_sync_handle(
+        &mut self,
+        handle: Box<dyn SyncHandle>,
+        with_rgba: RgbaBufRead,
+    ) -> Result<(), Error> {
+        let handle = Box::<dyn Any>::downcast::<SoftwareSyncHandle>(handle)
+            .expect("Sync handle must be a software sync handle");
+        let pixmap = as_bitmap(&handle.handle).pixmap();
+        let row_length = pixmap.width() as usize * 4;
+        let start = handle.area.y_min as usize * row_length + handle.area.x_min as usize * 4;
+        with_rgba(&pixmap.data()[start..], row_length as u32);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ruffle_render::commands::CommandHandler;
+    use ruffle_render::matrix::Matrix;
+    use swf::Twips;
+
+    #[test]
+    fn submit_frame_clears_and_draws() {
+        let mut backend = SoftwareRenderBackend::new(4, 4);
+        let mut commands = CommandList::new();
+        commands.draw_rect(
+            Color::RED,
+            Matrix::create_box(2.0, 2.0, Twips::from_pixels(1.0), Twips::from_pixels(1.0)),
+        );
+        backend.submit_frame(Color::WHITE, commands, vec![]);
+
+        let image = backend.capture_frame().unwrap();
+        assert_eq!(image.get_pixel(0, 0).0, [255, 255, 255, 255]);
+        assert_eq!(image.get_pixel(1, 1).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(3, 3).0, [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn offscreen_rendering_can_be_read_back() {
+        let mut backend = SoftwareRenderBackend::new(1, 1);
+        let handle = backend
+            .create_empty_texture(NonZeroU32::new(2).unwrap(), NonZeroU32::new(2).unwrap())
+            .unwrap();
+        let mut commands = CommandList::new();
+        commands.draw_rect(
+            Color::BLUE,
+            Matrix::create_box(1.0, 1.0, Twips::from_pixels(1.0), Twips::from_pixels(1.0)),
+        );
+        let sync = backend
+            .render_offscreen(
+                handle,
+                commands,
+                StageQuality::High,
+                PixelRegion::for_region(1, 1, 1, 1),
+            )
+            .unwrap();
+
+        backend
+            .resolve_sync_handle(
+                sync,
+                Box::new(|rgba, row_length| {
+                    assert_eq!(row_length, 8);
+                    assert_eq!(rgba[..4], [0, 0, 255, 255]);
+                }),
+            )
+            .unwrap();
+    }
+}
@@ -0,0 +1,40 @@
+use crate::pixmap::Pixmap;
+use ruffle_render::bitmap::{BitmapHandle, BitmapHandleImpl, PixelRegion, SyncHandle};
+use std::any::Any;
+use std::cell::{Ref, RefCell, RefMut};
+
+#[derive(Debug)]
+pub struct SoftwareBitmap {
+    pixmap: RefCell<Pixmap>,
+}
+
+impl SoftwareBitmap {
+    pub fn new(pixmap: Pixmap) -> Self {
+        Self {
+            pixmap: RefCell::new(pixmap),
+        }
+    }
+
+    pub fn pixmap(&self) -> Ref<'_, Pixmap> {
+        self.pixmap.borrow()
+    }
+
+    pub fn pixmap_mut(&self) -> RefMut<'_, Pixmap> {
+        self.pixmap.borrow_mut()
+    }
+}
+
+impl BitmapHandleImpl for SoftwareBitmap {}
+
+pub fn as_bitmap(handle: &BitmapHandle) -> &SoftwareBitmap {
+    <dyn Any>::downcast_ref(&*handle.0).expect("Bitmap handle must be a software bitmap")
+}
+
+/// Rendering happens immediately, so syncing only has to read back the pixels of the bitmap.
+#[derive(Debug)]
+pub struct SoftwareSyncHandle {
+    pub handle: BitmapHandle,
+    pub area: PixelRegion,
+}
+
+impl SyncHandle for SoftwareSyncHandle {}
@@ -0,0 +1,108 @@
+use crate::color::{Rgba, source_over};
+use swf::BlendMode;
+
+/// Composites a premultiplied `src` pixel of a layer onto the premultiplied `dst` pixel of its parent.
+///
+/// These formulas mirror the blend shaders and blend states of the wgpu backend.
+pub fn blend_pixel(mode: BlendMode, src: Rgba, dst: Rgba) -> Rgba {
+    let over_alpha = src[3] + dst[3] * (1.0 - src[3]);
+    match mode {
+        BlendMode::Normal | BlendMode::Layer => source_over(src, dst),
+        BlendMode::Add => [
+            src[0] + dst[0],
+            src[1] + dst[1],
+            src[2] + dst[2],
+            over_alpha,
+        ],
+        BlendMode::Subtract => [
+            dst[0] - src[0],
+            dst[1] - src[1],
+            dst[2] - src[2],
+            over_alpha,
+        ],
+        BlendMode::Screen => [
+            src[0] + dst[0] * (1.0 - src[0]),
+            src[1] + dst[1] * (1.0 - src[1]),
+            src[2] + dst[2] * (1.0 - src[2]),
+            over_alpha,
+        ],
+        _ if src[3] <= 0.0 => dst,
+        BlendMode::Alpha => [
+            dst[0] * src[3],
+            dst[1] * src[3],
+            dst[2] * src[3],
+            dst[3] * src[3],
+        ],
+        BlendMode::Erase => {
+            let keep = 1.0 - src[3];
+            [dst[0] * keep, dst[1] * keep, dst[2] * keep, dst[3] * keep]
+        }
+        // Flash replaces transparent pixels entirely instead of multiplying them.
+        BlendMode::Multiply if dst[3] <= 0.0 => src,
+        BlendMode::Multiply => separable(src, dst, |s, d| s * d),
+        BlendMode::Lighten => separable(src, dst, f32::max),
+        BlendMode::Darken => separable(src, dst, f32::min),
+        BlendMode::Difference => separable(src, dst, |s, d| (d - s).abs()),
+        BlendMode::Invert => separable(src, dst, |_, d| 1.0 - d),
+        BlendMode::Overlay => separable(src, dst, |s, d| {
+            if d <= 0.5 {
+                2.0 * s * d
+            } else {
+                1.0 - 2.0 * (1.0 - d) * (1.0 - s)
+            }
+        }),
+        BlendMode::HardLight => separable(src, dst, |s, d| {
+            if s <= 0.5 {
+                2.0 * s * d
+            } else {
+                1.0 - 2.0 * (1.0 - d) * (1.0 - s)
+            }
+        }),
+    }
+}
+
+/// Applies a separable blend function to the straight alpha colors of `src` and `dst`.
+fn separable(src: Rgba, dst: Rgba, blend: impl Fn(f32, f32) -> f32) -> Rgba {
+    let unmultiply = |color: f32, alpha: f32| if alpha > 0.0 { color / alpha } else { 0.0 };
+    let mut out = [0.0; 4];
+    for (i, component) in out.iter_mut().enumerate().take(3) {
+        *component = src[i] * (1.0 - dst[3])
+            + dst[i] * (1.0 - src[3])
+            + src[3] * dst[3] * blend(unmultiply(src[i], src[3]), unmultiply(dst[i], dst[3]));
+    }
+    out[3] = src[3] + dst[3] * (1.0 - src[3]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transparent_sources_leave_the_parent_alone() {
+        let dst = [0.25, 0.5, 0.0, 0.5];
+        for mode in [
+            BlendMode::Multiply,
+            BlendMode::Difference,
+            BlendMode::Alpha,
+            BlendMode::Erase,
+            BlendMode::Invert,
+        ] {
+            assert_eq!(blend_pixel(mode, [0.0; 4], dst), dst, "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn multiply_onto_transparent_replaces() {
+        let src = [0.5, 0.0, 0.0, 0.5];
+        assert_eq!(blend_pixel(BlendMode::Multiply, src, [0.0; 4]), src);
+        assert_eq!(
+            blend_pixel(
+                BlendMode::Multiply,
+                [1.0, 0.5, 0.0, 1.0],
+                [0.5, 0.5, 0.5, 1.0]
+            ),
+            [0.5, 0.25, 0.0, 1.0]
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use swf::{Color, ColorTransform};
+
+/// A premultiplied RGBA color, with every component normalized to `[0.0, 1.0]`.
+pub type Rgba = [f32; 4];
+
+pub const TRANSPARENT: Rgba = [0.0; 4];
+
+/// Converts a straight alpha color into a premultiplied one.
+pub fn premultiply(color: Color) -> Rgba {
+    let a = f32::from(color.a) / 255.0;
+    [
+        f32::from(color.r) / 255.0 * a,
+        f32::from(color.g) / 255.0 * a,
+        f32::from(color.b) / 255.0 * a,
+        a,
+    ]
+}
+
+#[inline]
+pub fn scale(color: Rgba, factor: f32) -> Rgba {
+    [
+        color[0] * factor,
+        color[1] * factor,
+        color[2] * factor,
+        color[3] * factor,
+    ]
+}
+
+#[inline]
+pub fn lerp(a: Rgba, b: Rgba, t: f32) -> Rgba {
+    [
+        a[0] + (b[0] - a[0]) * t,
+        a[1] + (b[1] - a[1]) * t,
+        a[2] + (b[2] - a[2]) * t,
+        a[3] + (b[3] - a[3]) * t,
+    ]
+}
+
+/// Composites `src` over `dst`, both premultiplied.
+#[inline]
+pub fn source_over(src: Rgba, dst: Rgba) -> Rgba {
+    let inv = 1.0 - src[3];
+    [
+        src[0] + dst[0] * inv,
+        src[1] + dst[1] * inv,
+        src[2] + dst[2] * inv,
+        src[3] + dst[3] * inv,
+    ]
+}
+
+/// Converts a color component from sRGB to linear color space.
+pub fn srgb_to_linear(color: f32) -> f32 {
+    if color <= 0.04045 {
+        color / 12.92
+    } else {
+        f32::powf((color + 0.055) / 1.055, 2.4)
+    }
+}
+
+/// Converts a color component from linear to sRGB color space.
+pub fn linear_to_srgb(color: f32) -> f32 {
+    if color < 0.0031308 {
+        color * 12.92
+    } else {
+        1.055 * color.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// A `ColorTransform` with its components normalized, ready to be applied per pixel.
+#[derive(Clone, Copy, Debug)]
+pub struct ColorAdjustment {
+    mult: [f32; 4],
+    add: [f32; 4],
+}
+
+impl ColorAdjustment {
+    pub fn new(transform: &ColorTransform) -> Self {
+        Self {
+            mult: transform.mult_rgba_normalized(),
+            add: transform.add_rgba_normalized(),
+        }
+    }
+
+    /// Applies this transform to a straight alpha color, returning a premultiplied color.
+    ///
+    /// This matches the solid color and gradient shaders of the wgpu backend.
+    #[inline]
+    pub fn apply_straight(&self, color: [f32; 4]) -> Rgba {
+        let r = (color[0] * self.mult[0] + self.add[0]).clamp(0.0, 1.0);
+        let g = (color[1] * self.mult[1] + self.add[1]).clamp(0.0, 1.0);
+        let b = (color[2] * self.mult[2] + self.add[2]).clamp(0.0, 1.0);
+        let a = (color[3] * self.mult[3] + self.add[3]).clamp(0.0, 1.0);
+        [r * a, g * a, b * a, a]
+    }
+
+    /// Applies this transform to a premultiplied color.
+    ///
+    /// Fully transparent pixels are left untouched, like in the bitmap shader of the wgpu backend.
+    #[inline]
+    pub fn apply_premultiplied(&self, color: Rgba) -> Rgba {
+        if color[3] <= 0.0 {
+            return color;
+        }
+        self.apply_straight([
+            color[0] / color[3],
+            color[1] / color[3],
+            color[2] / color[3],
+            color[3],
+        ])
+    }
+}
+
+/// Converts a `Color` into straight alpha normalized components.
+pub fn straight(color: Color) -> [f32; 4] {
+    [
+        f32::from(color.r) / 255.0,
+        f32::from(color.g) / 255.0,
+        f32::from(color.b) / 255.0,
+        f32::from(color.a) / 255.0,
+    ]
+}
@@ -0,0 +1,532 @@
+use crate::bitmap::as_bitmap;
+use crate::color::{Rgba, TRANSPARENT, premultiply, straight};
+use crate::mesh::gradient_filter_colors;
+use crate::pixmap::Pixmap;
+use ruffle_render::filters::{DisplacementMapFilter, DisplacementMapFilterMode, Filter};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// An image with floating point components, so that multiple passes don't accumulate rounding errors.
+#[derive(Clone)]
+struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<Rgba>,
+}
+
+impl Image {
+    fn from_pixmap(pixmap: &Pixmap) -> Self {
+        let mut pixels = Vec::with_capacity(pixmap.width() as usize * pixmap.height() as usize);
+        for y in 0..pixmap.height() {
+            for x in 0..pixmap.width() {
+                pixels.push(pixmap.get(x, y));
+            }
+        }
+        Self {
+            width: pixmap.width(),
+            height: pixmap.height(),
+            pixels,
+        }
+    }
+
+    fn to_pixmap(&self) -> Pixmap {
+        let mut pixmap = Pixmap::new(self.width, self.height);
+        for (i, pixel) in self.pixels.iter().enumerate() {
+            let i = i as u32;
+            pixmap.set(i % self.width, i / self.width, *pixel);
+        }
+        pixmap
+    }
+
+    #[inline]
+    fn get(&self, x: i32, y: i32) -> Rgba {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            TRANSPARENT
+        } else {
+            self.pixels[y as usize * self.width as usize + x as usize]
+        }
+    }
+
+    /// Bilinearly samples the alpha channel at the given position in pixels.
+    ///
+    /// Anything outside of the image is fully transparent.
+    fn sample_alpha(&self, x: f32, y: f32) -> f32 {
+        if x < 0.0 || y < 0.0 || x > self.width as f32 || y > self.height as f32 {
+            return 0.0;
+        }
+        let x = x - 0.5;
+        let y = y - 0.5;
+        let x0 = x.floor();
+        let y0 = y.floor();
+        let fx = x - x0;
+        let fy = y - y0;
+        let clamp = |value: i32, max: u32| value.clamp(0, max as i32 - 1);
+        let x1 = clamp(x0 as i32 + 1, self.width);
+        let y1 = clamp(y0 as i32 + 1, self.height);
+        let x0 = clamp(x0 as i32, self.width);
+        let y0 = clamp(y0 as i32, self.height);
+        let top = self.get(x0, y0)[3] * (1.0 - fx) + self.get(x1, y0)[3] * fx;
+        let bottom = self.get(x0, y1)[3] * (1.0 - fx) + self.get(x1, y1)[3] * fx;
+        top * (1.0 - fy) + bottom * fy
+    }
+
+    fn map(&self, f: impl Fn(u32, u32, Rgba) -> Rgba) -> Image {
+        let pixels = self
+            .pixels
+            .iter()
+            .enumerate()
+            .map(|(i, pixel)| {
+                let i = i as u32;
+                f(i % self.width, i / self.width, *pixel)
+            })
+            .collect();
+        Image {
+            width: self.width,
+            height: self.height,
+            pixels,
+        }
+    }
+}
+
+/// Applies a filter to the whole `source`, producing an image of the same size.
+///
+/// Returns `None` if the filter isn't supported by this backend.
+pub fn apply_filter(source: &Pixmap, filter: &Filter) -> Option<Pixmap> {
+    let image = Image::from_pixmap(source);
+    let result = match filter {
+        Filter::BlurFilter(filter) => blur(image, filter),
+        Filter::ColorMatrixFilter(filter) => color_matrix(&image, filter),
+        Filter::ConvolutionFilter(filter) => convolution(&image, filter),
+        Filter::GlowFilter(filter) => glow(&image, filter, (0.0, 0.0)),
+        Filter::DropShadowFilter(filter) => {
+            let (x, y) = polar(filter.angle, filter.distance);
+            glow(&image, &filter.inner_glow_filter(), (-x, -y))
+        }
+        Filter::BevelFilter(filter) => {
+            let bevel_type = BevelType::new(filter.is_inner(), filter.is_on_top());
+            let highlight = premultiply(filter.highlight_color);
+            let shadow = premultiply(filter.shadow_color);
+            let strength = filter.strength.to_f32();
+            bevel(
+                &image,
+                &filter.inner_blur_filter(),
+                polar(filter.angle, filter.distance),
+                bevel_type,
+                filter.is_knockout(),
+                |difference| {
+                    let highlight_alpha = (difference * strength).clamp(0.0, 1.0);
+                    let shadow_alpha = (-difference * strength).clamp(0.0, 1.0);
+                    std::array::from_fn(|i| {
+                        highlight[i] * highlight_alpha + shadow[i] * shadow_alpha
+                    })
+                },
+            )
+        }
+        Filter::GradientBevelFilter(filter) => {
+            let bevel_type = BevelType::new(filter.is_inner(), filter.is_on_top());
+            let colors = gradient_filter_colors(&filter.colors);
+            let strength = filter.strength.to_f32();
+            // The start of the gradient is the highlight, the end is the shadow.
+            bevel(
+                &image,
+                &filter.inner_blur_filter(),
+                polar(filter.angle, filter.distance),
+                bevel_type,
+                filter.is_knockout(),
+                |difference| lookup(&colors, 0.5 - difference * strength * 0.5),
+            )
+        }
+        Filter::GradientGlowFilter(filter) => {
+            let bevel_type = BevelType::new(filter.is_inner(), filter.is_on_top());
+            let colors = gradient_filter_colors(&filter.colors);
+            let strength = filter.strength.to_f32();
+            let (x, y) = polar(filter.angle, filter.distance);
+            let blurred = blur(image.clone(), &filter.inner_blur_filter());
+            let glow = |x: u32, y: u32, offset: (f32, f32)| {
+                let blur =
+                    blurred.sample_alpha(x as f32 + 0.5 + offset.0, y as f32 + 0.5 + offset.1);
+                let blur = if bevel_type == BevelType::Inner {
+                    1.0 - blur
+                } else {
+                    blur
+                };
+                lookup(&colors, blur * strength)
+            };
+            image.map(|px, py, dest| {
+                composite_bevel(
+                    glow(px, py, (-x, -y)),
+                    dest,
+                    bevel_type,
+                    filter.is_knockout(),
+                )
+            })
+        }
+        Filter::DisplacementMapFilter(filter) => displacement_map(&image, filter),
+        Filter::ShaderFilter(_) => {
+            static WARNED: AtomicBool = AtomicBool::new(false);
+            if !WARNED.swap(true, Ordering::Relaxed) {
+                tracing::warn!("Shader filters are not supported by the software renderer");
+            }
+            return None;
+        }
+    };
+    Some(result.to_pixmap())
+}
+
+/// Converts an angle (in radians) and a distance into an offset in pixels.
+fn polar(angle: swf::Fixed16, distance: swf::Fixed16) -> (f32, f32) {
+    let angle = angle.to_f32();
+    let distance = distance.to_f32();
+    (angle.cos() * distance, angle.sin() * distance)
+}
+
+/// Looks up a premultiplied color in a gradient lookup table, for `t` in `[0.0, 1.0]`.
+fn lookup(colors: &[Rgba], t: f32) -> Rgba {
+    let index = (t.clamp(0.0, 1.0) * (colors.len() - 1) as f32).round() as usize;
+    colors[index]
+}
+
+fn blur(mut image: Image, filter: &swf::BlurFilter) -> Image {
+    for _ in 0..filter.num_passes() {
+        for horizontal in [true, false] {
+            let strength = if horizontal {
+                filter.blur_x.to_f32()
+            } else {
+                filter.blur_y.to_f32()
+            };
+            // Full width of the kernel (left edge to right edge)
+            let full_size = strength.min(255.0);
+            if full_size > 1.0 {
+                image = blur_pass(&image, full_size, horizontal);
+            }
+        }
+    }
+    image
+}
+
+/// A box blur along a single axis, with fractional weights at both ends of the kernel.
+fn blur_pass(image: &Image, full_size: f32, horizontal: bool) -> Image {
+    let (width, height) = (image.width as usize, image.height as usize);
+    let (length, lines) = if horizontal {
+        (width, height)
+    } else {
+        (height, width)
+    };
+    let index = |line: usize, i: usize| {
+        if horizontal {
+            line * width + i
+        } else {
+            i * width + line
+        }
+    };
+
+    let mut pixels = vec![TRANSPARENT; image.pixels.len()];
+    let mut prefix = vec![TRANSPARENT; length + 1];
+    for line in 0..lines {
+        for i in 0..length {
+            let pixel = image.pixels[index(line, i)];
+            prefix[i + 1] = std::array::from_fn(|c| prefix[i][c] + pixel[c]);
+        }
+
+        // The sum of every pixel covering `[0, x]`, where pixel `i` spans `[i, i + 1]`.
+        let integral = |x: f32| -> Rgba {
+            let x = x.clamp(0.0, length as f32);
+            let whole = x.floor() as usize;
+            if whole >= length {
+                return prefix[length];
+            }
+            let fraction = x - whole as f32;
+            let pixel = image.pixels[index(line, whole)];
+            std::array::from_fn(|c| prefix[whole][c] + pixel[c] * fraction)
+        };
+
+        let half = full_size / 2.0;
+        for i in 0..length {
+            let center = i as f32 + 0.5;
+            let to = integral(center + half);
+            let from = integral(center - half);
+            // This rounding imitates the fixed-point computations of Flash Player.
+            pixels[index(line, i)] =
+                std::array::from_fn(|c| ((to[c] - from[c]) / full_size * 255.0).floor() / 255.0);
+        }
+    }
+
+    Image {
+        width: image.width,
+        height: image.height,
+        pixels,
+    }
+}
+
+fn color_matrix(image: &Image, filter: &swf::ColorMatrixFilter) -> Image {
+    let m = &filter.matrix;
+    image.map(|_, _, src| {
+        let unmultiply = |c: f32| if src[3] > 0.0 { c / src[3] } else { 0.0 };
+        let (r, g, b, a) = (
+            unmultiply(src[0]),
+            unmultiply(src[1]),
+            unmultiply(src[2]),
+            src[3],
+        );
+        let row = |i: usize| {
+            (m[i] * r + m[i + 1] * g + m[i + 2] * b + m[i + 3] * a + m[i + 4] / 255.0)
+                .clamp(0.0, 1.0)
+        };
+        let alpha = row(15);
+        [row(0) * alpha, row(5) * alpha, row(10) * alpha, alpha]
+    })
+}
+
+fn convolution(image: &Image, filter: &swf::ConvolutionFilter) -> Image {
+    let rows = filter.num_matrix_rows as i32;
+    let cols = filter.num_matrix_cols as i32;
+    let divisor = if filter.divisor == 0.0 {
+        1.0
+    } else {
+        filter.divisor
+    };
+    let bias = filter.bias / 255.0;
+    let default_color = straight(filter.default_color);
+    let preserve_alpha = filter.is_preserve_alpha();
+
+    let straight_at = |x: i32, y: i32| -> [f32; 4] {
+        let inside = x >= 0 && y >= 0 && x < image.width as i32 && y < image.height as i32;
+        if !inside && !filter.is_clamped() {
+            return default_color;
+        }
+        let pixel = image.get(
+            x.clamp(0, image.width as i32 - 1),
+            y.clamp(0, image.height as i32 - 1),
+        );
+        if pixel[3] > 0.0 {
+            [
+                pixel[0] / pixel[3],
+                pixel[1] / pixel[3],
+                pixel[2] / pixel[3],
+                pixel[3],
+            ]
+        } else {
+            [0.0; 4]
+        }
+    };
+
+    image.map(|x, y, src| {
+        let mut sum = [0.0; 4];
+        for row in 0..rows {
+            for col in 0..cols {
+                let weight = filter
+                    .matrix
+                    .get((row * cols + col) as usize)
+                    .copied()
+                    .unwrap_or_default();
+                if weight == 0.0 {
+                    continue;
+                }
+                let color = straight_at(x as i32 + col - cols / 2, y as i32 + row - rows / 2);
+                for (total, component) in sum.iter_mut().zip(color) {
+                    *total += component * weight;
+                }
+            }
+        }
+        let channel = |c: usize| (sum[c] / divisor + bias).clamp(0.0, 1.0);
+        let alpha = if preserve_alpha { src[3] } else { channel(3) };
+        [
+            channel(0) * alpha,
+            channel(1) * alpha,
+            channel(2) * alpha,
+            alpha,
+        ]
+    })
+}
+
+fn glow(image: &Image, filter: &swf::GlowFilter, blur_offset: (f32, f32)) -> Image {
+    let blurred = blur(image.clone(), &filter.inner_blur_filter());
+    let color = straight(filter.color);
+    let strength = filter.strength.to_f32();
+    let inner = filter.is_inner();
+    let knockout = filter.is_knockout();
+    let composite_source = filter.composite_source();
+
+    image.map(|x, y, dest| {
+        let blur = blurred.sample_alpha(
+            x as f32 + 0.5 + blur_offset.0,
+            y as f32 + 0.5 + blur_offset.1,
+        );
+        let glow = |alpha: f32| [color[0] * alpha, color[1] * alpha, color[2] * alpha, alpha];
+
+        if inner {
+            let alpha = color[3] * ((1.0 - blur) * strength).clamp(0.0, 1.0);
+            let glow = glow(alpha * dest[3]);
+            if composite_source && !knockout {
+                std::array::from_fn(|i| glow[i] + dest[i] * (1.0 - alpha))
+            } else {
+                glow
+            }
+        } else {
+            let alpha = color[3] * (blur * strength).clamp(0.0, 1.0);
+            if knockout {
+                glow(alpha * (1.0 - dest[3]))
+            } else if composite_source {
+                let glow = glow(alpha * (1.0 - dest[3]));
+                std::array::from_fn(|i| glow[i] + dest[i])
+            } else {
+                glow(alpha)
+            }
+        }
+    })
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum BevelType {
+    Inner,
+    Outer,
+    Full,
+}
+
+impl BevelType {
+    fn new(inner: bool, on_top: bool) -> Self {
+        if on_top {
+            BevelType::Full
+        } else if inner {
+            BevelType::Inner
+        } else {
+            BevelType::Outer
+        }
+    }
+}
+
+/// Shared implementation of the bevel filters.
+///
+/// `glow` maps the difference between the highlight and the shadow sides of the
+/// blurred object to the premultiplied color of the bevel at that point.
+fn bevel(
+    image: &Image,
+    blur_filter: &swf::BlurFilter,
+    blur_offset: (f32, f32),
+    bevel_type: BevelType,
+    knockout: bool,
+    glow: impl Fn(f32) -> Rgba,
+) -> Image {
+    let blurred = blur(image.clone(), blur_filter);
+    image.map(|x, y, dest| {
+        let (x, y) = (x as f32 + 0.5, y as f32 + 0.5);
+        let left = blurred.sample_alpha(x + blur_offset.0, y + blur_offset.1);
+        let right = blurred.sample_alpha(x - blur_offset.0, y - blur_offset.1);
+        composite_bevel(glow(left - right), dest, bevel_type, knockout)
+    })
+}
+
+fn composite_bevel(glow: Rgba, dest: Rgba, bevel_type: BevelType, knockout: bool) -> Rgba {
+    match (bevel_type, knockout) {
+        (BevelType::Full, true) => glow,
+        (BevelType::Full, false) => std::array::from_fn(|i| dest[i] - dest[i] * glow[3] + glow[i]),
+        (BevelType::Inner, true) => std::array::from_fn(|i| glow[i] * dest[3]),
+        (BevelType::Inner, false) => {
+            std::array::from_fn(|i| glow[i] * dest[3] + dest[i] * (1.0 - glow[3]))
+        }
+        (BevelType::Outer, true) => std::array::from_fn(|i| glow[i] - glow[i] * dest[3]),
+        (BevelType::Outer, false) => std::array::from_fn(|i| dest[i] + glow[i] - glow[i] * dest[3]),
+    }
+}
+
+fn displacement_map(image: &Image, filter: &DisplacementMapFilter) -> Image {
+    let Some(map_handle) = &filter.map_bitmap else {
+        return image.clone();
+    };
+    let map = as_bitmap(map_handle).pixmap().clone();
+    let component = |color: Rgba, component: u8| -> f32 {
+        match component {
+            1 => color[0] * 255.0,
+            2 => color[1] * 255.0,
+            4 => color[2] * 255.0,
+            8 => color[3] * 255.0,
+            // This means no displacement.
+            _ => 128.0,
+        }
+    };
+    let (width, height) = (image.width as f32, image.height as f32);
+    let out_of_bounds_color = premultiply(filter.color);
+
+    image.map(|x, y, src| {
+        let (x, y) = (x as f32 + 0.5, y as f32 + 0.5);
+        let map_x = (x - filter.map_point.0 as f32) / filter.viewscale_x;
+        let map_y = (y - filter.map_point.1 as f32) / filter.viewscale_y;
+        let map_color = if map_x < 0.0
+            || map_y < 0.0
+            || map_x > map.width() as f32
+            || map_y > map.height() as f32
+        {
+            [0.5; 4]
+        } else {
+            map.sample(map_x, map_y, false, false)
+        };
+
+        let displaced_x = x
+            + (component(map_color, filter.component_x) - 128.0)
+                * filter.viewscale_x
+                * filter.scale_x
+                / 256.0;
+        let displaced_y = y
+            + (component(map_color, filter.component_y) - 128.0)
+                * filter.viewscale_y
+                * filter.scale_y
+                / 256.0;
+        let out_of_bounds =
+            displaced_x < 0.0 || displaced_y < 0.0 || displaced_x > width || displaced_y > height;
+
+        let (sample_x, sample_y) = match filter.mode {
+            DisplacementMapFilterMode::Wrap => (
+                displaced_x.rem_euclid(width),
+                displaced_y.rem_euclid(height),
+            ),
+            DisplacementMapFilterMode::Clamp => (
+                displaced_x.clamp(0.0, width - 1.0),
+                displaced_y.clamp(0.0, height - 1.0),
+            ),
+            DisplacementMapFilterMode::Ignore if out_of_bounds => return src,
+            DisplacementMapFilterMode::Color if out_of_bounds => return out_of_bounds_color,
+            DisplacementMapFilterMode::Ignore | DisplacementMapFilterMode::Color => {
+                (displaced_x, displaced_y)
+            }
+        };
+        image.get(sample_x.floor() as i32, sample_y.floor() as i32)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::{BlurFilterFlags, Fixed16};
+
+    #[test]
+    fn box_blur_preserves_total_alpha() {
+        let mut pixmap = Pixmap::new(16, 1);
+        pixmap.set(8, 0, [1.0, 1.0, 1.0, 1.0]);
+
+        let blurred = blur(
+            Image::from_pixmap(&pixmap),
+            &swf::BlurFilter {
+                blur_x: Fixed16::from_f32(4.0),
+                blur_y: Fixed16::ONE,
+                flags: BlurFilterFlags::from_passes(1),
+            },
+        );
+
+        let total: f32 = blurred.pixels.iter().map(|pixel| pixel[3]).sum();
+        assert!((total - 1.0).abs() < 0.02, "total alpha was {total}");
+        assert_eq!(blurred.pixels[8][3], blurred.pixels[7][3]);
+        assert_eq!(blurred.pixels[5][3], 0.0);
+    }
+
+    #[test]
+    fn identity_color_matrix_is_a_no_op() {
+        let mut pixmap = Pixmap::new(1, 1);
+        pixmap.set(0, 0, [0.2, 0.4, 0.0, 0.8]);
+
+        let result = apply_filter(
+            &pixmap,
+            &Filter::ColorMatrixFilter(swf::ColorMatrixFilter::default()),
+        )
+        .unwrap();
+        assert_eq!(result.data(), pixmap.data());
+    }
+}
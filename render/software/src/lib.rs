@@ -0,0 +1,14 @@
+// Remove this when we decide on how to handle multithreaded rendering (especially on wasm)
+#![allow(clippy::arc_with_non_send_sync)]
+
+pub mod backend;
+mod bitmap;
+mod blend;
+mod color;
+mod filters;
+mod mesh;
+mod pixmap;
+mod raster;
+mod surface;
+
+pub use backend::SoftwareRenderBackend;
@@ -0,0 +1,263 @@
+use crate::color::{Rgba, linear_to_srgb, srgb_to_linear, straight};
+use crate::raster::{Affine, Point};
+use ruffle_render::backend::{RenderBackend, ShapeHandle, ShapeHandleImpl};
+use ruffle_render::bitmap::{BitmapHandle, BitmapSource};
+use ruffle_render::shape_utils::GradientType;
+use ruffle_render::tessellator::{
+    Draw as LyonDraw, DrawType as TessDrawType, Gradient as TessGradient,
+};
+use std::any::Any;
+use swf::{Color, GradientInterpolation, GradientSpread};
+
+/// How many colors to precompute for each gradient, like the gradient textures of the wgpu backend.
+const GRADIENT_SIZE: usize = 256;
+
+#[derive(Debug)]
+pub struct Mesh {
+    pub draws: Vec<Draw>,
+    pub gradients: Vec<Gradient>,
+}
+
+impl ShapeHandleImpl for Mesh {}
+
+pub fn as_mesh(handle: &ShapeHandle) -> &Mesh {
+    <dyn Any>::downcast_ref(&*handle.0).expect("Shape handle must be a software Mesh")
+}
+
+#[derive(Debug)]
+pub struct Draw {
+    pub fill: Fill,
+    pub triangles: Vec<Triangle>,
+    /// The number of leading triangles used when this draw is part of a mask.
+    pub num_mask_triangles: usize,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Triangle {
+    /// The corners of this triangle, in shape space pixels.
+    pub points: [Point; 3],
+    /// The color of a solid fill. Every vertex of a tessellated path shares the same color.
+    pub color: Color,
+}
+
+#[derive(Debug)]
+pub enum Fill {
+    Color,
+    Gradient {
+        /// Maps shape space to gradient space, where the gradient spans `[0.0, 1.0]`.
+        matrix: Affine,
+        gradient: usize,
+    },
+    Bitmap {
+        /// Maps shape space to normalized bitmap coordinates.
+        matrix: Affine,
+        handle: BitmapHandle,
+        is_smoothed: bool,
+        is_repeating: bool,
+    },
+    TexturedTriangles {
+        /// The homogeneous normalized bitmap coordinates of the corners of
+        /// each triangle of the draw.
+        texture_coords: Vec<[[f32; 3]; 3]>,
+        handle: BitmapHandle,
+        is_smoothed: bool,
+        is_repeating: bool,
+    },
+}
+
+impl Draw {
+    pub fn new(
+        draw: LyonDraw,
+        source: &dyn BitmapSource,
+        backend: &mut dyn RenderBackend,
+    ) -> Option<Self> {
+        let fill = match draw.draw_type {
+            TessDrawType::Color => Fill::Color,
+            TessDrawType::Gradient { matrix, gradient } => Fill::Gradient {
+                matrix: Affine::from_columns(matrix),
+                gradient,
+            },
+            TessDrawType::Bitmap(bitmap) => Fill::Bitmap {
+                matrix: Affine::from_columns(bitmap.matrix),
+                handle: source.bitmap_handle(bitmap.bitmap_id, backend)?,
+                is_smoothed: bitmap.is_smoothed,
+                is_repeating: bitmap.is_repeating,
+            },
+            TessDrawType::TexturedTriangles(textured) => Fill::TexturedTriangles {
+                texture_coords: draw
+                    .indices
+                    .chunks_exact(3)
+                    .map(|indices| [0, 1, 2].map(|i| textured.texture_coords[indices[i] as usize]))
+                    .collect(),
+                handle: source.bitmap_handle(textured.bitmap_id, backend)?,
+                is_smoothed: textured.is_smoothed,
+                is_repeating: textured.is_repeating,
+            },
+        };
+
+        let triangles = draw
+            .indices
+            .chunks_exact(3)
+            .map(|indices| {
+                let vertex = |i: u32| &draw.vertices[i as usize];
+                Triangle {
+                    points: [
+                        [vertex(indices[0]).x, vertex(indices[0]).y],
+                        [vertex(indices[1]).x, vertex(indices[1]).y],
+                        [vertex(indices[2]).x, vertex(indices[2]).y],
+                    ],
+                    color: vertex(indices[0]).color,
+                }
+            })
+            .collect();
+
+        Some(Self {
+            fill,
+            triangles,
+            num_mask_triangles: draw.mask_index_count as usize / 3,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Gradient {
+    gradient_type: GradientType,
+    repeat_mode: GradientSpread,
+    focal_point: f32,
+    interpolation: GradientInterpolation,
+    /// Straight alpha colors, evenly spread over the gradient.
+    colors: Box<[[f32; 4]; GRADIENT_SIZE]>,
+}
+
+impl Gradient {
+    pub fn new(gradient: TessGradient) -> Self {
+        let mut colors = Box::new([[0.0; 4]; GRADIENT_SIZE]);
+        let records = &gradient.records;
+
+        if !records.is_empty() {
+            let convert = |color: Color| {
+                let mut color = straight(color);
+                if gradient.interpolation == GradientInterpolation::LinearRgb {
+                    for component in &mut color[..3] {
+                        *component = srgb_to_linear(*component);
+                    }
+                }
+                color
+            };
+
+            let mut last = 0;
+            for (t, color) in colors.iter_mut().enumerate() {
+                if last + 1 < records.len() && t > records[last + 1].ratio as usize {
+                    last += 1;
+                }
+                let next = (last + 1).min(records.len() - 1);
+                let last_record = &records[last];
+                let next_record = &records[next];
+
+                let a = if t <= last_record.ratio as usize || last_record.ratio == next_record.ratio
+                {
+                    0.0
+                } else if t > next_record.ratio as usize {
+                    1.0
+                } else {
+                    (t as f32 - last_record.ratio as f32)
+                        / (next_record.ratio as f32 - last_record.ratio as f32)
+                };
+
+                let from = convert(last_record.color);
+                let to = convert(next_record.color);
+                for (i, component) in color.iter_mut().enumerate() {
+                    *component = from[i] + (to[i] - from[i]) * a;
+                }
+            }
+        }
+
+        Self {
+            gradient_type: gradient.gradient_type,
+            repeat_mode: gradient.repeat_mode,
+            focal_point: gradient.focal_point.to_f32().clamp(-0.98, 0.98),
+            interpolation: gradient.interpolation,
+            colors,
+        }
+    }
+
+    /// Returns the straight alpha color of this gradient at the given position in gradient space.
+    pub fn color_at(&self, uv: Point) -> [f32; 4] {
+        let t = match self.gradient_type {
+            GradientType::Linear => uv[0],
+            GradientType::Radial => {
+                let x = uv[0] * 2.0 - 1.0;
+                let y = uv[1] * 2.0 - 1.0;
+                (x * x + y * y).sqrt()
+            }
+            GradientType::Focal => {
+                let x = uv[0] * 2.0 - 1.0;
+                let y = uv[1] * 2.0 - 1.0;
+                let focal = self.focal_point;
+                let dx = focal - x;
+                let dy = -y;
+                let l = (dx * dx + dy * dy).sqrt();
+                if l == 0.0 {
+                    0.0
+                } else {
+                    let (dx, dy) = (dx / l, dy / l);
+                    l / ((1.0 - focal * focal * dy * dy).sqrt() + focal * dx)
+                }
+            }
+        };
+
+        let t = match self.repeat_mode {
+            GradientSpread::Pad => t.clamp(0.0, 1.0),
+            GradientSpread::Reflect => {
+                let t = t.abs();
+                if (t as i32) & 1 == 0 {
+                    t.fract()
+                } else {
+                    1.0 - t.fract()
+                }
+            }
+            GradientSpread::Repeat => t - t.floor(),
+        };
+
+        // Linearly filter between the precomputed colors, clamping at both ends.
+        let position = (t * GRADIENT_SIZE as f32 - 0.5).clamp(0.0, (GRADIENT_SIZE - 1) as f32);
+        let index = position.floor() as usize;
+        let next = (index + 1).min(GRADIENT_SIZE - 1);
+        let fraction = position - index as f32;
+        let mut color = [0.0; 4];
+        for (i, component) in color.iter_mut().enumerate() {
+            let from = self.colors[index][i];
+            *component = from + (self.colors[next][i] - from) * fraction;
+        }
+
+        if self.interpolation == GradientInterpolation::LinearRgb {
+            for component in &mut color[..3] {
+                *component = linear_to_srgb(*component);
+            }
+        }
+        color
+    }
+}
+
+/// Converts gradient records into a color lookup, used by the gradient filters.
+pub fn gradient_filter_colors(records: &[swf::GradientRecord]) -> Vec<Rgba> {
+    let gradient = Gradient::new(TessGradient {
+        gradient_type: GradientType::Linear,
+        repeat_mode: GradientSpread::Pad,
+        focal_point: swf::Fixed8::ZERO,
+        interpolation: GradientInterpolation::Rgb,
+        records: records.to_vec(),
+    });
+    gradient
+        .colors
+        .iter()
+        .map(|color| {
+            [
+                color[0] * color[3],
+                color[1] * color[3],
+                color[2] * color[3],
+                color[3],
+            ]
+        })
+        .collect()
+}
@@ -0,0 +1,204 @@
+use crate::color::{Rgba, TRANSPARENT, premultiply};
+use swf::Color;
+
+/// An image stored as premultiplied RGBA8, the same layout as `BitmapFormat::Rgba`.
+#[derive(Clone, Debug)]
+pub struct Pixmap {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+impl Pixmap {
+    /// Creates a fully transparent pixmap.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            data: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    pub fn from_rgba(width: u32, height: u32, data: Vec<u8>) -> Self {
+        debug_assert_eq!(data.len(), width as usize * height as usize * 4);
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn fill(&mut self, color: Color) {
+        let color = to_bytes(premultiply(color));
+        for pixel in self.data.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&color);
+        }
+    }
+
+    #[inline]
+    fn index(&self, x: u32, y: u32) -> usize {
+        (y as usize * self.width as usize + x as usize) * 4
+    }
+
+    #[inline]
+    pub fn get(&self, x: u32, y: u32) -> Rgba {
+        let i = self.index(x, y);
+        [
+            f32::from(self.data[i]) / 255.0,
+            f32::from(self.data[i + 1]) / 255.0,
+            f32::from(self.data[i + 2]) / 255.0,
+            f32::from(self.data[i + 3]) / 255.0,
+        ]
+    }
+
+    #[inline]
+    pub fn set(&mut self, x: u32, y: u32, color: Rgba) {
+        let i = self.index(x, y);
+        self.data[i..i + 4].copy_from_slice(&to_bytes(color));
+    }
+
+    #[inline]
+    fn get_wrapped(&self, x: i32, y: i32, repeating: bool) -> Rgba {
+        let (width, height) = (self.width as i32, self.height as i32);
+        let (x, y) = if repeating {
+            (x.rem_euclid(width), y.rem_euclid(height))
+        } else {
+            (x.clamp(0, width - 1), y.clamp(0, height - 1))
+        };
+        self.get(x as u32, y as u32)
+    }
+
+    /// Samples this pixmap at the given position, expressed in pixels.
+    ///
+    /// Positions outside of the pixmap either wrap around or are clamped to the nearest edge.
+    pub fn sample(&self, x: f32, y: f32, smoothed: bool, repeating: bool) -> Rgba {
+        if self.width == 0 || self.height == 0 {
+            return TRANSPARENT;
+        }
+
+        if !smoothed {
+            return self.get_wrapped(x.floor() as i32, y.floor() as i32, repeating);
+        }
+
+        let x = x - 0.5;
+        let y = y - 0.5;
+        let x0 = x.floor();
+        let y0 = y.floor();
+        let fx = x - x0;
+        let fy = y - y0;
+        let (x0, y0) = (x0 as i32, y0 as i32);
+
+        let top = crate::color::lerp(
+            self.get_wrapped(x0, y0, repeating),
+            self.get_wrapped(x0 + 1, y0, repeating),
+            fx,
+        );
+        let bottom = crate::color::lerp(
+            self.get_wrapped(x0, y0 + 1, repeating),
+            self.get_wrapped(x0 + 1, y0 + 1, repeating),
+            fx,
+        );
+        crate::color::lerp(top, bottom, fy)
+    }
+
+    /// Copies the given area of this pixmap into a new one.
+    ///
+    /// Parts of the area outside of this pixmap are left transparent.
+    pub fn region(&self, x: u32, y: u32, width: u32, height: u32) -> Pixmap {
+        let mut region = Pixmap::new(width, height);
+        region.copy_from(self, (x, y), (0, 0), (width, height));
+        region
+    }
+
+    /// Copies an area of `source` onto this pixmap, replacing the existing pixels.
+    ///
+    /// The area is clipped against both pixmaps.
+    pub fn copy_from(
+        &mut self,
+        source: &Pixmap,
+        source_point: (u32, u32),
+        dest_point: (i32, i32),
+        size: (u32, u32),
+    ) {
+        // Shift the source area when the destination starts outside of this pixmap.
+        let src_x = source_point.0 as i64 + i64::from(dest_point.0.min(0).unsigned_abs());
+        let src_y = source_point.1 as i64 + i64::from(dest_point.1.min(0).unsigned_abs());
+        let dest_x = dest_point.0.max(0) as i64;
+        let dest_y = dest_point.1.max(0) as i64;
+        let skipped_x = src_x - source_point.0 as i64;
+        let skipped_y = src_y - source_point.1 as i64;
+
+        let width = (size.0 as i64 - skipped_x)
+            .min(source.width as i64 - src_x)
+            .min(self.width as i64 - dest_x);
+        let height = (size.1 as i64 - skipped_y)
+            .min(source.height as i64 - src_y)
+            .min(self.height as i64 - dest_y);
+        if width <= 0 || height <= 0 {
+            return;
+        }
+
+        let row_len = width as usize * 4;
+        for row in 0..height {
+            let src_start = source.index(src_x as u32, (src_y + row) as u32);
+            let dest_start = self.index(dest_x as u32, (dest_y + row) as u32);
+            self.data[dest_start..dest_start + row_len]
+                .copy_from_slice(&source.data[src_start..src_start + row_len]);
+        }
+    }
+}
+
+#[inline]
+pub fn to_bytes(color: Rgba) -> [u8; 4] {
+    [
+        (color[0].clamp(0.0, 1.0) * 255.0).round() as u8,
+        (color[1].clamp(0.0, 1.0) * 255.0).round() as u8,
+        (color[2].clamp(0.0, 1.0) * 255.0).round() as u8,
+        (color[3].clamp(0.0, 1.0) * 255.0).round() as u8,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copy_from_clips_against_both_pixmaps() {
+        let mut source = Pixmap::new(4, 4);
+        source.fill(Color::RED);
+        let mut dest = Pixmap::new(4, 4);
+
+        dest.copy_from(&source, (0, 0), (-2, 3), (4, 4));
+
+        for y in 0..4 {
+            for x in 0..4 {
+                let expected = if y == 3 && x < 2 { 1.0 } else { 0.0 };
+                assert_eq!(dest.get(x, y)[3], expected, "pixel {x}, {y}");
+            }
+        }
+    }
+
+    #[test]
+    fn nearest_sampling_wraps_or_clamps() {
+        let mut pixmap = Pixmap::new(2, 1);
+        pixmap.set(1, 0, [1.0, 1.0, 1.0, 1.0]);
+
+        assert_eq!(pixmap.sample(2.5, 0.5, false, true)[3], 0.0);
+        assert_eq!(pixmap.sample(2.5, 0.5, false, false)[3], 1.0);
+    }
+}
@@ -0,0 +1,278 @@
+use ruffle_render::matrix::Matrix;
+use ruffle_render::quality::StageQuality;
+
+/// A point in pixels.
+pub type Point = [f32; 2];
+
+/// An affine transform in pixel units: `x' = a * x + c * y + tx`, `y' = b * x + d * y + ty`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Affine {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub tx: f32,
+    pub ty: f32,
+}
+
+impl Affine {
+    pub fn from_matrix(matrix: &Matrix) -> Self {
+        Self {
+            a: matrix.a,
+            b: matrix.b,
+            c: matrix.c,
+            d: matrix.d,
+            tx: matrix.tx.to_pixels() as f32,
+            ty: matrix.ty.to_pixels() as f32,
+        }
+    }
+
+    /// Reads a column-major 3x3 matrix, as produced by the tessellator for fills.
+    pub fn from_columns(matrix: [[f32; 3]; 3]) -> Self {
+        Self {
+            a: matrix[0][0],
+            b: matrix[0][1],
+            c: matrix[1][0],
+            d: matrix[1][1],
+            tx: matrix[2][0],
+            ty: matrix[2][1],
+        }
+    }
+
+    #[inline]
+    pub fn transform(&self, point: Point) -> Point {
+        [
+            self.a * point[0] + self.c * point[1] + self.tx,
+            self.b * point[0] + self.d * point[1] + self.ty,
+        ]
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.a * self.d - self.b * self.c;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let a = self.d / det;
+        let b = -self.b / det;
+        let c = -self.c / det;
+        let d = self.a / det;
+        Some(Self {
+            a,
+            b,
+            c,
+            d,
+            tx: -(a * self.tx + c * self.ty),
+            ty: -(b * self.tx + d * self.ty),
+        })
+    }
+
+    /// Returns the transform applying `first`, then `self`.
+    pub fn after(&self, first: &Affine) -> Self {
+        Self {
+            a: self.a * first.a + self.c * first.b,
+            b: self.b * first.a + self.d * first.b,
+            c: self.a * first.c + self.c * first.d,
+            d: self.b * first.c + self.d * first.d,
+            tx: self.a * first.tx + self.c * first.ty + self.tx,
+            ty: self.b * first.tx + self.d * first.ty + self.ty,
+        }
+    }
+}
+
+/// The number of samples along each axis of a pixel for the given quality.
+///
+/// Every pixel is sampled on a regular grid, so `High` gives 16 levels of coverage.
+/// Qualities above `High` are capped, as a coverage mask only holds 16 samples.
+pub fn samples_per_axis(quality: StageQuality) -> u32 {
+    quality.sample_count().clamp(1, 4)
+}
+
+/// Per-pixel anti-aliasing coverage of a set of triangles.
+///
+/// Each pixel keeps a mask of the samples covered by any triangle, so that
+/// triangles sharing an edge never cover the same sample twice, and never leave
+/// a seam between each other.
+pub struct Coverage {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    samples_per_axis: u32,
+    masks: Vec<u16>,
+}
+
+impl Coverage {
+    /// Creates an empty coverage for the area covered by `triangles`, clipped to `(0, 0, width, height)`.
+    ///
+    /// Returns `None` if nothing could be covered.
+    pub fn new(
+        triangles: &[[Point; 3]],
+        width: u32,
+        height: u32,
+        quality: StageQuality,
+    ) -> Option<Self> {
+        let mut x_min = f32::INFINITY;
+        let mut y_min = f32::INFINITY;
+        let mut x_max = f32::NEG_INFINITY;
+        let mut y_max = f32::NEG_INFINITY;
+        for point in triangles.iter().flatten() {
+            x_min = x_min.min(point[0]);
+            y_min = y_min.min(point[1]);
+            x_max = x_max.max(point[0]);
+            y_max = y_max.max(point[1]);
+        }
+        if !(x_min.is_finite() && y_min.is_finite() && x_max.is_finite() && y_max.is_finite()) {
+            return None;
+        }
+
+        let x0 = x_min.floor().clamp(0.0, width as f32) as u32;
+        let y0 = y_min.floor().clamp(0.0, height as f32) as u32;
+        let x1 = x_max.ceil().clamp(0.0, width as f32) as u32;
+        let y1 = y_max.ceil().clamp(0.0, height as f32) as u32;
+        if x1 <= x0 || y1 <= y0 {
+            return None;
+        }
+
+        let mut coverage = Self {
+            x: x0,
+            y: y0,
+            width: x1 - x0,
+            height: y1 - y0,
+            samples_per_axis: samples_per_axis(quality),
+            masks: vec![0; (x1 - x0) as usize * (y1 - y0) as usize],
+        };
+        for triangle in triangles {
+            coverage.add_triangle(*triangle);
+        }
+        Some(coverage)
+    }
+
+    fn add_triangle(&mut self, [p0, mut p1, mut p2]: [Point; 3]) {
+        let area = edge(p0, p1, p2);
+        if area == 0.0 || !area.is_finite() {
+            return;
+        }
+        if area < 0.0 {
+            std::mem::swap(&mut p1, &mut p2);
+        }
+
+        let x_min = p0[0].min(p1[0]).min(p2[0]).floor().max(self.x as f32) as u32;
+        let y_min = p0[1].min(p1[1]).min(p2[1]).floor().max(self.y as f32) as u32;
+        let x_max = (p0[0].max(p1[0]).max(p2[0]).ceil() as u32).min(self.x + self.width);
+        let y_max = (p0[1].max(p1[1]).max(p2[1]).ceil() as u32).min(self.y + self.height);
+
+        let edges = [(p0, p1), (p1, p2), (p2, p0)];
+        let n = self.samples_per_axis;
+        let step = 1.0 / n as f32;
+
+        for y in y_min..y_max {
+            for x in x_min..x_max {
+                let mut mask = 0u16;
+                for sy in 0..n {
+                    let py = y as f32 + (sy as f32 + 0.5) * step;
+                    for sx in 0..n {
+                        let px = x as f32 + (sx as f32 + 0.5) * step;
+                        if edges.iter().all(|(a, b)| inside(*a, *b, [px, py])) {
+                            mask |= 1 << (sy * n + sx);
+                        }
+                    }
+                }
+                if mask != 0 {
+                    let index = ((y - self.y) * self.width + (x - self.x)) as usize;
+                    self.masks[index] |= mask;
+                }
+            }
+        }
+    }
+
+    /// Iterates over every covered pixel, yielding its position and how much of it is covered.
+    pub fn pixels(&self) -> impl Iterator<Item = (u32, u32, f32)> + '_ {
+        let total = (self.samples_per_axis * self.samples_per_axis) as f32;
+        self.masks
+            .iter()
+            .enumerate()
+            .filter(|(_, mask)| **mask != 0)
+            .map(move |(index, mask)| {
+                let index = index as u32;
+                (
+                    self.x + index % self.width,
+                    self.y + index / self.width,
+                    mask.count_ones() as f32 / total,
+                )
+            })
+    }
+}
+
+#[inline]
+fn edge(a: Point, b: Point, p: Point) -> f32 {
+    (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0])
+}
+
+/// Whether `p` lies inside the edge going from `a` to `b`.
+///
+/// Samples exactly on an edge only belong to one of the two triangles sharing it,
+/// depending on the direction of the edge.
+#[inline]
+fn inside(a: Point, b: Point, p: Point) -> bool {
+    let value = edge(a, b, p);
+    if value != 0.0 {
+        return value > 0.0;
+    }
+    let dx = b[0] - a[0];
+    let dy = b[1] - a[1];
+    dy > 0.0 || (dy == 0.0 && dx < 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn total_coverage(triangles: &[[Point; 3]]) -> f32 {
+        Coverage::new(triangles, 10, 10, StageQuality::High)
+            .map(|coverage| coverage.pixels().map(|(_, _, c)| c).sum())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn shared_edges_are_covered_once() {
+        let square = [
+            [[1.0, 1.0], [5.0, 1.0], [5.0, 5.0]],
+            [[1.0, 1.0], [5.0, 5.0], [1.0, 5.0]],
+        ];
+        assert_eq!(total_coverage(&square), 16.0);
+
+        let coverage = Coverage::new(&square, 10, 10, StageQuality::High).unwrap();
+        assert!(coverage.pixels().all(|(_, _, c)| c == 1.0));
+    }
+
+    #[test]
+    fn partial_pixels_are_anti_aliased() {
+        let half = [
+            [[0.0, 0.0], [0.5, 0.0], [0.5, 1.0]],
+            [[0.0, 0.0], [0.5, 1.0], [0.0, 1.0]],
+        ];
+        assert_eq!(total_coverage(&half), 0.5);
+        assert_eq!(
+            Coverage::new(&half, 10, 10, StageQuality::Low)
+                .map(|coverage| coverage.pixels().count())
+                .unwrap_or_default(),
+            1
+        );
+    }
+
+    #[test]
+    fn inverse_round_trips() {
+        let transform = Affine {
+            a: 2.0,
+            b: 0.5,
+            c: -1.0,
+            d: 3.0,
+            tx: 10.0,
+            ty: -4.0,
+        };
+        let inverse = transform.inverse().unwrap();
+        let point = inverse.transform(transform.transform([3.0, 7.0]));
+        assert!((point[0] - 3.0).abs() < 1e-4);
+        assert!((point[1] - 7.0).abs() < 1e-4);
+    }
+}
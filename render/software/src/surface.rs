@@ -0,0 +1,474 @@
+use crate::bitmap::as_bitmap;
+use crate::blend::blend_pixel;
+use crate::color::{
+    ColorAdjustment, Rgba, TRANSPARENT, lerp, premultiply, scale, source_over, straight,
+};
+use crate::mesh::{Fill, as_mesh};
+use crate::pixmap::Pixmap;
+use crate::raster::{Affine, Coverage, Point};
+use ruffle_render::backend::ShapeHandle;
+use ruffle_render::bitmap::{BitmapHandle, PixelSnapping};
+use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
+use ruffle_render::lines::{emulate_line, emulate_line_rect};
+use ruffle_render::matrix::Matrix;
+use ruffle_render::quality::StageQuality;
+use ruffle_render::transform::Transform;
+use std::sync::atomic::{AtomicBool, Ordering};
+use swf::{BlendMode, Color};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum MaskState {
+    NoMask,
+    DrawMask,
+    DrawContent,
+    ClearMask,
+}
+
+/// Executes a `CommandList` by rasterizing every command into a `Pixmap`.
+///
+/// Masks are kept as a stack of coverage buffers, each one already intersected with its parent,
+/// which plays the role of the stencil buffer of the GPU backends.
+pub struct Surface<'a> {
+    target: &'a mut Pixmap,
+    quality: StageQuality,
+    masks: Vec<Vec<f32>>,
+    mask_state: MaskState,
+}
+
+impl<'a> Surface<'a> {
+    pub fn new(target: &'a mut Pixmap, quality: StageQuality) -> Self {
+        Self {
+            target,
+            quality,
+            masks: Vec::new(),
+            mask_state: MaskState::NoMask,
+        }
+    }
+
+    #[inline]
+    fn index(&self, x: u32, y: u32) -> usize {
+        y as usize * self.target.width() as usize + x as usize
+    }
+
+    /// How much of the content drawn at the given pixel is visible through the active masks.
+    #[inline]
+    fn clip(&self, x: u32, y: u32) -> f32 {
+        match self.masks.last() {
+            Some(mask) => mask[self.index(x, y)],
+            None => 1.0,
+        }
+    }
+
+    /// The clip of the mask enclosing the one currently being drawn.
+    #[inline]
+    fn parent_clip(&self, x: u32, y: u32) -> f32 {
+        match self.masks.len() {
+            0 | 1 => 1.0,
+            len => self.masks[len - 2][self.index(x, y)],
+        }
+    }
+
+    /// Fills the given triangles, using `shader` to compute the premultiplied color at a pixel center.
+    fn fill(&mut self, triangles: &[[Point; 3]], shader: impl Fn(Point) -> Rgba) {
+        if self.mask_state == MaskState::ClearMask {
+            return;
+        }
+        let Some(coverage) = Coverage::new(
+            triangles,
+            self.target.width(),
+            self.target.height(),
+            self.quality,
+        ) else {
+            return;
+        };
+
+        if self.mask_state == MaskState::DrawMask {
+            for (x, y, coverage) in coverage.pixels() {
+                let value = coverage * self.parent_clip(x, y);
+                let index = self.index(x, y);
+                let mask = self.masks.last_mut().expect("Mask must have been pushed");
+                mask[index] = mask[index].max(value);
+            }
+            return;
+        }
+
+        for (x, y, coverage) in coverage.pixels() {
+            let alpha = coverage * self.clip(x, y);
+            if alpha <= 0.0 {
+                continue;
+            }
+            let color = shader([x as f32 + 0.5, y as f32 + 0.5]);
+            let dst = self.target.get(x, y);
+            self.target.set(x, y, source_over(scale(color, alpha), dst));
+        }
+    }
+
+    /// Draws a parallelogram covering the unit square transformed by `matrix`.
+    fn fill_quad(
+        &mut self,
+        matrix: &Affine,
+        width: f32,
+        height: f32,
+        shader: impl Fn(Point) -> Rgba,
+    ) {
+        let corners = [
+            matrix.transform([0.0, 0.0]),
+            matrix.transform([width, 0.0]),
+            matrix.transform([width, height]),
+            matrix.transform([0.0, height]),
+        ];
+        self.fill(
+            &[
+                [corners[0], corners[1], corners[2]],
+                [corners[0], corners[2], corners[3]],
+            ],
+            shader,
+        );
+    }
+
+    /// Composites a layer rendered by a nested surface onto this surface.
+    fn composite(&mut self, layer: &Pixmap, blend_mode: BlendMode) {
+        match self.mask_state {
+            MaskState::ClearMask => {}
+            MaskState::DrawMask => {
+                for y in 0..layer.height() {
+                    for x in 0..layer.width() {
+                        if layer.get(x, y)[3] > 0.0 {
+                            let value = self.parent_clip(x, y);
+                            let index = self.index(x, y);
+                            let mask = self.masks.last_mut().expect("Mask must have been pushed");
+                            mask[index] = mask[index].max(value);
+                        }
+                    }
+                }
+            }
+            MaskState::NoMask | MaskState::DrawContent => {
+                for y in 0..layer.height() {
+                    for x in 0..layer.width() {
+                        let clip = self.clip(x, y);
+                        if clip <= 0.0 {
+                            continue;
+                        }
+                        let src = layer.get(x, y);
+                        let dst = self.target.get(x, y);
+                        let blended = blend_pixel(blend_mode, src, dst);
+                        self.target.set(x, y, lerp(dst, blended, clip));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Renders a command list into a new transparent layer the size of this surface.
+    fn render_layer(&self, commands: CommandList) -> Pixmap {
+        let mut layer = Pixmap::new(self.target.width(), self.target.height());
+        commands.execute(&mut Surface::new(&mut layer, self.quality));
+        layer
+    }
+
+    fn draw_bitmap(
+        &mut self,
+        bitmap: &BitmapHandle,
+        matrix: Matrix,
+        color_transform: &swf::ColorTransform,
+        smoothing: bool,
+    ) {
+        let matrix = Affine::from_matrix(&matrix);
+        let Some(inverse) = matrix.inverse() else {
+            return;
+        };
+        let pixmap = as_bitmap(bitmap).pixmap().clone();
+        let adjustment = ColorAdjustment::new(color_transform);
+        self.fill_quad(
+            &matrix,
+            pixmap.width() as f32,
+            pixmap.height() as f32,
+            |point| {
+                let [x, y] = inverse.transform(point);
+                adjustment.apply_premultiplied(pixmap.sample(x, y, smoothing, false))
+            },
+        );
+    }
+}
+
+impl CommandHandler for Surface<'_> {
+    fn render_bitmap(
+        &mut self,
+        bitmap: BitmapHandle,
+        transform: Transform,
+        smoothing: bool,
+        pixel_snapping: PixelSnapping,
+    ) {
+        let mut matrix = transform.matrix;
+        pixel_snapping.apply(&mut matrix);
+        self.draw_bitmap(&bitmap, matrix, &transform.color_transform, smoothing);
+    }
+
+    fn render_stage3d(&mut self, bitmap: BitmapHandle, transform: Transform) {
+        self.draw_bitmap(&bitmap, transform.matrix, &transform.color_transform, false);
+    }
+
+    fn render_shape(&mut self, shape: ShapeHandle, transform: Transform) {
+        let mesh = as_mesh(&shape);
+        let matrix = Affine::from_matrix(&transform.matrix);
+        let adjustment = ColorAdjustment::new(&transform.color_transform);
+        let to_shape = matrix.inverse();
+
+        for draw in &mesh.draws {
+            let triangles = if self.mask_state == MaskState::DrawMask {
+                &draw.triangles[..draw.num_mask_triangles.min(draw.triangles.len())]
+            } else {
+                &draw.triangles[..]
+            };
+
+            match &draw.fill {
+                Fill::Color => {
+                    // Every path has a single color, so draw each run of triangles at once.
+                    for run in triangles.chunk_by(|a, b| a.color == b.color) {
+                        let color = adjustment.apply_straight(straight(run[0].color));
+                        let points: Vec<_> = run
+                            .iter()
+                            .map(|triangle| triangle.points.map(|point| matrix.transform(point)))
+                            .collect();
+                        self.fill(&points, |_| color);
+                    }
+                }
+                Fill::Gradient {
+                    matrix: gradient_matrix,
+                    gradient,
+                } => {
+                    let Some(to_shape) = to_shape else {
+                        continue;
+                    };
+                    let gradient = &mesh.gradients[*gradient];
+                    let to_gradient = gradient_matrix.after(&to_shape);
+                    let points: Vec<_> = triangles
+                        .iter()
+                        .map(|triangle| triangle.points.map(|point| matrix.transform(point)))
+                        .collect();
+                    self.fill(&points, |point| {
+                        adjustment.apply_straight(gradient.color_at(to_gradient.transform(point)))
+                    });
+                }
+                Fill::Bitmap {
+                    matrix: bitmap_matrix,
+                    handle,
+                    is_smoothed,
+                    is_repeating,
+                } => {
+                    let Some(to_shape) = to_shape else {
+                        continue;
+                    };
+                    let pixmap = as_bitmap(handle).pixmap().clone();
+                    let to_bitmap = Affine {
+                        a: pixmap.width() as f32,
+                        b: 0.0,
+                        c: 0.0,
+                        d: pixmap.height() as f32,
+                        tx: 0.0,
+                        ty: 0.0,
+                    }
+                    .after(&bitmap_matrix.after(&to_shape));
+                    let smoothed = *is_smoothed && self.quality != StageQuality::Low;
+                    let points: Vec<_> = triangles
+                        .iter()
+                        .map(|triangle| triangle.points.map(|point| matrix.transform(point)))
+                        .collect();
+                    self.fill(&points, |point| {
+                        let [x, y] = to_bitmap.transform(point);
+                        adjustment.apply_premultiplied(pixmap.sample(x, y, smoothed, *is_repeating))
+                    });
+                }
+                Fill::TexturedTriangles {
+                    texture_coords,
+                    handle,
+                    is_smoothed,
+                    is_repeating,
+                } => {
+                    let pixmap = as_bitmap(handle).pixmap().clone();
+                    let (width, height) = (pixmap.width() as f32, pixmap.height() as f32);
+                    let smoothed = *is_smoothed && self.quality != StageQuality::Low;
+                    let points: Vec<_> = triangles
+                        .iter()
+                        .map(|triangle| triangle.points.map(|point| matrix.transform(point)))
+                        .collect();
+                    // Maps each triangle from the stage to the weights of its second and
+                    // third corners. Later triangles are drawn above earlier ones.
+                    let corners: Vec<_> = points
+                        .iter()
+                        .zip(texture_coords)
+                        .filter_map(|([p0, p1, p2], uvt)| {
+                            let to_corners = Affine {
+                                a: p1[0] - p0[0],
+                                b: p1[1] - p0[1],
+                                c: p2[0] - p0[0],
+                                d: p2[1] - p0[1],
+                                tx: p0[0],
+                                ty: p0[1],
+                            }
+                            .inverse()?;
+                            Some((to_corners, uvt))
+                        })
+                        .rev()
+                        .collect();
+                    self.fill(&points, |point| {
+                        // Antialiased edges can lie slightly outside of every triangle,
+                        // in which case the closest one is used.
+                        let mut closest = None;
+                        let mut closest_weight = f32::NEG_INFINITY;
+                        for (to_corners, uvt) in &corners {
+                            let [w1, w2] = to_corners.transform(point);
+                            let weights = [1.0 - w1 - w2, w1, w2];
+                            let weight = weights[0].min(w1).min(w2);
+                            if weight > closest_weight {
+                                closest = Some((weights, uvt));
+                                closest_weight = weight;
+                                if weight >= 0.0 {
+                                    break;
+                                }
+                            }
+                        }
+                        let Some((weights, uvt)) = closest else {
+                            return TRANSPARENT;
+                        };
+                        // The coordinates are linear on the stage only before dividing by `t`.
+                        let mut coords = [0.0; 3];
+                        for (corner, weight) in uvt.iter().zip(weights) {
+                            for (coord, value) in coords.iter_mut().zip(corner) {
+                                *coord += value * weight;
+                            }
+                        }
+                        let [x, y] = [
+                            coords[0] / coords[2] * width,
+                            coords[1] / coords[2] * height,
+                        ];
+                        adjustment.apply_premultiplied(pixmap.sample(x, y, smoothed, *is_repeating))
+                    });
+                }
+            }
+        }
+    }
+
+    fn render_alpha_mask(&mut self, maskee_commands: CommandList, mask_commands: CommandList) {
+        let mut maskee = self.render_layer(maskee_commands);
+        let mask = self.render_layer(mask_commands);
+        for y in 0..maskee.height() {
+            for x in 0..maskee.width() {
+                let color = scale(maskee.get(x, y), mask.get(x, y)[3]);
+                maskee.set(x, y, color);
+            }
+        }
+        self.composite(&maskee, BlendMode::Normal);
+    }
+
+    fn draw_rect(&mut self, color: Color, matrix: Matrix) {
+        let color = premultiply(color);
+        self.fill_quad(&Affine::from_matrix(&matrix), 1.0, 1.0, |_| color);
+    }
+
+    fn draw_line(&mut self, color: Color, matrix: Matrix) {
+        emulate_line(self, color, matrix);
+    }
+
+    fn draw_line_rect(&mut self, color: Color, matrix: Matrix) {
+        emulate_line_rect(self, color, matrix);
+    }
+
+    fn push_mask(&mut self) {
+        self.masks.push(vec![
+            0.0;
+            self.target.width() as usize
+                * self.target.height() as usize
+        ]);
+        self.mask_state = MaskState::DrawMask;
+    }
+
+    fn activate_mask(&mut self) {
+        self.mask_state = MaskState::DrawContent;
+    }
+
+    fn deactivate_mask(&mut self) {
+        self.mask_state = MaskState::ClearMask;
+    }
+
+    fn pop_mask(&mut self) {
+        self.masks.pop();
+        self.mask_state = if self.masks.is_empty() {
+            MaskState::NoMask
+        } else {
+            MaskState::DrawContent
+        };
+    }
+
+    fn blend(&mut self, commands: CommandList, blend_mode: RenderBlendMode) {
+        let blend_mode = match blend_mode {
+            RenderBlendMode::Builtin(blend_mode) => blend_mode,
+            RenderBlendMode::Shader(_) => {
+                static WARNED: AtomicBool = AtomicBool::new(false);
+                if !WARNED.swap(true, Ordering::Relaxed) {
+                    tracing::warn!("Shader blend modes are not supported by the software renderer");
+                }
+                BlendMode::Normal
+            }
+        };
+
+        // Normal compositing is associative, so those layers can be drawn directly.
+        let direct = match blend_mode {
+            BlendMode::Normal => true,
+            BlendMode::Layer => !commands.requires_layer_isolation(),
+            _ => false,
+        };
+        if direct {
+            commands.execute(self);
+        } else {
+            let layer = self.render_layer(commands);
+            self.composite(&layer, blend_mode);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::Twips;
+
+    fn rect(x: f32, y: f32, width: f32, height: f32) -> Matrix {
+        Matrix {
+            a: width,
+            d: height,
+            tx: Twips::from_pixels(x.into()),
+            ty: Twips::from_pixels(y.into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn masks_clip_content() {
+        let mut pixmap = Pixmap::new(4, 1);
+        let mut surface = Surface::new(&mut pixmap, StageQuality::High);
+        surface.push_mask();
+        surface.draw_rect(Color::WHITE, rect(1.0, 0.0, 2.0, 1.0));
+        surface.activate_mask();
+        surface.draw_rect(Color::RED, rect(0.0, 0.0, 4.0, 1.0));
+        surface.deactivate_mask();
+        surface.draw_rect(Color::WHITE, rect(1.0, 0.0, 2.0, 1.0));
+        surface.pop_mask();
+
+        let alpha: Vec<_> = (0..4).map(|x| pixmap.get(x, 0)[3]).collect();
+        assert_eq!(alpha, [0.0, 1.0, 1.0, 0.0]);
+        assert_eq!(pixmap.get(1, 0), [1.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn layers_blend_with_their_parent() {
+        let mut pixmap = Pixmap::new(1, 1);
+        let mut surface = Surface::new(&mut pixmap, StageQuality::High);
+        surface.draw_rect(Color::from_rgba(0xFF808080), rect(0.0, 0.0, 1.0, 1.0));
+
+        let mut commands = CommandList::new();
+        commands.draw_rect(Color::from_rgba(0xFFFFFFFF), rect(0.0, 0.0, 1.0, 1.0));
+        surface.blend(commands, RenderBlendMode::Builtin(BlendMode::Difference));
+
+        assert_eq!(pixmap.data(), [127, 127, 127, 255]);
+    }
+}
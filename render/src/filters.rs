@@ -78,6 +78,9 @@ impl Filter {
             Filter::DropShadowFilter(filter) => filter.calculate_dest_rect(source_rect),
             Filter::BevelFilter(filter) => filter.calculate_dest_rect(source_rect),
             Filter::DisplacementMapFilter(filter) => filter.calculate_dest_rect(source_rect),
+            Filter::GradientGlowFilter(filter) | Filter::GradientBevelFilter(filter) => {
+                filter.calculate_dest_rect(source_rect)
+            }
             _ => source_rect,
         }
     }
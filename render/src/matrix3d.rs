@@ -40,6 +40,80 @@ impl Matrix3D {
         }
     }
 
+    /// Composes a transformation that scales, then rotates around the x, y
+    /// and z axes (in radians, in that order), then translates.
+    pub fn from_components(translation: [f64; 3], rotation: [f64; 3], scale: [f64; 3]) -> Self {
+        let [cx, cy, cz] = rotation.map(f64::cos);
+        let [sx, sy, sz] = rotation.map(f64::sin);
+        let [scale_x, scale_y, scale_z] = scale;
+        let [tx, ty, tz] = translation;
+
+        #[rustfmt::skip]
+        let raw_data = [
+            cy * cz * scale_x,
+            cy * sz * scale_x,
+            -sy * scale_x,
+            0.0,
+            (sx * sy * cz - cx * sz) * scale_y,
+            (sx * sy * sz + cx * cz) * scale_y,
+            sx * cy * scale_y,
+            0.0,
+            (cx * sy * cz + sx * sz) * scale_z,
+            (cx * sy * sz - sx * cz) * scale_z,
+            cx * cy * scale_z,
+            0.0,
+            tx, ty, tz, 1.0,
+        ].map(|f| f as f32);
+
+        Self { raw_data }
+    }
+
+    /// Splits this matrix into the translation, rotation and scale accepted
+    /// by [`Self::from_components`]. Skew and projection are discarded.
+    pub fn to_components(&self) -> ([f64; 3], [f64; 3], [f64; 3]) {
+        let mut m = self.raw_data.map(f64::from);
+
+        let translation = [m[12], m[13], m[14]];
+
+        let scale_x = (m[0] * m[0] + m[1] * m[1] + m[2] * m[2]).sqrt();
+        let scale_y = (m[4] * m[4] + m[5] * m[5] + m[6] * m[6]).sqrt();
+        let mut scale_z = (m[8] * m[8] + m[9] * m[9] + m[10] * m[10]).sqrt();
+        if m[0] * (m[5] * m[10] - m[6] * m[9]) - m[1] * (m[4] * m[10] - m[6] * m[8])
+            + m[2] * (m[4] * m[9] - m[5] * m[8])
+            < 0.0
+        {
+            scale_z = -scale_z;
+        }
+
+        for (column, scale) in [scale_x, scale_y, scale_z].into_iter().enumerate() {
+            if scale != 0.0 {
+                for value in &mut m[column * 4..column * 4 + 3] {
+                    *value /= scale;
+                }
+            }
+        }
+
+        let rotation_y = (-m[2]).clamp(-1.0, 1.0).asin();
+        let (rotation_x, rotation_z) = if m[2] != 1.0 && m[2] != -1.0 {
+            (m[6].atan2(m[10]), m[1].atan2(m[0]))
+        } else {
+            (m[4].atan2(m[5]), 0.0)
+        };
+
+        (
+            translation,
+            [rotation_x, rotation_y, rotation_z],
+            [scale_x, scale_y, scale_z],
+        )
+    }
+
+    /// Transforms a point, returning its homogeneous coordinates.
+    pub fn transform_point(&self, point: [f32; 3]) -> [f32; 4] {
+        let m = &self.raw_data;
+        let [x, y, z] = point;
+        std::array::from_fn(|row| m[row] * x + m[4 + row] * y + m[8 + row] * z + m[12 + row])
+    }
+
     pub fn from_matrix(matrix: Matrix) -> Self {
         Self {
             raw_data: [
@@ -63,4 +63,43 @@ impl PerspectiveProjection {
             ],
         }
     }
+
+    /// The matrix projecting 3D content onto the `z = 0` plane, as seen from
+    /// a viewer standing at the focal length in front of `center`.
+    ///
+    /// Unlike [`Self::to_matrix3d`], this is expressed in the coordinate space
+    /// of the object owning the projection, and leaves `z = 0` untouched.
+    pub fn projection_matrix3d(&self, width: f32) -> Matrix3D {
+        let focal_length = self.focal_length(width);
+        let (center_x, center_y) = (self.center.0 as f32, self.center.1 as f32);
+
+        let projection = Matrix3D {
+            raw_data: [
+                //
+                focal_length,
+                0.0,
+                0.0,
+                0.0,
+                //
+                0.0,
+                focal_length,
+                0.0,
+                0.0,
+                //
+                0.0,
+                0.0,
+                1.0,
+                1.0,
+                //
+                0.0,
+                0.0,
+                0.0,
+                focal_length,
+            ],
+        };
+
+        Matrix3D::translate(center_x, center_y, 0.0)
+            .multiply(&projection)
+            .multiply(&Matrix3D::translate(-center_x, -center_y, 0.0))
+    }
 }
@@ -126,6 +126,76 @@ pub enum DrawPath<'a> {
         commands: Vec<DrawCommand>,
         winding_rule: FillRule,
     },
+    /// Triangles with a bitmap mapped onto each of them, as drawn by
+    /// `Graphics.drawTriangles` with UVT data.
+    TexturedTriangles {
+        bitmap_id: CharacterId,
+        is_smoothed: bool,
+        is_repeating: bool,
+        triangles: Vec<TexturedTriangle>,
+    },
+}
+
+/// A triangle with a bitmap mapped onto it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TexturedTriangle {
+    pub points: [swf::Point<Twips>; 3],
+    /// The normalized bitmap coordinates (`u`, `v`) and the perspective
+    /// weight (`t`) of each point.
+    pub uvt: [[f32; 3]; 3],
+}
+
+impl TexturedTriangle {
+    /// Returns the homogeneous normalized bitmap coordinates of each point,
+    /// as `u * t`, `v * t` and `t`.
+    ///
+    /// Unlike `u` and `v`, these vary linearly over the triangle, so dividing
+    /// the interpolated `x` and `y` by `z` gives the perspective-correct
+    /// bitmap coordinates of any point of the triangle.
+    pub fn texture_coords(&self) -> [[f32; 3]; 3] {
+        self.uvt.map(|[u, v, t]| [u * t, v * t, t])
+    }
+
+    /// Returns the bitmap fill matrix mapping a bitmap of the given size onto
+    /// this triangle, ignoring perspective.
+    ///
+    /// Returns `None` if the triangle or its bitmap coordinates are degenerate.
+    pub fn bitmap_matrix(&self, bitmap_width: u32, bitmap_height: u32) -> Option<swf::Matrix> {
+        let [p0, p1, p2] = self
+            .points
+            .map(|point| [f64::from(point.x.get()), f64::from(point.y.get())]);
+        let [uv0, uv1, uv2] = self.uvt.map(|[u, v, _]| {
+            [
+                f64::from(u) * f64::from(bitmap_width),
+                f64::from(v) * f64::from(bitmap_height),
+            ]
+        });
+
+        // Solve for the linear part mapping the bitmap edges onto the triangle edges.
+        let (du1, dv1) = (uv1[0] - uv0[0], uv1[1] - uv0[1]);
+        let (du2, dv2) = (uv2[0] - uv0[0], uv2[1] - uv0[1]);
+        let det = du1 * dv2 - du2 * dv1;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let (dx1, dy1) = (p1[0] - p0[0], p1[1] - p0[1]);
+        let (dx2, dy2) = (p2[0] - p0[0], p2[1] - p0[1]);
+        let a = (dx1 * dv2 - dx2 * dv1) / det;
+        let b = (dy1 * dv2 - dy2 * dv1) / det;
+        let c = (dx2 * du1 - dx1 * du2) / det;
+        let d = (dy2 * du1 - dy1 * du2) / det;
+        let tx = p0[0] - a * uv0[0] - c * uv0[1];
+        let ty = p0[1] - b * uv0[0] - d * uv0[1];
+
+        Some(swf::Matrix {
+            a: swf::Fixed16::from_f64(a),
+            b: swf::Fixed16::from_f64(b),
+            c: swf::Fixed16::from_f64(c),
+            d: swf::Fixed16::from_f64(d),
+            tx: Twips::new(tx.round() as i32),
+            ty: Twips::new(ty.round() as i32),
+        })
+    }
 }
 
 /// `DistilledShape` represents a ready-to-be-consumed collection of paths (both fills and strokes)
@@ -1283,6 +1353,162 @@ impl<'a> LineScales<'a> {
     }
 }
 
+/// Distorts shapes to implement 9-slice scaling (`scale9Grid`).
+///
+/// The shapes are remapped in the local space of the object owning the grid,
+/// so that once the scale of that object is applied, the corners of the grid
+/// keep their size, the edges are only stretched along their length, and the
+/// center absorbs the rest of the scaling.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScalingGrid {
+    x: ScalingGridAxis,
+    y: ScalingGridAxis,
+}
+
+impl ScalingGrid {
+    /// Creates the grid of an object with the given `bounds`, scaled by
+    /// `scale_x` and `scale_y`.
+    ///
+    /// Returns `None` if the grid has no effect, such as when the object isn't
+    /// scaled or the grid is empty.
+    pub fn new(
+        grid: Rectangle<Twips>,
+        bounds: Rectangle<Twips>,
+        scale_x: f64,
+        scale_y: f64,
+    ) -> Option<Self> {
+        if !grid.is_valid() || !bounds.is_valid() {
+            return None;
+        }
+        if scale_x == 1.0 && scale_y == 1.0 {
+            return None;
+        }
+
+        Some(Self {
+            x: ScalingGridAxis::new(
+                (bounds.x_min, bounds.x_max),
+                (grid.x_min, grid.x_max),
+                scale_x,
+            )?,
+            y: ScalingGridAxis::new(
+                (bounds.y_min, bounds.y_max),
+                (grid.y_min, grid.y_max),
+                scale_y,
+            )?,
+        })
+    }
+
+    /// Maps a point from the local space of the object owning the grid.
+    pub fn map_point(&self, point: swf::Point<Twips>) -> swf::Point<Twips> {
+        swf::Point::new(
+            Twips::new(self.x.map(point.x.get().into()).round() as i32),
+            Twips::new(self.y.map(point.y.get().into()).round() as i32),
+        )
+    }
+
+    /// Distorts the paths of a shape.
+    ///
+    /// `matrix` transforms the shape into the local space of the object owning
+    /// the grid, as shapes of child objects are affected by the grid too.
+    pub fn apply(&self, paths: &mut [DrawPath], matrix: Matrix) {
+        let Some(inverse) = matrix.inverse() else {
+            return;
+        };
+        let map =
+            |point: &mut swf::Point<Twips>| *point = inverse * self.map_point(matrix * *point);
+
+        for path in paths {
+            let commands = match path {
+                DrawPath::Fill { commands, .. } | DrawPath::Stroke { commands, .. } => commands,
+                DrawPath::TexturedTriangles { triangles, .. } => {
+                    triangles
+                        .iter_mut()
+                        .flat_map(|triangle| &mut triangle.points)
+                        .for_each(map);
+                    continue;
+                }
+            };
+            for command in commands {
+                match command {
+                    DrawCommand::MoveTo(point) | DrawCommand::LineTo(point) => map(point),
+                    DrawCommand::QuadraticCurveTo { control, anchor } => {
+                        map(control);
+                        map(anchor);
+                    }
+                    DrawCommand::CubicCurveTo {
+                        control_a,
+                        control_b,
+                        anchor,
+                    } => {
+                        map(control_a);
+                        map(control_b);
+                        map(anchor);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ScalingGridAxis {
+    bounds: (f64, f64),
+    grid: (f64, f64),
+    scale: f64,
+}
+
+impl ScalingGridAxis {
+    fn new(bounds: (Twips, Twips), grid: (Twips, Twips), scale: f64) -> Option<Self> {
+        if !scale.is_finite() || scale == 0.0 {
+            return None;
+        }
+
+        let bounds = (f64::from(bounds.0.get()), f64::from(bounds.1.get()));
+        let grid = (
+            f64::from(grid.0.get()).clamp(bounds.0, bounds.1),
+            f64::from(grid.1.get()).clamp(bounds.0, bounds.1),
+        );
+        if grid.1 <= grid.0 {
+            return None;
+        }
+
+        Some(Self {
+            bounds,
+            grid,
+            scale: scale.abs(),
+        })
+    }
+
+    fn map(&self, value: f64) -> f64 {
+        let (bounds_min, bounds_max) = self.bounds;
+        let (grid_min, grid_max) = self.grid;
+
+        // The size of the margins, once compensated for the scale of the object.
+        let start = (grid_min - bounds_min) / self.scale;
+        let end = (bounds_max - grid_max) / self.scale;
+        let center = (bounds_max - bounds_min) - start - end;
+
+        if center < 0.0 {
+            // The object is too small to fit the margins, which get shrunk
+            // proportionally while the center disappears.
+            let factor = (bounds_max - bounds_min) / (start + end);
+            if value <= grid_min {
+                bounds_min + (value - bounds_min) / self.scale * factor
+            } else if value >= grid_max {
+                bounds_max - (bounds_max - value) / self.scale * factor
+            } else {
+                bounds_min + start * factor
+            }
+        } else if value < grid_min {
+            bounds_min + (value - bounds_min) / self.scale
+        } else if value > grid_max {
+            bounds_max - (bounds_max - value) / self.scale
+        } else {
+            bounds_min + start + (value - grid_min) * center / (grid_max - grid_min)
+        }
+    }
+}
+
 pub fn quadratic_curve_bounds(
     start: swf::Point<Twips>,
     stroke_width: Twips,
@@ -1473,6 +1699,50 @@ mod tests {
         assert_eq!(commands, expected);
     }
 
+    #[test]
+    fn scaling_grid_keeps_corners() {
+        let grid = ScalingGrid::new(
+            Rectangle {
+                x_min: Twips::from_pixels(10.0),
+                y_min: Twips::from_pixels(10.0),
+                x_max: Twips::from_pixels(90.0),
+                y_max: Twips::from_pixels(90.0),
+            },
+            Rectangle {
+                x_min: Twips::ZERO,
+                y_min: Twips::ZERO,
+                x_max: Twips::from_pixels(100.0),
+                y_max: Twips::from_pixels(100.0),
+            },
+            2.0,
+            0.5,
+        )
+        .unwrap();
+
+        // Scaled by 2, the corners of 10 pixels take 5 local pixels.
+        assert_eq!(
+            grid.map_point(swf::Point::from_pixels(10.0, 100.0)),
+            swf::Point::from_pixels(5.0, 100.0)
+        );
+        assert_eq!(
+            grid.map_point(swf::Point::from_pixels(90.0, 0.0)),
+            swf::Point::from_pixels(95.0, 0.0)
+        );
+        assert_eq!(
+            grid.map_point(swf::Point::from_pixels(50.0, 50.0)),
+            swf::Point::from_pixels(50.0, 50.0)
+        );
+        // Scaled by 0.5, the corners of 10 pixels take 20 local pixels.
+        assert_eq!(
+            grid.map_point(swf::Point::from_pixels(0.0, 10.0)),
+            swf::Point::from_pixels(0.0, 20.0)
+        );
+        assert_eq!(
+            grid.map_point(swf::Point::from_pixels(0.0, 90.0)),
+            swf::Point::from_pixels(0.0, 80.0)
+        );
+    }
+
     #[test]
     fn test_winding_number_line() {
         fn test(
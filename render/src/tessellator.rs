@@ -1,5 +1,5 @@
 use crate::bitmap::BitmapSource;
-use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath, GradientType};
+use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath, GradientType, TexturedTriangle};
 use indexmap::IndexSet;
 use lyon::path::Path;
 use lyon::tessellation::{
@@ -71,6 +71,21 @@ impl ShapeTessellator {
                     ruffle_path_to_lyon_path(commands, *is_closed),
                     true,
                 ),
+                DrawPath::TexturedTriangles {
+                    bitmap_id,
+                    is_smoothed,
+                    is_repeating,
+                    triangles,
+                } => {
+                    self.tessellate_textured_triangles(
+                        *bitmap_id,
+                        *is_smoothed,
+                        *is_repeating,
+                        triangles,
+                        bitmap_source,
+                    );
+                    continue;
+                }
             };
 
             let (draw, color, needs_flush) = match fill_style {
@@ -222,6 +237,9 @@ impl ShapeTessellator {
                         &mut buffers_builder,
                     )
                 }
+                DrawPath::TexturedTriangles { .. } => {
+                    unreachable!("Textured triangles are not tessellated")
+                }
             };
             match result {
                 Ok(_) => {
@@ -247,6 +265,47 @@ impl ShapeTessellator {
         }
     }
 
+    fn tessellate_textured_triangles(
+        &mut self,
+        bitmap_id: swf::CharacterId,
+        is_smoothed: bool,
+        is_repeating: bool,
+        triangles: &[TexturedTriangle],
+        bitmap_source: &dyn BitmapSource,
+    ) {
+        if bitmap_source.bitmap_size(bitmap_id).is_none() {
+            // Missing bitmap -- the bitmap data may have been disposed.
+            return;
+        }
+
+        // Textured triangles are isolated draw calls; flush any pending fills.
+        self.flush_draw(DrawType::Color);
+        self.is_stroke = false;
+
+        // Each vertex carries its own texture coordinates, so all triangles
+        // are drawn at once regardless of how the bitmap is mapped on them.
+        let mut texture_coords = Vec::with_capacity(triangles.len() * 3);
+        for triangle in triangles {
+            let start = self.lyon_mesh.vertices.len() as u32;
+            self.lyon_mesh
+                .vertices
+                .extend(triangle.points.map(|point| Vertex {
+                    x: point.x.to_pixels() as f32,
+                    y: point.y.to_pixels() as f32,
+                    color: swf::Color::WHITE,
+                }));
+            self.lyon_mesh.indices.extend([start, start + 1, start + 2]);
+            texture_coords.extend(triangle.texture_coords());
+        }
+
+        self.flush_draw(DrawType::TexturedTriangles(TexturedTriangles {
+            bitmap_id,
+            is_smoothed,
+            is_repeating,
+            texture_coords,
+        }));
+    }
+
     fn flush_draw(&mut self, draw: DrawType) {
         if self.lyon_mesh.vertices.is_empty() || self.lyon_mesh.indices.len() < 3 {
             // Ignore degenerate fills
@@ -292,6 +351,7 @@ pub enum DrawType {
         gradient: usize,
     },
     Bitmap(Bitmap),
+    TexturedTriangles(TexturedTriangles),
 }
 
 impl DrawType {
@@ -300,6 +360,7 @@ impl DrawType {
             Self::Color => "Color",
             Self::Gradient { .. } => "Gradient",
             Self::Bitmap { .. } => "Bitmap",
+            Self::TexturedTriangles { .. } => "TexturedTriangles",
         }
     }
 }
@@ -328,6 +389,19 @@ pub struct Bitmap {
     pub is_repeating: bool,
 }
 
+/// A bitmap mapped onto triangles by the texture coordinates of their
+/// vertices, as drawn by `Graphics.drawTriangles`.
+#[derive(Clone, Debug)]
+pub struct TexturedTriangles {
+    pub bitmap_id: u16,
+    pub is_smoothed: bool,
+    pub is_repeating: bool,
+
+    /// The homogeneous normalized bitmap coordinates of each vertex of the
+    /// draw (see `TexturedTriangle::texture_coords`).
+    pub texture_coords: Vec<[f32; 3]>,
+}
+
 #[expect(clippy::many_single_char_names)]
 fn swf_to_gl_matrix(m: crate::matrix::Matrix) -> [[f32; 3]; 3] {
     let tx = m.tx.get() as f32;
@@ -471,3 +545,86 @@ impl StrokeVertexConstructor<Vertex> for RuffleVertexCtor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::RenderBackend;
+    use crate::bitmap::{BitmapHandle, BitmapSize};
+    use swf::{Point, Rectangle};
+
+    struct TestBitmapSource;
+
+    impl BitmapSource for TestBitmapSource {
+        fn bitmap_size(&self, _id: u16) -> Option<BitmapSize> {
+            Some(BitmapSize {
+                width: 2,
+                height: 2,
+            })
+        }
+
+        fn bitmap_handle(
+            &self,
+            _id: u16,
+            _renderer: &mut dyn RenderBackend,
+        ) -> Option<BitmapHandle> {
+            None
+        }
+    }
+
+    #[test]
+    fn textured_triangles_are_a_single_draw() {
+        // The two triangles map the bitmap differently, and the first one
+        // with a perspective.
+        let triangles = vec![
+            TexturedTriangle {
+                points: [
+                    Point::from_pixels(0.0, 0.0),
+                    Point::from_pixels(100.0, 0.0),
+                    Point::from_pixels(0.0, 50.0),
+                ],
+                uvt: [[0.0, 0.0, 2.0], [1.0, 0.0, 1.0], [0.0, 1.0, 2.0]],
+            },
+            TexturedTriangle {
+                points: [
+                    Point::from_pixels(100.0, 0.0),
+                    Point::from_pixels(100.0, 50.0),
+                    Point::from_pixels(0.0, 50.0),
+                ],
+                uvt: [[1.0, 0.0, 1.0], [1.0, 1.0, 1.0], [0.0, 1.0, 1.0]],
+            },
+        ];
+        let shape = DistilledShape {
+            paths: vec![DrawPath::TexturedTriangles {
+                bitmap_id: 1,
+                is_smoothed: true,
+                is_repeating: false,
+                triangles,
+            }],
+            shape_bounds: Rectangle::default(),
+            edge_bounds: Rectangle::default(),
+            id: 0,
+        };
+
+        let mesh = ShapeTessellator::new().tessellate_shape(shape, &TestBitmapSource);
+
+        assert_eq!(mesh.draws.len(), 1);
+        let draw = &mesh.draws[0];
+        assert_eq!(draw.indices, [0, 1, 2, 3, 4, 5]);
+        let DrawType::TexturedTriangles(textured) = &draw.draw_type else {
+            panic!("expected textured triangles, got {}", draw.draw_type.name());
+        };
+        assert_eq!(textured.bitmap_id, 1);
+        assert_eq!(
+            textured.texture_coords,
+            [
+                [0.0, 0.0, 2.0],
+                [1.0, 0.0, 1.0],
+                [0.0, 2.0, 2.0],
+                [1.0, 0.0, 1.0],
+                [1.0, 1.0, 1.0],
+                [0.0, 1.0, 1.0],
+            ]
+        );
+    }
+}
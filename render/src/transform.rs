@@ -1,4 +1,5 @@
 use crate::matrix::Matrix;
+use crate::matrix3d::Matrix3D;
 use crate::perspective_projection::PerspectiveProjection;
 use swf::ColorTransform;
 
@@ -7,36 +8,103 @@ use swf::ColorTransform;
 #[derive(Clone, Debug, Default)]
 pub struct Transform {
     pub matrix: Matrix,
+
+    /// The transformation matrix of objects transformed in 3D, in pixels.
+    ///
+    /// When present, this takes precedence over `matrix`, which is kept as
+    /// a 2D approximation for backends unable to render perspective.
+    pub matrix3d: Option<Matrix3D>,
+
     pub color_transform: ColorTransform,
     pub perspective_projection: Option<PerspectiveProjection>,
 }
 
-pub struct TransformStack(Vec<Transform>);
+struct TransformStackEntry {
+    transform: Transform,
+
+    /// The perspective projection applied to descendants entering 3D,
+    /// expressed in world space.
+    projection: Option<Matrix3D>,
+}
+
+pub struct TransformStack {
+    entries: Vec<TransformStackEntry>,
+
+    /// The width used to turn fields of view into focal lengths.
+    projection_width: f32,
+}
 
 impl TransformStack {
     pub fn new() -> Self {
-        Self(vec![Transform::default()])
+        Self {
+            entries: vec![TransformStackEntry {
+                transform: Transform::default(),
+                projection: None,
+            }],
+            projection_width: 500.0,
+        }
+    }
+
+    /// Sets the width used to turn fields of view into focal lengths, which
+    /// Flash takes from the stage.
+    pub fn set_projection_width(&mut self, width: f32) {
+        self.projection_width = width;
     }
 
     pub fn push(&mut self, transform: &Transform) {
-        let cur_transform = self.transform();
-        let matrix = cur_transform.matrix * transform.matrix;
-        let color_transform = cur_transform.color_transform * transform.color_transform;
-        self.0.push(Transform {
-            matrix,
-            color_transform,
-            // TODO: Merge perspective_projections from cur_transform and transform properly
-            perspective_projection: Default::default(),
+        let cur = self.entries.last().expect("Transform stack is never empty");
+        let matrix = cur.transform.matrix * transform.matrix;
+        let color_transform = cur.transform.color_transform * transform.color_transform;
+
+        let matrix3d = match (cur.transform.matrix3d, transform.matrix3d) {
+            (None, None) => None,
+            (Some(parent), local) => {
+                let local = local.unwrap_or_else(|| Matrix3D::from_matrix(transform.matrix));
+                Some(parent.multiply(&local))
+            }
+            (None, Some(local)) => {
+                // This object is the first one transformed in 3D, so this is
+                // where the content gets projected.
+                let world = Matrix3D::from_matrix(cur.transform.matrix).multiply(&local);
+                Some(match cur.projection {
+                    Some(projection) => projection.multiply(&world),
+                    None => world,
+                })
+            }
+        };
+
+        let projection = match transform.perspective_projection {
+            Some(perspective_projection) => {
+                let world = matrix3d.unwrap_or_else(|| Matrix3D::from_matrix(matrix));
+                world.invert().map(|inverse| {
+                    world
+                        .multiply(
+                            &perspective_projection.projection_matrix3d(self.projection_width),
+                        )
+                        .multiply(&inverse)
+                })
+            }
+            None => cur.projection,
+        };
+
+        self.entries.push(TransformStackEntry {
+            transform: Transform {
+                matrix,
+                matrix3d,
+                color_transform,
+                perspective_projection: Default::default(),
+            },
+            projection,
         });
     }
 
     pub fn pop(&mut self) {
-        assert!(self.0.len() > 1, "Transform stack underflow");
-        self.0.pop();
+        assert!(self.entries.len() > 1, "Transform stack underflow");
+        self.entries.pop();
     }
 
     pub fn transform(&self) -> Transform {
-        self.0[self.0.len() - 1].clone()
+        self.entries[self.entries.len() - 1].transform.clone()
     }
 }
 
@@ -45,3 +113,85 @@ impl Default for TransformStack {
         TransformStack::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(transform: &Transform, point: [f32; 3]) -> (f32, f32) {
+        let [x, y, _, w] = transform.matrix3d.unwrap().transform_point(point);
+        (x / w, y / w)
+    }
+
+    fn translated(x: f32, y: f32, z: f32) -> Transform {
+        let matrix = Matrix::translate(
+            swf::Twips::from_pixels(x.into()),
+            swf::Twips::from_pixels(y.into()),
+        );
+        Transform {
+            matrix,
+            matrix3d: Some(Matrix3D::translate(x, y, z)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn flat_transforms_stay_2d() {
+        let mut stack = TransformStack::new();
+        stack.push(&Transform {
+            perspective_projection: Some(PerspectiveProjection::default()),
+            ..Default::default()
+        });
+        stack.push(&Transform {
+            matrix: Matrix::scale(2.0, 2.0),
+            ..Default::default()
+        });
+
+        assert!(stack.transform().matrix3d.is_none());
+        assert_eq!(stack.transform().matrix, Matrix::scale(2.0, 2.0));
+    }
+
+    #[test]
+    fn depth_is_projected_towards_center() {
+        let mut stack = TransformStack::new();
+        stack.push(&Transform {
+            perspective_projection: Some(PerspectiveProjection {
+                field_of_view: 90.0,
+                center: (100.0, 100.0),
+            }),
+            ..Default::default()
+        });
+        // With a field of view of 90 degrees, the focal length is half the
+        // projection width.
+        let focal_length = 250.0;
+
+        stack.push(&translated(0.0, 0.0, 0.0));
+        let (x, y) = project(&stack.transform(), [300.0, 100.0, 0.0]);
+        assert!((x - 300.0).abs() < 0.01 && (y - 100.0).abs() < 0.01);
+        stack.pop();
+
+        stack.push(&translated(0.0, 0.0, focal_length));
+        let (x, y) = project(&stack.transform(), [300.0, 100.0, 0.0]);
+        assert!((x - 200.0).abs() < 0.01 && (y - 100.0).abs() < 0.01);
+
+        // Nested 3D objects are composed before being projected once.
+        stack.push(&translated(0.0, 0.0, -focal_length));
+        let (x, y) = project(&stack.transform(), [300.0, 100.0, 0.0]);
+        assert!((x - 300.0).abs() < 0.01 && (y - 100.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn components_round_trip() {
+        let translation = [10.0, -20.0, 30.0];
+        let rotation = [0.5, -0.25, 1.0];
+        let scale = [2.0, 0.5, 1.5];
+        let matrix = Matrix3D::from_components(translation, rotation, scale);
+        let (t, r, s) = matrix.to_components();
+
+        for (expected, actual) in [translation, rotation, scale].iter().zip([t, r, s]) {
+            for (expected, actual) in expected.iter().zip(actual) {
+                assert!((expected - actual).abs() < 0.0001, "{expected} != {actual}");
+            }
+        }
+    }
+}
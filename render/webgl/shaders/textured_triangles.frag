@@ -0,0 +1,31 @@
+#version 100
+
+#ifdef GL_FRAGMENT_PRECISION_HIGH
+    precision highp float;
+#else
+    precision mediump float;
+#endif
+
+uniform mat4 view_matrix;
+uniform mat4 world_matrix;
+uniform vec4 mult_color;
+uniform vec4 add_color;
+
+uniform sampler2D u_texture;
+
+varying vec3 frag_uvt;
+
+void main() {
+    // Dividing after interpolation gives perspective-correct coordinates.
+    vec4 color = texture2D(u_texture, frag_uvt.xy / frag_uvt.z);
+
+    // Unmultiply alpha before apply color transform.
+    if (color.a > 0.0) {
+        color.rgb /= color.a;
+        color = clamp(mult_color * color + add_color, 0.0, 1.0);
+        float alpha = clamp(color.a, 0.0, 1.0);
+        color = vec4(color.rgb * alpha, alpha);
+    }
+
+    gl_FragColor = color;
+}
@@ -81,7 +81,9 @@ const COLOR_FRAGMENT_GLSL: &str = include_str!("../shaders/color.frag");
 const TEXTURE_VERTEX_GLSL: &str = include_str!("../shaders/texture.vert");
 const GRADIENT_FRAGMENT_GLSL: &str = include_str!("../shaders/gradient.frag");
 const BITMAP_FRAGMENT_GLSL: &str = include_str!("../shaders/bitmap.frag");
-const NUM_VERTEX_ATTRIBUTES: u32 = 2;
+const TEXTURED_TRIANGLES_VERTEX_GLSL: &str = include_str!("../shaders/textured_triangles.vert");
+const TEXTURED_TRIANGLES_FRAGMENT_GLSL: &str = include_str!("../shaders/textured_triangles.frag");
+const NUM_VERTEX_ATTRIBUTES: u32 = 3;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum MaskState {
@@ -112,6 +114,14 @@ impl From<TessVertex> for Vertex {
     }
 }
 
+/// A vertex of textured triangles, carrying its own homogeneous texture coordinates.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct UvtVertex {
+    position: [f32; 2],
+    uvt: [f32; 3],
+}
+
 pub struct WebGlRenderBackend {
     /// WebGL1 context
     gl: Gl,
@@ -128,6 +138,7 @@ pub struct WebGlRenderBackend {
 
     color_program: ShaderProgram,
     bitmap_program: ShaderProgram,
+    textured_triangles_program: ShaderProgram,
     gradient_program: ShaderProgram,
 
     shape_tessellator: ShapeTessellator,
@@ -280,6 +291,15 @@ impl WebGlRenderBackend {
         let color_program = ShaderProgram::new(&gl, &color_vertex, &color_fragment)?;
         let bitmap_program = ShaderProgram::new(&gl, &texture_vertex, &bitmap_fragment)?;
         let gradient_program = ShaderProgram::new(&gl, &texture_vertex, &gradient_fragment)?;
+        let textured_triangles_vertex =
+            Self::compile_shader(&gl, Gl::VERTEX_SHADER, TEXTURED_TRIANGLES_VERTEX_GLSL)?;
+        let textured_triangles_fragment =
+            Self::compile_shader(&gl, Gl::FRAGMENT_SHADER, TEXTURED_TRIANGLES_FRAGMENT_GLSL)?;
+        let textured_triangles_program = ShaderProgram::new(
+            &gl,
+            &textured_triangles_vertex,
+            &textured_triangles_fragment,
+        )?;
 
         gl.enable(Gl::BLEND);
 
@@ -297,6 +317,7 @@ impl WebGlRenderBackend {
             color_program,
             gradient_program,
             bitmap_program,
+            textured_triangles_program,
 
             shape_tessellator: ShapeTessellator::new(),
 
@@ -584,12 +605,32 @@ impl WebGlRenderBackend {
             let vertex_buffer = self.gl.create_buffer().ok_or(Error::UnableToCreateBuffer)?;
             self.gl.bind_buffer(Gl::ARRAY_BUFFER, Some(&vertex_buffer));
 
-            let vertices: Vec<_> = draw.vertices.into_iter().map(Vertex::from).collect();
-            self.gl.buffer_data_with_u8_array(
-                Gl::ARRAY_BUFFER,
-                bytemuck::cast_slice(&vertices),
-                Gl::STATIC_DRAW,
-            );
+            // Textured triangles carry texture coordinates instead of a color.
+            let stride = if let TessDrawType::TexturedTriangles(textured) = &draw.draw_type {
+                let vertices: Vec<_> = draw
+                    .vertices
+                    .into_iter()
+                    .zip(&textured.texture_coords)
+                    .map(|(vertex, uvt)| UvtVertex {
+                        position: [vertex.x, vertex.y],
+                        uvt: *uvt,
+                    })
+                    .collect();
+                self.gl.buffer_data_with_u8_array(
+                    Gl::ARRAY_BUFFER,
+                    bytemuck::cast_slice(&vertices),
+                    Gl::STATIC_DRAW,
+                );
+                20
+            } else {
+                let vertices: Vec<_> = draw.vertices.into_iter().map(Vertex::from).collect();
+                self.gl.buffer_data_with_u8_array(
+                    Gl::ARRAY_BUFFER,
+                    bytemuck::cast_slice(&vertices),
+                    Gl::STATIC_DRAW,
+                );
+                12
+            };
 
             let index_buffer = self.gl.create_buffer().ok_or(Error::UnableToCreateBuffer)?;
             self.gl
@@ -604,6 +645,7 @@ impl WebGlRenderBackend {
                 TessDrawType::Color => &self.color_program,
                 TessDrawType::Gradient { .. } => &self.gradient_program,
                 TessDrawType::Bitmap(_) => &self.bitmap_program,
+                TessDrawType::TexturedTriangles(_) => &self.textured_triangles_program,
             };
 
             // Unfortunately it doesn't seem to be possible to ensure that vertex attributes will be in
@@ -616,7 +658,7 @@ impl WebGlRenderBackend {
                     2,
                     Gl::FLOAT,
                     false,
-                    12,
+                    stride,
                     0,
                 );
                 self.gl
@@ -629,13 +671,26 @@ impl WebGlRenderBackend {
                     4,
                     Gl::UNSIGNED_BYTE,
                     true,
-                    12,
+                    stride,
                     8,
                 );
                 self.gl
                     .enable_vertex_attrib_array(program.vertex_color_location);
             }
 
+            if program.vertex_uvt_location != 0xffff_ffff {
+                self.gl.vertex_attrib_pointer_with_i32(
+                    program.vertex_uvt_location,
+                    3,
+                    Gl::FLOAT,
+                    false,
+                    stride,
+                    8,
+                );
+                self.gl
+                    .enable_vertex_attrib_array(program.vertex_uvt_location);
+            }
+
             let num_vertex_attributes = program.num_vertex_attributes;
 
             draws.push(match draw.draw_type {
@@ -689,6 +744,26 @@ impl WebGlRenderBackend {
                     num_indices,
                     num_mask_indices,
                 },
+                TessDrawType::TexturedTriangles(textured) => Draw {
+                    draw_type: DrawType::TexturedTriangles(BitmapDraw {
+                        // The texture coordinates come from the vertices instead.
+                        matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+                        handle: bitmap_source.bitmap_handle(textured.bitmap_id, self),
+                        is_smoothed: textured.is_smoothed,
+                        is_repeating: textured.is_repeating,
+                    }),
+                    vao,
+                    vertex_buffer: Buffer {
+                        gl: self.gl.clone(),
+                        buffer: vertex_buffer,
+                    },
+                    index_buffer: Buffer {
+                        gl: self.gl.clone(),
+                        buffer: index_buffer,
+                    },
+                    num_indices,
+                    num_mask_indices,
+                },
             });
 
             self.bind_vertex_array(None);
@@ -1394,6 +1469,7 @@ impl CommandHandler for WebGlRenderBackend {
                 DrawType::Color => &self.color_program,
                 DrawType::Gradient(_) => &self.gradient_program,
                 DrawType::Bitmap { .. } => &self.bitmap_program,
+                DrawType::TexturedTriangles { .. } => &self.textured_triangles_program,
             };
 
             // Set common render state, while minimizing unnecessary state changes.
@@ -1455,7 +1531,7 @@ impl CommandHandler for WebGlRenderBackend {
                         (gradient.interpolation == swf::GradientInterpolation::LinearRgb) as i32,
                     );
                 }
-                DrawType::Bitmap(bitmap) => {
+                DrawType::Bitmap(bitmap) | DrawType::TexturedTriangles(bitmap) => {
                     let texture = match &bitmap.handle {
                         Some(handle) => &as_registry_data(handle).texture,
                         None => {
@@ -1692,6 +1768,7 @@ enum DrawType {
     Color,
     Gradient(Box<Gradient>),
     Bitmap(BitmapDraw),
+    TexturedTriangles(BitmapDraw),
 }
 
 struct MsaaBuffers {
@@ -1710,6 +1787,7 @@ struct ShaderProgram {
     uniforms: [Option<WebGlUniformLocation>; NUM_UNIFORMS],
     vertex_position_location: u32,
     vertex_color_location: u32,
+    vertex_uvt_location: u32,
     num_vertex_attributes: u32,
 }
 
@@ -1777,21 +1855,22 @@ impl ShaderProgram {
 
         let vertex_position_location = gl.get_attrib_location(&program, "position") as u32;
         let vertex_color_location = gl.get_attrib_location(&program, "color") as u32;
-        let num_vertex_attributes = if vertex_position_location != 0xffff_ffff {
-            1
-        } else {
-            0
-        } + if vertex_color_location != 0xffff_ffff {
-            1
-        } else {
-            0
-        };
+        let vertex_uvt_location = gl.get_attrib_location(&program, "uvt") as u32;
+        let num_vertex_attributes = [
+            vertex_position_location,
+            vertex_color_location,
+            vertex_uvt_location,
+        ]
+        .into_iter()
+        .filter(|&location| location != 0xffff_ffff)
+        .count() as u32;
 
         Ok(ShaderProgram {
             program,
             uniforms,
             vertex_position_location,
             vertex_color_location,
+            vertex_uvt_location,
             num_vertex_attributes,
         })
     }
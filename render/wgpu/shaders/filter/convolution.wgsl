@@ -0,0 +1,65 @@
+// NOTE: The `shader_filter_common.wgsl` source is prepended to this before compilation.
+
+struct Filter {
+    /// The weights of the matrix, row by row, packed by groups of four.
+    weights: array<vec4<f32>, 64>,
+    /// The color used outside of the source when not clamping, not premultiplied.
+    default_color: vec4<f32>,
+    /// The area of the texture used as the source, in pixels (left, top, right, bottom).
+    bounds: vec4<i32>,
+    columns: i32,
+    rows: i32,
+    divisor: f32,
+    bias: f32,
+    clamp: u32,
+    preserve_alpha: u32,
+    _padding: vec2<u32>,
+}
+
+@group(0) @binding(0) var texture: texture_2d<f32>;
+@group(0) @binding(1) var<uniform> filter_args: Filter;
+
+@vertex
+fn main_vertex(in: filter__VertexInput) -> filter__VertexOutput {
+    return filter__main_vertex(in);
+}
+
+/// Returns the unmultiplied color of a pixel of the source.
+fn unmultiplied_pixel(position: vec2<i32>) -> vec4<f32> {
+    let bounds = filter_args.bounds;
+    let inside = position.x >= bounds.x && position.y >= bounds.y && position.x < bounds.z && position.y < bounds.w;
+    if (!inside && filter_args.clamp == 0u) {
+        return filter_args.default_color;
+    }
+    let clamped = clamp(position, bounds.xy, bounds.zw - vec2<i32>(1, 1));
+    let pixel = textureLoad(texture, clamped, 0);
+    if (pixel.a > 0.0) {
+        return vec4<f32>(pixel.rgb / pixel.a, pixel.a);
+    }
+    return vec4<f32>(0.0);
+}
+
+@fragment
+fn main_fragment(in: filter__VertexOutput) -> @location(0) vec4<f32> {
+    let size = vec2<f32>(textureDimensions(texture));
+    let center = vec2<i32>(floor(in.uv * size));
+    let src = textureLoad(texture, clamp(center, filter_args.bounds.xy, filter_args.bounds.zw - vec2<i32>(1, 1)), 0);
+
+    var sum = vec4<f32>(0.0);
+    for (var row = 0; row < filter_args.rows; row++) {
+        for (var column = 0; column < filter_args.columns; column++) {
+            let index = row * filter_args.columns + column;
+            let weight = filter_args.weights[index / 4][index % 4];
+            if (weight != 0.0) {
+                let offset = vec2<i32>(column - filter_args.columns / 2, row - filter_args.rows / 2);
+                sum += unmultiplied_pixel(center + offset) * weight;
+            }
+        }
+    }
+
+    var color = saturate(sum / filter_args.divisor + vec4<f32>(filter_args.bias / 255.0));
+    if (filter_args.preserve_alpha > 0u) {
+        color.a = src.a;
+    }
+    return vec4<f32>(color.rgb * color.a, color.a);
+}
@@ -0,0 +1,39 @@
+/// Shader used for drawing bitmaps mapped onto triangles by per-vertex
+/// texture coordinates, as done by `Graphics.drawTriangles`.
+/// NOTE: The `common.wgsl` source is prepended to this before compilation.
+
+struct VertexInput {
+    /// The position of the vertex in object space.
+    @location(0) position: vec2<f32>,
+    /// The homogeneous texture coordinates of the vertex (`u * t`, `v * t`, `t`).
+    @location(1) uvt: vec3<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uvt: vec3<f32>,
+};
+
+@group(1) @binding(0) var<uniform> transforms: common__Transforms;
+@group(2) @binding(1) var texture: texture_2d<f32>;
+@group(2) @binding(2) var texture_sampler: sampler;
+
+@vertex
+fn main_vertex(in: VertexInput) -> VertexOutput {
+    let pos = common__globals.view_matrix * transforms.world_matrix * vec4<f32>(in.position.x, in.position.y, 0.0, 1.0);
+    return VertexOutput(pos, in.uvt);
+}
+
+@fragment
+fn main_fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Dividing after interpolation gives perspective-correct coordinates.
+    var color: vec4<f32> = textureSample(texture, texture_sampler, in.uvt.xy / in.uvt.z);
+    // Texture is premultiplied by alpha.
+    // Unmultiply alpha, apply color transform, remultiply alpha.
+    if (color.a > 0.0) {
+        color = vec4<f32>(color.rgb / color.a, color.a);
+        color = saturate(color * transforms.mult_color + transforms.add_color);
+        color = vec4<f32>(color.rgb * color.a, color.a);
+    }
+    return color;
+}
@@ -793,17 +793,8 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         Some(self.make_queue_sync_handle(target, None, handle, bounds))
     }
 
-    fn is_filter_supported(&self, filter: &Filter) -> bool {
-        matches!(
-            filter,
-            Filter::BlurFilter(_)
-                | Filter::GlowFilter(_)
-                | Filter::DropShadowFilter(_)
-                | Filter::ColorMatrixFilter(_)
-                | Filter::ShaderFilter(_)
-                | Filter::BevelFilter(_)
-                | Filter::DisplacementMapFilter(_)
-        )
+    fn is_filter_supported(&self, _filter: &Filter) -> bool {
+        true
     }
 
     fn is_offscreen_supported(&self) -> bool {
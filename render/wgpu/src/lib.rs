@@ -96,6 +96,22 @@ impl From<TessVertex> for PosVertex {
     }
 }
 
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct PosUvtVertex {
+    position: [f32; 2],
+    uvt: [f32; 3],
+}
+
+impl PosUvtVertex {
+    fn new(vertex: TessVertex, uvt: [f32; 3]) -> Self {
+        Self {
+            position: [vertex.x, vertex.y],
+            uvt,
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
 struct PosColorVertex {
@@ -1,7 +1,8 @@
 use crate::backend::WgpuRenderBackend;
 use crate::target::RenderTarget;
 use crate::{
-    Descriptors, GradientUniforms, PosColorVertex, PosVertex, TextureTransforms, as_texture,
+    Descriptors, GradientUniforms, PosColorVertex, PosUvtVertex, PosVertex, TextureTransforms,
+    as_texture,
 };
 use std::any::Any;
 use std::ops::Range;
@@ -10,8 +11,10 @@ use wgpu::util::DeviceExt;
 use crate::buffer_builder::BufferBuilder;
 use ruffle_render::backend::{RenderBackend, ShapeHandle, ShapeHandleImpl};
 use ruffle_render::bitmap::BitmapSource;
-use ruffle_render::tessellator::{Bitmap, Draw as LyonDraw, DrawType as TessDrawType, Gradient};
-use swf::{CharacterId, GradientInterpolation};
+use ruffle_render::tessellator::{
+    Bitmap, Draw as LyonDraw, DrawType as TessDrawType, Gradient, TexturedTriangles,
+};
+use swf::{CharacterId, GradientInterpolation, GradientRecord};
 
 /// How big to make gradient textures. Larger will keep more detail, but be slower and use more memory.
 const GRADIENT_SIZE: usize = 256;
@@ -39,6 +42,35 @@ pub struct PendingDraw {
 }
 
 impl PendingDraw {
+    pub fn textured_triangles(
+        textured: TexturedTriangles,
+        shape_id: CharacterId,
+        draw_id: usize,
+        source: &dyn BitmapSource,
+        backend: &mut dyn RenderBackend,
+        uniform_buffers: &mut BufferBuilder,
+    ) -> Option<Self> {
+        let handle = source.bitmap_handle(textured.bitmap_id, backend)?;
+        let texture = as_texture(&handle);
+        let texture_view = texture.texture.create_view(&Default::default());
+        // The texture coordinates come from the vertices, so the texture matrix is unused.
+        let identity = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        let texture_transforms_index = create_texture_transforms(&identity, uniform_buffers);
+        let bind_group_label = create_debug_label!(
+            "Shape {} (textured triangles) draw {} bindgroup",
+            shape_id,
+            draw_id
+        );
+
+        Some(PendingDrawType::TexturedTriangles {
+            texture_transforms_index,
+            texture_view,
+            is_repeating: textured.is_repeating,
+            is_smoothed: textured.is_smoothed,
+            bind_group_label,
+        })
+    }
+
     pub fn finish(
         self,
         descriptors: &Descriptors,
@@ -87,6 +119,16 @@ impl PendingDraw {
             vertex_buffer
                 .add(&vertices)
                 .expect("Mesh vertex buffer was too large!")
+        } else if let TessDrawType::TexturedTriangles(textured) = &draw.draw_type {
+            let vertices: Vec<_> = draw
+                .vertices
+                .into_iter()
+                .zip(&textured.texture_coords)
+                .map(|(vertex, uvt)| PosUvtVertex::new(vertex, *uvt))
+                .collect();
+            vertex_buffer
+                .add(&vertices)
+                .expect("Mesh vertex buffer was too large!")
         } else {
             let vertices: Vec<_> = draw.vertices.into_iter().map(PosVertex::from).collect();
             vertex_buffer
@@ -107,6 +149,14 @@ impl PendingDraw {
             TessDrawType::Bitmap(bitmap) => {
                 PendingDrawType::bitmap(bitmap, shape_id, draw_id, source, backend, uniform_buffer)?
             }
+            TessDrawType::TexturedTriangles(textured) => PendingDrawType::textured_triangles(
+                textured,
+                shape_id,
+                draw_id,
+                source,
+                backend,
+                uniform_buffer,
+            )?,
         };
         Some(PendingDraw {
             draw_type,
@@ -133,6 +183,13 @@ pub enum PendingDrawType {
         is_smoothed: bool,
         bind_group_label: Option<String>,
     },
+    TexturedTriangles {
+        texture_transforms_index: wgpu::BufferAddress,
+        texture_view: wgpu::TextureView,
+        is_repeating: bool,
+        is_smoothed: bool,
+        bind_group_label: Option<String>,
+    },
 }
 
 /// Converts an RGBA color from sRGB space to linear color space.
@@ -270,6 +327,27 @@ impl PendingDrawType {
 
                 DrawType::Bitmap { binds }
             }
+            PendingDrawType::TexturedTriangles {
+                texture_transforms_index,
+                texture_view,
+                is_repeating,
+                is_smoothed,
+                bind_group_label,
+            } => {
+                let binds = BitmapBinds::new(
+                    &descriptors.device,
+                    &descriptors.bind_layouts.bitmap,
+                    descriptors
+                        .bitmap_samplers
+                        .get_sampler(is_repeating, is_smoothed),
+                    uniform_buffer,
+                    texture_transforms_index,
+                    texture_view,
+                    bind_group_label,
+                );
+
+                DrawType::TexturedTriangles { binds }
+            }
         }
     }
 }
@@ -279,6 +357,93 @@ pub enum DrawType {
     Color,
     Gradient { bind_group: wgpu::BindGroup },
     Bitmap { binds: BitmapBinds },
+    TexturedTriangles { binds: BitmapBinds },
+}
+
+/// Creates a texture containing the colors of a gradient, sampled at `GRADIENT_SIZE` points.
+pub fn create_gradient_texture_view(
+    descriptors: &Descriptors,
+    records: &[GradientRecord],
+    interpolation: GradientInterpolation,
+) -> wgpu::TextureView {
+    let colors = if records.is_empty() {
+        [0; GRADIENT_SIZE * 4]
+    } else {
+        let mut colors = [0; GRADIENT_SIZE * 4];
+        let mut last = 0;
+        let mut next;
+
+        let convert = if interpolation == GradientInterpolation::LinearRgb {
+            |c| srgb_to_linear(c / 255.0) * 255.0
+        } else {
+            |c| c
+        };
+
+        for t in 0..GRADIENT_SIZE {
+            if last + 1 < records.len() && t > records[last + 1].ratio as usize {
+                last += 1;
+            }
+            next = (last + 1).min(records.len() - 1);
+
+            assert!(last == next || last + 1 == next);
+
+            let last_record = &records[last];
+            let next_record = &records[next];
+
+            let a = if t <= last_record.ratio as usize || last_record.ratio == next_record.ratio {
+                // We are before the first gradient record,
+                // or this record's ratio is equal to the next one,
+                // meaning we need to do a full stop of this color for 1 pixel.
+                0.0
+            } else if t > next_record.ratio as usize {
+                // We are after the last record
+                1.0
+            } else {
+                (t as f32 - last_record.ratio as f32)
+                    / (next_record.ratio as f32 - last_record.ratio as f32)
+            };
+
+            colors[t * 4] = lerp(
+                convert(last_record.color.r as f32),
+                convert(next_record.color.r as f32),
+                a,
+            ) as u8;
+            colors[(t * 4) + 1] = lerp(
+                convert(last_record.color.g as f32),
+                convert(next_record.color.g as f32),
+                a,
+            ) as u8;
+            colors[(t * 4) + 2] = lerp(
+                convert(last_record.color.b as f32),
+                convert(next_record.color.b as f32),
+                a,
+            ) as u8;
+            colors[(t * 4) + 3] =
+                lerp(last_record.color.a as f32, next_record.color.a as f32, a) as u8;
+        }
+
+        colors
+    };
+    let texture = descriptors.device.create_texture_with_data(
+        &descriptors.queue,
+        &wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: GRADIENT_SIZE as u32,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        },
+        wgpu::util::TextureDataOrder::LayerMajor,
+        &colors[..],
+    );
+    texture.create_view(&Default::default())
 }
 
 #[derive(Debug)]
@@ -293,87 +458,8 @@ impl CommonGradient {
         gradient: Gradient,
         uniform_buffers: &mut BufferBuilder,
     ) -> Self {
-        let colors = if gradient.records.is_empty() {
-            [0; GRADIENT_SIZE * 4]
-        } else {
-            let mut colors = [0; GRADIENT_SIZE * 4];
-            let mut last = 0;
-            let mut next;
-
-            let convert = if gradient.interpolation == GradientInterpolation::LinearRgb {
-                |c| srgb_to_linear(c / 255.0) * 255.0
-            } else {
-                |c| c
-            };
-
-            for t in 0..GRADIENT_SIZE {
-                if last + 1 < gradient.records.len()
-                    && t > gradient.records[last + 1].ratio as usize
-                {
-                    last += 1;
-                }
-                next = (last + 1).min(gradient.records.len() - 1);
-
-                assert!(last == next || last + 1 == next);
-
-                let last_record = &gradient.records[last];
-                let next_record = &gradient.records[next];
-
-                let a = if t <= last_record.ratio as usize || last_record.ratio == next_record.ratio
-                {
-                    // We are before the first gradient record,
-                    // or this record's ratio is equal to the next one,
-                    // meaning we need to do a full stop of this color for 1 pixel.
-                    0.0
-                } else if t > next_record.ratio as usize {
-                    // We are after the last record
-                    1.0
-                } else {
-                    (t as f32 - last_record.ratio as f32)
-                        / (next_record.ratio as f32 - last_record.ratio as f32)
-                };
-
-                colors[t * 4] = lerp(
-                    convert(last_record.color.r as f32),
-                    convert(next_record.color.r as f32),
-                    a,
-                ) as u8;
-                colors[(t * 4) + 1] = lerp(
-                    convert(last_record.color.g as f32),
-                    convert(next_record.color.g as f32),
-                    a,
-                ) as u8;
-                colors[(t * 4) + 2] = lerp(
-                    convert(last_record.color.b as f32),
-                    convert(next_record.color.b as f32),
-                    a,
-                ) as u8;
-                colors[(t * 4) + 3] =
-                    lerp(last_record.color.a as f32, next_record.color.a as f32, a) as u8;
-            }
-
-            colors
-        };
-        let texture = descriptors.device.create_texture_with_data(
-            &descriptors.queue,
-            &wgpu::TextureDescriptor {
-                label: None,
-                size: wgpu::Extent3d {
-                    width: GRADIENT_SIZE as u32,
-                    height: 1,
-                    depth_or_array_layers: 1,
-                },
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: wgpu::TextureDimension::D2,
-                format: wgpu::TextureFormat::Rgba8Unorm,
-                usage: wgpu::TextureUsages::TEXTURE_BINDING,
-                view_formats: &[],
-            },
-            wgpu::util::TextureDataOrder::LayerMajor,
-            &colors[..],
-        );
-        let view = texture.create_view(&Default::default());
+        let view =
+            create_gradient_texture_view(descriptors, &gradient.records, gradient.interpolation);
 
         let buffer_offset = uniform_buffers
             .add(&[GradientUniforms::from(gradient)])
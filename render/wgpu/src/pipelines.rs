@@ -1,7 +1,7 @@
 use crate::blend::{ComplexBlend, TrivialBlend};
 use crate::layouts::BindLayouts;
 use crate::shaders::Shaders;
-use crate::{MaskState, PosColorVertex, PosVertex};
+use crate::{MaskState, PosColorVertex, PosUvtVertex, PosVertex};
 use enum_map::{EnumMap, enum_map};
 use wgpu::{BlendState, PrimitiveTopology, vertex_attr_array};
 
@@ -24,6 +24,16 @@ pub const VERTEX_BUFFERS_DESCRIPTION_COLOR: [wgpu::VertexBufferLayout; 1] =
         ],
     }];
 
+pub const VERTEX_BUFFERS_DESCRIPTION_UVT: [wgpu::VertexBufferLayout; 1] =
+    [wgpu::VertexBufferLayout {
+        array_stride: std::mem::size_of::<PosUvtVertex>() as u64,
+        step_mode: wgpu::VertexStepMode::Vertex,
+        attributes: &vertex_attr_array![
+            0 => Float32x2,
+            1 => Float32x3,
+        ],
+    }];
+
 #[derive(Debug)]
 pub struct ShapePipeline {
     pub pipelines: EnumMap<MaskState, wgpu::RenderPipeline>,
@@ -44,6 +54,8 @@ pub struct Pipelines {
     /// or use it in any way.
     pub bitmap_opaque_dummy_stencil: wgpu::RenderPipeline,
     pub bitmap: EnumMap<TrivialBlend, ShapePipeline>,
+    /// Renders bitmaps mapped by per-vertex texture coordinates.
+    pub textured_triangles: ShapePipeline,
     pub gradients: ShapePipeline,
     pub complex_blends: EnumMap<ComplexBlend, ShapePipeline>,
     pub alpha_mask: ShapePipeline,
@@ -169,6 +181,19 @@ impl Pipelines {
             )
         });
 
+        let textured_triangles_pipeline = create_shape_pipeline(
+            "Textured triangles",
+            device,
+            format,
+            &shaders.textured_triangles_shader,
+            msaa_sample_count,
+            &VERTEX_BUFFERS_DESCRIPTION_UVT,
+            &bitmap_blend_bindings,
+            BlendState::PREMULTIPLIED_ALPHA_BLENDING,
+            &[],
+            PrimitiveTopology::TriangleList,
+        );
+
         let bitmap_opaque_pipeline_layout_label =
             create_debug_label!("Opaque bitmap pipeline layout");
         let bitmap_opaque_pipeline_layout =
@@ -246,6 +271,7 @@ impl Pipelines {
             color: color_pipelines,
             lines: lines_pipelines,
             bitmap: bitmap_pipelines,
+            textured_triangles: textured_triangles_pipeline,
             bitmap_opaque,
             bitmap_opaque_dummy_stencil: bitmap_opaque_dummy_depth,
             gradients: gradient_pipeline,
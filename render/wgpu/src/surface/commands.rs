@@ -12,6 +12,7 @@ use ruffle_render::bitmap::{BitmapHandle, PixelSnapping};
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::lines::{emulate_line, emulate_line_rect};
 use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::transform::Transform;
 use std::mem;
@@ -164,6 +165,21 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
         self.render_pass.set_bind_group(2, bind_group, &[]);
     }
 
+    pub fn prep_textured_triangles(&mut self, bind_group: &'pass wgpu::BindGroup) {
+        if self.needs_stencil {
+            self.render_pass.set_pipeline(
+                self.pipelines
+                    .textured_triangles
+                    .pipeline_for(self.mask_state),
+            );
+        } else {
+            self.render_pass
+                .set_pipeline(self.pipelines.textured_triangles.stencilless_pipeline());
+        }
+
+        self.render_pass.set_bind_group(2, bind_group, &[]);
+    }
+
     pub fn prep_alpha_mask(&mut self, bind_group: &'pass wgpu::BindGroup) {
         if self.needs_stencil {
             self.render_pass
@@ -289,6 +305,9 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
                 DrawType::Bitmap { binds, .. } => {
                     self.prep_bitmap(&binds.bind_group, TrivialBlend::Normal, false);
                 }
+                DrawType::TexturedTriangles { binds } => {
+                    self.prep_textured_triangles(&binds.bind_group);
+                }
             }
             self.render_pass.set_bind_group(
                 1,
@@ -607,18 +626,51 @@ impl<'a> WgpuCommandHandler<'a> {
         color_transform: ColorTransform,
         command_builder: impl FnOnce(wgpu::DynamicOffset) -> DrawCommand,
     ) {
-        let transform = Transforms {
-            world_matrix: [
-                [matrix.a, matrix.b, 0.0, 0.0],
-                [matrix.c, matrix.d, 0.0, 0.0],
-                [0.0, 0.0, 1.0, 0.0],
-                [
-                    matrix.tx.to_pixels() as f32,
-                    matrix.ty.to_pixels() as f32,
-                    0.0,
-                    1.0,
-                ],
+        let world_matrix = [
+            [matrix.a, matrix.b, 0.0, 0.0],
+            [matrix.c, matrix.d, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [
+                matrix.tx.to_pixels() as f32,
+                matrix.ty.to_pixels() as f32,
+                0.0,
+                1.0,
             ],
+        ];
+        self.add_to_current_with_world_matrix(world_matrix, color_transform, command_builder);
+    }
+
+    /// Like [`Self::add_to_current`], but for objects transformed in 3D.
+    fn add_to_current_3d(
+        &mut self,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+        command_builder: impl FnOnce(wgpu::DynamicOffset) -> DrawCommand,
+    ) {
+        let mut m = matrix.raw_data;
+        // The projection is already applied through `w`. Depth isn't used for
+        // anything else, so flatten it to keep content from being clipped.
+        m[2] = 0.0;
+        m[6] = 0.0;
+        m[10] = 0.0;
+        m[14] = 0.0;
+        let world_matrix = [
+            [m[0], m[1], m[2], m[3]],
+            [m[4], m[5], m[6], m[7]],
+            [m[8], m[9], m[10], m[11]],
+            [m[12], m[13], m[14], m[15]],
+        ];
+        self.add_to_current_with_world_matrix(world_matrix, color_transform, command_builder);
+    }
+
+    fn add_to_current_with_world_matrix(
+        &mut self,
+        world_matrix: [[f32; 4]; 4],
+        color_transform: ColorTransform,
+        command_builder: impl FnOnce(wgpu::DynamicOffset) -> DrawCommand,
+    ) {
+        let transform = Transforms {
+            world_matrix,
             mult_color: color_transform.mult_rgba_normalized(),
             add_color: color_transform.add_rgba_normalized(),
         };
@@ -687,24 +739,27 @@ impl CommandHandler for WgpuCommandHandler<'_> {
         smoothing: bool,
         pixel_snapping: PixelSnapping,
     ) {
-        let mut matrix = transform.matrix;
-        {
-            let texture = as_texture(&bitmap);
+        let texture = as_texture(&bitmap);
+        let (width, height) = (
+            texture.texture.width() as f32,
+            texture.texture.height() as f32,
+        );
+        let command_builder = |transform_buffer| DrawCommand::RenderBitmap {
+            bitmap,
+            transform_buffer,
+            smoothing,
+            blend_mode: TrivialBlend::Normal,
+            render_stage3d: false,
+        };
+        if let Some(matrix3d) = transform.matrix3d {
+            let matrix3d = matrix3d.multiply(&Matrix3D::scale(width, height, 1.0));
+            self.add_to_current_3d(matrix3d, transform.color_transform, command_builder);
+        } else {
+            let mut matrix = transform.matrix;
             pixel_snapping.apply(&mut matrix);
-            matrix *= Matrix::scale(
-                texture.texture.width() as f32,
-                texture.texture.height() as f32,
-            );
+            matrix *= Matrix::scale(width, height);
+            self.add_to_current(matrix, transform.color_transform, command_builder);
         }
-        self.add_to_current(matrix, transform.color_transform, |transform_buffer| {
-            DrawCommand::RenderBitmap {
-                bitmap,
-                transform_buffer,
-                smoothing,
-                blend_mode: TrivialBlend::Normal,
-                render_stage3d: false,
-            }
-        });
     }
     fn render_stage3d(&mut self, bitmap: BitmapHandle, transform: Transform) {
         let mut matrix = transform.matrix;
@@ -727,14 +782,15 @@ impl CommandHandler for WgpuCommandHandler<'_> {
     }
 
     fn render_shape(&mut self, shape: ShapeHandle, transform: Transform) {
-        self.add_to_current(
-            transform.matrix,
-            transform.color_transform,
-            |transform_buffer| DrawCommand::RenderShape {
-                shape,
-                transform_buffer,
-            },
-        );
+        let command_builder = |transform_buffer| DrawCommand::RenderShape {
+            shape,
+            transform_buffer,
+        };
+        if let Some(matrix3d) = transform.matrix3d {
+            self.add_to_current_3d(matrix3d, transform.color_transform, command_builder);
+        } else {
+            self.add_to_current(transform.matrix, transform.color_transform, command_builder);
+        }
     }
 
     fn draw_rect(&mut self, color: Color, matrix: Matrix) {
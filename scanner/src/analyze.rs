@@ -2,8 +2,23 @@
 
 use crate::cli_options::AnalyzeOpt;
 use crate::file_results::{FileResults, Step};
+use std::collections::HashMap;
 use std::fs::File;
 
+/// How many of the most common stubs to list.
+const TOP_STUBS: usize = 20;
+
+/// How often a stub was encountered.
+#[derive(Default)]
+struct StubStats {
+    /// The number of movies that encountered the stub.
+    movies: usize,
+
+    /// The number of movies that encountered the stub and didn't complete
+    /// without errors.
+    failed_movies: usize,
+}
+
 /// Generate and print statistics related to a scan's results
 pub fn analyze(results: impl Iterator<Item = FileResults>) {
     let mut total = 0;
@@ -13,10 +28,20 @@ pub fn analyze(results: impl Iterator<Item = FileResults>) {
     let mut parse = 0;
     let mut execute = 0;
     let mut complete = 0;
+    let mut stubs: HashMap<String, StubStats> = HashMap::new();
 
     for result in results {
         total += 1;
 
+        let failed = !matches!(result.progress, Step::Complete);
+        for stub in result.stubs.iter().flat_map(|stubs| stubs.lines()) {
+            let stats = stubs.entry(stub.to_string()).or_default();
+            stats.movies += 1;
+            if failed {
+                stats.failed_movies += 1;
+            }
+        }
+
         match result.progress {
             Step::Start => start += 1,
             Step::Read => read += 1,
@@ -48,6 +73,26 @@ pub fn analyze(results: impl Iterator<Item = FileResults>) {
     println!("{execute:>digits$} movies failed to execute");
     println!("{complete:>digits$} movies completed without errors");
     println!();
+
+    if !stubs.is_empty() {
+        // Stubs are ranked by how much content they may be blocking.
+        let mut stubs: Vec<_> = stubs.into_iter().collect();
+        stubs.sort_by(|(a_name, a), (b_name, b)| {
+            b.failed_movies
+                .cmp(&a.failed_movies)
+                .then(b.movies.cmp(&a.movies))
+                .then(a_name.cmp(b_name))
+        });
+
+        println!("Most encountered stubs (failed movies / movies):");
+        for (name, stats) in stubs.iter().take(TOP_STUBS) {
+            println!(
+                "{:>digits$} / {:<digits$} {name}",
+                stats.failed_movies, stats.movies
+            );
+        }
+        println!();
+    }
 }
 
 pub fn analyze_main(opt: AnalyzeOpt) -> Result<(), std::io::Error> {
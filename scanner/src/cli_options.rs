@@ -35,6 +35,9 @@ pub struct ScanOpt {
     /// Filenames to ignore
     #[clap(short = 'i', long = "ignore", action = clap::ArgAction::Append)]
     pub ignore: Vec<String>,
+
+    #[clap(flatten)]
+    pub run: RunOpt,
 }
 
 #[derive(Parser, Debug)]
@@ -49,4 +52,31 @@ pub struct ExecuteReportOpt {
     /// The single SWF file to parse and run
     #[clap(name = "file")]
     pub input_path: PathBuf,
+
+    #[clap(flatten)]
+    pub run: RunOpt,
+}
+
+/// How to run each SWF file.
+#[derive(Parser, Debug, Clone, Copy)]
+pub struct RunOpt {
+    /// The number of frames to run each SWF file for.
+    /// Running more frames gets past preloaders, at the cost of a slower scan.
+    #[clap(long = "frames", default_value = "1")]
+    pub frames: u32,
+
+    /// Click the center of the stage after every frame, to get past "click to play" screens
+    #[clap(long = "click", action)]
+    pub click: bool,
+}
+
+impl RunOpt {
+    /// The arguments that pass these options to an `execute-report` process.
+    pub fn to_args(self) -> Vec<String> {
+        let mut args = vec!["--frames".to_string(), self.frames.to_string()];
+        if self.click {
+            args.push("--click".to_string());
+        }
+        args
+    }
 }
@@ -1,37 +1,123 @@
 //! Child/executor process impls
 
-use crate::cli_options::ExecuteReportOpt;
+use crate::cli_options::{ExecuteReportOpt, RunOpt};
 use crate::file_results::{AvmType, FileResults, Step};
 use crate::logging::{LOCAL_LOGGER, ScanLogBackend, ThreadLocalScanLogger};
-use ruffle_core::FloatDuration;
-use ruffle_core::PlayerBuilder;
 use ruffle_core::backend::navigator::{NullExecutor, NullNavigatorBackend};
+use ruffle_core::events::MouseButton;
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::swf::{decompress_swf, parse_swf};
 use ruffle_core::tag_utils::movie_from_path;
+use ruffle_core::{FloatDuration, Player, PlayerBuilder, PlayerEvent};
 use sha2::{Digest, Sha256};
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::BTreeSet;
 use std::io::{Write, stdout};
-use std::panic::catch_unwind;
+use std::panic::{AssertUnwindSafe, catch_unwind};
 use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
 use std::time::{Duration, Instant};
 
-fn execute_swf(file: &Path) {
+thread_local! {
+    /// Where the last panic on this thread happened.
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// What was observed while running a movie.
+///
+/// This is filled in as the movie runs, so that it's still available if
+/// Ruffle panics partway through.
+#[derive(Default)]
+struct RunReport {
+    frames_run: u32,
+    player: Option<Arc<Mutex<Player>>>,
+}
+
+impl RunReport {
+    /// The stubs the movie hit, including in a frame that panicked.
+    fn stubs(&self) -> BTreeSet<String> {
+        let Some(player) = &self.player else {
+            return BTreeSet::new();
+        };
+
+        // A panic while the player was locked poisons the lock, but the stubs
+        // recorded until then are still worth reporting.
+        let player = player.lock().unwrap_or_else(PoisonError::into_inner);
+        player
+            .stub_tracker()
+            .iter()
+            .map(|stub| stub.to_string())
+            .collect()
+    }
+}
+
+fn execute_swf(file: &Path, run_opt: RunOpt, report: &mut RunReport) {
     let base_path = file.parent().unwrap();
-    let executor = NullExecutor::new();
+    let mut executor = NullExecutor::new();
     let movie = movie_from_path(file, None).unwrap();
     let frame_time = FloatDuration::from_millis(1000.0 / movie.frame_rate().to_f64());
+    let width = movie.width().to_pixels();
+    let height = movie.height().to_pixels();
     let player = PlayerBuilder::new()
         .with_log(ScanLogBackend::new())
         .with_navigator(NullNavigatorBackend::with_base_path(base_path, &executor).unwrap())
         .with_max_execution_duration(Duration::from_secs(300))
+        .with_viewport_dimensions(width as u32, height as u32, 1.0)
         .with_movie(movie)
         .build();
+    report.player = Some(player.clone());
+
+    for _ in 0..run_opt.frames {
+        player.lock().unwrap().preload(&mut ExecutionLimit::none());
 
-    player.lock().unwrap().preload(&mut ExecutionLimit::none());
+        player.lock().unwrap().run_frame();
+        player.lock().unwrap().update_timers(frame_time);
+        executor.run();
+
+        if run_opt.click {
+            let (x, y) = (width / 2.0, height / 2.0);
+            let mut player = player.lock().unwrap();
+            player.handle_event(PlayerEvent::MouseMove { x, y });
+            player.handle_event(PlayerEvent::MouseDown {
+                x,
+                y,
+                button: MouseButton::Left,
+                index: None,
+            });
+            player.handle_event(PlayerEvent::MouseUp {
+                x,
+                y,
+                button: MouseButton::Left,
+            });
+        }
+
+        report.frames_run += 1;
+    }
+}
+
+/// Describes the payload of a panic.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<String>() {
+        format!("PANIC: {message}")
+    } else if let Some(message) = payload.downcast_ref::<&str>() {
+        format!("PANIC: {message}")
+    } else {
+        "PANIC".to_string()
+    }
+}
 
-    player.lock().unwrap().run_frame();
-    player.lock().unwrap().update_timers(frame_time);
-    //executor.poll_all().unwrap();
+/// Records where panics happen, while still reporting them as usual.
+fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if let Some(location) = info.location() {
+            PANIC_LOCATION.with(|panic_location| {
+                *panic_location.borrow_mut() = Some(location.to_string());
+            });
+        }
+        default_hook(info);
+    }));
 }
 
 fn checkpoint<W: Write>(
@@ -53,6 +139,7 @@ fn checkpoint<W: Write>(
 
 pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), std::io::Error> {
     ThreadLocalScanLogger::init();
+    install_panic_hook();
 
     let start = Instant::now();
     let file_path = execute_report_opt.input_path;
@@ -132,33 +219,30 @@ pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), s
                 checkpoint(&mut file_result, &start, &mut writer)?;
             }
         },
-        Err(e) => match e.downcast::<String>() {
-            Ok(e) => {
-                file_result.error = Some(format!("PANIC: {e}"));
-                checkpoint(&mut file_result, &start, &mut writer)?;
-            }
-            Err(_) => {
-                file_result.error = Some("PANIC".to_string());
-                checkpoint(&mut file_result, &start, &mut writer)?;
-            }
-        },
+        Err(e) => {
+            file_result.error = Some(panic_message(e));
+            file_result.panic_location = PANIC_LOCATION.with(|location| location.take());
+            checkpoint(&mut file_result, &start, &mut writer)?;
+        }
     };
 
     checkpoint(&mut file_result, &start, &mut writer)?;
     file_result.progress = Step::Execute;
 
-    //Run one frame of the movie in Ruffle.
-    if let Err(e) = catch_unwind(|| execute_swf(&file_path)) {
-        match e.downcast::<String>() {
-            Ok(e) => {
-                file_result.error = Some(format!("PANIC: {e}"));
-                checkpoint(&mut file_result, &start, &mut writer)?;
-            }
-            Err(_) => {
-                file_result.error = Some("PANIC".to_string());
-                checkpoint(&mut file_result, &start, &mut writer)?;
-            }
-        }
+    // Run the movie in Ruffle.
+    let mut report = RunReport::default();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        execute_swf(&file_path, execute_report_opt.run, &mut report)
+    }));
+    file_result.frames_run = Some(report.frames_run);
+    let stubs = report.stubs();
+    if !stubs.is_empty() {
+        file_result.stubs = Some(stubs.into_iter().collect::<Vec<_>>().join("\n"));
+    }
+    if let Err(e) = result {
+        file_result.error = Some(panic_message(e));
+        file_result.panic_location = PANIC_LOCATION.with(|location| location.take());
+        checkpoint(&mut file_result, &start, &mut writer)?;
     }
 
     let errors = LOCAL_LOGGER.with(|log_buffer| {
@@ -167,8 +251,8 @@ pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), s
         log_buffer.borrow_mut().join("\n")
     });
     if !errors.is_empty() {
-        file_result.error = Some(errors);
-    } else {
+        file_result.avm_errors = Some(errors);
+    } else if file_result.error.is_none() {
         file_result.progress = Step::Complete;
     }
 
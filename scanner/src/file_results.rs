@@ -126,6 +126,23 @@ pub struct FileResults {
     /// The AVM type of the movie.
     #[serde(rename = "AVM Version")]
     pub vm_type: Option<AvmType>,
+
+    /// How many frames of the movie ran.
+    #[serde(rename = "Frames Run", default)]
+    pub frames_run: Option<u32>,
+
+    /// The stubs encountered while running the movie, one per line.
+    #[serde(rename = "Stubs", default)]
+    pub stubs: Option<String>,
+
+    /// The errors logged while running the movie, such as uncaught AVM
+    /// exceptions, one per line.
+    #[serde(rename = "AVM Errors", default)]
+    pub avm_errors: Option<String>,
+
+    /// Where in Ruffle's code the scanner panicked, if it did.
+    #[serde(rename = "Panic Location", default)]
+    pub panic_location: Option<String>,
 }
 
 impl Default for FileResults {
@@ -153,6 +170,10 @@ impl FileResults {
             use_gpu: None,
             use_network_sandbox: None,
             vm_type: None,
+            frames_run: None,
+            stubs: None,
+            avm_errors: None,
+            panic_location: None,
         }
     }
 }
@@ -17,6 +17,12 @@ use std::time::Duration;
 use url::{ParseError, Url};
 use vfs::VfsPath;
 
+/// The request Flash Player sends to socket policy servers.
+const SOCKET_POLICY_REQUEST: &[u8] = b"<policy-file-request/>\0";
+
+/// The port Flash Player requests socket policies from by default.
+const SOCKET_POLICY_PORT: u16 = 843;
+
 struct TestResponse {
     url: String,
     body: Vec<u8>,
@@ -76,6 +82,11 @@ impl SuccessResponse for TestResponse {
 ///
 /// These are formatted as query params, rather than domains/whole URLs, so that real/real-invalid
 /// URLs can be used in Flash Player when writing tests
+///
+/// Remote URLs are served from the test directory, e.g. `http://example.com/crossdomain.xml`
+/// from `example.com/crossdomain.xml`. Similarly, sockets act as a socket policy server on
+/// ports with a `{host}/socket_policy_{port}.xml` file, and nothing listens on port 843
+/// otherwise.
 pub struct TestNavigatorBackend {
     spawner: NullSpawner,
     relative_base_path: VfsPath,
@@ -99,6 +110,18 @@ impl TestNavigatorBackend {
     }
 }
 
+impl TestNavigatorBackend {
+    /// The socket policy served on the given port of `host`, if any.
+    fn socket_policy(&self, host: &str, port: u16) -> Option<Vec<u8>> {
+        let path = self
+            .relative_base_path
+            .join(host)
+            .and_then(|path| path.join(format!("socket_policy_{port}.xml")))
+            .ok()?;
+        read_bytes(&path).ok()
+    }
+}
+
 impl NavigatorBackend for TestNavigatorBackend {
     fn navigate_to_url(
         &self,
@@ -287,6 +310,40 @@ impl NavigatorBackend for TestNavigatorBackend {
             log.avm_trace(&format!("    Host: {host}; Port: {port}"));
         }
 
+        if let Some(policy) = self.socket_policy(&host, port) {
+            self.spawn_future(Box::pin(async move {
+                sender
+                    .try_send(SocketAction::Connect(handle, ConnectionState::Connected))
+                    .expect("working channel send");
+
+                // Connections that don't request the policy are left idle.
+                if receiver
+                    .recv()
+                    .await
+                    .is_ok_and(|data| data == SOCKET_POLICY_REQUEST)
+                {
+                    let mut payload = policy;
+                    payload.push(0);
+                    sender
+                        .try_send(SocketAction::Data(handle, payload))
+                        .expect("working channel send");
+                    sender
+                        .try_send(SocketAction::Close(handle))
+                        .expect("working channel send");
+                }
+
+                Ok(())
+            }));
+            return;
+        }
+
+        if port == SOCKET_POLICY_PORT {
+            sender
+                .try_send(SocketAction::Connect(handle, ConnectionState::Failed))
+                .expect("working channel send");
+            return;
+        }
+
         if let Some(events) = self.socket_events.clone() {
             self.spawn_future(Box::pin(async move {
                 sender
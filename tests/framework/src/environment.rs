@@ -56,6 +56,11 @@ pub trait RenderInterface {
     /// This name may be used in file paths, so it should contain appropriate characters for such.
     fn name(&self) -> String;
 
+    /// Gets the kind of renderer used, either `wgpu` or `software`.
+    ///
+    /// Image comparison checks can filter on this with the `renderer` predicate.
+    fn renderer_kind(&self) -> &'static str;
+
     /// Capture the stage rendered out by the given render backend.
     ///
     /// The provided backend is guaranteed to be the same one paired with this interface.
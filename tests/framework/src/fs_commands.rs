@@ -30,6 +30,8 @@ impl FsCommandProvider for TestFsCommandProvider {
 pub enum FsCommand {
     Quit,
     CaptureImage(String),
+    SaveState(String),
+    LoadState(String),
 }
 
 impl FsCommand {
@@ -37,6 +39,8 @@ impl FsCommand {
         match command {
             "quit" => Some(Self::Quit),
             "captureImage" => Some(Self::CaptureImage(args.to_string())),
+            "saveState" => Some(Self::SaveState(args.to_string())),
+            "loadState" => Some(Self::LoadState(args.to_string())),
             _ => None,
         }
     }
@@ -114,6 +114,7 @@ pub struct TestOptions {
     pub approximations: Option<Approximations>,
     pub player_options: PlayerOptions,
     pub log_fetch: bool,
+    pub url: Option<String>,
     pub required_features: RequiredFeatures,
     pub fonts: HashMap<String, FontOptions>,
     pub font_sorts: HashMap<String, FontSortOptions>,
@@ -140,6 +141,7 @@ impl Default for TestOptions {
             approximations: None,
             player_options: PlayerOptions::default(),
             log_fetch: false,
+            url: None,
             required_features: RequiredFeatures::default(),
             fonts: Default::default(),
             font_sorts: Default::default(),
@@ -260,11 +262,19 @@ impl TestOptions {
 pub struct RequiredFeatures {
     lzma: bool,
     jpegxr: bool,
+    software_renderer: bool,
 }
 
 impl RequiredFeatures {
     fn can_run(&self) -> bool {
-        (!self.lzma || cfg!(feature = "lzma")) && (!self.jpegxr || cfg!(feature = "jpegxr"))
+        (!self.lzma || cfg!(feature = "lzma"))
+            && (!self.jpegxr || cfg!(feature = "jpegxr"))
+            && (!self.software_renderer || cfg!(feature = "software_renderer"))
+    }
+
+    /// Whether images of this test are compared when taken by the software renderer.
+    pub fn software_renderer(&self) -> bool {
+        self.software_renderer
     }
 }
 
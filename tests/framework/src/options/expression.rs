@@ -7,7 +7,9 @@ use serde::Deserialize;
 /// Currently the following variables are supported:
 /// * `os` --- refers to [`std::env::consts::OS`],
 /// * `arch` --- refers to [`std::env::consts::ARCH`],
-/// * `family` --- refers to [`std::env::consts::FAMILY`].
+/// * `family` --- refers to [`std::env::consts::FAMILY`],
+/// * `renderer` --- refers to the kind of renderer taking the image, `wgpu` or
+///   `software` (only available in image comparison checks).
 ///
 /// Example expression:
 ///
@@ -19,6 +21,11 @@ pub struct TestExpression(String);
 
 impl TestExpression {
     pub fn evaluate(&self) -> anyhow::Result<bool> {
+        self.evaluate_with_renderer(None)
+    }
+
+    /// Evaluates this expression for an image taken by the given kind of renderer.
+    pub fn evaluate_with_renderer(&self, renderer: Option<&str>) -> anyhow::Result<bool> {
         let cfg_parsed = cfg_expr::Expression::parse(&self.0)
             .map_err(|err| anyhow!("Cannot parse expression:\n{err}"))?;
         let mut unknown_pred = None;
@@ -32,6 +39,11 @@ impl TestExpression {
             cfg_expr::Predicate::KeyValue { key, val } if *key == "family" => {
                 *val == std::env::consts::FAMILY
             }
+            cfg_expr::Predicate::KeyValue { key, val }
+                if *key == "renderer" && renderer.is_some() =>
+            {
+                Some(*val) == renderer
+            }
             _ => {
                 unknown_pred = Some(format!("{pred:?}"));
                 false
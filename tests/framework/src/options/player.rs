@@ -1,6 +1,7 @@
 use crate::backends::{FontRendererKind, TestAudioBackend};
 use crate::environment::{Environment, RenderInterface};
 use crate::options::RenderOptions;
+use ruffle_core::backend::time::VirtualTimeBackend;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::{PlayerBuilder, PlayerMode, PlayerRuntime};
 use ruffle_render::backend::{RenderBackend, ViewportDimensions};
@@ -20,6 +21,7 @@ pub struct PlayerOptions {
     mode: Option<PlayerMode>,
     with_default_font: bool,
     device_font_renderer: FontRendererKind,
+    with_virtual_time: bool,
 }
 
 impl PlayerOptions {
@@ -43,6 +45,10 @@ impl PlayerOptions {
             .with_player_mode(self.mode.unwrap_or(PlayerMode::Debug))
             .with_default_font(self.with_default_font);
 
+        if self.with_virtual_time {
+            player_builder = player_builder.with_time(VirtualTimeBackend::default());
+        }
+
         if self.with_video {
             #[cfg(feature = "ruffle_video_external")]
             {
@@ -20,6 +20,7 @@ use anyhow::{Result, anyhow};
 use ruffle_core::FloatDuration;
 use ruffle_core::backend::navigator::NullExecutor;
 use ruffle_core::limits::ExecutionLimit;
+use ruffle_core::save_state::PlayerState;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::{Player, PlayerBuilder};
 use ruffle_input_format::InputInjector;
@@ -53,6 +54,7 @@ pub struct TestRunner {
     render_interface: Option<Box<dyn RenderInterface>>,
     images: HashMap<String, ImageComparison>,
     audio_assertions: HashMap<String, AudioAssertion>,
+    save_states: HashMap<String, PlayerState>,
     remaining_iterations: u32,
     current_iteration: u32,
     preloaded: bool,
@@ -112,7 +114,11 @@ impl TestRunner {
 
         let render_interface = if let Some((interface, backend)) = renderer {
             builder = builder.with_boxed_renderer(backend);
-            Some(interface)
+            // Expected images are checked against the GPU renderers, so the software
+            // renderer only compares images of tests requiring it.
+            (interface.renderer_kind() != "software"
+                || test.options.required_features.software_renderer())
+            .then_some(interface)
         } else {
             None
         };
@@ -151,6 +157,7 @@ impl TestRunner {
             fs_commands,
             images,
             audio_assertions: test.options.audio_assertions.clone(),
+            save_states: HashMap::new(),
             remaining_iterations,
             current_iteration: 0,
             options: test.options.clone(),
@@ -238,6 +245,11 @@ impl TestRunner {
             self.player.lock().unwrap().run_frame();
             self.player.lock().unwrap().update_timers(self.frame_time);
             self.player.lock().unwrap().audio_mut().tick();
+            self.player
+                .lock()
+                .unwrap()
+                .time_mut()
+                .advance(self.frame_time.to_std());
         }
         self.remaining_iterations -= 1;
         self.current_iteration += 1;
@@ -275,6 +287,20 @@ impl TestRunner {
                         ));
                     }
                 }
+                FsCommand::SaveState(name) => {
+                    let state = self.player.lock().unwrap().save_state()?;
+                    // Go through the serialized form, as a frontend storing it would.
+                    let state = PlayerState::from_bytes(&state.to_bytes())?;
+                    self.save_states.insert(name, state);
+                }
+                FsCommand::LoadState(name) => {
+                    let Some(state) = self.save_states.get(&name) else {
+                        return Err(anyhow!(
+                            "Encountered fscommand to load state '{name}', but it was never saved"
+                        ));
+                    };
+                    self.player.lock().unwrap().load_state(state)?;
+                }
             }
         }
 
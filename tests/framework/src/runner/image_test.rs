@@ -44,7 +44,14 @@ pub fn capture_and_compare_image(
 
     let ruffle_expected_path = base_path.join(format!("{name}.ruffle.png"))?;
 
-    let diff = test(&image_comparison, name, &actual_image, expected_image)?;
+    let renderer = render_interface.renderer_kind();
+    let diff = test(
+        &image_comparison,
+        name,
+        renderer,
+        &actual_image,
+        expected_image,
+    )?;
     let (failure, failure_name) = match (diff, image_comparison.known_failure) {
         (None, false) => {
             return if ruffle_expected_path.exists()? {
@@ -76,7 +83,13 @@ pub fn capture_and_compare_image(
                 ));
             };
 
-            if let Some(diff) = test(&image_comparison, &ruffle_name, &actual_image, image)? {
+            if let Some(diff) = test(
+                &image_comparison,
+                &ruffle_name,
+                renderer,
+                &actual_image,
+                image,
+            )? {
                 (diff, Cow::Owned(ruffle_name))
             } else {
                 return Ok(());
@@ -114,6 +127,7 @@ pub fn capture_and_compare_image(
 fn test(
     comparison: &ImageComparison,
     name: &str,
+    renderer: &str,
     actual_image: &image::RgbaImage,
     expected_image: image::RgbaImage,
 ) -> anyhow::Result<Option<ImageDiff>> {
@@ -145,7 +159,7 @@ fn test(
         let filter_passed = check
             .filter
             .as_ref()
-            .map(|f| f.evaluate())
+            .map(|f| f.evaluate_with_renderer(Some(renderer)))
             .unwrap_or(Ok(true))?;
         if !filter_passed {
             println!("{check_name} skipped: Filtered out.");
@@ -8,7 +8,7 @@ use crate::util::read_bytes;
 use anyhow::{Result, anyhow};
 use ruffle_core::font::{FontQuery, FontType};
 use ruffle_core::tag_utils::SwfMovie;
-use ruffle_input_format::InputInjector;
+use ruffle_input_format::{InputInjector, validate_event};
 use ruffle_socket_format::SocketEvent;
 use vfs::VfsPath;
 
@@ -76,13 +76,12 @@ impl Test {
 
     pub fn movie(&self) -> Result<SwfMovie> {
         let data = read_bytes(&self.swf_path)?;
-        let movie = SwfMovie::from_data(
-            &data,
-            format!("file://{}", self.swf_path.as_str()),
-            None,
-            None,
-        )
-        .map_err(|e| anyhow!("Error parsing SWF: {e}"))?;
+        let url = match &self.options.url {
+            Some(url) => url.clone(),
+            None => format!("file://{}", self.swf_path.as_str()),
+        };
+        let movie = SwfMovie::from_data(&data, url, None, None)
+            .map_err(|e| anyhow!("Error parsing SWF: {e}"))?;
         Ok(movie)
     }
 
@@ -98,12 +97,17 @@ impl Test {
     }
 
     fn input_injector(&self) -> Result<InputInjector> {
-        Ok(if self.input_path.is_file()? {
-            InputInjector::from_reader(&read_bytes(&self.input_path)?[..])
-                .map_err(|e| anyhow!("Error reading {}: {e}", self.input_path.as_str()))?
-        } else {
-            InputInjector::empty()
-        })
+        if !self.input_path.is_file()? {
+            return Ok(InputInjector::empty());
+        }
+
+        let injector = InputInjector::from_reader(&read_bytes(&self.input_path)?[..])
+            .map_err(|e| anyhow!("Error reading {}: {e}", self.input_path.as_str()))?;
+        for event in injector.events() {
+            validate_event(event)
+                .map_err(|e| anyhow!("Invalid event in {}: {e}", self.input_path.as_str()))?;
+        }
+        Ok(injector)
     }
 
     pub fn fonts(&self) -> Result<HashMap<FontQuery, Font>> {
@@ -82,6 +82,11 @@ impl InputInjector {
         }
     }
 
+    /// All the events to inject, including the ones already injected.
+    pub fn events(&self) -> &[AutomatedEvent] {
+        &self.items
+    }
+
     /// Run the next frame's worth of events.
     pub fn next<Sink>(&mut self, mut event_sink: Sink)
     where
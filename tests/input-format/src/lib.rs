@@ -1,5 +1,9 @@
 mod format;
 mod injector;
+#[cfg(feature = "player")]
+mod player;
 
 pub use format::{AutomatedEvent, AutomatedKey, MouseButton, MouseCursor, TextControlCode};
 pub use injector::{InputInjector, MouseButtons};
+#[cfg(feature = "player")]
+pub use player::{InjectError, automated_key_to_descriptor, inject_event, validate_event};
@@ -0,0 +1,198 @@
+use ruffle_core::{PauseReason, StepKind};
+use ruffle_test_framework::environment::Environment;
+use ruffle_test_framework::options::TestOptions;
+use ruffle_test_framework::runner::TestStatus;
+use ruffle_test_framework::test::Test;
+use ruffle_test_framework::vfs::{PhysicalFS, VfsPath};
+use std::thread::sleep;
+
+pub fn debugger_avm2(environment: &impl Environment) -> Result<(), libtest_mimic::Failed> {
+    // Break in a function compiled with debugging information, and check what
+    // was recorded about the stack at that point.
+    let test = &Test::from_options(
+        TestOptions {
+            num_ticks: Some(2),
+            ..Default::default()
+        },
+        VfsPath::new(PhysicalFS::new("tests/swfs/avm2/debugger/")),
+        "debugger_avm2".to_string(),
+    )?;
+    let mut runner = test.create_test_runner(environment)?;
+
+    {
+        // Debugging information is only kept for code that first runs after
+        // the debugger is attached.
+        let mut player = runner.player().lock().unwrap();
+        let debugger = player.avm2_debugger();
+        debugger.attach();
+        debugger.add_breakpoint("Test.as", 15);
+        debugger.add_watch("sum");
+        debugger.add_watch("count");
+        debugger.add_watch("this.items[1]");
+        debugger.add_watch("this.items.length");
+        debugger.add_watch("missing");
+    }
+
+    let mut checked = false;
+
+    loop {
+        match runner.tick()? {
+            TestStatus::Continue => {}
+            TestStatus::Sleep(duration) => sleep(duration),
+            TestStatus::Finished => break,
+        }
+
+        let mut player = runner.player().lock().unwrap();
+        let debugger = player.avm2_debugger();
+        let Some(state) = debugger.paused_state() else {
+            continue;
+        };
+        assert!(!checked, "the breakpoint should only be hit once");
+        checked = true;
+
+        assert_eq!(state.reason, PauseReason::Breakpoint);
+
+        let frames: Vec<_> = state
+            .frames
+            .iter()
+            .map(|frame| (frame.function.as_str(), frame.line))
+            .collect();
+        assert_eq!(frames, [("Test/add", Some(15)), ("Test", Some(9))]);
+        assert!(
+            state.frames[0]
+                .file
+                .as_deref()
+                .is_some_and(|file| file.ends_with("Test.as"))
+        );
+
+        let locals: Vec<_> = state.frames[0]
+            .locals
+            .iter()
+            .map(|local| (local.name.as_str(), local.value.as_str()))
+            .collect();
+        assert_eq!(
+            locals,
+            [
+                ("this", "[object Test]"),
+                ("a", "1"),
+                ("b", "2"),
+                ("sum", "3")
+            ]
+        );
+        assert!(
+            state.frames[1]
+                .locals
+                .iter()
+                .any(|local| local.name == "this" && local.value == "[object Test]")
+        );
+
+        let watches: Vec<_> = state
+            .watches
+            .iter()
+            .map(|watch| (watch.expression.as_str(), watch.value.clone()))
+            .collect();
+        assert_eq!(
+            watches,
+            [
+                ("sum", Ok("3".to_string())),
+                ("count", Ok("3".to_string())),
+                ("this.items[1]", Ok("\"second\"".to_string())),
+                (
+                    "this.items.length",
+                    Err("length is a getter, which isn't evaluated".to_string())
+                ),
+                ("missing", Err("missing is not defined".to_string())),
+            ]
+        );
+
+        // Stepping out walks the recorded lines back into the constructor.
+        debugger.step(StepKind::Out);
+        let frame = debugger
+            .paused_state()
+            .and_then(|state| state.current_frame())
+            .expect("the constructor should have been recorded");
+        assert_eq!((frame.function.as_str(), frame.line), ("Test", Some(10)));
+
+        debugger.resume();
+        assert!(!debugger.is_paused());
+    }
+
+    assert!(checked, "the breakpoint should have been hit");
+
+    Ok(())
+}
+
+pub fn debugger_enable_tag_avm2(
+    environment: &impl Environment,
+) -> Result<(), libtest_mimic::Failed> {
+    // Movies with an `EnableDebugger2` tag keep their debugging information
+    // even if the debugger is attached after their code first ran.
+    let test = &Test::from_options(
+        TestOptions {
+            num_ticks: Some(4),
+            ..Default::default()
+        },
+        VfsPath::new(PhysicalFS::new("tests/swfs/avm2/debugger_enable_tag/")),
+        "debugger_enable_tag_avm2".to_string(),
+    )?;
+    let mut runner = test.create_test_runner(environment)?;
+
+    let mut checked = false;
+    let mut ticks = 0;
+
+    loop {
+        match runner.tick()? {
+            TestStatus::Continue => {}
+            TestStatus::Sleep(duration) => sleep(duration),
+            TestStatus::Finished => break,
+        }
+        ticks += 1;
+
+        let mut player = runner.player().lock().unwrap();
+        let debugger = player.avm2_debugger();
+        if ticks == 1 {
+            // `add` has already run once, from the constructor.
+            debugger.attach();
+            debugger.add_breakpoint("Test.as", 22);
+            continue;
+        }
+
+        let Some(state) = debugger.paused_state() else {
+            continue;
+        };
+        assert!(!checked, "the breakpoint should only be hit once");
+        checked = true;
+
+        let frames: Vec<_> = state
+            .frames
+            .iter()
+            .map(|frame| (frame.function.as_str(), frame.line))
+            .collect();
+        assert_eq!(
+            frames[..2],
+            [("Test/add", Some(22)), ("Test/onEnterFrame", Some(16))]
+        );
+
+        let locals: Vec<_> = state.frames[0]
+            .locals
+            .iter()
+            .map(|local| (local.name.as_str(), local.value.as_str()))
+            .collect();
+        assert_eq!(
+            locals,
+            [
+                ("this", "[object Test]"),
+                ("a", "3"),
+                ("b", "4"),
+                ("sum", "7")
+            ]
+        );
+
+        debugger.resume();
+        assert!(!debugger.is_paused());
+    }
+
+    assert!(checked, "the breakpoint should have been hit");
+
+    Ok(())
+}
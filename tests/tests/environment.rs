@@ -49,15 +49,20 @@ impl NativeEnvironment {
 }
 
 impl Environment for NativeEnvironment {
-    #[cfg(feature = "imgtests")]
+    #[cfg(any(feature = "imgtests", feature = "software_renderer"))]
     fn is_render_supported(
         &self,
         _requirements: &ruffle_test_framework::options::RenderOptions,
     ) -> bool {
-        self.descriptors.is_some()
+        #[cfg(feature = "imgtests")]
+        if self.descriptors.is_some() {
+            return true;
+        }
+        cfg!(feature = "software_renderer")
     }
 
-    #[cfg(feature = "imgtests")]
+    /// Prefers wgpu when available, falling back to the software renderer otherwise.
+    #[cfg(any(feature = "imgtests", feature = "software_renderer"))]
     fn create_renderer(
         &self,
         width: u32,
@@ -66,7 +71,12 @@ impl Environment for NativeEnvironment {
         Box<dyn ruffle_test_framework::environment::RenderInterface>,
         Box<dyn ruffle_test_framework::environment::RenderBackend>,
     )> {
-        renderer::create_pair(self, width, height)
+        let pair = None;
+        #[cfg(feature = "imgtests")]
+        let pair = pair.or_else(|| renderer::create_pair(self, width, height));
+        #[cfg(feature = "software_renderer")]
+        let pair = pair.or_else(|| Some(software_renderer::create_pair(width, height)));
+        pair
     }
 
     fn compile_mode(&self) -> CompileMode {
@@ -74,6 +84,42 @@ impl Environment for NativeEnvironment {
     }
 }
 
+#[cfg(feature = "software_renderer")]
+mod software_renderer {
+    use image::RgbaImage;
+    use ruffle_render_software::SoftwareRenderBackend;
+    use ruffle_test_framework::environment::{RenderBackend, RenderInterface};
+    use std::any::Any;
+
+    pub struct SoftwareRenderInterface;
+
+    pub fn create_pair(
+        width: u32,
+        height: u32,
+    ) -> (Box<dyn RenderInterface>, Box<dyn RenderBackend>) {
+        (
+            Box::new(SoftwareRenderInterface),
+            Box::new(SoftwareRenderBackend::new(width, height)),
+        )
+    }
+
+    impl RenderInterface for SoftwareRenderInterface {
+        fn name(&self) -> String {
+            "software".to_string()
+        }
+
+        fn renderer_kind(&self) -> &'static str {
+            "software"
+        }
+
+        fn capture(&self, backend: &mut dyn RenderBackend) -> RgbaImage {
+            let renderer = <dyn Any>::downcast_mut::<SoftwareRenderBackend>(backend).unwrap();
+
+            renderer.capture_frame().expect("Failed to capture image")
+        }
+    }
+}
+
 #[cfg(feature = "imgtests")]
 mod renderer {
     use super::NativeEnvironment;
@@ -118,6 +164,10 @@ mod renderer {
             self.name.clone()
         }
 
+        fn renderer_kind(&self) -> &'static str {
+            "wgpu"
+        }
+
         fn capture(&self, backend: &mut dyn RenderBackend) -> RgbaImage {
             let renderer =
                 <dyn Any>::downcast_mut::<WgpuRenderBackend<TextureTarget>>(backend).unwrap();
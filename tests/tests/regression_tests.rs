@@ -2,6 +2,7 @@
 //!
 //! Trace output can be compared with correct output from the official Flash Player.
 
+use crate::debugger::{debugger_avm2, debugger_enable_tag_avm2};
 use crate::environment::NativeEnvironment;
 use crate::external_interface::tests::{external_interface_avm1, external_interface_avm2};
 use crate::shared_object::{shared_object_avm1, shared_object_avm2, shared_object_self_ref_avm1};
@@ -19,6 +20,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::thread::sleep;
 
+mod debugger;
 mod environment;
 mod external_interface;
 mod shared_object;
@@ -107,6 +109,16 @@ fn main() {
         external_interface_avm2(&*env_clone)
     }));
 
+    let env_clone = env.clone();
+    runner.with_additional_test(Trial::test("debugger_avm2", move || {
+        debugger_avm2(&*env_clone)
+    }));
+
+    let env_clone = env.clone();
+    runner.with_additional_test(Trial::test("debugger_enable_tag_avm2", move || {
+        debugger_enable_tag_avm2(&*env_clone)
+    }));
+
     let conclusion = runner.run();
 
     // Workaround for shutdown races on slow / software GPU drivers; see
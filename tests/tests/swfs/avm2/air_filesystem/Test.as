@@ -0,0 +1,160 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.FileListEvent;
+    import flash.events.IOErrorEvent;
+    import flash.events.ProgressEvent;
+    import flash.filesystem.File;
+    import flash.filesystem.FileMode;
+    import flash.filesystem.FileStream;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var dir:File = File.applicationStorageDirectory;
+            trace("// applicationStorageDirectory");
+            trace(dir.url, dir.nativePath, dir.exists, dir.isDirectory, dir.parent);
+
+            var file:File = dir.resolvePath("saves/../saves/./slot1.dat");
+            trace("// resolvePath");
+            trace(file.url, file.nativePath, file.name, file.extension, file.type, file.exists);
+            trace(file.parent.url, new File(file.nativePath).url);
+            trace(dir.resolvePath("../../etc/passwd").nativePath);
+            trace(File.applicationDirectory.url, File.documentsDirectory.url);
+
+            trace("// FileStream.open WRITE");
+            var stream:FileStream = new FileStream();
+            stream.open(file, FileMode.WRITE);
+            stream.writeUTF("hello");
+            stream.writeInt(42);
+            stream.close();
+            trace(file.exists, file.isDirectory, file.size, file.parent.isDirectory);
+
+            trace("// FileStream writes before close");
+            var pending:File = dir.resolvePath("pending.dat");
+            var writer:FileStream = new FileStream();
+            writer.open(pending, FileMode.WRITE);
+            writer.writeUTF("abc");
+            trace(pending.exists, pending.size);
+            var reader:FileStream = new FileStream();
+            reader.open(pending, FileMode.READ);
+            trace(reader.readUTF());
+            reader.close();
+            writer.writeByte(1);
+            trace(pending.size);
+            writer.close();
+            pending.deleteFile();
+
+            trace("// FileStream.open READ");
+            stream.open(file, FileMode.READ);
+            trace(stream.bytesAvailable, stream.readUTF(), stream.readInt(), stream.bytesAvailable);
+            try {
+                stream.writeByte(1);
+            } catch (e:Error) {
+                trace(e);
+            }
+            stream.close();
+
+            trace("// FileStream.open APPEND");
+            stream.open(file, FileMode.APPEND);
+            stream.writeByte(7);
+            stream.close();
+            trace(file.size);
+
+            trace("// FileStream.open UPDATE");
+            stream.open(file, FileMode.UPDATE);
+            stream.position = 7;
+            stream.writeInt(1000);
+            stream.position = 0;
+            trace(stream.readUTF(), stream.readInt(), stream.readByte());
+            stream.close();
+
+            trace("// Errors");
+            try {
+                new File("relative/path");
+            } catch (e:Error) {
+                trace(e);
+            }
+            try {
+                stream.open(dir.resolvePath("missing.txt"), FileMode.READ);
+            } catch (e:Error) {
+                trace(e);
+            }
+            try {
+                File.applicationDirectory.resolvePath("data").createDirectory();
+            } catch (e:Error) {
+                trace(e);
+            }
+            try {
+                stream.readByte();
+            } catch (e:Error) {
+                trace(e);
+            }
+
+            trace("// getDirectoryListing");
+            dir.resolvePath("saves/sub").createDirectory();
+            trace(names(dir.resolvePath("saves").getDirectoryListing()));
+
+            trace("// copyTo / moveTo");
+            file.copyTo(dir.resolvePath("backup/slot1.dat"));
+            try {
+                file.copyTo(dir.resolvePath("backup/slot1.dat"));
+            } catch (e:Error) {
+                trace(e);
+            }
+            file.moveTo(dir.resolvePath("backup/slot2.dat"));
+            trace(file.exists, names(dir.resolvePath("backup").getDirectoryListing()));
+            try {
+                dir.resolvePath("backup").deleteDirectory();
+            } catch (e:Error) {
+                trace(e);
+            }
+
+            trace("// load");
+            var loaded:File = dir.resolvePath("backup/slot1.dat");
+            loaded.addEventListener(Event.COMPLETE, function(e:Event):void {
+                trace("load complete", loaded.data.length);
+            });
+            loaded.load();
+
+            trace("// Asynchronous operations");
+            var async:FileStream = new FileStream();
+            async.openAsync(dir.resolvePath("backup/slot2.dat"), FileMode.READ);
+            async.addEventListener(ProgressEvent.PROGRESS, function(e:ProgressEvent):void {
+                trace("progress", e.bytesLoaded, e.bytesTotal);
+            });
+            async.addEventListener(Event.COMPLETE, function(e:Event):void {
+                trace("complete", async.readUTF());
+                async.close();
+            });
+            async.addEventListener(Event.CLOSE, function(e:Event):void {
+                trace("close");
+            });
+
+            var missing:FileStream = new FileStream();
+            missing.openAsync(dir.resolvePath("missing.txt"), FileMode.READ);
+            missing.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+                trace("ioError", e.errorID);
+            });
+
+            dir.getDirectoryListingAsync();
+            dir.addEventListener(FileListEvent.DIRECTORY_LISTING, function(e:FileListEvent):void {
+                trace("directoryListing", names(e.files));
+            });
+
+            var sub:File = dir.resolvePath("saves/sub");
+            sub.deleteDirectoryAsync();
+            sub.addEventListener(Event.COMPLETE, function(e:Event):void {
+                trace("deleted", sub.exists);
+            });
+            trace("// Asynchronous operations started");
+        }
+
+        private static function names(files:Array):String {
+            var result:Array = [];
+            for each (var file:File in files) {
+                result.push(file.name + (file.isDirectory ? "/" : ""));
+            }
+            return result.join(",");
+        }
+    }
+}
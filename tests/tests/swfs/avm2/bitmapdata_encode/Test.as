@@ -0,0 +1,90 @@
+package {
+	import flash.display.Bitmap;
+	import flash.display.BitmapData;
+	import flash.display.JPEGEncoderOptions;
+	import flash.display.Loader;
+	import flash.display.PNGEncoderOptions;
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.geom.Rectangle;
+	import flash.utils.ByteArray;
+
+	public class Test extends Sprite {
+		private var source:BitmapData;
+
+		public function Test() {
+			source = new BitmapData(4, 3, true, 0xFFFF0000);
+			source.setPixel32(1, 0, 0xFF00FF00);
+			source.setPixel32(2, 1, 0xFF0000FF);
+			source.setPixel32(3, 2, 0x00000000);
+
+			var png:ByteArray = source.encode(source.rect, new PNGEncoderOptions());
+			trace("PNG signature: " + hex(png, 8));
+			load("PNG", png, source.rect);
+
+			var fast:ByteArray = new ByteArray();
+			fast.writeUTFBytes("prefix");
+			var result:ByteArray = source.encode(new Rectangle(1, 0, 10, 2), new PNGEncoderOptions(true), fast);
+			trace("Returns the given ByteArray: " + (result == fast));
+			trace("Written after prefix: " + hex(fast, 6, 6));
+			var cropped:ByteArray = new ByteArray();
+			fast.position = 6;
+			fast.readBytes(cropped);
+			load("Cropped fast PNG", cropped, new Rectangle(1, 0, 3, 2));
+
+			var solid:BitmapData = new BitmapData(8, 8, false, 0x336699);
+			var jpeg:ByteArray = solid.encode(solid.rect, new JPEGEncoderOptions(100));
+			trace("JPEG signature: " + hex(jpeg, 2));
+			loadJPEG(jpeg);
+
+			try {
+				source.encode(source.rect, {});
+			} catch (e:Error) {
+				trace("Unknown compressor: " + e);
+			}
+		}
+
+		private function hex(bytes:ByteArray, length:int, offset:int = 0):String {
+			var parts:Array = [];
+			for (var i:int = offset; i < offset + length; i++) {
+				var part:String = bytes[i].toString(16);
+				parts.push(part.length == 1 ? "0" + part : part);
+			}
+			return parts.join(" ");
+		}
+
+		private function load(name:String, bytes:ByteArray, rect:Rectangle):void {
+			var loader:Loader = new Loader();
+			loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(e:Event):void {
+				var loaded:BitmapData = Bitmap(loader.content).bitmapData;
+				trace(name + ": " + loaded.width + "x" + loaded.height + ", transparent: " + loaded.transparent);
+				var matches:Boolean = true;
+				for (var y:int = 0; y < loaded.height; y++) {
+					for (var x:int = 0; x < loaded.width; x++) {
+						if (loaded.getPixel32(x, y) != source.getPixel32(rect.x + x, rect.y + y)) {
+							trace("Mismatch at " + x + "," + y + ": " + loaded.getPixel32(x, y).toString(16));
+							matches = false;
+						}
+					}
+				}
+				trace(name + " pixels match: " + matches);
+			});
+			loader.loadBytes(bytes);
+		}
+
+		private function loadJPEG(bytes:ByteArray):void {
+			var loader:Loader = new Loader();
+			loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(e:Event):void {
+				var loaded:BitmapData = Bitmap(loader.content).bitmapData;
+				trace("JPEG: " + loaded.width + "x" + loaded.height);
+				var color:uint = loaded.getPixel(4, 4);
+				trace("JPEG close to source: " + (near(color >> 16, 0x33) && near((color >> 8) & 0xFF, 0x66) && near(color & 0xFF, 0x99)));
+			});
+			loader.loadBytes(bytes);
+		}
+
+		private function near(a:int, b:int):Boolean {
+			return Math.abs(a - b) <= 4;
+		}
+	}
+}
@@ -0,0 +1,63 @@
+package {
+	import flash.display.Bitmap;
+	import flash.display.BitmapData;
+	import flash.display.JPEGXREncoderOptions;
+	import flash.display.Loader;
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.utils.ByteArray;
+
+	public class Test extends Sprite {
+		public function Test() {
+			var opaque:BitmapData = new BitmapData(5, 4, false, 0x336699);
+			opaque.setPixel(0, 0, 0xFF0000);
+			opaque.setPixel(2, 1, 0x123456);
+			opaque.setPixel(4, 3, 0x00FF00);
+			var lossless:ByteArray = opaque.encode(opaque.rect, new JPEGXREncoderOptions(0));
+			trace("JPEG XR signature: " + hex(lossless, 4));
+			load("Lossless opaque", lossless, opaque, 0);
+
+			var transparent:BitmapData = new BitmapData(3, 3, true, 0xFFFF0000);
+			transparent.setPixel32(1, 1, 0x00000000);
+			transparent.setPixel32(2, 0, 0xFF00FF00);
+			load("Lossless transparent", transparent.encode(transparent.rect, new JPEGXREncoderOptions(0, "4:4:4")), transparent, 0);
+
+			var solid:BitmapData = new BitmapData(16, 16, false, 0x336699);
+			load("Default options", solid.encode(solid.rect, new JPEGXREncoderOptions()), solid, 4);
+			load("4:2:2 with trimmed bits", solid.encode(solid.rect, new JPEGXREncoderOptions(20, "4:2:2", 3)), solid, 4);
+		}
+
+		private function hex(bytes:ByteArray, length:int):String {
+			var parts:Array = [];
+			for (var i:int = 0; i < length; i++) {
+				var part:String = bytes[i].toString(16);
+				parts.push(part.length == 1 ? "0" + part : part);
+			}
+			return parts.join(" ");
+		}
+
+		private function load(name:String, bytes:ByteArray, source:BitmapData, tolerance:int):void {
+			var loader:Loader = new Loader();
+			loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(e:Event):void {
+				var loaded:BitmapData = Bitmap(loader.content).bitmapData;
+				trace(name + ": " + loaded.width + "x" + loaded.height);
+				var matches:Boolean = true;
+				for (var y:int = 0; y < loaded.height; y++) {
+					for (var x:int = 0; x < loaded.width; x++) {
+						var actual:uint = loaded.getPixel32(x, y);
+						var expected:uint = source.getPixel32(x, y);
+						for (var shift:int = 0; shift < 32; shift += 8) {
+							if (Math.abs(int((actual >>> shift) & 0xFF) - int((expected >>> shift) & 0xFF)) > tolerance) {
+								trace("Mismatch at " + x + "," + y + ": " + actual.toString(16));
+								matches = false;
+								break;
+							}
+						}
+					}
+				}
+				trace(name + " pixels match: " + matches);
+			});
+			loader.loadBytes(bytes);
+		}
+	}
+}
@@ -0,0 +1,18 @@
+package {
+    import flash.display.MovieClip;
+
+    public class Test extends MovieClip {
+        public var count:int = 3;
+        public var items:Array = ["first", "second"];
+
+        public function Test() {
+            var total:int = add(1, 2);
+            trace("total: " + total);
+        }
+
+        private function add(a:int, b:int):int {
+            var sum:int = a + b;
+            return sum;
+        }
+    }
+}
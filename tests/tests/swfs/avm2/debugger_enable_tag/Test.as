@@ -0,0 +1,26 @@
+package {
+    import flash.display.MovieClip;
+    import flash.events.Event;
+
+    public class Test extends MovieClip {
+        private var frames:int = 0;
+
+        public function Test() {
+            trace("total: " + add(1, 2));
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private function onEnterFrame(e:Event):void {
+            frames++;
+            if (frames == 2) {
+                trace("total: " + add(3, 4));
+                removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+            }
+        }
+
+        private function add(a:int, b:int):int {
+            var sum:int = a + b;
+            return sum;
+        }
+    }
+}
@@ -0,0 +1,61 @@
+// Two postcards flipped with rotationY under a PerspectiveProjection.
+// The left one shows its front, the right one is flipped past 90 degrees so
+// it shows its back, and goes through the cacheAsBitmap and filter path,
+// with a filter turning its blue back green.
+
+package {
+    import flash.display.Shape;
+    import flash.display.Sprite;
+    import flash.filters.ColorMatrixFilter;
+    import flash.geom.PerspectiveProjection;
+    import flash.geom.Point;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var container:Sprite = new Sprite();
+            var projection:PerspectiveProjection = new PerspectiveProjection();
+            projection.fieldOfView = 60;
+            projection.projectionCenter = new Point(275, 200);
+            container.transform.perspectiveProjection = projection;
+            addChild(container);
+
+            container.addChild(postcard(140, 40));
+
+            var flipped:Sprite = postcard(410, 140);
+            flipped.cacheAsBitmap = true;
+            flipped.filters = [new ColorMatrixFilter([
+                0, 0, 0, 0, 0,
+                0, 0, 1, 0, 0,
+                0, 0, 0, 0, 0,
+                0, 0, 0, 1, 0
+            ])];
+            container.addChild(flipped);
+        }
+
+        private static function postcard(x:Number, rotationY:Number):Sprite {
+            var card:Sprite = new Sprite();
+            card.x = x;
+            card.y = 200;
+
+            var front:Shape = side(0xFF0000);
+            var back:Shape = side(0x0000FF);
+            // The back is mirrored so it reads the right way once flipped.
+            back.scaleX = -1;
+            front.visible = rotationY < 90;
+            back.visible = !front.visible;
+            card.addChild(front);
+            card.addChild(back);
+
+            card.rotationY = rotationY;
+            return card;
+        }
+
+        private static function side(color:uint):Shape {
+            var shape:Shape = new Shape();
+            shape.graphics.beginFill(color);
+            shape.graphics.drawRect(-80, -50, 160, 100);
+            shape.graphics.endFill();
+            return shape;
+        }
+    }
+}
@@ -0,0 +1,67 @@
+package {
+    import flash.display.*;
+    import flash.geom.*;
+
+    public class Test extends MovieClip {
+        public function Test() {
+            super();
+
+            testProperties();
+            trace("");
+
+            testProjection();
+        }
+
+        private function testProperties() : void {
+            trace("// testProperties");
+
+            var s: Sprite = new Sprite();
+            addChild(s);
+            trace("defaults", s.z, s.rotationX, s.rotationY, s.rotationZ, s.scaleZ);
+            trace("matrix3D", s.transform.matrix3D);
+
+            s.x = 10;
+            s.z = 5;
+            trace("matrix", s.transform.matrix);
+            trace("rawData", s.transform.matrix3D.rawData);
+
+            s.y = 20;
+            trace("rawData", s.transform.matrix3D.rawData);
+
+            s.scaleZ = 2;
+            trace("scaleZ", s.scaleZ);
+
+            s.rotationX = 450;
+            trace("rotationX", Math.round(s.rotationX));
+            trace("x, y, z", s.x, s.y, Math.round(s.z));
+
+            s.transform.matrix3D = null;
+            trace("after reset", s.x, s.y, s.z, s.rotationX, s.transform.matrix);
+        }
+
+        private function testProjection() : void {
+            trace("// testProjection");
+
+            var container: Sprite = new Sprite();
+            addChild(container);
+            var projection: PerspectiveProjection = new PerspectiveProjection();
+            projection.fieldOfView = 90;
+            projection.projectionCenter = new Point(100, 100);
+            container.transform.perspectiveProjection = projection;
+
+            var child: Sprite = new Sprite();
+            container.addChild(child);
+
+            var p: Point = child.local3DToGlobal(new Vector3D(300, 100, 0));
+            trace("flat", Math.round(p.x), Math.round(p.y));
+
+            // With a field of view of 90 degrees, the focal length is half the stage width.
+            child.z = 275;
+            p = child.local3DToGlobal(new Vector3D(300, 100, 0));
+            trace("local3DToGlobal", Math.round(p.x), Math.round(p.y));
+
+            var v: Vector3D = child.globalToLocal3D(new Point(200, 100));
+            trace("globalToLocal3D", Math.round(v.x), Math.round(v.y), v.z);
+        }
+    }
+}
@@ -0,0 +1,22 @@
+package {
+import flash.display.Sprite;
+import flash.events.Event;
+
+public class Pic extends Sprite {
+    private var tracedEnterFrame:Boolean = false;
+
+    public function Pic() {
+        graphics.beginFill(0xFF0000);
+        graphics.drawRect(0, 0, 10, 10);
+        graphics.endFill();
+        addEventListener(Event.ENTER_FRAME, onEnterFrame);
+    }
+
+    private function onEnterFrame(e:Event):void {
+        if (!tracedEnterFrame) {
+            tracedEnterFrame = true;
+            trace("pic enterFrame, on stage: " + (stage != null));
+        }
+    }
+}
+}
@@ -0,0 +1,44 @@
+package {
+import flash.display.DisplayObject;
+import flash.display.Sprite;
+import flash.events.MouseEvent;
+import flash.geom.Point;
+import flash.text.TextField;
+import flash.text.TextFormat;
+
+public class Test extends Sprite {
+    public function Test() {
+        var text:TextField = new TextField();
+        text.x = 10;
+        text.y = 10;
+        text.width = 200;
+        text.height = 100;
+        text.multiline = true;
+        text.defaultTextFormat = new TextFormat("TestFont", 10);
+        addChild(text);
+
+        text.htmlText = "<img src='Pic' id='pic' width='40' height='15' hspace='0' vspace='0'>ab<br>ab<br>ab";
+
+        var pic:DisplayObject = text.getImageReference("pic");
+        trace("pic is Pic: " + (pic is Pic));
+        trace("pic.parent == text: " + (pic.parent == text));
+        trace("pic.stage == stage: " + (pic.stage == stage));
+        trace("pic.root == root: " + (pic.root == root));
+        trace("pic position: " + pic.x + ", " + pic.y);
+        trace("pic size: " + pic.width + ", " + pic.height);
+        trace("pic.getBounds(text): " + pic.getBounds(text));
+        trace("pic.localToGlobal(0, 0): " + pic.localToGlobal(new Point(0, 0)));
+        trace("missing image: " + text.getImageReference("missing"));
+
+        // The first two lines flow around the image, the third is below it.
+        trace("text: " + escape(text.text));
+        for each (var i:int in [0, 1, 3, 4, 6, 7]) {
+            trace("getCharBoundaries(" + i + "): " + text.getCharBoundaries(i));
+        }
+
+        text.addEventListener(MouseEvent.MOUSE_DOWN, function(e:MouseEvent):void {
+            trace("mouseDown target: " + e.target + ", currentTarget: " + e.currentTarget);
+        });
+    }
+}
+}
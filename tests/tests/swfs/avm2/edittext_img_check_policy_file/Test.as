@@ -0,0 +1,52 @@
+package {
+    import flash.display.BitmapData;
+    import flash.display.Loader;
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.text.TextField;
+
+    // Runs from http://www.example.com/test.swf, see test.toml.
+    // images.test serves a policy file granting access to www.example.com.
+    public class Test extends Sprite {
+        private var text:TextField = new TextField();
+        private var pending:int = 2;
+
+        public function Test() {
+            text.htmlText = '<img id="checked" src="http://images.test/checked.png" checkPolicyFile="true">' +
+                '<img id="unchecked" src="http://images.test/unchecked.png">';
+            addChild(text);
+
+            listen("checked");
+            listen("unchecked");
+        }
+
+        private function listen(id:String):void {
+            var loader:Loader = Loader(text.getImageReference(id));
+            loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(e:Event):void {
+                pending--;
+                if (pending == 0) {
+                    check("checked");
+                    check("unchecked");
+                }
+            });
+        }
+
+        private function check(id:String):void {
+            var loader:Loader = Loader(text.getImageReference(id));
+            trace("// " + id);
+            try {
+                trace("content: " + loader.content);
+            } catch (error:SecurityError) {
+                trace("content: SecurityError #" + error.errorID);
+            }
+
+            var target:BitmapData = new BitmapData(2, 2, false, 0xFFFFFF);
+            try {
+                target.draw(loader);
+                trace("draw: " + target.getPixel(0, 0).toString(16));
+            } catch (error:SecurityError) {
+                trace("draw: SecurityError #" + error.errorID);
+            }
+        }
+    }
+}
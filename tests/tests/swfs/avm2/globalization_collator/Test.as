@@ -0,0 +1,40 @@
+package {
+    import flash.display.Sprite;
+    import flash.globalization.Collator;
+    import flash.globalization.CollatorMode;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var sorting:Collator = new Collator("en-US");
+            trace(sorting.actualLocaleIDName, sorting.lastOperationStatus);
+            trace(sorting.ignoreCase, sorting.ignoreCharacterWidth, sorting.ignoreDiacritics, sorting.ignoreKanaType, sorting.ignoreSymbols, sorting.numericComparison);
+            trace(sorting.compare("a", "B"));
+            trace(sorting.compare("a", "A"));
+            trace(sorting.compare("résumé", "resume"));
+            trace(sorting.compare("file10", "file9"));
+            trace(sorting.equals("abc", "abc"));
+
+            var words:Array = ["banana", "Apple", "cherry", "apple", "Éclair", "eclair", "item 10", "item 9"];
+            words.sort(sorting.compare);
+            trace(words);
+
+            sorting.numericComparison = true;
+            trace(sorting.compare("file10", "file9"));
+            sorting.ignoreSymbols = true;
+            trace(sorting.compare("co-op", "coop"));
+
+            var matching:Collator = new Collator("fr-FR", CollatorMode.MATCHING);
+            trace(matching.ignoreCase, matching.ignoreCharacterWidth, matching.ignoreDiacritics, matching.ignoreKanaType, matching.ignoreSymbols, matching.numericComparison);
+            trace(matching.equals("RÉSUMÉ", "resume"));
+            trace(matching.equals("ｒｕｆｆｌｅ", "ruffle"));
+            trace(matching.equals("カタカナ", "かたかな"));
+            trace(matching.equals("ruffle", "flash"));
+
+            try {
+                new Collator("en-US", "bogus");
+            } catch (e:ArgumentError) {
+                trace("new Collator(\"en-US\", \"bogus\"): " + e.errorID);
+            }
+        }
+    }
+}
@@ -0,0 +1,51 @@
+package {
+    import flash.display.Sprite;
+    import flash.globalization.CurrencyFormatter;
+    import flash.globalization.CurrencyParseResult;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var cf:CurrencyFormatter = new CurrencyFormatter("en-US");
+            trace(cf.actualLocaleIDName, cf.lastOperationStatus);
+            trace(cf.currencyISOCode, cf.currencySymbol, cf.fractionalDigits);
+            trace(cf.positiveCurrencyFormat, cf.negativeCurrencyFormat);
+            trace(cf.format(1234.5));
+            trace(cf.format(1234.5, true));
+            trace(cf.format(-1234.5, true));
+
+            cf.negativeCurrencyFormat = 0;
+            trace(cf.format(-5, true));
+            cf.negativeCurrencyFormat = 15;
+            trace(cf.format(-5, true));
+            cf.positiveCurrencyFormat = 2;
+            trace(cf.format(5));
+            try {
+                cf.positiveCurrencyFormat = 4;
+            } catch (e:ArgumentError) {
+                trace("positiveCurrencyFormat = 4: " + e.errorID);
+            }
+
+            var result:CurrencyParseResult = cf.parse("$1,000.25");
+            trace(result.value, result.currencyString, cf.lastOperationStatus);
+            result = cf.parse("USD 12");
+            trace(result.value, result.currencyString, cf.lastOperationStatus);
+            result = cf.parse("($7.50)");
+            trace(result.value, result.currencyString, cf.lastOperationStatus);
+            result = cf.parse("seven dollars");
+            trace(result.value, result.currencyString, cf.lastOperationStatus);
+
+            trace(cf.formattingWithCurrencySymbolIsSafe("USD"), cf.formattingWithCurrencySymbolIsSafe("EUR"));
+            cf.setCurrency("EUR", "€");
+            trace(cf.currencyISOCode, cf.currencySymbol);
+            trace(cf.format(3, true));
+
+            var de:CurrencyFormatter = new CurrencyFormatter("de-DE");
+            trace(de.currencyISOCode, de.currencySymbol);
+            trace(de.format(1234.5, true));
+            trace(de.format(-1234.5, true));
+
+            var ja:CurrencyFormatter = new CurrencyFormatter("ja-JP");
+            trace(ja.format(1234.5));
+        }
+    }
+}
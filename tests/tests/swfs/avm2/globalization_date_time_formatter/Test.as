@@ -0,0 +1,61 @@
+package {
+    import flash.display.Sprite;
+    import flash.globalization.DateTimeFormatter;
+    import flash.globalization.DateTimeNameStyle;
+    import flash.globalization.DateTimeStyle;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var date:Date = new Date(Date.UTC(2001, 1, 3, 16, 5, 6, 789));
+
+            var f:DateTimeFormatter = new DateTimeFormatter("en-US");
+            trace(f.actualLocaleIDName, f.lastOperationStatus);
+            trace(f.getDateStyle(), f.getTimeStyle());
+            trace(f.getDateTimePattern());
+            trace(f.formatUTC(date));
+
+            f.setDateTimeStyles(DateTimeStyle.SHORT, DateTimeStyle.NONE);
+            trace(f.getDateTimePattern());
+            trace(f.formatUTC(date));
+            f.setDateTimeStyles(DateTimeStyle.NONE, DateTimeStyle.MEDIUM);
+            trace(f.formatUTC(date));
+
+            f.setDateTimePattern("yyyy-MM-dd'T'HH:mm:ss.SSS 'o''clock' QQQ");
+            trace(f.getDateStyle(), f.getTimeStyle());
+            trace(f.formatUTC(date));
+            f.setDateTimePattern("EEE d MMM yy, K:mm a, 'day' D, 'week' w");
+            trace(f.formatUTC(date));
+
+            trace(f.getFirstWeekday());
+            trace(f.getMonthNames());
+            trace(f.getMonthNames(DateTimeNameStyle.LONG_ABBREVIATION));
+            trace(f.getWeekdayNames(DateTimeNameStyle.SHORT_ABBREVIATION));
+
+            try {
+                f.setDateTimeStyles("bogus", DateTimeStyle.LONG);
+            } catch (e:ArgumentError) {
+                trace("setDateTimeStyles(\"bogus\", \"long\"): " + e.errorID);
+            }
+            try {
+                f.setDateTimeStyles(DateTimeStyle.CUSTOM, DateTimeStyle.LONG);
+            } catch (e:ArgumentError) {
+                trace("setDateTimeStyles(\"custom\", \"long\"): " + e.errorID);
+            }
+            try {
+                f.formatUTC(null);
+            } catch (e:TypeError) {
+                trace("formatUTC(null): " + e.errorID);
+            }
+
+            var de:DateTimeFormatter = new DateTimeFormatter("de-DE", DateTimeStyle.MEDIUM, DateTimeStyle.SHORT);
+            trace(de.formatUTC(date));
+            trace(de.getFirstWeekday());
+            trace(de.getWeekdayNames(DateTimeNameStyle.LONG_ABBREVIATION));
+
+            var ja:DateTimeFormatter = new DateTimeFormatter("ja-JP", DateTimeStyle.LONG, DateTimeStyle.NONE);
+            trace(ja.formatUTC(date));
+            trace(ja.getMonthNames(DateTimeNameStyle.SHORT_ABBREVIATION));
+            trace(ja.getWeekdayNames(DateTimeNameStyle.SHORT_ABBREVIATION));
+        }
+    }
+}
@@ -0,0 +1,38 @@
+package {
+    import flash.globalization.LocaleID;
+    import flash.display.Sprite;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var names:Array = ["en-US", "zh-Hant-TW", "sr_Latn_RS_REVISED", "es-419", "ar-EG", "he", "de-DE@collation=phonebook;currency=EUR"];
+            for each (var name:String in names) {
+                var id:LocaleID = new LocaleID(name);
+                trace(id.name);
+                trace("  language: " + id.getLanguage());
+                trace("  script: " + id.getScript());
+                trace("  region: " + id.getRegion());
+                trace("  variant: " + id.getVariant());
+                trace("  isRightToLeft: " + id.isRightToLeft());
+
+                var keys:Array = [];
+                var keysAndValues:Object = id.getKeysAndValues();
+                for (var key:String in keysAndValues) {
+                    keys.push(key + "=" + keysAndValues[key]);
+                }
+                keys.sort();
+                trace("  keysAndValues: " + keys);
+                trace("  lastOperationStatus: " + id.lastOperationStatus);
+            }
+
+            var want:Vector.<String> = new <String>["fr-CA", "en-GB", "de"];
+            var have:Vector.<String> = new <String>["en-US", "de-DE", "fr-FR", "en-GB", "ja-JP"];
+            trace(LocaleID.determinePreferredLocales(want, have));
+
+            try {
+                new LocaleID(null);
+            } catch (e:TypeError) {
+                trace("new LocaleID(null): " + e.errorID);
+            }
+        }
+    }
+}
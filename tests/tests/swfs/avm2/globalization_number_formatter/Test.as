@@ -0,0 +1,69 @@
+package {
+    import flash.display.Sprite;
+    import flash.globalization.LocaleID;
+    import flash.globalization.NationalDigitsType;
+    import flash.globalization.NumberFormatter;
+    import flash.globalization.NumberParseResult;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var nf:NumberFormatter = new NumberFormatter("en-US");
+            trace(nf.requestedLocaleIDName, nf.actualLocaleIDName, nf.lastOperationStatus);
+            trace(nf.decimalSeparator, nf.groupingSeparator, nf.groupingPattern, nf.negativeNumberFormat);
+            trace(nf.formatNumber(1234567.891));
+            trace(nf.formatNumber(-0.5));
+            trace(nf.formatNumber(-0.001));
+            trace(nf.formatInt(-42));
+            trace(nf.formatUint(1000));
+
+            nf.negativeNumberFormat = 0;
+            trace(nf.formatNumber(-3.14159));
+            nf.negativeNumberFormat = 4;
+            trace(nf.formatNumber(-3.14159));
+            try {
+                nf.negativeNumberFormat = 5;
+            } catch (e:ArgumentError) {
+                trace("negativeNumberFormat = 5: " + e.errorID);
+            }
+
+            nf.fractionalDigits = 0;
+            nf.useGrouping = false;
+            trace(nf.formatNumber(98765.7));
+            nf.fractionalDigits = 3;
+            nf.leadingZero = false;
+            nf.trailingZeros = false;
+            trace(nf.formatNumber(0.25));
+            nf.useGrouping = true;
+            nf.groupingPattern = "3;2;*";
+            trace(nf.formatNumber(12345678));
+            nf.digitsType = NationalDigitsType.ARABIC_INDIC;
+            trace(nf.formatNumber(12.25));
+
+            var de:NumberFormatter = new NumberFormatter("de-DE");
+            trace(de.actualLocaleIDName, de.lastOperationStatus);
+            trace(de.formatNumber(-1234.5));
+
+            var fr:NumberFormatter = new NumberFormatter("fr-CA");
+            trace(fr.actualLocaleIDName, fr.lastOperationStatus);
+
+            var def:NumberFormatter = new NumberFormatter(LocaleID.DEFAULT);
+            trace(def.actualLocaleIDName, def.lastOperationStatus);
+
+            var p:NumberFormatter = new NumberFormatter("en-US");
+            var result:NumberParseResult = p.parse("total: -1,234.5 items");
+            trace(result.value, result.startIndex, result.endIndex, p.lastOperationStatus);
+            result = p.parse("nothing here");
+            trace(result.value, result.startIndex, result.endIndex, p.lastOperationStatus);
+            trace(p.parseNumber(" 12.5 "), p.lastOperationStatus);
+            trace(p.parseNumber("12 apples"), p.lastOperationStatus);
+
+            trace(NumberFormatter.getAvailableLocaleIDNames().indexOf("en-US") != -1);
+
+            try {
+                new NumberFormatter(null);
+            } catch (e:TypeError) {
+                trace("new NumberFormatter(null): " + e.errorID);
+            }
+        }
+    }
+}
@@ -0,0 +1,24 @@
+package {
+    import flash.display.Sprite;
+    import flash.globalization.StringTools;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var en:StringTools = new StringTools("en-US");
+            trace(en.actualLocaleIDName, en.lastOperationStatus);
+            trace(en.toUpperCase("istanbul straße"));
+            trace(en.toLowerCase("DIYARBAKIR ÉTÉ"));
+
+            var tr:StringTools = new StringTools("tr-TR");
+            trace(tr.requestedLocaleIDName, tr.actualLocaleIDName, tr.lastOperationStatus);
+            trace(tr.toUpperCase("istanbul ılık"));
+            trace(tr.toLowerCase("DİYARBAKIR"));
+
+            try {
+                en.toUpperCase(null);
+            } catch (e:TypeError) {
+                trace("toUpperCase(null): " + e.errorID);
+            }
+        }
+    }
+}
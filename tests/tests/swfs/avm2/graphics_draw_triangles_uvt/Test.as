@@ -0,0 +1,130 @@
+package {
+
+import flash.display.BitmapData;
+import flash.display.Graphics;
+import flash.display.MovieClip;
+import flash.display.Shape;
+import flash.geom.Rectangle;
+
+public class Test extends MovieClip {
+    public function Test() {
+        var bitmap:BitmapData = new BitmapData(4, 4, false, 0xFF0000);
+        var vertices:Vector.<Number> = Vector.<Number>([
+            10, 10,
+            50, 10,
+            50, 50,
+            10, 50
+        ]);
+        var indices:Vector.<int> = Vector.<int>([
+            0, 1, 2,
+            0, 2, 3
+        ]);
+
+        trace("// uv");
+        testShape(function (g:Graphics):void {
+            g.beginBitmapFill(bitmap);
+            g.drawTriangles(vertices, indices, Vector.<Number>([
+                0, 0,
+                1, 0,
+                1, 1,
+                0, 1
+            ]));
+            g.endFill();
+        });
+
+        trace("// uvt");
+        testShape(function (g:Graphics):void {
+            g.beginBitmapFill(bitmap);
+            g.drawTriangles(vertices, indices, Vector.<Number>([
+                0, 0, 1,
+                1, 0, 0.5,
+                1, 1, 0.5,
+                0, 1, 1
+            ]));
+            g.endFill();
+        });
+
+        trace("// uvt with a solid fill");
+        testShape(function (g:Graphics):void {
+            g.beginFill(0x00FF00);
+            g.drawTriangles(vertices, indices, Vector.<Number>([
+                0, 0,
+                1, 0,
+                1, 1,
+                0, 1
+            ]));
+            g.endFill();
+        });
+
+        trace("// uvt with culling");
+        testShape(function (g:Graphics):void {
+            g.beginBitmapFill(bitmap);
+            g.drawTriangles(vertices, Vector.<int>([
+                0, 1, 2,
+                0, 3, 2
+            ]), Vector.<Number>([
+                0, 0,
+                1, 0,
+                1, 1,
+                0, 1
+            ]), "positive");
+            g.endFill();
+        });
+
+        trace("// uvt with a line style");
+        testShape(function (g:Graphics):void {
+            g.lineStyle(4, 0x0000FF);
+            g.beginBitmapFill(bitmap);
+            g.drawTriangles(vertices, indices, Vector.<Number>([
+                0, 0,
+                1, 0,
+                1, 1,
+                0, 1
+            ]));
+            g.endFill();
+        });
+
+        trace("// uvt without indices");
+        testShape(function (g:Graphics):void {
+            g.beginBitmapFill(bitmap);
+            g.drawTriangles(Vector.<Number>([
+                10, 10,
+                50, 10,
+                50, 50
+            ]), null, Vector.<Number>([
+                0, 0,
+                1, 0,
+                1, 1
+            ]));
+            g.endFill();
+        });
+
+        trace("// uvt with an invalid length");
+        testShape(function (g:Graphics):void {
+            g.beginBitmapFill(bitmap);
+            g.drawTriangles(vertices, indices, Vector.<Number>([
+                0, 0,
+                1, 0,
+                1, 1
+            ]));
+            g.endFill();
+        });
+    }
+
+    private function testShape(draw:Function):void {
+        var shape:Shape = new Shape();
+        addChild(shape);
+        try {
+            draw(shape.graphics);
+        } catch (e:*) {
+            trace("Error thrown: " + e);
+        }
+        var bounds:Rectangle = shape.getBounds(shape);
+        trace("bounds: " + bounds);
+        trace("hit (40, 20): " + shape.hitTestPoint(40, 20, true));
+        trace("hit (20, 40): " + shape.hitTestPoint(20, 40, true));
+        trace("hit (5, 5): " + shape.hitTestPoint(5, 5, true));
+        removeChild(shape);
+    }
+}
+}
@@ -0,0 +1,133 @@
+package {
+
+import flash.display.BitmapData;
+import flash.display.Graphics;
+import flash.display.GraphicsBitmapFill;
+import flash.display.GraphicsGradientFill;
+import flash.display.GraphicsPath;
+import flash.display.GraphicsSolidFill;
+import flash.display.GraphicsStroke;
+import flash.display.GraphicsTrianglePath;
+import flash.display.IGraphicsData;
+import flash.display.MovieClip;
+import flash.display.Shape;
+import flash.display.Sprite;
+import flash.geom.Matrix;
+import flash.utils.getQualifiedClassName;
+
+public class Test extends MovieClip {
+    public function Test() {
+        var shape:Shape = new Shape();
+        trace("// empty");
+        dump(shape.graphics.readGraphicsData());
+
+        trace("// solid fill");
+        shape.graphics.beginFill(0xFF0000, 0.5);
+        shape.graphics.drawRect(10, 20, 30, 40);
+        shape.graphics.endFill();
+        var data:Vector.<IGraphicsData> = shape.graphics.readGraphicsData();
+        dump(data);
+
+        trace("// round trip");
+        var copy:Shape = new Shape();
+        copy.graphics.drawGraphicsData(data);
+        trace(copy.getBounds(copy));
+        dump(copy.graphics.readGraphicsData());
+
+        trace("// stroke");
+        shape = new Shape();
+        shape.graphics.lineStyle(2, 0x00FF00, 1, true, "none", "square", "miter", 5);
+        shape.graphics.moveTo(0, 0);
+        shape.graphics.lineTo(10, 10);
+        shape.graphics.curveTo(20, 0, 30, 10);
+        dump(shape.graphics.readGraphicsData());
+
+        trace("// gradient fill");
+        shape = new Shape();
+        var matrix:Matrix = new Matrix();
+        matrix.createGradientBox(100, 50, 0, 10, 20);
+        shape.graphics.beginGradientFill("linear", [0xFF0000, 0x0000FF], [1, 0.5], [0, 255], matrix, "reflect", "linearRGB");
+        shape.graphics.drawRect(0, 0, 10, 10);
+        dump(shape.graphics.readGraphicsData());
+
+        trace("// bitmap fill");
+        shape = new Shape();
+        var bitmap:BitmapData = new BitmapData(4, 4, false, 0x0000FF);
+        shape.graphics.beginBitmapFill(bitmap, new Matrix(2, 0, 0, 2, 5, 5), false, true);
+        shape.graphics.drawRect(0, 0, 8, 8);
+        data = shape.graphics.readGraphicsData();
+        trace("same bitmapData: " + (GraphicsBitmapFill(data[0]).bitmapData === bitmap));
+        dump(data);
+
+        trace("// textured triangles");
+        shape = new Shape();
+        shape.graphics.beginBitmapFill(bitmap);
+        shape.graphics.drawTriangles(
+            Vector.<Number>([0, 0, 10, 0, 10, 10]),
+            null,
+            Vector.<Number>([0, 0, 1, 0, 1, 1]));
+        data = shape.graphics.readGraphicsData();
+        trace("same bitmapData: " + (GraphicsBitmapFill(data[0]).bitmapData === bitmap));
+        dump(data);
+
+        trace("// recurse");
+        var sprite:Sprite = new Sprite();
+        sprite.graphics.beginFill(0x000000);
+        sprite.graphics.drawRect(0, 0, 10, 10);
+        var child:Shape = new Shape();
+        child.graphics.beginFill(0xFFFFFF);
+        child.graphics.drawRect(0, 0, 5, 5);
+        child.x = 100;
+        child.y = 50;
+        sprite.addChild(child);
+        trace("recurse = false");
+        dump(sprite.graphics.readGraphicsData(false));
+        trace("recurse = true");
+        dump(sprite.graphics.readGraphicsData(true));
+
+        trace("// clear");
+        sprite.graphics.clear();
+        dump(sprite.graphics.readGraphicsData(false));
+    }
+
+    private function dump(data:Vector.<IGraphicsData>):void {
+        trace("length: " + data.length);
+        for each (var item:IGraphicsData in data) {
+            var name:String = getQualifiedClassName(item);
+            if (item is GraphicsSolidFill) {
+                var solid:GraphicsSolidFill = GraphicsSolidFill(item);
+                trace(name, solid.color.toString(16), round(solid.alpha));
+            } else if (item is GraphicsGradientFill) {
+                var gradient:GraphicsGradientFill = GraphicsGradientFill(item);
+                trace(name, gradient.type, gradient.colors, gradient.alphas.map(roundItem), gradient.ratios);
+                trace("  ", gradient.matrix, gradient.spreadMethod, gradient.interpolationMethod, gradient.focalPointRatio);
+            } else if (item is GraphicsBitmapFill) {
+                var bitmapFill:GraphicsBitmapFill = GraphicsBitmapFill(item);
+                trace(name, bitmapFill.bitmapData.width, bitmapFill.bitmapData.height, bitmapFill.matrix, bitmapFill.repeat, bitmapFill.smooth);
+            } else if (item is GraphicsPath) {
+                var path:GraphicsPath = GraphicsPath(item);
+                trace(name, path.commands, path.data, path.winding);
+            } else if (item is GraphicsStroke) {
+                var stroke:GraphicsStroke = GraphicsStroke(item);
+                trace(name, stroke.thickness, stroke.pixelHinting, stroke.scaleMode, stroke.caps, stroke.joints, stroke.miterLimit);
+                if (stroke.fill is GraphicsSolidFill) {
+                    trace("  ", GraphicsSolidFill(stroke.fill).color.toString(16), round(GraphicsSolidFill(stroke.fill).alpha));
+                }
+            } else if (item is GraphicsTrianglePath) {
+                var triangles:GraphicsTrianglePath = GraphicsTrianglePath(item);
+                trace(name, triangles.vertices, triangles.indices, triangles.uvtData, triangles.culling);
+            } else {
+                trace(name);
+            }
+        }
+    }
+
+    private function round(value:Number):Number {
+        return Math.round(value * 100) / 100;
+    }
+
+    private function roundItem(value:*, index:int, array:Array):Number {
+        return round(value);
+    }
+}
+}
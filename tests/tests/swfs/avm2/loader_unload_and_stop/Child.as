@@ -0,0 +1,41 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.TimerEvent;
+    import flash.utils.Timer;
+    import flash.utils.setInterval;
+
+    public class Child extends Sprite {
+        public var enterFrameTicks:int = 0;
+        public var intervalTicks:int = 0;
+        public var timerTicks:int = 0;
+
+        public function Child() {
+            if (stage) {
+                setup();
+            } else {
+                addEventListener(Event.ADDED_TO_STAGE, function(e:Event):void {
+                    setup();
+                });
+            }
+        }
+
+        private function setup():void {
+            stage.addEventListener(Event.ENTER_FRAME, onEnterFrame);
+
+            setInterval(function():void {
+                intervalTicks++;
+            }, 10);
+
+            var timer:Timer = new Timer(10);
+            timer.addEventListener(TimerEvent.TIMER, function(e:TimerEvent):void {
+                timerTicks++;
+            });
+            timer.start();
+        }
+
+        private function onEnterFrame(e:Event):void {
+            enterFrameTicks++;
+        }
+    }
+}
@@ -0,0 +1,62 @@
+package {
+    import flash.display.Loader;
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.net.URLRequest;
+
+    public class Test extends Sprite {
+        private var loader:Loader;
+        private var child:Object;
+        private var frames:int = 0;
+        private var enterFrameTicks:int;
+        private var intervalTicks:int;
+        private var timerTicks:int;
+
+        public function Test() {
+            loader = new Loader();
+            addChild(loader);
+            loader.contentLoaderInfo.addEventListener(Event.INIT, function(e:Event):void {
+                trace("init");
+                child = loader.content;
+                addEventListener(Event.ENTER_FRAME, onEnterFrame);
+            });
+            loader.contentLoaderInfo.addEventListener(Event.UNLOAD, function(e:Event):void {
+                trace("unload event");
+            });
+            loader.load(new URLRequest("child.swf"));
+
+            var closedLoader:Loader = new Loader();
+            closedLoader.contentLoaderInfo.addEventListener(Event.INIT, function(e:Event):void {
+                trace("closed loader init (should not appear)");
+            });
+            closedLoader.load(new URLRequest("child.swf"));
+            closedLoader.close();
+
+            var reusedLoader:Loader = new Loader();
+            reusedLoader.contentLoaderInfo.addEventListener(Event.INIT, function(e:Event):void {
+                trace("reused loader init");
+            });
+            reusedLoader.load(new URLRequest("child.swf"));
+            reusedLoader.load(new URLRequest("child.swf"));
+        }
+
+        private function onEnterFrame(e:Event):void {
+            frames++;
+            if (frames == 5) {
+                trace("child ticking: " + (child.enterFrameTicks > 0) + " " + (child.intervalTicks > 0) + " " + (child.timerTicks > 0));
+                enterFrameTicks = child.enterFrameTicks;
+                intervalTicks = child.intervalTicks;
+                timerTicks = child.timerTicks;
+
+                trace("unloadAndStop");
+                loader.unloadAndStop();
+                trace("content after unload: " + loader.content);
+            } else if (frames == 10) {
+                trace("enterFrame stopped: " + (child.enterFrameTicks == enterFrameTicks));
+                trace("interval stopped: " + (child.intervalTicks == intervalTicks));
+                trace("timer stopped: " + (child.timerTicks == timerTicks));
+                removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+            }
+        }
+    }
+}
@@ -0,0 +1,42 @@
+package {
+    import flash.display.Loader;
+    import flash.display.MovieClip;
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.net.URLRequest;
+
+    public class Test extends Sprite {
+        private var loader:Loader;
+        private var anim:MovieClip;
+        private var frames:int = 0;
+        private var frameAtUnload:int;
+
+        public function Test() {
+            loader = new Loader();
+            addChild(loader);
+            loader.contentLoaderInfo.addEventListener(Event.INIT, function(e:Event):void {
+                // child.swf places a clip that morphs a shape on every frame and
+                // places a new shape halfway through its timeline.
+                anim = MovieClip(MovieClip(loader.content).getChildAt(0));
+                addChild(anim);
+                trace("reparented: " + (anim.parent == this));
+                addEventListener(Event.ENTER_FRAME, onEnterFrame);
+            });
+            loader.load(new URLRequest("child.swf"));
+        }
+
+        private function onEnterFrame(e:Event):void {
+            frames++;
+            if (frames == 2) {
+                frameAtUnload = anim.currentFrame;
+                loader.unloadAndStop();
+                trace("unloadAndStop");
+                trace("content after unload: " + loader.content);
+            } else if (frames == 20) {
+                trace("reparented clip still playing: " + (anim.currentFrame != frameAtUnload));
+                trace("reparented clip still on stage: " + (anim.stage != null));
+                removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+// Feeds a NetStream in data generation mode with FLV script tags, and checks
+// which of them play after each appendBytesAction.
+
+package {
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.NetStatusEvent;
+    import flash.net.NetConnection;
+    import flash.net.NetStream;
+    import flash.net.NetStreamAppendBytesAction;
+    import flash.system.fscommand;
+    import flash.utils.ByteArray;
+
+    public class Test extends Sprite {
+        private var frame:int = 0;
+        private var ns:NetStream;
+        private var log:Array = [];
+
+        public function Test() {
+            var nc:NetConnection = new NetConnection();
+            nc.connect(null);
+            ns = new NetStream(nc);
+            ns.client = {
+                onCue: function(n:Number):void {
+                    log.push("cue " + n);
+                }
+            };
+            ns.addEventListener(NetStatusEvent.NET_STATUS, function(e:NetStatusEvent):void {
+                log.push(e.info.code);
+            });
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private function onEnterFrame(e:Event):void {
+            frame++;
+            if (frame == 1) {
+                var rejected:ByteArray = header();
+                rejected.writeBytes(cue(0, 0));
+                ns.appendBytes(rejected);
+                ns.appendBytesAction(NetStreamAppendBytesAction.END_SEQUENCE);
+                trace("// Before play(null)");
+                trace("bytesLoaded: " + ns.bytesLoaded);
+
+                ns.play(null);
+                ns.appendBytesAction(NetStreamAppendBytesAction.RESET_BEGIN);
+                var bytes:ByteArray = header();
+                bytes.writeBytes(cue(0, 0));
+                bytes.writeBytes(cue(1000, 1));
+                bytes.writeBytes(cue(2000, 2));
+                ns.appendBytes(bytes);
+                trace("// After RESET_BEGIN");
+                trace("bytesLoaded: " + ns.bytesLoaded);
+            } else if (frame == 6) {
+                dump();
+                trace("bufferLength close to 2s: " + (ns.bufferLength > 1.5 && ns.bufferLength < 2));
+
+                ns.appendBytesAction(NetStreamAppendBytesAction.RESET_SEEK);
+                var seeked:ByteArray = new ByteArray();
+                seeked.writeBytes(cue(5000, 50));
+                seeked.writeBytes(cue(5100, 51));
+                ns.appendBytes(seeked);
+                trace("// After RESET_SEEK");
+                trace("bytesLoaded: " + ns.bytesLoaded);
+                trace("bufferLength: " + ns.bufferLength);
+            } else if (frame == 16) {
+                dump();
+                trace("bufferLength: " + ns.bufferLength);
+
+                ns.appendBytes(cue(5200, 52));
+                ns.appendBytesAction(NetStreamAppendBytesAction.END_SEQUENCE);
+                trace("// After END_SEQUENCE");
+            } else if (frame == 20) {
+                dump();
+                fscommand("quit");
+            }
+        }
+
+        // Prints what happened since the last call, without repeated events.
+        private function dump():void {
+            var seen:Object = {};
+            for each (var entry:String in log) {
+                if (!seen[entry]) {
+                    seen[entry] = true;
+                    trace(entry);
+                }
+            }
+            log = [];
+        }
+
+        private static function header():ByteArray {
+            var bytes:ByteArray = new ByteArray();
+            bytes.writeUTFBytes("FLV");
+            bytes.writeByte(1);
+            bytes.writeByte(0);
+            bytes.writeUnsignedInt(9);
+            return bytes;
+        }
+
+        // A script tag calling `onCue(value)` on the client, preceded by the
+        // size of the previous tag.
+        private static function cue(timestamp:int, value:Number):ByteArray {
+            var body:ByteArray = new ByteArray();
+            body.writeByte(2);
+            body.writeUTF("onCue");
+            body.writeByte(0);
+            body.writeDouble(value);
+
+            var bytes:ByteArray = new ByteArray();
+            bytes.writeUnsignedInt(0);
+            bytes.writeByte(18);
+            writeU24(bytes, body.length);
+            writeU24(bytes, timestamp & 0xFFFFFF);
+            bytes.writeByte(timestamp >>> 24);
+            writeU24(bytes, 0);
+            bytes.writeBytes(body);
+            return bytes;
+        }
+
+        private static function writeU24(bytes:ByteArray, value:int):void {
+            bytes.writeByte(value >> 16);
+            bytes.writeByte(value >> 8);
+            bytes.writeByte(value);
+        }
+    }
+}
@@ -0,0 +1,162 @@
+// Saves the state of the movie, changes most of it, then restores the
+// state and checks that everything is back to how it was when saved.
+// The test runner keeps running the clock across a restore, which is
+// how the movie tells the second pass through frame 2 apart.
+
+package {
+    import flash.display.Shape;
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.geom.Point;
+    import flash.net.SharedObject;
+    import flash.system.fscommand;
+    import flash.text.TextField;
+    import flash.utils.ByteArray;
+    import flash.utils.Dictionary;
+    import flash.utils.getTimer;
+
+    public class Test extends Sprite {
+        public static var counter:int = 0;
+
+        private var frame:int = 0;
+        private var savedAt:int = 0;
+        private var data:Object;
+        private var shape:Shape;
+        private var label:TextField;
+        private var customEvents:int = 0;
+
+        public function Test() {
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private function onEnterFrame(e:Event):void {
+            frame++;
+            if (frame == 1) {
+                setup();
+                trace("// Saved");
+                dump();
+                savedAt = getTimer();
+                fscommand("saveState", "before");
+            } else if (frame == 2) {
+                if (getTimer() - savedAt > 60) {
+                    trace("// Restored");
+                    dump();
+                    fscommand("quit");
+                } else {
+                    mutate();
+                    trace("// Mutated");
+                    dump();
+                    fscommand("loadState", "before");
+                }
+            }
+        }
+
+        private function setup():void {
+            shape = new Shape();
+            shape.x = 10;
+            shape.y = 20;
+            shape.graphics.beginFill(0xFF0000);
+            shape.graphics.drawRect(0, 0, 30, 40);
+            shape.graphics.endFill();
+            addChild(shape);
+
+            label = new TextField();
+            label.text = "saved";
+            label.name = "label";
+            label.addEventListener("custom", onCustom);
+            addChild(label);
+
+            var bytes:ByteArray = new ByteArray();
+            bytes.writeUTF("bytes");
+
+            var dict:Dictionary = new Dictionary();
+            dict[shape] = "shape key";
+
+            data = {
+                name: "player",
+                scores: [1, 2, 3],
+                nested: {},
+                vec: new <int>[4, 5],
+                when: new Date(2000, 0, 1),
+                point: new Point(1, 2),
+                pattern: /a+b/gi,
+                xml: <item id="1">sword</item>,
+                bytes: bytes,
+                dict: dict,
+                nan: NaN,
+                missing: undefined,
+                surrogate: "a" + String.fromCharCode(0xD800) + "b"
+            };
+            data.nested.parent = data;
+            var self:Object = data;
+            data.describe = function():String {
+                return self.name + " has " + self.scores.length + " scores";
+            };
+
+            // Only the player keeps this shared object open.
+            SharedObject.getLocal("save_state_heap").data.score = 10;
+
+            counter = 1;
+        }
+
+        private function mutate():void {
+            data.name = "changed";
+            data.scores.push(4);
+            data.nested.parent = null;
+            data.vec[0] = 99;
+            data.when.fullYear = 2020;
+            data.point.x = 50;
+            data.pattern.lastIndex = 3;
+            data.xml.@id = "2";
+            data.bytes.clear();
+            data.dict[shape] = "other";
+            data.nan = 0;
+            delete data.missing;
+            data.extra = true;
+            data.surrogate = "x";
+            SharedObject.getLocal("save_state_heap").data.score = 20;
+
+            shape.x = 100;
+            shape.graphics.clear();
+            removeChild(shape);
+            label.text = "mutated";
+            label.removeEventListener("custom", onCustom);
+            addChild(new Sprite());
+
+            counter = 5;
+        }
+
+        private function onCustom(e:Event):void {
+            customEvents++;
+        }
+
+        private function dump():void {
+            trace("name: " + data.name);
+            trace("scores: " + data.scores.join(","));
+            trace("cycle intact: " + (data.nested.parent === data));
+            trace("vec: " + data.vec.join(",") + " (" + (data.vec is Vector.<int>) + ")");
+            trace("when: " + data.when.fullYear);
+            trace("point: " + data.point);
+            trace("pattern: " + data.pattern + " lastIndex " + data.pattern.lastIndex);
+            trace("xml: " + data.xml.toXMLString());
+            data.bytes.position = 0;
+            trace("bytes: " + (data.bytes.length > 0 ? data.bytes.readUTF() : "(empty)"));
+            trace("dict: " + data.dict[shape]);
+            trace("nan: " + data.nan);
+            trace("missing: " + ("missing" in data) + " " + data.missing);
+            trace("extra: " + data.extra);
+            trace("describe: " + data.describe());
+            trace("surrogate: " + data.surrogate.length + " " + data.surrogate.charCodeAt(1));
+            trace("shared object: " + SharedObject.getLocal("save_state_heap").data.score);
+
+            trace("numChildren: " + numChildren);
+            trace("shape: " + (shape.parent == this) + " x=" + shape.x + " y=" + shape.y +
+                " width=" + shape.width + " height=" + shape.height);
+            trace("label: " + label.text + " " + (getChildByName("label") == label));
+            customEvents = 0;
+            label.dispatchEvent(new Event("custom"));
+            trace("custom events: " + customEvents);
+            trace("counter: " + counter);
+        }
+    }
+}
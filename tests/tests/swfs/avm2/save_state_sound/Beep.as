@@ -0,0 +1,7 @@
+package {
+    import flash.media.Sound;
+
+    // Two seconds of silence, embedded as character 1.
+    public class Beep extends Sound {
+    }
+}
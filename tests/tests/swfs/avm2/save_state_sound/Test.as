@@ -0,0 +1,48 @@
+// Saves the state while an embedded sound is playing, stops the sound,
+// then restores the state. The sound should resume from where it was.
+
+package {
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.media.SoundChannel;
+    import flash.system.fscommand;
+    import flash.utils.getTimer;
+
+    public class Test extends Sprite {
+        private var frame:int = 0;
+        private var savedAt:int = 0;
+        private var savedPosition:Number = 0;
+        private var lastPosition:Number = 0;
+        private var channel:SoundChannel;
+
+        public function Test() {
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private function onEnterFrame(e:Event):void {
+            frame++;
+            if (frame == 1) {
+                channel = new Beep().play();
+            } else if (frame == 5) {
+                savedPosition = channel.position;
+                trace("// Saved, playing: " + (savedPosition > 0));
+                savedAt = getTimer();
+                fscommand("saveState", "sound");
+            } else if (frame == 6) {
+                if (getTimer() - savedAt > 60) {
+                    lastPosition = channel.position;
+                    trace("// Restored");
+                    trace("resumed from saved position: " +
+                        (lastPosition >= savedPosition && lastPosition < savedPosition + 100));
+                } else {
+                    channel.stop();
+                    trace("// Stopped");
+                    fscommand("loadState", "sound");
+                }
+            } else if (frame == 8) {
+                trace("still playing: " + (channel.position > lastPosition));
+                fscommand("quit");
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+// Saves the state while a Timer and a timeout are pending, cancels both,
+// then restores the state. Both should fire as if nothing happened.
+
+package {
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.TimerEvent;
+    import flash.system.fscommand;
+    import flash.utils.Timer;
+    import flash.utils.clearTimeout;
+    import flash.utils.getTimer;
+    import flash.utils.setTimeout;
+
+    public class Test extends Sprite {
+        private var frame:int = 0;
+        private var savedAt:int = 0;
+        private var timer:Timer;
+        private var timeout:uint;
+
+        public function Test() {
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private function onEnterFrame(e:Event):void {
+            frame++;
+            if (frame == 1) {
+                timer = new Timer(100, 3);
+                timer.addEventListener(TimerEvent.TIMER, onTimer);
+                timer.addEventListener(TimerEvent.TIMER_COMPLETE, onComplete);
+                timer.start();
+                timeout = setTimeout(onTimeout, 250, "param");
+                trace("// Saved, running: " + timer.running);
+                savedAt = getTimer();
+                fscommand("saveState", "timers");
+            } else if (frame == 2) {
+                if (getTimer() - savedAt > 60) {
+                    trace("// Restored, running: " + timer.running + ", count: " + timer.currentCount);
+                } else {
+                    timer.stop();
+                    clearTimeout(timeout);
+                    trace("// Cancelled, running: " + timer.running);
+                    fscommand("loadState", "timers");
+                }
+            }
+        }
+
+        private function onTimer(e:TimerEvent):void {
+            trace("timer " + timer.currentCount);
+        }
+
+        private function onTimeout(param:String):void {
+            trace("timeout " + param);
+        }
+
+        private function onComplete(e:TimerEvent):void {
+            trace("complete, running: " + timer.running);
+            fscommand("quit");
+        }
+    }
+}
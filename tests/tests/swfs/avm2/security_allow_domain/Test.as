@@ -0,0 +1,36 @@
+package {
+    import flash.display.MovieClip;
+    import flash.system.Security;
+
+    public class Test extends MovieClip {
+        public function Test() {
+            Security.allowDomain("*");
+            Security.allowDomain("example.com", "http://www.example.com/movie.swf");
+            Security.allowInsecureDomain("*.example.com");
+            trace("allowDomain with Strings succeeded");
+
+            try {
+                Security.allowDomain("example.com", 5);
+            } catch (e:Error) {
+                trace("allowDomain with a non-String: " + e.errorID);
+            }
+
+            try {
+                Security.allowInsecureDomain(null);
+            } catch (e:Error) {
+                trace("allowInsecureDomain with null: " + e.errorID);
+            }
+
+            trace("childAllowsParent: " + loaderInfo.childAllowsParent);
+            trace("parentAllowsChild: " + loaderInfo.parentAllowsChild);
+            trace("isURLInaccessible: " + loaderInfo.isURLInaccessible);
+
+            trace("parentSandboxBridge: " + loaderInfo.parentSandboxBridge);
+            trace("childSandboxBridge: " + loaderInfo.childSandboxBridge);
+            var bridge:Object = {name: "bridge"};
+            loaderInfo.childSandboxBridge = bridge;
+            trace("childSandboxBridge after set: " + loaderInfo.childSandboxBridge.name);
+            trace("Same object: " + (loaderInfo.childSandboxBridge === bridge));
+        }
+    }
+}
@@ -0,0 +1,72 @@
+package {
+    import flash.display.DisplayObject;
+    import flash.display.Loader;
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.net.URLRequest;
+    import flash.system.ApplicationDomain;
+    import flash.system.LoaderContext;
+
+    // Runs from http://www.example.com/test.swf, see test.toml.
+    public class Test extends Sprite {
+        private var cases:Array = [
+            ["Movie without allowDomain", "http://child.test/ChildPrivate.swf", "ChildPrivate"],
+            ["Movie calling allowDomain", "http://child.test/ChildAllow.swf", "ChildAllow"]
+        ];
+
+        public function Test() {
+            next();
+        }
+
+        private function next():void {
+            if (cases.length == 0) {
+                trace("Done");
+                return;
+            }
+
+            var test:Array = cases.shift();
+            var loader:Loader = new Loader();
+            addChild(loader);
+            loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(e:Event):void {
+                trace("// " + test[0]);
+                trace("numChildren: " + loader.numChildren);
+                try {
+                    trace("content: " + loader.content);
+                } catch (error:SecurityError) {
+                    trace("content: SecurityError #" + error.errorID);
+                }
+
+                var child:DisplayObject = null;
+                try {
+                    child = loader.getChildAt(0);
+                    trace("getChildAt: " + child);
+                } catch (error:SecurityError) {
+                    trace("getChildAt: SecurityError #" + error.errorID);
+                }
+
+                if (child != null) {
+                    trace("getChildByName: " + (loader.getChildByName(child.name) === child));
+                    trace("child.parent: " + (child.parent === loader));
+                    trace("child.root: " + (child.root === child));
+                }
+
+                try {
+                    trace("applicationDomain: " + loader.contentLoaderInfo.applicationDomain);
+                } catch (error:SecurityError) {
+                    trace("applicationDomain: SecurityError #" + error.errorID);
+                }
+
+                trace("current domain sees " + test[2] + ": " +
+                    ApplicationDomain.currentDomain.hasDefinition(test[2]));
+
+                loader.contentLoaderInfo.sharedEvents.dispatchEvent(new Event("check"));
+                next();
+            });
+
+            // Asking for our own domain must not share definitions with
+            // a movie from another security domain.
+            var context:LoaderContext = new LoaderContext(false, ApplicationDomain.currentDomain);
+            loader.load(new URLRequest(test[1]), context);
+        }
+    }
+}
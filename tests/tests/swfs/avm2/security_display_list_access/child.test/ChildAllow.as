@@ -0,0 +1,10 @@
+package {
+    import flash.display.Sprite;
+    import flash.system.Security;
+
+    public class ChildAllow extends Sprite {
+        public function ChildAllow() {
+            Security.allowDomain("www.example.com");
+        }
+    }
+}
@@ -0,0 +1,31 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.system.ApplicationDomain;
+
+    public class ChildPrivate extends Sprite {
+        public function ChildPrivate() {
+            loaderInfo.sharedEvents.addEventListener("check", onCheck);
+        }
+
+        private function onCheck(e:Event):void {
+            trace("child root is itself: " + (root == this));
+            try {
+                trace("child parent: " + parent);
+            } catch (error:SecurityError) {
+                trace("child parent: SecurityError #" + error.errorID);
+            }
+            try {
+                trace("child stage.getChildAt: " + stage.getChildAt(0));
+            } catch (error:SecurityError) {
+                trace("child stage.getChildAt: SecurityError #" + error.errorID);
+            }
+            try {
+                trace("child stage.getChildByName: " + stage.getChildByName("root1"));
+            } catch (error:SecurityError) {
+                trace("child stage.getChildByName: SecurityError #" + error.errorID);
+            }
+            trace("child sees Test: " + ApplicationDomain.currentDomain.hasDefinition("Test"));
+        }
+    }
+}
@@ -0,0 +1,63 @@
+package {
+    import flash.display.BitmapData;
+    import flash.display.Loader;
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.net.URLRequest;
+    import flash.system.LoaderContext;
+    import flash.system.Security;
+
+    // Runs from http://www.example.com/test.swf, see test.toml.
+    public class Test extends Sprite {
+        private var cases:Array = [
+            ["Image without checkPolicyFile", "http://nopolicy.test/pic.png", false],
+            ["Image without a policy file", "http://nopolicy.test/pic.png", true],
+            ["Image with a policy file for other domains", "http://denied.test/pic.png", true],
+            ["Image with a policy file granting access", "http://granted.test/pic.png", true],
+            ["Movie without allowDomain", "http://child.test/ChildPrivate.swf", false],
+            ["Movie calling allowDomain", "http://child.test/ChildAllow.swf", false]
+        ];
+
+        public function Test() {
+            trace("sandboxType: " + Security.sandboxType);
+            next();
+        }
+
+        private function next():void {
+            if (cases.length == 0) {
+                trace("Done");
+                return;
+            }
+
+            var test:Array = cases.shift();
+            var loader:Loader = new Loader();
+            loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(e:Event):void {
+                trace("// " + test[0]);
+                try {
+                    trace("content: " + loader.content);
+                } catch (error:SecurityError) {
+                    trace("content: SecurityError #" + error.errorID);
+                }
+
+                var target:BitmapData = new BitmapData(2, 2, false, 0xFFFFFF);
+                try {
+                    target.draw(loader);
+                    trace("draw: " + target.getPixel(0, 0).toString(16));
+                } catch (error:SecurityError) {
+                    trace("draw: SecurityError #" + error.errorID);
+                }
+
+                if (loader.contentLoaderInfo.contentType == "application/x-shockwave-flash") {
+                    trace("childAllowsParent: " + loader.contentLoaderInfo.childAllowsParent);
+                    try {
+                        trace("secret: " + Object(loader.content).secret);
+                    } catch (error:SecurityError) {
+                        trace("secret: SecurityError #" + error.errorID);
+                    }
+                }
+                next();
+            });
+            loader.load(new URLRequest(test[1]), new LoaderContext(test[2]));
+        }
+    }
+}
@@ -0,0 +1,14 @@
+package {
+    import flash.display.Sprite;
+    import flash.system.Security;
+
+    public class ChildAllow extends Sprite {
+        public var secret:String = "shared";
+
+        public function ChildAllow() {
+            Security.allowDomain("www.example.com");
+            graphics.beginFill(0x0000FF);
+            graphics.drawRect(0, 0, 2, 2);
+        }
+    }
+}
@@ -0,0 +1,10 @@
+package {
+    import flash.display.Sprite;
+
+    public class ChildPrivate extends Sprite {
+        public function ChildPrivate() {
+            graphics.beginFill(0x00FF00);
+            graphics.drawRect(0, 0, 2, 2);
+        }
+    }
+}
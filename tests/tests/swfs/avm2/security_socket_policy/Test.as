@@ -0,0 +1,49 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.IOErrorEvent;
+    import flash.events.SecurityErrorEvent;
+    import flash.net.Socket;
+
+    // Runs from http://www.example.com/test.swf, see test.toml.
+    public class Test extends Sprite {
+        private var cases:Array = [
+            // No policy server on port 843, the policy comes from port 8001.
+            ["localhost", 8001],
+            // The policy of localhost is known, and doesn't cover port 8002.
+            ["localhost", 8002],
+            // The policy server on port 843 only grants access to other domains.
+            ["127.0.0.1", 8003],
+            // The policy server on port 843 grants access to ports 8000-8010.
+            ["sockets.test", 8004]
+        ];
+
+        public function Test() {
+            next();
+        }
+
+        private function next():void {
+            if (cases.length == 0) {
+                trace("Done");
+                return;
+            }
+
+            var test:Array = cases.shift();
+            var socket:Socket = new Socket();
+            socket.addEventListener(Event.CONNECT, function(e:Event):void {
+                trace(test[0] + ":" + test[1] + ": connect");
+                socket.close();
+                next();
+            });
+            socket.addEventListener(SecurityErrorEvent.SECURITY_ERROR, function(e:SecurityErrorEvent):void {
+                trace(test[0] + ":" + test[1] + ": securityError #" + e.errorID);
+                next();
+            });
+            socket.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+                trace(test[0] + ":" + test[1] + ": ioError");
+                next();
+            });
+            socket.connect(test[0], test[1]);
+        }
+    }
+}
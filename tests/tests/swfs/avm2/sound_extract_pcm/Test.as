@@ -0,0 +1,82 @@
+package {
+	import flash.display.Sprite;
+	import flash.media.Sound;
+	import flash.utils.ByteArray;
+	import flash.utils.Endian;
+
+	public class Test extends Sprite {
+		public function Test() {
+			var floats:ByteArray = new ByteArray();
+			for (var i:int = 0; i < 8; i++) {
+				floats.writeFloat(i / 8);
+				floats.writeFloat(-i / 16);
+			}
+			floats.position = 0;
+
+			var sound:Sound = new Sound();
+			trace("url: " + sound.url);
+			trace("isBuffering: " + sound.isBuffering);
+			try {
+				sound.close();
+			} catch (e:Error) {
+				trace("close: " + e);
+			}
+
+			sound.loadPCMFromByteArray(floats, 8);
+			trace("bytes read: " + floats.position);
+
+			var out:ByteArray = new ByteArray();
+			trace("extract(3): " + sound.extract(out, 3));
+			trace("extract(10): " + sound.extract(out, 10));
+			trace("extract(2, 6): " + sound.extract(out, 2, 6));
+			trace("extract(1): " + sound.extract(out, 1));
+			trace("extract(2, 1): " + sound.extract(out, 2, 1));
+			dump(out);
+
+			var shorts:ByteArray = new ByteArray();
+			shorts.endian = Endian.LITTLE_ENDIAN;
+			shorts.writeShort(16384);
+			shorts.writeShort(-8192);
+			shorts.position = 0;
+
+			var mono:Sound = new Sound();
+			mono.loadPCMFromByteArray(shorts, 2, "short", false);
+			out = new ByteArray();
+			out.endian = Endian.LITTLE_ENDIAN;
+			trace("mono extract(4): " + mono.extract(out, 4));
+			dump(out);
+
+			// Float samples are kept as they are, even out of range.
+			var precise:ByteArray = new ByteArray();
+			precise.writeFloat(1 / 3);
+			precise.writeFloat(1.5);
+			precise.writeFloat(-1 / 7);
+			precise.writeFloat(-2);
+			precise.position = 0;
+
+			var preciseSound:Sound = new Sound();
+			preciseSound.loadPCMFromByteArray(precise, 2, "float", true, 44100);
+			out = new ByteArray();
+			trace("precise extract(2): " + preciseSound.extract(out, 2));
+			dump(out);
+
+			try {
+				new Sound().loadPCMFromByteArray(new ByteArray(), 1, "double");
+			} catch (e:Error) {
+				trace("bad format: " + e);
+			}
+			try {
+				new Sound().loadPCMFromByteArray(new ByteArray(), 1);
+			} catch (e:Error) {
+				trace("too short: " + e);
+			}
+		}
+
+		private function dump(bytes:ByteArray):void {
+			bytes.position = 0;
+			while (bytes.bytesAvailable > 0) {
+				trace(bytes.readFloat() + " " + bytes.readFloat());
+			}
+		}
+	}
+}
@@ -0,0 +1,67 @@
+package {
+    import flash.display.Sprite;
+    import flash.text.engine.*;
+
+    public class Test extends Sprite {
+        private var format:ElementFormat;
+        private var blue:ElementFormat;
+
+        public function Test() {
+            var font:FontDescription = new FontDescription("TestFontBidi");
+            font.fontLookup = FontLookup.DEVICE;
+            format = new ElementFormat(font, 20);
+            blue = new ElementFormat(font, 20, 0x0000FF);
+
+            trace("// Justified");
+            var block:TextBlock = new TextBlock(group("ab ab ", "a b ab ba"));
+            block.textJustifier = new SpaceJustifier("en", LineJustification.ALL_BUT_LAST);
+            traceLines(block, 110);
+
+            trace("// Justified, letter spacing");
+            block = new TextBlock(group("ab ab ", "a b ab ba"));
+            block.textJustifier = new SpaceJustifier("en", LineJustification.ALL_BUT_LAST, true);
+            traceLines(block, 110);
+
+            trace("// Justified, including the last line");
+            block = new TextBlock(group("ab ", "ab"));
+            block.textJustifier = new SpaceJustifier("en", LineJustification.ALL_INCLUDING_LAST);
+            traceLines(block, 110);
+
+            trace("// Tab stops");
+            block = new TextBlock(group("a\tab\tab", "\tab\taab\tb"));
+            var tabStops:Vector.<TabStop> = new Vector.<TabStop>();
+            tabStops.push(new TabStop(TabAlignment.START, 40));
+            tabStops.push(new TabStop(TabAlignment.CENTER, 120));
+            tabStops.push(new TabStop(TabAlignment.END, 200));
+            tabStops.push(new TabStop(TabAlignment.DECIMAL, 280, "b"));
+            block.tabStops = tabStops;
+            traceLines(block, 1000);
+        }
+
+        private function group(first:String, second:String):GroupElement {
+            var elements:Vector.<ContentElement> = new Vector.<ContentElement>();
+            elements.push(new TextElement(first, format), new TextElement(second, blue));
+            return new GroupElement(elements, format);
+        }
+
+        private function traceLines(block:TextBlock, width:Number):void {
+            var line:TextLine = block.createTextLine(null, width);
+            while (line != null) {
+                trace("line " + line.textBlockBeginIndex + "-" +
+                    (line.textBlockBeginIndex + line.rawTextLength) +
+                    " textWidth=" + line.textWidth +
+                    " unjustifiedTextWidth=" + line.unjustifiedTextWidth +
+                    " hasTabs=" + line.hasTabs +
+                    " ascent=" + line.ascent + " descent=" + line.descent);
+                var atoms:Array = [];
+                for (var i:int = 0; i < line.atomCount; i++) {
+                    var bounds:* = line.getAtomBounds(i);
+                    atoms.push(bounds.x + "+" + bounds.width);
+                }
+                trace("  atoms: " + atoms.join(" "));
+                line = block.createTextLine(line, width);
+            }
+            trace("  " + block.textLineCreationResult);
+        }
+    }
+}
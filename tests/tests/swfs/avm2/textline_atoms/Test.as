@@ -0,0 +1,111 @@
+package {
+    import flash.display.Shape;
+    import flash.display.Sprite;
+    import flash.text.engine.*;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var format:ElementFormat = new ElementFormat();
+            var hello:TextElement = new TextElement("Hello world", format);
+            var graphic:GraphicElement = new GraphicElement(new Shape(), 10, 20, format);
+            var again:TextElement = new TextElement(" again\nNext", format);
+
+            trace("// Before adding to a group");
+            trace("hello.groupElement: " + hello.groupElement);
+            trace("hello.textBlock: " + hello.textBlock);
+            trace("hello.textBlockBeginIndex: " + hello.textBlockBeginIndex);
+            trace("graphic.elementWidth: " + graphic.elementWidth);
+            trace("graphic.elementHeight: " + graphic.elementHeight);
+            trace("graphic.graphic is Shape: " + (graphic.graphic is Shape));
+
+            var elements:Vector.<ContentElement> = new Vector.<ContentElement>();
+            elements.push(hello, graphic, again);
+            var group:GroupElement = new GroupElement(elements);
+
+            trace("// In a group, without a block");
+            trace("hello.groupElement === group: " + (hello.groupElement === group));
+            trace("again.textBlockBeginIndex: " + again.textBlockBeginIndex);
+            trace("group.text: " + escape(group.text));
+            trace("group.rawText: " + escape(group.rawText));
+
+            var block:TextBlock = new TextBlock(group);
+
+            trace("// In a block");
+            trace("group.textBlock === block: " + (group.textBlock === block));
+            trace("again.textBlock === block: " + (again.textBlock === block));
+            trace("hello.textBlockBeginIndex: " + hello.textBlockBeginIndex);
+            trace("graphic.textBlockBeginIndex: " + graphic.textBlockBeginIndex);
+            trace("again.textBlockBeginIndex: " + again.textBlockBeginIndex);
+            trace("block.firstInvalidLine: " + block.firstInvalidLine);
+
+            var line:TextLine = block.createTextLine(null, 10000);
+            trace("// First line");
+            trace("textLineCreationResult: " + block.textLineCreationResult);
+            trace("rawTextLength: " + line.rawTextLength);
+            trace("atomCount: " + line.atomCount);
+            trace("hasGraphicElement: " + line.hasGraphicElement);
+            trace("hasTabs: " + line.hasTabs);
+            trace("ascent > 0: " + (line.ascent > 0));
+            trace("totalAscent >= 20: " + (line.totalAscent >= 20));
+            trace("textWidth > 0: " + (line.textWidth > 0));
+            trace("getAtomIndexAtCharIndex(11): " + line.getAtomIndexAtCharIndex(11));
+            trace("getAtomIndexAtCharIndex(100): " + line.getAtomIndexAtCharIndex(100));
+            trace("getAtomGraphic(11) is Shape: " + (line.getAtomGraphic(11) is Shape));
+            trace("getAtomGraphic(0): " + line.getAtomGraphic(0));
+            trace("getAtomBounds(11).width: " + line.getAtomBounds(11).width);
+            trace("getAtomBidiLevel(0): " + line.getAtomBidiLevel(0));
+            trace("getAtomTextRotation(0): " + line.getAtomTextRotation(0));
+            trace("getBaselinePosition(roman): " + line.getBaselinePosition(TextBaseline.ROMAN));
+            for (var i:int = 0; i < line.atomCount; i++) {
+                trace("atom " + i + ": " + line.getAtomTextBlockBeginIndex(i) + "-" +
+                    line.getAtomTextBlockEndIndex(i) + " wordBoundaryOnLeft=" +
+                    line.getAtomWordBoundaryOnLeft(i));
+            }
+            try {
+                line.getAtomBounds(line.atomCount);
+            } catch (e:Error) {
+                trace("getAtomBounds(atomCount): " + e.errorID);
+            }
+            try {
+                line.getBaselinePosition("nonsense");
+            } catch (e:Error) {
+                trace("getBaselinePosition(nonsense): " + e.errorID);
+            }
+
+            var line2:TextLine = block.createTextLine(line, 10000);
+            trace("// Second line");
+            trace("textBlockBeginIndex: " + line2.textBlockBeginIndex);
+            trace("rawTextLength: " + line2.rawTextLength);
+            trace("atomCount: " + line2.atomCount);
+            trace("block.lastLine === line2: " + (block.lastLine === line2));
+            trace("createTextLine after the end: " + block.createTextLine(line2, 10000));
+            trace("textLineCreationResult: " + block.textLineCreationResult);
+
+            trace("// Narrow lines");
+            var narrow:TextBlock = new TextBlock(new TextElement("aaa bbb ccc", format));
+            var narrowLine:TextLine = narrow.createTextLine(null, 1);
+            trace("without fitSomething: " + narrowLine + " " + narrow.textLineCreationResult);
+            narrowLine = narrow.createTextLine(null, 1, 0, true);
+            trace("with fitSomething: " + narrowLine.rawTextLength + " " + narrow.textLineCreationResult);
+            var count:int = 0;
+            narrowLine = null;
+            do {
+                narrowLine = narrow.createTextLine(narrowLine, 30, 0, true);
+                if (narrowLine != null) {
+                    count++;
+                }
+            } while (narrowLine != null);
+            trace("lines at width 30 > 1: " + (count > 1));
+
+            trace("// Changing the content");
+            block.content = new TextElement("Other", format);
+            trace("group.textBlock: " + group.textBlock);
+            trace("hello.textBlockBeginIndex: " + hello.textBlockBeginIndex);
+            trace("block.firstInvalidLine === line: " + (block.firstInvalidLine === line));
+
+            elements = group.replaceElements(0, 1, null);
+            trace("removed hello.groupElement: " + hello.groupElement);
+            trace("graphic.textBlockBeginIndex: " + graphic.textBlockBeginIndex);
+        }
+    }
+}
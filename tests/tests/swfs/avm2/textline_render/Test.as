@@ -0,0 +1,32 @@
+package {
+    import flash.display.Sprite;
+    import flash.text.engine.*;
+
+    [SWF(width="150", height="100")]
+    public class Test extends Sprite {
+        public function Test() {
+            var font:FontDescription = new FontDescription("TestFontBidi");
+            font.fontLookup = FontLookup.DEVICE;
+            var format:ElementFormat = new ElementFormat(font, 20, 0x0000FF);
+
+            // The glyphs of the font are rectangles, "a" being taller than
+            // "b", so that the position of every glyph shows in the image.
+            var block:TextBlock = new TextBlock(new TextElement("ab ab a b", format));
+            block.textJustifier = new SpaceJustifier("en", LineJustification.ALL_BUT_LAST);
+            addLine(block.createTextLine(null, 110), 10, 30);
+            addLine(block.createTextLine(block.lastLine, 110), 10, 55);
+
+            block = new TextBlock(new TextElement("a\tb", format));
+            var tabStops:Vector.<TabStop> = new Vector.<TabStop>();
+            tabStops.push(new TabStop(TabAlignment.START, 60));
+            block.tabStops = tabStops;
+            addLine(block.createTextLine(null, 110), 10, 80);
+        }
+
+        private function addLine(line:TextLine, x:Number, y:Number):void {
+            line.x = x;
+            line.y = y;
+            addChild(line);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+package {
+	import flash.display.DisplayObject;
+	import flash.display.Sprite;
+	import flash.geom.Rectangle;
+	import flash.text.engine.GroupElement;
+	import flash.text.engine.TextLine;
+
+	import flashx.textLayout.compose.TextFlowLine;
+	import flashx.textLayout.container.ContainerController;
+	import flashx.textLayout.conversion.TextConverter;
+	import flashx.textLayout.elements.TextFlow;
+	import flashx.textLayout.factory.StringTextLineFactory;
+
+	// Composes text with the Text Layout Framework, which is built on flash.text.engine.
+	public class Test extends Sprite {
+		public function Test() {
+			var markup:String = "<TextFlow xmlns='http://ns.adobe.com/textLayout/2008'>" +
+				"<p>Hello <span fontWeight='bold'>TLF</span></p>" +
+				"<p>Second paragraph</p>" +
+				"</TextFlow>";
+			var flow:TextFlow = TextConverter.importToFlow(markup, TextConverter.TEXT_LAYOUT_FORMAT);
+			trace("// TextFlow");
+			trace("numChildren: " + flow.numChildren);
+			trace("textLength: " + flow.textLength);
+
+			var container:Sprite = new Sprite();
+			addChild(container);
+			flow.flowComposer.addController(new ContainerController(container, 500, 200));
+			flow.flowComposer.updateAllControllers();
+
+			trace("// Composed");
+			trace("numLines: " + flow.flowComposer.numLines);
+			for (var i:int = 0; i < flow.flowComposer.numLines; i++) {
+				var flowLine:TextFlowLine = flow.flowComposer.getLineAt(i);
+				trace("line " + i + ": " + flowLine.absoluteStart + ", " + flowLine.textLength);
+			}
+			trace("container.numChildren: " + container.numChildren);
+
+			var textLine:TextLine = flow.flowComposer.getLineAt(0).getTextLine();
+			trace("textLine.parent === container: " + (textLine.parent === container));
+			trace("rawTextLength: " + textLine.rawTextLength);
+			trace("atomCount: " + textLine.atomCount);
+			trace("content is GroupElement: " + (textLine.textBlock.content is GroupElement));
+			trace("textWidth > 0: " + (textLine.textWidth > 0));
+
+			trace("// StringTextLineFactory");
+			var factory:StringTextLineFactory = new StringTextLineFactory();
+			factory.compositionBounds = new Rectangle(0, 0, 500, 100);
+			factory.text = "Factory text";
+			var lines:Array = [];
+			factory.createTextLines(function(line:DisplayObject):void {
+				lines.push(line);
+			});
+			trace("lines: " + lines.length);
+			trace("rawTextLength: " + TextLine(lines[0]).rawTextLength);
+
+			trace("Done");
+		}
+	}
+}
@@ -0,0 +1,30 @@
+package {
+import flash.display.Sprite;
+import flash.text.TextField;
+import flash.text.TextFormat;
+
+public class Test extends Sprite {
+    private var text:TextField;
+
+    public function Test() {
+        text = new TextField();
+        text.width = 200;
+        text.height = 100;
+        text.defaultTextFormat = new TextFormat("TestFontBidi", 10);
+        addChild(text);
+
+        // A right-to-left run in a left-to-right paragraph.
+        testText("ab \u05D0\u05D1");
+        // A left-to-right run in a right-to-left paragraph.
+        testText("\u05D0\u05D1 ab");
+    }
+
+    private function testText(value:String):void {
+        text.text = value;
+        trace("Text: " + escape(text.text));
+        for (var i:int = 0; i < text.length; ++i) {
+            trace("  text.getCharBoundaries(" + i + ") = " + text.getCharBoundaries(i));
+        }
+    }
+}
+}
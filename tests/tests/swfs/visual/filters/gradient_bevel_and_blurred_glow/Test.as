@@ -0,0 +1,43 @@
+package {
+	import flash.display.Bitmap;
+	import flash.display.BitmapData;
+	import flash.display.Sprite;
+	import flash.filters.GradientBevelFilter;
+	import flash.filters.GradientGlowFilter;
+	import flash.geom.Point;
+	import flash.geom.Rectangle;
+
+	public class Test extends Sprite {
+		public function Test() {
+			graphics.beginFill(0xFFFFFF);
+			graphics.drawRect(0, 0, 550, 400);
+			graphics.endFill();
+
+			var source:BitmapData = new BitmapData(100, 100, true, 0);
+			source.fillRect(new Rectangle(30, 30, 40, 40), 0xFFFF0000);
+
+			// A blurred outer glow fading from transparent to opaque blue.
+			var glow:GradientGlowFilter = new GradientGlowFilter(0, 0, [0x0000FF, 0x0000FF], [0, 1], [0, 255], 5, 5, 1, 1, "outer", false);
+			addBitmap(applyFilter(source, glow), 10, 10);
+
+			// An inner bevel lit from the left, with a transparent middle.
+			var bevel:GradientBevelFilter = new GradientBevelFilter(2, 0, [0xFFFFFF, 0x000000, 0x000000], [1, 0, 1], [0, 128, 255], 5, 5, 1, 1, "inner", false);
+			addBitmap(applyFilter(source, bevel), 120, 10);
+
+			trace("Done");
+		}
+
+		private function applyFilter(source:BitmapData, filter:*):BitmapData {
+			var result:BitmapData = new BitmapData(source.width, source.height, true, 0);
+			result.applyFilter(source, source.rect, new Point(0, 0), filter);
+			return result;
+		}
+
+		private function addBitmap(data:BitmapData, x:Number, y:Number):void {
+			var bitmap:Bitmap = new Bitmap(data);
+			bitmap.x = x;
+			bitmap.y = y;
+			addChild(bitmap);
+		}
+	}
+}
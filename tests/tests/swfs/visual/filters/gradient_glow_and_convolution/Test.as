@@ -0,0 +1,42 @@
+package {
+	import flash.display.Bitmap;
+	import flash.display.BitmapData;
+	import flash.display.Sprite;
+	import flash.filters.ConvolutionFilter;
+	import flash.filters.GradientGlowFilter;
+	import flash.geom.Point;
+
+	public class Test extends Sprite {
+		public function Test() {
+			graphics.beginFill(0xFFFFFF);
+			graphics.drawRect(0, 0, 550, 400);
+			graphics.endFill();
+
+			// Without any blur, a full knockout glow maps every pixel to the
+			// gradient color at its alpha.
+			var glowSource:BitmapData = new BitmapData(100, 100, true, 0);
+			glowSource.fillRect(new flash.geom.Rectangle(30, 30, 40, 40), 0xFFFF0000);
+			var glow:GradientGlowFilter = new GradientGlowFilter(0, 45, [0x0000FF, 0x00FF00], [1, 1], [0, 255], 0, 0, 1, 1, "full", true);
+			var glowResult:BitmapData = new BitmapData(100, 100, true, 0);
+			glowResult.applyFilter(glowSource, glowSource.rect, new Point(0, 0), glow);
+			addBitmap(glowResult, 10, 10);
+
+			// This kernel moves the content one pixel up and to the left.
+			var convolutionSource:BitmapData = new BitmapData(100, 100, false, 0xFFFFFF);
+			convolutionSource.fillRect(new flash.geom.Rectangle(30, 30, 40, 40), 0xFF0000);
+			var convolution:ConvolutionFilter = new ConvolutionFilter(3, 3, [0, 0, 0, 0, 0, 0, 0, 0, 1], 1, 0, true, true);
+			var convolutionResult:BitmapData = new BitmapData(100, 100, false, 0);
+			convolutionResult.applyFilter(convolutionSource, convolutionSource.rect, new Point(0, 0), convolution);
+			addBitmap(convolutionResult, 120, 10);
+
+			trace("Done");
+		}
+
+		private function addBitmap(data:BitmapData, x:Number, y:Number):void {
+			var bitmap:Bitmap = new Bitmap(data);
+			bitmap.x = x;
+			bitmap.y = y;
+			addChild(bitmap);
+		}
+	}
+}
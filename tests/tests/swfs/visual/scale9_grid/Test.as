@@ -0,0 +1,49 @@
+package {
+    import flash.display.*;
+    import flash.geom.*;
+
+    public class Test extends MovieClip {
+        public function Test() {
+            super();
+
+            graphics.beginFill(0xFFFFFF);
+            graphics.drawRect(0, 0, 550, 400);
+            graphics.endFill();
+
+            // A frame drawn on the graphics of the sprite owning the grid.
+            var sprite: Sprite = new Sprite();
+            drawFrame(sprite.graphics, 0xFF0000);
+            trace("default", sprite.scale9Grid);
+            sprite.scale9Grid = new Rectangle(10, 10, 80, 80);
+            trace("set", sprite.scale9Grid);
+            sprite.x = 20;
+            sprite.y = 20;
+            sprite.scaleX = 3;
+            sprite.scaleY = 2;
+            addChild(sprite);
+            trace("width", sprite.width, "height", sprite.height);
+
+            // A frame drawn by a child shape, which is affected by the grid of its parent.
+            var container: Sprite = new Sprite();
+            var shape: Shape = new Shape();
+            drawFrame(shape.graphics, 0x0000FF);
+            container.addChild(shape);
+            container.scale9Grid = new Rectangle(10, 10, 80, 80);
+            container.x = 20;
+            container.y = 250;
+            container.scaleX = 4;
+            addChild(container);
+
+            container.scale9Grid = null;
+            trace("reset", container.scale9Grid);
+            container.scale9Grid = new Rectangle(10, 10, 80, 80);
+        }
+
+        private function drawFrame(graphics: Graphics, color: uint): void {
+            graphics.beginFill(color);
+            graphics.drawRect(0, 0, 100, 100);
+            graphics.drawRect(10, 10, 80, 80);
+            graphics.endFill();
+        }
+    }
+}
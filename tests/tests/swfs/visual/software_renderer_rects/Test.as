@@ -0,0 +1,20 @@
+package {
+	import flash.display.Sprite;
+
+	// Draws pixel-aligned rectangles, which every renderer fills without antialiasing.
+	public class Test extends Sprite {
+		public function Test() {
+			graphics.beginFill(0xFF0000);
+			graphics.drawRect(10, 10, 40, 30);
+			graphics.endFill();
+
+			graphics.beginFill(0x0000FF, 0.5);
+			graphics.drawRect(30, 20, 50, 50);
+			graphics.endFill();
+
+			graphics.beginFill(0x00FF00);
+			graphics.drawRect(60, 80, 30, 10);
+			graphics.endFill();
+		}
+	}
+}
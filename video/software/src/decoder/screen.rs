@@ -24,6 +24,12 @@ pub enum ScreenError {
 
     #[error("Not all blocks were updated by a supposed keyframe")]
     KeyframeInvalid,
+
+    #[error("Invalid image block: {0}")]
+    InvalidBlock(&'static str),
+
+    #[error("Unsupported color depth: {0}")]
+    UnsupportedColorDepth(u8),
 }
 
 impl From<ScreenError> for Error {
@@ -44,17 +50,17 @@ pub struct ScreenVideoDecoder {
     last_frame: Vec<u8>,
 }
 
-struct ByteReader<'a> {
+pub(super) struct ByteReader<'a> {
     data: &'a [u8],
     pos: usize,
 }
 
 impl<'a> ByteReader<'a> {
-    fn new(data: &'a [u8]) -> Self {
+    pub(super) fn new(data: &'a [u8]) -> Self {
         Self { data, pos: 0 }
     }
 
-    fn read_byte(&mut self) -> Result<u8, ScreenError> {
+    pub(super) fn read_byte(&mut self) -> Result<u8, ScreenError> {
         if self.pos >= self.data.len() {
             return Err(ScreenError::UnexpectedEOF);
         }
@@ -63,13 +69,13 @@ impl<'a> ByteReader<'a> {
         Ok(byte)
     }
 
-    fn read_u16be(&mut self) -> Result<u16, ScreenError> {
+    pub(super) fn read_u16be(&mut self) -> Result<u16, ScreenError> {
         let byte1 = self.read_byte()?;
         let byte2 = self.read_byte()?;
         Ok(((byte1 as u16) << 8) | (byte2 as u16))
     }
 
-    fn read_buf_ref(&mut self, length: usize) -> Result<&[u8], ScreenError> {
+    pub(super) fn read_buf_ref(&mut self, length: usize) -> Result<&'a [u8], ScreenError> {
         if self.pos + length > self.data.len() {
             return Err(ScreenError::UnexpectedEOF);
         }
@@ -189,16 +195,8 @@ impl VideoDecoder for ScreenVideoDecoder {
         // `last_frame` was updated in-place by `decode_v1`.
         let data = &self.last_frame[..frame_size];
 
-        // convert from BGR to RGB and flip Y
         let rgb = &mut self.scratch[..frame_size];
-        for y in 0..h {
-            let data_row = &data[y * w * 3..(y + 1) * w * 3];
-            let rgb_row = &mut rgb[(h - y - 1) * w * 3..(h - y) * w * 3];
-
-            for (bgr, rgb) in data_row.chunks(3).zip(rgb_row.chunks_mut(3)) {
-                rgb.copy_from_slice(&[bgr[2], bgr[1], bgr[0]]);
-            }
-        }
+        bgr_to_rgb_flipped(data, rgb, w, h);
 
         callback(DecodedFrame::new(
             w as u32,
@@ -210,6 +208,18 @@ impl VideoDecoder for ScreenVideoDecoder {
     }
 }
 
+/// Converts a bottom-up BGR image, as stored by Screen Video, into a top-down RGB image.
+pub(super) fn bgr_to_rgb_flipped(bgr: &[u8], rgb: &mut [u8], w: usize, h: usize) {
+    for y in 0..h {
+        let bgr_row = &bgr[y * w * 3..(y + 1) * w * 3];
+        let rgb_row = &mut rgb[(h - y - 1) * w * 3..(h - y) * w * 3];
+
+        for (bgr, rgb) in bgr_row.chunks(3).zip(rgb_row.chunks_mut(3)) {
+            rgb.copy_from_slice(&[bgr[2], bgr[1], bgr[0]]);
+        }
+    }
+}
+
 impl Default for ScreenVideoDecoder {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,219 @@
+#!/usr/bin/env python3
+"""Encodes the Screen Video V2 fixtures used by the decoder tests.
+
+This is not a reference encoder: it writes the block layout, zlib priming
+and 15/7 color encoding as described by the FLV specification, the same way
+FFmpeg's flashsv2enc.c lays them out, and additionally writes custom palettes
+and IFrame images, which FFmpeg never emits. Streams produced by Flash or by
+`ffmpeg -c:v flashsv2` should be preferred over these fixtures where they are
+available, as they would catch misreadings of the specification shared by
+this script and the decoder.
+
+The images are computed from simple formulas, which the tests repeat to
+check every decoded pixel. Run from this directory to regenerate.
+"""
+
+import struct
+import zlib
+
+DEFAULT_PALETTE = [
+    0x000000, 0x333333, 0x666666, 0x999999, 0xCCCCCC, 0xFFFFFF,
+    0x330000, 0x660000, 0x990000, 0xCC0000, 0xFF0000, 0x003300,
+    0x006600, 0x009900, 0x00CC00, 0x00FF00, 0x000033, 0x000066,
+    0x000099, 0x0000CC, 0x0000FF, 0x333300, 0x666600, 0x999900,
+    0xCCCC00, 0xFFFF00, 0x003333, 0x006666, 0x009999, 0x00CCCC,
+    0x00FFFF, 0x330033, 0x660066, 0x990099, 0xCC00CC, 0xFF00FF,
+    0xFFFF33, 0xFFFF66, 0xFFFF99, 0xFFFFCC, 0xFF33FF, 0xFF66FF,
+    0xFF99FF, 0xFFCCFF, 0x33FFFF, 0x66FFFF, 0x99FFFF, 0xCCFFFF,
+    0xCCCC33, 0xCCCC66, 0xCCCC99, 0xCCCCFF, 0xCC33CC, 0xCC66CC,
+    0xCC99CC, 0xCCFFCC, 0x33CCCC, 0x66CCCC, 0x99CCCC, 0xFFCCCC,
+    0x999933, 0x999966, 0x9999CC, 0x9999FF, 0x993399, 0x996699,
+    0x99CC99, 0x99FF99, 0x339999, 0x669999, 0xCC9999, 0xFF9999,
+    0x666633, 0x666699, 0x6666CC, 0x6666FF, 0x663366, 0x669966,
+    0x66CC66, 0x66FF66, 0x336666, 0x996666, 0xCC6666, 0xFF6666,
+    0x333366, 0x333399, 0x3333CC, 0x3333FF, 0x336633, 0x339933,
+    0x33CC33, 0x33FF33, 0x663333, 0x993333, 0xCC3333, 0xFF3333,
+    0x003366, 0x336600, 0x660033, 0x006633, 0x330066, 0x663300,
+    0x336699, 0x669933, 0x993366, 0x339966, 0x663399, 0x996633,
+    0x6699CC, 0x99CC66, 0xCC6699, 0x66CC99, 0x9966CC, 0xCC9966,
+    0x99CCFF, 0xCCFF99, 0xFF99CC, 0x99FFCC, 0xCC99FF, 0xFFCC99,
+    0x111111, 0x222222, 0x444444, 0x555555, 0xAAAAAA, 0xBBBBBB,
+    0xDDDDDD, 0xEEEEEE,
+]
+
+COLORSPACE_BGR = 0x00
+COLORSPACE_15_7 = 0x10
+HAS_DIFF_BLOCKS = 0x04
+ZLIB_PRIME_COMPRESS_PREVIOUS = 0x01
+
+
+class Encoder:
+    def __init__(self, width, height, block_size, palette_mode=False, palette=None):
+        self.width = width
+        self.height = height
+        self.block_size = block_size
+        self.palette_mode = palette_mode
+        self.palette = palette or DEFAULT_PALETTE
+        self.cols = -(-width // block_size)
+        self.rows = -(-height // block_size)
+        self.previous = None
+        self.key_blocks = None
+
+    def block_rect(self, index):
+        x = (index % self.cols) * self.block_size
+        y = (index // self.cols) * self.block_size
+        return x, y, min(self.block_size, self.width - x), min(self.block_size, self.height - y)
+
+    def encode_pixel(self, rgb):
+        if not self.palette_mode:
+            r, g, b = rgb
+            return bytes([b, g, r])
+        value = (rgb[0] << 16) | (rgb[1] << 8) | rgb[2]
+        if value in self.palette:
+            return bytes([self.palette.index(value)])
+        r, g, b = (c >> 3 for c in rgb)
+        return struct.pack(">H", 0x8000 | (r << 10) | (g << 5) | b)
+
+    def block_rows(self, image, index):
+        """The encoded rows of a block, bottom-up like the rest of the format."""
+        x, y, w, h = self.block_rect(index)
+        rows = []
+        for row in range(y, y + h):
+            line = image[self.height - 1 - row]
+            rows.append(b"".join(self.encode_pixel(line[col]) for col in range(x, x + w)))
+        return rows
+
+    def header(self, flags=0):
+        code = (self.block_size >> 4) - 1
+        return struct.pack(">HHB", (code << 12) | self.width, (code << 12) | self.height, flags)
+
+    @staticmethod
+    def block(flags, headers, data):
+        return struct.pack(">HB", 1 + len(headers) + len(data), flags) + bytes(headers) + data
+
+    def colorspace(self):
+        return COLORSPACE_15_7 if self.palette_mode else COLORSPACE_BGR
+
+    def keyframe(self, image, extra_flags=0, palette_block=b""):
+        out = bytearray([0x16]) + self.header(extra_flags) + palette_block
+        blocks = []
+        for index in range(self.rows * self.cols):
+            data = b"".join(self.block_rows(image, index))
+            blocks.append(data)
+            out += self.block(self.colorspace(), [], zlib.compress(data, 9))
+        self.previous = image
+        self.key_blocks = blocks
+        return bytes(out)
+
+    def interframe(self, image, iframe=False):
+        out = bytearray([0x26]) + self.header(0b10 if iframe else 0)
+        for index in range(self.rows * self.cols):
+            rows = self.block_rows(image, index)
+            old = self.block_rows(self.previous, index)
+            changed = [i for i, (a, b) in enumerate(zip(rows, old)) if a != b]
+            if not changed:
+                out += b"\0\0"
+                continue
+            start, end = changed[0], changed[-1] + 1
+            data = b"".join(rows[start:end])
+            flags = self.colorspace()
+            headers = []
+            if (start, end) != (0, len(rows)):
+                flags |= HAS_DIFF_BLOCKS
+                headers = [start, end - start]
+            plain = zlib.compress(data, 9)
+            primed = prime_compress(self.key_blocks[index], data)
+            if len(primed) < len(plain):
+                flags |= ZLIB_PRIME_COMPRESS_PREVIOUS
+                plain = primed
+            out += self.block(flags, headers, plain)
+        if iframe:
+            # The IFrame image replaces the blocks that later frames are primed with.
+            blocks = []
+            for index in range(self.rows * self.cols):
+                data = b"".join(self.block_rows(image, index))
+                blocks.append(data)
+                out += self.block(self.colorspace(), [], zlib.compress(data, 9))
+            self.key_blocks = blocks
+        self.previous = image
+        return bytes(out)
+
+
+def prime_compress(prime, data):
+    """Compresses `data` with the zlib stream primed by `prime`, like encode_zlibprime."""
+    compress = zlib.compressobj(9)
+    compress.compress(prime)
+    compress.flush(zlib.Z_SYNC_FLUSH)
+    return compress.compress(data) + compress.flush()
+
+
+def palette_block(palette):
+    data = b"".join(bytes([c & 0xFF, (c >> 8) & 0xFF, c >> 16]) for c in palette)
+    return Encoder.block(COLORSPACE_BGR, [], zlib.compress(data, 9))
+
+
+def flv(path, frames, width, height):
+    out = bytearray(b"FLV\x01\x01\0\0\0\x09\0\0\0\0")
+    for i, frame in enumerate(frames):
+        timestamp = i * 100
+        tag = struct.pack(">B", 9) + len(frame).to_bytes(3, "big")
+        tag += (timestamp & 0xFFFFFF).to_bytes(3, "big") + bytes([timestamp >> 24]) + b"\0\0\0"
+        out += tag + frame + struct.pack(">I", 11 + len(frame))
+    with open(path, "wb") as f:
+        f.write(out)
+
+
+def image(width, height, pixel):
+    return [[pixel(x, y) for x in range(width)] for y in range(height)]
+
+
+# Keep in sync with the formulas in screen_v2.rs.
+def gradient(x, y):
+    return ((x * 5) & 0xFF, (y * 6) & 0xFF, ((x + y) * 3) & 0xFF)
+
+
+def with_box(x, y):
+    return (0xFF, 0, 0) if 10 <= x < 20 and 5 <= y < 9 else gradient(x, y)
+
+
+def swatches(x, y):
+    # Palette colors in the top half, and off-palette colors in the bottom half.
+    if y < 12:
+        c = DEFAULT_PALETTE[(x // 4 + y) % 128]
+        return (c >> 16, (c >> 8) & 0xFF, c & 0xFF)
+    return ((x * 7 + 1) & 0xFF, (y * 9 + 2) & 0xFF, 0x42)
+
+
+CUSTOM_PALETTE = [((i * 2) << 16) | ((255 - i) << 8) | (i * 37 & 0xFF) for i in range(128)]
+
+
+def custom(x, y):
+    c = CUSTOM_PALETTE[(x + y * 8) % 128]
+    return (c >> 16, (c >> 8) & 0xFF, c & 0xFF)
+
+
+encoder = Encoder(48, 40, 32)
+flv("keyframe_diff.flv", [
+    encoder.keyframe(image(48, 40, gradient)),
+    encoder.interframe(image(48, 40, with_box)),
+    encoder.interframe(image(48, 40, with_box)),
+], 48, 40)
+
+encoder = Encoder(40, 24, 16, palette_mode=True)
+flv("palette.flv", [
+    encoder.keyframe(image(40, 24, swatches)),
+    encoder.interframe(image(40, 24, lambda x, y: (0xFF, 0, 0) if x < 3 else swatches(x, y))),
+], 40, 24)
+
+encoder = Encoder(16, 16, 16, palette_mode=True, palette=CUSTOM_PALETTE)
+flv("custom_palette.flv", [
+    encoder.keyframe(image(16, 16, custom), extra_flags=0b1,
+                     palette_block=palette_block(CUSTOM_PALETTE)),
+], 16, 16)
+
+encoder = Encoder(32, 32, 16)
+flv("iframe.flv", [
+    encoder.keyframe(image(32, 32, gradient)),
+    encoder.interframe(image(32, 32, with_box), iframe=True),
+    encoder.interframe(image(32, 32, lambda x, y: (0, 0, 0xFF) if (x, y) == (12, 6) else with_box(x, y))),
+], 32, 32)